@@ -1,207 +1,6 @@
-// Build script to embed resources into the executable
-
-use aes_gcm::{
-    aead::{Aead, KeyInit},
-    Aes256Gcm, Nonce,
-};
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use serde::Serialize;
-use std::fs;
-use std::path::Path;
-
-/// Secrets structure to encrypt
-#[derive(Serialize)]
-struct SecretsData {
-    discogs_consumer_key: String,
-    discogs_consumer_secret: String,
-}
-
-/// Encrypt secrets and write to file for embedding
-fn encrypt_secrets() {
-    let out_dir = std::env::var("OUT_DIR").unwrap();
-    let secrets_path = Path::new(&out_dir).join("secrets.enc");
-
-    // Check if we have all required environment variables
-    let encryption_key = std::env::var("ENCRYPTION_KEY").ok();
-    let consumer_key = std::env::var("DISCOGS_CONSUMER_KEY").ok();
-    let consumer_secret = std::env::var("DISCOGS_CONSUMER_SECRET").ok();
-
-    match (encryption_key, consumer_key, consumer_secret) {
-        (Some(key), Some(ck), Some(cs)) => {
-            println!("cargo:warning=Encrypting Discogs secrets for release build");
-
-            // Decode the base64 encryption key
-            let key_bytes = BASE64.decode(&key).expect("Invalid ENCRYPTION_KEY base64");
-            assert_eq!(key_bytes.len(), 32, "ENCRYPTION_KEY must be 32 bytes (256 bits)");
-
-            // Create the secrets JSON
-            let secrets = SecretsData {
-                discogs_consumer_key: ck,
-                discogs_consumer_secret: cs,
-            };
-            let plaintext = serde_json::to_string(&secrets).unwrap();
-
-            // Generate a random nonce (12 bytes for AES-GCM)
-            // For reproducible builds, we derive it from the key
-            let nonce_bytes: [u8; 12] = {
-                use std::hash::{Hash, Hasher};
-                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                key_bytes.hash(&mut hasher);
-                let hash = hasher.finish();
-                let mut nonce = [0u8; 12];
-                nonce[..8].copy_from_slice(&hash.to_le_bytes());
-                nonce
-            };
-            let nonce = Nonce::from_slice(&nonce_bytes);
-
-            // Encrypt
-            let cipher = Aes256Gcm::new_from_slice(&key_bytes).unwrap();
-            let ciphertext = cipher
-                .encrypt(nonce, plaintext.as_bytes())
-                .expect("Encryption failed");
-
-            // Write: nonce (12 bytes) + ciphertext
-            let mut output = Vec::new();
-            output.extend_from_slice(&nonce_bytes);
-            output.extend_from_slice(&ciphertext);
-
-            // Write as base64 for easier embedding
-            let encoded = BASE64.encode(&output);
-            fs::write(&secrets_path, &encoded).expect("Failed to write secrets.enc");
-
-            println!("cargo:warning=Secrets encrypted successfully ({} bytes)", encoded.len());
-        }
-        _ => {
-            // No secrets available - write empty marker
-            // This allows local development without secrets
-            fs::write(&secrets_path, "").expect("Failed to write empty secrets.enc");
-            println!("cargo:warning=No secrets environment variables found, using empty secrets");
-        }
-    }
-
-    // Tell cargo to rerun if these env vars change
-    println!("cargo:rerun-if-env-changed=ENCRYPTION_KEY");
-    println!("cargo:rerun-if-env-changed=DISCOGS_CONSUMER_KEY");
-    println!("cargo:rerun-if-env-changed=DISCOGS_CONSUMER_SECRET");
-}
-
-/// Optionally download a fresh `ode-lookup.sqlite.zst` (and its sha256
-/// sidecars) from the ODE-lookup-db release page so it can be embedded as a
-/// first-run seed via `include_bytes!`.
-///
-/// Behavior is driven by three env vars (the legacy `EMBED_REDUMP_DB*` names
-/// are still honored as aliases so CI doesn't have to flip in lockstep):
-/// - `EMBED_LOOKUP_DB=1` — opt in. Default (unset) writes empty stub files and
-///   skips the network entirely. This keeps local builds offline & fast.
-/// - `EMBED_LOOKUP_DB_REQUIRED=1` — make a failed fetch a hard build error. CI
-///   sets this only on scheduled runs.
-/// - `EMBED_LOOKUP_DB_NONCE=...` — opaque value (e.g. github.run_id) that forces
-///   this build script to rerun even when the cargo cache is warm.
-fn embed_lookup_db() {
-    let out_dir = std::env::var("OUT_DIR").unwrap();
-    let zst_path = Path::new(&out_dir).join("ode-lookup.sqlite.zst");
-    let zst_sha_path = Path::new(&out_dir).join("ode-lookup.sqlite.zst.sha256");
-    let plain_sha_path = Path::new(&out_dir).join("ode-lookup.sqlite.sha256");
-
-    for var in [
-        "EMBED_LOOKUP_DB",
-        "EMBED_LOOKUP_DB_REQUIRED",
-        "EMBED_LOOKUP_DB_NONCE",
-        "EMBED_REDUMP_DB",
-        "EMBED_REDUMP_DB_REQUIRED",
-        "EMBED_REDUMP_DB_NONCE",
-    ] {
-        println!("cargo:rerun-if-env-changed={var}");
-    }
-
-    let enabled = env_flag("EMBED_LOOKUP_DB") || env_flag("EMBED_REDUMP_DB");
-    if !enabled {
-        write_stub(&zst_path, &zst_sha_path, &plain_sha_path);
-        return;
-    }
-
-    let required = env_flag("EMBED_LOOKUP_DB_REQUIRED") || env_flag("EMBED_REDUMP_DB_REQUIRED");
-    let base = "https://github.com/danifunker/ODE-lookup-db/releases/download/latest";
-
-    let result = (|| -> Result<(), String> {
-        download_file(&format!("{base}/ode-lookup.sqlite.zst"), &zst_path)?;
-        download_file(
-            &format!("{base}/ode-lookup.sqlite.zst.sha256"),
-            &zst_sha_path,
-        )?;
-        download_file(
-            &format!("{base}/ode-lookup.sqlite.sha256"),
-            &plain_sha_path,
-        )?;
-        Ok(())
-    })();
-
-    match result {
-        Ok(()) => {
-            let size = fs::metadata(&zst_path).map(|m| m.len()).unwrap_or(0);
-            println!(
-                "cargo:warning=Embedded ODE-lookup DB seed ({} bytes compressed)",
-                size
-            );
-        }
-        Err(e) if required => {
-            // Scheduled CI run: refusing to ship a binary with a stale embedded
-            // DB. Bubble the error up so the workflow alerts.
-            panic!("EMBED_LOOKUP_DB_REQUIRED=1 but seed fetch failed: {e}");
-        }
-        Err(e) => {
-            println!(
-                "cargo:warning=ODE-lookup DB seed fetch failed (proceeding without seed): {e}"
-            );
-            write_stub(&zst_path, &zst_sha_path, &plain_sha_path);
-        }
-    }
-}
-
-fn env_flag(name: &str) -> bool {
-    std::env::var(name).ok().as_deref() == Some("1")
-}
-
-fn write_stub(zst: &Path, zst_sha: &Path, plain_sha: &Path) {
-    // Empty files are fine — `include_bytes!` works on them and the seed
-    // module treats zero-length bytes as "no seed available".
-    fs::write(zst, &[][..]).expect("write stub zst");
-    fs::write(zst_sha, "").expect("write stub zst.sha256");
-    fs::write(plain_sha, "").expect("write stub plain sha256");
-}
-
-fn download_file(url: &str, dest: &Path) -> Result<(), String> {
-    // Shell out to curl: it's preinstalled on every GitHub runner OS image
-    // (including windows-latest), avoids adding a build-dep crate, and the
-    // retry behavior is exactly what we want here.
-    let status = std::process::Command::new("curl")
-        .args([
-            "-fL",
-            "--retry",
-            "3",
-            "--retry-delay",
-            "5",
-            "--connect-timeout",
-            "30",
-            "--max-time",
-            "300",
-            "-o",
-        ])
-        .arg(dest)
-        .arg(url)
-        .status()
-        .map_err(|e| format!("spawn curl for {url}: {e}"))?;
-    if !status.success() {
-        return Err(format!("curl exit {status} for {url}"));
-    }
-    Ok(())
-}
+// Build script to embed the version and Windows resources into the executable
 
 fn main() {
-    // Encrypt secrets for embedding
-    encrypt_secrets();
-    // Optionally embed an ODE-lookup DB seed (CI-only by default)
-    embed_lookup_db();
     // Set version at compile time
     // Reads from RELEASE_VERSION env var (set by CI) or falls back to Cargo.toml version
     let version = std::env::var("RELEASE_VERSION")
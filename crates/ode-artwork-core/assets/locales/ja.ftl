@@ -0,0 +1,562 @@
+# 日本語
+
+## Main window
+
+audio-tracks-button = 🎵 オーディオトラック ({ $count })
+open-the-cd-da-player-tip = CD-DA プレーヤーを開く
+extract-audio = オーディオを抽出...
+save-every-audio-track-to-tip = すべてのオーディオトラックを CD-TEXT または MusicBrainz の名前でフォルダーに保存
+now-playing-preparing = { $icon } トラック { $track } — 準備中…
+now-playing = { $icon } トラック { $track } — { $secs } 秒
+processing-queue = 処理キュー
+queue-progress = { $total } 件中 { $done } 件完了
+done = 完了
+failed = 失敗
+retry = 再試行
+retry-failed = 失敗したものを再試行
+clear-finished = 完了したものを削除
+cancel = キャンセル
+audio-tracks = 🎵 オーディオトラック
+close = 閉じる
+track-preparing = トラック { $track }: 準備中…
+track-position = { $icon } トラック { $track }   { $pos } 秒 / { $total } 秒
+playback-failed = 再生に失敗しました: { $error }
+resume = 再開
+pause = 一時停止
+stop = 停止
+play-track = トラック { $track } を再生
+track-elapsed = トラック { $track } — { $pos } 秒
+track-duration = トラック { $track } ({ $duration })
+queue-json-or-csv = キュー (JSON または CSV)
+queue-json = キュー JSON
+fuzzy-scan-csv = fuzzy_scan CSV
+all-files = すべてのファイル
+broken-cue-file = 壊れた CUE ファイル
+broken-cue-missing-files = 参照されているデータファイル { $total } 件のうち { $missing } 件が見つかりません:
+delete-the-cue-file-the-cue = CUE ファイルを削除しますか? (データがなければ使えません。)
+broken-cue-auto-skip = { $secs } 秒後に自動的にスキップします (削除せず残します) …
+delete-cue = CUE を削除
+keep-skip = 残す (スキップ)
+open-bulk-job = 一括処理を開く
+bulk-file = ファイル: { $path }
+bulk-items = 項目数: { $count }
+bulk-already-done = 処理済み (スキップ): { $count }
+bulk-reprocess-existing = アートワークがあるディスクも再処理する
+bulk-include-fuzzy = 部分一致のディスクも含める (あいまい一致)
+minimum-confidence = 最低信頼度:
+bulk-fuzzy-score-hint = 0.60 = あいまい一致の下限 (誤りが多い)。0.85 なら正しい一致を落とさずに絞り込めます。0.90 以上は完全一致に近い信頼度です。
+start = 開始
+bulk-complete = 一括処理 — 完了
+bulk-mode = 一括処理モード
+bulk-progress = { $done } / { $total } 完了
+back = 戻る
+bulk-back-tip = B — 前の項目に戻る
+skip = スキップ
+bulk-skip-tip = S — スキップとして記録して次へ
+exit-bulk = 一括処理を終了
+bulk-exit-tip = Esc
+up-next = 次:
+last-item-in-queue = キューの最後の項目です。
+bulk-all-processed = すべての項目を処理しました — 「一括処理を終了」で 1 枚ずつのモードに戻ります。
+disc-images = ディスクイメージ
+iso-toast-files = ISO/Toast ファイル
+chd-files = CHD ファイル
+bin-cue-files = BIN/CUE ファイル
+cover-pack-zip = カバーパック (zip)
+folder-scan = フォルダーのスキャン
+scan-identified = { $total } 件中 { $done } 件を識別
+scan-looking = ディスクイメージを探しています...
+scan-disc-images = ディスクイメージ { $count } 件
+unreadable = 読み込めません
+found = あり
+missing = なし
+open = 開く
+select-missing-artwork = アートワークのないものを選択
+scan-process-selected = 選択したものを処理 ({ $count })
+process-selected-tip = 選択した各ディスクを処理キューで識別・検索し、アートワークを保存します
+fix-existing-artwork = 既存のアートワークを修正
+fix-covers-failing = { $root } 以下のカバー { $count } 件が USBODE の形式を満たしていません:
+fix-backup-note = ファイルはその場で再エンコードされます。元のファイルは先に { $dir }/ にコピーされます。
+fix-files = { $count } 件のファイルを修正
+preview = プレビュー
+url-is-web-page = ⚠ サーバーが返すのは画像ではなくウェブページです。保存されません
+url-http-status = サーバーの応答: HTTP { $status }
+url-check-failed = この画像についてサーバーに問い合わせできませんでした
+compare-candidates = 候補を比較
+pinned = ピン留め
+source-size = 元画像 { $width }x{ $height }
+use-pinned = ピン留めしたものを使う
+selected = 選択中
+select-another-result-to-compare = 比較する別の結果を選択してください
+loading = 読み込み中...
+image-load-failed = この画像を読み込めませんでした
+use-selected = 選択したものを使う
+log-button-count = ログ ({ $count })
+log = ログ
+settings = 設定
+queue-button = キュー ({ $done }/{ $total })
+clear = クリア
+copy = コピー
+save-log = ログを保存...
+save-log-tip = 表示中のメッセージをタイムスタンプ (UTC) 付きでテキストファイルに保存します
+log-info = 情報
+log-warnings = 警告
+log-errors = エラー
+log-filter-hint = 絞り込み...
+log-showing = { $total } 件中 { $shown } 件を表示
+log-settings = ログ設定
+log-level = ログレベル:
+log-settings-note = すぐに反映され、config.json に保存されます。
+log-file-level = ログファイルのレベル:
+scan-workers = フォルダースキャンのスレッド数:
+scan-workers-tip = 「フォルダーをスキャン...」が同時に識別するディスクの数。0 で CPU コアごとに 1 つ。
+chd-cache-mb = CHD ハンクキャッシュ:
+chd-cache-mb-tip = 同じディスクの閲覧・識別・ハッシュ計算で再利用するため、展開済みの CHD データをメモリーに保持します。0 で無効。
+open-log-file = ログファイルを開く
+open-log-file-tip = バグ報告に添付するためにログファイル (1 メッセージにつき JSON 1 行) を開きます
+crash-title = ODE Artwork Downloader がクラッシュしました
+crash-description =
+    問題が発生したため、アプリを終了する必要があります。クラッシュレポートを次の場所に保存しました:
+    { $path }
+
+    発生した内容と直近のログメッセージが含まれています。問題を報告する際に添付してください。今すぐ開きますか?
+browse-disc-contents = ディスクの内容を参照
+no-disc-loaded = ディスクが読み込まれていません
+artwork-search = アートワーク検索
+content-type = 種類:
+auto-pick = 最適な結果を自動選択
+auto-pick-tip = 検索が終わると、評価が最も高い結果 (正方形、480 px 以上、信頼できるソース、タイトル一致) をプレビューし、確認なしで保存します
+content-type-any = すべて
+content-type-games = ゲーム
+content-type-apps = アプリ・ユーティリティ
+content-type-audio = 音楽 CD
+search-label = 検索:
+refine-search-query = 検索語を絞り込む...
+reset = リセット
+search-musicbrainz = MusicBrainz を検索
+search-button = 検索
+open-in-browser = ブラウザーで開く
+searching = 検索中...
+manual-url = URL を指定:
+paste-image-url-here = 画像の URL をここに貼り付け...
+search-results = 検索結果
+no-results-yet = まだ結果はありません。
+save-placeholder = 仮のアートワークを保存
+save-placeholder-tip = 代わりにタイトル入りの生成アートワークを保存します
+results-count = 画像 { $count } 件 - クリックでプレビュー
+results-count-hidden = 画像 { $count } 件中 { $shown } 件を表示 - クリックでプレビュー
+source-filter-tip = チェックを外すとこのソースの結果を非表示にします
+source-local = ローカル
+copy-url-to-clipboard = URL をクリップボードにコピー
+open-in-browser-tip = ブラウザーで開く
+rotate-90-left = 左に 90° 回転
+rotate-90-right = 右に 90° 回転
+flip-horizontally = 左右反転
+adjust-colours = 色を調整
+brightness = 明るさ
+contrast = コントラスト
+saturation = 彩度
+show-output = 出力を表示
+show-output-tip = 保存されるファイルを実寸で表示: 正方形化、リサイズ、シャープ、バッジ、選んだ品質でのエンコードを反映
+zoom-2x = 2x
+before = 変更前
+after = 変更後
+size-on-disk = ディスク上 { $size }
+style = スタイル:
+save-as = 保存の種類:
+save-as-tip = 裏表紙とディスクのスキャンは game.back.jpg / game.disc.jpg として保存されます
+downloading = ダウンロード中...
+download-and-save = ダウンロードして保存
+current-artwork = 現在
+replace-current-artwork = 現在のアートワークをこの画像で置き換えますか?
+replace = 置き換える
+keep-current = 現在のものを残す
+pin-for-compare = 比較用にピン留め
+pin-for-compare-tip = この画像を取っておき、次に選択した画像と並べて表示します
+converting = 変換中...
+download-connecting = 接続中...
+download-progress = { $done } / { $total }
+download-progress-unknown = { $done } ダウンロード済み
+export-processing = 画像を処理中...
+export-writing = ファイルを保存中 ({ $done } / { $total })...
+save-to = 保存先:
+output-path-tip = 保存先です。編集すると今回だけ別の場所に保存し、次回はファイル名テンプレートに戻ります
+output-path-reset-tip = ファイル名テンプレートのパスに戻す
+download-manually-tip = ヒント: 画像を手動でダウンロードして、ここにドロップしてください
+will-save-to = 保存先: { $path }
+select-an-image-to-preview = プレビューする画像を選択してください
+tab-empty = ディスクなし
+tab-new = 新しいタブ
+tab-close = タブを閉じる
+file-selection = ファイルの選択
+browse = 参照...
+recent = 最近使った項目
+recent-files = ディスクイメージ
+recent-folders = スキャンしたフォルダー
+recent-empty = まだ何も開いていません
+recent-clear = 履歴を消去
+recent-missing = { $path } は存在しません
+recent-file-results = { $name }（保存済みの結果 { $count } 件）
+bulk-job = 一括処理...
+scan-folder = フォルダーをスキャン...
+scan-folder-tip = フォルダー内のすべてのディスクイメージを識別し、アートワークがないものを一覧表示します
+watch-folders = フォルダーを監視
+stop-watching = 監視を停止
+watch-folders-tip = これらのフォルダーに追加されたディスクイメージは、処理キューで自動的にアートワークが付けられます。監視中はウィンドウがトレイに最小化されます。
+usbode-device = USBODE デバイス
+usbode-device-tip = 「USBODE に送信」で保存済みアートワークを送るネットワーク上の USBODE。SD カードへのコピーが不要になります
+usbode-address = アドレス
+profiles = プロファイル
+profiles-tip = 検索とエクスポートの設定に名前を付けて保存し、作業内容に応じて切り替えたり共有したりできます。このコンピューターのフォルダー、USBODE のアドレス、外観、言語はプロファイルに含まれません。
+profile-name = 名前:
+profile-name-hint = PC ゲーム
+saved-profiles = 保存済みプロファイル
+profile-load = 読み込む
+profile-save = プロファイルとして保存
+profile-delete = 削除
+profile-import = インポート...
+profile-export = エクスポート...
+profile-loaded = 「{ $name }」を読み込みました。使うには保存をクリックしてください。
+profile-saved = プロファイル「{ $name }」を保存しました
+profile-deleted = プロファイル「{ $name }」を削除しました
+profile-imported = プロファイル「{ $name }」をインポートしました
+profile-exported = { $path } にエクスポートしました
+profile-files = プロファイルと設定 (JSON、Python スクリプトの config.py)
+tray-show = ウィンドウを表示
+tray-quit = 終了
+watch-notify-title = 監視フォルダー
+watch-notify-body = 保存 { $saved } 件、失敗 { $failed } 件、確認待ち { $waiting } 件
+fix-artwork = アートワークを修正...
+fix-artwork-tip = USBODE で表示できない既存のカバーをフォルダーから探して再エンコードします
+undo-restore-tip = { $path } が置き換えたカバーを元に戻す
+undo-delete-tip = { $path } を削除
+undo-nothing-tip = 元に戻す操作はありません
+undo-last-export = 最後の書き出しを元に戻す
+undo = 元に戻す
+copy-result = 結果をコピー
+copy-result-tip = 最後に保存したカバーを画像としてクリップボードにコピー
+open-output-folder = フォルダーを開く
+open-output-folder-tip = 最後にアートワークを保存したフォルダーを開きます
+reveal-in-file-manager = 表示
+reveal-in-file-manager-tip = 最後に保存したアートワークをファイルマネージャーで表示します
+send-to-usbode = USBODE に送信
+send-to-usbode-tip = このディスクの保存済みアートワークを、設定の USBODE 上のディスクと同じ場所にアップロードします
+no-file-selected = ファイルが選択されていません (ドラッグ＆ドロップ可)
+search-settings = 検索設定
+browser-identity = ブラウザー識別情報:
+configured = 設定済み
+not-set = 未設定
+waiting-for-browser = ブラウザーを待っています...
+configure-browser-identity = ブラウザー識別情報を設定
+cover-packs = カバーパック:
+pack-images = 画像 { $count } 件
+none = なし
+import-zip = zip を読み込む...
+import-folder = フォルダーを読み込む...
+output-size = 出力サイズ:
+also-save = 追加で保存:
+also-save-hint = 例: 480
+format = 形式:
+max-size = 最大サイズ:
+max-size-tip = 0 = 制限なし。ファイルが収まるまで JPEG 品質を下げます。
+jpeg-quality = JPEG 品質:
+jpeg-quality-tip = 高いほど細部が残りますが、ファイルが大きくなります。USBODE の既定値は 90 です。
+file-name = ファイル名:
+file-name-tip = 項目: {"{"}stem{"}"} {"{"}title{"}"} {"{"}serial{"}"} {"{"}region{"}"} {"{"}year{"}"} {"{"}disc{"}"}。/ を使うとディスクイメージの隣にサブフォルダーを作ります。
+artwork-folder = アートワークのフォルダー:
+artwork-folder-tip = 各ディスクイメージの隣ではなく、1 つのフォルダーにすべてのカバーを保存します
+remote-target = コピー先:
+remote-target-tip = 保存したアートワークをネットワーク上の場所にもコピーします: smb://サーバー/共有/フォルダー、ftp://ユーザー:パスワード@サーバー/フォルダー、sftp://ユーザー@サーバー/フォルダー (SFTP は SSH キーを使用)。macOS と Linux では先に SMB 共有をマウントしてください
+retroarch-thumbnails = RetroArch サムネイル:
+retroarch-thumbnails-tip = RetroArch の thumbnails フォルダー。保存したアートワークを <プレイリスト>/Named_Boxarts/<ディスクのファイル名>.png として PNG でも書き込みます
+retroarch-playlist = RetroArch プレイリスト:
+change = 変更...
+next-to-each-disc-image = 各ディスクイメージの隣
+write-metadata-sidecar = メタデータファイルを書き出す
+write-sidecar-tip = 各カバーの隣に、取得元 URL・切り抜き・書き出し設定・ディスク情報を記した <name>.artwork.json を保存します
+store-cover-inside-chd-images = CHD イメージ内にカバーを保存
+copy-to-sd-card = マウントされた USBODE の SD カードにもコピー
+copy-to-sd-card-tip = ディスクを含む USBODE の SD カードが接続されている場合 (ファイル名、名前が変わっていれば内容で照合)、カード上のディスクの隣にもアートワークを書き込みます
+embed-in-chd-tip = 表紙を .chd ディスクにメタデータとしても書き込み、別のカードにコピーしてもファイルと一緒に残るようにします
+back-up-overwritten-covers = 上書きする前にカバーをバックアップ
+back-up-overwritten-covers-tip = 既存のカバーを先に隣の .artwork-backup フォルダーへ移動し、「最後の書き出しを元に戻す」で戻せるようにします
+fit = 合わせ方:
+sharpen = シャープ:
+sharpen-tip = リサイズ後にアンシャープマスクをかけ、カバーの文字をくっきりさせます。0 = オフ
+smart-crop = スマート切り抜き
+smart-crop-tip = スキャナーの余白や黒帯を取り除き、常に中央を切り抜く代わりにカバーの最も情報量の多い部分を残します
+upscale-small-images = 小さい画像の拡大:
+upscale-tip = 元画像が出力サイズより小さいときに使います
+disc-badge = ディスクバッジ
+disc-badge-tip = 複数枚組の 2 枚目以降にディスク番号を入れます
+disc-information = ディスク情報
+volume-label = ボリュームラベル:
+volume-icon-tip = ディスク自身のアイコン
+not-found = 見つかりません
+write-cue = CUE を書き出す
+write-cue-tip = chdman で抽出した BIN 用の .cue シートを CHD の隣に保存します
+extract-bin-cue = BIN/CUE を抽出...
+extract-bin-cue-tip = CHD を読めない ODE 向けに BIN/CUE へ変換します
+filesystem = ファイルシステム:
+browse-contents = 内容を参照...
+no-browser = (ブラウザーなし)
+confidence = 信頼度:
+parsed-title = 解析したタイトル:
+disc-number = ディスク番号:
+serial = シリアル:
+volume-descriptor = ボリューム記述子:
+volume-descriptor-details = ISO 9660 の詳細
+pvd-system-id = システム ID
+pvd-application-id = アプリケーション ID
+pvd-publisher = 発行元
+pvd-data-preparer = データ作成者
+pvd-created = 作成日
+pvd-size = サイズ
+pvd-size-value = { $sectors } セクター（{ $size }）
+pvd-contents = 内容
+pvd-contents-value = フォルダー { $directories } 個、ファイル { $files } 個
+pvd-contents-partial = フォルダー { $directories } 個以上、ファイル { $files } 個以上
+copy-all = すべてコピー
+hashing = ハッシュ計算:
+redump = Redump:
+view-on-redump = redump.org で見る
+redump-system = 機種
+redump-media = メディア
+redump-category = カテゴリー
+redump-edition = エディション
+redump-version = バージョン
+redump-catalog = カタログ番号
+other-candidates = ほかの候補 { $count } 件
+redump-waiting-on-hash = 検索中… (ハッシュ待ち)
+no-match = 一致なし
+possible-matches = 一致の候補:
+cover-art = カバーアート:
+check = 確認
+check-artwork-tip = 既存のアートワークを USBODE の形式ルールで確認します
+stored-in-chd = CHD 内に保存済み
+audio-tracks-label = オーディオトラック:
+total-length = 合計時間:
+musicbrainz-id = MusicBrainz ID:
+copy-to-clipboard = クリップボードにコピー
+search-on-musicbrainz = MusicBrainz で検索
+tracklist = トラックリスト:
+tracklist-looking-up = 検索中...
+tracklist-source = { $source } より
+cd-text = CD-TEXT:
+use-as-search-query = 検索語として使用
+use-as-search-query-tip = 「{ $query }」のアートワークを検索
+cd-text-tracks = CD-TEXT トラック:
+cd-text-track-count = { $count } トラック
+files = ファイル数:
+hfs-version = HFS+ バージョン:
+files-folders = ファイル/フォルダー:
+total-size = 合計サイズ:
+free-space = 空き容量:
+enter-search-query = 検索語を入力...
+error-message = エラー: { $error }
+toast-search-failed = 検索に失敗しました
+toast-preview-failed = プレビューに失敗しました
+toast-save-failed = アートワークを保存できませんでした
+toast-usbode-failed = USBODE にアートワークを送信できませんでした
+toast-upload-failed = アートワークをネットワーク上の場所にコピーできませんでした
+toast-sd-card-failed = SD カードにアートワークをコピーできませんでした
+toast-retroarch-failed = RetroArch のサムネイルを書き込めませんでした
+toast-update-failed = アップデートをインストールできませんでした
+show-log = ログを表示
+select-a-disc-image-file-to = ディスクイメージを選択すると情報が表示されます。
+drag-and-drop-disc-image-files = ディスクイメージをここにドラッグ＆ドロップ
+supported-iso-toast-chd-bin-cue = 対応形式: ISO、Toast、CHD、BIN/CUE、MDS/MDF
+update-available = アップデートがあります
+update-new-version = 新しいバージョンがあります: v{ $version }
+update-new-beta = 新しいベータ版があります: v{ $version }
+update-beta-warning = ベータ版はプレリリースのため、不具合がある場合があります。
+update-current-version = 現在のバージョン: v{ $version }
+update-whats-new = 変更点
+update-install = ダウンロードしてインストール
+update-installed = アップデートをインストールしました
+update-restart-prompt = 新しいバージョンをインストールしました。今すぐ再起動して使いますか？
+update-restart-now = 今すぐ再起動
+update-later = 後で
+take-me-to-the-download = ダウンロードページへ
+drop-disc-image = ドロップしてディスクイメージをスキャン
+drop-link = リンクをドロップして画像をプレビュー、またはページを検索に追加
+match-hash = 確定 (ハッシュ)
+match-serial = 確定 (シリアル)
+match-barcode = 確定 (バーコード)
+match-volume-label = 有力 (ボリュームラベル)
+match-title = 候補 (タイトル)
+
+## Scan Folder window
+
+scan-column-title = タイトル
+scan-column-format = 形式
+scan-column-confidence = 信頼度
+scan-column-redump = Redump
+scan-column-artwork = アートワーク
+scan-hash-unmatched = redump に未登録
+
+## Processing queue
+
+stage-waiting = 待機中
+stage-identifying = 識別中
+stage-searching = 検索中
+stage-exporting = 書き出し中
+stage-review = 確認待ち
+stage-skipped = スキップ
+stage-exists = アートワークあり
+stage-would-save = 保存予定
+review-before-saving = 保存前に確認
+review-before-saving-tip = 検索後に各ディスクを止め、保存する前に選択を承認・変更・スキップできるようにします
+would-replace-tip = { $path }（現在のアートワークを置き換えます）
+queue-existing = { $count } 枚のディスクにはすでにアートワークがあります:
+replace-all = すべて置き換える
+replace-all-tip = これらのディスクと、このバッチで後に続くディスクのアートワークを置き換えます
+keep-all = すべて残す
+keep-all-tip = これらのディスクと、このバッチで後に続くディスクのアートワークを残します
+dry-run = ドライラン
+dry-run-tip = ファイルを書き込まずに、各ディスクのアートワークの保存先を報告します
+review-count = 確認 ({ $count })...
+batch-review = 一括確認
+review-empty = 確認待ちのディスクはありません。
+review-summary = { $total } 件中 { $approved } 件を承認
+review-no-thumbnail = プレビューを表示できません
+approve = 承認
+approve-all = すべて承認
+skip-all = すべてスキップ
+save-approved = 承認分を保存 ({ $count })
+save-approved-tip = 承認した各ディスクの選択したアートワークを保存し、残りはスキップします
+
+## Settings window
+
+save = 保存
+saved-to-config-json-and-applied = config.json に保存され、すぐに反映されます。
+settings-content-type = 種類:
+user-agent = ユーザーエージェント:
+settings-auto-pick = 自動選択:
+built-in-browser-identity = 内蔵のブラウザー識別情報
+excluded-sites = 除外するサイト:
+excluded-platforms = 除外する機種:
+cd-keywords = CD のキーワード:
+dvd-keywords = DVD のキーワード:
+known-publishers = 既知のパブリッシャー:
+game-sites = ゲームのサイト:
+app-sites = アプリのサイト:
+audio-cd-sites = 音楽 CD のサイト:
+settings-export = 書き出し
+settings-output-size = 出力サイズ:
+also-save-sizes = 追加で保存するサイズ:
+also-save-sizes-hint = 例: 480, 600
+max-file-size = 最大ファイルサイズ:
+max-file-size-tip = 0 = 制限なし
+settings-next-to-each-disc-image = 各ディスクイメージの隣
+pad-with = 余白の埋め方:
+upscale = 拡大方法:
+settings-disc-badge = ディスクバッジ:
+network = ネットワーク
+connect-timeout = 接続タイムアウト:
+request-timeout = リクエストタイムアウト:
+download-timeout = ダウンロードタイムアウト:
+retries = 再試行回数:
+retry-backoff = 再試行の間隔:
+parallel-downloads = 同時ダウンロード数:
+site-rules-json = サイトごとのルール (JSON):
+fuzzy-redump-matching = Redump のあいまい一致
+fuzzy-source-threshold = ソースごとのしきい値:
+fuzzy-merged-floor = 統合後の下限:
+fuzzy-strong-score = 強い一致のスコア:
+fuzzy-agreement-bonus = 一致ボーナス:
+fuzzy-size-ok-ratio = サイズ許容比:
+fuzzy-size-drop-ratio = サイズ除外比:
+fuzzy-size-penalty = サイズのペナルティ:
+candidate-cap = 候補の上限:
+date-window = 日付の幅:
+unit-days = 日
+track-tolerance = トラックの許容差:
+unit-frames = フレーム
+min-tracks-for-signature = シグネチャに必要な最小トラック数:
+min-strong-for-verify = 確定に必要な強い一致の最小数:
+appearance = 外観
+theme = テーマ:
+language = 言語:
+accent-color = アクセントカラー:
+folders = フォルダー:
+add-folder = フォルダーを追加...
+ui-scale = UI の拡大率:
+ui-scale-tip = 文字やボタンの大きさ。ディスプレイ自体の拡大率に掛け合わされます
+default = 既定
+updates-logging-and-discogs = アップデート、ログ、Discogs
+check-for-updates = アップデートを確認:
+repository = リポジトリ:
+update-channel = アップデートチャンネル:
+update-channel-tip = ベータではプレリリースも含めて最新版を通知します
+update-channel-stable = 安定版
+update-channel-beta = ベータ版
+update-check-interval = 確認間隔:
+update-check-interval-tip = GitHub に再度問い合わせるまでの時間。それまでは前回の結果を使います。0 で起動のたびに確認します
+discogs-request-token-url = Discogs リクエストトークン URL:
+discogs-authorize-url = Discogs 認可 URL:
+discogs-access-token-url = Discogs アクセストークン URL:
+one-per-line = 1 行に 1 つ
+language-system = システムに従う
+
+## Setting values
+
+template-cover = カバー
+template-cd-label = CD レーベル
+template-jewel-case = ジュエルケース
+role-front = 表紙
+role-back = 裏表紙
+role-disc = ディスクのスキャン
+fit-crop = 切り抜き
+fit-pad = 余白で埋める
+pad-fill-color = 単色
+pad-fill-blur = ぼかし
+upscale-lanczos = Lanczos
+upscale-scale2x = Scale2x (輪郭くっきり)
+badge-top-left = 左上
+badge-top-right = 右上
+badge-bottom-left = 左下
+badge-bottom-right = 右下
+badge-style-pill = 「Disc N」ラベル
+badge-style-circle = 番号のみ
+confidence-low = 低 (ファイル名)
+confidence-medium = 中 (メタデータ)
+confidence-high = 高 (ボリュームラベル)
+theme-system = システム
+theme-dark = ダーク
+theme-light = ライト
+
+## Disc browser
+
+browse-files = ファイル
+content = 内容
+view = 表示:
+auto = 自動
+hex = 16 進
+text = テキスト
+export-file = 書き出し...
+text-encoding = 文字コード: { $encoding }
+select-a-file-to-view-its = ファイルを選択すると内容が表示されます
+file-too-large = ファイルが大きすぎて表示できません ({ $size } > { $limit } MB)
+use-export-to-save-the-file = 「書き出し」でファイルを保存できます
+click-on-a-file-to-load = ファイルをクリックすると内容を読み込みます
+select-a-file-from-the-tree = ツリーからファイルを選択すると内容が表示されます
+wrap-lines = 折り返す
+
+## Command palette
+
+command-open-file = ディスクイメージを開く
+command-scan-folder = フォルダー内のディスクイメージをスキャン
+command-watch = フォルダー監視の開始/停止
+command-bulk-job = 一括ジョブを読み込む
+command-fix-artwork = フォルダー内のアートワークを修正
+command-search = アートワークを検索
+command-focus-search = 検索語を編集
+command-save = プレビューをダウンロードして保存
+command-toggle-log = ログの表示/非表示
+command-open-log-file = ログファイルを開く
+command-queue = 処理キューを表示
+command-import-pack-zip = アートワークパックをインポート (zip)
+command-import-pack-folder = アートワークパックをインポート (フォルダー)
+command-palette-hint = コマンドを入力…
+command-palette-empty = 一致するコマンドはありません
@@ -6,17 +6,17 @@
 //! `chdman extractcd` rip.
 //!
 //! Usage:
-//!   cargo run --release --example play_chd -- <path-to-chd> [track] [--save-wav <file>]
+//!   cargo run --release -p ode-artwork-core --example play_chd -- <path-to-chd> [track] [--save-wav <file>]
 //!
 //! Examples:
 //!   # List tracks and play the first AUDIO track:
-//!   cargo run --release --example play_chd -- game.chd
+//!   cargo run --release -p ode-artwork-core --example play_chd -- game.chd
 //!
 //!   # Play track 3 specifically:
-//!   cargo run --release --example play_chd -- game.chd 3
+//!   cargo run --release -p ode-artwork-core --example play_chd -- game.chd 3
 //!
 //!   # Dump track 2 to a 44.1kHz/16-bit/stereo WAV instead of playing:
-//!   cargo run --release --example play_chd -- game.chd 2 --save-wav track2.wav
+//!   cargo run --release -p ode-artwork-core --example play_chd -- game.chd 2 --save-wav track2.wav
 //!
 //! The `--save-wav` output should be bit-identical (modulo the WAV header) to
 //! `chdman extractcd` for the same track, confirming byte order and sector
@@ -26,7 +26,7 @@ use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 
-use ode_artwork_downloader::disc::cd_audio::{
+use ode_artwork_core::disc::cd_audio::{
     self, CdTrack, CDDA_CHANNELS, CDDA_SAMPLE_RATE,
 };
 
@@ -1,7 +1,7 @@
 //! Downstream smoke test for the opticaldiscs 0.4.0 / libchdman-rs swap.
 //!
 //! Usage:
-//!   cargo run --release --example smoke_chd -- <path-to-chd>
+//!   cargo run --release -p ode-artwork-core --example smoke_chd -- <path-to-chd>
 //!
 //! Reads the CHD through opticaldiscs the same way the main app does, then
 //! prints a small report so we can sanity-check that:
@@ -16,8 +16,8 @@
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use ode_artwork_downloader::disc::hasher::{hash_data_track, HashProgress};
-use ode_artwork_downloader::disc::DiscReader;
+use ode_artwork_core::disc::hasher::{hash_data_track, HashProgress};
+use ode_artwork_core::disc::DiscReader;
 
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
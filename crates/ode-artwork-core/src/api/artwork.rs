@@ -2,7 +2,7 @@
 //!
 //! Provides cover art search using web image searches based on parsed filename information.
 
-use crate::config::config_file_path;
+use crate::config::{KnownSites, SearchSettings};
 use crate::disc::{DiscInfo, ParsedFilename};
 use std::path::Path;
 use regex::Regex;
@@ -37,6 +37,8 @@ pub struct SearchConfig {
     pub audio_sites: Vec<String>,
     /// Custom user agent string for HTTP requests
     pub user_agent: Option<String>,
+    /// Preview and save the best-scoring result as soon as a search finishes
+    pub auto_pick: bool,
 }
 
 /// Content type for different disc categories
@@ -78,88 +80,54 @@ impl ContentType {
 }
 
 impl Default for SearchConfig {
+    /// The `search` section of the loaded config.
     fn default() -> Self {
-        // Try to load from the per-user config.json
-        if let Ok(config_str) = config_file_path()
-            .and_then(|p| std::fs::read_to_string(&p).map_err(|e| e.to_string()))
-        {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&config_str) {
-                if let Some(search) = json.get("search") {
-                    return Self {
-                        exclusion_sites: search.get("exclusion_sites")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_else(|| vec!["ebay.com".to_string()]),
-                        
-                        exclusion_platforms: search.get("exclusion_platforms")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_else(|| vec!["playstation".to_string(), "xbox".to_string(), "nintendo".to_string()]),
-                        
-                        cd_keywords: search.get("cd_keywords")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_else(|| vec!["CD".to_string(), "jewel case".to_string()]),
-                        
-                        dvd_keywords: search.get("dvd_keywords")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_else(|| vec!["DVD".to_string()]),
-                        
-                        content_type: search.get("content_type")
-                            .and_then(|v| v.as_str())
-                            .map(ContentType::from_str)
-                            .unwrap_or(ContentType::Any),
-                        
-                        games_sites: search.get("known_sites")
-                            .and_then(|v| v.get("games"))
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-                        
-                        apps_sites: search.get("known_sites")
-                            .and_then(|v| v.get("apps_utilities"))
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-                        
-                        audio_sites: search.get("known_sites")
-                            .and_then(|v| v.get("audio_cds"))
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-                        
-                        known_publishers: search.get("known_publishers")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-
-                        user_agent: search.get("user_agent")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string()),
-                    };
-                }
-            }
-        }
+        Self::from_settings(&crate::config::get_config().search)
+    }
+}
 
-        // Fallback if config.json doesn't exist or can't be read
+impl SearchConfig {
+    pub fn from_settings(settings: &SearchSettings) -> Self {
         Self {
-            exclusion_sites: vec!["ebay.com".to_string()],
-            exclusion_platforms: vec![
-                "playstation".to_string(),
-                "xbox".to_string(),
-                "nintendo".to_string(),
-            ],
-            cd_keywords: vec!["CD".to_string(), "jewel case".to_string()],
-            dvd_keywords: vec!["DVD".to_string()],
-            content_type: ContentType::Any,
-            games_sites: Vec::new(),
-            apps_sites: Vec::new(),
-            audio_sites: Vec::new(),
-            known_publishers: Vec::new(),
-            user_agent: None,
+            exclusion_sites: settings.exclusion_sites.clone(),
+            exclusion_platforms: settings.exclusion_platforms.clone(),
+            cd_keywords: settings.cd_keywords.clone(),
+            dvd_keywords: settings.dvd_keywords.clone(),
+            known_publishers: settings.known_publishers.clone(),
+            content_type: ContentType::from_str(&settings.content_type),
+            games_sites: settings.known_sites.games.clone(),
+            apps_sites: settings.known_sites.apps_utilities.clone(),
+            audio_sites: settings.known_sites.audio_cds.clone(),
+            user_agent: settings.user_agent.clone(),
+            auto_pick: settings.auto_pick,
         }
     }
+
+    /// These settings in the form `config.json` keeps them.
+    pub fn to_settings(&self) -> SearchSettings {
+        SearchSettings {
+            exclusion_sites: self.exclusion_sites.clone(),
+            exclusion_platforms: self.exclusion_platforms.clone(),
+            cd_keywords: self.cd_keywords.clone(),
+            dvd_keywords: self.dvd_keywords.clone(),
+            known_publishers: self.known_publishers.clone(),
+            content_type: self.content_type.as_str().to_string(),
+            known_sites: KnownSites {
+                games: self.games_sites.clone(),
+                apps_utilities: self.apps_sites.clone(),
+                audio_cds: self.audio_sites.clone(),
+            },
+            user_agent: self.user_agent.clone(),
+            auto_pick: self.auto_pick,
+        }
+    }
+
+    /// Write these settings to the `search` section of `config.json` and
+    /// make them the current ones.
+    pub fn save(&self) -> Result<(), String> {
+        let settings = self.to_settings();
+        crate::config::update_config(|config| config.search = settings)
+    }
 }
 
 /// Search query configuration
@@ -672,11 +640,101 @@ pub fn open_in_browser(url: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Open a folder in the system file manager
+pub fn open_folder(dir: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Show a file selected in the system file manager. Linux file managers
+/// are asked over D-Bus; without one that answers, the folder is opened.
+pub fn reveal_in_file_manager(file: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(file)
+            .spawn()
+            .map_err(|e| format!("Failed to reveal file: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // explorer wants "/select," and the path as one argument
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(file);
+        std::process::Command::new("explorer")
+            .arg(select)
+            .spawn()
+            .map_err(|e| format!("Failed to reveal file: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let path = file.to_string_lossy();
+        let segments: Vec<_> = path.split('/').map(urlencoding::encode).collect();
+        let uri = format!("file://{}", segments.join("/"));
+        let shown = std::process::Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ])
+            .arg(format!("array:string:{}", uri))
+            .arg("string:")
+            .output()
+            .is_ok_and(|out| out.status.success());
+        if !shown {
+            let dir = file.parent().unwrap_or(file);
+            return open_folder(dir);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::Path;
 
+    #[test]
+    fn search_config_round_trips_through_settings() {
+        let json = r#"{"content_type": "games", "known_sites": {"games": ["mobygames.com"]}, "user_agent": "UA"}"#;
+        let settings: SearchSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.exclusion_sites, vec!["ebay.com"]);
+        let config = SearchConfig::from_settings(&settings);
+        assert_eq!(config.content_type, ContentType::Games);
+        assert_eq!(config.games_sites, vec!["mobygames.com"]);
+        assert_eq!(config.user_agent.as_deref(), Some("UA"));
+        assert_eq!(config.to_settings(), settings);
+    }
+
     #[test]
     fn test_build_query_simple() {
         let query = ArtworkSearchQuery {
@@ -3,8 +3,10 @@
 //! Provides direct album search and cover art retrieval from Discogs.
 
 use serde::Deserialize;
+use super::ApiError;
 use crate::config::get_secrets;
 use crate::crypto::get_embedded_secrets;
+use crate::net::{client_builder, Timeout};
 
 /// Discogs search response
 #[derive(Debug, Deserialize)]
@@ -35,7 +37,7 @@ pub struct DiscogsResult {
     pub result_type: String,
 }
 
-fn build_client() -> Result<reqwest::blocking::Client, String> {
+fn build_client() -> Result<reqwest::blocking::Client, ApiError> {
     let mut headers = reqwest::header::HeaderMap::new();
 
     // Try to get credentials in order of preference:
@@ -71,14 +73,13 @@ fn build_client() -> Result<reqwest::blocking::Client, String> {
         headers.insert(
             reqwest::header::AUTHORIZATION,
             reqwest::header::HeaderValue::from_str(&auth_value)
-                .map_err(|e| format!("Invalid auth header: {}", e))?,
+                .map_err(|e| ApiError::Other(format!("Invalid auth header: {}", e)))?,
         );
     } else {
         log::debug!("No Discogs API credentials available, using anonymous access");
     }
 
-    reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
+    client_builder(Timeout::Request)
         .default_headers(headers)
         .user_agent(concat!(
             "ODE-Artwork-Downloader/",
@@ -86,11 +87,11 @@ fn build_client() -> Result<reqwest::blocking::Client, String> {
             " +https://github.com/danifunker/ODE-artwork-downloader"
         ))
         .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+        .map_err(ApiError::network("Failed to create HTTP client"))
 }
 
 /// Search Discogs for releases matching artist and title
-pub fn search_release(artist: &str, title: &str) -> Result<Vec<DiscogsResult>, String> {
+pub fn search_release(artist: &str, title: &str) -> Result<Vec<DiscogsResult>, ApiError> {
     let client = build_client()?;
 
     // Build search query - skip "Various Artists" type names
@@ -116,18 +117,21 @@ pub fn search_release(artist: &str, title: &str) -> Result<Vec<DiscogsResult>, S
     let response = client
         .get(&url)
         .send()
-        .map_err(|e| format!("Discogs API request failed: {}", e))?;
+        .map_err(ApiError::network("Discogs API request failed"))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().unwrap_or_default();
         log::error!("Discogs API error: {} - {}", status, body);
-        return Err(format!("Discogs API error: {}", status));
+        return Err(ApiError::Status {
+            context: "Discogs API error",
+            status,
+        });
     }
 
     let search_response: DiscogsSearchResponse = response
         .json()
-        .map_err(|e| format!("Failed to parse Discogs response: {}", e))?;
+        .map_err(|e| ApiError::Parse(format!("Failed to parse Discogs response: {}", e)))?;
 
     log::info!("Discogs API returned {} results", search_response.results.len());
 
@@ -158,7 +162,7 @@ pub fn search_release(artist: &str, title: &str) -> Result<Vec<DiscogsResult>, S
 }
 
 /// Get high-resolution cover art for a specific release
-pub fn get_release_images(release_id: u64, is_master: bool) -> Result<Vec<String>, String> {
+pub fn get_release_images(release_id: u64, is_master: bool) -> Result<Vec<String>, ApiError> {
     let client = build_client()?;
 
     let url = if is_master {
@@ -172,16 +176,19 @@ pub fn get_release_images(release_id: u64, is_master: bool) -> Result<Vec<String
     let response = client
         .get(&url)
         .send()
-        .map_err(|e| format!("Failed to fetch release details: {}", e))?;
+        .map_err(ApiError::network("Failed to fetch release details"))?;
 
     if !response.status().is_success() {
-        return Err(format!("Failed to fetch release: {}", response.status()));
+        return Err(ApiError::Status {
+            context: "Failed to fetch release",
+            status: response.status(),
+        });
     }
 
     // Parse as generic JSON to handle both release and master
     let json: serde_json::Value = response
         .json()
-        .map_err(|e| format!("Failed to parse release: {}", e))?;
+        .map_err(|e| ApiError::Parse(format!("Failed to parse release: {}", e)))?;
 
     let mut image_urls = Vec::new();
 
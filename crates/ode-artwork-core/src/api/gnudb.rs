@@ -0,0 +1,225 @@
+//! gnudb.org lookups for audio CDs MusicBrainz doesn't know.
+//!
+//! gnudb carries on the freedb database and speaks its CDDB protocol over
+//! HTTP: `cddb query` finds entries by the disc's CDDB ID and track offsets,
+//! and `cddb read` returns one as xmcd `KEY=value` lines.
+
+use super::tracklist::{AlbumTrack, Toc, Tracklist};
+use super::ApiError;
+use crate::net::{client_builder, send_with_retry, Timeout};
+
+const CDDB_URL: &str = "https://gnudb.gnudb.org/~cddb/cddb.cgi";
+
+/// CDDB `hello`: user, host, client name and version.
+const HELLO: &str = concat!(
+    "anonymous+localhost+ODE-Artwork-Downloader+",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Protocol level 6 answers in UTF-8.
+const PROTO: u32 = 6;
+
+/// The album gnudb has for the disc with `toc`, if any. Takes the first of
+/// several matches.
+pub fn tracklist_by_toc(toc: &Toc) -> Result<Option<Tracklist>, ApiError> {
+    let offsets: Vec<String> = toc.offsets.iter().map(u32::to_string).collect();
+    let query = format!(
+        "cddb query {:08x} {} {} {}",
+        disc_id(toc),
+        toc.offsets.len(),
+        offsets.join(" "),
+        toc.leadout / 75
+    );
+    let Some((category, id)) = parse_query(&cddb_command(&query)?)? else {
+        return Ok(None);
+    };
+    let entry = cddb_command(&format!("cddb read {} {}", category, id))?;
+    Ok(parse_entry(&entry, toc.first))
+}
+
+fn cddb_command(command: &str) -> Result<String, ApiError> {
+    let url = format!(
+        "{}?cmd={}&hello={}&proto={}",
+        CDDB_URL,
+        command.replace(' ', "+"),
+        HELLO,
+        PROTO
+    );
+    let client = client_builder(Timeout::Request)
+        .user_agent(crate::db::USER_AGENT)
+        .build()
+        .map_err(ApiError::network("Failed to create HTTP client"))?;
+    let response = send_with_retry(|| client.get(&url).send())
+        .map_err(ApiError::network("gnudb lookup failed"))?;
+    if !response.status().is_success() {
+        return Err(ApiError::Status {
+            context: "gnudb error",
+            status: response.status(),
+        });
+    }
+    response
+        .text()
+        .map_err(ApiError::network("Failed to read gnudb response"))
+}
+
+/// CDDB disc ID: a checksum of the track start times in seconds, the
+/// playing time, and the track count.
+fn disc_id(toc: &Toc) -> u32 {
+    let digit_sum = |mut n: u32| {
+        let mut sum = 0;
+        while n > 0 {
+            sum += n % 10;
+            n /= 10;
+        }
+        sum
+    };
+    let checksum: u32 = toc.offsets.iter().map(|o| digit_sum(o / 75)).sum();
+    let first = toc.offsets.first().copied().unwrap_or_default();
+    let length = (toc.leadout / 75).saturating_sub(first / 75);
+    ((checksum % 0xff) << 24) | (length << 8) | toc.offsets.len() as u32
+}
+
+/// Category and ID of the first match in a `cddb query` response. `None`
+/// when nothing matched; other status codes are errors.
+fn parse_query(response: &str) -> Result<Option<(String, String)>, ApiError> {
+    let mut lines = response.lines();
+    let status = lines.next().unwrap_or_default().trim();
+    let matched = match status.get(..3) {
+        // Exact match, on the status line itself
+        Some("200") => status[3..].trim_start(),
+        // Several exact (210) or close (211) matches, one per line
+        Some("210" | "211") => lines.next().unwrap_or_default(),
+        Some("202") => return Ok(None),
+        _ => return Err(ApiError::Parse(format!("gnudb query failed: {}", status))),
+    };
+    let mut fields = matched.split_whitespace();
+    Ok(fields
+        .next()
+        .zip(fields.next())
+        .filter(|(category, _)| *category != ".")
+        .map(|(category, id)| (category.to_string(), id.to_string())))
+}
+
+/// Album from a `cddb read` response. `TTITLE0` is track `first`. Values
+/// may be split over several lines with the same key.
+fn parse_entry(response: &str, first: u32) -> Option<Tracklist> {
+    let mut lines = response.lines();
+    if !lines.next()?.starts_with("210") {
+        return None;
+    }
+    let mut dtitle = String::new();
+    let mut titles: Vec<String> = Vec::new();
+    for line in lines {
+        if line == "." {
+            break;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key == "DTITLE" {
+            dtitle.push_str(value);
+        } else if let Some(Ok(index)) = key.strip_prefix("TTITLE").map(str::parse::<usize>) {
+            if titles.len() <= index {
+                titles.resize(index + 1, String::new());
+            }
+            titles[index].push_str(value);
+        }
+    }
+    // "Artist / Album", or just one name when they're the same
+    let (artist, album) = dtitle.split_once(" / ").unwrap_or((&dtitle, &dtitle));
+    let tracks = titles
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| !title.trim().is_empty())
+        .map(|(i, title)| AlbumTrack {
+            number: first + i as u32,
+            title: unescape(title.trim()),
+            seconds: None,
+        })
+        .collect();
+    Some(Tracklist {
+        source: "gnudb",
+        album: unescape(album.trim()),
+        artist: unescape(artist.trim()),
+        tracks,
+    })
+}
+
+/// Undo xmcd escapes; line breaks become spaces.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 't') => out.push(' '),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disc_id_follows_the_cddb_checksum() {
+        let toc = Toc::parse("1 6 242457 150 44942 61305 72755 96360 130485").unwrap();
+        // Start seconds 2, 599, 817, 970, 1284, 1739 have digit sums adding
+        // up to 92; 3230 seconds long; 6 tracks
+        assert_eq!(disc_id(&toc), (92 << 24) | (3230 << 8) | 6);
+        assert_eq!(format!("{:08x}", disc_id(&toc)), "5c0c9e06");
+    }
+
+    #[test]
+    fn query_takes_the_first_match() {
+        assert_eq!(
+            parse_query("200 rock 5c0c9e06 Artist / Album\r\n").unwrap(),
+            Some(("rock".to_string(), "5c0c9e06".to_string()))
+        );
+        let several =
+            "211 close matches found\r\nmisc 5c0c9e06 A / B\r\nrock 5c0c9e07 C / D\r\n.\r\n";
+        assert_eq!(
+            parse_query(several).unwrap(),
+            Some(("misc".to_string(), "5c0c9e06".to_string()))
+        );
+        assert_eq!(parse_query("202 No match found\r\n").unwrap(), None);
+        assert!(matches!(
+            parse_query("409 No handshake\r\n"),
+            Err(ApiError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn entry_joins_split_values_and_numbers_from_first() {
+        let entry = "210 rock 5c0c9e06 CD database entry follows\r\n\
+            # xmcd\r\n\
+            DTITLE=Pink Floyd / The Wall\r\n\
+            TTITLE0=In the Flesh?\r\n\
+            TTITLE1=The Thin \r\n\
+            TTITLE1=Ice\r\n\
+            TTITLE2=\r\n\
+            EXTD=\r\n\
+            .\r\n";
+        let list = parse_entry(entry, 1).unwrap();
+        assert_eq!(list.artist, "Pink Floyd");
+        assert_eq!(list.album, "The Wall");
+        assert_eq!(list.tracks.len(), 2);
+        assert_eq!(list.tracks[1].number, 2);
+        assert_eq!(list.tracks[1].title, "The Thin Ice");
+        assert!(parse_entry("401 not found\r\n", 1).is_none());
+    }
+
+    #[test]
+    fn single_name_dtitle_is_artist_and_album() {
+        let list = parse_entry("210 misc 1 entry\r\nDTITLE=Sampler\\nVol. 2\r\n.\r\n", 1).unwrap();
+        assert_eq!(list.artist, "Sampler Vol. 2");
+        assert_eq!(list.album, "Sampler Vol. 2");
+        assert!(list.tracks.is_empty());
+    }
+}
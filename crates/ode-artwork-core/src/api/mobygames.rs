@@ -3,6 +3,7 @@
 //! Uses headless Chromium to bypass Cloudflare protection and scrape
 //! cover art from MobyGames game pages.
 
+use super::ApiError;
 use headless_chrome::{Browser, LaunchOptions};
 use std::time::Duration;
 
@@ -41,7 +42,7 @@ const PLATFORM_PRIORITY: &[&str] = &[
 pub fn search_game_covers(
     title: &str,
     user_agent: Option<&str>,
-) -> Result<Vec<crate::search::ImageResult>, String> {
+) -> Result<Vec<crate::search::ImageResult>, ApiError> {
     log::info!("MobyGames search for: {}", title);
 
     // Step 1: DDG search to find MobyGames game page
@@ -53,7 +54,7 @@ pub fn search_game_covers(
         .iter()
         .find(|r| r.url.contains("mobygames.com/game/"))
         .map(|r| &r.url)
-        .ok_or_else(|| format!("No MobyGames result found for '{}'", title))?;
+        .ok_or_else(|| ApiError::NotFound(format!("No MobyGames result found for '{}'", title)))?;
 
     log::info!("Found MobyGames game page: {}", game_url);
 
@@ -61,14 +62,17 @@ pub fn search_game_covers(
     let covers = fetch_covers_with_chromium(game_url)?;
 
     if covers.is_empty() {
-        return Err("No covers found on MobyGames page".to_string());
+        return Err(ApiError::NotFound(
+            "No covers found on MobyGames page".to_string(),
+        ));
     }
 
     log::info!("Found {} covers on MobyGames", covers.len());
 
     // Step 4: Find best cover based on platform priority
-    let best_cover = find_best_cover(&covers)
-        .ok_or_else(|| "No suitable cover found (no front covers)".to_string())?;
+    let best_cover = find_best_cover(&covers).ok_or_else(|| {
+        ApiError::NotFound("No suitable cover found (no front covers)".to_string())
+    })?;
 
     log::info!(
         "Selected cover: {} - {} ({})",
@@ -92,7 +96,7 @@ pub fn search_game_covers(
 }
 
 /// Fetch covers page using headless Chromium
-fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, String> {
+fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, ApiError> {
     log::info!("Launching headless Chromium...");
 
     // Configure browser launch options
@@ -100,16 +104,16 @@ fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, String>
         .headless(true)
         .idle_browser_timeout(Duration::from_secs(60))
         .build()
-        .map_err(|e| format!("Failed to configure browser: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Failed to configure browser: {}", e)))?;
 
     // Launch browser (will auto-download Chromium if needed)
     let browser = Browser::new(launch_options)
-        .map_err(|e| format!("Failed to launch browser: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Failed to launch browser: {}", e)))?;
 
     // Create new tab
     let tab = browser
         .new_tab()
-        .map_err(|e| format!("Failed to create tab: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Failed to create tab: {}", e)))?;
 
     // Build covers page URL
     let covers_url = format!("{}/covers/", game_url.trim_end_matches('/'));
@@ -117,11 +121,11 @@ fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, String>
 
     // Navigate to covers page
     tab.navigate_to(&covers_url)
-        .map_err(|e| format!("Failed to navigate: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Failed to navigate: {}", e)))?;
 
     // Wait for page to load
     tab.wait_until_navigated()
-        .map_err(|e| format!("Navigation timeout: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Navigation timeout: {}", e)))?;
 
     // Give the page a moment to fully render
     std::thread::sleep(Duration::from_millis(1000));
@@ -129,7 +133,7 @@ fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, String>
     // Get page content
     let html = tab
         .get_content()
-        .map_err(|e| format!("Failed to get page content: {}", e))?;
+        .map_err(|e| ApiError::Browser(format!("Failed to get page content: {}", e)))?;
 
     log::debug!("Got {} bytes of HTML from covers page", html.len());
 
@@ -138,7 +142,7 @@ fn fetch_covers_with_chromium(game_url: &str) -> Result<Vec<CoverInfo>, String>
 }
 
 /// Parse MobyGames covers page HTML to extract cover information
-fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, String> {
+fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, ApiError> {
     let mut covers = Vec::new();
 
     // MobyGames covers page structure (based on typical structure):
@@ -149,12 +153,12 @@ fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, String> {
     // Looking for image thumbnails with cover info
     let _cover_pattern = regex::Regex::new(
         r#"<a[^>]*href="(/game/[^"]+/cover/[^"]+)"[^>]*>.*?<img[^>]*src="([^"]+)"[^>]*/?>.*?</a>"#
-    ).map_err(|e| format!("Failed to compile cover regex: {}", e))?;
+    ).map_err(|e| ApiError::Other(format!("Failed to compile cover regex: {}", e)))?;
 
     // Pattern to find platform sections
     let platform_pattern = regex::Regex::new(
         r#"<h2[^>]*>([^<]+)</h2>"#
-    ).map_err(|e| format!("Failed to compile platform regex: {}", e))?;
+    ).map_err(|e| ApiError::Other(format!("Failed to compile platform regex: {}", e)))?;
 
     // Find all platform headers and their positions
     let mut platform_positions: Vec<(usize, String)> = platform_pattern
@@ -173,7 +177,7 @@ fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, String> {
     // <div class="cover-art-group">
     let group_pattern = regex::Regex::new(
         r#"(?s)<div[^>]*class="[^"]*coverHeading[^"]*"[^>]*>([^<]+)</div>.*?<img[^>]*src="([^"]+)"#
-    ).map_err(|e| format!("Failed to compile group regex: {}", e))?;
+    ).map_err(|e| ApiError::Other(format!("Failed to compile group regex: {}", e)))?;
 
     for caps in group_pattern.captures_iter(html) {
         if let (Some(info_match), Some(img_match)) = (caps.get(1), caps.get(2)) {
@@ -201,7 +205,7 @@ fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, String> {
         // Look for any cover images
         let img_pattern = regex::Regex::new(
             r#"<img[^>]*src="(https://cdn\.mobygames\.com/[^"]+)"[^>]*alt="([^"]*)"#
-        ).map_err(|e| format!("Failed to compile img regex: {}", e))?;
+        ).map_err(|e| ApiError::Other(format!("Failed to compile img regex: {}", e)))?;
 
         for caps in img_pattern.captures_iter(html) {
             if let (Some(src_match), Some(alt_match)) = (caps.get(1), caps.get(2)) {
@@ -235,7 +239,7 @@ fn parse_covers_html(html: &str) -> Result<Vec<CoverInfo>, String> {
     if covers.is_empty() {
         let cdn_pattern = regex::Regex::new(
             r#"https://cdn\.mobygames\.com/[a-f0-9\-]+/covers/[^"'\s]+"#
-        ).map_err(|e| format!("Failed to compile cdn regex: {}", e))?;
+        ).map_err(|e| ApiError::Other(format!("Failed to compile cdn regex: {}", e)))?;
 
         for mat in cdn_pattern.find_iter(html) {
             let url = mat.as_str().to_string();
@@ -0,0 +1,75 @@
+//! API and database integration module
+//!
+//! Provides functionality for matching disc images against game databases
+//! and searching for cover artwork.
+
+pub mod artwork;
+pub mod discogs;
+pub mod gnudb;
+pub mod mobygames;
+pub mod musicbrainz;
+pub mod tracklist;
+
+pub use artwork::{
+    open_folder, open_in_browser, reveal_in_file_manager, ArtworkSearchQuery, SearchConfig,
+    ContentType,
+};
+pub use discogs::{search_release as discogs_search, DiscogsResult};
+pub use mobygames::search_game_covers;
+pub use musicbrainz::{search_by_discid, track_titles_by_discid, MusicBrainzResult};
+pub use tracklist::{lookup_tracklist, Tracklist};
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Why a MusicBrainz, Discogs, gnudb or MobyGames lookup failed.
+#[derive(Error, Debug)]
+pub enum ApiError {
+    /// No answer from the service: offline, DNS, timeout
+    #[error("{context}: {source}")]
+    Network {
+        context: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// The service answered with an error status
+    #[error("{context}: HTTP {status}")]
+    Status {
+        context: &'static str,
+        status: StatusCode,
+    },
+
+    /// The response wasn't in the shape expected
+    #[error("{0}")]
+    Parse(String),
+
+    /// The service answered but had nothing for the disc
+    #[error("{0}")]
+    NotFound(String),
+
+    /// The headless browser used for MobyGames failed
+    #[error("{0}")]
+    Browser(String),
+
+    /// The web search used to find the page failed
+    #[error(transparent)]
+    Search(#[from] crate::search::SearchError),
+
+    /// A bad credentials header or pattern; not the service's fault
+    #[error("{0}")]
+    Other(String),
+}
+
+impl ApiError {
+    fn network(context: &'static str) -> impl FnOnce(reqwest::Error) -> Self {
+        move |source| ApiError::Network { context, source }
+    }
+}
+
+/// For callers that only report the error.
+impl From<ApiError> for String {
+    fn from(e: ApiError) -> Self {
+        e.to_string()
+    }
+}
@@ -0,0 +1,374 @@
+//! MusicBrainz API integration for audio CD identification
+//!
+//! Provides disc lookup and cover art retrieval from MusicBrainz
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::tracklist::{AlbumTrack, Tracklist};
+use super::ApiError;
+use crate::net::{client_builder, Timeout};
+
+#[derive(Debug, Deserialize)]
+struct CoverArtArchiveResponse {
+    images: Vec<CoverArtImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoverArtImage {
+    image: String,
+    thumbnails: Option<CoverArtThumbnails>,
+    front: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoverArtThumbnails {
+    #[serde(rename = "250")]
+    small: Option<String>,
+    #[serde(rename = "500")]
+    medium: Option<String>,
+    #[serde(rename = "1200")]
+    large: Option<String>,
+}
+
+/// MusicBrainz API result
+#[derive(Debug, Clone)]
+pub struct MusicBrainzResult {
+    /// Album/release title
+    pub title: String,
+    /// Artist name
+    pub artist: String,
+    /// Release date (if available)
+    pub date: Option<String>,
+    /// MusicBrainz release ID
+    pub release_id: String,
+    /// Cover Art Archive URL (if available)
+    pub cover_art_url: Option<String>,
+    /// Thumbnail URL (if available)
+    pub thumbnail_url: Option<String>,
+}
+
+/// Query MusicBrainz for releases matching the disc ID
+/// If toc_string is provided, it will be used for fuzzy matching when disc ID lookup fails
+pub fn search_by_discid(disc_id: &str, toc_string: Option<&str>) -> Result<Vec<MusicBrainzResult>, ApiError> {
+    log::info!("Querying MusicBrainz for disc ID: {}", disc_id);
+
+    // Build the URL manually for disc ID lookup
+    let mut url = format!(
+        "https://musicbrainz.org/ws/2/discid/{}?fmt=json&inc=artist-credits+release-groups",
+        disc_id
+    );
+    
+    // Add TOC parameter for fuzzy matching if provided
+    if let Some(toc) = toc_string {
+        url.push_str(&format!("&toc={}", toc));
+        log::info!("Added TOC for fuzzy matching: {}", toc);
+    }
+
+    // Use reqwest directly since musicbrainz_rs doesn't have disc ID lookup built-in
+    let client = client_builder(Timeout::Request)
+        .user_agent(concat!(
+            "ODE-Artwork-Downloader/",
+            env!("CARGO_PKG_VERSION"),
+            " ( https://github.com/dani/ODE-artwork-downloader )"
+        ))
+        .build()
+        .map_err(ApiError::network("Failed to create HTTP client"))?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .map_err(ApiError::network("MusicBrainz lookup failed"))?;
+
+    if !response.status().is_success() {
+        if response.status().as_u16() == 404 {
+            log::warn!("No releases found for disc ID: {}", disc_id);
+            return Ok(Vec::new());
+        }
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        log::error!("MusicBrainz API error response: {}", body);
+        return Err(ApiError::Status {
+            context: "MusicBrainz API error",
+            status,
+        });
+    }
+
+    // Parse the JSON response manually
+    let json: serde_json::Value = response
+        .json()
+        .map_err(|e| ApiError::Parse(format!("Failed to parse MusicBrainz response: {}", e)))?;
+
+    let releases = json["releases"]
+        .as_array()
+        .ok_or_else(|| ApiError::Parse("Invalid response format".to_string()))?;
+
+    if releases.is_empty() {
+        log::warn!("No releases found for disc ID: {}", disc_id);
+        return Ok(Vec::new());
+    }
+
+    log::info!("Found {} release(s) for disc ID", releases.len());
+
+    // Convert to our result format and fetch cover art
+    let mut results = Vec::new();
+    for release in releases {
+        let release_id = release["id"]
+            .as_str()
+            .ok_or_else(|| ApiError::Parse("Missing release ID".to_string()))?
+            .to_string();
+
+        let title = release["title"]
+            .as_str()
+            .unwrap_or("Unknown Album")
+            .to_string();
+
+        let artist = release["artist-credit"]
+            .as_array()
+            .and_then(|credits| credits.first())
+            .and_then(|credit| credit["name"].as_str())
+            .unwrap_or("Unknown Artist")
+            .to_string();
+
+        let date = release["date"].as_str().map(|s| s.to_string());
+
+        // Try to get cover art
+        let (cover_art_url, thumbnail_url) = match get_cover_art(&release_id) {
+            Ok((cover, thumb)) => (Some(cover), thumb),
+            Err(e) => {
+                log::warn!("Failed to get cover art for {}: {}", release_id, e);
+                (None, None)
+            }
+        };
+
+        results.push(MusicBrainzResult {
+            title,
+            artist,
+            date,
+            release_id,
+            cover_art_url,
+            thumbnail_url,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Track titles of the first release matching the disc ID, keyed by track
+/// number. Used to name ripped audio tracks; empty when nothing matches.
+pub fn track_titles_by_discid(disc_id: &str, toc_string: Option<&str>) -> Result<BTreeMap<u32, String>, ApiError> {
+    Ok(discid_releases(disc_id, toc_string)?
+        .map(|json| track_titles_from_json(&json, disc_id))
+        .unwrap_or_default())
+}
+
+/// Album, artist and tracks of the first release matching the disc ID, or
+/// `None` when nothing matches.
+pub fn tracklist_by_discid(disc_id: &str, toc_string: Option<&str>) -> Result<Option<Tracklist>, ApiError> {
+    Ok(discid_releases(disc_id, toc_string)?.and_then(|json| tracklist_from_json(&json, disc_id)))
+}
+
+/// Releases matching the disc ID with their tracks, or `None` on a 404.
+fn discid_releases(disc_id: &str, toc_string: Option<&str>) -> Result<Option<serde_json::Value>, ApiError> {
+    let mut url = format!(
+        "https://musicbrainz.org/ws/2/discid/{}?fmt=json&inc=artist-credits+recordings",
+        disc_id
+    );
+    if let Some(toc) = toc_string {
+        url.push_str(&format!("&toc={}", toc));
+    }
+
+    let client = client_builder(Timeout::Request)
+        .user_agent(concat!(
+            "ODE-Artwork-Downloader/",
+            env!("CARGO_PKG_VERSION"),
+            " ( https://github.com/dani/ODE-artwork-downloader )"
+        ))
+        .build()
+        .map_err(ApiError::network("Failed to create HTTP client"))?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .map_err(ApiError::network("MusicBrainz lookup failed"))?;
+
+    if response.status().as_u16() == 404 {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(ApiError::Status {
+            context: "MusicBrainz API error",
+            status: response.status(),
+        });
+    }
+
+    response
+        .json()
+        .map(Some)
+        .map_err(|e| ApiError::Parse(format!("Failed to parse MusicBrainz response: {}", e)))
+}
+
+/// Track titles from [`tracklist_from_json`], keyed by track number.
+fn track_titles_from_json(json: &serde_json::Value, disc_id: &str) -> BTreeMap<u32, String> {
+    tracklist_from_json(json, disc_id)
+        .map(|list| list.tracks.into_iter().map(|t| (t.number, t.title)).collect())
+        .unwrap_or_default()
+}
+
+/// Pick the medium carrying `disc_id` (multi-disc releases list every disc)
+/// from the first release, falling back to its first medium.
+fn tracklist_from_json(json: &serde_json::Value, disc_id: &str) -> Option<Tracklist> {
+    let release = json["releases"].get(0)?;
+    let media = release["media"].as_array().cloned().unwrap_or_default();
+    let medium = media
+        .iter()
+        .find(|m| {
+            m["discs"]
+                .as_array()
+                .is_some_and(|discs| discs.iter().any(|d| d["id"] == disc_id))
+        })
+        .or_else(|| media.first());
+    let tracks = medium
+        .and_then(|m| m["tracks"].as_array())
+        .map(|tracks| {
+            tracks
+                .iter()
+                .filter_map(|t| {
+                    let number = t["position"].as_u64()? as u32;
+                    let title = t["title"].as_str()?.trim();
+                    (!title.is_empty()).then(|| AlbumTrack {
+                        number,
+                        title: title.to_string(),
+                        // Milliseconds
+                        seconds: t["length"].as_u64().map(|ms| (ms / 1000) as u32),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let artist = release["artist-credit"]
+        .as_array()
+        .and_then(|credits| credits.first())
+        .and_then(|credit| credit["name"].as_str())
+        .unwrap_or("Unknown Artist");
+    Some(Tracklist {
+        source: "MusicBrainz",
+        album: release["title"].as_str().unwrap_or("Unknown Album").to_string(),
+        artist: artist.to_string(),
+        tracks,
+    })
+}
+
+/// Get cover art URL from Cover Art Archive
+fn get_cover_art(release_id: &str) -> Result<(String, Option<String>), ApiError> {
+    let url = format!("https://coverartarchive.org/release/{}", release_id);
+
+    let client = client_builder(Timeout::Request)
+        .user_agent(concat!(
+            "ODE-Artwork-Downloader/",
+            env!("CARGO_PKG_VERSION"),
+            " ( https://github.com/dani/ODE-artwork-downloader )"
+        ))
+        .build()
+        .map_err(ApiError::network("Failed to create HTTP client"))?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .map_err(ApiError::network("Failed to query Cover Art Archive"))?;
+
+    if !response.status().is_success() {
+        return Err(ApiError::Status {
+            context: "Cover Art Archive error",
+            status: response.status(),
+        });
+    }
+
+    let coverart: CoverArtArchiveResponse = response
+        .json()
+        .map_err(|e| ApiError::Parse(format!("Failed to parse cover art response: {}", e)))?;
+
+    // Find front cover, or use first image
+    let image = coverart
+        .images
+        .iter()
+        .find(|img| img.front)
+        .or_else(|| coverart.images.first())
+        .ok_or_else(|| ApiError::NotFound("No cover art found".to_string()))?;
+
+    let thumbnail = image
+        .thumbnails
+        .as_ref()
+        .and_then(|t| {
+            t.large
+                .clone()
+                .or_else(|| t.medium.clone())
+                .or_else(|| t.small.clone())
+        });
+
+    Ok((image.image.clone(), thumbnail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network access
+    fn test_search_by_discid() {
+        // Example disc ID for testing (Pink Floyd - The Wall)
+        let disc_id = "Wn8eRBtd9vAbzyhjiSRQ_ZQT49w-";
+        let results = search_by_discid(disc_id, None).unwrap();
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn track_titles_come_from_the_matching_medium() {
+        let json = serde_json::json!({
+            "releases": [{
+                "media": [
+                    {
+                        "discs": [{ "id": "disc-one" }],
+                        "tracks": [{ "position": 1, "title": "Intro" }]
+                    },
+                    {
+                        "discs": [{ "id": "disc-two" }],
+                        "tracks": [
+                            { "position": 1, "title": "Hey You" },
+                            { "position": 2, "title": " Nobody Home " },
+                            { "position": 3, "title": "" }
+                        ]
+                    }
+                ]
+            }]
+        });
+        let titles = track_titles_from_json(&json, "disc-two");
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&2], "Nobody Home");
+        // Unknown disc IDs fall back to the first medium
+        assert_eq!(track_titles_from_json(&json, "other")[&1], "Intro");
+    }
+
+    #[test]
+    fn tracklist_has_album_artist_and_lengths() {
+        let json = serde_json::json!({
+            "releases": [{
+                "title": "The Wall",
+                "artist-credit": [{ "name": "Pink Floyd" }],
+                "media": [{
+                    "tracks": [
+                        { "position": 1, "title": "In the Flesh?", "length": 199_000 },
+                        { "position": 2, "title": "The Thin Ice", "length": null }
+                    ]
+                }]
+            }]
+        });
+        let list = tracklist_from_json(&json, "any").unwrap();
+        assert_eq!(list.album, "The Wall");
+        assert_eq!(list.artist, "Pink Floyd");
+        assert_eq!(list.tracks[0].seconds, Some(199));
+        assert_eq!(list.tracks[1].seconds, None);
+        assert!(tracklist_from_json(&serde_json::json!({ "releases": [] }), "any").is_none());
+    }
+}
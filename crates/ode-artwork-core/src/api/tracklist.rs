@@ -0,0 +1,168 @@
+//! Album tracklists for audio CDs, listed in Disc Information so a match can
+//! be checked against the disc before its cover is grabbed.
+//!
+//! MusicBrainz is asked first; gnudb (the freedb successor) covers CDs
+//! MusicBrainz doesn't know. Track lengths the source leaves out are taken
+//! from the disc's own TOC.
+
+use super::{gnudb, musicbrainz, ApiError};
+
+/// CD sectors per second.
+const SECTORS_PER_SECOND: u32 = 75;
+
+/// An album matched to a disc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracklist {
+    /// Where the match came from ("MusicBrainz" or "gnudb")
+    pub source: &'static str,
+    pub album: String,
+    pub artist: String,
+    pub tracks: Vec<AlbumTrack>,
+}
+
+/// One track of a [`Tracklist`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumTrack {
+    pub number: u32,
+    pub title: String,
+    /// Length in seconds, when known
+    pub seconds: Option<u32>,
+}
+
+impl AlbumTrack {
+    /// Length as `M:SS`, when known.
+    pub fn length(&self) -> Option<String> {
+        self.seconds.map(|s| format!("{}:{:02}", s / 60, s % 60))
+    }
+}
+
+/// A disc's TOC as MusicBrainz writes it: first track, last track, lead-out
+/// and each track's start, all in sectors counting the 2-second lead-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toc {
+    pub first: u32,
+    pub leadout: u32,
+    /// Start of each track, first to last
+    pub offsets: Vec<u32>,
+}
+
+impl Toc {
+    /// Parse `first last leadout offset...`, separated by spaces or `+`.
+    pub fn parse(toc: &str) -> Option<Self> {
+        let numbers: Vec<u32> = toc
+            .split([' ', '+'])
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().ok())
+            .collect::<Option<_>>()?;
+        let [first, last, leadout, offsets @ ..] = numbers.as_slice() else {
+            return None;
+        };
+        if last < first || offsets.len() != (last - first + 1) as usize {
+            return None;
+        }
+        Some(Self {
+            first: *first,
+            leadout: *leadout,
+            offsets: offsets.to_vec(),
+        })
+    }
+
+    /// Length of each track in seconds, first to last.
+    pub fn track_seconds(&self) -> Vec<u32> {
+        let ends = self.offsets.iter().skip(1).chain([&self.leadout]);
+        self.offsets
+            .iter()
+            .zip(ends)
+            .map(|(start, end)| end.saturating_sub(*start) / SECTORS_PER_SECOND)
+            .collect()
+    }
+}
+
+/// Look up the album on the disc with MusicBrainz `disc_id` and TOC
+/// `toc_string`. `Ok(None)` when neither MusicBrainz nor gnudb knows it.
+pub fn lookup_tracklist(disc_id: &str, toc_string: &str) -> Result<Option<Tracklist>, ApiError> {
+    let toc = Toc::parse(toc_string);
+    let mut found = musicbrainz::tracklist_by_discid(disc_id, Some(toc_string));
+    if !matches!(found, Ok(Some(_))) {
+        if let Err(e) = &found {
+            log::warn!("MusicBrainz tracklist lookup failed: {}", e);
+        }
+        // gnudb only answers for what MusicBrainz couldn't: a miss there
+        // keeps MusicBrainz's result
+        match toc.as_ref().map(gnudb::tracklist_by_toc) {
+            Some(Ok(None)) | None => {}
+            Some(other) => found = other,
+        }
+    }
+    Ok(found?.map(|list| match &toc {
+        Some(toc) => with_toc_lengths(list, toc),
+        None => list,
+    }))
+}
+
+/// Fill in lengths the source didn't give from the disc's TOC.
+fn with_toc_lengths(mut list: Tracklist, toc: &Toc) -> Tracklist {
+    let seconds = toc.track_seconds();
+    for track in list.tracks.iter_mut().filter(|t| t.seconds.is_none()) {
+        track.seconds = track
+            .number
+            .checked_sub(toc.first)
+            .and_then(|i| seconds.get(i as usize))
+            .copied();
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toc_parses_either_separator() {
+        let spaced = Toc::parse("1 3 60000 150 15150 30150").unwrap();
+        assert_eq!(
+            Toc::parse("1+3+60000+150+15150+30150"),
+            Some(spaced.clone())
+        );
+        assert_eq!(spaced.first, 1);
+        assert_eq!(spaced.leadout, 60000);
+        assert_eq!(spaced.offsets, [150, 15150, 30150]);
+
+        // Track count has to match the offsets given
+        assert!(Toc::parse("1 3 60000 150 15150").is_none());
+        assert!(Toc::parse("1 x 60000 150").is_none());
+        assert!(Toc::parse("").is_none());
+    }
+
+    #[test]
+    fn track_seconds_run_to_the_next_track_or_leadout() {
+        let toc = Toc::parse("1 3 60000 150 15150 30150").unwrap();
+        assert_eq!(toc.track_seconds(), [200, 200, 398]);
+    }
+
+    #[test]
+    fn missing_lengths_come_from_the_toc() {
+        let toc = Toc::parse("2 3 30000 150 15150").unwrap();
+        let list = Tracklist {
+            source: "gnudb",
+            album: "Album".to_string(),
+            artist: "Artist".to_string(),
+            tracks: vec![
+                AlbumTrack {
+                    number: 2,
+                    title: "Given".to_string(),
+                    seconds: Some(61),
+                },
+                AlbumTrack {
+                    number: 3,
+                    title: "From TOC".to_string(),
+                    seconds: None,
+                },
+            ],
+        };
+        let list = with_toc_lengths(list, &toc);
+        assert_eq!(list.tracks[0].seconds, Some(61));
+        assert_eq!(list.tracks[1].seconds, Some(198));
+        assert_eq!(list.tracks[1].length().as_deref(), Some("3:18"));
+    }
+}
@@ -0,0 +1,420 @@
+//! Headless batch processing for the `ode-artwork batch` and `watch`
+//! commands: every disc image under a folder is identified, searched and,
+//! with [`BatchOptions::auto_pick`], given the best-scoring cover, with no
+//! window.
+//!
+//! It's the processing queue's pipeline (`gui::queue` in the app) plus the
+//! redump database lookup the window does when a disc is opened, so discs
+//! the database knows are searched by their redump title. Without
+//! `auto_pick` nothing is saved: each disc reports the result it would pick.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::api::{ArtworkSearchQuery, SearchConfig};
+use crate::config::ExportConfig;
+use crate::db::manager::DbError;
+use crate::db::DatabaseManager;
+use crate::disc::scan::find_disc_images;
+use crate::disc::{parse_filename, DiscInfo, DiscReader};
+use crate::export::{
+    claim_named_output_path, export_artwork_from_url, generate_named_output_path, ArtworkRole,
+    DiscSummary, ExportSettings, LaunchBoxGame, OutputNaming, Provenance,
+};
+use crate::net::CancelToken;
+use crate::search::{pick, ImageResult};
+
+/// Workers when `--jobs` isn't given. Kept low so the image search isn't
+/// rate-limited.
+pub const DEFAULT_JOBS: usize = 2;
+
+/// How a batch runs.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchOptions {
+    /// Save the best result of each disc. Off, the pick is only reported.
+    pub auto_pick: bool,
+    /// Leave discs that already have a cover alone.
+    pub skip_existing: bool,
+    /// Discs processed at once
+    pub jobs: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            auto_pick: false,
+            skip_existing: false,
+            jobs: DEFAULT_JOBS,
+        }
+    }
+}
+
+/// What happened to one disc.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// The cover was saved to this path.
+    Fetched(String),
+    /// Without `auto_pick`: the result that would have been saved.
+    Found {
+        url: String,
+        score: f32,
+    },
+    /// Left alone, and why.
+    Skipped(String),
+    Failed(String),
+}
+
+/// One processed disc.
+#[derive(Debug, Clone)]
+pub struct DiscReport {
+    pub path: PathBuf,
+    /// Title the disc was searched by, once it was read
+    pub title: Option<String>,
+    pub outcome: Outcome,
+}
+
+/// Process every disc image under `root`, `on_done` being called with each
+/// report as its disc finishes (from the worker thread). Returns the reports
+/// in path order.
+pub fn run(
+    root: &Path,
+    options: BatchOptions,
+    on_done: &(dyn Fn(&DiscReport) + Sync),
+) -> Vec<DiscReport> {
+    run_discs(&find_disc_images(root), options, on_done)
+}
+
+/// [`run`] for a list of disc images, such as the new ones a watched
+/// folder gained. Returns the reports in the order of `discs`. A disc whose
+/// processing panicked is reported as failed once the others are done.
+pub fn run_discs(
+    discs: &[PathBuf],
+    options: BatchOptions,
+    on_done: &(dyn Fn(&DiscReport) + Sync),
+) -> Vec<DiscReport> {
+    let search_config = SearchConfig::default();
+    let export_config = crate::config::get_config().export.clone();
+
+    let results = crate::pool::map_with(
+        discs,
+        options.jobs,
+        // One connection per worker; rusqlite's isn't Sync
+        open_database,
+        |conn, path| {
+            let report = process_disc(path, options, conn.as_ref(), &search_config, &export_config);
+            on_done(&report);
+            report
+        },
+    );
+    discs
+        .iter()
+        .zip(results)
+        .map(|(path, result)| {
+            result.unwrap_or_else(|e| {
+                let report = DiscReport {
+                    path: path.clone(),
+                    title: None,
+                    outcome: Outcome::Failed(e.to_string()),
+                };
+                on_done(&report);
+                report
+            })
+        })
+        .collect()
+}
+
+/// The redump database, or `None` (searching by the disc's own title) when
+/// it isn't downloaded or won't open.
+fn open_database() -> Option<Connection> {
+    match DatabaseManager::new().and_then(|m| m.open()) {
+        Ok(conn) => Some(conn),
+        Err(DbError::NotInstalled) => {
+            log::debug!("Redump lookup skipped: DB not yet downloaded");
+            None
+        }
+        Err(e) => {
+            log::warn!("Redump lookup skipped: {e}");
+            None
+        }
+    }
+}
+
+fn process_disc(
+    path: &Path,
+    options: BatchOptions,
+    conn: Option<&Connection>,
+    search_config: &SearchConfig,
+    export_config: &ExportConfig,
+) -> DiscReport {
+    let naming = OutputNaming::from_config(export_config);
+    let mut report = DiscReport {
+        path: path.to_path_buf(),
+        title: None,
+        outcome: Outcome::Skipped(String::new()),
+    };
+    // Checked before reading the disc, which is the slow part
+    if options.skip_existing {
+        let output = generate_named_output_path(path, &naming, ArtworkRole::Front);
+        if Path::new(&output).exists() {
+            report.outcome = Outcome::Skipped(format!("already has {}", output));
+            return report;
+        }
+    }
+    report.outcome = match identify_and_search(path, conn, search_config) {
+        Ok((info, title, results)) => {
+            report.title = Some(title.clone());
+            match pick_and_save(path, &info, &title, &results, options, export_config) {
+                Ok(outcome) => outcome,
+                Err(e) => Outcome::Failed(e),
+            }
+        }
+        Err(e) => Outcome::Failed(e),
+    };
+    report
+}
+
+/// Read the disc, look it up in the redump database and search for its
+/// artwork, the local cover packs first. Returns the disc, the title it was
+/// searched by and the results.
+fn identify_and_search(
+    path: &Path,
+    conn: Option<&Connection>,
+    search_config: &SearchConfig,
+) -> Result<(DiscInfo, String, Vec<ImageResult>), String> {
+    let mut info = DiscReader::read(path).map_err(|e| format!("Failed to read disc: {}", e))?;
+    if let Some(conn) = conn {
+        match crate::db::cascade_from_disc(conn, &info) {
+            Ok(matches) => info.redump_matches = Some(matches),
+            Err(e) => log::warn!("Redump cascade failed: {e}"),
+        }
+    }
+    let query = ArtworkSearchQuery::from_disc_info_with_config(&info, search_config);
+    let mut results = crate::packs::search_local(&query.title, 5);
+    if results.is_empty() {
+        results = crate::search::search_images_cancellable(
+            &query.build_query(),
+            20,
+            search_config.user_agent.as_deref(),
+            &CancelToken::new(),
+        )?;
+    }
+    if results.is_empty() {
+        return Err("No artwork found".to_string());
+    }
+    Ok((info, query.title, results))
+}
+
+fn pick_and_save(
+    path: &Path,
+    info: &DiscInfo,
+    title: &str,
+    results: &[ImageResult],
+    options: BatchOptions,
+    export_config: &ExportConfig,
+) -> Result<Outcome, String> {
+    if !options.auto_pick {
+        let (best, score) =
+            pick::best(results, title).ok_or_else(|| "No artwork found".to_string())?;
+        return Ok(Outcome::Found {
+            url: results[best].image_url.clone(),
+            score,
+        });
+    }
+    let (best, _) = pick::auto_pick(results, title)
+        .ok_or_else(|| "No result scored high enough to pick".to_string())?;
+    let pick = &results[best];
+
+    let naming = OutputNaming::from_config(export_config);
+    let output = claim_named_output_path(path, &naming, ArtworkRole::Front)?;
+    let settings = ExportSettings {
+        provenance: Some(Provenance::new(
+            Some(title.to_string()),
+            Some(pick.image_url.clone()),
+            None,
+        )),
+        disc: Some(DiscSummary {
+            path: path.display().to_string(),
+            title: info.title.clone(),
+            format: Some(info.format.display_name().to_string()),
+            filesystem: Some(info.filesystem.display_name().to_string()),
+            volume_label: info.volume_label.clone(),
+            ..DiscSummary::default()
+        }),
+        ..ExportSettings::from_config(export_config)
+    };
+    let result = export_artwork_from_url(&pick.image_url, &output, &settings)?;
+    match result.upload_error() {
+        Some(e) => Err(format!("Saved {} but {}", result.output_path, e)),
+        None => Ok(Outcome::Fetched(result.output_path)),
+    }
+}
+
+/// One line for a finished disc, e.g. `fetched  Ridge Racer -> /covers/...`.
+pub fn report_line(report: &DiscReport) -> String {
+    let name = report
+        .title
+        .clone()
+        .unwrap_or_else(|| report.path.display().to_string());
+    match &report.outcome {
+        Outcome::Fetched(output) => format!("fetched  {} -> {}", name, output),
+        Outcome::Found { url, score } => format!("found    {} ({:.2}) {}", name, score, url),
+        Outcome::Skipped(reason) => format!("skipped  {}: {}", name, reason),
+        Outcome::Failed(error) => format!("failed   {}: {}", name, error),
+    }
+}
+
+/// The report printed once the batch is done: how many discs were fetched,
+/// found, skipped and failed, then each failed disc with its error.
+pub fn summary(reports: &[DiscReport]) -> String {
+    let count =
+        |matches: fn(&Outcome) -> bool| reports.iter().filter(|r| matches(&r.outcome)).count();
+    let mut out = format!("{} discs\n", reports.len());
+    out += &format!(
+        "  fetched: {}\n",
+        count(|o| matches!(o, Outcome::Fetched(_)))
+    );
+    let found = count(|o| matches!(o, Outcome::Found { .. }));
+    if found > 0 {
+        out += &format!("  found (not saved, no --auto-pick): {}\n", found);
+    }
+    out += &format!(
+        "  skipped: {}\n",
+        count(|o| matches!(o, Outcome::Skipped(_)))
+    );
+    let failed: Vec<&DiscReport> = reports
+        .iter()
+        .filter(|r| matches!(r.outcome, Outcome::Failed(_)))
+        .collect();
+    out += &format!("  failed:  {}\n", failed.len());
+    for report in failed {
+        if let Outcome::Failed(error) = &report.outcome {
+            out += &format!("    {}: {}\n", report.path.display(), error);
+        }
+    }
+    out
+}
+
+/// The discs of a batch that didn't fail, for adding to LaunchBox with
+/// [`crate::export::export_launchbox`]. Each gets its saved cover if it has
+/// one; discs skipped before being read are titled from their file name.
+pub fn launchbox_games(reports: &[DiscReport], export_config: &ExportConfig) -> Vec<LaunchBoxGame> {
+    let naming = OutputNaming::from_config(export_config);
+    reports
+        .iter()
+        .filter(|r| !matches!(r.outcome, Outcome::Failed(_)))
+        .map(|report| {
+            let parsed = parse_filename(&report.path);
+            let cover = PathBuf::from(generate_named_output_path(
+                &report.path,
+                &naming,
+                ArtworkRole::Front,
+            ));
+            LaunchBoxGame {
+                title: report.title.clone().unwrap_or(parsed.title),
+                application_path: report.path.clone(),
+                region: parsed.region,
+                cover: cover.is_file().then_some(cover),
+            }
+        })
+        .collect()
+}
+
+/// Whether the batch went through without a failed disc.
+pub fn all_succeeded(reports: &[DiscReport]) -> bool {
+    !reports
+        .iter()
+        .any(|r| matches!(r.outcome, Outcome::Failed(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(path: &str, title: Option<&str>, outcome: Outcome) -> DiscReport {
+        DiscReport {
+            path: PathBuf::from(path),
+            title: title.map(str::to_string),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn summary_counts_each_outcome() {
+        let reports = vec![
+            report("/d/a.iso", Some("A"), Outcome::Fetched("/d/a.jpg".into())),
+            report("/d/b.iso", Some("B"), Outcome::Fetched("/d/b.jpg".into())),
+            report(
+                "/d/c.iso",
+                None,
+                Outcome::Skipped("already has /d/c.jpg".into()),
+            ),
+            report(
+                "/d/d.iso",
+                None,
+                Outcome::Failed("Failed to read disc: bad".into()),
+            ),
+        ];
+        let text = summary(&reports);
+        assert!(text.starts_with("4 discs\n"));
+        assert!(text.contains("fetched: 2\n"));
+        assert!(text.contains("skipped: 1\n"));
+        assert!(text.contains("failed:  1\n"));
+        assert!(text.contains("/d/d.iso: Failed to read disc: bad"));
+        assert!(!text.contains("found"));
+        assert!(!all_succeeded(&reports));
+    }
+
+    #[test]
+    fn summary_mentions_unsaved_picks() {
+        let reports = vec![report(
+            "/d/a.iso",
+            Some("A"),
+            Outcome::Found {
+                url: "https://example.com/a.jpg".into(),
+                score: 0.8,
+            },
+        )];
+        assert!(summary(&reports).contains("found (not saved, no --auto-pick): 1\n"));
+        assert!(all_succeeded(&reports));
+    }
+
+    #[test]
+    fn report_line_falls_back_to_path() {
+        let line = report_line(&report(
+            "/d/x.cue",
+            None,
+            Outcome::Failed("No artwork found".into()),
+        ));
+        assert_eq!(line, "failed   /d/x.cue: No artwork found");
+        let line = report_line(&report(
+            "/d/x.cue",
+            Some("Ridge Racer"),
+            Outcome::Fetched("/d/x.jpg".into()),
+        ));
+        assert_eq!(line, "fetched  Ridge Racer -> /d/x.jpg");
+    }
+
+    #[test]
+    fn launchbox_games_leave_out_failed_discs() {
+        let reports = vec![
+            report(
+                "/d/a.iso",
+                Some("Ridge Racer"),
+                Outcome::Fetched("/d/a.jpg".into()),
+            ),
+            report(
+                "/d/Myst (USA).cue",
+                None,
+                Outcome::Skipped("already has".into()),
+            ),
+            report("/d/c.iso", None, Outcome::Failed("No artwork found".into())),
+        ];
+        let games = launchbox_games(&reports, &ExportConfig::default());
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].title, "Ridge Racer");
+        assert_eq!(games[1].title, "Myst");
+        assert_eq!(games[1].region.as_deref(), Some("USA"));
+        assert!(games[1].cover.is_none());
+    }
+}
@@ -23,14 +23,14 @@ use std::process::ExitCode;
 
 use std::sync::{Arc, Mutex};
 
-use ode_artwork_downloader::api::{search_by_discid, MusicBrainzResult};
-use ode_artwork_downloader::config::get_config;
-use ode_artwork_downloader::db::{
+use ode_artwork_core::api::{search_by_discid, MusicBrainzResult};
+use ode_artwork_core::config::get_config;
+use ode_artwork_core::db::{
     cascade, fuzzy_from_disc, CascadeInputs, DatabaseManager, FuzzyCandidate, RedumpMatch,
     ScoreSource,
 };
-use ode_artwork_downloader::disc::hasher::{hash_data_track, HashProgress};
-use ode_artwork_downloader::disc::{detect_sector_layout, DiscFormat, DiscReader};
+use ode_artwork_core::disc::hasher::{hash_data_track, HashProgress};
+use ode_artwork_core::disc::{detect_sector_layout, DiscFormat, DiscReader};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, PartialEq)]
@@ -291,7 +291,7 @@ fn write_csv(w: &mut dyn Write, records: &[Record]) -> std::io::Result<()> {
 
 /// One queue entry per file: the chosen best match plus any alternates the
 /// user can swap to during bulk processing. `has_existing_art` is set when a
-/// sidecar image (same dir, same basename, .jpg/.jpeg/.png) is already on disk.
+/// sidecar image (same dir, same basename, .jpg/.jpeg/.png/.bmp) is already on disk.
 #[derive(Serialize, Deserialize)]
 struct QueueItem {
     file: String,
@@ -305,7 +305,7 @@ struct QueueItem {
 fn has_sidecar_art(disc_path: &std::path::Path) -> bool {
     let Some(stem) = disc_path.file_stem() else { return false; };
     let Some(dir) = disc_path.parent() else { return false; };
-    for ext in ["jpg", "jpeg", "png"] {
+    for ext in ["jpg", "jpeg", "png", "bmp"] {
         let candidate = dir.join(format!("{}.{}", stem.to_string_lossy(), ext));
         if candidate.is_file() {
             return true;
@@ -0,0 +1,252 @@
+//! Command-line front end for working without the window.
+//!
+//! Examples:
+//!   ode-artwork batch /discs
+//!   ode-artwork batch /discs --auto-pick --skip-existing --jobs 4
+//!   ode-artwork watch /downloads/discs
+//!
+//! `batch <dir>` goes through every disc image under `dir` (recursively):
+//! each is read, looked up in the redump database if it's downloaded, and
+//! searched for artwork, local cover packs first. A line is printed as each
+//! disc finishes and a summary of fetched/skipped/failed discs at the end.
+//!
+//! Flags:
+//!   --auto-pick      Save the best-scoring result as the disc's cover.
+//!                    Without it nothing is written; each disc's pick is
+//!                    only reported.
+//!   --skip-existing  Leave discs that already have a cover alone.
+//!   --jobs <N>       Discs processed at once (default: 2).
+//!   --launchbox <dir> --platform <name>
+//!                    Also add the discs to that LaunchBox platform, copying
+//!                    their covers in as "Box - Front" images. Close
+//!                    LaunchBox first; it rewrites its XML while running.
+//!
+//! `watch [<dir>...]` stays running and gives each disc image that turns up
+//! under the folders (the `watch` folders from the settings if none are
+//! given) a cover once its copy has finished, like watch mode in the app.
+//! It always saves, using the same auto-pick threshold as `--auto-pick`;
+//! `--skip-existing`, `--jobs` and `--launchbox` work as for `batch`. Stop it with Ctrl+C.
+//!
+//! Covers are named and placed as set in the app's export settings. The
+//! exit code of `batch` is non-zero if any disc failed.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use ode_artwork_core::batch::{self, BatchOptions, DiscReport, DEFAULT_JOBS};
+use ode_artwork_core::export::export_launchbox;
+use ode_artwork_core::watch::FolderWatch;
+
+const USAGE: &str = "Usage: ode-artwork batch <dir> [--auto-pick] [--skip-existing] [--jobs N]\n\
+                     \x20        [--launchbox <dir> --platform <name>]\n\
+                     \x20      ode-artwork watch [<dir>...] [--skip-existing] [--jobs N]\n\
+                     \x20        [--launchbox <dir> --platform <name>]";
+
+/// How often `watch` checks for folders that have settled.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+struct CommandArgs {
+    folders: Vec<PathBuf>,
+    options: BatchOptions,
+    /// LaunchBox folder and platform to add the discs to
+    launchbox: Option<(PathBuf, String)>,
+}
+
+/// Folders and flags after the command name.
+fn parse_command_args(mut it: impl Iterator<Item = String>) -> Result<CommandArgs, String> {
+    let mut folders = Vec::new();
+    let mut options = BatchOptions::default();
+    let mut launchbox = None;
+    let mut platform = None;
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--auto-pick" => options.auto_pick = true,
+            "--skip-existing" => options.skip_existing = true,
+            "--jobs" | "-j" => {
+                options.jobs = it
+                    .next()
+                    .ok_or("--jobs requires a number")?
+                    .parse()
+                    .map_err(|e| format!("invalid --jobs: {e}"))?;
+                if options.jobs == 0 {
+                    return Err("--jobs must be at least 1".to_string());
+                }
+            }
+            "--launchbox" => {
+                let dir = PathBuf::from(it.next().ok_or("--launchbox requires a folder")?);
+                if !dir.join("Data").is_dir() {
+                    return Err(format!("not a LaunchBox folder: {}", dir.display()));
+                }
+                launchbox = Some(dir);
+            }
+            "--platform" => platform = Some(it.next().ok_or("--platform requires a name")?),
+            "--help" | "-h" => return Err("help".to_string()),
+            flag if flag.starts_with('-') => return Err(format!("unknown flag: {flag}")),
+            _ => folders.push(PathBuf::from(arg)),
+        }
+    }
+    if let Some(folder) = folders.iter().find(|f| !f.is_dir()) {
+        return Err(format!("not a folder: {}", folder.display()));
+    }
+    let launchbox = match (launchbox, platform) {
+        (Some(dir), Some(platform)) => Some((dir, platform)),
+        (None, None) => None,
+        (Some(_), None) => return Err("--launchbox requires --platform".to_string()),
+        (None, Some(_)) => return Err("--platform requires --launchbox".to_string()),
+    };
+    Ok(CommandArgs {
+        folders,
+        options,
+        launchbox,
+    })
+}
+
+fn run_batch(args: CommandArgs) -> ExitCode {
+    let root = match args.folders.as_slice() {
+        [root] => root,
+        [] => {
+            eprintln!("error: batch requires a folder\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        _ => {
+            eprintln!("error: batch takes one folder\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!(
+        "Processing {} ({} job{}{})",
+        root.display(),
+        args.options.jobs,
+        if args.options.jobs == 1 { "" } else { "s" },
+        if args.options.auto_pick {
+            ""
+        } else {
+            ", dry run: pass --auto-pick to save covers"
+        }
+    );
+    let finished = AtomicUsize::new(0);
+    let reports = batch::run(root, args.options, &|report| {
+        let n = finished.fetch_add(1, Ordering::Relaxed) + 1;
+        println!("[{n}] {}", batch::report_line(report));
+    });
+    if reports.is_empty() {
+        eprintln!("No disc images found under {}", root.display());
+        return ExitCode::SUCCESS;
+    }
+    println!();
+    print!("{}", batch::summary(&reports));
+    if let Some((dir, platform)) = &args.launchbox {
+        if !add_to_launchbox(dir, platform, &reports) {
+            return ExitCode::FAILURE;
+        }
+    }
+    if batch::all_succeeded(&reports) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Watch the folders until killed, giving each new disc image a cover.
+fn run_watch(args: CommandArgs) -> ExitCode {
+    let folders = if args.folders.is_empty() {
+        let configured = ode_artwork_core::config::get_config().watch.folders.clone();
+        configured.into_iter().map(PathBuf::from).collect()
+    } else {
+        args.folders
+    };
+    if folders.is_empty() {
+        eprintln!(
+            "error: no folders to watch (pass some, or set watch folders in the settings)\n{USAGE}"
+        );
+        return ExitCode::FAILURE;
+    }
+    let mut watch = match FolderWatch::start_with(&folders, || {}) {
+        Ok(watch) => watch,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let options = BatchOptions {
+        auto_pick: true,
+        ..args.options
+    };
+    for folder in &folders {
+        eprintln!("Watching {}", folder.display());
+    }
+    eprintln!("New disc images get artwork once copied. Press Ctrl+C to stop.");
+
+    let finished = AtomicUsize::new(0);
+    loop {
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+        let new = watch.poll();
+        if new.is_empty() {
+            continue;
+        }
+        eprintln!("Found {} new disc image(s)", new.len());
+        let reports = batch::run_discs(&new, options, &|report| {
+            let n = finished.fetch_add(1, Ordering::Relaxed) + 1;
+            println!("[{n}] {}", batch::report_line(report));
+        });
+        print!("{}", batch::summary(&reports));
+        if let Some((dir, platform)) = &args.launchbox {
+            add_to_launchbox(dir, platform, &reports);
+        }
+    }
+}
+
+/// Add the batch's discs to a LaunchBox platform. Returns whether it worked.
+fn add_to_launchbox(dir: &Path, platform: &str, reports: &[DiscReport]) -> bool {
+    let export_config = &ode_artwork_core::config::get_config().export;
+    let games = batch::launchbox_games(reports, export_config);
+    match export_launchbox(dir, platform, &games) {
+        Ok(summary) => {
+            println!(
+                "LaunchBox: {} added, {} already listed, {} cover(s) copied ({})",
+                summary.added,
+                summary.already_listed,
+                summary.covers_copied,
+                summary.xml_path.display()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("error: LaunchBox export failed: {e}");
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let run: fn(CommandArgs) -> ExitCode = match args.next().as_deref() {
+        Some("batch") => run_batch,
+        Some("watch") => run_watch,
+        Some("--help" | "-h" | "help") => {
+            eprintln!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(other) => {
+            eprintln!("error: unknown command: {other}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match parse_command_args(args) {
+        Ok(args) => run(args),
+        Err(e) if e == "help" => {
+            eprintln!("{USAGE}\n--jobs defaults to {DEFAULT_JOBS}.");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,1294 @@
+//! Application configuration
+//!
+//! Handles loading and managing configuration from config.json and secrets.json
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Global application config. Loaded on first use and replaced by
+/// `set_config`; each one is leaked so `get_config` can keep handing out
+/// `&'static` references (a replacement only happens on a Settings save).
+static APP_CONFIG: RwLock<Option<&'static AppConfig>> = RwLock::new(None);
+
+/// Global secrets
+static APP_SECRETS: OnceLock<AppSecrets> = OnceLock::new();
+
+/// Config files an older, portable build kept next to the executable.
+/// Copied to `config_dir` on first run by `migrate_local_config`.
+const LOCAL_CONFIG_FILES: [&str; 2] = ["config.json", "secrets.json"];
+
+/// Top-level sections of `config.json`. A local file needs at least one to
+/// be taken for an ODE config rather than some other program's.
+const CONFIG_SECTIONS: [&str; 9] = [
+    "appearance",
+    "update_check",
+    "discogs",
+    "fuzzy_match",
+    "network",
+    "export",
+    "watch",
+    "usbode",
+    "search",
+];
+
+fn project_dirs() -> Result<ProjectDirs, String> {
+    ProjectDirs::from("", "", "ODE-artwork-downloader")
+        .ok_or_else(|| "could not resolve a per-user directory".to_string())
+}
+
+fn create_dir(dir: PathBuf) -> Result<PathBuf, String> {
+    fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {e}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Per-user config directory (e.g. ~/Library/Application Support/ODE-artwork-downloader
+/// on macOS). The directory is created on demand so callers can write into it
+/// immediately.
+pub fn config_dir() -> Result<PathBuf, String> {
+    create_dir(project_dirs()?.config_dir().to_path_buf())
+}
+
+/// Per-user data directory for things worth keeping: the lookup database
+/// (`databases/`), imported cover packs (`packs/`) and the log file
+/// (`logs/`). `%APPDATA%` on Windows, `~/.local/share` on Linux.
+/// Created on demand.
+pub fn data_dir() -> Result<PathBuf, String> {
+    create_dir(project_dirs()?.data_dir().to_path_buf())
+}
+
+/// Per-user cache directory for things that can be fetched again, like
+/// search result thumbnails (`thumbnails/`). `%LOCALAPPDATA%` on Windows,
+/// `~/.cache` on Linux, `~/Library/Caches` on macOS. Created on demand.
+pub fn cache_dir() -> Result<PathBuf, String> {
+    create_dir(project_dirs()?.cache_dir().to_path_buf())
+}
+
+/// Copy `config.json` and `secrets.json` left next to the executable by an
+/// older build into `config_dir`, unless there's one there already. The
+/// working directory isn't searched, since it may hold some other
+/// program's `config.json`. The originals are left alone, since the old
+/// build may still be in use. Runs once per start, before loading.
+fn migrate_local_config() {
+    static MIGRATED: OnceLock<()> = OnceLock::new();
+    MIGRATED.get_or_init(|| {
+        let Ok(dir) = config_dir() else {
+            return;
+        };
+        let Some(exe_dir) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        else {
+            return;
+        };
+        for name in LOCAL_CONFIG_FILES {
+            let target = dir.join(name);
+            let local = exe_dir.join(name);
+            if target.exists() || !local.is_file() {
+                continue;
+            }
+            if !is_ode_config_file(name, &local) {
+                log::debug!("Not migrating {}: not an ODE {name}", local.display());
+                continue;
+            }
+            match fs::copy(&local, &target) {
+                Ok(_) => log::info!("Copied {} to {}", local.display(), target.display()),
+                Err(e) => log::warn!(
+                    "Could not copy {} to {}: {e}",
+                    local.display(),
+                    target.display()
+                ),
+            }
+        }
+    });
+}
+
+/// Whether `path` parses as the ODE file `name` and has one of its sections,
+/// so an unrelated JSON file of the same name isn't copied.
+fn is_ode_config_file(name: &str, path: &Path) -> bool {
+    let Some(serde_json::Value::Object(map)) = fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+    else {
+        return false;
+    };
+    let sections: &[&str] = if name == "secrets.json" {
+        &["discogs"]
+    } else {
+        &CONFIG_SECTIONS
+    };
+    if !sections.iter().any(|s| map.contains_key(*s)) {
+        return false;
+    }
+    let value = serde_json::Value::Object(map);
+    if name == "secrets.json" {
+        serde_json::from_value::<AppSecrets>(value).is_ok()
+    } else {
+        serde_json::from_value::<AppConfig>(value).is_ok()
+    }
+}
+
+/// Canonical location of `config.json` under the per-user config directory.
+pub fn config_file_path() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("config.json"))
+}
+
+/// Canonical location of `secrets.json` under the per-user config directory.
+pub fn secrets_file_path() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("secrets.json"))
+}
+
+/// The raw `config.json` object, or an empty one if the file doesn't exist.
+pub fn load_config_json() -> Result<serde_json::Value, String> {
+    let path = config_file_path()?;
+    let json: serde_json::Value = match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s)
+            .map_err(|e| format!("Failed to parse config.json: {e}"))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(format!("Failed to read config.json: {e}")),
+    };
+    Ok(if json.is_object() { json } else { serde_json::json!({}) })
+}
+
+/// Write `contents` to a temporary file next to `path` and rename it over
+/// `path`, so a crash or full disk mid-write can't leave a truncated
+/// JSON file (`config.json`, the central folder's artwork index) behind.
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, contents)
+        .map_err(|e| format!("Failed to write {}: {e}", temp.display()))?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!("Failed to write {}: {e}", path.display())
+    })
+}
+
+/// Get the global application config
+pub fn get_config() -> &'static AppConfig {
+    if let Some(config) = *APP_CONFIG.read().unwrap_or_else(|e| e.into_inner()) {
+        return config;
+    }
+    let mut slot = APP_CONFIG.write().unwrap_or_else(|e| e.into_inner());
+    *slot.get_or_insert_with(|| Box::leak(Box::new(AppConfig::load())))
+}
+
+/// Make `config` what `get_config` returns from now on, so saved settings
+/// apply without a restart. Doesn't write `config.json`.
+pub fn set_config(config: AppConfig) {
+    *APP_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::leak(Box::new(config)));
+}
+
+/// Change one setting outside the Settings window (the theme toggle, the
+/// content type picker, a captured user agent): `change` is applied to a
+/// copy of the current config, which is saved and made current.
+pub fn update_config(change: impl FnOnce(&mut AppConfig)) -> Result<(), String> {
+    let mut config = get_config().clone();
+    change(&mut config);
+    config.save()?;
+    set_config(config);
+    Ok(())
+}
+
+/// Range of `appearance.ui_scale`
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+
+/// Accepted values of `log_level` and `log_file_level`
+pub const LOG_LEVELS: [&str; 6] = ["error", "warn", "info", "debug", "trace", "off"];
+
+/// Accepted values of `search.content_type` (see `api::ContentType`)
+pub const CONTENT_TYPES: [&str; 6] =
+    ["any", "games", "apps_utilities", "apps", "audio_cds", "audio"];
+
+/// Get the global application secrets
+pub fn get_secrets() -> &'static AppSecrets {
+    APP_SECRETS.get_or_init(AppSecrets::load)
+}
+
+/// Root application configuration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub appearance: AppearanceConfig,
+    #[serde(default)]
+    pub update_check: UpdateCheckConfig,
+    #[serde(default)]
+    pub discogs: DiscogsConfig,
+    /// UI log verbosity. One of: error, warn, info, debug, trace, off.
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Log file verbosity, same values as `log_level`. The file is
+    /// `logs/ode-artwork.log` in the data directory.
+    #[serde(default = "default_log_level")]
+    pub log_file_level: String,
+    /// Window language: a code from `i18n::LANGUAGES`, or "system".
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default)]
+    pub fuzzy_match: FuzzyMatchConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
+    #[serde(default)]
+    pub usbode: UsbodeConfig,
+    #[serde(default)]
+    pub search: SearchSettings,
+    /// Discs "Scan Folder..." identifies at once; 0 is one per CPU core.
+    #[serde(default)]
+    pub scan_workers: usize,
+    /// Megabytes of decompressed CHD hunks kept for every open CHD to
+    /// share (`disc::hunk_cache`); 0 turns the cache off.
+    #[serde(default = "default_chd_cache_mb")]
+    pub chd_cache_mb: u32,
+}
+
+/// How searches are built; `api::SearchConfig` is made from this.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SearchSettings {
+    /// Sites whose results are dropped
+    #[serde(default = "default_exclusion_sites")]
+    pub exclusion_sites: Vec<String>,
+    /// Platform keywords excluded from queries
+    #[serde(default = "default_exclusion_platforms")]
+    pub exclusion_platforms: Vec<String>,
+    /// Keywords added to CD searches
+    #[serde(default = "default_cd_keywords")]
+    pub cd_keywords: Vec<String>,
+    /// Keywords added to DVD searches
+    #[serde(default = "default_dvd_keywords")]
+    pub dvd_keywords: Vec<String>,
+    /// Publishers stripped from game titles
+    #[serde(default)]
+    pub known_publishers: Vec<String>,
+    /// Which `known_sites` list searches prefer. One of `CONTENT_TYPES`.
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+    #[serde(default)]
+    pub known_sites: KnownSites,
+    /// User agent sent with searches, as captured from a browser; `null`
+    /// uses the built-in one
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Preview and save the best-scoring result as soon as a search finishes
+    #[serde(default)]
+    pub auto_pick: bool,
+}
+
+/// Sites searched first for each content type.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct KnownSites {
+    #[serde(default)]
+    pub games: Vec<String>,
+    #[serde(default)]
+    pub apps_utilities: Vec<String>,
+    #[serde(default)]
+    pub audio_cds: Vec<String>,
+}
+
+fn default_exclusion_sites() -> Vec<String> {
+    vec!["ebay.com".to_string()]
+}
+
+fn default_exclusion_platforms() -> Vec<String> {
+    vec![
+        "playstation".to_string(),
+        "xbox".to_string(),
+        "nintendo".to_string(),
+    ]
+}
+
+fn default_cd_keywords() -> Vec<String> {
+    vec!["CD".to_string(), "jewel case".to_string()]
+}
+
+fn default_dvd_keywords() -> Vec<String> {
+    vec!["DVD".to_string()]
+}
+
+fn default_content_type() -> String {
+    "any".to_string()
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            exclusion_sites: default_exclusion_sites(),
+            exclusion_platforms: default_exclusion_platforms(),
+            cd_keywords: default_cd_keywords(),
+            dvd_keywords: default_dvd_keywords(),
+            known_publishers: Vec::new(),
+            content_type: default_content_type(),
+            known_sites: KnownSites::default(),
+            user_agent: None,
+            auto_pick: false,
+        }
+    }
+}
+
+impl SearchSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !CONTENT_TYPES.contains(&self.content_type.to_lowercase().as_str()) {
+            return Err(format!(
+                "Unknown search content type {} (use any, games, apps_utilities or audio_cds)",
+                self.content_type
+            ));
+        }
+        if let Some(ua) = &self.user_agent {
+            if ua.trim().is_empty() || ua.contains(['\r', '\n']) {
+                return Err("Search user agent must be a single non-empty line".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Look of the window.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct AppearanceConfig {
+    #[serde(default)]
+    pub theme: ThemeMode,
+    /// `#RRGGBB` for selections and links; `null` keeps egui's blue
+    #[serde(default)]
+    pub accent_color: Option<String>,
+    /// Size of text and controls on top of the display's own scaling (so
+    /// pixels per point = display scale × this); `null` is 1.0
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
+}
+
+impl AppearanceConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(scale) = self.ui_scale {
+            if !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&scale) {
+                return Err(format!(
+                    "UI scale must be between {MIN_UI_SCALE} and {MAX_UI_SCALE}"
+                ));
+            }
+        }
+        match &self.accent_color {
+            Some(color) if crate::export::parse_hex_color(color).is_none() => {
+                Err(format!("Accent color {color} isn't a #RRGGBB color"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Dark or light visuals.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    /// Follow the operating system
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::System, ThemeMode::Dark, ThemeMode::Light];
+
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThemeMode::System => "System",
+            ThemeMode::Dark => "Dark",
+            ThemeMode::Light => "Light",
+        }
+    }
+}
+
+/// Output settings for saved artwork.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ExportConfig {
+    /// Edge length in pixels of the main (square) output image.
+    #[serde(default = "default_export_target_size")]
+    pub target_size: u32,
+    /// Additional sizes written next to the main image as `<name>_<size>.jpg`,
+    /// for front-ends that want a larger copy. Empty by default.
+    #[serde(default)]
+    pub extra_sizes: Vec<u32>,
+    /// Output file format. JPEG is what USBODE expects; PNG/BMP are for
+    /// front-ends that prefer lossless files.
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// JPEG quality, 10-100. Ignored for PNG/BMP.
+    #[serde(default = "default_export_quality")]
+    pub quality: u8,
+    /// Output filename template, e.g. `{stem}` (USBODE) or
+    /// `covers/{title} ({region})`. See `export::naming` for the fields.
+    #[serde(default = "default_name_template")]
+    pub name_template: String,
+    /// Write every cover into this one folder instead of next to each disc
+    /// image, for ODEs that read art from a single directory. `null` keeps
+    /// art beside the discs.
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// Also copy every saved cover to this network location: an
+    /// `smb://`, `ftp://` or `sftp://` URL (see `export::remote`). `null`
+    /// keeps covers local only.
+    #[serde(default)]
+    pub remote_target: Option<String>,
+    /// Also write `<name>.artwork.json` next to each cover, recording the
+    /// source URL, crop, export settings and disc details so the export can
+    /// be reproduced or indexed later.
+    #[serde(default)]
+    pub write_sidecar: bool,
+    /// Also store the front cover inside CHD images as a metadata entry,
+    /// so it travels with the file between SD cards.
+    #[serde(default)]
+    pub embed_in_chd: bool,
+    /// Also copy each cover onto a mounted USBODE SD card that has the
+    /// disc, next to the card's copy (see `usbode::card`).
+    #[serde(default)]
+    pub copy_to_sd_card: bool,
+    /// Also write each cover as a PNG RetroArch thumbnail under this
+    /// `thumbnails` folder (see `export::retroarch`). `null` turns it off.
+    #[serde(default)]
+    pub retroarch_thumbnails: Option<String>,
+    /// RetroArch playlist the thumbnails are for, e.g. `Sony - PlayStation`.
+    #[serde(default)]
+    pub retroarch_system: String,
+    /// Optional cap on the main JPEG's file size, in KiB. When set, the
+    /// encoder lowers quality until the file fits. `null` means no limit.
+    #[serde(default)]
+    pub max_file_size_kb: Option<u32>,
+    /// Trim scanner margins / letterbox bars and bias the square crop toward
+    /// the cover's detail. `false` restores the plain center-crop.
+    #[serde(default = "default_smart_crop")]
+    pub smart_crop: bool,
+    /// Move a file a save is about to overwrite (the cover, extra sizes,
+    /// sidecar, SD card copy and RetroArch thumbnail) into `.artwork-backup/`
+    /// beside it, so the save can be undone.
+    #[serde(default = "default_backup_existing")]
+    pub backup_existing: bool,
+    /// How a non-square image becomes square: crop it, or pad it onto a
+    /// square canvas so nothing (e.g. a DVD cover's title) is cut off.
+    #[serde(default)]
+    pub fit: FitMode,
+    /// Canvas fill used when `fit` is `pad`.
+    #[serde(default)]
+    pub pad_fill: PadFill,
+    /// Canvas colour for `pad_fill: "color"`, as `#RRGGBB`.
+    #[serde(default = "default_pad_color")]
+    pub pad_color: String,
+    /// Unsharp-mask strength applied after resizing, in percent. 0 disables it.
+    #[serde(default)]
+    pub sharpen: u32,
+    /// How sources smaller than `target_size` are enlarged.
+    #[serde(default)]
+    pub upscale: UpscaleMode,
+    /// Disc/case frame composited around the finished artwork.
+    #[serde(default)]
+    pub template: ArtTemplate,
+    /// Stamp a disc-number badge on discs 2+ of a multi-disc set (and a
+    /// role badge on "Install"/"Bonus"-style discs).
+    #[serde(default = "default_disc_badge")]
+    pub disc_badge: bool,
+    /// Corner the badge is drawn in.
+    #[serde(default)]
+    pub badge_position: BadgePosition,
+    /// Badge shape: a "Disc N" pill or a round number.
+    #[serde(default)]
+    pub badge_style: BadgeStyle,
+}
+
+/// Corner of the artwork the disc badge is drawn in.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgePosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl BadgePosition {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BadgePosition::TopLeft => "Top left",
+            BadgePosition::TopRight => "Top right",
+            BadgePosition::BottomLeft => "Bottom left",
+            BadgePosition::BottomRight => "Bottom right",
+        }
+    }
+}
+
+/// Look of the disc badge.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BadgeStyle {
+    /// Rounded pill reading "Disc N" or "Disc N/M".
+    #[default]
+    Pill,
+    /// Round badge with just the number. Role markers fall back to the pill.
+    Circle,
+}
+
+impl BadgeStyle {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BadgeStyle::Pill => "\"Disc N\" label",
+            BadgeStyle::Circle => "Number only",
+        }
+    }
+}
+
+/// Frame drawn around the exported artwork, for front-ends that show
+/// disc- or case-style art.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtTemplate {
+    /// Flat cover, no frame.
+    #[default]
+    None,
+    /// Round disc with a clear hub and center hole.
+    CdLabel,
+    /// Cover inside a jewel case with a hinge spine.
+    JewelCase,
+}
+
+impl ArtTemplate {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ArtTemplate::None => "Cover",
+            ArtTemplate::CdLabel => "CD label",
+            ArtTemplate::JewelCase => "Jewel case",
+        }
+    }
+}
+
+/// Enlargement method for small source images.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpscaleMode {
+    /// Plain Lanczos stretch.
+    #[default]
+    Lanczos,
+    /// Edge-preserving Scale2x passes, then Lanczos to the exact size.
+    Scale2x,
+}
+
+impl UpscaleMode {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            UpscaleMode::Lanczos => "Lanczos",
+            UpscaleMode::Scale2x => "Scale2x (sharp edges)",
+        }
+    }
+}
+
+/// How non-square artwork is made square.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FitMode {
+    #[default]
+    Crop,
+    Pad,
+}
+
+impl FitMode {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FitMode::Crop => "Crop",
+            FitMode::Pad => "Pad",
+        }
+    }
+}
+
+/// Background behind padded artwork.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PadFill {
+    /// Solid `pad_color`.
+    #[default]
+    Color,
+    /// A blurred, zoomed copy of the artwork itself.
+    Blur,
+}
+
+impl PadFill {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PadFill::Color => "Color",
+            PadFill::Blur => "Blurred",
+        }
+    }
+}
+
+/// File format for exported artwork.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Jpeg,
+    Png,
+    Bmp,
+}
+
+impl OutputFormat {
+    /// File extension (without the dot) for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "jpg",
+            OutputFormat::Png => "png",
+            OutputFormat::Bmp => "bmp",
+        }
+    }
+
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "JPEG",
+            OutputFormat::Png => "PNG",
+            OutputFormat::Bmp => "BMP",
+        }
+    }
+}
+
+fn default_export_target_size() -> u32 {
+    240
+}
+
+fn default_export_quality() -> u8 {
+    90
+}
+
+fn default_smart_crop() -> bool {
+    true
+}
+
+fn default_backup_existing() -> bool {
+    true
+}
+
+fn default_name_template() -> String {
+    crate::export::DEFAULT_NAME_TEMPLATE.to_string()
+}
+
+fn default_disc_badge() -> bool {
+    true
+}
+
+fn default_pad_color() -> String {
+    "#000000".to_string()
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            target_size: default_export_target_size(),
+            extra_sizes: Vec::new(),
+            format: OutputFormat::default(),
+            quality: default_export_quality(),
+            name_template: default_name_template(),
+            output_dir: None,
+            remote_target: None,
+            write_sidecar: false,
+            embed_in_chd: false,
+            copy_to_sd_card: false,
+            retroarch_thumbnails: None,
+            retroarch_system: String::new(),
+            max_file_size_kb: None,
+            smart_crop: default_smart_crop(),
+            backup_existing: default_backup_existing(),
+            fit: FitMode::default(),
+            pad_fill: PadFill::default(),
+            pad_color: default_pad_color(),
+            sharpen: 0,
+            upscale: UpscaleMode::default(),
+            template: ArtTemplate::default(),
+            disc_badge: default_disc_badge(),
+            badge_position: BadgePosition::default(),
+            badge_style: BadgeStyle::default(),
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Check values a hand-edited `config.json` (or the Settings window) could
+    /// get wrong. Out-of-range sizes are clamped on export anyway; this says
+    /// so up front instead.
+    pub fn validate(&self) -> Result<(), String> {
+        use crate::export::{MAX_SHARPEN, MAX_TARGET_SIZE, MIN_JPEG_QUALITY, MIN_TARGET_SIZE};
+        let sizes = MIN_TARGET_SIZE..=MAX_TARGET_SIZE;
+        if let Some(size) = std::iter::once(&self.target_size)
+            .chain(&self.extra_sizes)
+            .find(|s| !sizes.contains(s))
+        {
+            return Err(format!(
+                "Output size {size} px is outside {MIN_TARGET_SIZE}-{MAX_TARGET_SIZE} px"
+            ));
+        }
+        if !(MIN_JPEG_QUALITY..=100).contains(&self.quality) {
+            return Err(format!("JPEG quality must be {MIN_JPEG_QUALITY}-100"));
+        }
+        crate::export::validate_name_template(&self.name_template)
+            .map_err(|e| format!("File name template: {e}"))?;
+        if let Some(dir) = self.output_dir.as_deref().filter(|d| !d.trim().is_empty()) {
+            if !Path::new(dir).is_dir() {
+                return Err(format!("Artwork folder {dir} doesn't exist"));
+            }
+        }
+        if let Some(url) = self.remote_target.as_deref().filter(|u| !u.trim().is_empty()) {
+            crate::export::RemoteTarget::parse(url)?;
+        }
+        if let Some(dir) = self.retroarch_thumbnails.as_deref().filter(|d| !d.trim().is_empty()) {
+            if !Path::new(dir).is_dir() {
+                return Err(format!("RetroArch thumbnails folder {dir} doesn't exist"));
+            }
+            if self.retroarch_system.trim().is_empty() {
+                return Err(
+                    "RetroArch thumbnails need a playlist name, e.g. \"Sony - PlayStation\""
+                        .to_string(),
+                );
+            }
+        }
+        if crate::export::parse_hex_color(&self.pad_color).is_none() {
+            return Err(format!("Pad color {} isn't a #RRGGBB color", self.pad_color));
+        }
+        if self.sharpen > MAX_SHARPEN {
+            return Err(format!("Sharpen must be 0-{MAX_SHARPEN}%"));
+        }
+        Ok(())
+    }
+}
+
+/// HTTP timeouts and retry policy shared by image search and downloads.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NetworkConfig {
+    /// Time allowed to establish a TCP/TLS connection, in seconds.
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Total time for search/API requests and previews, in seconds.
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Total time for full-size image downloads during export and for the
+    /// lookup database download, in seconds.
+    #[serde(default = "default_download_timeout_secs")]
+    pub download_timeout_secs: u64,
+    /// Extra attempts after a failed request (0 disables retrying). Only
+    /// connection errors, timeouts, 429 and 5xx responses are retried.
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// Delay before the first retry, in milliseconds. Doubles per attempt.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Maximum image downloads in flight at once across the whole app.
+    /// Further downloads wait in a queue for a free slot.
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// Extra headers/cookies sent when downloading from specific hosts, for
+    /// sites that refuse hotlinked full-size images.
+    #[serde(default)]
+    pub site_rules: Vec<SiteRule>,
+}
+
+/// Per-domain download tweaks. `domain` matches the host exactly or any of
+/// its subdomains (`example.com` covers `img.example.com`).
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct SiteRule {
+    pub domain: String,
+    /// Header name → value, e.g. `{"Referer": "https://example.com/"}`.
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// Raw `Cookie` header value, e.g. `"session=abc; agreed=1"`.
+    #[serde(default)]
+    pub cookie: Option<String>,
+}
+
+impl NetworkConfig {
+    /// Check for timeouts and limits that would stop every request.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.connect_timeout_secs == 0
+            || self.request_timeout_secs == 0
+            || self.download_timeout_secs == 0
+        {
+            return Err("Network timeouts must be at least 1 second".to_string());
+        }
+        if self.max_concurrent_downloads == 0 {
+            return Err("Allow at least 1 download at a time".to_string());
+        }
+        if self.site_rules.iter().any(|r| r.domain.trim().is_empty()) {
+            return Err("Every site rule needs a domain".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl SiteRule {
+    /// Whether this rule applies to `host`.
+    pub fn matches_host(&self, host: &str) -> bool {
+        let domain = self.domain.trim().trim_start_matches('.').to_ascii_lowercase();
+        if domain.is_empty() {
+            return false;
+        }
+        let host = host.to_ascii_lowercase();
+        host == domain || host.ends_with(&format!(".{domain}"))
+    }
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+fn default_download_timeout_secs() -> u64 {
+    60
+}
+fn default_retries() -> u32 {
+    2
+}
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+fn default_max_concurrent_downloads() -> usize {
+    4
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: default_connect_timeout_secs(),
+            request_timeout_secs: default_request_timeout_secs(),
+            download_timeout_secs: default_download_timeout_secs(),
+            retries: default_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            max_concurrent_downloads: default_max_concurrent_downloads(),
+            site_rules: Vec::new(),
+        }
+    }
+}
+
+/// Tunable knobs for fuzzy redump matching (see
+/// `docs/fuzzy_match_against_redump.md`). Defaults are deliberately loose for
+/// the initial data-collection phase; tighten once real candidate
+/// distributions are known.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FuzzyMatchConfig {
+    /// Minimum per-source similarity for a candidate to be considered.
+    #[serde(default = "default_fuzzy_source_threshold")]
+    pub source_threshold: f64,
+    /// Minimum merged score for a candidate to be returned.
+    #[serde(default = "default_fuzzy_floor")]
+    pub merged_floor: f64,
+    /// Maximum number of ranked candidates returned.
+    #[serde(default = "default_fuzzy_candidate_cap")]
+    pub candidate_cap: usize,
+    /// Half-window (days) for the relaxed PVD creation-date match.
+    #[serde(default = "default_fuzzy_date_window_days")]
+    pub date_window_days: i64,
+    /// Per-track duration tolerance in CD frames (~75 frames = 1s).
+    #[serde(default = "default_fuzzy_track_frame_tolerance")]
+    pub track_frame_tolerance: u32,
+    /// Minimum disc track count before the track-signature source runs. Low
+    /// counts (1–2 tracks) are too generic and produce false positives.
+    #[serde(default = "default_fuzzy_min_tracks_for_signature")]
+    pub min_tracks_for_signature: usize,
+    /// Score threshold above which we count a candidate as "strong" for
+    /// the verification trigger.
+    #[serde(default = "default_fuzzy_strong_score")]
+    pub strong_score: f64,
+    /// Minimum number of strong candidates before content-inspection
+    /// verification runs. With one obvious answer we skip the disc walk.
+    #[serde(default = "default_fuzzy_min_strong_for_verify")]
+    pub min_strong_for_verify: usize,
+    /// Bonus added per additional source that agrees on a candidate.
+    #[serde(default = "default_fuzzy_agreement_bonus")]
+    pub agreement_bonus: f64,
+    /// Payload-size ratio at/above which no size penalty applies.
+    #[serde(default = "default_fuzzy_size_ok_ratio")]
+    pub size_ok_ratio: f64,
+    /// Payload-size ratio below which a candidate is dropped entirely.
+    #[serde(default = "default_fuzzy_size_drop_ratio")]
+    pub size_drop_ratio: f64,
+    /// Score multiplier applied in the mid size-ratio band.
+    #[serde(default = "default_fuzzy_size_penalty")]
+    pub size_penalty: f64,
+}
+
+fn default_fuzzy_source_threshold() -> f64 {
+    0.70
+}
+fn default_fuzzy_floor() -> f64 {
+    0.60
+}
+fn default_fuzzy_candidate_cap() -> usize {
+    20
+}
+fn default_fuzzy_date_window_days() -> i64 {
+    30
+}
+fn default_fuzzy_track_frame_tolerance() -> u32 {
+    150
+}
+fn default_fuzzy_min_tracks_for_signature() -> usize {
+    3
+}
+fn default_fuzzy_strong_score() -> f64 {
+    0.90
+}
+fn default_fuzzy_min_strong_for_verify() -> usize {
+    1
+}
+fn default_fuzzy_agreement_bonus() -> f64 {
+    0.05
+}
+fn default_fuzzy_size_ok_ratio() -> f64 {
+    0.80
+}
+fn default_fuzzy_size_drop_ratio() -> f64 {
+    0.50
+}
+fn default_fuzzy_size_penalty() -> f64 {
+    0.85
+}
+
+impl Default for FuzzyMatchConfig {
+    fn default() -> Self {
+        Self {
+            source_threshold: default_fuzzy_source_threshold(),
+            merged_floor: default_fuzzy_floor(),
+            candidate_cap: default_fuzzy_candidate_cap(),
+            date_window_days: default_fuzzy_date_window_days(),
+            track_frame_tolerance: default_fuzzy_track_frame_tolerance(),
+            min_tracks_for_signature: default_fuzzy_min_tracks_for_signature(),
+            strong_score: default_fuzzy_strong_score(),
+            min_strong_for_verify: default_fuzzy_min_strong_for_verify(),
+            agreement_bonus: default_fuzzy_agreement_bonus(),
+            size_ok_ratio: default_fuzzy_size_ok_ratio(),
+            size_drop_ratio: default_fuzzy_size_drop_ratio(),
+            size_penalty: default_fuzzy_size_penalty(),
+        }
+    }
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_language() -> String {
+    crate::i18n::SYSTEM_LANGUAGE.to_string()
+}
+
+fn default_chd_cache_mb() -> u32 {
+    crate::disc::hunk_cache::DEFAULT_CACHE_MB
+}
+
+impl FuzzyMatchConfig {
+    /// Check that scores and ratios are fractions and the cap allows a result.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("source threshold", self.source_threshold),
+            ("merged floor", self.merged_floor),
+            ("strong score", self.strong_score),
+            ("size OK ratio", self.size_ok_ratio),
+            ("size drop ratio", self.size_drop_ratio),
+            ("size penalty", self.size_penalty),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("Fuzzy match {name} must be between 0 and 1"));
+            }
+        }
+        if self.candidate_cap == 0 {
+            return Err("Fuzzy match candidate cap must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Application secrets (loaded from secrets.json)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AppSecrets {
+    #[serde(default)]
+    pub discogs: DiscogsSecrets,
+}
+
+/// Discogs API secrets
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct DiscogsSecrets {
+    /// Consumer Key (API Key)
+    #[serde(default)]
+    pub consumer_key: String,
+    /// Consumer Secret
+    #[serde(default)]
+    pub consumer_secret: String,
+}
+
+impl DiscogsSecrets {
+    /// Check if API credentials are configured
+    pub fn has_credentials(&self) -> bool {
+        !self.consumer_key.is_empty() && !self.consumer_secret.is_empty()
+    }
+}
+
+impl AppSecrets {
+    /// Load secrets from the per-user `secrets.json`.
+    pub fn load() -> Self {
+        migrate_local_config();
+        let path = match secrets_file_path() {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("{e}; Discogs API will use anonymous access");
+                return Self::default();
+            }
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<AppSecrets>(&content) {
+                Ok(secrets) => {
+                    log::info!("Loaded secrets from {}", path.display());
+                    secrets
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse {}: {e}", path.display());
+                    Self::default()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log::info!(
+                    "No secrets.json at {}, Discogs API will use anonymous access",
+                    path.display()
+                );
+                Self::default()
+            }
+            Err(e) => {
+                log::warn!("Failed to read {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Update check configuration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpdateCheckConfig {
+    #[serde(default = "default_update_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_repository_url")]
+    pub repository_url: String,
+    /// Which releases count as updates
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// Hours before GitHub is asked again; until then the check answers
+    /// from the last response. 0 asks on every start.
+    #[serde(default = "default_update_interval_hours")]
+    pub check_interval_hours: u64,
+}
+
+/// Which releases the update check offers.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Full releases only
+    #[default]
+    Stable,
+    /// Pre-releases too, whichever is newest
+    Beta,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Beta];
+}
+
+fn default_update_enabled() -> bool {
+    true
+}
+
+pub fn default_update_interval_hours() -> u64 {
+    24
+}
+
+fn default_repository_url() -> String {
+    "https://github.com/danifunker/ODE-artwork-downloader".to_string()
+}
+
+impl Default for UpdateCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_update_enabled(),
+            repository_url: default_repository_url(),
+            channel: UpdateChannel::default(),
+            check_interval_hours: default_update_interval_hours(),
+        }
+    }
+}
+
+impl UpdateCheckConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.repository_url.starts_with("https://") {
+            return Err("Update repository URL must start with https://".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Watch mode: folders whose new disc images get artwork on their own.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct WatchConfig {
+    /// Folders to watch, subfolders included
+    #[serde(default)]
+    pub folders: Vec<String>,
+}
+
+impl WatchConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.folders.iter().find(|f| !Path::new(f).is_dir()) {
+            Some(folder) => Err(format!("Watch folder {folder} isn't a folder")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A USBODE on the network that saved covers can be sent to.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct UsbodeConfig {
+    /// Host name, IP or `http://` URL of the device's web interface; empty
+    /// when there's none
+    #[serde(default)]
+    pub address: String,
+}
+
+impl UsbodeConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.address.trim().is_empty() {
+            return Ok(());
+        }
+        crate::usbode::Device::new(&self.address).map(|_| ())
+    }
+}
+
+/// Discogs API configuration (URLs only - secrets are in secrets.json)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct DiscogsConfig {
+    /// Request Token URL (OAuth 1.0)
+    #[serde(default = "default_request_token_url")]
+    pub request_token_url: String,
+    /// Authorize URL (OAuth 1.0)
+    #[serde(default = "default_authorize_url")]
+    pub authorize_url: String,
+    /// Access Token URL (OAuth 1.0)
+    #[serde(default = "default_access_token_url")]
+    pub access_token_url: String,
+}
+
+fn default_request_token_url() -> String {
+    "https://api.discogs.com/oauth/request_token".to_string()
+}
+
+fn default_authorize_url() -> String {
+    "https://www.discogs.com/oauth/authorize".to_string()
+}
+
+fn default_access_token_url() -> String {
+    "https://api.discogs.com/oauth/access_token".to_string()
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            appearance: AppearanceConfig::default(),
+            update_check: UpdateCheckConfig::default(),
+            discogs: DiscogsConfig::default(),
+            log_level: default_log_level(),
+            log_file_level: default_log_level(),
+            language: default_language(),
+            fuzzy_match: FuzzyMatchConfig::default(),
+            network: NetworkConfig::default(),
+            export: ExportConfig::default(),
+            watch: WatchConfig::default(),
+            usbode: UsbodeConfig::default(),
+            search: SearchSettings::default(),
+            scan_workers: 0,
+            chd_cache_mb: default_chd_cache_mb(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Check every section; the error names the first bad setting.
+    pub fn validate(&self) -> Result<(), String> {
+        if !LOG_LEVELS.contains(&self.log_level.as_str()) {
+            return Err(format!("Unknown log level {}", self.log_level));
+        }
+        if !LOG_LEVELS.contains(&self.log_file_level.as_str()) {
+            return Err(format!("Unknown log file level {}", self.log_file_level));
+        }
+        if self.language != crate::i18n::SYSTEM_LANGUAGE
+            && !crate::i18n::LANGUAGES.iter().any(|(code, _)| *code == self.language)
+        {
+            return Err(format!("Unknown language {}", self.language));
+        }
+        self.appearance.validate()?;
+        self.export.validate()?;
+        self.network.validate()?;
+        self.fuzzy_match.validate()?;
+        self.watch.validate()?;
+        self.usbode.validate()?;
+        self.search.validate()?;
+        self.update_check.validate()
+    }
+
+    /// Write every section to `config.json` in one atomic replace, keeping
+    /// top-level keys this struct doesn't know (from a newer version, say).
+    pub fn save(&self) -> Result<(), String> {
+        let mut json = load_config_json()?;
+        let fields = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize config: {e}"))?;
+        if let (Some(json), serde_json::Value::Object(fields)) = (json.as_object_mut(), fields) {
+            json.extend(fields);
+        }
+        let updated = serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("Failed to serialize config: {e}"))?;
+        write_atomically(&config_file_path()?, &updated)
+    }
+
+    /// Load configuration from the per-user `config.json`.
+    pub fn load() -> Self {
+        migrate_local_config();
+        let path = match config_file_path() {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("{e}; using default config");
+                return Self::default();
+            }
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<AppConfig>(&content) {
+                Ok(config) => {
+                    log::info!("Loaded config from {}", path.display());
+                    // Kept anyway: sizes and the like are clamped where
+                    // they're used, and Settings shows the same error on Save
+                    if let Err(e) = config.validate() {
+                        log::warn!("{}: {e}", path.display());
+                    }
+                    config
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse {}: {e}", path.display());
+                    Self::default()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log::info!("No config.json at {}, using defaults", path.display());
+                Self::default()
+            }
+            Err(e) => {
+                log::warn!("Failed to read {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+}
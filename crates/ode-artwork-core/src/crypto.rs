@@ -0,0 +1,214 @@
+//! Cryptographic utilities for secure secrets handling
+//!
+//! Decrypts embedded secrets at runtime using AES-256-GCM, and checks the
+//! Ed25519 signatures release builds are published with before an update
+//! is installed.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ed25519_dalek::{Signature, VerifyingKey, SIGNATURE_LENGTH};
+use serde::Deserialize;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Embedded encrypted secrets (generated at build time)
+const ENCRYPTED_SECRETS: &str = include_str!(concat!(env!("OUT_DIR"), "/secrets.enc"));
+
+/// Public half of the key releases are signed with, base64 (set at build
+/// time by the release workflow)
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("UPDATE_SIGNING_PUBLIC_KEY");
+
+/// Decrypted secrets cache
+static DECRYPTED_SECRETS: OnceLock<Option<EmbeddedSecrets>> = OnceLock::new();
+
+/// Decrypted secrets structure
+#[derive(Debug, Deserialize, Clone)]
+pub struct EmbeddedSecrets {
+    pub discogs_consumer_key: String,
+    pub discogs_consumer_secret: String,
+}
+
+impl EmbeddedSecrets {
+    /// Check if credentials are available
+    pub fn has_credentials(&self) -> bool {
+        !self.discogs_consumer_key.is_empty() && !self.discogs_consumer_secret.is_empty()
+    }
+}
+
+/// Get the embedded secrets (decrypted at first access)
+///
+/// Returns None if:
+/// - No secrets were embedded at build time
+/// - Decryption fails (wrong key, corrupted data)
+/// - The ENCRYPTION_KEY environment variable is not set
+pub fn get_embedded_secrets() -> Option<&'static EmbeddedSecrets> {
+    DECRYPTED_SECRETS
+        .get_or_init(|| decrypt_secrets())
+        .as_ref()
+}
+
+/// Decrypt the embedded secrets
+fn decrypt_secrets() -> Option<EmbeddedSecrets> {
+    // Check if we have encrypted secrets
+    if ENCRYPTED_SECRETS.is_empty() {
+        log::debug!("No embedded secrets found (local development build)");
+        return None;
+    }
+
+    // Get the encryption key from compile-time environment variable
+    let key_base64 = option_env!("ENCRYPTION_KEY")?;
+
+    let key_bytes = match BASE64.decode(key_base64) {
+        Ok(bytes) if bytes.len() == 32 => bytes,
+        Ok(_) => {
+            log::error!("Invalid encryption key length");
+            return None;
+        }
+        Err(e) => {
+            log::error!("Failed to decode encryption key: {}", e);
+            return None;
+        }
+    };
+
+    // Decode the encrypted data
+    let encrypted_data = match BASE64.decode(ENCRYPTED_SECRETS.trim()) {
+        Ok(data) => data,
+        Err(e) => {
+            log::error!("Failed to decode encrypted secrets: {}", e);
+            return None;
+        }
+    };
+
+    // Extract nonce (first 12 bytes) and ciphertext
+    if encrypted_data.len() < 12 {
+        log::error!("Encrypted data too short");
+        return None;
+    }
+
+    let (nonce_bytes, ciphertext) = encrypted_data.split_at(12);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    // Decrypt
+    let cipher = Aes256Gcm::new_from_slice(&key_bytes).ok()?;
+    let plaintext = match cipher.decrypt(nonce, ciphertext) {
+        Ok(data) => data,
+        Err(e) => {
+            log::error!("Failed to decrypt secrets: {}", e);
+            return None;
+        }
+    };
+
+    // Parse JSON
+    match serde_json::from_slice(&plaintext) {
+        Ok(secrets) => {
+            log::debug!("Successfully decrypted embedded secrets");
+            Some(secrets)
+        }
+        Err(e) => {
+            log::error!("Failed to parse decrypted secrets: {}", e);
+            None
+        }
+    }
+}
+
+/// Whether this build can check update signatures. Local builds can't, so
+/// they never install updates themselves.
+pub fn has_update_key() -> bool {
+    update_public_key().is_some()
+}
+
+/// The release key built into this build. An empty or malformed
+/// `UPDATE_SIGNING_PUBLIC_KEY` counts as none: an unset Actions variable
+/// expands to an empty string rather than leaving it unset.
+fn update_public_key() -> Option<[u8; 32]> {
+    let key: [u8; 32] = BASE64
+        .decode(UPDATE_PUBLIC_KEY?.trim())
+        .ok()?
+        .try_into()
+        .ok()?;
+    VerifyingKey::from_bytes(&key).ok()?;
+    Some(key)
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("this build has no update signing key")]
+    NoKey,
+    #[error("the update signing key is malformed")]
+    BadKey,
+    #[error("the signature file is malformed")]
+    Malformed,
+    #[error("the signature doesn't match")]
+    Mismatch,
+}
+
+/// Check `signature` (the `.sig` file published next to a release asset) is
+/// this build's release key's signature over `data`.
+pub fn verify_update_signature(data: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+    let key = update_public_key().ok_or(SignatureError::NoKey)?;
+    verify_signature(&key, data, signature)
+}
+
+/// Check an Ed25519 `signature` over `data` against `public_key`. The
+/// signature is the raw 64 bytes `openssl pkeyutl -sign` writes, or the
+/// same in base64.
+pub fn verify_signature(
+    public_key: &[u8; 32],
+    data: &[u8],
+    signature: &[u8],
+) -> Result<(), SignatureError> {
+    let key = VerifyingKey::from_bytes(public_key).map_err(|_| SignatureError::BadKey)?;
+    let raw: [u8; SIGNATURE_LENGTH] = match signature.try_into() {
+        Ok(raw) => raw,
+        Err(_) => std::str::from_utf8(signature)
+            .ok()
+            .and_then(|text| BASE64.decode(text.trim()).ok())
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(SignatureError::Malformed)?,
+    };
+    key.verify_strict(data, &Signature::from_bytes(&raw))
+        .map_err(|_| SignatureError::Mismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn checks_update_signatures() {
+        let signer = SigningKey::from_bytes(&[7; 32]);
+        let public = signer.verifying_key().to_bytes();
+        let data = b"OAD-linux-x64-2.0.0.AppImage";
+        let signature = signer.sign(data).to_bytes();
+
+        assert_eq!(verify_signature(&public, data, &signature), Ok(()));
+        let encoded = BASE64.encode(signature);
+        assert_eq!(verify_signature(&public, data, encoded.as_bytes()), Ok(()));
+
+        assert_eq!(
+            verify_signature(&public, b"OAD-linux-x64-2.0.1.AppImage", &signature),
+            Err(SignatureError::Mismatch)
+        );
+        let other = SigningKey::from_bytes(&[8; 32]).verifying_key().to_bytes();
+        assert_eq!(
+            verify_signature(&other, data, &signature),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify_signature(&public, data, &signature[..32]),
+            Err(SignatureError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_no_embedded_secrets_in_dev() {
+        // In development builds without secrets, this should return None gracefully
+        let secrets = get_embedded_secrets();
+        // This test just ensures it doesn't panic
+        println!("Embedded secrets available: {}", secrets.is_some());
+    }
+}
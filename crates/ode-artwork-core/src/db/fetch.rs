@@ -3,12 +3,12 @@
 use std::fs::File;
 use std::io::{BufWriter, Read, Write};
 use std::path::Path;
-use std::time::Duration;
 
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 use super::{LATEST_RELEASE_BASE, USER_AGENT};
+use crate::net::{client_builder, Timeout};
 
 #[derive(Debug, Error)]
 pub enum FetchError {
@@ -43,10 +43,8 @@ impl Urls {
 }
 
 pub fn build_client() -> Result<reqwest::blocking::Client, FetchError> {
-    Ok(reqwest::blocking::Client::builder()
+    Ok(client_builder(Timeout::Download)
         .user_agent(USER_AGENT)
-        .connect_timeout(Duration::from_secs(30))
-        .timeout(Duration::from_secs(5 * 60))
         .build()?)
 }
 
@@ -0,0 +1,84 @@
+//! Platform-appropriate paths for the cached ODE-lookup DB: the
+//! `databases/` folder under the per-user data directory.
+
+use std::path::PathBuf;
+
+/// Folder under `config::data_dir` holding the DB.
+const DATABASES_DIR: &str = "databases";
+
+/// Files moved into [`DATABASES_DIR`] from the data directory itself,
+/// where builds before it kept them.
+const MOVED_FILES: [&str; 2] = ["ode-lookup.sqlite", "ode-lookup.sqlite.zst.sha256"];
+
+pub struct DbPaths {
+    pub data_dir: PathBuf,
+    /// The data directory the DB used to sit in directly
+    parent_dir: PathBuf,
+}
+
+impl DbPaths {
+    pub fn discover() -> Result<Self, String> {
+        let parent_dir = crate::config::data_dir()?;
+        let data_dir = parent_dir.join(DATABASES_DIR);
+        std::fs::create_dir_all(&data_dir)
+            .map_err(|e| format!("could not create {}: {e}", data_dir.display()))?;
+        let paths = Self {
+            data_dir,
+            parent_dir,
+        };
+        paths.move_from_parent();
+        Ok(paths)
+    }
+
+    /// Move a DB downloaded by an older build into [`DATABASES_DIR`], so it
+    /// isn't downloaded again. A rename, as both are on the same disk.
+    fn move_from_parent(&self) {
+        for name in MOVED_FILES {
+            let old = self.parent_dir.join(name);
+            let new = self.data_dir.join(name);
+            if !old.is_file() || new.exists() {
+                continue;
+            }
+            match std::fs::rename(&old, &new) {
+                Ok(()) => log::info!("Moved {} to {}", old.display(), new.display()),
+                Err(e) => log::warn!("Could not move {}: {e}", old.display()),
+            }
+        }
+    }
+
+    pub fn sqlite(&self) -> PathBuf {
+        self.data_dir.join("ode-lookup.sqlite")
+    }
+
+    /// Cached sha256 of the most recently installed `.zst` artifact. Stored as
+    /// the raw 64-char hex string. Used to short-circuit downloads when the
+    /// upstream artifact hasn't changed.
+    pub fn last_zst_sha256(&self) -> PathBuf {
+        self.data_dir.join("ode-lookup.sqlite.zst.sha256")
+    }
+
+    /// Temp location for the in-flight download. Sits next to the live DB so
+    /// the final atomic rename stays on the same filesystem.
+    pub fn download_tmp(&self) -> PathBuf {
+        self.data_dir.join("ode-lookup.sqlite.zst.partial")
+    }
+
+    /// Temp location for the decompressed DB before the swap.
+    pub fn decompress_tmp(&self) -> PathBuf {
+        self.data_dir.join("ode-lookup.sqlite.partial")
+    }
+
+    /// Pre-v3 artifact paths, and the partial downloads of builds that kept
+    /// the DB directly in the data directory. Used once at startup to clean
+    /// up after those.
+    pub fn legacy_artifacts(&self) -> Vec<PathBuf> {
+        vec![
+            self.parent_dir.join("redump.sqlite"),
+            self.parent_dir.join("redump.sqlite.zst.sha256"),
+            self.parent_dir.join("redump.sqlite.zst.partial"),
+            self.parent_dir.join("redump.sqlite.partial"),
+            self.parent_dir.join("ode-lookup.sqlite.zst.partial"),
+            self.parent_dir.join("ode-lookup.sqlite.partial"),
+        ]
+    }
+}
@@ -0,0 +1,53 @@
+//! Disc filesystem browsing module
+//!
+//! Delegates all format/filesystem browsing to the `opticaldiscs` library.
+//! For images ODE reads itself, opticaldiscs gets its sectors from the
+//! [`ImageSectors`] shared on the `OpenedImage`, so browsing reuses the
+//! handle hashing reads through, including its memory map under the `mmap`
+//! feature.
+
+use std::sync::Arc;
+
+// Re-export everything the rest of ODE needs from opticaldiscs
+pub use opticaldiscs::browse::entry::{EntryType, FileEntry};
+pub use opticaldiscs::browse::filesystem::{Filesystem, FilesystemError};
+pub use opticaldiscs::browse::open_disc_filesystem;
+use opticaldiscs::browse::open_disc_filesystem_with_reader;
+use opticaldiscs::browse::sector_reader::SectorReader;
+
+use crate::disc::sectors::{ImageSectors, SECTOR_SIZE};
+use crate::disc::DiscInfo;
+
+/// Open a filesystem from disc info.
+///
+/// Wraps `opticaldiscs::browse::open_disc_filesystem`, reusing the image
+/// `DiscReader::read` already opened and, when it has one, its sector
+/// reader. Only a `DiscInfo` without an image (the filename-only fallback)
+/// gets the image probed again.
+pub fn open_filesystem(disc_info: &DiscInfo) -> Result<Box<dyn Filesystem>, FilesystemError> {
+    if let Some(image) = &disc_info.image {
+        return match image.sectors() {
+            Some(sectors) => open_disc_filesystem_with_reader(
+                image.info(),
+                Box::new(SharedSectorReader(Arc::clone(sectors))),
+            ),
+            None => open_disc_filesystem(image.info()),
+        };
+    }
+    let odi = opticaldiscs::detect::DiscImageInfo::open(&disc_info.path)
+        .map_err(|e| FilesystemError::Parse(e.to_string()))?;
+    open_disc_filesystem(&odi)
+}
+
+/// opticaldiscs' view of a shared [`ImageSectors`].
+struct SharedSectorReader(Arc<ImageSectors>);
+
+impl SectorReader for SharedSectorReader {
+    fn read_sector(&mut self, lba: u64) -> Result<Vec<u8>, FilesystemError> {
+        let mut sector = vec![0u8; SECTOR_SIZE];
+        self.0
+            .read_sector(lba, &mut sector)
+            .map_err(|e| FilesystemError::Parse(format!("sector {lba}: {e}")))?;
+        Ok(sector)
+    }
+}
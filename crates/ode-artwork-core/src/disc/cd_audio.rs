@@ -21,7 +21,6 @@
 //! CD-Extra inter-session gap (correct for a physical disc) and drop real audio
 //! from a gap-stripped extract.
 
-use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use cd_da_reader::{lba_to_msf, read_track_with_bounds, AudioSectorReader, Toc, Track, TrackBounds};
@@ -30,6 +29,8 @@ use libchdman_rs::Chd;
 use opticaldiscs::bincue::{self, BinTrack};
 use opticaldiscs::formats::DiscFormat;
 
+use super::image_file::ImageFile;
+
 /// CD-DA sample rate (Hz).
 pub const CDDA_SAMPLE_RATE: u32 = 44_100;
 /// CD-DA channel count (stereo).
@@ -89,13 +90,41 @@ where
     }
 }
 
-/// Convert little-endian PCM bytes to interleaved `i16` samples, never relying
-/// on host endianness.
-fn pcm_le_i16(bytes: &[u8]) -> Vec<i16> {
-    bytes
-        .chunks_exact(2)
-        .map(|b| i16::from_le_bytes([b[0], b[1]]))
-        .collect()
+/// Stream several audio tracks' PCM in order, invoking `on_samples` with the
+/// track number and each batch. Like [`extract_audio_pcm`], but a CHD is
+/// extracted once for the whole set instead of once per track.
+pub fn extract_audio_tracks_pcm<F>(
+    path: &Path,
+    track_numbers: &[u32],
+    mut on_samples: F,
+) -> Result<(), String>
+where
+    F: FnMut(u32, &[i16]),
+{
+    match DiscFormat::from_path(path) {
+        Some(DiscFormat::Chd) => {
+            let disc = ChdDisc::open(path)?;
+            for &n in track_numbers {
+                disc.track_pcm(n, |samples| on_samples(n, samples))?;
+            }
+            Ok(())
+        }
+        Some(DiscFormat::BinCue) => {
+            for &n in track_numbers {
+                extract_audio_pcm_bincue(path, n, |samples| on_samples(n, samples))?;
+            }
+            Ok(())
+        }
+        other => Err(format!("no CD-DA support for {other:?} ({})", path.display())),
+    }
+}
+
+/// Convert little-endian PCM bytes to interleaved `i16` samples in `out`
+/// (replacing what was there), never relying on host endianness. Callers
+/// pass the same `out` for every batch so a track decodes into one buffer.
+fn pcm_le_i16(bytes: &[u8], out: &mut Vec<i16>) {
+    out.clear();
+    out.extend(bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])));
 }
 
 // ── CHD backing ─────────────────────────────────────────────────────────────
@@ -122,50 +151,13 @@ fn read_tracks_chd(path: &Path) -> Result<Vec<CdTrack>, String> {
 
 /// Stream a CHD audio track's PCM. Extracts the whole disc to a temp BIN, then
 /// reads the track's sector range in one shot and emits it in ~1 s batches.
-fn extract_audio_pcm_chd<F>(
-    path: &Path,
-    track_number: u32,
-    mut on_samples: F,
-) -> Result<u64, String>
+fn extract_audio_pcm_chd<F>(path: &Path, track_number: u32, on_samples: F) -> Result<u64, String>
 where
     F: FnMut(&[i16]),
 {
     // ChdDisc::open is the slow "Preparing" cost (whole-disc extract); the
-    // per-track read below is fast.
-    let disc = ChdDisc::open(path)?;
-
-    // Guard against reading a data track's bytes as "audio": read_track_with_bounds
-    // resolves geometry only and does not validate track type on a file backing.
-    let idx = disc
-        .toc
-        .tracks
-        .iter()
-        .position(|t| u32::from(t.number) == track_number)
-        .ok_or_else(|| format!("no track {track_number} on this disc"))?;
-    let track = &disc.toc.tracks[idx];
-    if !track.is_audio {
-        return Err(format!("track {track_number} is not an AUDIO track"));
-    }
-    let track_no = track.number;
-
-    // The extracted BIN is gapless — each track is exactly `frames` sectors,
-    // back-to-back — so resolve bounds with TrackBounds::Gapless. The default
-    // SessionGap policy (what CdReader::read_track uses) would subtract the
-    // 11,400-sector CD-Extra inter-session gap from the last audio track before
-    // a data track; that gap is real on a physical disc but absent from a
-    // CHD-derived image, so it would chop ~2.5 min of real audio off that track.
-    let pcm = read_track_with_bounds(&disc, &disc.toc, track_no, TrackBounds::Gapless)
-        .map_err(|e| format!("read track {track_number}: {e}"))?;
-
-    // Batch ~1 second of audio per callback: 1176 i16 per CD frame × 75 frames/s.
-    const BYTES_PER_BATCH: usize = 1176 * 75 * 2;
-    let mut total = 0u64;
-    for chunk in pcm.chunks(BYTES_PER_BATCH) {
-        let samples = pcm_le_i16(chunk);
-        total += samples.len() as u64;
-        on_samples(&samples);
-    }
-    Ok(total)
+    // per-track read is fast.
+    ChdDisc::open(path)?.track_pcm(track_number, on_samples)
 }
 
 /// A CHD CD image extracted to a temporary redump-style BIN, exposing its audio
@@ -173,8 +165,8 @@ where
 struct ChdDisc {
     /// Temp BIN written by [`extract_to_cue`]: little-endian, 2352 B/sector,
     /// tracks laid out back-to-back.
-    bin_path: PathBuf,
-    /// TOC describing where each track's sectors live in `bin_path`.
+    bin: ImageFile,
+    /// TOC describing where each track's sectors live in `bin`.
     toc: Toc,
     /// Owns the temp dir; dropping it deletes the extracted BIN/CUE.
     _tmp: tempfile::TempDir,
@@ -231,24 +223,62 @@ impl ChdDisc {
             leadout_lba: lba,
         };
 
+        let bin = ImageFile::open(&bin_path).map_err(|e| format!("open extracted BIN: {e}"))?;
         Ok(Self {
-            bin_path,
+            bin,
             toc,
             _tmp: tmp,
         })
     }
+
+    /// Emit one audio track's PCM in ~1 s batches.
+    fn track_pcm<F>(&self, track_number: u32, mut on_samples: F) -> Result<u64, String>
+    where
+        F: FnMut(&[i16]),
+    {
+        // Guard against reading a data track's bytes as "audio": read_track_with_bounds
+        // resolves geometry only and does not validate track type on a file backing.
+        let idx = self
+            .toc
+            .tracks
+            .iter()
+            .position(|t| u32::from(t.number) == track_number)
+            .ok_or_else(|| format!("no track {track_number} on this disc"))?;
+        let track = &self.toc.tracks[idx];
+        if !track.is_audio {
+            return Err(format!("track {track_number} is not an AUDIO track"));
+        }
+        let track_no = track.number;
+
+        // The extracted BIN is gapless — each track is exactly `frames` sectors,
+        // back-to-back — so resolve bounds with TrackBounds::Gapless. The default
+        // SessionGap policy (what CdReader::read_track uses) would subtract the
+        // 11,400-sector CD-Extra inter-session gap from the last audio track before
+        // a data track; that gap is real on a physical disc but absent from a
+        // CHD-derived image, so it would chop ~2.5 min of real audio off that track.
+        let pcm = read_track_with_bounds(self, &self.toc, track_no, TrackBounds::Gapless)
+            .map_err(|e| format!("read track {track_number}: {e}"))?;
+
+        // Batch ~1 second of audio per callback: 1176 i16 per CD frame × 75 frames/s.
+        const BYTES_PER_BATCH: usize = 1176 * 75 * 2;
+        let mut total = 0u64;
+        let mut samples = Vec::with_capacity(BYTES_PER_BATCH / 2);
+        for chunk in pcm.chunks(BYTES_PER_BATCH) {
+            pcm_le_i16(chunk, &mut samples);
+            total += samples.len() as u64;
+            on_samples(&samples);
+        }
+        Ok(total)
+    }
 }
 
 impl AudioSectorReader for ChdDisc {
     type Error = std::io::Error;
 
     fn read_audio_sectors(&self, start_lba: u32, count: u32) -> Result<Vec<u8>, Self::Error> {
-        let mut f = std::fs::File::open(&self.bin_path)?;
-        f.seek(SeekFrom::Start(
-            u64::from(start_lba) * BYTES_PER_SECTOR as u64,
-        ))?;
         let mut buf = vec![0u8; count as usize * BYTES_PER_SECTOR];
-        f.read_exact(&mut buf)?;
+        self.bin
+            .read_exact_at(u64::from(start_lba) * BYTES_PER_SECTOR as u64, &mut buf)?;
         Ok(buf)
     }
 }
@@ -304,22 +334,23 @@ where
     }
     let frames = bincue_frames(track)?;
 
-    let mut f = std::fs::File::open(&track.bin_path)
+    let bin = ImageFile::open(&track.bin_path)
         .map_err(|e| format!("open {}: {e}", track.bin_path.display()))?;
-    f.seek(SeekFrom::Start(track.file_byte_offset))
-        .map_err(|e| format!("seek {}: {e}", track.bin_path.display()))?;
+    let mut offset = track.file_byte_offset;
 
     // Emit ~1 s (75 sectors) per callback so the player starts quickly and never
     // holds the whole track in memory.
     let mut buf = vec![0u8; SECTORS_PER_SECOND as usize * BYTES_PER_SECTOR];
+    let mut samples = Vec::with_capacity(buf.len() / 2);
     let mut remaining = frames;
     let mut total = 0u64;
     while remaining > 0 {
         let n = remaining.min(SECTORS_PER_SECOND);
         let bytes = n as usize * BYTES_PER_SECTOR;
-        f.read_exact(&mut buf[..bytes])
+        bin.read_exact_at(offset, &mut buf[..bytes])
             .map_err(|e| format!("read track {track_number}: {e}"))?;
-        let samples = pcm_le_i16(&buf[..bytes]);
+        offset += bytes as u64;
+        pcm_le_i16(&buf[..bytes], &mut samples);
         total += samples.len() as u64;
         on_samples(&samples);
         remaining -= n;
@@ -343,7 +374,7 @@ fn bincue_frames(t: &BinTrack) -> Result<u32, String> {
 
 /// Resolve a BIN/CUE path to its `.cue`: pass a `.cue` through, or find the
 /// `.cue` sitting next to a `.bin`.
-fn resolve_cue(path: &Path) -> Result<PathBuf, String> {
+pub(super) fn resolve_cue(path: &Path) -> Result<PathBuf, String> {
     match path
         .extension()
         .and_then(|e| e.to_str())
@@ -378,4 +409,15 @@ mod tests {
         };
         assert_eq!(t.duration_mmss(), "01:30");
     }
+
+    #[test]
+    fn pcm_reuses_the_sample_buffer() {
+        let mut samples = Vec::new();
+        pcm_le_i16(&[0x01, 0x00, 0xff, 0xff, 0x00, 0x80], &mut samples);
+        assert_eq!(samples, [1, -1, i16::MIN]);
+        let capacity = samples.capacity();
+        pcm_le_i16(&[0x02, 0x00], &mut samples);
+        assert_eq!(samples, [2]);
+        assert_eq!(samples.capacity(), capacity);
+    }
 }
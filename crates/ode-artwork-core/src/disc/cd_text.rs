@@ -0,0 +1,133 @@
+//! CD-TEXT from the cue sheet of a BIN/CUE image.
+//!
+//! `PERFORMER` and `TITLE` lines before the first `TRACK` name the album;
+//! the ones under a track name that track.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::cd_audio;
+
+/// Album and track names a cue sheet carries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdText {
+    pub album: Option<String>,
+    pub performer: Option<String>,
+    pub tracks: BTreeMap<u32, CdTextTrack>,
+}
+
+/// Names given under one `TRACK`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdTextTrack {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+}
+
+impl CdText {
+    /// Whether the cue named nothing at all.
+    pub fn is_empty(&self) -> bool {
+        self.album.is_none() && self.performer.is_none() && self.tracks.is_empty()
+    }
+
+    /// Track titles by number, for tracks that have one.
+    pub fn titles(&self) -> BTreeMap<u32, String> {
+        self.tracks
+            .iter()
+            .filter_map(|(n, track)| Some((*n, track.title.clone()?)))
+            .collect()
+    }
+
+    /// `Performer Album` to search artwork with, when the album is named.
+    pub fn search_query(&self) -> Option<String> {
+        let album = self.album.as_deref()?;
+        Some(match self.performer.as_deref() {
+            Some(performer) => format!("{} {}", performer, album),
+            None => album.to_string(),
+        })
+    }
+}
+
+/// CD-TEXT of the BIN/CUE image at `path`. `None` for other formats, an
+/// unreadable cue, or a cue without names.
+pub fn read_cd_text(path: &Path) -> Option<CdText> {
+    let cue = cd_audio::resolve_cue(path).ok()?;
+    let text = std::fs::read_to_string(cue).ok()?;
+    Some(parse_cd_text(&text)).filter(|cd_text| !cd_text.is_empty())
+}
+
+pub(super) fn parse_cd_text(cue: &str) -> CdText {
+    let mut cd_text = CdText::default();
+    let mut track = None;
+    for line in cue.lines() {
+        let line = line.trim();
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let keyword = keyword.to_ascii_uppercase();
+        if keyword == "TRACK" {
+            track = rest.split_whitespace().next().and_then(|n| n.parse().ok());
+            continue;
+        }
+        let value = rest.trim().trim_matches('"').trim();
+        if value.is_empty() || !matches!(keyword.as_str(), "TITLE" | "PERFORMER") {
+            continue;
+        }
+        let value = Some(value.to_string());
+        match (track, keyword.as_str()) {
+            (None, "TITLE") => cd_text.album = value,
+            (None, _) => cd_text.performer = value,
+            (Some(n), "TITLE") => cd_text.tracks.entry(n).or_default().title = value,
+            (Some(n), _) => cd_text.tracks.entry(n).or_default().performer = value,
+        }
+    }
+    cd_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUE: &str = r#"REM GENRE Rock
+PERFORMER "Pink Floyd"
+TITLE "The Wall"
+FILE "wall.bin" BINARY
+  TRACK 01 AUDIO
+    TITLE "In the Flesh?"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 01 03:19:00
+  TRACK 03 AUDIO
+    title "Another Brick in the Wall"
+    performer "Pink Floyd & Choir"
+    INDEX 01 06:00:00
+"#;
+
+    #[test]
+    fn album_names_come_before_the_first_track() {
+        let cd_text = parse_cd_text(CUE);
+        assert_eq!(cd_text.album.as_deref(), Some("The Wall"));
+        assert_eq!(cd_text.performer.as_deref(), Some("Pink Floyd"));
+        assert_eq!(
+            cd_text.search_query().as_deref(),
+            Some("Pink Floyd The Wall")
+        );
+    }
+
+    #[test]
+    fn track_names_stay_with_their_track() {
+        let cd_text = parse_cd_text(CUE);
+        assert_eq!(cd_text.tracks.len(), 2);
+        assert_eq!(
+            cd_text.tracks[&3].performer.as_deref(),
+            Some("Pink Floyd & Choir")
+        );
+        let titles = cd_text.titles();
+        assert_eq!(titles[&1], "In the Flesh?");
+        assert_eq!(titles[&3], "Another Brick in the Wall");
+    }
+
+    #[test]
+    fn cue_without_names_is_empty() {
+        let cd_text = parse_cd_text("FILE \"a.bin\" BINARY\n  TRACK 01 MODE1/2352\n");
+        assert!(cd_text.is_empty());
+        assert_eq!(cd_text.search_query(), None);
+    }
+}
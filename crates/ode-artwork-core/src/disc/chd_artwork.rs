@@ -0,0 +1,81 @@
+//! Cover art stored inside CHD images.
+//!
+//! A cover next to the disc is easily left behind when CHDs are copied
+//! between SD cards. With `embed_in_chd` on, the exported front cover is
+//! also written into the CHD itself as a metadata entry under our own tag,
+//! so it travels with the file, and the "has cover art" checks look there
+//! when no cover file is found.
+//!
+//! The entry is written without MAME's checksum flag, so it doesn't change
+//! the CHD's overall SHA-1 and redump/hash lookups keep matching.
+
+use std::path::Path;
+
+use libchdman_rs::{Chd, ChdError};
+
+/// Metadata tag for the embedded cover (`ODEA`, big-endian like MAME's
+/// `CHD_MAKE_TAG`).
+pub const ARTWORK_TAG: u32 = u32::from_be_bytes(*b"ODEA");
+
+/// Metadata flags: no `CHD_MDFLAGS_CHECKSUM`, see the module docs.
+const ARTWORK_FLAGS: u8 = 0;
+
+/// Whether `path` names a CHD image.
+pub fn is_chd(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.eq_ignore_ascii_case("chd"))
+        .unwrap_or(false)
+}
+
+/// Store `image` (the encoded cover) in the CHD at `path`, replacing any
+/// cover embedded earlier.
+pub fn write_artwork(path: &Path, image: &[u8]) -> Result<(), String> {
+    let mut chd = open(path, true)?;
+    chd.write_metadata(ARTWORK_TAG, 0, image, ARTWORK_FLAGS)
+        .map_err(|e| format!("Failed to write artwork into {}: {e:?}", path.display()))
+}
+
+/// The cover embedded in the CHD at `path`, if there is one.
+pub fn read_artwork(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    let chd = open(path, false)?;
+    match chd.read_metadata(ARTWORK_TAG, 0) {
+        Ok(data) => Ok(Some(data)),
+        Err(ChdError::MetadataNotFound) => Ok(None),
+        Err(e) => Err(format!(
+            "Failed to read artwork from {}: {e:?}",
+            path.display()
+        )),
+    }
+}
+
+/// Whether `path` is a CHD with an embedded cover. Unreadable files count
+/// as having none.
+pub fn has_artwork(path: &Path) -> bool {
+    is_chd(path) && matches!(read_artwork(path), Ok(Some(_)))
+}
+
+fn open(path: &Path, writeable: bool) -> Result<Chd, String> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| format!("non-UTF-8 path: {}", path.display()))?;
+    Chd::open(path_str, writeable, None)
+        .map_err(|e| format!("Failed to open {}: {e:?}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_matches_chd_make_tag() {
+        // CHD_MAKE_TAG('O','D','E','A')
+        assert_eq!(ARTWORK_TAG, 0x4F44_4541);
+    }
+
+    #[test]
+    fn only_chd_paths_are_checked() {
+        assert!(is_chd(Path::new("/roms/Myst.CHD")));
+        assert!(!is_chd(Path::new("/roms/Myst.cue")));
+        assert!(!has_artwork(Path::new("/roms/Myst.cue")));
+    }
+}
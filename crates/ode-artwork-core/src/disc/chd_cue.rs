@@ -0,0 +1,170 @@
+//! CUE sheets for CHD images, for ODEs that want BIN/CUE.
+//!
+//! [`write_cue`] writes just the sheet, built from the CHD's track metadata,
+//! for a BIN extracted elsewhere (`chdman extractcd`). [`extract_bincue`]
+//! writes both, through libchdman's own extractor. Either way the BIN is
+//! raw 2352-byte sectors with each track's frames back to back, so the
+//! sheet is a single `FILE` with pregaps that aren't stored in the BIN
+//! given as `PREGAP`.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use libchdman_rs::cd::{extract_to_cue, list_tracks, TrackType};
+use libchdman_rs::Chd;
+
+/// 75 frames (sectors) per second of CD time.
+const FRAMES_PER_SECOND: u32 = 75;
+
+/// One track as the sheet needs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueTrack {
+    pub number: u32,
+    /// `AUDIO`, `MODE1/2352` or `MODE2/2352`
+    pub mode: &'static str,
+    /// Frames stored in the BIN
+    pub frames: u32,
+    /// Frames of silence before the track that the BIN doesn't hold
+    pub pregap: u32,
+}
+
+/// The track layout of the CHD at `path`.
+pub fn chd_tracks(path: &Path) -> Result<Vec<CueTrack>, String> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| format!("non-UTF-8 path: {}", path.display()))?;
+    let chd = Chd::open(path_str, false, None)
+        .map_err(|e| format!("Failed to open {}: {e:?}", path.display()))?;
+    tracks_of(&chd)
+}
+
+/// The track layout of an open CHD.
+pub(super) fn tracks_of(chd: &Chd) -> Result<Vec<CueTrack>, String> {
+    let tracks = list_tracks(chd).map_err(|e| format!("Failed to list CHD tracks: {e:?}"))?;
+    if tracks.is_empty() {
+        return Err("no tracks in image".to_string());
+    }
+    Ok(tracks
+        .into_iter()
+        .map(|t| CueTrack {
+            number: t.track_num,
+            mode: cue_mode(&t.track_type),
+            frames: t.frames,
+            pregap: t.pregap,
+        })
+        .collect())
+}
+
+/// CUE track mode for a CHD track type. Data tracks come out of the
+/// extractor as raw 2352-byte sectors whatever their stored form.
+fn cue_mode(track_type: &TrackType) -> &'static str {
+    if *track_type == TrackType::Audio {
+        return "AUDIO";
+    }
+    if format!("{track_type:?}")
+        .to_ascii_lowercase()
+        .contains("mode2")
+    {
+        "MODE2/2352"
+    } else {
+        "MODE1/2352"
+    }
+}
+
+/// CUE sheet text for `tracks` stored back to back in `bin_name`.
+pub fn cue_sheet(tracks: &[CueTrack], bin_name: &str) -> String {
+    let mut out = format!("FILE \"{}\" BINARY\r\n", bin_name.replace('"', "'"));
+    let mut offset = 0u32;
+    for track in tracks {
+        let _ = write!(out, "  TRACK {:02} {}\r\n", track.number, track.mode);
+        if track.pregap > 0 {
+            let _ = write!(out, "    PREGAP {}\r\n", msf(track.pregap));
+        }
+        let _ = write!(out, "    INDEX 01 {}\r\n", msf(offset));
+        offset = offset.saturating_add(track.frames);
+    }
+    out
+}
+
+/// Frames as `MM:SS:FF`.
+fn msf(frames: u32) -> String {
+    let seconds = frames / FRAMES_PER_SECOND;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 60,
+        seconds % 60,
+        frames % FRAMES_PER_SECOND
+    )
+}
+
+/// Write `<stem>.cue` next to the CHD, pointing at `<stem>.bin`. Returns the
+/// sheet's path.
+pub fn write_cue(chd: &Path) -> Result<PathBuf, String> {
+    let cue_path = chd.with_extension("cue");
+    let bin_name = bin_file_name(chd);
+    let sheet = cue_sheet(&chd_tracks(chd)?, &bin_name);
+    std::fs::write(&cue_path, sheet)
+        .map_err(|e| format!("Failed to write {}: {}", cue_path.display(), e))?;
+    Ok(cue_path)
+}
+
+/// Extract the CHD to `<stem>.cue` + `<stem>.bin` in `out_dir`. Returns the
+/// sheet's path.
+pub fn extract_bincue(chd: &Path, out_dir: &Path) -> Result<PathBuf, String> {
+    let cue_path = out_dir.join(format!("{}.cue", stem(chd)));
+    let bin_path = out_dir.join(bin_file_name(chd));
+    let mut on_progress = |_written: u64| {};
+    extract_to_cue(chd, &cue_path, &bin_path, &mut on_progress)
+        .map_err(|e| format!("Failed to extract {}: {e:?}", chd.display()))?;
+    Ok(cue_path)
+}
+
+fn bin_file_name(chd: &Path) -> String {
+    format!("{}.bin", stem(chd))
+}
+
+fn stem(chd: &Path) -> String {
+    chd.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "disc".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheet_lays_tracks_back_to_back() {
+        let tracks = [
+            CueTrack {
+                number: 1,
+                mode: "MODE1/2352",
+                frames: 75 * 60 + 10,
+                pregap: 0,
+            },
+            CueTrack {
+                number: 2,
+                mode: "AUDIO",
+                frames: 75 * 200,
+                pregap: 150,
+            },
+        ];
+        assert_eq!(
+            cue_sheet(&tracks, "Myst.bin"),
+            "FILE \"Myst.bin\" BINARY\r\n\
+             \x20 TRACK 01 MODE1/2352\r\n\
+             \x20   INDEX 01 00:00:00\r\n\
+             \x20 TRACK 02 AUDIO\r\n\
+             \x20   PREGAP 00:02:00\r\n\
+             \x20   INDEX 01 01:00:10\r\n"
+        );
+    }
+
+    #[test]
+    fn bin_sits_next_to_the_sheet() {
+        assert_eq!(
+            bin_file_name(Path::new("/roms/Myst (USA).chd")),
+            "Myst (USA).bin"
+        );
+    }
+}
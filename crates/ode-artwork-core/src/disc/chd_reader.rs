@@ -0,0 +1,151 @@
+//! CHD images read a hunk at a time, for the shared sector reader
+//! ([`super::sectors`]).
+//!
+//! A CHD stores the disc as compressed hunks of a few CD frames each
+//! (2352 bytes of sector plus 96 of subcode per frame). [`ChdReader`]
+//! presents the decompressed hunks as one run of bytes. Decoded hunks go
+//! into the process-wide [`hunk_cache`](super::hunk_cache), and the reader
+//! keeps the last one it used, so reading a hunk's frames one after another
+//! decompresses it once, and so does reading it again from another reader.
+//! Hunks are decoded into a buffer the reader reuses: the one it held
+//! before, or one the cache just evicted, so streaming a whole CHD with the
+//! cache full (or off) doesn't allocate per hunk.
+
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use libchdman_rs::Chd;
+
+use super::chd_cue::{tracks_of, CueTrack};
+use super::hunk_cache::{self, HunkKey};
+
+/// An open CHD, read by byte offset into its decompressed hunks. Shareable
+/// between threads; reads take turns on the one handle.
+pub struct ChdReader {
+    state: Mutex<State>,
+    hunk_bytes: u32,
+    hunk_count: u32,
+}
+
+struct State {
+    chd: Chd,
+    /// The file in `hunk_cache` keys
+    file: u64,
+    /// Index and bytes of the hunk used last
+    last: Option<(u32, Arc<Vec<u8>>)>,
+    /// A hunk-sized buffer nothing else holds, for the next decode
+    spare: Option<Vec<u8>>,
+}
+
+impl ChdReader {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let path_str = path.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("non-UTF-8 path: {}", path.display()),
+            )
+        })?;
+        let chd = Chd::open(path_str, false, None)
+            .map_err(|e| io::Error::other(format!("open CHD: {e:?}")))?;
+        let hunk_bytes = chd.hunk_bytes();
+        let hunk_count = chd.hunk_count();
+        Ok(Self {
+            state: Mutex::new(State {
+                chd,
+                file: hunk_cache::file_id(path),
+                last: None,
+                spare: None,
+            }),
+            hunk_bytes,
+            hunk_count,
+        })
+    }
+
+    /// The track layout, read from the CHD's metadata.
+    pub fn tracks(&self) -> io::Result<Vec<CueTrack>> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        tracks_of(&state.chd).map_err(io::Error::other)
+    }
+
+    /// Bytes per hunk.
+    pub fn hunk_bytes(&self) -> u32 {
+        self.hunk_bytes
+    }
+
+    /// Decompressed size in bytes.
+    pub fn len(&self) -> u64 {
+        u64::from(self.hunk_bytes) * u64::from(self.hunk_count)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fill as much of `buf` as the CHD has from `offset` on and return the
+    /// byte count, which is only short at the end.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let hunk_bytes = u64::from(self.hunk_bytes);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut filled = 0;
+        while filled < buf.len() {
+            let pos = offset + filled as u64;
+            let hunk = pos / hunk_bytes;
+            if hunk >= u64::from(self.hunk_count) {
+                break;
+            }
+            let data = state.hunk(hunk as u32, self.hunk_bytes)?;
+            let within = (pos % hunk_bytes) as usize;
+            let n = (buf.len() - filled).min(data.len() - within);
+            buf[filled..filled + n].copy_from_slice(&data[within..within + n]);
+            filled += n;
+        }
+        Ok(filled)
+    }
+}
+
+impl State {
+    /// The decompressed bytes of hunk `index`, decoding it unless it was
+    /// the last one read or is in the cache.
+    fn hunk(&mut self, index: u32, hunk_bytes: u32) -> io::Result<&[u8]> {
+        if !matches!(&self.last, Some((last, _)) if *last == index) {
+            let key = HunkKey {
+                file: self.file,
+                hunk: index,
+            };
+            let (data, recycled) = match hunk_cache::get(key) {
+                Some(data) => (data, None),
+                None => {
+                    let mut data = self
+                        .spare
+                        .take()
+                        .unwrap_or_else(|| vec![0u8; hunk_bytes as usize]);
+                    if let Err(e) = self.chd.read_hunk(index, &mut data) {
+                        self.spare = Some(data);
+                        return Err(io::Error::other(format!("read CHD hunk {index}: {e:?}")));
+                    }
+                    let data = Arc::new(data);
+                    let recycled = hunk_cache::insert(key, Arc::clone(&data));
+                    (data, recycled)
+                }
+            };
+            // The hunk held until now is free unless the cache (or another
+            // reader) still has it
+            let previous = self
+                .last
+                .replace((index, data))
+                .and_then(|(_, data)| Arc::try_unwrap(data).ok());
+            if self.spare.is_none() {
+                self.spare = recycled
+                    .into_iter()
+                    .chain(previous)
+                    .find(|buf| buf.len() == hunk_bytes as usize);
+            }
+        }
+        Ok(self
+            .last
+            .as_ref()
+            .map(|(_, data)| data.as_slice())
+            .unwrap_or_default())
+    }
+}
@@ -1,14 +1,14 @@
 //! Track-level hashing for redump lookup.
 //!
-//! Redump stores hashes of the raw 2352-byte per-sector BIN data, so for
-//! BIN/CUE we open the BIN directly and feed the raw track bytes through
-//! all three hashers in a single streaming pass. Plain ISO files are hashed
-//! end-to-end — that doesn't match the typical BIN-based redump entries but
-//! catches the minority of redump entries stored as raw .iso. CHD is not
-//! yet supported (it needs frame-by-frame subcode stripping; planned).
-
-use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+//! Redump stores hashes of the raw 2352-byte per-sector BIN data, so the
+//! first data track is fed through all three hashers in a single streaming
+//! pass, read through the [`ImageSectors`] the disc was opened with: the
+//! BIN of a BIN/CUE, the hunks of a CHD (without their subcode), or a plain
+//! ISO end-to-end — that doesn't match the typical BIN-based redump entries
+//! but catches the minority of redump entries stored as raw .iso. CHDs
+//! whose first track isn't raw data are extracted to a temporary BIN first.
+
+use std::io::Read;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
@@ -16,12 +16,13 @@ use crc32fast::Hasher as Crc32;
 use libchdman_rs::cd::{extract_to_cue, list_tracks};
 use libchdman_rs::Chd;
 use md5::{Digest as Md5Digest, Md5};
-use opticaldiscs::bincue::parse_cue_tracks;
 use opticaldiscs::formats::DiscFormat;
 use sha1::Sha1;
 use thiserror::Error;
 
 use super::reader::DiscInfo;
+use super::sectors::{ImageSectors, SectorsError};
+use crate::net::CancelToken;
 
 /// Read buffer size for streaming hash. 1 MiB balances syscall overhead
 /// against responsiveness of the progress counter.
@@ -79,6 +80,17 @@ impl From<opticaldiscs::error::OpticaldiscsError> for HashError {
     }
 }
 
+impl From<SectorsError> for HashError {
+    fn from(e: SectorsError) -> Self {
+        match e {
+            SectorsError::Io(e) => HashError::Io(e),
+            SectorsError::Layout(e) => HashError::Opticaldiscs(e),
+            SectorsError::NoDataTrack => HashError::NoDataTrack,
+            SectorsError::Unsupported(format) => HashError::Unsupported(format!("{format:?}")),
+        }
+    }
+}
+
 /// Compute SHA1 / MD5 / CRC32 of the first data track in `info` (or the
 /// whole file for plain ISOs). Calls into `progress` periodically; bails
 /// out with `Cancelled` if `progress.cancelled` flips true mid-flight.
@@ -86,101 +98,85 @@ pub fn hash_data_track(
     info: &DiscInfo,
     progress: Arc<Mutex<HashProgress>>,
 ) -> Result<TrackHashes, HashError> {
-    match info.format {
-        DiscFormat::BinCue => hash_bincue(&info.path, &progress),
-        DiscFormat::Iso => hash_iso(&info.path, &progress),
-        DiscFormat::Chd => hash_chd(&info.path, &progress),
-        // MDS/MDF (and anything else) not implemented yet.
-        other => Err(HashError::Unsupported(format!("{other:?}"))),
-    }
+    let tracker = Tracker {
+        shared: progress,
+        cancel: &CancelToken::new(),
+        on_update: &|_| {},
+    };
+    hash_with(info, &tracker)
 }
 
-fn hash_bincue(
-    cue_or_bin: &Path,
-    progress: &Arc<Mutex<HashProgress>>,
+/// [`hash_data_track`] for callers without a UI thread polling a shared
+/// [`HashProgress`]: `progress` is called on this thread with each update,
+/// and `cancel` stops the hashing between reads.
+pub fn hash_data_track_with_progress(
+    info: &DiscInfo,
+    cancel: &CancelToken,
+    progress: &dyn Fn(&HashProgress),
 ) -> Result<TrackHashes, HashError> {
-    // Accept either a .cue path or a .bin path with a sibling .cue.
-    let cue_path = if cue_or_bin
-        .extension()
-        .map(|e| e.eq_ignore_ascii_case("cue"))
-        .unwrap_or(false)
-    {
-        cue_or_bin.to_path_buf()
-    } else {
-        cue_or_bin.with_extension("cue")
+    let tracker = Tracker {
+        shared: Arc::default(),
+        cancel,
+        on_update: progress,
     };
+    hash_with(info, &tracker)
+}
 
-    if !cue_path.exists() {
-        return Err(HashError::Opticaldiscs(format!(
-            "missing cue sheet next to {}",
-            cue_or_bin.display()
-        )));
+fn hash_with(info: &DiscInfo, progress: &Tracker) -> Result<TrackHashes, HashError> {
+    // The reader `DiscReader::read` opened, or one of our own for a disc
+    // that only got identified by filename
+    let sectors = match info.image.as_ref().and_then(|image| image.sectors()) {
+        Some(sectors) => Ok(Arc::clone(sectors)),
+        None => ImageSectors::open(&info.path, info.format).map(Arc::new),
+    };
+    match (info.format, sectors) {
+        (DiscFormat::Chd, Ok(sectors)) if sectors.is_raw() => hash_sectors(&sectors, progress),
+        // Cooked or audio-first CHDs hash the BIN chdman writes for them
+        (DiscFormat::Chd, _) => hash_chd(&info.path, progress),
+        (_, Ok(sectors)) => hash_sectors(&sectors, progress),
+        // MDS/MDF (and anything else) not implemented yet.
+        (_, Err(e)) => Err(e.into()),
     }
+}
 
-    let tracks = parse_cue_tracks(&cue_path)?;
-    let track = tracks
-        .iter()
-        .find(|t| t.is_data())
-        .ok_or(HashError::NoDataTrack)?;
-
-    // Bytes to read: prefer the cue-declared frame_count when available;
-    // otherwise use the gap to the next track (or file end) as a fallback.
-    let total = if track.frame_count > 0 {
-        track.frame_count * track.sector_size()
-    } else {
-        let next_offset = tracks
-            .iter()
-            .filter(|t| t.bin_path == track.bin_path && t.file_byte_offset > track.file_byte_offset)
-            .map(|t| t.file_byte_offset)
-            .min();
-        match next_offset {
-            Some(end) => end.saturating_sub(track.file_byte_offset),
-            None => {
-                let len = std::fs::metadata(&track.bin_path)?.len();
-                len.saturating_sub(track.file_byte_offset)
-            }
-        }
-    };
+/// Where the hashing steps report to: the shared [`HashProgress`], plus
+/// the callback and cancel token of [`hash_data_track_with_progress`].
+struct Tracker<'a> {
+    shared: Arc<Mutex<HashProgress>>,
+    cancel: &'a CancelToken,
+    on_update: &'a dyn Fn(&HashProgress),
+}
 
-    let mut file = BufReader::new(File::open(&track.bin_path)?);
-    file.seek(SeekFrom::Start(track.file_byte_offset))?;
+impl Tracker<'_> {
+    /// Change `HashProgress` with `change`, then pass it to the callback.
+    fn update(&self, change: impl FnOnce(&mut HashProgress)) {
+        let mut p = self.shared.lock().unwrap();
+        change(&mut p);
+        (self.on_update)(&p);
+    }
 
-    let source = format!("BIN track {} (raw)", track.track_no);
-    {
-        let mut p = progress.lock().unwrap();
-        p.stage = format!("Hashing {source}");
-        p.total_bytes = total;
-        p.current_bytes = 0;
-        p.active = true;
+    /// Start a stage of `total` bytes.
+    fn stage(&self, stage: String, total: u64) {
+        self.update(|p| {
+            p.stage = stage;
+            p.total_bytes = total;
+            p.current_bytes = 0;
+            p.active = true;
+        });
     }
 
-    let hashed = stream_hash(&mut file, total, progress)?;
-    Ok(TrackHashes {
-        sha1: hashed.sha1,
-        md5: hashed.md5,
-        crc32: hashed.crc32,
-        size_bytes: hashed.size_bytes,
-        source,
-    })
+    fn cancelled(&self) -> bool {
+        self.cancel.is_cancelled() || self.shared.lock().unwrap().cancelled
+    }
 }
 
-fn hash_iso(
-    path: &Path,
-    progress: &Arc<Mutex<HashProgress>>,
-) -> Result<TrackHashes, HashError> {
-    let total = std::fs::metadata(path)?.len();
-    let mut file = BufReader::new(File::open(path)?);
-
-    let source = "ISO file".to_string();
-    {
-        let mut p = progress.lock().unwrap();
-        p.stage = format!("Hashing {source}");
-        p.total_bytes = total;
-        p.current_bytes = 0;
-        p.active = true;
-    }
+/// Hash the data track through `sectors`, as dumped.
+fn hash_sectors(sectors: &ImageSectors, progress: &Tracker) -> Result<TrackHashes, HashError> {
+    let total = sectors.track_len();
+    let source = sectors.source().to_string();
+    progress.stage(format!("Hashing {source}"), total);
 
-    let hashed = stream_hash(&mut file, total, progress)?;
+    let hashed = stream_hash(&mut sectors.track_reader(), total, progress)?;
     Ok(TrackHashes {
         sha1: hashed.sha1,
         md5: hashed.md5,
@@ -195,12 +191,12 @@ fn hash_iso(
 /// then hash track 1 of the BIN with the existing BIN/CUE path.
 ///
 /// Two stages get progress: "Extracting CHD" (libchdman-rs decompression)
-/// and "Hashing BIN track 1 (raw)" (handed off to `hash_bincue`). The
+/// and "Hashing BIN track 1 (raw)" (handed off to `hash_sectors`). The
 /// `RateTracker` resets its rolling window on stage change so the ETA stays
 /// sane across the transition.
 fn hash_chd(
     chd_path: &Path,
-    progress: &Arc<Mutex<HashProgress>>,
+    progress: &Tracker,
 ) -> Result<TrackHashes, HashError> {
     // Open the CHD just to enumerate tracks so we can guess a sensible
     // total-bytes target for the extract progress bar.
@@ -224,36 +220,26 @@ fn hash_chd(
     let cue_path = tmp.path().join("disc.cue");
     let bin_path = tmp.path().join("disc.bin");
 
-    {
-        let mut p = progress.lock().unwrap();
-        p.stage = "Extracting CHD".into();
-        p.total_bytes = extract_total;
-        p.current_bytes = 0;
-        p.active = true;
-        p.cancelled = false;
-    }
+    progress.update(|p| p.cancelled = false);
+    progress.stage("Extracting CHD".into(), extract_total);
 
     // libchdman-rs's extract callback fires per frame with the cumulative
     // bytes-written counter. Forward straight into HashProgress.
-    let progress_for_cb = Arc::clone(progress);
-    let mut cb = move |bytes_written: u64| {
-        if let Ok(mut p) = progress_for_cb.lock() {
-            p.current_bytes = bytes_written;
-        }
-    };
+    let mut cb = |bytes_written: u64| progress.update(|p| p.current_bytes = bytes_written);
 
     extract_to_cue(chd_path, &cue_path, &bin_path, &mut cb)
         .map_err(|e| HashError::Opticaldiscs(format!("CHD extract: {e:?}")))?;
 
     // Cancellation check between stages.
-    if progress.lock().unwrap().cancelled {
+    if progress.cancelled() {
         return Err(HashError::Cancelled);
     }
 
-    // Hand the extracted BIN/CUE to the regular hashing path. `hash_bincue`
+    // Hand the extracted BIN/CUE to the regular hashing path. `hash_sectors`
     // overwrites `stage`/`total_bytes`/`current_bytes`, so `RateTracker`
     // will see a new stage label and reset its rolling window.
-    let mut hashes = hash_bincue(&cue_path, progress)?;
+    let extracted = ImageSectors::open(&cue_path, DiscFormat::BinCue)?;
+    let mut hashes = hash_sectors(&extracted, progress)?;
 
     // Re-label so the success log says "from a CHD" rather than "from a
     // BIN" (which would be confusing — the user picked a .chd).
@@ -273,7 +259,7 @@ struct StreamHashResult {
 fn stream_hash<R: Read>(
     reader: &mut R,
     bytes_to_read: u64,
-    progress: &Arc<Mutex<HashProgress>>,
+    progress: &Tracker,
 ) -> Result<StreamHashResult, HashError> {
     let mut sha1 = Sha1::new();
     let mut md5 = Md5::new();
@@ -288,7 +274,7 @@ fn stream_hash<R: Read>(
     let mut bytes_since_update: u64 = 0;
 
     while remaining > 0 {
-        if progress.lock().unwrap().cancelled {
+        if progress.cancelled() {
             return Err(HashError::Cancelled);
         }
 
@@ -308,18 +294,16 @@ fn stream_hash<R: Read>(
         if bytes_since_update >= 16 * 1024 * 1024
             || last_update.elapsed() >= std::time::Duration::from_millis(250)
         {
-            let mut p = progress.lock().unwrap();
-            p.current_bytes = hashed;
+            progress.update(|p| p.current_bytes = hashed);
             last_update = std::time::Instant::now();
             bytes_since_update = 0;
         }
     }
 
-    {
-        let mut p = progress.lock().unwrap();
+    progress.update(|p| {
         p.current_bytes = hashed;
         p.active = false;
-    }
+    });
 
     Ok(StreamHashResult {
         sha1: hex::encode(sha1.finalize()),
@@ -328,3 +312,40 @@ fn stream_hash<R: Read>(
         size_bytes: hashed,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disc::DiscReader;
+    use std::cell::Cell;
+    use std::io::Write;
+
+    fn junk_iso(len: usize) -> (tempfile::NamedTempFile, Vec<u8>) {
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        let mut file = tempfile::Builder::new().suffix(".iso").tempfile().unwrap();
+        file.write_all(&data).unwrap();
+        (file, data)
+    }
+
+    #[test]
+    fn progress_callback_sees_every_byte() {
+        let (file, data) = junk_iso(3 * READ_BUF + 17);
+        let info = DiscReader::read(file.path()).unwrap();
+        let last = Cell::new(0);
+        let report = |p: &HashProgress| last.set(p.current_bytes);
+        let hashes = hash_data_track_with_progress(&info, &CancelToken::new(), &report).unwrap();
+        assert_eq!(hashes.sha1, hex::encode(Sha1::digest(&data)));
+        assert_eq!(hashes.size_bytes, data.len() as u64);
+        assert_eq!(last.get(), data.len() as u64);
+    }
+
+    #[test]
+    fn cancel_token_stops_hashing() {
+        let (file, _) = junk_iso(READ_BUF);
+        let info = DiscReader::read(file.path()).unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let result = hash_data_track_with_progress(&info, &cancel, &|_| {});
+        assert!(matches!(result, Err(HashError::Cancelled)));
+    }
+}
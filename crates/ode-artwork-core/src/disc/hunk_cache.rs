@@ -0,0 +1,195 @@
+//! Decompressed CHD hunks, kept in one least-recently-used cache for the
+//! whole process.
+//!
+//! Every [`ChdReader`](super::chd_reader::ChdReader) looks here before
+//! decompressing, so the reader identification opened (which browsing and
+//! hashing share) and any reader opened again for the same file later, such
+//! as a rescan or a folder-scan worker, find the hunks already decoded. The
+//! cache holds at most `chd_cache_mb` of hunks, the "CHD hunk cache" field
+//! of the `settings_general` grid in the GUI's Settings window; 0 turns it
+//! off.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+/// Default for `chd_cache_mb`.
+pub const DEFAULT_CACHE_MB: u32 = 64;
+
+static CACHE: LazyLock<Mutex<HunkCache>> = LazyLock::new(Mutex::default);
+
+/// A hunk of one CHD file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HunkKey {
+    /// See [`file_id`]
+    pub file: u64,
+    pub hunk: u32,
+}
+
+/// Identifies the CHD at `path` as it is now: the same file rewritten
+/// (a different size or modification time) gets a new id, so stale hunks
+/// are never served.
+pub fn file_id(path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    if let Ok(meta) = path.metadata() {
+        meta.len().hash(&mut hasher);
+        meta.modified()
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// The cached bytes of `key`, if any.
+pub fn get(key: HunkKey) -> Option<Arc<Vec<u8>>> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner()).get(key)
+}
+
+/// Keep `data` as the bytes of `key`, evicting the least recently used
+/// hunks beyond the configured size. Returns the buffer of an evicted hunk
+/// no reader holds any more, for the caller to decode into next.
+pub fn insert(key: HunkKey, data: Arc<Vec<u8>>) -> Option<Vec<u8>> {
+    let capacity = capacity_bytes(crate::config::get_config().chd_cache_mb);
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, data, capacity)
+}
+
+fn capacity_bytes(mb: u32) -> usize {
+    mb as usize * 1024 * 1024
+}
+
+#[derive(Debug, Default)]
+struct HunkCache {
+    entries: HashMap<HunkKey, Entry>,
+    /// Keys by last use, oldest first
+    by_use: BTreeMap<u64, HunkKey>,
+    /// Bytes held
+    used: usize,
+    /// Counts uses, for `by_use`
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    data: Arc<Vec<u8>>,
+    used_at: u64,
+}
+
+impl HunkCache {
+    fn get(&mut self, key: HunkKey) -> Option<Arc<Vec<u8>>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&key)?;
+        self.by_use.remove(&entry.used_at);
+        self.by_use.insert(self.clock, key);
+        entry.used_at = self.clock;
+        Some(Arc::clone(&entry.data))
+    }
+
+    fn insert(&mut self, key: HunkKey, data: Arc<Vec<u8>>, capacity: usize) -> Option<Vec<u8>> {
+        let mut recycled = self.remove(key).and_then(|old| Arc::try_unwrap(old).ok());
+        if data.len() <= capacity {
+            self.clock += 1;
+            self.used += data.len();
+            self.by_use.insert(self.clock, key);
+            self.entries.insert(
+                key,
+                Entry {
+                    data,
+                    used_at: self.clock,
+                },
+            );
+        }
+        // A smaller setting takes effect on the next insert
+        while self.used > capacity {
+            let Some((_, oldest)) = self.by_use.pop_first() else {
+                break;
+            };
+            let evicted = self.remove(oldest);
+            if recycled.is_none() {
+                recycled = evicted.and_then(|old| Arc::try_unwrap(old).ok());
+            }
+        }
+        recycled
+    }
+
+    fn remove(&mut self, key: HunkKey) -> Option<Arc<Vec<u8>>> {
+        let entry = self.entries.remove(&key)?;
+        self.by_use.remove(&entry.used_at);
+        self.used -= entry.data.len();
+        Some(entry.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(hunk: u32) -> HunkKey {
+        HunkKey { file: 7, hunk }
+    }
+
+    fn hunk(fill: u8) -> Arc<Vec<u8>> {
+        Arc::new(vec![fill; 100])
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used() {
+        let mut cache = HunkCache::default();
+        cache.insert(key(0), hunk(0), 300);
+        cache.insert(key(1), hunk(1), 300);
+        cache.insert(key(2), hunk(2), 300);
+        // Hunk 0 is now the most recently used, so 1 goes first
+        assert!(cache.get(key(0)).is_some());
+        cache.insert(key(3), hunk(3), 300);
+
+        assert!(cache.get(key(1)).is_none());
+        assert_eq!(cache.get(key(0)).unwrap()[0], 0);
+        assert_eq!(cache.get(key(3)).unwrap()[0], 3);
+        assert_eq!(cache.used, 300);
+    }
+
+    #[test]
+    fn zero_capacity_keeps_nothing() {
+        let mut cache = HunkCache::default();
+        cache.insert(key(0), hunk(0), 0);
+        assert!(cache.get(key(0)).is_none());
+        assert_eq!(cache.used, 0);
+    }
+
+    #[test]
+    fn shrinks_to_a_smaller_capacity() {
+        let mut cache = HunkCache::default();
+        for n in 0..4 {
+            cache.insert(key(n), hunk(n as u8), 400);
+        }
+        cache.insert(key(4), hunk(4), 200);
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.get(key(3)).is_some());
+        assert!(cache.get(key(4)).is_some());
+    }
+
+    #[test]
+    fn hands_back_evicted_buffers_nobody_holds() {
+        let mut cache = HunkCache::default();
+        let held = hunk(0);
+        cache.insert(key(0), Arc::clone(&held), 200);
+        cache.insert(key(1), hunk(1), 200);
+        // Hunk 0 is evicted but a reader still holds it
+        assert!(cache.insert(key(2), hunk(2), 200).is_none());
+        // Hunk 1 isn't held anywhere else
+        let recycled = cache.insert(key(3), hunk(3), 200).unwrap();
+        assert_eq!(recycled, vec![1; 100]);
+    }
+
+    #[test]
+    fn other_files_are_other_hunks() {
+        let mut cache = HunkCache::default();
+        cache.insert(key(0), hunk(1), 300);
+        assert!(cache.get(HunkKey { file: 8, hunk: 0 }).is_none());
+    }
+}
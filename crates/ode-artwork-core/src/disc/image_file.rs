@@ -0,0 +1,182 @@
+//! Local disc-image files (ISO, BIN) opened once for positional reads.
+//!
+//! With the `mmap` feature an [`ImageFile`] maps the whole file, so the
+//! file browser (through [`super::sectors`]), the hasher and the CD-DA
+//! player copy sectors straight out of memory instead of paying a seek and
+//! a read syscall per chunk. Without the feature, or
+//! when mapping fails (some network shares refuse it), reads go through the
+//! file as before.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+
+/// A disc image opened for reading at arbitrary offsets. Shareable between
+/// readers: every read says where it starts.
+#[derive(Debug)]
+pub struct ImageFile {
+    backing: Backing,
+    len: u64,
+}
+
+#[derive(Debug)]
+enum Backing {
+    File(Mutex<File>),
+    #[cfg(feature = "mmap")]
+    Mapped(Mmap),
+}
+
+impl ImageFile {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        #[cfg(feature = "mmap")]
+        {
+            // SAFETY: the map is only read. If another program truncates the
+            // image while it's mapped, reads past the new end fault; that's
+            // the usual trade-off for mapping user files, and images aren't
+            // rewritten while ODE has them open.
+            match unsafe { Mmap::map(&file) } {
+                Ok(map) => {
+                    return Ok(Self {
+                        backing: Backing::Mapped(map),
+                        len,
+                    })
+                }
+                Err(e) => log::debug!("Could not map {} ({e}), reading it instead", path.display()),
+            }
+        }
+
+        Ok(Self {
+            backing: Backing::File(Mutex::new(file)),
+            len,
+        })
+    }
+
+    /// File size in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether reads come from a memory map rather than the file.
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self.backing, Backing::File(_))
+    }
+
+    /// Fill as much of `buf` as the file has from `offset` on and return the
+    /// byte count, which is only short at the end of the file.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        match &self.backing {
+            Backing::File(file) => {
+                // Every read seeks first, so a reader that panicked holding
+                // the lock leaves nothing behind
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                file.seek(SeekFrom::Start(offset))?;
+                let mut filled = 0;
+                while filled < buf.len() {
+                    match file.read(&mut buf[filled..]) {
+                        Ok(0) => break,
+                        Ok(n) => filled += n,
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(filled)
+            }
+            #[cfg(feature = "mmap")]
+            Backing::Mapped(map) => {
+                let start = offset.min(map.len() as u64) as usize;
+                let n = buf.len().min(map.len() - start);
+                buf[..n].copy_from_slice(&map[start..start + n]);
+                Ok(n)
+            }
+        }
+    }
+
+    /// Like `read_at`, but running out of file is an `UnexpectedEof` error.
+    pub fn read_exact_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        if self.read_at(offset, buf)? < buf.len() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// A [`Read`] over the file starting at `offset`.
+    pub fn reader_at(&self, offset: u64) -> ImageReader<'_> {
+        ImageReader {
+            image: self,
+            pos: offset,
+        }
+    }
+}
+
+/// Sequential reads from an [`ImageFile`], see [`ImageFile::reader_at`].
+pub struct ImageReader<'a> {
+    image: &'a ImageFile,
+    pos: u64,
+}
+
+impl Read for ImageReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.image.read_at(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn image(bytes: &[u8]) -> (tempfile::NamedTempFile, ImageFile) {
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(bytes).unwrap();
+        f.flush().unwrap();
+        let image = ImageFile::open(f.path()).unwrap();
+        (f, image)
+    }
+
+    #[test]
+    fn reads_at_offsets_and_stops_at_the_end() {
+        let data: Vec<u8> = (0..=255).collect();
+        let (_f, image) = image(&data);
+        assert_eq!(image.len(), 256);
+
+        let mut buf = [0u8; 4];
+        image.read_exact_at(100, &mut buf).unwrap();
+        assert_eq!(buf, [100, 101, 102, 103]);
+
+        assert_eq!(image.read_at(254, &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], [254, 255]);
+        assert_eq!(image.read_at(300, &mut buf).unwrap(), 0);
+
+        let err = image.read_exact_at(254, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn reader_streams_from_offset() {
+        let data: Vec<u8> = (0..=255).collect();
+        let (_f, image) = image(&data);
+        let mut rest = Vec::new();
+        image.reader_at(250).read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, [250, 251, 252, 253, 254, 255]);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn maps_the_file() {
+        let (_f, image) = image(b"CD001");
+        assert!(image.is_mapped());
+    }
+}
@@ -8,12 +8,23 @@
 
 pub mod browse;
 pub mod cd_audio;
+pub mod cd_text;
+pub mod chd_artwork;
+pub mod chd_cue;
+pub mod chd_reader;
 pub mod content;
 pub mod hasher;
+pub mod hunk_cache;
 mod identifier;
+pub mod image_file;
 pub mod layout;
+pub mod rip;
 mod reader;
+pub mod scan;
+pub mod sectors;
 pub mod set_membership;
+pub mod volume_details;
+pub mod volume_icon;
 
 // Re-exports from opticaldiscs
 pub use opticaldiscs::formats::{supported_extensions, DiscFormat, FilesystemType};
@@ -25,9 +36,12 @@ pub use opticaldiscs::toc::{DiscTOC, TrackInfo};
 // ODE-specific re-exports
 pub use identifier::{normalize_volume_label, parse_filename, ConfidenceLevel, ParsedFilename};
 pub use cd_audio::{CdTrack, CDDA_CHANNELS, CDDA_SAMPLE_RATE};
+pub use cd_text::{read_cd_text, CdText};
 pub use content::{read_content, DiscContent};
 pub use layout::{detect_sector_layout, SectorLayout};
+pub use volume_details::{count_entries, EntryCounts};
+pub use volume_icon::read_volume_icon;
 pub use reader::{
     clear_log_callback, scan_cue_references, set_log_callback, CueReferenceScan,
-    DiscError, DiscInfo, DiscReader,
+    DiscError, DiscInfo, DiscReader, OpenedImage, ReadStage,
 };
@@ -3,6 +3,7 @@
 //! Unified interface for reading disc images in various formats.
 //! Delegates all format/filesystem detection to the `opticaldiscs` library.
 
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
@@ -15,6 +16,8 @@ use opticaldiscs::iso9660::PrimaryVolumeDescriptor;
 use opticaldiscs::toc::DiscTOC;
 
 use super::identifier::{parse_filename, normalize_volume_label, ConfidenceLevel, ParsedFilename};
+use super::sectors::ImageSectors;
+use crate::net::CancelToken;
 
 /// Callback for logging disc reading progress
 pub type LogCallback = Arc<Mutex<dyn FnMut(String) + Send>>;
@@ -82,6 +85,10 @@ pub enum DiscError {
         /// `missing.len() < total_refs` means it's partially broken.
         total_refs: usize,
     },
+
+    /// The cancel token of [`DiscReader::read_with_progress`] fired
+    #[error("{}", crate::net::CANCELLED)]
+    Cancelled,
 }
 
 impl From<opticaldiscs::error::OpticaldiscsError> for DiscError {
@@ -133,6 +140,41 @@ pub struct DiscInfo {
     /// `None` = not attempted; `Some(vec![])` = attempted, nothing cleared the
     /// floor.
     pub fuzzy_matches: Option<Vec<crate::db::FuzzyCandidate>>,
+    /// The opened image identification worked from, shared with the browse
+    /// layer. `None` when identification fell back to the filename.
+    pub image: Option<OpenedImage>,
+}
+
+/// An image `opticaldiscs` has already opened: the format, the parsed
+/// cue/CHD track layout and the volume descriptors, plus the reader for its
+/// data track. Cloning shares both, so browsing, counting entries, reading
+/// the volume icon and hashing don't open or parse the image again.
+#[derive(Clone)]
+pub struct OpenedImage {
+    info: Arc<DiscImageInfo>,
+    sectors: Option<Arc<ImageSectors>>,
+}
+
+impl OpenedImage {
+    pub(crate) fn info(&self) -> &DiscImageInfo {
+        &self.info
+    }
+
+    /// The data track, opened once when the disc was read. `None` for
+    /// formats ODE doesn't read itself (MDS/MDF, CHDs starting with audio)
+    /// or when opening it failed.
+    pub fn sectors(&self) -> Option<&Arc<ImageSectors>> {
+        self.sectors.as_ref()
+    }
+}
+
+impl fmt::Debug for OpenedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenedImage")
+            .field("format", &self.info.format)
+            .field("sectors", &self.sectors.as_ref().map(|s| s.source()))
+            .finish_non_exhaustive()
+    }
 }
 
 impl DiscInfo {
@@ -141,18 +183,38 @@ impl DiscInfo {
         self.path.with_extension("jpg")
     }
 
-    /// Check if cover art already exists for this disc
+    /// Check if cover art already exists for this disc, next to it or
+    /// stored inside a CHD
     pub fn has_cover_art(&self) -> bool {
-        self.cover_art_path().exists()
+        self.cover_art_path().exists() || super::chd_artwork::has_artwork(&self.path)
     }
 }
 
+/// Step [`DiscReader::read_with_progress`] is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadStage {
+    /// Checking that the BIN files a cue sheet names are there
+    CheckingCue,
+    /// Detecting the format and reading the filesystem headers and TOC
+    Opening,
+}
+
 /// Reader for disc images
 pub struct DiscReader;
 
 impl DiscReader {
     /// Read disc information from a file path
     pub fn read(path: &Path) -> Result<DiscInfo, DiscError> {
+        Self::read_with_progress(path, &CancelToken::new(), &|_| {})
+    }
+
+    /// Like `read`, calling `progress` as each step starts and giving up
+    /// between steps once `cancel` fires.
+    pub fn read_with_progress(
+        path: &Path,
+        cancel: &CancelToken,
+        progress: &dyn Fn(ReadStage),
+    ) -> Result<DiscInfo, DiscError> {
         if !path.exists() {
             return Err(DiscError::FileNotFound(path.to_path_buf()));
         }
@@ -169,6 +231,7 @@ impl DiscReader {
             .map(|e| e.eq_ignore_ascii_case("cue"))
             .unwrap_or(false);
         if is_cue {
+            progress(ReadStage::CheckingCue);
             let scan = scan_cue_references(path);
             if !scan.missing.is_empty() {
                 return Err(DiscError::BrokenCueReference {
@@ -179,8 +242,12 @@ impl DiscReader {
             }
         }
 
+        if cancel.is_cancelled() {
+            return Err(DiscError::Cancelled);
+        }
         let parsed_filename = parse_filename(path);
 
+        progress(ReadStage::Opening);
         disc_log!(info, "Opening disc image: {}", path.display());
 
         match DiscImageInfo::open(path) {
@@ -202,20 +269,36 @@ impl DiscReader {
                     (parsed_filename.title.clone(), ConfidenceLevel::Low)
                 };
 
+                let sectors = match ImageSectors::open(path, info.format) {
+                    Ok(sectors) => {
+                        let mapped = if sectors.is_mapped() { ", memory-mapped" } else { "" };
+                        disc_log!(debug, "Reading sectors from {}{}", sectors.source(), mapped);
+                        Some(Arc::new(sectors))
+                    }
+                    Err(e) => {
+                        disc_log!(debug, "Data track not opened for reading ({}), browsing through opticaldiscs", e);
+                        None
+                    }
+                };
+
                 Ok(DiscInfo {
                     path: path.to_path_buf(),
                     format: info.format,
                     filesystem: info.filesystem,
-                    volume_label: info.volume_label,
+                    volume_label: info.volume_label.clone(),
                     parsed_filename,
                     title,
                     confidence,
-                    pvd: info.pvd,
-                    toc: info.toc,
-                    hfs_mdb: info.hfs_mdb,
-                    hfsplus_header: info.hfsplus_header,
+                    pvd: info.pvd.clone(),
+                    toc: info.toc.clone(),
+                    hfs_mdb: info.hfs_mdb.clone(),
+                    hfsplus_header: info.hfsplus_header.clone(),
                     redump_matches: None,
                     fuzzy_matches: None,
+                    image: Some(OpenedImage {
+                        info: Arc::new(info),
+                        sectors,
+                    }),
                 })
             }
             Err(opticaldiscs::error::OpticaldiscsError::UnsupportedFormat(fmt)) => {
@@ -249,6 +332,7 @@ impl DiscReader {
                     hfsplus_header: None,
                     redump_matches: None,
                     fuzzy_matches: None,
+                    image: None,
                 })
             }
         }
@@ -386,6 +470,21 @@ mod tests {
         assert_eq!(info.filesystem, FilesystemType::Iso9660);
         assert_eq!(info.volume_label, Some("TEST_GAME".to_string()));
         assert_eq!(info.confidence, ConfidenceLevel::High);
+        assert!(info.image.is_some());
+    }
+
+    #[test]
+    fn test_read_reports_stages_and_stops_when_cancelled() {
+        let file = create_test_iso();
+        let stages = std::cell::RefCell::new(Vec::new());
+        let report = |stage| stages.borrow_mut().push(stage);
+        DiscReader::read_with_progress(file.path(), &CancelToken::new(), &report).unwrap();
+        assert_eq!(stages.borrow().as_slice(), [ReadStage::Opening]);
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let result = DiscReader::read_with_progress(file.path(), &cancel, &|_| {});
+        assert!(matches!(result, Err(DiscError::Cancelled)));
     }
 
     #[test]
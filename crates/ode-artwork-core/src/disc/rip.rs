@@ -0,0 +1,285 @@
+//! Ripping a disc's audio tracks to WAV or FLAC files.
+//!
+//! PCM comes from [`cd_audio`](super::cd_audio), so this covers the same CHD
+//! and BIN/CUE images the player does. Files are named `NN - Title.ext`,
+//! with titles taken from the cue sheet's CD-TEXT (`TITLE` lines) when
+//! present, else from whatever the caller looked up (MusicBrainz), else
+//! just `Track NN`.
+//!
+//! WAV is streamed to disk. FLAC is encoded per track in memory, since the
+//! encoder wants the whole signal up front; a CD track is at most ~800 MB of
+//! PCM and typically a few tens.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+
+use super::cd_audio::{self, CDDA_CHANNELS, CDDA_SAMPLE_RATE};
+use super::cd_text;
+
+/// Output container for ripped tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioFormat {
+    #[default]
+    Wav,
+    Flac,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 2] = [AudioFormat::Wav, AudioFormat::Flac];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "WAV",
+            AudioFormat::Flac => "FLAC",
+        }
+    }
+}
+
+/// Characters not allowed in file names on at least one platform.
+const RESERVED: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// File name for track `number`: `03 - Title.flac`, or `Track 03.flac`
+/// without a usable title.
+pub fn track_file_name(number: u32, title: Option<&str>, format: AudioFormat) -> String {
+    let title = title
+        .map(|t| {
+            let t: String = t
+                .chars()
+                .filter(|c| !RESERVED.contains(c) && !c.is_control())
+                .collect();
+            t.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .map(|t| t.trim_matches('.').to_string())
+        .filter(|t| !t.is_empty());
+    match title {
+        Some(title) => format!("{:02} - {}.{}", number, title, format.extension()),
+        None => format!("Track {:02}.{}", number, format.extension()),
+    }
+}
+
+/// Per-track CD-TEXT titles from the cue sheet of a BIN/CUE image. Empty for
+/// other formats or cues without `TITLE` lines under their tracks (the
+/// disc-level `TITLE` before the first `TRACK` is the album, not a track).
+pub fn cue_track_titles(path: &Path) -> BTreeMap<u32, String> {
+    cd_text::read_cd_text(path)
+        .map(|cd_text| cd_text.titles())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+fn parse_cue_titles(cue: &str) -> BTreeMap<u32, String> {
+    cd_text::parse_cd_text(cue).titles()
+}
+
+/// Rip `tracks` from the disc at `path` into `out_dir`, naming them from
+/// `titles`. `on_track` is called with each file as it's finished. Returns
+/// the written paths.
+pub fn rip_tracks<F>(
+    path: &Path,
+    tracks: &[u32],
+    out_dir: &Path,
+    format: AudioFormat,
+    titles: &BTreeMap<u32, String>,
+    on_track: F,
+) -> Result<Vec<PathBuf>, String>
+where
+    F: FnMut(&Path),
+{
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Failed to create {}: {}", out_dir.display(), e))?;
+
+    let mut ripper = Ripper {
+        out_dir,
+        format,
+        titles,
+        on_track,
+        current: None,
+        written: Vec::new(),
+    };
+    // PCM arrives through a callback, so hold the first write error and stop
+    // writing after it
+    let mut error = None;
+    cd_audio::extract_audio_tracks_pcm(path, tracks, |number, samples| {
+        if error.is_none() {
+            error = ripper.push(number, samples).err();
+        }
+    })?;
+    match error {
+        Some(e) => Err(e),
+        None => ripper.finish(),
+    }
+}
+
+/// Routes each batch of PCM to its track's file.
+struct Ripper<'a, F> {
+    out_dir: &'a Path,
+    format: AudioFormat,
+    titles: &'a BTreeMap<u32, String>,
+    on_track: F,
+    /// Track being written and its file
+    current: Option<(u32, PathBuf, TrackWriter)>,
+    written: Vec<PathBuf>,
+}
+
+impl<F: FnMut(&Path)> Ripper<'_, F> {
+    fn push(&mut self, number: u32, samples: &[i16]) -> Result<(), String> {
+        if self.current.as_ref().map(|(n, _, _)| *n) != Some(number) {
+            self.close_current()?;
+            let title = self.titles.get(&number).map(String::as_str);
+            let out = self
+                .out_dir
+                .join(track_file_name(number, title, self.format));
+            let writer = TrackWriter::create(&out, self.format)?;
+            self.current = Some((number, out, writer));
+        }
+        let (_, out, writer) = self.current.as_mut().expect("track file was just opened");
+        writer.push(samples, out)
+    }
+
+    fn close_current(&mut self) -> Result<(), String> {
+        if let Some((_, out, writer)) = self.current.take() {
+            writer.finish(&out)?;
+            (self.on_track)(&out);
+            self.written.push(out);
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<PathBuf>, String> {
+        self.close_current()?;
+        Ok(self.written)
+    }
+}
+
+/// One track being written.
+enum TrackWriter {
+    Wav(hound::WavWriter<BufWriter<File>>),
+    /// Interleaved samples, widened for the encoder
+    Flac(Vec<i32>),
+}
+
+impl TrackWriter {
+    fn create(path: &Path, format: AudioFormat) -> Result<Self, String> {
+        match format {
+            AudioFormat::Wav => {
+                let spec = hound::WavSpec {
+                    channels: CDDA_CHANNELS,
+                    sample_rate: CDDA_SAMPLE_RATE,
+                    bits_per_sample: 16,
+                    sample_format: hound::SampleFormat::Int,
+                };
+                hound::WavWriter::create(path, spec)
+                    .map(TrackWriter::Wav)
+                    .map_err(|e| format!("Failed to create {}: {}", path.display(), e))
+            }
+            AudioFormat::Flac => Ok(TrackWriter::Flac(Vec::new())),
+        }
+    }
+
+    fn push(&mut self, samples: &[i16], path: &Path) -> Result<(), String> {
+        match self {
+            TrackWriter::Wav(writer) => {
+                for &s in samples {
+                    writer
+                        .write_sample(s)
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                }
+            }
+            TrackWriter::Flac(buf) => buf.extend(samples.iter().map(|&s| i32::from(s))),
+        }
+        Ok(())
+    }
+
+    fn finish(self, path: &Path) -> Result<(), String> {
+        match self {
+            TrackWriter::Wav(writer) => writer
+                .finalize()
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e)),
+            TrackWriter::Flac(samples) => {
+                let bytes = encode_flac(&samples)
+                    .map_err(|e| format!("Failed to encode {}: {}", path.display(), e))?;
+                std::fs::write(path, bytes)
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+            }
+        }
+    }
+}
+
+/// Encode interleaved 16-bit stereo CD audio as a FLAC stream.
+fn encode_flac(samples: &[i32]) -> Result<Vec<u8>, String> {
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| format!("{:?}", e))?;
+    let source = flacenc::source::MemSource::from_samples(
+        samples,
+        CDDA_CHANNELS as usize,
+        16,
+        CDDA_SAMPLE_RATE as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| format!("{:?}", e))?;
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream.write(&mut sink).map_err(|e| format!("{:?}", e))?;
+    Ok(sink.as_slice().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_names_are_padded_and_sanitized() {
+        assert_eq!(
+            track_file_name(3, Some("Hey You?"), AudioFormat::Flac),
+            "03 - Hey You.flac"
+        );
+        assert_eq!(
+            track_file_name(12, Some("  AC/DC:  Live  "), AudioFormat::Wav),
+            "12 - ACDC Live.wav"
+        );
+        assert_eq!(
+            track_file_name(2, Some("..."), AudioFormat::Wav),
+            "Track 02.wav"
+        );
+        assert_eq!(track_file_name(2, None, AudioFormat::Wav), "Track 02.wav");
+    }
+
+    #[test]
+    fn cue_titles_skip_the_album_title() {
+        let cue = r#"PERFORMER "Pink Floyd"
+TITLE "The Wall"
+FILE "wall.bin" BINARY
+  TRACK 01 AUDIO
+    TITLE "In the Flesh?"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 01 03:19:00
+  TRACK 03 AUDIO
+    title "Another Brick in the Wall"
+    INDEX 01 06:00:00
+"#;
+        let titles = parse_cue_titles(cue);
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[&1], "In the Flesh?");
+        assert_eq!(titles[&3], "Another Brick in the Wall");
+    }
+
+    #[test]
+    fn flac_output_has_the_stream_marker() {
+        let samples: Vec<i32> = (0..4410 * 2).map(|i| (i % 200) - 100).collect();
+        let bytes = encode_flac(&samples).unwrap();
+        assert!(bytes.starts_with(b"fLaC"));
+    }
+}
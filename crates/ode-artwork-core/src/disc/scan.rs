@@ -0,0 +1,129 @@
+//! Finding disc images under a folder, for "Scan Folder...".
+//!
+//! Every file with a supported extension counts, except BIN/IMG tracks a cue
+//! sheet in the same folder already points at (the cue stands for the whole
+//! disc), macOS `._` resource forks, and anything under a hidden folder.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::supported_extensions;
+
+/// Disc images under `root`, sorted by path.
+pub fn find_disc_images(root: &Path) -> Vec<PathBuf> {
+    let extensions: Vec<String> = supported_extensions()
+        .iter()
+        .map(|e| e.to_ascii_lowercase())
+        .collect();
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(read) = fs::read_dir(&current) else {
+            continue;
+        };
+        let mut files = Vec::new();
+        for entry in read.flatten() {
+            let path = entry.path();
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                stack.push(path);
+            } else if extensions.contains(&extension(&path)) {
+                files.push(path);
+            }
+        }
+
+        // Tracks named by a cue in this folder, lower-cased
+        let tracks: HashSet<String> = files
+            .iter()
+            .filter(|p| extension(p) == "cue")
+            .filter_map(|cue| fs::read_to_string(cue).ok())
+            .flat_map(|text| cue_file_names(&text))
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+        out.extend(files.into_iter().filter(|p| {
+            let name = p
+                .file_name()
+                .map(|n| n.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+            extension(p) == "cue" || !tracks.contains(&name)
+        }));
+    }
+    out.sort();
+    out
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// File names from a cue sheet's `FILE` lines.
+fn cue_file_names(cue: &str) -> Vec<String> {
+    cue.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let rest = line
+                .get(..4)
+                .filter(|k| k.eq_ignore_ascii_case("FILE"))
+                .map(|_| line[4..].trim_start())?;
+            let name = match rest.chars().next()? {
+                quote @ ('"' | '\'') => rest[1..].split(quote).next()?,
+                _ => rest.split_whitespace().next()?,
+            };
+            Some(name.to_string()).filter(|n| !n.is_empty())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cue_tracks_and_hidden_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("Myst.cue"),
+            "FILE \"Myst (Track 1).BIN\" BINARY\n  TRACK 01 MODE1/2352\n",
+        )
+        .unwrap();
+        fs::write(root.join("Myst (Track 1).bin"), b"").unwrap();
+        fs::write(root.join("Loose.bin"), b"").unwrap();
+        fs::write(root.join("._Loose.bin"), b"").unwrap();
+        fs::write(root.join("notes.txt"), b"").unwrap();
+        fs::create_dir_all(root.join("PS1")).unwrap();
+        fs::write(root.join("PS1/Tekken.chd"), b"").unwrap();
+        fs::create_dir_all(root.join(".Trash")).unwrap();
+        fs::write(root.join(".Trash/Old.iso"), b"").unwrap();
+
+        let found: Vec<_> = find_disc_images(root)
+            .into_iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            found,
+            [
+                PathBuf::from("Loose.bin"),
+                PathBuf::from("Myst.cue"),
+                PathBuf::from("PS1/Tekken.chd"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cue_file_names_handle_quoting() {
+        let cue =
+            "FILE \"a b.bin\" BINARY\nfile 'c.bin' BINARY\nFILE d.bin BINARY\nTRACK 01 AUDIO\n";
+        assert_eq!(cue_file_names(cue), ["a b.bin", "c.bin", "d.bin"]);
+    }
+}
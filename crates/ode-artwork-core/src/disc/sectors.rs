@@ -0,0 +1,368 @@
+//! The data track of an opened image, read by ODE itself.
+//!
+//! [`DiscReader::read`](super::DiscReader::read) opens one [`ImageSectors`]
+//! per image and keeps it on the [`OpenedImage`](super::OpenedImage), so
+//! browsing (through opticaldiscs' `SectorReader`, see [`super::browse`])
+//! and hashing read through the same handle instead of opening and parsing
+//! the image again each time.
+//!
+//! Covers ISOs, BIN/CUE (the cue's first data track, in its own BIN) and
+//! CHDs whose first track is data. Other formats, and CHDs that start with
+//! audio, are left to opticaldiscs and the CHD extractor.
+
+use std::io::{self, Read};
+use std::path::Path;
+
+use opticaldiscs::bincue::parse_cue_tracks;
+use opticaldiscs::formats::DiscFormat;
+use thiserror::Error;
+
+use super::cd_audio::resolve_cue;
+use super::chd_reader::ChdReader;
+use super::image_file::ImageFile;
+
+/// User data in a sector, what filesystems address.
+pub const SECTOR_SIZE: usize = 2048;
+
+/// A raw CD sector: sync, header, user data and error correction.
+const RAW_SECTOR: u64 = 2352;
+/// A CHD frame: a raw sector followed by 96 bytes of subcode.
+const CHD_FRAME: u64 = 2448;
+/// 12-byte sync pattern that begins every raw sector.
+const SYNC: [u8; 12] = [
+    0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+];
+
+#[derive(Debug, Error)]
+pub enum SectorsError {
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Layout(String),
+    #[error("no data track found")]
+    NoDataTrack,
+    #[error("format not read by sector: {0:?}")]
+    Unsupported(DiscFormat),
+}
+
+/// Where the bytes come from.
+enum Backing {
+    /// An ISO, or the BIN holding the data track
+    File(ImageFile),
+    Chd(ChdReader),
+}
+
+impl Backing {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Backing::File(file) => file.read_at(offset, buf),
+            Backing::Chd(chd) => chd.read_at(offset, buf),
+        }
+    }
+}
+
+/// Where the data track lies in the backing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TrackLayout {
+    /// Byte offset of the track's first frame
+    start: u64,
+    /// Bytes from one frame to the next
+    stride: u64,
+    /// Bytes of each frame that belong to the track as dumped: 2352 for
+    /// raw sectors, 2048 for cooked ones
+    frame_len: u64,
+    /// Where the 2048 bytes of user data start in a frame
+    data_offset: u64,
+    /// Size of the track as dumped, `frame_len` per frame
+    track_bytes: u64,
+}
+
+impl TrackLayout {
+    /// The layout of a track at `start` whose frames are `stride` apart,
+    /// told apart by the first frame: raw sectors start with the sync
+    /// pattern and name their mode in the header.
+    fn detect(backing: &Backing, start: u64, stride: u64, frames: u64) -> io::Result<Self> {
+        let mut head = [0u8; 16];
+        let synced = stride >= RAW_SECTOR
+            && backing.read_at(start, &mut head)? == head.len()
+            && head[..12] == SYNC;
+        let (frame_len, data_offset) = match (synced, head[15]) {
+            // Mode 2 has an 8-byte subheader before the data
+            (true, 2) => (RAW_SECTOR, 24),
+            (true, _) => (RAW_SECTOR, 16),
+            // A BIN track of 2352-byte frames is dumped raw even when its
+            // first sector doesn't sync
+            (false, _) if stride == RAW_SECTOR => (RAW_SECTOR, 16),
+            (false, _) => (SECTOR_SIZE as u64, 0),
+        };
+        Ok(Self {
+            start,
+            stride,
+            frame_len,
+            data_offset,
+            track_bytes: frames * frame_len,
+        })
+    }
+
+    fn kind(&self) -> &'static str {
+        if self.frame_len == RAW_SECTOR {
+            "raw"
+        } else {
+            "cooked"
+        }
+    }
+}
+
+/// The first data track of an image, opened once and shared (in an `Arc`)
+/// by everything that reads it.
+pub struct ImageSectors {
+    backing: Backing,
+    layout: TrackLayout,
+    /// What's read, for log lines (e.g. "BIN track 1 (raw)")
+    source: String,
+}
+
+impl ImageSectors {
+    /// Open the data track of the `format` image at `path`.
+    pub fn open(path: &Path, format: DiscFormat) -> Result<Self, SectorsError> {
+        match format {
+            DiscFormat::Iso => {
+                let file = ImageFile::open(path)?;
+                let track_bytes = file.len();
+                Ok(Self {
+                    backing: Backing::File(file),
+                    layout: TrackLayout {
+                        start: 0,
+                        stride: SECTOR_SIZE as u64,
+                        frame_len: SECTOR_SIZE as u64,
+                        data_offset: 0,
+                        track_bytes,
+                    },
+                    source: "ISO file".to_string(),
+                })
+            }
+            DiscFormat::BinCue => Self::open_bincue(path),
+            DiscFormat::Chd => Self::open_chd(path),
+            other => Err(SectorsError::Unsupported(other)),
+        }
+    }
+
+    fn open_bincue(path: &Path) -> Result<Self, SectorsError> {
+        let cue = resolve_cue(path).map_err(SectorsError::Layout)?;
+        if !cue.exists() {
+            return Err(SectorsError::Layout(format!(
+                "missing cue sheet next to {}",
+                path.display()
+            )));
+        }
+        let tracks = parse_cue_tracks(&cue).map_err(|e| SectorsError::Layout(e.to_string()))?;
+        let track = tracks
+            .iter()
+            .find(|t| t.is_data())
+            .ok_or(SectorsError::NoDataTrack)?;
+        let file = ImageFile::open(&track.bin_path)?;
+        let stride = track.sector_size();
+
+        // Prefer the cue-declared frame count; otherwise the track runs to
+        // the next one in the same BIN, or to the end of the file
+        let frames = if track.frame_count > 0 {
+            track.frame_count
+        } else {
+            let end = tracks
+                .iter()
+                .filter(|t| {
+                    t.bin_path == track.bin_path && t.file_byte_offset > track.file_byte_offset
+                })
+                .map(|t| t.file_byte_offset)
+                .min()
+                .unwrap_or(file.len());
+            end.saturating_sub(track.file_byte_offset) / stride
+        };
+
+        let backing = Backing::File(file);
+        let layout = TrackLayout::detect(&backing, track.file_byte_offset, stride, frames)?;
+        Ok(Self {
+            backing,
+            layout,
+            source: format!("BIN track {} ({})", track.track_no, layout.kind()),
+        })
+    }
+
+    fn open_chd(path: &Path) -> Result<Self, SectorsError> {
+        let chd = ChdReader::open(path)?;
+        let tracks = chd.tracks()?;
+        // Tracks after the first sit behind chdman's padding frames, which
+        // the track list doesn't give; those discs go through the extractor
+        let first = tracks.first().ok_or(SectorsError::NoDataTrack)?;
+        if first.mode == "AUDIO" {
+            return Err(SectorsError::NoDataTrack);
+        }
+        let (number, frames) = (first.number, first.frames);
+        let backing = Backing::Chd(chd);
+        let layout = TrackLayout::detect(&backing, 0, CHD_FRAME, u64::from(frames))?;
+        Ok(Self {
+            backing,
+            layout,
+            source: format!("CHD track {number} ({})", layout.kind()),
+        })
+    }
+
+    /// What's read, for log lines.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether sectors are copied out of a memory map (an ISO or BIN with
+    /// the `mmap` feature) rather than read from the file or decompressed.
+    pub fn is_mapped(&self) -> bool {
+        match &self.backing {
+            Backing::File(file) => file.is_mapped(),
+            Backing::Chd(_) => false,
+        }
+    }
+
+    /// Whether the track holds raw 2352-byte sectors, the layout redump
+    /// hashes.
+    pub fn is_raw(&self) -> bool {
+        self.layout.frame_len == RAW_SECTOR
+    }
+
+    /// Size of the track as dumped, in bytes.
+    pub fn track_len(&self) -> u64 {
+        self.layout.track_bytes
+    }
+
+    /// Read the user data of sector `lba` into `buf`, which holds
+    /// [`SECTOR_SIZE`] bytes.
+    pub fn read_sector(&self, lba: u64, buf: &mut [u8]) -> io::Result<()> {
+        let layout = &self.layout;
+        let offset = layout.start + lba * layout.stride + layout.data_offset;
+        let buf = &mut buf[..SECTOR_SIZE];
+        if self.backing.read_at(offset, buf)? < buf.len() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// Fill `buf` from `offset` into the track as dumped (frames back to
+    /// back, without CHD subcode) and return the byte count, which is 0 at
+    /// the end of the track.
+    pub fn read_track_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let layout = &self.layout;
+        let left = layout.track_bytes.saturating_sub(offset);
+        let want = (buf.len() as u64).min(left) as usize;
+        if layout.stride == layout.frame_len {
+            return self
+                .backing
+                .read_at(layout.start + offset, &mut buf[..want]);
+        }
+        // Frames have bytes between them (CHD subcode): a frame at a time
+        let mut filled = 0;
+        while filled < want {
+            let pos = offset + filled as u64;
+            let (frame, within) = (pos / layout.frame_len, pos % layout.frame_len);
+            let n = (want - filled).min((layout.frame_len - within) as usize);
+            let at = layout.start + frame * layout.stride + within;
+            let read = self.backing.read_at(at, &mut buf[filled..filled + n])?;
+            filled += read;
+            if read < n {
+                break;
+            }
+        }
+        Ok(filled)
+    }
+
+    /// A [`Read`] over the track as dumped, from the start.
+    pub fn track_reader(&self) -> TrackReader<'_> {
+        TrackReader {
+            sectors: self,
+            pos: 0,
+        }
+    }
+}
+
+/// Sequential reads of a track, see [`ImageSectors::track_reader`].
+pub struct TrackReader<'a> {
+    sectors: &'a ImageSectors,
+    pos: u64,
+}
+
+impl Read for TrackReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.sectors.read_track_at(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// A raw mode 1 sector whose user data is `fill`.
+    fn raw_sector(fill: u8) -> Vec<u8> {
+        let mut sector = SYNC.to_vec();
+        sector.extend_from_slice(&[0, 2, 0, 1]);
+        sector.extend_from_slice(&[fill; SECTOR_SIZE]);
+        sector.resize(RAW_SECTOR as usize, 0xEC);
+        sector
+    }
+
+    fn write_temp(dir: &Path, name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = dir.join(name);
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(bytes)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn iso_sectors_are_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let data: Vec<u8> = (0..3 * SECTOR_SIZE)
+            .map(|i| (i / SECTOR_SIZE) as u8)
+            .collect();
+        let iso = write_temp(dir.path(), "disc.iso", &data);
+        let sectors = ImageSectors::open(&iso, DiscFormat::Iso).unwrap();
+        assert!(!sectors.is_raw());
+        assert_eq!(sectors.track_len(), data.len() as u64);
+
+        let mut buf = [0u8; SECTOR_SIZE];
+        sectors.read_sector(2, &mut buf).unwrap();
+        assert!(buf.iter().all(|&b| b == 2));
+    }
+
+    #[test]
+    fn raw_bin_sectors_skip_the_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin: Vec<u8> = (0..4).flat_map(raw_sector).collect();
+        write_temp(dir.path(), "disc.bin", &bin);
+        let cue = write_temp(
+            dir.path(),
+            "disc.cue",
+            b"FILE \"disc.bin\" BINARY\n  TRACK 01 MODE1/2352\n    INDEX 01 00:00:00\n",
+        );
+        let sectors = ImageSectors::open(&cue, DiscFormat::BinCue).unwrap();
+        assert!(sectors.is_raw());
+        assert_eq!(sectors.track_len(), bin.len() as u64);
+
+        let mut buf = [0u8; SECTOR_SIZE];
+        sectors.read_sector(3, &mut buf).unwrap();
+        assert!(buf.iter().all(|&b| b == 3));
+
+        let mut dumped = Vec::new();
+        sectors.track_reader().read_to_end(&mut dumped).unwrap();
+        assert_eq!(dumped, bin);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn file_sectors_are_mapped() {
+        let dir = tempfile::tempdir().unwrap();
+        let iso = write_temp(dir.path(), "disc.iso", &[0u8; SECTOR_SIZE]);
+        let sectors = ImageSectors::open(&iso, DiscFormat::Iso).unwrap();
+        assert!(sectors.is_mapped());
+    }
+}
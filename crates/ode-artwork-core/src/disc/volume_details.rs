@@ -0,0 +1,103 @@
+//! Extra ISO 9660 volume details for the Disc Information panel: the
+//! descriptor's text fields, and how many directories and files the disc
+//! holds, which helps tell apart releases that share a volume label.
+
+use crate::disc::browse::{open_filesystem, EntryType, FileEntry, Filesystem};
+use crate::disc::DiscInfo;
+
+/// Entries counted before giving up on a huge disc.
+const MAX_ENTRIES: usize = 500_000;
+
+/// Directory depth the count descends to.
+const MAX_DEPTH: usize = 32;
+
+/// Block size assumed when the descriptor gives none.
+const DEFAULT_BLOCK_SIZE: u64 = 2048;
+
+/// Directories and files on a disc.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntryCounts {
+    pub directories: usize,
+    pub files: usize,
+    /// The walk stopped at [`MAX_ENTRIES`] or [`MAX_DEPTH`], so these are
+    /// lower bounds.
+    pub truncated: bool,
+}
+
+/// Count the directories and files on the disc. `None` if its filesystem
+/// can't be opened.
+pub fn count_entries(info: &DiscInfo) -> Option<EntryCounts> {
+    let mut fs = open_filesystem(info).ok()?;
+    let root = fs.root().ok()?;
+    let mut counts = EntryCounts::default();
+    walk(&mut *fs, &root, 0, &mut counts);
+    Some(counts)
+}
+
+fn walk(fs: &mut dyn Filesystem, dir: &FileEntry, depth: usize, counts: &mut EntryCounts) {
+    if depth > MAX_DEPTH {
+        counts.truncated = true;
+        return;
+    }
+    let Ok(children) = fs.list_directory(dir) else {
+        return;
+    };
+    for child in children {
+        if counts.directories + counts.files >= MAX_ENTRIES {
+            counts.truncated = true;
+            return;
+        }
+        match child.entry_type {
+            EntryType::Directory => {
+                counts.directories += 1;
+                walk(fs, &child, depth + 1, counts);
+            }
+            EntryType::File => counts.files += 1,
+        }
+    }
+}
+
+/// A descriptor text field without its space (or NUL) padding; `None` when
+/// the mastering tool left it blank.
+pub fn id_field(field: &str) -> Option<&str> {
+    let field = field.trim_matches(|c: char| c == ' ' || c == '\0');
+    (!field.is_empty()).then_some(field)
+}
+
+/// Bytes in a volume of `sectors` logical blocks of `block_size` bytes.
+pub fn volume_bytes(sectors: u64, block_size: u64) -> u64 {
+    let block_size = match block_size {
+        0 => DEFAULT_BLOCK_SIZE,
+        n => n,
+    };
+    sectors * block_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_field_drops_padding() {
+        assert_eq!(
+            id_field("PLAYSTATION                     "),
+            Some("PLAYSTATION")
+        );
+        assert_eq!(id_field("SONY\0\0\0"), Some("SONY"));
+        assert_eq!(id_field("  MY DISC  "), Some("MY DISC"));
+    }
+
+    #[test]
+    fn id_field_blank_is_none() {
+        assert_eq!(id_field(""), None);
+        assert_eq!(id_field("        "), None);
+        assert_eq!(id_field("\0\0\0\0"), None);
+    }
+
+    #[test]
+    fn volume_bytes_uses_block_size() {
+        assert_eq!(volume_bytes(333_000, 2048), 681_984_000);
+        assert_eq!(volume_bytes(10, 512), 5120);
+        assert_eq!(volume_bytes(10, 0), 20_480);
+    }
+}
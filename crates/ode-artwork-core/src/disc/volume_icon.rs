@@ -0,0 +1,175 @@
+//! The icon a disc gives itself, shown next to its volume label.
+//!
+//! PC discs name one with `icon=` in `autorun.inf`; Mac OS X discs carry a
+//! `.VolumeIcon.icns` at the root. Icons inside an `.exe` aren't extracted,
+//! and classic Mac discs keep theirs in the resource fork of an `Icon\r`
+//! file, which the filesystem layer doesn't expose; those discs show none.
+
+use image::DynamicImage;
+
+use crate::disc::browse::{open_filesystem, FileEntry, Filesystem};
+use crate::disc::DiscInfo;
+
+/// Largest icon file read off the disc.
+const MAX_ICON_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Root file holding a Mac OS X volume's custom icon.
+const MAC_VOLUME_ICON: &str = ".VolumeIcon.icns";
+
+/// `autorun.inf` icons the image crate can decode.
+const ICON_EXTENSIONS: &[&str] = &["ico", "bmp", "png"];
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// The disc's own icon, if it has one we can read.
+pub fn read_volume_icon(info: &DiscInfo) -> Option<DynamicImage> {
+    let mut fs = open_filesystem(info).ok()?;
+    let root = fs.root().ok()?;
+    let entries = fs.list_directory(&root).ok()?;
+    let find = |name: &str| {
+        entries
+            .iter()
+            .find(|e| e.is_file() && same_name(&e.name, name))
+            .cloned()
+    };
+
+    if let Some(entry) = find(MAC_VOLUME_ICON) {
+        let icon = read_small(&mut *fs, &entry)
+            .and_then(|bytes| icns_png(&bytes).and_then(|png| image::load_from_memory(png).ok()));
+        if icon.is_some() {
+            return icon;
+        }
+    }
+
+    let inf = find("autorun.inf")?;
+    let text = String::from_utf8_lossy(&read_small(&mut *fs, &inf)?).into_owned();
+    let entry = resolve(&mut *fs, &root, &autorun_icon(&text)?)?;
+    image::load_from_memory(&read_small(&mut *fs, &entry)?).ok()
+}
+
+fn read_small(fs: &mut dyn Filesystem, entry: &FileEntry) -> Option<Vec<u8>> {
+    if entry.size > MAX_ICON_BYTES {
+        return None;
+    }
+    fs.read_file(entry).ok()
+}
+
+/// Case-insensitive name match that ignores ISO 9660 `;1` version suffixes.
+fn same_name(entry_name: &str, wanted: &str) -> bool {
+    let bare = entry_name.split(';').next().unwrap_or(entry_name);
+    bare.trim_end_matches('.').eq_ignore_ascii_case(wanted)
+}
+
+/// Follow a `\`- or `/`-separated path down from `root`.
+fn resolve(fs: &mut dyn Filesystem, root: &FileEntry, path: &str) -> Option<FileEntry> {
+    let mut entry = root.clone();
+    for part in path
+        .split(['\\', '/'])
+        .filter(|p| !p.is_empty() && *p != ".")
+    {
+        entry = fs
+            .list_directory(&entry)
+            .ok()?
+            .into_iter()
+            .find(|e| same_name(&e.name, part))?;
+    }
+    entry.is_file().then_some(entry)
+}
+
+/// Path of the `icon=` file in `autorun.inf`'s `[autorun]` section, without
+/// its `,index` suffix. `None` for icons we can't decode, such as an `.exe`.
+fn autorun_icon(inf: &str) -> Option<String> {
+    let mut in_autorun = false;
+    for line in inf.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_autorun = line.eq_ignore_ascii_case("[autorun]");
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if !in_autorun || !key.trim().eq_ignore_ascii_case("icon") {
+            continue;
+        }
+        let path = value.split(',').next().unwrap_or_default();
+        let path = path.trim().trim_matches('"').trim();
+        let extension = path.rsplit_once('.').map(|(_, e)| e.to_ascii_lowercase());
+        return extension
+            .filter(|e| ICON_EXTENSIONS.contains(&e.as_str()))
+            .map(|_| path.to_string());
+    }
+    None
+}
+
+/// The largest PNG in an `.icns` file. Mac OS X 10.5 and later store their
+/// bigger sizes as PNG; the older packed bitmaps are skipped.
+fn icns_png(icns: &[u8]) -> Option<&[u8]> {
+    if icns.get(..4)? != b"icns" {
+        return None;
+    }
+    let mut best: Option<&[u8]> = None;
+    let mut at = 8;
+    while let Some(header) = icns.get(at..at + 8) {
+        let len = u32::from_be_bytes(header[4..8].try_into().ok()?) as usize;
+        if len < 8 {
+            break;
+        }
+        let Some(data) = icns.get(at + 8..at + len) else {
+            break;
+        };
+        if data.starts_with(PNG_SIGNATURE) && best.is_none_or(|b| data.len() > b.len()) {
+            best = Some(data);
+        }
+        at += len;
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn autorun_icon_drops_the_index_and_quotes() {
+        let inf = "[AutoRun]\r\nopen=setup.exe\r\nicon=\"Data\\Disc.ico\",0\r\n";
+        assert_eq!(autorun_icon(inf).as_deref(), Some("Data\\Disc.ico"));
+    }
+
+    #[test]
+    fn autorun_icon_skips_executables_and_other_sections() {
+        assert_eq!(autorun_icon("[autorun]\nicon=setup.exe,1\n"), None);
+        assert_eq!(autorun_icon("[other]\nicon=disc.ico\n"), None);
+        assert_eq!(autorun_icon("open=setup.exe\n"), None);
+    }
+
+    #[test]
+    fn names_match_without_case_or_version() {
+        assert!(same_name("AUTORUN.INF;1", "autorun.inf"));
+        assert!(same_name("DISC.ICO", "Disc.ico"));
+        assert!(!same_name("DISC.ICO", "disc.bmp"));
+    }
+
+    #[test]
+    fn icns_takes_the_largest_png() {
+        let entry = |kind: &[u8], data: &[u8]| {
+            let mut out = kind.to_vec();
+            out.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+            out.extend_from_slice(data);
+            out
+        };
+        let small = [PNG_SIGNATURE, b"small"].concat();
+        let large = [PNG_SIGNATURE, b"larger one"].concat();
+        let body = [
+            entry(b"is32", b"packed bitmap bytes"),
+            entry(b"ic07", &small),
+            entry(b"ic08", &large),
+        ]
+        .concat();
+        let mut icns = b"icns".to_vec();
+        icns.extend_from_slice(&(body.len() as u32 + 8).to_be_bytes());
+        icns.extend_from_slice(&body);
+
+        assert_eq!(icns_png(&icns), Some(large.as_slice()));
+        assert_eq!(icns_png(b"not an icns file"), None);
+    }
+}
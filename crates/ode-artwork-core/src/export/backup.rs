@@ -0,0 +1,143 @@
+//! Keep the previous cover when an export overwrites it.
+//!
+//! Saving over hand-picked art by mistake is easy in bulk mode (one Enter
+//! too many). Before an output is written, an existing file is moved into
+//! an `.artwork-backup/` folder beside it, and the export result lists
+//! every file written with where the one it replaced went, so the GUI can
+//! undo the whole save. Fix Artwork copies its originals into the same
+//! folder name at the scanned root.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder (next to the artwork) that receives overwritten covers.
+pub const BACKUP_DIR_NAME: &str = ".artwork-backup";
+
+/// A file an export wrote, for undoing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrittenFile {
+    pub path: PathBuf,
+    /// Where the file previously at `path` was moved, or `None` if the
+    /// export created it
+    pub backup: Option<PathBuf>,
+}
+
+/// Move `path` into `.artwork-backup/` if it exists. The backup is named
+/// `<stem>.<unix time>.<ext>` so earlier backups of the same cover are
+/// kept. Returns the backup's path, or `None` if there was nothing to keep.
+pub fn backup_existing(path: &Path) -> Result<Option<PathBuf>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .join(BACKUP_DIR_NAME);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let ext = path.extension().map(|e| e.to_string_lossy()).unwrap_or_default();
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut backup = dir.join(format!("{}.{}.{}", stem, secs, ext));
+    let mut n = 1;
+    while backup.exists() {
+        n += 1;
+        backup = dir.join(format!("{}.{}-{}.{}", stem, secs, n, ext));
+    }
+
+    fs::rename(path, &backup).map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    Ok(Some(backup))
+}
+
+/// Undo an export of `output`: put `backup` back in its place, or delete
+/// `output` when the export created it (`backup` is `None`).
+pub fn restore_backup(output: &Path, backup: Option<&Path>) -> Result<(), String> {
+    match backup {
+        Some(backup) => fs::rename(backup, output)
+            .map_err(|e| format!("Failed to restore {}: {}", backup.display(), e)),
+        None => match fs::remove_file(output) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove {}: {}", output.display(), e)),
+        },
+    }
+}
+
+/// Undo a whole export: restore or remove each of `written`, last written
+/// first. Keeps going past a failure so one locked file doesn't strand the
+/// rest; the errors are joined.
+pub fn undo_export(written: &[WrittenFile]) -> Result<(), String> {
+    let errors: Vec<String> = written
+        .iter()
+        .rev()
+        .filter_map(|file| restore_backup(&file.path, file.backup.as_deref()).err())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_and_restore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cover = dir.path().join("game.jpg");
+        fs::write(&cover, b"original").unwrap();
+
+        let backup = backup_existing(&cover).unwrap().unwrap();
+        assert!(!cover.exists());
+        assert!(backup.starts_with(dir.path().join(BACKUP_DIR_NAME)));
+
+        fs::write(&cover, b"new").unwrap();
+        restore_backup(&cover, Some(&backup)).unwrap();
+        assert_eq!(fs::read(&cover).unwrap(), b"original");
+        assert!(!backup.exists());
+    }
+
+    #[test]
+    fn nothing_to_back_up_means_undo_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cover = dir.path().join("game.jpg");
+        assert!(backup_existing(&cover).unwrap().is_none());
+
+        fs::write(&cover, b"new").unwrap();
+        restore_backup(&cover, None).unwrap();
+        assert!(!cover.exists());
+    }
+
+    #[test]
+    fn undo_export_restores_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let cover = dir.path().join("game.jpg");
+        let sidecar = dir.path().join("game.artwork.json");
+        fs::write(&cover, b"original").unwrap();
+
+        let written = vec![
+            WrittenFile {
+                backup: backup_existing(&cover).unwrap(),
+                path: cover.clone(),
+            },
+            WrittenFile {
+                backup: backup_existing(&sidecar).unwrap(),
+                path: sidecar.clone(),
+            },
+        ];
+        fs::write(&cover, b"new").unwrap();
+        fs::write(&sidecar, b"{}").unwrap();
+
+        undo_export(&written).unwrap();
+        assert_eq!(fs::read(&cover).unwrap(), b"original");
+        assert!(!sidecar.exists());
+    }
+}
@@ -0,0 +1,211 @@
+//! "Disc N" overlay badge for multi-disc artwork.
+//!
+//! Real jewel-case sets traditionally share box art across discs but stamp
+//! the disc face with a number. We mirror that here: composite a small
+//! semi-transparent badge in a corner of the saved cover (bottom-right by
+//! default) so the ODE picker can visually disambiguate discs in the same
+//! game.
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use crate::config::{BadgePosition, BadgeStyle};
+
+/// Bundled font. ~127 KB, Apache-2.0. Keeps the badge code self-contained
+/// rather than depending on a system font that varies per OS.
+pub(super) const FONT_BYTES: &[u8] = include_bytes!("../../assets/fonts/RobotoMono-Bold.ttf");
+
+/// Format "Disc N" or "Disc N/M" depending on whether the set total is known.
+pub fn format_label(n: u32, total: Option<u32>) -> String {
+    match total {
+        Some(t) if t > 1 => format!("Disc {n}/{t}"),
+        _ => format!("Disc {n}"),
+    }
+}
+
+/// Draw a badge with `label` onto `img` — either a `format_label` "Disc N"
+/// string or a role marker ("Install", "Game", "Bonus"). Operates on an RGBA
+/// buffer converted from the input. The output is returned as an RGB-ready
+/// `DynamicImage` so the JPEG encoder downstream doesn't need to know about
+/// the alpha channel we used for the overlay.
+///
+/// `BadgeStyle::Circle` shows just the disc number in a round badge; role
+/// markers have no number and keep the pill.
+pub fn apply_label_badge(
+    img: DynamicImage,
+    label: &str,
+    position: BadgePosition,
+    style: BadgeStyle,
+) -> DynamicImage {
+    match (style, disc_number_text(label)) {
+        (BadgeStyle::Circle, Some(number)) => apply_badge_text(img, number, position, true),
+        _ => apply_badge_text(img, label, position, false),
+    }
+}
+
+/// "2" from "Disc 2" or "Disc 2/3"; `None` for role markers.
+fn disc_number_text(label: &str) -> Option<&str> {
+    let rest = label.strip_prefix("Disc ")?;
+    let number = rest.split('/').next().unwrap_or(rest);
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then_some(number)
+}
+
+fn apply_badge_text(
+    img: DynamicImage,
+    text: &str,
+    position: BadgePosition,
+    round: bool,
+) -> DynamicImage {
+    let font = match FontVec::try_from_vec(FONT_BYTES.to_vec()) {
+        Ok(f) => f,
+        Err(_) => return img, // Font load failed: leave the image untouched.
+    };
+
+    let mut rgba: RgbaImage = img.to_rgba8();
+    let (w, h) = (rgba.width() as i32, rgba.height() as i32);
+
+    // Scale the badge so it's readable but not dominant. Use 8% of the
+    // shorter dimension as the cap height; this lands around 40px on a
+    // 500px artwork, which is roughly the proportion a real Disc N stamp
+    // had on a CD label.
+    let short = w.min(h) as f32;
+    let cap_h = (short * 0.08).round().max(14.0);
+    let scale = PxScale::from(cap_h * 1.35); // PxScale ~= line height in px.
+    let scaled = font.as_scaled(scale);
+
+    // Pre-measure the glyph run to size the background pill.
+    let mut run_w = 0.0f32;
+    for ch in text.chars() {
+        let g = scaled.scaled_glyph(ch);
+        run_w += scaled.h_advance(g.id);
+    }
+    let pad_x = (cap_h * 0.55).round();
+    let pad_y = (cap_h * 0.30).round();
+    let mut pill_w = (run_w + pad_x * 2.0).round() as i32;
+    let mut pill_h = (cap_h + pad_y * 2.0).round() as i32;
+    if round {
+        // A circle wide enough for the number; one digit sits with the same
+        // padding as the pill's height.
+        let d = pill_h.max((run_w + pad_y * 2.0).round() as i32);
+        pill_w = d;
+        pill_h = d;
+    }
+    let margin = (short * 0.025).round() as i32;
+    let pill_x = match position {
+        BadgePosition::TopLeft | BadgePosition::BottomLeft => margin,
+        BadgePosition::TopRight | BadgePosition::BottomRight => w - pill_w - margin,
+    };
+    let pill_y = match position {
+        BadgePosition::TopLeft | BadgePosition::TopRight => margin,
+        BadgePosition::BottomLeft | BadgePosition::BottomRight => h - pill_h - margin,
+    };
+
+    // Backing pill: semi-transparent black so the label stays legible over
+    // both light and dark cover art. Rounded corners drawn by skipping a
+    // quarter-circle in each corner; a corner radius of half the height
+    // makes the circle badge.
+    let corner = if round {
+        pill_h / 2
+    } else {
+        (cap_h * 0.30).round() as i32
+    };
+    let bg = Rgba([0u8, 0, 0, 200]);
+    for y in pill_y..pill_y + pill_h {
+        for x in pill_x..pill_x + pill_w {
+            if x < 0 || y < 0 || x >= w || y >= h {
+                continue;
+            }
+            if in_rounded_rect(x, y, pill_x, pill_y, pill_w, pill_h, corner) {
+                blend_pixel(&mut rgba, x as u32, y as u32, bg);
+            }
+        }
+    }
+
+    // Text: white, drawn glyph-by-glyph onto the pill, centered in it.
+    let text_x = pill_x + ((pill_w as f32 - run_w) / 2.0).round() as i32;
+    let baseline = pill_y + ((pill_h as f32 - cap_h) / 2.0).round() as i32 + scaled.ascent() as i32;
+    imageproc::drawing::draw_text_mut(
+        &mut rgba,
+        Rgba([255u8, 255, 255, 255]),
+        text_x,
+        baseline - scaled.ascent() as i32,
+        scale,
+        &font,
+        text,
+    );
+
+    DynamicImage::ImageRgba8(rgba)
+}
+
+fn in_rounded_rect(x: i32, y: i32, rx: i32, ry: i32, w: i32, h: i32, r: i32) -> bool {
+    // Quick interior check.
+    let cx = if x < rx + r {
+        rx + r
+    } else if x >= rx + w - r {
+        rx + w - 1 - r
+    } else {
+        return true;
+    };
+    let cy = if y < ry + r {
+        ry + r
+    } else if y >= ry + h - r {
+        ry + h - 1 - r
+    } else {
+        return true;
+    };
+    let dx = (x - cx) as i64;
+    let dy = (y - cy) as i64;
+    dx * dx + dy * dy <= (r as i64) * (r as i64)
+}
+
+fn blend_pixel(img: &mut RgbaImage, x: u32, y: u32, src: Rgba<u8>) {
+    let dst = img.get_pixel_mut(x, y);
+    let a = src[3] as u16;
+    let inv = 255 - a;
+    for c in 0..3 {
+        let new = (src[c] as u16 * a + dst[c] as u16 * inv) / 255;
+        dst[c] = new as u8;
+    }
+    dst[3] = 255;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn white() -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 200, Rgb([255, 255, 255])))
+    }
+
+    /// True if anything in the 40x40 corner block at (x, y) was darkened.
+    fn corner_marked(img: &RgbaImage, x: u32, y: u32) -> bool {
+        (y..y + 40).any(|py| (x..x + 40).any(|px| img.get_pixel(px, py)[0] < 128))
+    }
+
+    #[test]
+    fn badge_lands_in_the_chosen_corner() {
+        let out = apply_label_badge(white(), "Disc 2", BadgePosition::TopLeft, BadgeStyle::Pill)
+            .to_rgba8();
+        assert!(corner_marked(&out, 0, 0));
+        assert!(!corner_marked(&out, 160, 160));
+
+        let out = apply_label_badge(
+            white(),
+            "Disc 2",
+            BadgePosition::BottomRight,
+            BadgeStyle::Circle,
+        )
+        .to_rgba8();
+        assert!(corner_marked(&out, 160, 160));
+        assert!(!corner_marked(&out, 0, 0));
+    }
+
+    #[test]
+    fn circle_shows_only_the_disc_number() {
+        assert_eq!(disc_number_text("Disc 2"), Some("2"));
+        assert_eq!(disc_number_text("Disc 10/12"), Some("10"));
+        assert_eq!(disc_number_text("Install"), None);
+        assert_eq!(disc_number_text("Disc "), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! Name bookkeeping for the central artwork folder.
+//!
+//! With `output_dir` set, every disc's art lands in one folder, so two discs
+//! in different directories can expand the filename template to the same
+//! name (two "Myst (USA).iso" dumps, or a `{title}` template across a
+//! multi-region set). The folder keeps a small index mapping each name to
+//! the disc that owns it: re-saving a disc reuses its name, while a
+//! different disc gets a numbered one (`Myst (2)`) instead of overwriting.
+//! A name only goes into the index once its cover is written, so a failed
+//! export doesn't keep it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Index file kept in the central folder.
+pub const INDEX_FILE_NAME: &str = ".artwork-index.json";
+
+/// Give up numbering after this many clashes; something is wrong by then.
+const MAX_SUFFIX: u32 = 999;
+
+/// Names handed out but not written yet, by `dir.join(name)`. They count
+/// as taken, so two exports running at once can't pick the same one.
+static PENDING: Mutex<BTreeMap<PathBuf, Pending>> = Mutex::new(BTreeMap::new());
+
+struct Pending {
+    dir: PathBuf,
+    key: String,
+    owner: String,
+}
+
+/// Claim `name` (relative to `dir`, without extension) for `disc_path`.
+/// Returns the name to use: `name` itself if it's free or already this
+/// disc's, otherwise the first free `name (N)`. A file at `<name>.<ext>`
+/// that isn't in the index counts as taken. A new name is held until
+/// [`record_written`] adds it to the index.
+pub fn claim_name(dir: &Path, name: &Path, disc_path: &Path, ext: &str) -> Result<PathBuf, String> {
+    let index = load_index(&dir.join(INDEX_FILE_NAME))?;
+    let owner = disc_path.display().to_string();
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+
+    for n in 1..=MAX_SUFFIX {
+        let candidate = if n == 1 {
+            name.to_path_buf()
+        } else {
+            numbered(name, n)
+        };
+        let key = candidate.to_string_lossy().replace('\\', "/");
+        let on_disk = dir.join(format!("{}.{}", candidate.display(), ext));
+        let held = pending.get(&dir.join(&candidate)).map(|p| &p.owner);
+        match index.get(&key).or(held) {
+            Some(existing) if *existing == owner => return Ok(candidate),
+            Some(_) => continue,
+            None if on_disk.exists() => continue,
+            None => {
+                let base = dir.join(&candidate);
+                let dir = dir.to_path_buf();
+                pending.insert(base, Pending { dir, key, owner });
+                return Ok(candidate);
+            }
+        }
+    }
+    Err(format!(
+        "Too many artwork files named {} in {}",
+        name.display(),
+        dir.display()
+    ))
+}
+
+/// Add the name held for `base` (`dir.join(name)` as `claim_name` handed it
+/// out) to the index, now that its cover is written. Does nothing for a
+/// name that isn't held.
+pub fn record_written(base: &Path) -> Result<(), String> {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(claim) = pending.remove(base) else {
+        return Ok(());
+    };
+    let index_path = claim.dir.join(INDEX_FILE_NAME);
+    let mut index = load_index(&index_path)?;
+    index.insert(claim.key, claim.owner);
+    save_index(&claim.dir, &index_path, &index)
+}
+
+/// Let go of the name held for `base` without indexing it, when its cover
+/// couldn't be written. Does nothing for a name that isn't held.
+pub fn release_claim(base: &Path) {
+    PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(base);
+}
+
+/// `Myst` -> `Myst (2)`, keeping any subfolder.
+fn numbered(name: &Path, n: u32) -> PathBuf {
+    let file = name
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    name.with_file_name(format!("{} ({})", file, n))
+}
+
+fn load_index(path: &Path) -> Result<BTreeMap<String, String>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn save_index(dir: &Path, path: &Path, index: &BTreeMap<String, String>) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize artwork index: {}", e))?;
+    crate::config::write_atomically(path, &json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clashing_discs_get_numbered_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let name = Path::new("Myst");
+        let usa = Path::new("/roms/usa/Myst.iso");
+        let eur = Path::new("/roms/eur/Myst.iso");
+
+        assert_eq!(
+            claim_name(dir.path(), name, usa, "jpg").unwrap(),
+            PathBuf::from("Myst")
+        );
+        assert_eq!(
+            claim_name(dir.path(), name, eur, "jpg").unwrap(),
+            PathBuf::from("Myst (2)")
+        );
+        // Re-saving keeps each disc's own name.
+        assert_eq!(
+            claim_name(dir.path(), name, usa, "jpg").unwrap(),
+            PathBuf::from("Myst")
+        );
+        assert_eq!(
+            claim_name(dir.path(), name, eur, "jpg").unwrap(),
+            PathBuf::from("Myst (2)")
+        );
+    }
+
+    #[test]
+    fn names_are_indexed_once_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_path = dir.path().join(INDEX_FILE_NAME);
+        let disc = Path::new("/roms/Myst.iso");
+        let name = claim_name(dir.path(), Path::new("Myst"), disc, "jpg").unwrap();
+        assert!(!index_path.exists());
+
+        record_written(&dir.path().join(&name)).unwrap();
+        let index = load_index(&index_path).unwrap();
+        assert_eq!(
+            index.get("Myst").map(String::as_str),
+            Some("/roms/Myst.iso")
+        );
+        // Recording again is a no-op
+        record_written(&dir.path().join(&name)).unwrap();
+    }
+
+    #[test]
+    fn released_names_are_free_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let name = Path::new("Loom");
+        let first = claim_name(dir.path(), name, Path::new("/roms/a/Loom.iso"), "jpg").unwrap();
+        release_claim(&dir.path().join(&first));
+
+        let second = claim_name(dir.path(), name, Path::new("/roms/b/Loom.iso"), "jpg").unwrap();
+        assert_eq!(second, PathBuf::from("Loom"));
+        release_claim(&dir.path().join(&second));
+        assert!(!dir.path().join(INDEX_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn unindexed_files_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Riven.jpg"), b"old").unwrap();
+        let claimed = claim_name(
+            dir.path(),
+            Path::new("Riven"),
+            Path::new("/roms/Riven.iso"),
+            "jpg",
+        );
+        assert_eq!(claimed.unwrap(), PathBuf::from("Riven (2)"));
+    }
+}
@@ -0,0 +1,372 @@
+//! Content-aware square cropping.
+//!
+//! Loose scans and screenshots often carry scanner margins, letterbox bars or
+//! a flat backdrop around the actual cover. A plain center-crop keeps those
+//! and loses real artwork instead. This trims uniform-colour borders first,
+//! then slides the square along the long axis toward the busiest part of what
+//! remains, with a pull back toward the middle so ordinary covers still come
+//! out centered.
+
+use image::{DynamicImage, GenericImageView, GrayImage, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+
+/// Analysis runs on a thumbnail with this longest edge; the result is mapped
+/// back onto the full-size image.
+const ANALYSIS_SIZE: u32 = 512;
+
+/// Per-channel difference from the border colour still counted as border.
+const BORDER_TOLERANCE: i32 = 24;
+
+/// Share of a row/column that must match the border colour.
+const BORDER_FRACTION: f32 = 0.98;
+
+/// Never trim more than this much of a dimension from one side. Hitting the
+/// limit means the "border" is really a flat part of the cover, so that side
+/// is left alone.
+const MAX_TRIM_FRACTION: f32 = 0.4;
+
+/// How strongly the crop window is pulled toward the center (0 = pure
+/// detail-seeking, 1 = never leaves the center unless detail dominates).
+const CENTER_BIAS: f32 = 0.35;
+
+/// Rectangle in image coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Crop `img` to a square, trimming uniform borders and biasing toward the
+/// detailed part of the cover. Returns whether anything was cut away.
+pub fn smart_crop_to_square(img: DynamicImage) -> (DynamicImage, bool) {
+    let (width, height) = img.dimensions();
+    let square = smart_square(&img);
+    if square.width == width && square.height == height {
+        return (img, false);
+    }
+    let cropped = img.crop_imm(square.x, square.y, square.width, square.height);
+    (cropped, true)
+}
+
+/// Remove uniform-colour borders (scanner margins, letterbox bars) without
+/// squaring the image. Used before padding, where cropping isn't wanted.
+pub fn trim_borders(img: DynamicImage) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    if width == 0 || height == 0 {
+        return img;
+    }
+    let content = Analysis::new(&img).content;
+    if content.width == width && content.height == height {
+        return img;
+    }
+    img.crop_imm(content.x, content.y, content.width, content.height)
+}
+
+/// Thumbnail plus the detected content box, in both thumbnail and
+/// full-size coordinates.
+struct Analysis {
+    thumb: RgbImage,
+    /// Content box on the thumbnail.
+    small: Region,
+    /// Content box on the full-size image.
+    content: Region,
+    sx: f32,
+    sy: f32,
+}
+
+impl Analysis {
+    fn new(img: &DynamicImage) -> Self {
+        let (width, height) = img.dimensions();
+        let thumb = if width.max(height) > ANALYSIS_SIZE {
+            img.thumbnail(ANALYSIS_SIZE, ANALYSIS_SIZE).to_rgb8()
+        } else {
+            img.to_rgb8()
+        };
+        let sx = width as f32 / thumb.width() as f32;
+        let sy = height as f32 / thumb.height() as f32;
+
+        let small = detect_content(&thumb);
+        let x = ((small.x as f32 * sx) as u32).min(width - 1);
+        let y = ((small.y as f32 * sy) as u32).min(height - 1);
+        let content = Region {
+            x,
+            y,
+            width: ((small.width as f32 * sx) as u32).clamp(1, width - x),
+            height: ((small.height as f32 * sy) as u32).clamp(1, height - y),
+        };
+        Self {
+            thumb,
+            small,
+            content,
+            sx,
+            sy,
+        }
+    }
+}
+
+/// The square region `smart_crop_to_square` would keep.
+pub fn smart_square(img: &DynamicImage) -> Region {
+    let (width, height) = img.dimensions();
+    if width == 0 || height == 0 {
+        return Region {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+    }
+
+    let Analysis {
+        thumb,
+        small,
+        content,
+        sx,
+        sy,
+    } = Analysis::new(img);
+
+    let side = content.width.min(content.height);
+    if content.width == content.height {
+        return Region {
+            width: side,
+            height: side,
+            ..content
+        };
+    }
+
+    let gray = image::imageops::grayscale(&thumb);
+    if content.width > content.height {
+        let profile = column_energy(&gray, &small);
+        let window = ((side as f32 / sx) as usize).clamp(1, profile.len());
+        let offset = best_window(&profile, window) as f32 * sx;
+        let x = content.x + (offset as u32).min(content.width - side);
+        Region {
+            x,
+            y: content.y,
+            width: side,
+            height: side,
+        }
+    } else {
+        let profile = row_energy(&gray, &small);
+        let window = ((side as f32 / sy) as usize).clamp(1, profile.len());
+        let offset = best_window(&profile, window) as f32 * sy;
+        let y = content.y + (offset as u32).min(content.height - side);
+        Region {
+            x: content.x,
+            y,
+            width: side,
+            height: side,
+        }
+    }
+}
+
+/// Bounding box of `img` with uniform-colour borders removed.
+pub fn detect_content(img: &RgbImage) -> Region {
+    let (w, h) = img.dimensions();
+    let row = |y: u32| (0..w).map(move |x| *img.get_pixel(x, y));
+    let col = |x: u32| (0..h).map(move |y| *img.get_pixel(x, y));
+
+    let top = trim_side(h, |i| row(i).collect());
+    let bottom = trim_side(h, |i| row(h - 1 - i).collect());
+    let left = trim_side(w, |i| col(i).collect());
+    let right = trim_side(w, |i| col(w - 1 - i).collect());
+
+    Region {
+        x: left,
+        y: top,
+        width: w.saturating_sub(left + right).max(1),
+        height: h.saturating_sub(top + bottom).max(1),
+    }
+}
+
+/// How many lines (from the outside in) match the outermost line's colour.
+fn trim_side<F>(len: u32, line: F) -> u32
+where
+    F: Fn(u32) -> Vec<Rgb<u8>>,
+{
+    let limit = (len as f32 * MAX_TRIM_FRACTION) as u32;
+    if limit == 0 {
+        return 0;
+    }
+    let reference = mean_colour(&line(0));
+    let mut n = 0;
+    while n < limit && is_uniform(&line(n), reference) {
+        n += 1;
+    }
+    if n >= limit {
+        0
+    } else {
+        n
+    }
+}
+
+fn mean_colour(pixels: &[Rgb<u8>]) -> [i32; 3] {
+    let mut sum = [0i64; 3];
+    for p in pixels {
+        for (s, v) in sum.iter_mut().zip(p.0) {
+            *s += v as i64;
+        }
+    }
+    let n = pixels.len().max(1) as i64;
+    [
+        (sum[0] / n) as i32,
+        (sum[1] / n) as i32,
+        (sum[2] / n) as i32,
+    ]
+}
+
+fn is_uniform(pixels: &[Rgb<u8>], reference: [i32; 3]) -> bool {
+    if pixels.is_empty() {
+        return false;
+    }
+    let matching = pixels
+        .iter()
+        .filter(|p| (0..3).all(|c| (p[c] as i32 - reference[c]).abs() <= BORDER_TOLERANCE))
+        .count();
+    matching as f32 >= pixels.len() as f32 * BORDER_FRACTION
+}
+
+/// Gradient magnitude summed per column of `region`.
+fn column_energy(gray: &GrayImage, region: &Region) -> Vec<f32> {
+    let mut out = vec![0.0; region.width as usize];
+    for (i, x) in (region.x..region.x + region.width).enumerate() {
+        for y in region.y..region.y + region.height {
+            out[i] += gradient(gray, x, y);
+        }
+    }
+    out
+}
+
+/// Gradient magnitude summed per row of `region`.
+fn row_energy(gray: &GrayImage, region: &Region) -> Vec<f32> {
+    let mut out = vec![0.0; region.height as usize];
+    for (i, y) in (region.y..region.y + region.height).enumerate() {
+        for x in region.x..region.x + region.width {
+            out[i] += gradient(gray, x, y);
+        }
+    }
+    out
+}
+
+fn gradient(gray: &GrayImage, x: u32, y: u32) -> f32 {
+    let (w, h) = gray.dimensions();
+    let at = |x: u32, y: u32| gray.get_pixel(x.min(w - 1), y.min(h - 1))[0] as f32;
+    let here = at(x, y);
+    (at(x + 1, y) - here).abs() + (at(x, y + 1) - here).abs()
+}
+
+/// Start index of the `window`-long span of `profile` with the most energy,
+/// discounted by distance from the centered position.
+fn best_window(profile: &[f32], window: usize) -> usize {
+    let max_offset = profile.len().saturating_sub(window);
+    if max_offset == 0 {
+        return 0;
+    }
+    let center = max_offset / 2;
+
+    let total: f32 = profile.iter().sum();
+    if total <= f32::EPSILON {
+        return center;
+    }
+
+    let mut sum: f32 = profile[..window].iter().sum();
+    let mut best = (center, f32::MIN);
+    for offset in 0..=max_offset {
+        if offset > 0 {
+            sum += profile[offset + window - 1] - profile[offset - 1];
+        }
+        let distance = offset.abs_diff(center) as f32 / center.max(1) as f32;
+        let score = sum * (1.0 - CENTER_BIAS * distance);
+        if score > best.1 || (score == best.1 && offset.abs_diff(center) < best.0.abs_diff(center))
+        {
+            best = (offset, score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A noisy "cover" of `cover` size on a flat `canvas`, placed at (x, y).
+    fn framed(canvas: (u32, u32), cover: (u32, u32), at: (u32, u32), border: [u8; 3]) -> RgbImage {
+        let mut img = RgbImage::from_pixel(canvas.0, canvas.1, Rgb(border));
+        for y in 0..cover.1 {
+            for x in 0..cover.0 {
+                let v = ((x * 31 + y * 17) % 200) as u8 + 20;
+                img.put_pixel(at.0 + x, at.1 + y, Rgb([v, 255 - v, v / 2]));
+            }
+        }
+        img
+    }
+
+    #[test]
+    fn trims_scanner_margins() {
+        let img = framed((300, 260), (200, 200), (40, 20), [250, 250, 250]);
+        assert_eq!(
+            detect_content(&img),
+            Region {
+                x: 40,
+                y: 20,
+                width: 200,
+                height: 200
+            }
+        );
+    }
+
+    #[test]
+    fn letterboxed_cover_crops_to_the_cover() {
+        // 16:9 frame with black bars around a square cover.
+        let img = DynamicImage::ImageRgb8(framed((320, 180), (160, 160), (80, 10), [0, 0, 0]));
+        let region = smart_square(&img);
+        assert_eq!(
+            region,
+            Region {
+                x: 80,
+                y: 10,
+                width: 160,
+                height: 160
+            }
+        );
+    }
+
+    #[test]
+    fn flat_image_is_center_cropped() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 200, Rgb([90, 90, 90])));
+        let region = smart_square(&img);
+        assert_eq!(
+            region,
+            Region {
+                x: 50,
+                y: 0,
+                width: 200,
+                height: 200
+            }
+        );
+    }
+
+    #[test]
+    fn crop_leans_toward_detail() {
+        // Detail on the right third, flat on the left.
+        let img =
+            DynamicImage::ImageRgb8(framed((300, 100), (100, 100), (200, 0), [128, 128, 128]));
+        assert_eq!(smart_square(&img).x, 200);
+        let (cropped, was_cropped) = smart_crop_to_square(img);
+        assert!(was_cropped);
+        assert_eq!(cropped.dimensions(), (100, 100));
+    }
+
+    #[test]
+    fn trim_borders_keeps_aspect() {
+        let img = DynamicImage::ImageRgb8(framed((300, 400), (200, 300), (50, 40), [0, 0, 0]));
+        assert_eq!(trim_borders(img).dimensions(), (200, 300));
+    }
+
+    #[test]
+    fn best_window_prefers_center_on_ties() {
+        assert_eq!(best_window(&[1.0; 10], 4), 3);
+        assert_eq!(best_window(&[0.0, 0.0, 0.0, 0.0, 9.0, 9.0], 2), 4);
+    }
+}
@@ -0,0 +1,437 @@
+//! Decoding downloaded candidate images.
+//!
+//! Image hosts increasingly serve AVIF or HEIC, which the `image` crate
+//! can't decode in a default build. Those, and PDF scans, are optional Cargo
+//! features since they link native libraries:
+//!
+//! - `avif`: enables the `image` crate's dav1d-based AVIF decoder
+//! - `heic`: decodes HEIC/HEIF through libheif
+//! - `pdf`: renders the first page of a PDF (cover scans on archive.org)
+//!   through pdfium, loaded at runtime from next to the executable or the
+//!   system library path
+//!
+//! Without them such files still fail, but with an error naming the format
+//! and the feature instead of a generic "unsupported format".
+//!
+//! Logo and label sources sometimes return SVG. Those are rasterized with
+//! resvg, and PDF pages rendered, at a caller-chosen size (twice the output,
+//! see `ExportSettings::raster_size`) so the usual downscale applies.
+//!
+//! Previews of JPEGs, most of what image hosts serve, are scaled down by
+//! the decoder itself, so a large scan is never held at full size just to
+//! be shown.
+
+use image::{DynamicImage, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use thiserror::Error;
+
+/// Why image bytes couldn't be decoded.
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    /// Not a format `image` reads, or a damaged file
+    #[error("Failed to load image: {0}")]
+    Image(#[source] image::ImageError),
+
+    /// A format this build leaves out; `feature` is the Cargo feature
+    /// that adds it
+    #[error("{what} need a build with the `{feature}` feature")]
+    MissingFeature {
+        what: &'static str,
+        feature: &'static str,
+    },
+
+    /// An SVG, PDF or HEIC file its decoder couldn't read
+    #[error("{0}")]
+    Format(String),
+}
+
+/// For callers that only report the error.
+impl From<DecodeError> for String {
+    fn from(e: DecodeError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Container formats recognised from the ISO-BMFF `ftyp` box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeifKind {
+    Avif,
+    Heic,
+}
+
+/// Brands that mark an AVIF file.
+const AVIF_BRANDS: [&[u8]; 2] = [b"avif", b"avis"];
+
+/// Brands that mark a HEIC/HEIF file.
+const HEIC_BRANDS: [&[u8]; 8] = [
+    b"heic", b"heix", b"heim", b"heis", b"hevc", b"hevx", b"mif1", b"msf1",
+];
+
+/// How far into a file to look for the `<svg` tag.
+const SVG_SNIFF_LEN: usize = 1024;
+
+/// Decode image bytes of any supported format. SVGs and PDF pages are
+/// rasterized with their longer edge at `raster_edge` pixels.
+pub fn decode_image(data: &[u8], raster_edge: u32) -> Result<DynamicImage, DecodeError> {
+    let err = match image::load_from_memory(data) {
+        Ok(img) => return Ok(img),
+        Err(e) => e,
+    };
+    if looks_like_svg(data) {
+        return rasterize_svg(data, raster_edge).map_err(DecodeError::Format);
+    }
+    if data.starts_with(b"%PDF-") {
+        #[cfg(feature = "pdf")]
+        return pdf::first_page(data, raster_edge).map_err(DecodeError::Format);
+        #[cfg(not(feature = "pdf"))]
+        return Err(DecodeError::MissingFeature {
+            what: "PDF files",
+            feature: "pdf",
+        });
+    }
+    match heif_kind(data) {
+        #[cfg(feature = "heic")]
+        Some(HeifKind::Heic) => heic::decode(data).map_err(DecodeError::Format),
+        #[cfg(not(feature = "heic"))]
+        Some(HeifKind::Heic) => Err(DecodeError::MissingFeature {
+            what: "HEIC images",
+            feature: "heic",
+        }),
+        #[cfg(not(feature = "avif"))]
+        Some(HeifKind::Avif) => Err(DecodeError::MissingFeature {
+            what: "AVIF images",
+            feature: "avif",
+        }),
+        _ => Err(DecodeError::Image(err)),
+    }
+}
+
+/// A decoded image shrunk for showing, not saving.
+#[derive(Debug, Clone)]
+pub struct PreviewImage {
+    /// At most `max_edge` on its longer side
+    pub image: DynamicImage,
+    /// At most `render_edge` on its longer side, for rendering the output
+    /// as it will be saved. `None` when `image` wasn't shrunk, so it is the
+    /// source itself.
+    pub render_source: Option<DynamicImage>,
+    /// Width and height of the source, before shrinking
+    pub source_size: (u32, u32),
+}
+
+/// [`decode_image`] for a preview pane: anything over `max_edge` on its
+/// longer side is shrunk to fit, with a second copy bounded by
+/// `render_edge` for output renders. JPEGs are decoded at the smallest
+/// scale that still covers both, and the full-size pixels of other formats
+/// are dropped before returning, so an 8000px scan costs a few MB rather
+/// than a few hundred. Meant for a worker thread; save from the original
+/// bytes.
+pub fn decode_preview(
+    data: &[u8],
+    raster_edge: u32,
+    max_edge: u32,
+    render_edge: u32,
+) -> Result<PreviewImage, DecodeError> {
+    let (image, source_size) = match jpeg::decode_scaled(data, max_edge.max(render_edge)) {
+        Some(decoded) => decoded,
+        None => {
+            let image = decode_image(data, raster_edge)?;
+            let size = (image.width(), image.height());
+            (image, size)
+        }
+    };
+    let shrink = |image: &DynamicImage, edge: u32| {
+        (image.width().max(image.height()) > edge).then(|| image.thumbnail(edge, edge))
+    };
+    let Some(preview) = shrink(&image, max_edge) else {
+        return Ok(PreviewImage {
+            image,
+            render_source: None,
+            source_size,
+        });
+    };
+    let render_source = shrink(&image, render_edge).unwrap_or(image);
+    Ok(PreviewImage {
+        image: preview,
+        render_source: Some(render_source),
+        source_size,
+    })
+}
+
+/// Sniff the `ftyp` box: major brand first, then the compatible brands.
+/// AVIF files also list `mif1`, so any AVIF brand wins.
+fn heif_kind(data: &[u8]) -> Option<HeifKind> {
+    if data.len() < 16 || &data[4..8] != b"ftyp" {
+        return None;
+    }
+    let box_len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let end = box_len.clamp(16, data.len());
+    // Major brand, minor version, then compatible brands
+    let brands: Vec<&[u8]> = std::iter::once(&data[8..12])
+        .chain(data[16..end].chunks_exact(4))
+        .collect();
+    if brands.iter().any(|b| AVIF_BRANDS.contains(b)) {
+        Some(HeifKind::Avif)
+    } else if brands.iter().any(|b| HEIC_BRANDS.contains(b)) {
+        Some(HeifKind::Heic)
+    } else {
+        None
+    }
+}
+
+/// XML that opens an `<svg>` element near the start.
+fn looks_like_svg(data: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&data[..data.len().min(SVG_SNIFF_LEN)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    (head.starts_with("<?xml") || head.starts_with("<!DOCTYPE") || head.starts_with("<svg"))
+        && head.contains("<svg")
+}
+
+/// Render an SVG onto a white canvas with its longer edge at `edge` pixels.
+/// White rather than transparent, since the JPEG output has no alpha and
+/// logos are drawn for a light background.
+fn rasterize_svg(data: &[u8], edge: u32) -> Result<DynamicImage, String> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree =
+        usvg::Tree::from_data(data, &options).map_err(|e| format!("Failed to parse SVG: {}", e))?;
+
+    let size = tree.size();
+    let scale = edge.max(1) as f32 / size.width().max(size.height());
+    let width = (size.width() * scale).round().max(1.0) as u32;
+    let height = (size.height() * scale).round().max(1.0) as u32;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| format!("Failed to rasterize SVG at {}x{}", width, height))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    // Fully opaque, so the premultiplied pixels are plain RGBA
+    RgbaImage::from_raw(width, height, pixmap.take())
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| "Failed to rasterize SVG: short pixel buffer".to_string())
+}
+
+mod jpeg {
+    use std::io::Cursor;
+
+    use image::{ColorType, DynamicImage, GrayImage, ImageDecoder, ImageFormat, RgbImage};
+    use jpeg_decoder::{Decoder, PixelFormat};
+
+    /// Decode a JPEG at 1/2, 1/4 or 1/8 scale (in the DCT, so the full-size
+    /// pixels never exist), the smallest that keeps the longer edge at
+    /// `edge` pixels or more. Returns the image and the JPEG's full size, or
+    /// `None` for anything else (16-bit, CMYK, not a JPEG) so the caller
+    /// falls back to a full decode.
+    pub fn decode_scaled(data: &[u8], edge: u32) -> Option<(DynamicImage, (u32, u32))> {
+        // Checked with `image` first, which rejects the headers
+        // jpeg-decoder would panic on
+        let reader = image::ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .ok()?;
+        if reader.format() != Some(ImageFormat::Jpeg) {
+            return None;
+        }
+        let decoder = reader.into_decoder().ok()?;
+        if !matches!(decoder.color_type(), ColorType::L8 | ColorType::Rgb8) {
+            return None;
+        }
+        let source_size = decoder.dimensions();
+
+        let mut decoder = Decoder::new(data);
+        let edge = edge.clamp(1, u32::from(u16::MAX)) as u16;
+        let (width, height) = decoder.scale(edge, edge).ok()?;
+        let format = decoder.info()?.pixel_format;
+        let pixels = decoder.decode().ok()?;
+        let (width, height) = (u32::from(width), u32::from(height));
+        let image = match format {
+            PixelFormat::RGB24 => {
+                DynamicImage::ImageRgb8(RgbImage::from_raw(width, height, pixels)?)
+            }
+            PixelFormat::L8 => {
+                DynamicImage::ImageLuma8(GrayImage::from_raw(width, height, pixels)?)
+            }
+            PixelFormat::L16 | PixelFormat::CMYK32 => return None,
+        };
+        Some((image, source_size))
+    }
+}
+
+#[cfg(feature = "pdf")]
+mod pdf {
+    use image::DynamicImage;
+    use pdfium_render::prelude::*;
+
+    /// Render the first page with its longer edge at `edge` pixels.
+    pub fn first_page(data: &[u8], edge: u32) -> Result<DynamicImage, String> {
+        let pdfium = Pdfium::new(bind()?);
+        let document = pdfium
+            .load_pdf_from_byte_slice(data, None)
+            .map_err(|e| format!("Failed to open PDF: {}", e))?;
+        let page = document
+            .pages()
+            .first()
+            .map_err(|e| format!("Failed to read PDF page: {}", e))?;
+        let config = PdfRenderConfig::new()
+            .set_target_width(edge as i32)
+            .set_maximum_height(edge as i32);
+        let bitmap = page
+            .render_with_config(&config)
+            .map_err(|e| format!("Failed to render PDF page: {}", e))?;
+        Ok(bitmap.as_image())
+    }
+
+    /// Bind pdfium from next to the executable (how release builds ship
+    /// it), falling back to the system library path.
+    fn bind() -> Result<Box<dyn PdfiumLibraryBindings>, String> {
+        let bundled = std::env::current_exe()
+            .ok()
+            .and_then(|exe| {
+                exe.parent()
+                    .map(Pdfium::pdfium_platform_library_name_at_path)
+            })
+            .and_then(|lib| Pdfium::bind_to_library(lib).ok());
+        match bundled {
+            Some(bindings) => Ok(bindings),
+            None => Pdfium::bind_to_system_library()
+                .map_err(|e| format!("Failed to load the pdfium library: {}", e)),
+        }
+    }
+}
+
+#[cfg(feature = "heic")]
+mod heic {
+    use image::{DynamicImage, RgbaImage};
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    /// Decode the primary image of a HEIC file to RGBA.
+    pub fn decode(data: &[u8]) -> Result<DynamicImage, String> {
+        let lib = LibHeif::new();
+        let ctx = HeifContext::read_from_bytes(data)
+            .map_err(|e| format!("Failed to read HEIC: {}", e))?;
+        let handle = ctx
+            .primary_image_handle()
+            .map_err(|e| format!("Failed to read HEIC: {}", e))?;
+        let decoded = lib
+            .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgba), None)
+            .map_err(|e| format!("Failed to decode HEIC: {}", e))?;
+        let plane = decoded
+            .planes()
+            .interleaved
+            .ok_or_else(|| "Failed to decode HEIC: no interleaved plane".to_string())?;
+
+        // Rows may be padded past width * 4
+        let row = plane.width as usize * 4;
+        let mut pixels = Vec::with_capacity(row * plane.height as usize);
+        for line in plane.data.chunks(plane.stride).take(plane.height as usize) {
+            pixels.extend_from_slice(&line[..row]);
+        }
+        RgbaImage::from_raw(plane.width, plane.height, pixels)
+            .map(DynamicImage::ImageRgba8)
+            .ok_or_else(|| "Failed to decode HEIC: short pixel buffer".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal `ftyp` box with the given major and compatible brands.
+    fn ftyp(major: &[u8; 4], compatible: &[&[u8; 4]]) -> Vec<u8> {
+        let len = 16 + 4 * compatible.len();
+        let mut out = (len as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(b"ftyp");
+        out.extend_from_slice(major);
+        out.extend_from_slice(&[0, 0, 0, 0]);
+        for brand in compatible {
+            out.extend_from_slice(*brand);
+        }
+        out
+    }
+
+    #[test]
+    fn sniffs_avif_and_heic() {
+        assert_eq!(
+            heif_kind(&ftyp(b"avif", &[b"mif1", b"miaf"])),
+            Some(HeifKind::Avif)
+        );
+        // Some encoders put the AVIF brand only in the compatible list
+        assert_eq!(heif_kind(&ftyp(b"mif1", &[b"avif"])), Some(HeifKind::Avif));
+        assert_eq!(heif_kind(&ftyp(b"heic", &[b"mif1"])), Some(HeifKind::Heic));
+        assert_eq!(heif_kind(&ftyp(b"isom", &[b"mp41"])), None);
+        assert_eq!(heif_kind(b"\xFF\xD8\xFF\xE0 not a heif"), None);
+    }
+
+    #[test]
+    fn svg_is_rasterized_at_the_requested_size() {
+        let svg = br##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50">
+  <rect x="50" width="50" height="50" fill="#ff0000"/>
+</svg>"##;
+        let img = decode_image(svg, 480).unwrap().to_rgb8();
+        assert_eq!(img.dimensions(), (480, 240));
+        // Transparent areas come out white, the shape keeps its colour
+        assert_eq!(img.get_pixel(100, 120).0, [255, 255, 255]);
+        assert_eq!(img.get_pixel(380, 120).0, [255, 0, 0]);
+    }
+
+    #[test]
+    fn preview_is_shrunk_to_fit_and_remembers_the_source_size() {
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(3000, 1500)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let preview = decode_preview(&png, 480, 1024, 2048).unwrap();
+        assert_eq!(preview.source_size, (3000, 1500));
+        assert_eq!(preview.image.width(), 1024);
+        assert_eq!(preview.image.height(), 512);
+        let render = preview.render_source.unwrap();
+        assert_eq!((render.width(), render.height()), (2048, 1024));
+
+        let small = decode_preview(&png, 480, 4096, 4096).unwrap();
+        assert_eq!((small.image.width(), small.image.height()), (3000, 1500));
+        assert!(small.render_source.is_none());
+    }
+
+    #[test]
+    fn jpeg_preview_is_decoded_scaled_down() {
+        let mut jpg = Vec::new();
+        DynamicImage::new_rgb8(4000, 2000)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpg),
+                image::ImageFormat::Jpeg,
+            )
+            .unwrap();
+        // 1/2 scale is the smallest that still covers 1024
+        let (scaled, source_size) = jpeg::decode_scaled(&jpg, 1024).unwrap();
+        assert_eq!(source_size, (4000, 2000));
+        assert_eq!((scaled.width(), scaled.height()), (2000, 1000));
+
+        let preview = decode_preview(&jpg, 480, 512, 1024).unwrap();
+        assert_eq!(preview.source_size, (4000, 2000));
+        assert_eq!((preview.image.width(), preview.image.height()), (512, 256));
+        let render = preview.render_source.unwrap();
+        assert_eq!((render.width(), render.height()), (1024, 512));
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    #[test]
+    fn pdf_without_feature_names_the_feature() {
+        let err = decode_image(b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n", 480).unwrap_err();
+        assert!(
+            matches!(err, DecodeError::MissingFeature { feature: "pdf", .. }),
+            "{err}"
+        );
+        assert!(err.to_string().contains("`pdf` feature"), "{}", err);
+    }
+
+    #[cfg(not(feature = "heic"))]
+    #[test]
+    fn heic_without_feature_names_the_feature() {
+        let err = decode_image(&ftyp(b"heic", &[b"mif1"]), 480).unwrap_err();
+        assert!(err.to_string().contains("`heic` feature"), "{}", err);
+    }
+}
@@ -0,0 +1,179 @@
+//! Per-image edits chosen in the preview pane.
+//!
+//! Unlike `ExportSettings`' config-backed fields these belong to one
+//! candidate image: they're reset whenever a new preview loads and are
+//! applied to the decoded source before the square crop/pad.
+
+use image::{DynamicImage, Rgba};
+use serde::{Deserialize, Serialize};
+
+/// Range of the brightness/contrast/saturation sliders.
+pub const ADJUST_MIN: i16 = -100;
+pub const ADJUST_MAX: i16 = 100;
+
+/// Orientation and colour fix-ups for a candidate image.
+///
+/// Orientation is stored as "rotate clockwise `quarter_turns` times, then
+/// mirror horizontally if `flip_horizontal`", which can express every
+/// combination of the preview buttons. The colour adjustments are
+/// percentages in `ADJUST_MIN..=ADJUST_MAX`, 0 meaning unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ImageEdits {
+    /// Clockwise 90° turns, 0-3.
+    pub quarter_turns: u8,
+    pub flip_horizontal: bool,
+    /// Shifts every channel up/down; ±100 is ±half the range.
+    pub brightness: i16,
+    /// Stretches channels away from (or toward) mid-grey; -100 is flat grey,
+    /// +100 doubles the spread.
+    pub contrast: i16,
+    /// Scales colourfulness; -100 is greyscale, +100 doubles it.
+    pub saturation: i16,
+}
+
+impl ImageEdits {
+    /// True when `apply` would return the image unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Rotate what's currently displayed 90° clockwise.
+    pub fn rotate_cw(&mut self) {
+        self.turn(1);
+    }
+
+    /// Rotate what's currently displayed 90° counter-clockwise.
+    pub fn rotate_ccw(&mut self) {
+        self.turn(3);
+    }
+
+    /// Mirror what's currently displayed left-to-right.
+    pub fn flip(&mut self) {
+        self.flip_horizontal = !self.flip_horizontal;
+    }
+
+    /// Rotating a mirrored image clockwise is the same as mirroring an image
+    /// rotated the other way, so the stored turn count runs backwards while
+    /// flipped.
+    fn turn(&mut self, clockwise: u8) {
+        let step = if self.flip_horizontal { 4 - clockwise } else { clockwise };
+        self.quarter_turns = (self.quarter_turns + step) % 4;
+    }
+
+    /// True when any of the colour sliders is off zero.
+    pub fn has_color_adjustments(&self) -> bool {
+        self.brightness != 0 || self.contrast != 0 || self.saturation != 0
+    }
+
+    /// Apply the edits to `img`.
+    pub fn apply(&self, img: DynamicImage) -> DynamicImage {
+        let rotated = match self.quarter_turns % 4 {
+            1 => img.rotate90(),
+            2 => img.rotate180(),
+            3 => img.rotate270(),
+            _ => img,
+        };
+        let oriented = if self.flip_horizontal {
+            rotated.fliph()
+        } else {
+            rotated
+        };
+        if self.has_color_adjustments() {
+            self.adjust_colors(oriented)
+        } else {
+            oriented
+        }
+    }
+
+    /// Contrast, then brightness, then saturation, per pixel. Alpha is kept.
+    fn adjust_colors(&self, img: DynamicImage) -> DynamicImage {
+        let contrast = 1.0 + f32::from(self.contrast.clamp(ADJUST_MIN, ADJUST_MAX)) / 100.0;
+        let brightness = f32::from(self.brightness.clamp(ADJUST_MIN, ADJUST_MAX)) * 1.28;
+        let saturation = 1.0 + f32::from(self.saturation.clamp(ADJUST_MIN, ADJUST_MAX)) / 100.0;
+
+        let mut rgba = img.to_rgba8();
+        for Rgba([r, g, b, _]) in rgba.pixels_mut() {
+            let mut c = [*r, *g, *b].map(|v| (f32::from(v) - 128.0) * contrast + 128.0 + brightness);
+            // BT.601 luma, matching the JPEG encoder's YCbCr conversion.
+            let luma = 0.299 * c[0] + 0.587 * c[1] + 0.114 * c[2];
+            for v in &mut c {
+                *v = luma + (*v - luma) * saturation;
+            }
+            [*r, *g, *b] = c.map(|v| v.round().clamp(0.0, 255.0) as u8);
+        }
+        DynamicImage::ImageRgba8(rgba)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GenericImageView, Rgb, RgbImage};
+
+    /// 2x1 image: red on the left, blue on the right.
+    fn pair() -> DynamicImage {
+        let mut img = RgbImage::new(2, 1);
+        img.put_pixel(0, 0, Rgb([255, 0, 0]));
+        img.put_pixel(1, 0, Rgb([0, 0, 255]));
+        DynamicImage::ImageRgb8(img)
+    }
+
+    fn pixel(img: &DynamicImage, x: u32, y: u32) -> [u8; 3] {
+        let p = img.get_pixel(x, y);
+        [p[0], p[1], p[2]]
+    }
+
+    #[test]
+    fn rotate_cw_turns_the_image() {
+        let mut edits = ImageEdits::default();
+        edits.rotate_cw();
+        let out = edits.apply(pair());
+        assert_eq!(out.dimensions(), (1, 2));
+        // Left edge ends up on top.
+        assert_eq!(pixel(&out, 0, 0), [255, 0, 0]);
+    }
+
+    #[test]
+    fn four_turns_and_two_flips_are_identity() {
+        let mut edits = ImageEdits::default();
+        for _ in 0..4 {
+            edits.rotate_cw();
+        }
+        edits.flip();
+        edits.flip();
+        assert!(edits.is_identity());
+    }
+
+    #[test]
+    fn color_adjustments() {
+        let grey = || DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, Rgb([100, 100, 100])));
+        let colour = || DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, Rgb([200, 100, 50])));
+
+        let brighter = ImageEdits { brightness: 50, ..ImageEdits::default() };
+        assert_eq!(pixel(&brighter.apply(grey()), 0, 0), [164, 164, 164]);
+
+        let flat = ImageEdits { contrast: ADJUST_MIN, ..ImageEdits::default() };
+        assert_eq!(pixel(&flat.apply(colour()), 0, 0), [128, 128, 128]);
+
+        let mono = ImageEdits { saturation: ADJUST_MIN, ..ImageEdits::default() };
+        let [r, g, b] = pixel(&mono.apply(colour()), 0, 0);
+        assert!(r == g && g == b, "{r} {g} {b}");
+
+        // No sliders moved: pixels pass through untouched.
+        assert_eq!(pixel(&ImageEdits::default().apply(colour()), 0, 0), [200, 100, 50]);
+    }
+
+    #[test]
+    fn rotation_after_flip_matches_what_is_displayed() {
+        // Flip, then rotate clockwise: the displayed image is the flipped
+        // one turned clockwise.
+        let mut edits = ImageEdits::default();
+        edits.flip();
+        edits.rotate_cw();
+        let expected = pair().fliph().rotate90();
+        let out = edits.apply(pair());
+        assert_eq!(out.dimensions(), expected.dimensions());
+        assert_eq!(pixel(&out, 0, 0), pixel(&expected, 0, 0));
+        assert_eq!(pixel(&out, 0, 1), pixel(&expected, 0, 1));
+    }
+}
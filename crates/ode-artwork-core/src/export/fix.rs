@@ -0,0 +1,222 @@
+//! Batch repair of existing artwork.
+//!
+//! Walks a folder for `.jpg` covers (usually made by other tools), runs each
+//! through the compliance validator, and re-encodes the failures in place with
+//! the normal export pipeline. Originals are copied into a backup folder at the
+//! root of the scan first, mirroring their relative path.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{
+    decode_image, export_artwork, validate_jpeg_bytes, ArtTemplate, ComplianceIssue,
+    ExportSettings, OutputFormat, BACKUP_DIR_NAME,
+};
+
+/// An existing cover that needs re-encoding.
+#[derive(Debug, Clone)]
+pub struct FixCandidate {
+    pub path: PathBuf,
+    /// Human-readable reasons, one per failed rule.
+    pub problems: Vec<String>,
+}
+
+/// Outcome of `fix_artwork_batch`.
+#[derive(Debug, Default)]
+pub struct FixSummary {
+    pub fixed: usize,
+    /// `(path, error)` for every file that could not be re-encoded.
+    pub failed: Vec<(PathBuf, String)>,
+    pub backup_dir: PathBuf,
+}
+
+/// Dry run: list every `.jpg`/`.jpeg` under `root` that breaks the USBODE
+/// rules for `settings.target_size`, or exceeds `settings.max_file_size`.
+/// Nothing is written.
+pub fn scan_for_fixes(root: &Path, settings: &ExportSettings) -> Result<Vec<FixCandidate>, String> {
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", root.display()));
+    }
+    let mut out = Vec::new();
+    for path in collect_jpegs(root, &settings.extra_sizes) {
+        let data = match fs::read(&path) {
+            Ok(d) => d,
+            Err(e) => {
+                log::warn!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let problems = check(&data, settings);
+        if !problems.is_empty() {
+            out.push(FixCandidate { path, problems });
+        }
+    }
+    Ok(out)
+}
+
+/// Re-encode `candidates` in place, backing each original up under
+/// `<root>/.artwork-backup/` first. Always writes JPEG regardless of the
+/// configured output format, since the file keeps its `.jpg` name, and never
+/// adds a template frame (the existing art may already have one). No
+/// sidecar is written, since there's no source to record.
+pub fn fix_artwork_batch(root: &Path, candidates: &[FixCandidate], settings: &ExportSettings) -> FixSummary {
+    let settings = ExportSettings {
+        format: OutputFormat::Jpeg,
+        extra_sizes: Vec::new(),
+        template: ArtTemplate::None,
+        // The original is already copied into the batch backup folder.
+        backup_existing: false,
+        write_sidecar: false,
+        // Fixed in place; the remote copies are left as they are
+        remote_target: None,
+        copy_to_sd_card: false,
+        retroarch: None,
+        ..settings.clone()
+    };
+    let backup_dir = root.join(BACKUP_DIR_NAME);
+    let mut summary = FixSummary {
+        backup_dir: backup_dir.clone(),
+        ..FixSummary::default()
+    };
+    for candidate in candidates {
+        match fix_one(root, &backup_dir, &candidate.path, &settings) {
+            Ok(()) => summary.fixed += 1,
+            Err(e) => summary.failed.push((candidate.path.clone(), e)),
+        }
+    }
+    summary
+}
+
+fn fix_one(root: &Path, backup_dir: &Path, path: &Path, settings: &ExportSettings) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read: {}", e))?;
+
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let backup = backup_dir.join(relative);
+    if let Some(parent) = backup.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    }
+    // Never clobber an earlier backup: the first one is the real original.
+    if !backup.exists() {
+        fs::write(&backup, &data).map_err(|e| format!("Failed to back up original: {}", e))?;
+    }
+
+    export_artwork(&data, path, settings)
+        .map(|_| ())
+        .map_err(String::from)
+}
+
+/// Problems with `data` under `settings`, empty when it's fine as-is.
+fn check(data: &[u8], settings: &ExportSettings) -> Vec<String> {
+    let report = validate_jpeg_bytes(data, settings.target_size);
+    // A file that isn't even a JPEG can still be fixed if `image` can
+    // decode it (PNG saved with a .jpg name is common).
+    let decodable = || decode_image(data, settings.raster_size()).is_ok();
+    let mut problems: Vec<String> = report
+        .issues
+        .iter()
+        .filter(|i| !matches!(i, ComplianceIssue::NotJpeg) || decodable())
+        .map(|i| i.to_string())
+        .collect();
+    if let Some(max) = settings.max_file_size {
+        if data.len() > max {
+            problems.push(format!("{} KB (limit {} KB)", data.len() / 1024, max / 1024));
+        }
+    }
+    problems
+}
+
+/// JPEG files under `root`, skipping backup folders and the
+/// `<name>_<size>.jpg` companions written for `extra_sizes`.
+fn collect_jpegs(root: &Path, extra_sizes: &[u32]) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(read) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in read.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let name = path.file_name().and_then(|n| n.to_str());
+                if name != Some(BACKUP_DIR_NAME) {
+                    stack.push(path);
+                }
+                continue;
+            }
+            let ext = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_ascii_lowercase())
+                .unwrap_or_default();
+            if !matches!(ext.as_str(), "jpg" | "jpeg") || is_extra_size(&path, extra_sizes) {
+                continue;
+            }
+            out.push(path);
+        }
+    }
+    out.sort();
+    out
+}
+
+fn is_extra_size(path: &Path, extra_sizes: &[u32]) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    stem.rsplit_once('_')
+        .and_then(|(_, n)| n.parse::<u32>().ok())
+        .is_some_and(|n| extra_sizes.contains(&n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    fn write_jpeg(path: &Path, size: u32) {
+        let img = RgbImage::from_pixel(size, size, image::Rgb([10, 120, 200]));
+        img.save_with_format(path, ImageFormat::Jpeg).unwrap();
+    }
+
+    #[test]
+    fn scan_then_fix_replaces_and_backs_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        let bad = root.join("sub").join("game.jpg");
+        write_jpeg(&bad, 500);
+        let good = root.join("ok.jpg");
+        let settings = ExportSettings::default();
+        export_artwork(&fs::read(&bad).unwrap(), &good, &settings).unwrap();
+
+        let candidates = scan_for_fixes(root, &settings).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, bad);
+        // Dry run leaves the file alone.
+        assert!(!root.join(BACKUP_DIR_NAME).exists());
+
+        let summary = fix_artwork_batch(root, &candidates, &settings);
+        assert_eq!(summary.fixed, 1);
+        assert!(summary.failed.is_empty());
+        assert!(root.join(BACKUP_DIR_NAME).join("sub").join("game.jpg").exists());
+        assert!(scan_for_fixes(root, &settings).unwrap().is_empty());
+    }
+
+    #[test]
+    fn oversized_files_are_flagged() {
+        let settings = ExportSettings {
+            max_file_size: Some(1),
+            ..ExportSettings::default()
+        };
+        let img = RgbImage::from_pixel(240, 240, image::Rgb([0, 0, 0]));
+        let data = super::super::encode_baseline_jpeg(&img, 90).unwrap();
+        assert_eq!(check(&data, &settings).len(), 1);
+        assert!(check(&data, &ExportSettings::default()).is_empty());
+    }
+
+    #[test]
+    fn extra_size_companions_are_skipped() {
+        assert!(is_extra_size(Path::new("game_480.jpg"), &[480]));
+        assert!(!is_extra_size(Path::new("game_480.jpg"), &[]));
+        assert!(!is_extra_size(Path::new("final_fantasy_7.jpg"), &[480]));
+    }
+}
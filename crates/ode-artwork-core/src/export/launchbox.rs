@@ -0,0 +1,312 @@
+//! LaunchBox / Big Box export: identified discs are added to a platform's
+//! `Data/Platforms/<Platform>.xml` and their covers copied to
+//! `Images/<Platform>/Box - Front/<Title>-01.jpg`, the layout LaunchBox
+//! reads its box art from.
+//!
+//! The XML is edited as text rather than parsed: new `<Game>` entries go in
+//! before the closing `</LaunchBox>`, and discs whose `ApplicationPath` is
+//! already listed are left alone. The file is backed up to `.xml.bak` first.
+//! LaunchBox rewrites these files while it runs, so it should be closed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Image type folder for front covers.
+pub const BOX_FRONT_DIR: &str = "Box - Front";
+
+const XML_HEADER: &str = "<?xml version=\"1.0\" standalone=\"yes\"?>\n<LaunchBox>\n";
+const XML_FOOTER: &str = "</LaunchBox>";
+
+/// A disc to list in LaunchBox.
+#[derive(Debug, Clone)]
+pub struct LaunchBoxGame {
+    pub title: String,
+    /// The disc image, which LaunchBox launches
+    pub application_path: PathBuf,
+    pub region: Option<String>,
+    /// Saved front cover to copy into LaunchBox's images folder
+    pub cover: Option<PathBuf>,
+}
+
+/// What an export did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchBoxSummary {
+    /// The platform XML written
+    pub xml_path: PathBuf,
+    pub added: usize,
+    /// Already in the platform XML
+    pub already_listed: usize,
+    pub covers_copied: usize,
+}
+
+/// Add `games` to `platform` in the LaunchBox install at `root`.
+pub fn export_launchbox(
+    root: &Path,
+    platform: &str,
+    games: &[LaunchBoxGame],
+) -> Result<LaunchBoxSummary, String> {
+    let platform = platform.trim();
+    if platform.is_empty() {
+        return Err("No LaunchBox platform given".to_string());
+    }
+    let data_dir = root.join("Data");
+    if !data_dir.is_dir() {
+        return Err(format!(
+            "{} isn't a LaunchBox folder (no Data folder)",
+            root.display()
+        ));
+    }
+    let file_name = sanitize_file_name(platform);
+    let xml_path = data_dir
+        .join("Platforms")
+        .join(format!("{}.xml", file_name));
+    let images_dir = root.join("Images").join(&file_name).join(BOX_FRONT_DIR);
+    let mut summary = LaunchBoxSummary {
+        xml_path: xml_path.clone(),
+        ..LaunchBoxSummary::default()
+    };
+
+    let mut xml = read_or_empty(&xml_path)?;
+    let mut entries = String::new();
+    for game in games {
+        if let Some(cover) = &game.cover {
+            if copy_cover(cover, &images_dir, &game.title)? {
+                summary.covers_copied += 1;
+            }
+        }
+        let path = game.application_path.display().to_string();
+        if xml.contains(&format!(
+            "<ApplicationPath>{}</ApplicationPath>",
+            xml_escape(&path)
+        )) {
+            summary.already_listed += 1;
+            continue;
+        }
+        entries += &game_xml(game, platform);
+        summary.added += 1;
+    }
+    if summary.added > 0 {
+        insert_before_footer(&mut xml, &entries);
+        write_with_backup(&xml_path, &xml)?;
+        add_platform(&data_dir.join("Platforms.xml"), platform)?;
+    }
+    Ok(summary)
+}
+
+/// List `platform` in `Data/Platforms.xml`, so LaunchBox shows a platform
+/// created by the export.
+fn add_platform(path: &Path, platform: &str) -> Result<(), String> {
+    let mut xml = read_or_empty(path)?;
+    let name = format!("<Name>{}</Name>", xml_escape(platform));
+    if xml.contains(&name) {
+        return Ok(());
+    }
+    insert_before_footer(
+        &mut xml,
+        &format!("  <Platform>\n    {}\n  </Platform>\n", name),
+    );
+    write_with_backup(path, &xml)
+}
+
+fn read_or_empty(path: &Path) -> Result<String, String> {
+    match fs::read_to_string(path) {
+        Ok(xml) if xml.contains(XML_FOOTER) => Ok(xml),
+        Ok(_) => Err(format!("{} isn't a LaunchBox XML file", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Ok(format!("{}{}\n", XML_HEADER, XML_FOOTER))
+        }
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn insert_before_footer(xml: &mut String, entries: &str) {
+    let at = xml.rfind(XML_FOOTER).unwrap_or(xml.len());
+    xml.insert_str(at, entries);
+}
+
+/// Write `xml` to `path`, keeping the previous file as `<name>.bak`.
+fn write_with_backup(path: &Path, xml: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    if path.exists() {
+        let backup = path.with_extension("xml.bak");
+        fs::copy(path, &backup)
+            .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    }
+    let temp = path.with_extension("xml.tmp");
+    fs::write(&temp, xml).map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+    fs::rename(&temp, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Copy `cover` to `<dir>/<Title>-NN.<ext>`, unless one of the title's
+/// images there is already the same file. Returns whether it was copied.
+fn copy_cover(cover: &Path, dir: &Path, title: &str) -> Result<bool, String> {
+    let data = fs::read(cover).map_err(|e| format!("Failed to read {}: {}", cover.display(), e))?;
+    let extension = cover
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("jpg")
+        .to_lowercase();
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for n in 1..100 {
+        let path = dir.join(image_file_name(title, n, &extension));
+        match fs::read(&path) {
+            Ok(existing) if existing == data => return Ok(false),
+            Ok(_) => continue,
+            Err(_) => {
+                fs::write(&path, &data)
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                return Ok(true);
+            }
+        }
+    }
+    Err(format!(
+        "Too many images for {} in {}",
+        title,
+        dir.display()
+    ))
+}
+
+/// LaunchBox's name for image `n` of `title`: `Title-01.jpg`, with the
+/// characters it can't use in file names replaced by `_`.
+pub fn image_file_name(title: &str, n: u32, extension: &str) -> String {
+    let title = title.replace('\'', "_");
+    format!("{}-{:02}.{}", sanitize_file_name(&title), n, extension)
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            ':' | '/' | '\\' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn game_xml(game: &LaunchBoxGame, platform: &str) -> String {
+    let path = game.application_path.display().to_string();
+    let mut xml = String::from("  <Game>\n");
+    let mut field = |name: &str, value: &str| {
+        xml += &format!("    <{name}>{}</{name}>\n", xml_escape(value));
+    };
+    field("ID", &game_id(&path));
+    field("Title", &game.title);
+    field("ApplicationPath", &path);
+    field("Platform", platform);
+    if let Some(region) = &game.region {
+        field("Region", region);
+    }
+    xml += "  </Game>\n";
+    xml
+}
+
+/// A GUID for the disc, derived from its path so exporting twice doesn't
+/// make a second one.
+fn game_id(path: &str) -> String {
+    let hash = Sha256::digest(path.as_bytes());
+    let mut b: [u8; 16] = hash[..16].try_into().expect("SHA-256 is 32 bytes");
+    // Version 4 / RFC 4122 variant bits, as LaunchBox's own IDs have
+    b[6] = (b[6] & 0x0f) | 0x40;
+    b[8] = (b[8] & 0x3f) | 0x80;
+    let hex = hex::encode(b);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ode_launchbox_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn image_names_follow_launchbox() {
+        assert_eq!(
+            image_file_name("Myst: Masterpiece Edition", 1, "jpg"),
+            "Myst_ Masterpiece Edition-01.jpg"
+        );
+        assert_eq!(
+            image_file_name("Tony Hawk's Pro Skater", 2, "png"),
+            "Tony Hawk_s Pro Skater-02.png"
+        );
+    }
+
+    #[test]
+    fn ids_are_stable_guids() {
+        let id = game_id("/discs/Myst.cue");
+        assert_eq!(id, game_id("/discs/Myst.cue"));
+        assert_ne!(id, game_id("/discs/Riven.cue"));
+        assert_eq!(id.len(), 36);
+        assert_eq!(&id[14..15], "4");
+    }
+
+    #[test]
+    fn adds_games_once_and_copies_covers() {
+        let root = temp_dir("export");
+        fs::create_dir_all(root.join("Data")).unwrap();
+        let cover = root.join("cover.jpg");
+        fs::write(&cover, b"jpeg").unwrap();
+        let games = vec![LaunchBoxGame {
+            title: "Rock & Roll Racing".to_string(),
+            application_path: PathBuf::from("/discs/Rock & Roll Racing.cue"),
+            region: Some("USA".to_string()),
+            cover: Some(cover),
+        }];
+
+        let summary = export_launchbox(&root, "Sony Playstation", &games).unwrap();
+        assert_eq!(
+            (summary.added, summary.already_listed, summary.covers_copied),
+            (1, 0, 1)
+        );
+        let xml = fs::read_to_string(&summary.xml_path).unwrap();
+        assert!(xml.starts_with(XML_HEADER));
+        assert!(xml.trim_end().ends_with(XML_FOOTER));
+        assert!(xml.contains("<Title>Rock &amp; Roll Racing</Title>"));
+        assert!(xml.contains("<Platform>Sony Playstation</Platform>"));
+        assert!(root
+            .join("Images/Sony Playstation/Box - Front/Rock & Roll Racing-01.jpg")
+            .is_file());
+        let platforms = fs::read_to_string(root.join("Data/Platforms.xml")).unwrap();
+        assert!(platforms.contains("<Name>Sony Playstation</Name>"));
+
+        let again = export_launchbox(&root, "Sony Playstation", &games).unwrap();
+        assert_eq!(
+            (again.added, again.already_listed, again.covers_copied),
+            (0, 1, 0)
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn needs_a_launchbox_folder() {
+        let root = temp_dir("not_launchbox");
+        assert!(export_launchbox(&root, "Sony Playstation", &[]).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,1644 @@
+//! Image export module for USBODE artwork
+//!
+//! Handles cropping, resizing, and JPEG conversion for cover art.
+//! Output is a 240x240px (configurable) baseline JPEG with specific color
+//! space settings, optionally with extra sizes written alongside.
+
+use image::{DynamicImage, RgbImage};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::config::ExportConfig;
+use crate::disc::chd_artwork;
+use crate::net::{CancelToken, FetchError};
+pub use crate::config::{
+    ArtTemplate, BadgePosition, BadgeStyle, FitMode, OutputFormat, PadFill, UpscaleMode,
+};
+
+mod badge;
+pub use badge::format_label as format_disc_label;
+
+mod edits;
+pub use edits::{ImageEdits, ADJUST_MAX, ADJUST_MIN};
+
+mod decode;
+pub use decode::{decode_image, decode_preview, DecodeError, PreviewImage};
+
+mod crop;
+pub use crop::{smart_crop_to_square, trim_borders, Region};
+
+mod pad;
+pub use pad::{format_hex_color, pad_to_square, parse_hex_color, PadBackground};
+
+mod sharpen;
+pub use sharpen::{sharpen, MAX_SHARPEN};
+
+mod upscale;
+
+mod template;
+
+mod placeholder;
+pub use placeholder::render_placeholder;
+
+mod naming;
+pub use naming::{render_name, validate_name_template, DEFAULT_NAME_TEMPLATE};
+
+mod central;
+
+mod backup;
+pub use backup::{backup_existing, restore_backup, undo_export, WrittenFile, BACKUP_DIR_NAME};
+
+mod provenance;
+pub use provenance::{read_provenance, Provenance};
+
+mod sidecar;
+pub use sidecar::{read_sidecar, sidecar_path, ArtworkSidecar, DiscSummary, SIDECAR_EXTENSION};
+
+mod remote;
+pub use remote::RemoteTarget;
+
+mod retroarch;
+pub use retroarch::RetroArchThumbnails;
+
+mod launchbox;
+pub use launchbox::{export_launchbox, LaunchBoxGame, LaunchBoxSummary};
+
+mod fix;
+pub use fix::{fix_artwork_batch, scan_for_fixes, FixCandidate, FixSummary};
+
+mod progress;
+pub use progress::{ExportProgress, ProgressHook};
+
+mod validate;
+pub use validate::{
+    validate_artwork, validate_artwork_with_size, validate_jpeg_bytes, ComplianceIssue,
+    ComplianceReport,
+};
+
+/// Target size for USBODE artwork
+pub const TARGET_SIZE: u32 = 240;
+
+/// JPEG quality setting (0-100)
+pub const JPEG_QUALITY: u8 = 90;
+
+/// Lowest quality the size-constrained encoder will go to before giving up.
+pub const MIN_JPEG_QUALITY: u8 = 10;
+
+/// Why an export failed. Copies made after the cover is saved (sidecar,
+/// CHD, remote, SD card, RetroArch) don't fail the export and are reported
+/// in [`ExportResult`] instead.
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// The image couldn't be downloaded: no answer, an HTTP error status,
+    /// or an unreadable local file
+    #[error(transparent)]
+    Download(FetchError),
+
+    /// `ExportSettings::cancel` fired before anything was written
+    #[error("{}", crate::net::CANCELLED)]
+    Cancelled,
+
+    /// The source image couldn't be read
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+
+    /// The output couldn't be encoded
+    #[error("{0}")]
+    Encode(String),
+
+    /// Even the lowest JPEG quality doesn't fit `max_file_size`
+    #[error("Cannot fit artwork in {max_kb} KB even at quality {quality}")]
+    TooLarge { max_kb: usize, quality: u8 },
+
+    /// The existing file couldn't be moved aside
+    #[error("{0}")]
+    Backup(String),
+
+    /// Creating the output folder or writing the file failed
+    #[error("Failed to {action} {}: {source}", .path.display())]
+    Io {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// For callers that only report the error.
+impl From<ExportError> for String {
+    fn from(e: ExportError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Result of an export operation
+#[derive(Debug)]
+pub struct ExportResult {
+    /// Path where the image was saved
+    pub output_path: String,
+    /// Original image dimensions
+    pub original_size: (u32, u32),
+    /// Final image dimensions of the main output (target_size x target_size)
+    pub final_size: (u32, u32),
+    /// Size in bytes of the main output file
+    pub file_size: usize,
+    /// Whether cropping was applied
+    pub was_cropped: bool,
+    /// Square cut out of the (edited) source when `was_cropped`
+    pub crop: Option<Region>,
+    /// Paths of the additional sizes written alongside the main output
+    pub extra_outputs: Vec<String>,
+    /// Every local file written, the main output first, each with where
+    /// the file it replaced was moved. Undone together by `undo_export`.
+    /// The remote copy and a cover embedded in the CHD aren't included.
+    pub written: Vec<WrittenFile>,
+    /// Whether the main output was also stored inside the disc's CHD
+    pub embedded_in_chd: bool,
+    /// Where the main output was copied on the remote target, or why it
+    /// couldn't be. `None` without a target.
+    pub uploaded: Option<Result<String, String>>,
+    /// Where the main output was copied on a mounted USBODE SD card, or why
+    /// it couldn't be. `None` when that's off or no card has the disc.
+    pub on_sd_card: Option<Result<PathBuf, String>>,
+    /// Where the RetroArch thumbnail was written, or why it couldn't be.
+    /// `None` when that's off.
+    pub retroarch_thumbnail: Option<Result<PathBuf, String>>,
+}
+
+impl ExportResult {
+    /// Why the copy to the remote target failed, if it did.
+    pub fn upload_error(&self) -> Option<&str> {
+        match &self.uploaded {
+            Some(Err(e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Export settings
+#[derive(Debug, Clone)]
+pub struct ExportSettings {
+    /// Target width and height (square)
+    pub target_size: u32,
+    /// JPEG quality (0-100)
+    pub quality: u8,
+    /// Extra square sizes to write next to the main output
+    pub extra_sizes: Vec<u32>,
+    /// Output file format
+    pub format: OutputFormat,
+    /// Maximum size in bytes of the main JPEG output; quality is lowered
+    /// until it fits. Ignored for PNG/BMP and for the extra sizes.
+    pub max_file_size: Option<usize>,
+    /// Trim uniform borders and bias the square crop toward the cover's
+    /// detail instead of always center-cropping. With `FitMode::Pad` only
+    /// the border trim applies.
+    pub smart_crop: bool,
+    /// Crop to square, or pad onto a square canvas
+    pub fit: FitMode,
+    /// Canvas fill for `FitMode::Pad`
+    pub pad_background: PadBackground,
+    /// Rotation/flip and colour adjustments picked in the preview for this
+    /// particular image
+    pub edits: ImageEdits,
+    /// Unsharp-mask strength after resizing, in percent (0 = off)
+    pub sharpen: u32,
+    /// Enlargement method when the square is smaller than the output
+    pub upscale: UpscaleMode,
+    /// Disc/case frame composited around the resized artwork
+    pub template: ArtTemplate,
+    /// Written into a JPEG comment so the cover's origin can be recovered
+    /// later (see `read_provenance`). Ignored for PNG/BMP.
+    pub provenance: Option<Provenance>,
+    /// Move existing outputs (the main one, the extra sizes, the sidecar,
+    /// the SD card copy and the RetroArch thumbnail) into `.artwork-backup/`
+    /// before overwriting them
+    pub backup_existing: bool,
+    /// Write `<name>.artwork.json` next to the main output
+    pub write_sidecar: bool,
+    /// Disc details recorded in the sidecar
+    pub disc: Option<DiscSummary>,
+    /// Store the main output inside `disc` when it's a CHD
+    pub embed_in_chd: bool,
+    /// Network location the main output is also copied to
+    pub remote_target: Option<RemoteTarget>,
+    /// Copy the main output onto a mounted USBODE SD card that has `disc`
+    pub copy_to_sd_card: bool,
+    /// Also write the main output as `disc`'s RetroArch thumbnail
+    pub retroarch: Option<RetroArchThumbnails>,
+    /// Whether badge labels are drawn at all
+    pub disc_badge: bool,
+    /// Corner the badge goes in
+    pub badge_position: BadgePosition,
+    /// Pill or round-number badge
+    pub badge_style: BadgeStyle,
+    /// Where to report progress from the worker thread, if anywhere
+    pub progress: Option<ProgressHook>,
+    /// Stops the export once it fires: downloads between chunks, and
+    /// nothing is written after that
+    pub cancel: Option<CancelToken>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            target_size: TARGET_SIZE,
+            quality: JPEG_QUALITY,
+            extra_sizes: Vec::new(),
+            format: OutputFormat::Jpeg,
+            max_file_size: None,
+            smart_crop: true,
+            fit: FitMode::Crop,
+            pad_background: PadBackground::default(),
+            edits: ImageEdits::default(),
+            sharpen: 0,
+            upscale: UpscaleMode::Lanczos,
+            template: ArtTemplate::None,
+            provenance: None,
+            backup_existing: false,
+            write_sidecar: false,
+            disc: None,
+            embed_in_chd: false,
+            remote_target: None,
+            copy_to_sd_card: false,
+            retroarch: None,
+            disc_badge: true,
+            badge_position: BadgePosition::BottomRight,
+            badge_style: BadgeStyle::Pill,
+            progress: None,
+            cancel: None,
+        }
+    }
+}
+
+/// Smallest / largest edge length accepted from config or the GUI.
+pub const MIN_TARGET_SIZE: u32 = 16;
+pub const MAX_TARGET_SIZE: u32 = 4096;
+
+impl ExportSettings {
+    /// Settings from the `export` section of the config, with sizes clamped
+    /// to a sane range. Extra sizes equal to the main size are dropped.
+    pub fn from_config(config: &ExportConfig) -> Self {
+        let target_size = config.target_size.clamp(MIN_TARGET_SIZE, MAX_TARGET_SIZE);
+        let mut extra_sizes: Vec<u32> = config
+            .extra_sizes
+            .iter()
+            .map(|s| (*s).clamp(MIN_TARGET_SIZE, MAX_TARGET_SIZE))
+            .filter(|s| *s != target_size)
+            .collect();
+        extra_sizes.sort_unstable();
+        extra_sizes.dedup();
+        Self {
+            target_size,
+            quality: config.quality.clamp(MIN_JPEG_QUALITY, 100),
+            extra_sizes,
+            format: config.format,
+            max_file_size: config
+                .max_file_size_kb
+                .filter(|kb| *kb > 0)
+                .map(|kb| kb as usize * 1024),
+            smart_crop: config.smart_crop,
+            fit: config.fit,
+            pad_background: PadBackground::from_config(config.pad_fill, &config.pad_color),
+            sharpen: config.sharpen.min(MAX_SHARPEN),
+            upscale: config.upscale,
+            template: config.template,
+            backup_existing: config.backup_existing,
+            write_sidecar: config.write_sidecar,
+            embed_in_chd: config.embed_in_chd,
+            remote_target: config
+                .remote_target
+                .as_deref()
+                .filter(|u| !u.trim().is_empty())
+                .and_then(|url| match RemoteTarget::parse(url) {
+                    Ok(target) => Some(target),
+                    Err(e) => {
+                        log::warn!("{}", e);
+                        None
+                    }
+                }),
+            copy_to_sd_card: config.copy_to_sd_card,
+            retroarch: config
+                .retroarch_thumbnails
+                .as_deref()
+                .filter(|d| !d.trim().is_empty() && !config.retroarch_system.trim().is_empty())
+                .map(|dir| RetroArchThumbnails {
+                    dir: PathBuf::from(dir),
+                    system: config.retroarch_system.trim().to_string(),
+                }),
+            disc_badge: config.disc_badge,
+            badge_position: config.badge_position,
+            badge_style: config.badge_style,
+            ..Self::default()
+        }
+    }
+
+    /// Settings from the global config.
+    pub fn configured() -> Self {
+        Self::from_config(&crate::config::get_config().export)
+    }
+
+    /// Edge length SVG and PDF candidates are rasterized at: twice the
+    /// largest output, so they're downscaled like any other source.
+    pub fn raster_size(&self) -> u32 {
+        let largest = self.extra_sizes.iter().copied().fold(self.target_size, u32::max);
+        largest * 2
+    }
+
+    /// Pass `progress` to the hook, if any.
+    fn report(&self, progress: ExportProgress) {
+        if let Some(hook) = &self.progress {
+            hook.report(progress);
+        }
+    }
+
+    /// `Err(Cancelled)` once the cancel token has fired.
+    fn check_cancelled(&self) -> Result<(), ExportError> {
+        match &self.cancel {
+            Some(cancel) if cancel.is_cancelled() => Err(ExportError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Process and export an image for USBODE
+///
+/// This function:
+/// 1. Applies any rotation/flip and colour adjustments from `settings.edits`
+/// 2. Makes non-square images square: crops (trimming borders first when
+///    `smart_crop` is on, otherwise a plain center-crop), or pads onto a
+///    square canvas when `fit` is `Pad`
+/// 3. Resizes to the target size (240x240 by default), then sharpens if
+///    `sharpen` is set
+/// 4. Converts to baseline JPEG with YCbCr color space (BT.601)
+/// 5. Saves at `quality` (90 by default), 4:4:4 subsampling, no ICC profile
+///
+/// Any `extra_sizes` are written next to the main output as `<name>_<size>.jpg`,
+/// and with `write_sidecar` on, the metadata as `<name>.artwork.json`. With
+/// `embed_in_chd` on and a CHD `disc`, the main output is also stored in it.
+pub fn export_artwork<P: AsRef<Path>>(
+    image_data: &[u8],
+    output_path: P,
+    settings: &ExportSettings,
+) -> Result<ExportResult, ExportError> {
+    export_artwork_with_label(image_data, output_path, settings, None)
+}
+
+/// Like `export_artwork`, but stamps a "Disc N" badge in the configured
+/// corner when `disc_number > 1` and `settings.disc_badge` is on. Disc 1 (or
+/// unset) is left clean to match the historical "shared box art, numbered
+/// disc face" convention.
+pub fn export_artwork_with_disc<P: AsRef<Path>>(
+    image_data: &[u8],
+    output_path: P,
+    settings: &ExportSettings,
+    disc_number: Option<u32>,
+    disc_total: Option<u32>,
+) -> Result<ExportResult, ExportError> {
+    let label = match disc_number {
+        Some(n) if n > 1 => Some(badge::format_label(n, disc_total)),
+        _ => None,
+    };
+    export_artwork_with_label(image_data, output_path, settings, label.as_deref())
+}
+
+/// Like `export_artwork`, but stamps an arbitrary label badge. Used for
+/// role-marked discs ("Install", "Game", "Bonus") that don't have a number
+/// to use in `Disc N`.
+pub fn export_artwork_with_label<P: AsRef<Path>>(
+    image_data: &[u8],
+    output_path: P,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+) -> Result<ExportResult, ExportError> {
+    // Load the image
+    settings.check_cancelled()?;
+    settings.report(ExportProgress::Processing);
+    let img = decode_image(image_data, settings.raster_size())?;
+
+    let original_size = (img.width(), img.height());
+
+    let (cropped_img, crop) = make_square(img, settings);
+    settings.check_cancelled()?;
+
+    let output_path = output_path.as_ref();
+    let outputs = 1 + settings.extra_sizes.len();
+    settings.report(ExportProgress::Writing { n: 1, total: outputs });
+    let (backup, file_size) = write_main(&cropped_img, output_path, settings, badge_label)?;
+    let mut written = vec![WrittenFile {
+        path: output_path.to_path_buf(),
+        backup,
+    }];
+
+    let mut extra_outputs = Vec::new();
+    for (i, &size) in settings.extra_sizes.iter().enumerate() {
+        settings.report(ExportProgress::Writing { n: i + 2, total: outputs });
+        let path = sized_output_path(output_path, size);
+        let (backup, _) = write_backed_up(&path, settings, || {
+            write_sized(&cropped_img, &path, size, settings, badge_label, None)
+        })?;
+        extra_outputs.push(path.display().to_string());
+        written.push(WrittenFile { path, backup });
+    }
+
+    if settings.write_sidecar {
+        let sidecar = ArtworkSidecar::new(settings, original_size, crop, badge_label);
+        write_sidecar_logged(output_path, &sidecar, settings, &mut written);
+    }
+    let embedded_in_chd = embed_in_chd_logged(output_path, settings);
+    let uploaded = upload_to_remote(output_path, settings);
+    let on_sd_card = copy_to_sd_card(output_path, settings, &mut written);
+    let retroarch_thumbnail = write_retroarch_thumbnail(output_path, settings, &mut written);
+
+    Ok(ExportResult {
+        output_path: output_path.display().to_string(),
+        original_size,
+        final_size: (settings.target_size, settings.target_size),
+        file_size,
+        was_cropped: crop.is_some(),
+        crop,
+        extra_outputs,
+        written,
+        embedded_in_chd,
+        uploaded,
+        on_sd_card,
+        retroarch_thumbnail,
+    })
+}
+
+/// Write a generated placeholder (see `render_placeholder`) for `title`
+/// instead of downloaded artwork. Each output size is rendered directly so
+/// the text stays crisp; the template, encoding and `extra_sizes` follow
+/// `settings` as for a real cover. No badge is drawn, since the title is
+/// already on the image.
+pub fn export_placeholder<P: AsRef<Path>>(
+    title: &str,
+    output_path: P,
+    settings: &ExportSettings,
+) -> Result<ExportResult, ExportError> {
+    let output_path = output_path.as_ref();
+    let size = settings.target_size;
+    let (backup, file_size) =
+        write_main(&render_placeholder(title, size), output_path, settings, None)?;
+    let mut written = vec![WrittenFile {
+        path: output_path.to_path_buf(),
+        backup,
+    }];
+
+    let mut extra_outputs = Vec::new();
+    for &extra in &settings.extra_sizes {
+        let path = sized_output_path(output_path, extra);
+        let (backup, _) = write_backed_up(&path, settings, || {
+            write_sized(&render_placeholder(title, extra), &path, extra, settings, None, None)
+        })?;
+        extra_outputs.push(path.display().to_string());
+        written.push(WrittenFile { path, backup });
+    }
+
+    if settings.write_sidecar {
+        let sidecar = ArtworkSidecar::new(settings, (size, size), None, None);
+        write_sidecar_logged(output_path, &sidecar, settings, &mut written);
+    }
+    let embedded_in_chd = embed_in_chd_logged(output_path, settings);
+    let uploaded = upload_to_remote(output_path, settings);
+    let on_sd_card = copy_to_sd_card(output_path, settings, &mut written);
+    let retroarch_thumbnail = write_retroarch_thumbnail(output_path, settings, &mut written);
+
+    Ok(ExportResult {
+        output_path: output_path.display().to_string(),
+        original_size: (size, size),
+        final_size: (size, size),
+        file_size,
+        was_cropped: false,
+        crop: None,
+        extra_outputs,
+        written,
+        embedded_in_chd,
+        uploaded,
+        on_sd_card,
+        retroarch_thumbnail,
+    })
+}
+
+/// Write the sidecar for `output_path`, adding it to `written`. The cover
+/// is already saved by then, so a failure here is only logged rather than
+/// failing the export.
+fn write_sidecar_logged(
+    output_path: &Path,
+    sidecar: &ArtworkSidecar,
+    settings: &ExportSettings,
+    written: &mut Vec<WrittenFile>,
+) {
+    let path = sidecar::sidecar_path(output_path);
+    match write_secondary(&path, settings, || sidecar::write_sidecar(output_path, sidecar)) {
+        Ok(file) => written.push(file),
+        Err(e) => log::warn!("{}", e),
+    }
+}
+
+/// Store the saved cover at `output_path` inside the disc's CHD when
+/// `settings.embed_in_chd` is on and the disc is one. Like the sidecar, a
+/// failure is only logged. Returns whether it was stored.
+fn embed_in_chd_logged(output_path: &Path, settings: &ExportSettings) -> bool {
+    let Some(disc) = settings.disc.as_ref().filter(|_| settings.embed_in_chd) else {
+        return false;
+    };
+    let disc_path = Path::new(&disc.path);
+    if !chd_artwork::is_chd(disc_path) {
+        return false;
+    }
+    let result = std::fs::read(output_path)
+        .map_err(|e| format!("Failed to read {}: {}", output_path.display(), e))
+        .and_then(|data| chd_artwork::write_artwork(disc_path, &data));
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("{}", e);
+            false
+        }
+    }
+}
+
+/// Copy the saved cover at `output_path` to `settings.remote_target`, if
+/// there is one. The cover is saved locally either way, so a failure is
+/// reported in the result rather than failing the export.
+fn upload_to_remote(
+    output_path: &Path,
+    settings: &ExportSettings,
+) -> Option<Result<String, String>> {
+    let target = settings.remote_target.as_ref()?;
+    let result = target.upload(output_path);
+    if let Err(e) = &result {
+        log::warn!("{}", e);
+    }
+    Some(result)
+}
+
+/// Copy the saved cover at `output_path` onto a mounted USBODE SD card
+/// that has the disc, when `settings.copy_to_sd_card` is on, adding the
+/// copy to `written`. Like the remote copy, a failure is reported in the
+/// result.
+fn copy_to_sd_card(
+    output_path: &Path,
+    settings: &ExportSettings,
+    written: &mut Vec<WrittenFile>,
+) -> Option<Result<PathBuf, String>> {
+    let disc = settings.disc.as_ref().filter(|_| settings.copy_to_sd_card)?;
+    let target = crate::usbode::card::card_target(Path::new(&disc.path))?;
+    if target == output_path {
+        // Saved straight onto the card already
+        return Some(Ok(target));
+    }
+    let result = write_secondary(&target, settings, || {
+        crate::usbode::card::copy_to_card(output_path, &target).map(|()| target.clone())
+    });
+    Some(record_secondary(result, written))
+}
+
+/// Write the saved cover at `output_path` as the disc's RetroArch
+/// thumbnail, when `settings.retroarch` is set, adding it to `written`.
+/// Like the SD card copy, a failure is reported in the result.
+fn write_retroarch_thumbnail(
+    output_path: &Path,
+    settings: &ExportSettings,
+    written: &mut Vec<WrittenFile>,
+) -> Option<Result<PathBuf, String>> {
+    let thumbnails = settings.retroarch.as_ref()?;
+    let disc_path = Path::new(&settings.disc.as_ref()?.path);
+    let result = write_secondary(&thumbnails.thumbnail_path(disc_path), settings, || {
+        thumbnails.write(disc_path, output_path)
+    });
+    Some(record_secondary(result, written))
+}
+
+/// Run `write` for a file derived from the saved cover (the sidecar, the SD
+/// card copy, the RetroArch thumbnail) at `path`, backing up what it
+/// replaces like `write_backed_up` does.
+fn write_secondary(
+    path: &Path,
+    settings: &ExportSettings,
+    write: impl FnOnce() -> Result<PathBuf, String>,
+) -> Result<WrittenFile, String> {
+    let backup = if settings.backup_existing {
+        backup_existing(path)?
+    } else {
+        None
+    };
+    match write() {
+        Ok(path) => Ok(WrittenFile { path, backup }),
+        Err(e) => {
+            if let Some(backup) = &backup {
+                let _ = restore_backup(path, Some(backup));
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Add a successful `write_secondary` to `written`, logging a failure, and
+/// hand back where the file went for the export result.
+fn record_secondary(
+    result: Result<WrittenFile, String>,
+    written: &mut Vec<WrittenFile>,
+) -> Result<PathBuf, String> {
+    match result {
+        Ok(file) => {
+            let path = file.path.clone();
+            written.push(file);
+            Ok(path)
+        }
+        Err(e) => {
+            log::warn!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Write the main output, first moving any existing file aside when
+/// `settings.backup_existing` is on. Returns where the old file went and
+/// the new file's size in bytes.
+fn write_main(
+    square: &DynamicImage,
+    output_path: &Path,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+) -> Result<(Option<PathBuf>, usize), ExportError> {
+    write_backed_up(output_path, settings, || {
+        write_sized(
+            square,
+            output_path,
+            settings.target_size,
+            settings,
+            badge_label,
+            settings.max_file_size,
+        )
+    })
+}
+
+/// Run `write` for `output_path`, first moving any existing file aside when
+/// `settings.backup_existing` is on. If the write fails the old file is put
+/// back and the central-folder name claimed for it is let go. A cancel
+/// that arrives before the write stops the export here; once a file is
+/// being written it's finished.
+fn write_backed_up(
+    output_path: &Path,
+    settings: &ExportSettings,
+    write: impl FnOnce() -> Result<usize, ExportError>,
+) -> Result<(Option<PathBuf>, usize), ExportError> {
+    if let Err(e) = settings.check_cancelled() {
+        for base in claim_bases(output_path) {
+            central::release_claim(&base);
+        }
+        return Err(e);
+    }
+    let backup_path = if settings.backup_existing {
+        backup_existing(output_path).map_err(ExportError::Backup)?
+    } else {
+        None
+    };
+    match write() {
+        Ok(file_size) => {
+            record_claim(output_path);
+            Ok((backup_path, file_size))
+        }
+        Err(e) => {
+            if let Some(backup) = &backup_path {
+                let _ = restore_backup(output_path, Some(backup));
+            }
+            for base in claim_bases(output_path) {
+                central::release_claim(&base);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Add the central-folder name `output_path` was claimed under to the
+/// folder's index, now that it's written. The cover is saved either way,
+/// so a failure is only logged.
+fn record_claim(output_path: &Path) {
+    for base in claim_bases(output_path) {
+        if let Err(e) = central::record_written(&base) {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+/// The central-folder names `output_path` may have been claimed under: its
+/// own, and the disc's base name when it's a role-suffixed (`.back`, ...)
+/// output.
+fn claim_bases(output_path: &Path) -> Vec<PathBuf> {
+    let base = output_path.with_extension("");
+    let name = base.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let role_bases = ArtworkRole::ALL
+        .iter()
+        .filter_map(|role| name.strip_suffix(&format!(".{}", role.suffix()?)))
+        .map(|name| base.with_file_name(name));
+    std::iter::once(base.clone()).chain(role_bases).collect()
+}
+
+/// Apply the preview edits, then crop or pad to a square per `settings`.
+/// Returns the region cropped out, if anything was cut away. Border trimming
+/// before padding isn't reported.
+fn make_square(img: DynamicImage, settings: &ExportSettings) -> (DynamicImage, Option<Region>) {
+    // Preview-pane rotation/flip and colour adjustments, before anything
+    // depends on the orientation
+    let img = settings.edits.apply(img);
+
+    // Make it square: crop, or letterbox onto a padded canvas
+    match settings.fit {
+        FitMode::Crop if settings.smart_crop => {
+            let square = crop::smart_square(&img);
+            crop_to_region(img, square)
+        }
+        FitMode::Crop => crop_to_square(img),
+        FitMode::Pad => {
+            let img = if settings.smart_crop { trim_borders(img) } else { img };
+            (pad_to_square(img, settings.pad_background), None)
+        }
+    }
+}
+
+/// Resize an already-square image to `size`, sharpen it, and frame it in
+/// the configured template. Sources smaller than `size` go through the
+/// configured upscaler first.
+fn resize_square(square: &DynamicImage, size: u32, settings: &ExportSettings) -> DynamicImage {
+    let enlarged = upscale::upscale_for(square, size, settings.upscale);
+    let source = enlarged.as_ref().unwrap_or(square);
+    let resized = source.resize_exact(size, size, image::imageops::FilterType::Lanczos3);
+    template::apply_template(sharpen(resized, settings.sharpen), settings.template)
+}
+
+/// What the main output would look like, without badge or encoding. Used by
+/// the GUI to preview the result (e.g. before/after sharpening).
+pub fn render_output(img: DynamicImage, settings: &ExportSettings) -> DynamicImage {
+    let (square, _) = make_square(img, settings);
+    resize_square(&square, settings.target_size, settings)
+}
+
+/// The main output exactly as it would be saved: rendered, badged, encoded
+/// in the configured format (JPEG at `quality`, within `max_file_size`) and
+/// decoded again, so compression artifacts show. Returns the image and the
+/// encoded size in bytes. Used by the GUI's final-output preview.
+pub fn render_saved_output(
+    img: DynamicImage,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+) -> Result<(DynamicImage, usize), ExportError> {
+    let (square, _) = make_square(img, settings);
+    let data = encode_sized(
+        &square,
+        settings.target_size,
+        settings,
+        badge_label,
+        settings.max_file_size,
+    )?;
+    let decoded =
+        image::load_from_memory(&data).map_err(|e| ExportError::Decode(DecodeError::Image(e)))?;
+    Ok((decoded, data.len()))
+}
+
+/// Resize an already-square image to `size`, badge it, encode and write it.
+/// Returns the number of bytes written.
+fn write_sized(
+    square: &DynamicImage,
+    output_path: &Path,
+    size: u32,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+    max_bytes: Option<usize>,
+) -> Result<usize, ExportError> {
+    let data = encode_sized(square, size, settings, badge_label, max_bytes)?;
+
+    // Filename templates can put the output in a subfolder
+    if let Some(dir) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|source| ExportError::Io {
+            action: "create",
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    std::fs::write(output_path, &data).map_err(|source| ExportError::Io {
+        action: "write",
+        path: output_path.to_path_buf(),
+        source,
+    })?;
+    Ok(data.len())
+}
+
+/// Resize an already-square image to `size`, badge it and encode it,
+/// with the provenance comment for JPEG.
+fn encode_sized(
+    square: &DynamicImage,
+    size: u32,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+    max_bytes: Option<usize>,
+) -> Result<Vec<u8>, ExportError> {
+    let resized = resize_square(square, size, settings);
+
+    let stamped = match badge_label {
+        Some(label) if settings.disc_badge && !label.is_empty() => {
+            badge::apply_label_badge(resized, label, settings.badge_position, settings.badge_style)
+        }
+        _ => resized,
+    };
+
+    // Convert to RGB
+    let rgb_image = stamped.to_rgb8();
+
+    let comment = match (settings.format, &settings.provenance) {
+        (OutputFormat::Jpeg, Some(p)) => Some(p.to_comment()),
+        _ => None,
+    };
+    // Leave room for the comment under the size cap
+    let max_bytes = match &comment {
+        Some(text) => max_bytes.map(|m| m.saturating_sub(provenance::comment_overhead(text))),
+        None => max_bytes,
+    };
+    let mut data = encode_image(&rgb_image, settings, max_bytes)?;
+    if let Some(text) = &comment {
+        data = provenance::embed_comment(data, text);
+    }
+    Ok(data)
+}
+
+/// Encode in the configured output format, honouring `max_bytes` for JPEG.
+fn encode_image(
+    rgb_image: &RgbImage,
+    settings: &ExportSettings,
+    max_bytes: Option<usize>,
+) -> Result<Vec<u8>, ExportError> {
+    match settings.format {
+        OutputFormat::Jpeg => match max_bytes {
+            Some(max) => encode_jpeg_within(rgb_image, settings.quality, max),
+            None => encode_baseline_jpeg(rgb_image, settings.quality),
+        },
+        OutputFormat::Png => encode_lossless(rgb_image, image::ImageFormat::Png),
+        OutputFormat::Bmp => encode_lossless(rgb_image, image::ImageFormat::Bmp),
+    }
+}
+
+/// Encode a JPEG no larger than `max_bytes`, using the highest quality (up
+/// to `quality`) that fits. Binary-searches quality since file size grows
+/// (near-)monotonically with it.
+fn encode_jpeg_within(
+    rgb_image: &RgbImage,
+    quality: u8,
+    max_bytes: usize,
+) -> Result<Vec<u8>, ExportError> {
+    let data = encode_baseline_jpeg(rgb_image, quality)?;
+    if data.len() <= max_bytes {
+        return Ok(data);
+    }
+
+    let mut best: Option<(u8, Vec<u8>)> = None;
+    let (mut lo, mut hi) = (MIN_JPEG_QUALITY, quality.saturating_sub(1));
+    while lo <= hi {
+        let mid = lo + (hi - lo) / 2;
+        let data = encode_baseline_jpeg(rgb_image, mid)?;
+        if data.len() <= max_bytes {
+            best = Some((mid, data));
+            lo = mid + 1;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    match best {
+        Some((q, data)) => {
+            log::info!(
+                "Lowered JPEG quality {} -> {} to fit {} bytes ({} bytes)",
+                quality,
+                q,
+                max_bytes,
+                data.len()
+            );
+            Ok(data)
+        }
+        None => Err(ExportError::TooLarge {
+            max_kb: max_bytes / 1024,
+            quality: MIN_JPEG_QUALITY,
+        }),
+    }
+}
+
+/// Encode as PNG or 24-bit BMP. Neither carries colour-profile chunks when
+/// written by the image crate, matching the "no ICC" rule for JPEG output.
+fn encode_lossless(
+    rgb_image: &RgbImage,
+    format: image::ImageFormat,
+) -> Result<Vec<u8>, ExportError> {
+    let mut output = std::io::Cursor::new(Vec::new());
+    rgb_image
+        .write_to(&mut output, format)
+        .map_err(|e| ExportError::Encode(format!("Failed to encode {:?}: {}", format, e)))?;
+    Ok(output.into_inner())
+}
+
+/// Path for an extra-size copy: `game.jpg` → `game_480.jpg`.
+pub fn sized_output_path(output_path: &Path, size: u32) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("cover");
+    let ext = output_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("jpg");
+    output_path.with_file_name(format!("{stem}_{size}.{ext}"))
+}
+
+/// Export artwork from a URL
+pub fn export_artwork_from_url<P: AsRef<Path>>(
+    url: &str,
+    output_path: P,
+    settings: &ExportSettings,
+) -> Result<ExportResult, ExportError> {
+    export_artwork_from_url_with_disc(url, output_path, settings, None, None)
+}
+
+/// Like `export_artwork_from_url`, but stamps a disc-number badge when
+/// `disc_number > 1`.
+pub fn export_artwork_from_url_with_disc<P: AsRef<Path>>(
+    url: &str,
+    output_path: P,
+    settings: &ExportSettings,
+    disc_number: Option<u32>,
+    disc_total: Option<u32>,
+) -> Result<ExportResult, ExportError> {
+    let image_data = fetch_image(url, settings)?;
+    export_artwork_with_disc(&image_data, output_path, settings, disc_number, disc_total)
+}
+
+/// Like `export_artwork_from_url`, but stamps an arbitrary label badge.
+/// Used by the sibling auto-apply path for role-marked discs ("Install",
+/// "Game", "Bonus") that don't have a number to use in `Disc N`.
+pub fn export_artwork_from_url_with_label<P: AsRef<Path>>(
+    url: &str,
+    output_path: P,
+    settings: &ExportSettings,
+    badge_label: Option<&str>,
+) -> Result<ExportResult, ExportError> {
+    let image_data = fetch_image(url, settings)?;
+    export_artwork_with_label(&image_data, output_path, settings, badge_label)
+}
+
+/// Fetch image data from a URL, using the configured download timeout and
+/// reporting the bytes received to `settings.progress`
+fn fetch_image(url: &str, settings: &ExportSettings) -> Result<Vec<u8>, ExportError> {
+    let never = CancelToken::new();
+    crate::net::fetch_bytes_with_progress(
+        url,
+        crate::net::Timeout::Download,
+        settings.cancel.as_ref().unwrap_or(&never),
+        &|transfer| settings.report(ExportProgress::Downloading(transfer)),
+    )
+    .map_err(|e| match e {
+        FetchError::Cancelled => ExportError::Cancelled,
+        e => ExportError::Download(e),
+    })
+}
+
+/// Center-crop an image to a square
+///
+/// If the image is already square, returns it unchanged.
+/// Otherwise, crops from the center to make it square.
+fn crop_to_square(img: DynamicImage) -> (DynamicImage, Option<Region>) {
+    let width = img.width();
+    let height = img.height();
+
+    let size = width.min(height);
+    let square = Region {
+        x: (width - size) / 2,
+        y: (height - size) / 2,
+        width: size,
+        height: size,
+    };
+    crop_to_region(img, square)
+}
+
+/// Cut `region` out of `img`. Returns the image unchanged (and no region)
+/// when `region` covers all of it.
+fn crop_to_region(img: DynamicImage, region: Region) -> (DynamicImage, Option<Region>) {
+    if region.width == img.width() && region.height == img.height() {
+        return (img, None);
+    }
+    let cropped = img.crop_imm(region.x, region.y, region.width, region.height);
+    (cropped, Some(region))
+}
+
+/// Encode image as baseline JPEG
+///
+/// This produces:
+/// - Baseline (non-progressive) JPEG with standard Huffman tables
+/// - YCbCr color space, full-range BT.601 conversion (standard JFIF)
+/// - 4:4:4 chroma subsampling (no chroma downsampling)
+/// - No ICC profile
+/// - No EXIF data
+///
+/// Matches the settings of the original Python export script, which is what
+/// the USBODE firmware was tested against.
+fn encode_baseline_jpeg(rgb_image: &RgbImage, quality: u8) -> Result<Vec<u8>, ExportError> {
+    let width = u16::try_from(rgb_image.width()).map_err(|_| {
+        ExportError::Encode(format!("Image too wide for JPEG: {}", rgb_image.width()))
+    })?;
+    let height = u16::try_from(rgb_image.height()).map_err(|_| {
+        ExportError::Encode(format!("Image too tall for JPEG: {}", rgb_image.height()))
+    })?;
+
+    let mut output = Vec::new();
+    let mut encoder = jpeg_encoder::Encoder::new(&mut output, quality);
+    encoder.set_sampling_factor(jpeg_encoder::SamplingFactor::R_4_4_4);
+    encoder.set_progressive(false);
+    encoder.set_optimized_huffman_tables(false);
+
+    encoder
+        .encode(rgb_image.as_raw(), width, height, jpeg_encoder::ColorType::Rgb)
+        .map_err(|e| ExportError::Encode(format!("Failed to encode JPEG: {}", e)))?;
+
+    Ok(output)
+}
+
+/// Generate output filename from disc image path
+///
+/// Changes the extension to .jpg
+pub fn generate_output_path<P: AsRef<Path>>(disc_path: P) -> String {
+    generate_output_path_for(disc_path, OutputFormat::Jpeg)
+}
+
+/// Like `generate_output_path`, but with the extension for `format`.
+pub fn generate_output_path_for<P: AsRef<Path>>(disc_path: P, format: OutputFormat) -> String {
+    let path = disc_path.as_ref();
+    let ext = format.extension();
+
+    // Use with_extension if the path has a parent directory, otherwise construct with ./
+    if path.parent().is_some() && path.parent() != Some(Path::new("")) {
+        path.with_extension(ext).display().to_string()
+    } else {
+        // For bare filenames, ensure we get "./filename.jpg"
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("cover");
+        format!("./{}.{}", stem, ext)
+    }
+}
+
+/// Which image of a disc's artwork set a file holds. Front-ends that show
+/// more than the cover pick the others up by suffix: `game.jpg` (front),
+/// `game.back.jpg`, `game.disc.jpg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArtworkRole {
+    #[default]
+    Front,
+    Back,
+    Disc,
+}
+
+impl ArtworkRole {
+    pub const ALL: [ArtworkRole; 3] = [ArtworkRole::Front, ArtworkRole::Back, ArtworkRole::Disc];
+
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ArtworkRole::Front => "Front cover",
+            ArtworkRole::Back => "Back cover",
+            ArtworkRole::Disc => "Disc scan",
+        }
+    }
+
+    /// Name suffix placed before the extension; `None` for the front cover,
+    /// which keeps the plain `game.jpg` name USBODE looks for.
+    pub fn suffix(&self) -> Option<&'static str> {
+        match self {
+            ArtworkRole::Front => None,
+            ArtworkRole::Back => Some("back"),
+            ArtworkRole::Disc => Some("disc"),
+        }
+    }
+}
+
+/// Like `generate_output_path_for`, with the role suffix added:
+/// `game.iso` -> `game.back.jpg`.
+pub fn generate_role_output_path<P: AsRef<Path>>(
+    disc_path: P,
+    format: OutputFormat,
+    role: ArtworkRole,
+) -> String {
+    let base = generate_output_path_for(disc_path, format);
+    match role.suffix() {
+        Some(suffix) => Path::new(&base)
+            .with_extension(format!("{}.{}", suffix, format.extension()))
+            .display()
+            .to_string(),
+        None => base,
+    }
+}
+
+/// Where and under what name artwork is written, from the `export` config.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputNaming<'a> {
+    pub format: OutputFormat,
+    /// Filename template (see `naming`)
+    pub name_template: &'a str,
+    /// Central artwork folder; `None` writes next to each disc image
+    pub output_dir: Option<&'a Path>,
+}
+
+impl<'a> OutputNaming<'a> {
+    pub fn from_config(config: &'a ExportConfig) -> Self {
+        Self {
+            format: config.format,
+            name_template: &config.name_template,
+            output_dir: config
+                .output_dir
+                .as_deref()
+                .filter(|d| !d.trim().is_empty())
+                .map(Path::new),
+        }
+    }
+}
+
+/// Output path for `disc_path` per `naming`, with the role suffix and
+/// format extension. An invalid template falls back to the default `<stem>`
+/// naming. In central-folder mode this is the name before any collision
+/// numbering; use `claim_named_output_path` when actually saving.
+pub fn generate_named_output_path<P: AsRef<Path>>(
+    disc_path: P,
+    naming: &OutputNaming,
+    role: ArtworkRole,
+) -> String {
+    let disc_path = disc_path.as_ref();
+    if naming.name_template == DEFAULT_NAME_TEMPLATE && naming.output_dir.is_none() {
+        return generate_role_output_path(disc_path, naming.format, role);
+    }
+    let dir = match naming.output_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => disc_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf(),
+    };
+    role_file_path(&dir, &output_name(disc_path, naming.name_template), naming.format, role)
+}
+
+/// Like `generate_named_output_path`, but in central-folder mode claims
+/// the name for `disc_path` first so a different disc that expands to the
+/// same name gets `name (2)` rather than overwriting (see `central`).
+pub fn claim_named_output_path<P: AsRef<Path>>(
+    disc_path: P,
+    naming: &OutputNaming,
+    role: ArtworkRole,
+) -> Result<String, String> {
+    let disc_path = disc_path.as_ref();
+    let Some(dir) = naming.output_dir else {
+        return Ok(generate_named_output_path(disc_path, naming, role));
+    };
+    let name = output_name(disc_path, naming.name_template);
+    let name = central::claim_name(dir, &name, disc_path, naming.format.extension())?;
+    Ok(role_file_path(dir, &name, naming.format, role))
+}
+
+/// Template-expanded name, or the disc's stem if the template is invalid.
+fn output_name(disc_path: &Path, name_template: &str) -> PathBuf {
+    render_name(name_template, disc_path).unwrap_or_else(|_| {
+        PathBuf::from(disc_path.file_stem().unwrap_or(std::ffi::OsStr::new("cover")))
+    })
+}
+
+fn role_file_path(dir: &Path, name: &Path, format: OutputFormat, role: ArtworkRole) -> String {
+    let file_name = match role.suffix() {
+        Some(suffix) => format!("{}.{}.{}", name.display(), suffix, format.extension()),
+        None => format!("{}.{}", name.display(), format.extension()),
+    };
+    dir.join(file_name).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_named_output_path() {
+        let mut naming = OutputNaming {
+            format: OutputFormat::Jpeg,
+            name_template: DEFAULT_NAME_TEMPLATE,
+            output_dir: None,
+        };
+        assert_eq!(
+            generate_named_output_path("/path/to/game.iso", &naming, ArtworkRole::Front),
+            "/path/to/game.jpg"
+        );
+
+        naming.format = OutputFormat::Png;
+        naming.name_template = "art/{title}";
+        assert_eq!(
+            Path::new(&generate_named_output_path(
+                "/path/to/Myst (USA).iso",
+                &naming,
+                ArtworkRole::Back
+            )),
+            Path::new("/path/to/art/Myst.back.png")
+        );
+
+        naming.output_dir = Some(Path::new("/covers"));
+        assert_eq!(
+            Path::new(&generate_named_output_path(
+                "/path/to/Myst (USA).iso",
+                &naming,
+                ArtworkRole::Front
+            )),
+            Path::new("/covers/art/Myst.png")
+        );
+    }
+
+    #[test]
+    fn test_generate_role_output_path() {
+        assert_eq!(
+            generate_role_output_path("/path/to/game.iso", OutputFormat::Jpeg, ArtworkRole::Front),
+            "/path/to/game.jpg"
+        );
+        assert_eq!(
+            generate_role_output_path("/path/to/game.iso", OutputFormat::Jpeg, ArtworkRole::Back),
+            "/path/to/game.back.jpg"
+        );
+        assert_eq!(
+            generate_role_output_path("game.bin", OutputFormat::Png, ArtworkRole::Disc),
+            "./game.disc.png"
+        );
+    }
+
+    #[test]
+    fn test_generate_output_path() {
+        assert_eq!(
+            generate_output_path("/path/to/game.iso"),
+            "/path/to/game.jpg"
+        );
+        assert_eq!(
+            generate_output_path("/path/to/game.chd"),
+            "/path/to/game.jpg"
+        );
+        assert_eq!(
+            generate_output_path("game.bin"),
+            "./game.jpg"
+        );
+    }
+
+    #[test]
+    fn test_generate_output_path_for_format() {
+        assert_eq!(
+            generate_output_path_for("/path/to/game.iso", OutputFormat::Png),
+            "/path/to/game.png"
+        );
+        assert_eq!(
+            generate_output_path_for("game.bin", OutputFormat::Bmp),
+            "./game.bmp"
+        );
+    }
+
+    /// Walk the marker segments of a JPEG up to SOS. Returns (marker, payload).
+    fn jpeg_segments(data: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        assert_eq!(&data[..2], &[0xFF, 0xD8], "missing SOI");
+        let mut out = Vec::new();
+        let mut pos = 2;
+        while pos + 4 <= data.len() {
+            assert_eq!(data[pos], 0xFF, "expected marker at {pos}");
+            let marker = data[pos + 1];
+            let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+            out.push((marker, data[pos + 4..pos + 2 + len].to_vec()));
+            if marker == 0xDA {
+                break;
+            }
+            pos += 2 + len;
+        }
+        out
+    }
+
+    #[test]
+    fn test_jpeg_is_baseline_444_ycbcr() {
+        let img = RgbImage::from_fn(240, 240, |x, y| image::Rgb([x as u8, y as u8, 128]));
+        let data = encode_baseline_jpeg(&img, JPEG_QUALITY).unwrap();
+        assert_eq!(&data[data.len() - 2..], &[0xFF, 0xD9], "missing EOI");
+
+        let segments = jpeg_segments(&data);
+        let markers: Vec<u8> = segments.iter().map(|(m, _)| *m).collect();
+
+        // Baseline SOF0 only; no progressive (SOF2) or other frame types.
+        assert!(markers.contains(&0xC0), "no SOF0 in {markers:X?}");
+        assert!(!markers.contains(&0xC2), "progressive frame present");
+        // No EXIF (APP1) or ICC profile (APP2).
+        assert!(!markers.contains(&0xE1), "EXIF segment present");
+        assert!(!markers.contains(&0xE2), "ICC segment present");
+
+        let (_, sof) = segments.iter().find(|(m, _)| *m == 0xC0).unwrap();
+        // precision, height, width, component count
+        assert_eq!(sof[0], 8);
+        assert_eq!(u16::from_be_bytes([sof[1], sof[2]]), 240);
+        assert_eq!(u16::from_be_bytes([sof[3], sof[4]]), 240);
+        assert_eq!(sof[5], 3, "expected 3 YCbCr components");
+        for c in 0..3 {
+            let comp = &sof[6 + c * 3..9 + c * 3];
+            assert_eq!(comp[0], c as u8 + 1, "component ids should be 1,2,3");
+            assert_eq!(comp[1], 0x11, "component {} not 1x1 (4:4:4)", c + 1);
+        }
+    }
+
+    fn noisy_image() -> RgbImage {
+        // Pseudo-random pixels so the JPEG doesn't compress to nothing.
+        let mut state = 12345u32;
+        RgbImage::from_fn(240, 240, |_, _| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let b = state.to_be_bytes();
+            image::Rgb([b[0], b[1], b[2]])
+        })
+    }
+
+    #[test]
+    fn test_jpeg_size_limit_lowers_quality() {
+        let img = noisy_image();
+        let full = encode_baseline_jpeg(&img, JPEG_QUALITY).unwrap();
+        let limit = full.len() / 2;
+        let fitted = encode_jpeg_within(&img, JPEG_QUALITY, limit).unwrap();
+        assert!(fitted.len() <= limit);
+        assert!(!fitted.is_empty());
+    }
+
+    #[test]
+    fn test_jpeg_size_limit_unreachable() {
+        assert!(matches!(
+            encode_jpeg_within(&noisy_image(), JPEG_QUALITY, 100),
+            Err(ExportError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lossless_formats_round_trip() {
+        let img = RgbImage::from_pixel(4, 4, image::Rgb([10, 200, 30]));
+        for (format, image_format) in [
+            (OutputFormat::Png, image::ImageFormat::Png),
+            (OutputFormat::Bmp, image::ImageFormat::Bmp),
+        ] {
+            let settings = ExportSettings {
+                format,
+                ..ExportSettings::default()
+            };
+            let data = encode_image(&img, &settings, None).unwrap();
+            assert_eq!(image::guess_format(&data).unwrap(), image_format);
+            let decoded = image::load_from_memory(&data).unwrap().to_rgb8();
+            assert_eq!(decoded, img);
+        }
+    }
+
+    #[test]
+    fn test_crop_to_square_already_square() {
+        let img = DynamicImage::new_rgb8(100, 100);
+        let (result, crop) = crop_to_square(img);
+        assert_eq!(crop, None);
+        assert_eq!(result.width(), 100);
+        assert_eq!(result.height(), 100);
+    }
+
+    #[test]
+    fn test_crop_to_square_landscape() {
+        let img = DynamicImage::new_rgb8(200, 100);
+        let (result, crop) = crop_to_square(img);
+        assert_eq!(crop.map(|r| (r.x, r.y)), Some((50, 0)));
+        assert_eq!(result.width(), 100);
+        assert_eq!(result.height(), 100);
+    }
+
+    #[test]
+    fn test_crop_to_square_portrait() {
+        let img = DynamicImage::new_rgb8(100, 200);
+        let (result, crop) = crop_to_square(img);
+        assert_eq!(crop.map(|r| (r.x, r.y)), Some((0, 50)));
+        assert_eq!(result.width(), 100);
+        assert_eq!(result.height(), 100);
+    }
+
+    #[test]
+    fn test_default_settings() {
+        let settings = ExportSettings::default();
+        assert_eq!(settings.target_size, 240);
+        assert_eq!(settings.quality, 90);
+        assert!(settings.extra_sizes.is_empty());
+    }
+
+    #[test]
+    fn test_settings_from_config_clamps_and_dedups() {
+        let config = ExportConfig {
+            target_size: 8,
+            extra_sizes: vec![480, 16, 480, 9000],
+            quality: 0,
+            ..ExportConfig::default()
+        };
+        let settings = ExportSettings::from_config(&config);
+        assert_eq!(settings.target_size, MIN_TARGET_SIZE);
+        assert_eq!(settings.quality, MIN_JPEG_QUALITY);
+        assert_eq!(settings.extra_sizes, vec![480, MAX_TARGET_SIZE]);
+    }
+
+    #[test]
+    fn test_sized_output_path() {
+        assert_eq!(
+            sized_output_path(Path::new("/path/to/game.jpg"), 480),
+            PathBuf::from("/path/to/game_480.jpg")
+        );
+    }
+
+    #[test]
+    fn test_export_writes_extra_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(300, 200)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let settings = ExportSettings {
+            extra_sizes: vec![480],
+            ..ExportSettings::default()
+        };
+        let result = export_artwork(&png, &out, &settings).unwrap();
+        assert!(result.was_cropped);
+        assert_eq!(result.extra_outputs.len(), 1);
+        let big = image::open(dir.path().join("game_480.jpg")).unwrap();
+        assert_eq!((big.width(), big.height()), (480, 480));
+        let main = image::open(&out).unwrap();
+        assert_eq!((main.width(), main.height()), (240, 240));
+    }
+
+    #[test]
+    fn test_export_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(300, 200)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let settings = ExportSettings {
+            extra_sizes: vec![480],
+            progress: Some(sender.into()),
+            ..ExportSettings::default()
+        };
+        export_artwork(&png, &out, &settings).unwrap();
+        drop(settings);
+        let reports: Vec<ExportProgress> = receiver.iter().collect();
+        assert_eq!(
+            reports,
+            [
+                ExportProgress::Processing,
+                ExportProgress::Writing { n: 1, total: 2 },
+                ExportProgress::Writing { n: 2, total: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_reports_undecodable_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let err = export_artwork(b"not an image", &out, &ExportSettings::default()).unwrap_err();
+        assert!(matches!(err, ExportError::Decode(DecodeError::Image(_))), "{err}");
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn test_render_saved_output_matches_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let img = DynamicImage::ImageRgb8(noisy_image());
+        let mut png = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let settings = ExportSettings {
+            quality: 60,
+            ..ExportSettings::default()
+        };
+        let (rendered, bytes) = render_saved_output(img, &settings, Some("Disc 2")).unwrap();
+        let result = export_artwork_with_label(&png, &out, &settings, Some("Disc 2")).unwrap();
+        let saved = std::fs::read(&out).unwrap();
+        assert_eq!(result.file_size, saved.len());
+        assert_eq!(bytes, saved.len());
+        assert_eq!(rendered.to_rgb8(), image::load_from_memory(&saved).unwrap().to_rgb8());
+    }
+
+    #[test]
+    fn test_export_writes_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(300, 200)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        export_artwork(&png, &out, &ExportSettings::default()).unwrap();
+        assert!(!sidecar_path(&out).exists());
+
+        let settings = ExportSettings {
+            smart_crop: false,
+            write_sidecar: true,
+            ..ExportSettings::default()
+        };
+        export_artwork(&png, &out, &settings).unwrap();
+        let sidecar = read_sidecar(&out).unwrap().unwrap();
+        assert_eq!(sidecar.original_size, (300, 200));
+        assert_eq!(
+            sidecar.crop,
+            Some(Region {
+                x: 50,
+                y: 0,
+                width: 200,
+                height: 200
+            })
+        );
+        assert_eq!(sidecar.settings.target_size, 240);
+    }
+
+    #[test]
+    fn test_export_pad_mode_letterboxes() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(120, 240, image::Rgb([255, 255, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let settings = ExportSettings {
+            fit: FitMode::Pad,
+            ..ExportSettings::default()
+        };
+        let result = export_artwork(&png, &out, &settings).unwrap();
+        assert!(!result.was_cropped);
+        let saved = image::open(&out).unwrap().to_rgb8();
+        assert_eq!(saved.dimensions(), (240, 240));
+        // Black bars left and right, the white cover in the middle.
+        assert!(saved.get_pixel(5, 120)[0] < 30);
+        assert!(saved.get_pixel(120, 120)[0] > 225);
+    }
+
+    #[test]
+    fn test_export_backs_up_overwritten_cover() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let settings = ExportSettings {
+            extra_sizes: vec![500],
+            backup_existing: true,
+            write_sidecar: true,
+            ..ExportSettings::default()
+        };
+        let first = export_placeholder("Myst", &out, &settings).unwrap();
+        assert!(first.written.iter().all(|file| file.backup.is_none()));
+        let first_sidecar = std::fs::read(sidecar_path(&out)).unwrap();
+
+        let second = export_placeholder("Riven", &out, &settings).unwrap();
+        // The cover, the extra size and the sidecar, main output first
+        assert_eq!(second.written.len(), 3);
+        assert_eq!(second.written[0].path, out);
+        let backup = second.written[0].backup.clone().unwrap();
+        assert!(backup.starts_with(dir.path().join(BACKUP_DIR_NAME)));
+        assert!(validate_artwork(&backup).unwrap().is_compliant());
+        let backups = std::fs::read_dir(dir.path().join(BACKUP_DIR_NAME)).unwrap().count();
+        assert_eq!(backups, 3);
+
+        // Undo puts all three back
+        undo_export(&second.written).unwrap();
+        assert_eq!(std::fs::read(sidecar_path(&out)).unwrap(), first_sidecar);
+        let backups = std::fs::read_dir(dir.path().join(BACKUP_DIR_NAME)).unwrap().count();
+        assert_eq!(backups, 0);
+        // Undoing the first save removes everything it created
+        undo_export(&first.written).unwrap();
+        assert!(!out.exists());
+        assert!(!sidecar_path(&out).exists());
+
+        // Without backups the old cover is simply replaced
+        export_placeholder("Myst", &out, &ExportSettings::default()).unwrap();
+        let third = export_placeholder("Myst", &out, &ExportSettings::default()).unwrap();
+        assert!(third.written[0].backup.is_none());
+    }
+
+    #[test]
+    fn test_cancelled_export_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let settings = ExportSettings {
+            cancel: Some(cancel),
+            ..ExportSettings::default()
+        };
+        let result = export_placeholder("Myst", &out, &settings);
+        assert!(matches!(result, Err(ExportError::Cancelled)));
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn test_export_placeholder_is_compliant() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("game.jpg");
+        let result = export_placeholder("Myst", &out, &ExportSettings::default()).unwrap();
+        assert_eq!(result.final_size, (240, 240));
+        assert!(validate_artwork(&out).unwrap().is_compliant());
+    }
+}
@@ -0,0 +1,155 @@
+//! Output filename templates.
+//!
+//! USBODE wants `<disc stem>.jpg` next to the image, but other front-ends
+//! expect covers named after the game title or serial, sometimes in a
+//! subfolder. The template is expanded per disc from fields parsed out of
+//! the disc filename:
+//!
+//! - `{stem}`: disc filename without extension (the default template)
+//! - `{title}`: cleaned game title
+//! - `{serial}`, `{region}`, `{year}`, `{disc}`: when present in the name
+//!
+//! `/` in the template makes subfolders relative to the disc's folder.
+//! Fields that aren't present expand to nothing, and brackets left empty
+//! as a result are dropped, so `{title} ({region})` still gives a clean
+//! name for discs without a region tag.
+
+use std::path::{Path, PathBuf};
+
+use crate::disc::parse_filename;
+
+/// Template that reproduces the USBODE `<stem>.jpg` naming.
+pub const DEFAULT_NAME_TEMPLATE: &str = "{stem}";
+
+/// Placeholders accepted in a template.
+const FIELDS: [&str; 6] = ["stem", "title", "serial", "region", "year", "disc"];
+
+/// Characters not allowed in a file name on at least one supported OS.
+const RESERVED: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Check `template` for unknown placeholders, unbalanced braces and paths
+/// that would escape the disc's folder.
+pub fn validate_name_template(template: &str) -> Result<(), String> {
+    expand(template, |_| Some(String::new())).map(|_| ())
+}
+
+/// Expand `template` for `disc_path`. Returns a path relative to the disc's
+/// folder, without extension.
+pub fn render_name(template: &str, disc_path: &Path) -> Result<PathBuf, String> {
+    let parsed = parse_filename(disc_path);
+    let name = expand(template, |field| match field {
+        "stem" => Some(parsed.original.clone()),
+        "title" => Some(parsed.title.clone()),
+        "serial" => parsed.serial.clone(),
+        "region" => parsed.region.clone(),
+        "year" => parsed.year.map(|y| y.to_string()),
+        "disc" => parsed.disc_number.map(|n| n.to_string()),
+        _ => None,
+    })?;
+    if name.as_os_str().is_empty() {
+        // Everything expanded to nothing: fall back to the USBODE name.
+        return Ok(PathBuf::from(&parsed.original));
+    }
+    Ok(name)
+}
+
+fn expand(template: &str, value: impl Fn(&str) -> Option<String>) -> Result<PathBuf, String> {
+    if template.trim().is_empty() {
+        return Err("Filename template is empty".to_string());
+    }
+    if template.starts_with('/') || template.starts_with('\\') {
+        return Err("Filename template must be relative to the disc's folder".to_string());
+    }
+
+    let mut path = PathBuf::new();
+    for part in template.split(['/', '\\']) {
+        if part.trim() == ".." {
+            return Err("Filename template can't use '..'".to_string());
+        }
+        let mut out = String::new();
+        let mut rest = part;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(format!("Unmatched '}}' in filename template: {}", template));
+            }
+            out.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let close = after
+                .find('}')
+                .ok_or_else(|| format!("Unclosed '{{' in filename template: {}", template))?;
+            let field = &after[..close];
+            if !FIELDS.contains(&field) {
+                return Err(format!(
+                    "Unknown placeholder {{{}}} in filename template",
+                    field
+                ));
+            }
+            if let Some(v) = value(field) {
+                out.push_str(&v);
+            }
+            rest = &after[close + 1..];
+        }
+        out.push_str(rest);
+
+        let component = tidy(&out);
+        if !component.is_empty() {
+            path.push(component);
+        }
+    }
+    Ok(path)
+}
+
+/// Strip characters that aren't valid in a file name, drop brackets emptied
+/// by missing fields, collapse whitespace, and trim separators left dangling
+/// at either end.
+fn tidy(s: &str) -> String {
+    let mut out: String = s
+        .chars()
+        .filter(|c| !RESERVED.contains(c) && !c.is_control())
+        .collect();
+    for empty in ["()", "[]", "( )", "[ ]"] {
+        out = out.replace(empty, "");
+    }
+    let out = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    out.trim_matches(|c: char| c == '-' || c == '_' || c == '.' || c.is_whitespace())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, file: &str) -> String {
+        render_name(template, Path::new(file))
+            .unwrap()
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn default_template_keeps_the_stem() {
+        assert_eq!(
+            render(DEFAULT_NAME_TEMPLATE, "/roms/Myst (USA) (Disc 1).chd"),
+            "Myst (USA) (Disc 1)"
+        );
+    }
+
+    #[test]
+    fn fields_and_subfolders() {
+        let name = render("covers/{title} [{region}]", "/roms/Myst (USA).iso");
+        assert_eq!(Path::new(&name), Path::new("covers/Myst [USA]"));
+        // Missing region: the empty brackets disappear.
+        assert_eq!(render("{title} [{region}]", "/roms/Myst.iso"), "Myst");
+    }
+
+    #[test]
+    fn bad_templates_are_rejected() {
+        assert!(validate_name_template("{stem}").is_ok());
+        assert!(validate_name_template("{bogus}").is_err());
+        assert!(validate_name_template("{title").is_err());
+        assert!(validate_name_template("title}").is_err());
+        assert!(validate_name_template("../{stem}").is_err());
+        assert!(validate_name_template("/art/{stem}").is_err());
+        assert!(validate_name_template("  ").is_err());
+    }
+}
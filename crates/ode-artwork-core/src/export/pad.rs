@@ -0,0 +1,115 @@
+//! Pad-to-square fitting.
+//!
+//! Tall DVD and game covers lose their title when center-cropped. Padding
+//! keeps the whole cover and letterboxes it onto a square canvas, filled with
+//! a solid colour or a blurred, zoomed copy of the cover itself.
+
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, GenericImageView, Rgb, Rgba, RgbaImage};
+
+use crate::config::PadFill;
+
+/// Edge length the blurred background is computed at before being scaled
+/// up. Blurring a full-resolution scan directly is far too slow.
+const BLUR_WORK_SIZE: u32 = 64;
+
+/// Gaussian sigma at `BLUR_WORK_SIZE`.
+const BLUR_SIGMA: f32 = 6.0;
+
+/// Canvas fill behind padded artwork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadBackground {
+    Color(Rgb<u8>),
+    Blur,
+}
+
+impl Default for PadBackground {
+    fn default() -> Self {
+        PadBackground::Color(Rgb([0, 0, 0]))
+    }
+}
+
+impl PadBackground {
+    /// From the `pad_fill` / `pad_color` config pair. An unparseable colour
+    /// falls back to black.
+    pub fn from_config(fill: PadFill, color: &str) -> Self {
+        match fill {
+            PadFill::Color => PadBackground::Color(parse_hex_color(color).unwrap_or(Rgb([0, 0, 0]))),
+            PadFill::Blur => PadBackground::Blur,
+        }
+    }
+}
+
+/// Parse `#RRGGBB` (the `#` is optional).
+pub fn parse_hex_color(s: &str) -> Option<Rgb<u8>> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Rgb([channel(0)?, channel(2)?, channel(4)?]))
+}
+
+/// Format a colour as `#RRGGBB`.
+pub fn format_hex_color(c: Rgb<u8>) -> String {
+    format!("#{:02X}{:02X}{:02X}", c[0], c[1], c[2])
+}
+
+/// Center `img` on a square canvas the size of its longer edge.
+pub fn pad_to_square(img: DynamicImage, background: PadBackground) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    if width == height {
+        return img;
+    }
+    let side = width.max(height);
+
+    let mut canvas = match background {
+        PadBackground::Color(c) => RgbaImage::from_pixel(side, side, Rgba([c[0], c[1], c[2], 255])),
+        PadBackground::Blur => {
+            let small = img
+                .resize_to_fill(BLUR_WORK_SIZE, BLUR_WORK_SIZE, FilterType::Triangle)
+                .to_rgba8();
+            let blurred = imageops::blur(&small, BLUR_SIGMA);
+            imageops::resize(&blurred, side, side, FilterType::Triangle)
+        }
+    };
+
+    let x = (side - width) / 2;
+    let y = (side - height) / 2;
+    imageops::overlay(&mut canvas, &img.to_rgba8(), x as i64, y as i64);
+    DynamicImage::ImageRgba8(canvas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    #[test]
+    fn pads_tall_cover_with_color() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(100, 200, Rgb([200, 10, 10])));
+        let out = pad_to_square(img, PadBackground::Color(Rgb([0, 0, 255]))).to_rgb8();
+        assert_eq!(out.dimensions(), (200, 200));
+        assert_eq!(*out.get_pixel(10, 100), Rgb([0, 0, 255]));
+        assert_eq!(*out.get_pixel(100, 100), Rgb([200, 10, 10]));
+        assert_eq!(*out.get_pixel(190, 100), Rgb([0, 0, 255]));
+    }
+
+    #[test]
+    fn blurred_fill_takes_cover_colours() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 100, Rgb([20, 180, 60])));
+        let out = pad_to_square(img, PadBackground::Blur).to_rgb8();
+        assert_eq!(out.dimensions(), (300, 300));
+        let top = out.get_pixel(150, 5);
+        assert!(top[1] > 150 && top[0] < 50, "{top:?}");
+    }
+
+    #[test]
+    fn hex_colors_round_trip() {
+        assert_eq!(parse_hex_color("#FF8000"), Some(Rgb([255, 128, 0])));
+        assert_eq!(parse_hex_color("ff8000"), Some(Rgb([255, 128, 0])));
+        assert_eq!(parse_hex_color("#FFF"), None);
+        assert_eq!(parse_hex_color("#GG0000"), None);
+        assert_eq!(format_hex_color(Rgb([255, 128, 0])), "#FF8000");
+    }
+}
@@ -0,0 +1,221 @@
+//! Generated placeholder artwork.
+//!
+//! Some discs have no usable cover anywhere (homebrew, obscure demo discs,
+//! personal backups). Rather than leaving a blank slot in the ODE menu, draw
+//! the title on a coloured background. The colour is derived from the title,
+//! so a shelf of placeholders isn't one flat block and the same disc gets the
+//! same colour on every run.
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use super::badge::FONT_BYTES;
+
+/// Fraction of the edge kept clear of text on each side.
+const TEXT_MARGIN: f32 = 0.08;
+
+/// Largest and smallest font size, as fractions of the edge.
+const MAX_FONT: f32 = 0.2;
+const MIN_FONT: f32 = 0.05;
+
+/// Line height relative to the font size.
+const LINE_SPACING: f32 = 1.15;
+
+/// Draw a `size`x`size` placeholder with `title` wrapped and sized to fit.
+pub fn render_placeholder(title: &str, size: u32) -> DynamicImage {
+    let size = size.max(1);
+    let mut canvas = background(title, size);
+
+    let title = title.trim();
+    let font = match FontVec::try_from_vec(FONT_BYTES.to_vec()) {
+        Ok(f) => f,
+        Err(_) => return DynamicImage::ImageRgba8(canvas), // No font: background only.
+    };
+    if title.is_empty() {
+        return DynamicImage::ImageRgba8(canvas);
+    }
+
+    let sizef = size as f32;
+    let (scale, lines) = fit_text(&font, title, sizef);
+    let line_height = scale.y * LINE_SPACING;
+    let block_height = line_height * lines.len() as f32;
+
+    let mut y = (sizef - block_height) / 2.0;
+    for line in &lines {
+        let width = text_width(&font, scale, line);
+        let x = (sizef - width) / 2.0;
+        // Soft shadow first so light backgrounds still read.
+        let offset = (scale.y / 16.0).max(1.0);
+        imageproc::drawing::draw_text_mut(
+            &mut canvas,
+            Rgba([0, 0, 0, 160]),
+            (x + offset) as i32,
+            (y + offset) as i32,
+            scale,
+            &font,
+            line,
+        );
+        imageproc::drawing::draw_text_mut(
+            &mut canvas,
+            Rgba([255, 255, 255, 255]),
+            x as i32,
+            y as i32,
+            scale,
+            &font,
+            line,
+        );
+        y += line_height;
+    }
+
+    DynamicImage::ImageRgba8(canvas)
+}
+
+/// Largest font size (from `MAX_FONT` down to `MIN_FONT` of `edge`) at which
+/// `title` wraps into a block that fits inside the margins of an `edge`-sized
+/// square. Falls back to the minimum size, however it wraps.
+fn fit_text(font: &FontVec, title: &str, edge: f32) -> (PxScale, Vec<String>) {
+    let max_extent = edge * (1.0 - 2.0 * TEXT_MARGIN);
+    let mut px = (edge * MAX_FONT).max(1.0);
+    let min_px = (edge * MIN_FONT).max(1.0);
+    loop {
+        let scale = PxScale::from(px);
+        let lines = wrap_text(font, scale, title, max_extent);
+        let fits_height = lines.len() as f32 * px * LINE_SPACING <= max_extent;
+        let fits_width = lines
+            .iter()
+            .all(|l| text_width(font, scale, l) <= max_extent);
+        if (fits_height && fits_width) || px <= min_px {
+            return (scale, lines);
+        }
+        px = (px * 0.92).max(min_px);
+    }
+}
+
+/// Greedy word wrap. Words wider than a line on their own are left whole
+/// (the caller shrinks the font until they fit).
+fn wrap_text(font: &FontVec, scale: PxScale, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current.push_str(word);
+            continue;
+        }
+        let candidate = format!("{current} {word}");
+        if text_width(font, scale, &candidate) <= max_width {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn text_width(font: &FontVec, scale: PxScale, text: &str) -> f32 {
+    let scaled = font.as_scaled(scale);
+    text.chars()
+        .map(|ch| scaled.h_advance(scaled.glyph_id(ch)))
+        .sum()
+}
+
+/// Diagonal gradient in a hue picked from the title, with a faint disc
+/// outline in the middle.
+fn background(title: &str, size: u32) -> RgbaImage {
+    let hue = (fnv1a(title.trim().to_lowercase().as_bytes()) % 360) as f32;
+    let top = hsv_to_rgb(hue, 0.55, 0.45);
+    let bottom = hsv_to_rgb((hue + 30.0) % 360.0, 0.65, 0.18);
+
+    let sizef = size as f32;
+    let center = sizef / 2.0;
+    let ring = sizef * 0.42;
+    let ring_width = (sizef / 120.0).max(1.0);
+
+    RgbaImage::from_fn(size, size, |x, y| {
+        let t = (x as f32 + y as f32) / (2.0 * sizef);
+        let mut c = [0.0f32; 3];
+        for (i, v) in c.iter_mut().enumerate() {
+            *v = top[i] + (bottom[i] - top[i]) * t;
+        }
+        let dx = x as f32 + 0.5 - center;
+        let dy = y as f32 + 0.5 - center;
+        let d = (dx * dx + dy * dy).sqrt();
+        if (d - ring).abs() < ring_width || (d - ring * 0.3).abs() < ring_width {
+            for v in &mut c {
+                *v += (255.0 - *v) * 0.08;
+            }
+        }
+        let [r, g, b] = c.map(|v| v.round().clamp(0.0, 255.0) as u8);
+        Rgba([r, g, b, 255])
+    })
+}
+
+/// `h` in degrees, `s`/`v` in 0..=1; returns 0..=255 channels.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = v - c;
+    let (r, g, b) = match (h / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    [r, g, b].map(|ch| (ch + m) * 255.0)
+}
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5u32, |hash, b| {
+        (hash ^ u32::from(*b)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+
+    fn font() -> FontVec {
+        FontVec::try_from_vec(FONT_BYTES.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn renders_requested_size_with_text() {
+        let img = render_placeholder("Myst", 240);
+        assert_eq!(img.dimensions(), (240, 240));
+        let rgba = img.to_rgba8();
+        let white = rgba
+            .pixels()
+            .filter(|p| p[0] > 240 && p[1] > 240 && p[2] > 240)
+            .count();
+        assert!(
+            white > 100,
+            "expected title text, found {white} white pixels"
+        );
+    }
+
+    #[test]
+    fn long_titles_wrap_and_shrink() {
+        let font = font();
+        let title = "The Journeyman Project Turbo: Pegasus Prime Collector's Edition";
+        let (scale, lines) = fit_text(&font, title, 240.0);
+        assert!(lines.len() > 1);
+        assert!(scale.y < 240.0 * MAX_FONT);
+        let max_extent = 240.0 * (1.0 - 2.0 * TEXT_MARGIN);
+        assert!(lines
+            .iter()
+            .all(|l| text_width(&font, scale, l) <= max_extent));
+        assert_eq!(lines.join(" "), title);
+    }
+
+    #[test]
+    fn background_is_stable_per_title() {
+        let a = background("Myst", 32);
+        assert_eq!(a, background("Myst", 32));
+        assert_ne!(a, background("Riven", 32));
+    }
+}
@@ -0,0 +1,106 @@
+//! Progress of a running export, for a determinate progress bar.
+//!
+//! An export with `ExportSettings::progress` set reports an [`ExportProgress`]
+//! as it goes: download byte counts (when it fetches its source), then
+//! processing, then one report per output file written.
+
+use std::fmt;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use crate::net::Transfer;
+
+/// Share of the bar the download takes up when its size is known.
+const DOWNLOAD_SHARE: f32 = 0.5;
+
+/// Share of the bar done once the source is decoded and squared.
+const PROCESSED_SHARE: f32 = 0.6;
+
+/// Where an export is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportProgress {
+    /// Fetching the source image
+    Downloading(Transfer),
+    /// Decoding, editing and squaring the source
+    Processing,
+    /// Resizing, encoding and writing output `n` (from 1) of `total`
+    Writing { n: usize, total: usize },
+}
+
+impl ExportProgress {
+    /// Share of the whole export done, or `None` while downloading without
+    /// a known size.
+    pub fn fraction(&self) -> Option<f32> {
+        match self {
+            ExportProgress::Downloading(transfer) => {
+                transfer.fraction().map(|f| f * DOWNLOAD_SHARE)
+            }
+            ExportProgress::Processing => Some(DOWNLOAD_SHARE),
+            ExportProgress::Writing { n, total } => {
+                let written = n.saturating_sub(1) as f32 / (*total).max(1) as f32;
+                Some(PROCESSED_SHARE + (1.0 - PROCESSED_SHARE) * written)
+            }
+        }
+    }
+}
+
+/// Where an export's reports go: a callback run on the thread doing the
+/// export. A channel's `Sender` converts into one, for a UI thread to poll.
+#[derive(Clone)]
+pub struct ProgressHook(Arc<dyn Fn(ExportProgress) + Send + Sync>);
+
+impl ProgressHook {
+    pub fn new(report: impl Fn(ExportProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(report))
+    }
+
+    pub fn report(&self, progress: ExportProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressHook")
+    }
+}
+
+/// A closed channel just means nobody is watching any more.
+impl From<Sender<ExportProgress>> for ProgressHook {
+    fn from(sender: Sender<ExportProgress>) -> Self {
+        Self::new(move |progress| {
+            let _ = sender.send(progress);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraction_rises_through_the_stages() {
+        let downloading = ExportProgress::Downloading(Transfer {
+            done: 300,
+            total: Some(600),
+        });
+        let stages = [
+            downloading,
+            ExportProgress::Processing,
+            ExportProgress::Writing { n: 1, total: 2 },
+            ExportProgress::Writing { n: 2, total: 2 },
+        ];
+        let fractions: Vec<f32> = stages.iter().map(|s| s.fraction().unwrap()).collect();
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]), "{fractions:?}");
+        assert!(fractions.iter().all(|f| (0.0..1.0).contains(f)));
+    }
+
+    #[test]
+    fn unknown_download_size_has_no_fraction() {
+        let progress = ExportProgress::Downloading(Transfer {
+            done: 300,
+            total: None,
+        });
+        assert_eq!(progress.fraction(), None);
+    }
+}
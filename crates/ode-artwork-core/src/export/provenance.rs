@@ -0,0 +1,176 @@
+//! Provenance comment embedded in exported JPEGs.
+//!
+//! Once a cover is on the SD card there's no telling where it came from or
+//! which disc it was picked for. Exports carry a short JPEG comment (COM
+//! segment) naming the tool, the game title, the source URL and the disc's
+//! SHA-1 when it was hashed. Decoders ignore COM, so USBODE is unaffected.
+
+use std::path::Path;
+
+use super::validate::jpeg_segments;
+
+/// First word of every comment we write; identifies our own covers.
+const TOOL_NAME: &str = "ODE-artwork-downloader";
+
+/// COM marker.
+const COM: u8 = 0xFE;
+
+/// Largest COM payload (segment length is a u16 that includes itself).
+const MAX_COMMENT: usize = u16::MAX as usize - 2;
+
+/// Where a cover came from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Provenance {
+    /// Version of the tool that wrote it.
+    pub tool_version: String,
+    pub title: Option<String>,
+    pub source_url: Option<String>,
+    /// SHA-1 of the disc image's data track.
+    pub disc_sha1: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance stamped with this build's version.
+    pub fn new(
+        title: Option<String>,
+        source_url: Option<String>,
+        disc_sha1: Option<String>,
+    ) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            title,
+            source_url,
+            disc_sha1,
+        }
+    }
+
+    /// Comment text: a `<tool> <version>` line, then `key: value` lines.
+    pub fn to_comment(&self) -> String {
+        let mut out = format!("{} {}", TOOL_NAME, self.tool_version);
+        for (key, value) in [
+            ("title", &self.title),
+            ("source", &self.source_url),
+            ("disc-sha1", &self.disc_sha1),
+        ] {
+            if let Some(v) = value {
+                // Keep each field on its line.
+                let v: String = v
+                    .chars()
+                    .map(|c| if c.is_control() { ' ' } else { c })
+                    .collect();
+                out.push_str(&format!("\n{}: {}", key, v.trim()));
+            }
+        }
+        out
+    }
+
+    /// Parse a comment written by `to_comment`. `None` for other tools'
+    /// comments.
+    pub fn from_comment(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let version = lines.next()?.strip_prefix(TOOL_NAME)?.trim();
+        let mut out = Provenance {
+            tool_version: version.to_string(),
+            ..Provenance::default()
+        };
+        for line in lines {
+            let Some((key, value)) = line.split_once(": ") else {
+                continue;
+            };
+            let value = Some(value.to_string());
+            match key {
+                "title" => out.title = value,
+                "source" => out.source_url = value,
+                "disc-sha1" => out.disc_sha1 = value,
+                _ => {}
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Insert a COM segment with `text` into `jpeg`, after SOI and any APPn
+/// segments (JFIF requires its APP0 first). Text past the 64 KB segment
+/// limit is cut off. Returns the input unchanged if it doesn't parse.
+pub fn embed_comment(jpeg: Vec<u8>, text: &str) -> Vec<u8> {
+    let Ok(segments) = jpeg_segments(&jpeg) else {
+        return jpeg;
+    };
+    let mut pos = 2;
+    for segment in &segments {
+        if !(0xE0..=0xEF).contains(&segment.marker) {
+            break;
+        }
+        pos += 4 + segment.payload.len();
+    }
+
+    let mut payload = text.as_bytes();
+    if payload.len() > MAX_COMMENT {
+        payload = &payload[..MAX_COMMENT];
+    }
+    let len = (payload.len() + 2) as u16;
+    let mut out = Vec::with_capacity(jpeg.len() + payload.len() + 4);
+    out.extend_from_slice(&jpeg[..pos]);
+    out.extend_from_slice(&[0xFF, COM]);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(payload);
+    out.extend_from_slice(&jpeg[pos..]);
+    out
+}
+
+/// Bytes `embed_comment` adds for `text`.
+pub fn comment_overhead(text: &str) -> usize {
+    text.len().min(MAX_COMMENT) + 4
+}
+
+/// Read our provenance comment from a JPEG file, if it has one.
+pub fn read_provenance<P: AsRef<Path>>(path: P) -> Result<Option<Provenance>, String> {
+    let path = path.as_ref();
+    let data =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(provenance_from_bytes(&data))
+}
+
+fn provenance_from_bytes(data: &[u8]) -> Option<Provenance> {
+    jpeg_segments(data)
+        .ok()?
+        .iter()
+        .filter(|s| s.marker == COM)
+        .find_map(|s| Provenance::from_comment(&String::from_utf8_lossy(&s.payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{encode_baseline_jpeg, validate_jpeg_bytes};
+    use image::RgbImage;
+
+    #[test]
+    fn comment_round_trips_through_a_jpeg() {
+        let img = RgbImage::from_pixel(240, 240, image::Rgb([40, 80, 120]));
+        let jpeg = encode_baseline_jpeg(&img, 90).unwrap();
+        let provenance = Provenance::new(
+            Some("Myst".to_string()),
+            Some("https://example.com/myst.jpg".to_string()),
+            Some("da39a3ee5e6b4b0d3255bfef95601890afd80709".to_string()),
+        );
+
+        let text = provenance.to_comment();
+        let stamped = embed_comment(jpeg.clone(), &text);
+        assert_eq!(stamped.len(), jpeg.len() + comment_overhead(&text));
+        assert_eq!(provenance_from_bytes(&stamped), Some(provenance));
+        // Still decodes, and still passes the USBODE checks.
+        assert!(image::load_from_memory(&stamped).is_ok());
+        assert!(validate_jpeg_bytes(&stamped, 240).is_compliant());
+    }
+
+    #[test]
+    fn foreign_comments_are_ignored() {
+        assert_eq!(Provenance::from_comment("Created with GIMP"), None);
+        let parsed =
+            Provenance::from_comment("ODE-artwork-downloader 1.0.0\ntitle: Riven").unwrap();
+        assert_eq!(parsed.tool_version, "1.0.0");
+        assert_eq!(parsed.title.as_deref(), Some("Riven"));
+        assert_eq!(parsed.source_url, None);
+    }
+}
@@ -0,0 +1,117 @@
+//! RetroArch thumbnail copies: with `export.retroarch_thumbnails` set, each
+//! saved cover is also written as a PNG to
+//! `<thumbnails>/<System>/Named_Boxarts/<Game>.png`, where RetroArch looks
+//! up box art for a playlist entry.
+//!
+//! `<System>` is the playlist's name (e.g. `Sony - PlayStation`) and
+//! `<Game>` the entry's label, which for scanned discs without a DAT match
+//! is the file name without its extension, with the characters RetroArch
+//! can't use in file names swapped for `_` (see [`sanitize_name`]).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder under each system holding front covers.
+pub const BOXARTS_DIR: &str = "Named_Boxarts";
+
+/// Where covers are copied and for which playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetroArchThumbnails {
+    /// RetroArch's `thumbnails` folder
+    pub dir: PathBuf,
+    /// Playlist name, e.g. `Sony - PlayStation`
+    pub system: String,
+}
+
+impl RetroArchThumbnails {
+    /// The thumbnail RetroArch reads for the disc at `disc_path`.
+    pub fn thumbnail_path(&self, disc_path: &Path) -> PathBuf {
+        let label = disc_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.dir
+            .join(sanitize_name(&self.system))
+            .join(BOXARTS_DIR)
+            .join(format!("{}.png", sanitize_name(&label)))
+    }
+
+    /// Write the cover saved at `artwork` as the disc's thumbnail,
+    /// converting it to PNG. Returns where it went.
+    pub fn write(&self, disc_path: &Path, artwork: &Path) -> Result<PathBuf, String> {
+        let target = self.thumbnail_path(disc_path);
+        let image = image::open(artwork)
+            .map_err(|e| format!("Failed to read {}: {}", artwork.display(), e))?;
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        image
+            .to_rgb8()
+            .save_with_format(&target, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        Ok(target)
+    }
+}
+
+/// A playlist or label as RetroArch's thumbnail file names have it.
+pub fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '&' | '*' | '/' | ':' | '`' | '<' | '>' | '?' | '\\' | '|' | '"' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    #[test]
+    fn sanitizes_like_retroarch() {
+        assert_eq!(
+            sanitize_name("Tom & Jerry: Fists of Furry"),
+            "Tom _ Jerry_ Fists of Furry"
+        );
+        assert_eq!(sanitize_name("Who? <Me>|\"Yes\""), "Who_ _Me___Yes_");
+        assert_eq!(sanitize_name("Ridge Racer (USA)"), "Ridge Racer (USA)");
+    }
+
+    #[test]
+    fn thumbnail_goes_under_named_boxarts() {
+        let thumbnails = RetroArchThumbnails {
+            dir: PathBuf::from("/ra/thumbnails"),
+            system: "Sony - PlayStation".to_string(),
+        };
+        assert_eq!(
+            thumbnails.thumbnail_path(Path::new("/discs/Myst: Deluxe (USA).cue")),
+            PathBuf::from("/ra/thumbnails/Sony - PlayStation/Named_Boxarts/Myst_ Deluxe (USA).png")
+        );
+    }
+
+    #[test]
+    fn writes_a_png() {
+        let dir = std::env::temp_dir().join(format!("ode_retroarch_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let artwork = dir.join("cover.jpg");
+        RgbImage::new(8, 8).save(&artwork).unwrap();
+        let thumbnails = RetroArchThumbnails {
+            dir: dir.join("thumbnails"),
+            system: "Sega - Saturn".to_string(),
+        };
+
+        let written = thumbnails
+            .write(Path::new("Panzer Dragoon.cue"), &artwork)
+            .unwrap();
+        assert_eq!(
+            written,
+            dir.join("thumbnails/Sega - Saturn/Named_Boxarts/Panzer Dragoon.png")
+        );
+        let data = fs::read(&written).unwrap();
+        assert_eq!(image::guess_format(&data).unwrap(), image::ImageFormat::Png);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,66 @@
+//! Unsharp mask for the downscaled output.
+//!
+//! Lanczos-shrinking a full cover to 240px softens fine print (publisher
+//! logos, back-of-box text bleeding onto the front). A light unsharp mask
+//! after the resize brings edges back without touching flat areas.
+
+use image::{imageops, DynamicImage, RgbImage};
+
+/// Blur radius of the mask. Small, since it runs on the final-size image.
+const SHARPEN_SIGMA: f32 = 1.0;
+
+/// Differences below this are treated as noise and left alone, so JPEG
+/// blocking and gradients don't get amplified.
+const SHARPEN_THRESHOLD: f32 = 2.0;
+
+/// Largest accepted `amount` (percent).
+pub const MAX_SHARPEN: u32 = 300;
+
+/// Sharpen `img` by `amount` percent (0 = unchanged, 100 = add the full
+/// high-pass detail back once).
+pub fn sharpen(img: DynamicImage, amount: u32) -> DynamicImage {
+    if amount == 0 {
+        return img;
+    }
+    DynamicImage::ImageRgb8(unsharp_mask(&img.to_rgb8(), amount.min(MAX_SHARPEN) as f32 / 100.0))
+}
+
+fn unsharp_mask(img: &RgbImage, amount: f32) -> RgbImage {
+    let blurred = imageops::blur(img, SHARPEN_SIGMA);
+    let mut out = img.clone();
+    for (px, soft) in out.pixels_mut().zip(blurred.pixels()) {
+        for (v, b) in px.0.iter_mut().zip(soft.0) {
+            let detail = f32::from(*v) - f32::from(b);
+            if detail.abs() >= SHARPEN_THRESHOLD {
+                *v = (f32::from(*v) + amount * detail).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    /// Left half dark, right half light.
+    fn edge() -> RgbImage {
+        RgbImage::from_fn(32, 16, |x, _| if x < 16 { Rgb([80, 80, 80]) } else { Rgb([170, 170, 170]) })
+    }
+
+    #[test]
+    fn sharpening_increases_edge_contrast() {
+        let out = sharpen(DynamicImage::ImageRgb8(edge()), 100).to_rgb8();
+        assert!(out.get_pixel(15, 8)[0] < 80);
+        assert!(out.get_pixel(16, 8)[0] > 170);
+        // Flat areas away from the edge are untouched.
+        assert_eq!(out.get_pixel(6, 8)[0], 80);
+    }
+
+    #[test]
+    fn zero_amount_is_a_no_op() {
+        let out = sharpen(DynamicImage::ImageRgb8(edge()), 0).to_rgb8();
+        assert_eq!(out, edge());
+    }
+}
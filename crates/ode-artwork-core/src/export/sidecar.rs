@@ -0,0 +1,184 @@
+//! Metadata sidecar written next to exported artwork.
+//!
+//! The provenance comment says where a cover came from, but not how it was
+//! made. With `write_sidecar` on, `game.jpg` also gets a `game.artwork.json`
+//! recording the source URL and resolution, the square cut out of it, the
+//! export settings and a summary of the disc. That's enough to redo the
+//! export later (at another size, say) and lets library tools index covers
+//! without opening every disc image.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::crop::Region;
+use super::{
+    format_hex_color, ArtTemplate, ExportSettings, FitMode, ImageEdits, OutputFormat,
+    PadBackground, UpscaleMode,
+};
+
+/// Replaces the cover's extension: `game.jpg` -> `game.artwork.json`.
+pub const SIDECAR_EXTENSION: &str = "artwork.json";
+
+/// The disc a cover was exported for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscSummary {
+    pub path: String,
+    pub title: String,
+    pub format: Option<String>,
+    pub filesystem: Option<String>,
+    pub volume_label: Option<String>,
+    pub serial: Option<String>,
+    pub region: Option<String>,
+    /// SHA-1 of the data track, once the disc has been hashed
+    pub sha1: Option<String>,
+}
+
+/// The `ExportSettings` that shape the image, in a stable on-disk form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SidecarSettings {
+    pub target_size: u32,
+    pub quality: u8,
+    pub format: OutputFormat,
+    pub extra_sizes: Vec<u32>,
+    pub max_file_size: Option<usize>,
+    pub fit: FitMode,
+    pub smart_crop: bool,
+    /// `blur`, or the canvas colour as `#RRGGBB`
+    pub pad_background: String,
+    pub sharpen: u32,
+    pub upscale: UpscaleMode,
+    pub template: ArtTemplate,
+    pub edits: ImageEdits,
+}
+
+impl From<&ExportSettings> for SidecarSettings {
+    fn from(settings: &ExportSettings) -> Self {
+        Self {
+            target_size: settings.target_size,
+            quality: settings.quality,
+            format: settings.format,
+            extra_sizes: settings.extra_sizes.clone(),
+            max_file_size: settings.max_file_size,
+            fit: settings.fit,
+            smart_crop: settings.smart_crop,
+            pad_background: match settings.pad_background {
+                PadBackground::Color(c) => format_hex_color(c),
+                PadBackground::Blur => "blur".to_string(),
+            },
+            sharpen: settings.sharpen,
+            upscale: settings.upscale,
+            template: settings.template,
+            edits: settings.edits,
+        }
+    }
+}
+
+/// Contents of a `.artwork.json` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtworkSidecar {
+    /// Version of the tool that wrote it
+    pub tool_version: String,
+    pub source_url: Option<String>,
+    /// Source dimensions as downloaded
+    pub original_size: (u32, u32),
+    /// Square cut out of the source, in coordinates after `edits` are
+    /// applied. `None` when nothing was cropped (already square, or padded).
+    pub crop: Option<Region>,
+    /// Badge label drawn on the cover, if any
+    pub badge: Option<String>,
+    pub settings: SidecarSettings,
+    pub disc: Option<DiscSummary>,
+}
+
+impl ArtworkSidecar {
+    /// Sidecar for an export made with `settings`. The source URL comes from
+    /// `settings.provenance`, the disc from `settings.disc`.
+    pub fn new(
+        settings: &ExportSettings,
+        original_size: (u32, u32),
+        crop: Option<Region>,
+        badge: Option<&str>,
+    ) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            source_url: settings
+                .provenance
+                .as_ref()
+                .and_then(|p| p.source_url.clone()),
+            original_size,
+            crop,
+            badge: badge
+                .filter(|b| settings.disc_badge && !b.is_empty())
+                .map(str::to_string),
+            settings: SidecarSettings::from(settings),
+            disc: settings.disc.clone(),
+        }
+    }
+}
+
+/// Sidecar path for the cover at `output_path`.
+pub fn sidecar_path(output_path: &Path) -> PathBuf {
+    output_path.with_extension(SIDECAR_EXTENSION)
+}
+
+/// Write `sidecar` next to `output_path` as pretty-printed JSON. Returns the
+/// sidecar's path.
+pub fn write_sidecar(output_path: &Path, sidecar: &ArtworkSidecar) -> Result<PathBuf, String> {
+    let path = sidecar_path(output_path);
+    let json = serde_json::to_string_pretty(sidecar)
+        .map_err(|e| format!("Failed to serialize artwork sidecar: {}", e))?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Read the sidecar for the cover at `output_path`, if there is one.
+pub fn read_sidecar(output_path: &Path) -> Result<Option<ArtworkSidecar>, String> {
+    let path = sidecar_path(output_path);
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidecar_sits_next_to_the_cover() {
+        assert_eq!(
+            sidecar_path(Path::new("/roms/Myst v1.1.jpg")),
+            PathBuf::from("/roms/Myst v1.1.artwork.json")
+        );
+    }
+
+    #[test]
+    fn sidecar_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let cover = dir.path().join("game.jpg");
+        let settings = ExportSettings {
+            disc: Some(DiscSummary {
+                title: "Myst".to_string(),
+                serial: Some("SLUS-00001".to_string()),
+                ..DiscSummary::default()
+            }),
+            ..ExportSettings::default()
+        };
+        let crop = Region {
+            x: 40,
+            y: 0,
+            width: 300,
+            height: 300,
+        };
+        let sidecar = ArtworkSidecar::new(&settings, (380, 300), Some(crop), Some("Disc 2"));
+
+        assert_eq!(read_sidecar(&cover).unwrap(), None);
+        write_sidecar(&cover, &sidecar).unwrap();
+        assert_eq!(read_sidecar(&cover).unwrap(), Some(sidecar));
+    }
+}
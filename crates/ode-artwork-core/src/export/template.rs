@@ -0,0 +1,167 @@
+//! Disc and case templates composited around the finished artwork.
+//!
+//! Some ODE front-ends draw their menu as a shelf of discs or cases rather
+//! than flat covers. These templates are drawn procedurally at the output
+//! size so they stay sharp at any `target_size`.
+
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+use crate::config::ArtTemplate;
+
+/// Colour outside the disc / around the case.
+const BACKGROUND: [f32; 3] = [0.0, 0.0, 0.0];
+
+/// Disc proportions, as fractions of the output edge. Based on a 120mm disc
+/// with a 15mm hole and a ~33mm clear hub.
+const DISC_HOLE_RADIUS: f32 = 15.0 / 240.0;
+const DISC_HUB_RADIUS: f32 = 33.0 / 240.0;
+const HUB_TINT: [f32; 3] = [210.0, 210.0, 215.0];
+const HUB_TINT_STRENGTH: f32 = 0.55;
+const RIM_SHADE: f32 = 0.6;
+
+/// Jewel-case proportions, as fractions of the output edge.
+const CASE_SPINE: f32 = 0.09;
+const CASE_MARGIN: f32 = 0.04;
+const SPINE_COLOR: [f32; 3] = [38.0, 38.0, 42.0];
+const SPINE_RIDGE: [f32; 3] = [70.0, 70.0, 76.0];
+const GLARE_STRENGTH: f32 = 0.12;
+
+/// Composite `img` (already `size`x`size`) into `template`.
+pub fn apply_template(img: DynamicImage, template: ArtTemplate) -> DynamicImage {
+    match template {
+        ArtTemplate::None => img,
+        ArtTemplate::CdLabel => DynamicImage::ImageRgba8(cd_label(&img.to_rgba8())),
+        ArtTemplate::JewelCase => DynamicImage::ImageRgba8(jewel_case(&img)),
+    }
+}
+
+/// Mask the art to a disc: transparent-looking outer corners, a clear hub
+/// ring and the center hole.
+fn cd_label(art: &RgbaImage) -> RgbaImage {
+    let (w, h) = art.dimensions();
+    let size = w.min(h) as f32;
+    let center = size / 2.0;
+    let outer = center - 0.5;
+    let hole = size * DISC_HOLE_RADIUS;
+    let hub = size * DISC_HUB_RADIUS;
+
+    let mut out = art.clone();
+    for (x, y, px) in out.enumerate_pixels_mut() {
+        let dx = x as f32 + 0.5 - center;
+        let dy = y as f32 + 0.5 - center;
+        let d = (dx * dx + dy * dy).sqrt();
+
+        // Anti-aliased coverage of the disc surface (outside the hole,
+        // inside the rim).
+        let coverage = (outer - d + 0.5).clamp(0.0, 1.0) * (d - hole + 0.5).clamp(0.0, 1.0);
+        let mut rgb = [px[0], px[1], px[2]].map(f32::from);
+
+        if d < hub {
+            for (c, tint) in rgb.iter_mut().zip(HUB_TINT) {
+                *c += (tint - *c) * HUB_TINT_STRENGTH;
+            }
+        }
+        // Darken the last pixel before the edge so the disc reads as a disc
+        // even when the art is dark.
+        if (outer - d) < 1.5 {
+            for c in &mut rgb {
+                *c *= RIM_SHADE;
+            }
+        }
+
+        let blended = blend(BACKGROUND, rgb, coverage);
+        *px = Rgba([blended[0], blended[1], blended[2], 255]);
+    }
+    out
+}
+
+/// Shrink the art into a jewel case: dark hinge spine on the left, a thin
+/// border, and a faint diagonal glare across the "plastic".
+fn jewel_case(art: &DynamicImage) -> RgbaImage {
+    let (w, h) = art.dimensions();
+    let size = w.min(h);
+    let sizef = size as f32;
+    let spine = (sizef * CASE_SPINE).round() as u32;
+    let margin = (sizef * CASE_MARGIN).round() as u32;
+    let inner = size.saturating_sub(spine + margin * 2).max(1);
+
+    let bg = BACKGROUND.map(|c| c as u8);
+    let mut out = RgbaImage::from_pixel(size, size, Rgba([bg[0], bg[1], bg[2], 255]));
+
+    // Spine with a couple of raised ridges
+    for y in 0..size {
+        for x in 0..spine {
+            let ridge = x == spine / 3 || x == spine * 2 / 3;
+            let c = if ridge { SPINE_RIDGE } else { SPINE_COLOR }.map(|c| c as u8);
+            out.put_pixel(x, y, Rgba([c[0], c[1], c[2], 255]));
+        }
+    }
+
+    let cover = art
+        .resize_exact(inner, inner, image::imageops::FilterType::Lanczos3)
+        .to_rgba8();
+    let left = spine + margin;
+    let top = (size - inner) / 2;
+    image::imageops::overlay(&mut out, &cover, left as i64, top as i64);
+
+    // Glare: brighten a diagonal band over the cover.
+    for (x, y, px) in out.enumerate_pixels_mut() {
+        if x < spine {
+            continue;
+        }
+        let t = (x as f32 + y as f32) / (2.0 * sizef);
+        let band = 1.0 - ((t - 0.35).abs() / 0.12).min(1.0);
+        if band > 0.0 {
+            let rgb = [px[0], px[1], px[2]].map(f32::from);
+            let lit = blend(rgb, [255.0; 3], band * GLARE_STRENGTH);
+            *px = Rgba([lit[0], lit[1], lit[2], 255]);
+        }
+    }
+    out
+}
+
+/// `from` → `to` by `t` (0..=1), rounded to bytes.
+fn blend(from: [f32; 3], to: [f32; 3], t: f32) -> [u8; 3] {
+    let mut out = [0u8; 3];
+    for ((o, a), b) in out.iter_mut().zip(from).zip(to) {
+        *o = (a + (b - a) * t).round().clamp(0.0, 255.0) as u8;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn red(size: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(size, size, Rgb([220, 20, 20])))
+    }
+
+    #[test]
+    fn cd_label_masks_corners_and_hole() {
+        let out = apply_template(red(240), ArtTemplate::CdLabel).to_rgb8();
+        assert_eq!(out.dimensions(), (240, 240));
+        assert_eq!(*out.get_pixel(0, 0), Rgb([0, 0, 0]));
+        assert_eq!(*out.get_pixel(120, 120), Rgb([0, 0, 0]));
+        // Label area between hub and rim keeps the art.
+        assert_eq!(*out.get_pixel(120, 40), Rgb([220, 20, 20]));
+    }
+
+    #[test]
+    fn jewel_case_keeps_size_and_draws_spine() {
+        let out = apply_template(red(240), ArtTemplate::JewelCase).to_rgb8();
+        assert_eq!(out.dimensions(), (240, 240));
+        let spine = out.get_pixel(2, 120);
+        assert!(spine[0] < 80 && spine[0] == spine[1]);
+        // Cover area still reads as red.
+        let cover = out.get_pixel(200, 200);
+        assert!(cover[0] > 180 && cover[1] < 80);
+    }
+
+    #[test]
+    fn no_template_is_a_no_op() {
+        let out = apply_template(red(16), ArtTemplate::None).to_rgb8();
+        assert_eq!(out, red(16).to_rgb8());
+    }
+}
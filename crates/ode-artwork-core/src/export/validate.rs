@@ -0,0 +1,318 @@
+//! Compliance check for saved artwork.
+//!
+//! USBODE only decodes a narrow slice of JPEG: baseline (SOF0), 8-bit,
+//! three-component YCbCr with no chroma subsampling, and no ICC/EXIF
+//! payloads. Anything else either fails to show or shows corrupted. This
+//! walks the marker segments of an existing file and reports every rule it
+//! breaks, so users can tell *why* a cover from another tool doesn't display.
+
+use std::fmt;
+use std::path::Path;
+
+/// One broken rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComplianceIssue {
+    /// File doesn't start with the JPEG SOI marker.
+    NotJpeg,
+    /// Segment structure is cut short or malformed.
+    Malformed(String),
+    /// Progressive (SOF2) frame.
+    Progressive,
+    /// Some other non-baseline frame type (extended, lossless, arithmetic).
+    NotBaseline { marker: u8 },
+    /// Sample precision other than 8 bits.
+    Precision(u8),
+    /// Dimensions differ from the expected square size.
+    WrongSize { width: u16, height: u16, expected: u32 },
+    /// Not three-component YCbCr (grayscale, CMYK, or Adobe-tagged RGB).
+    NotYCbCr { components: u8 },
+    /// A component is subsampled (anything other than 1x1 = 4:4:4).
+    Subsampled { component: u8, h: u8, v: u8 },
+    /// Embedded ICC colour profile (APP2 "ICC_PROFILE").
+    HasIcc,
+    /// Embedded EXIF block (APP1 "Exif").
+    HasExif,
+}
+
+impl fmt::Display for ComplianceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComplianceIssue::NotJpeg => write!(f, "not a JPEG file"),
+            ComplianceIssue::Malformed(why) => write!(f, "malformed JPEG: {why}"),
+            ComplianceIssue::Progressive => write!(f, "progressive JPEG (must be baseline)"),
+            ComplianceIssue::NotBaseline { marker } => {
+                write!(f, "non-baseline frame SOF{} (must be SOF0)", marker - 0xC0)
+            }
+            ComplianceIssue::Precision(p) => write!(f, "{p}-bit samples (must be 8-bit)"),
+            ComplianceIssue::WrongSize { width, height, expected } => {
+                write!(f, "{width}x{height} (must be {expected}x{expected})")
+            }
+            ComplianceIssue::NotYCbCr { components } => {
+                write!(f, "{components}-component image (must be 3-component YCbCr)")
+            }
+            ComplianceIssue::Subsampled { component, h, v } => {
+                write!(f, "component {component} sampled {h}x{v} (must be 4:4:4)")
+            }
+            ComplianceIssue::HasIcc => write!(f, "contains an ICC profile"),
+            ComplianceIssue::HasExif => write!(f, "contains EXIF data"),
+        }
+    }
+}
+
+/// Outcome of `validate_artwork`.
+#[derive(Debug, Clone, Default)]
+pub struct ComplianceReport {
+    /// Frame dimensions, when a frame header was found.
+    pub size: Option<(u16, u16)>,
+    pub issues: Vec<ComplianceIssue>,
+}
+
+impl ComplianceReport {
+    pub fn is_compliant(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// One-line summary for the log.
+    pub fn summary(&self) -> String {
+        if self.is_compliant() {
+            "OK".to_string()
+        } else {
+            self.issues
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        }
+    }
+}
+
+/// Check the artwork at `path` against the 240x240 USBODE rules.
+pub fn validate_artwork<P: AsRef<Path>>(path: P) -> Result<ComplianceReport, String> {
+    validate_artwork_with_size(path, super::TARGET_SIZE)
+}
+
+/// Like `validate_artwork`, for a non-default configured output size.
+pub fn validate_artwork_with_size<P: AsRef<Path>>(
+    path: P,
+    expected_size: u32,
+) -> Result<ComplianceReport, String> {
+    let path = path.as_ref();
+    let data =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(validate_jpeg_bytes(&data, expected_size))
+}
+
+/// Validate an in-memory JPEG.
+pub fn validate_jpeg_bytes(data: &[u8], expected_size: u32) -> ComplianceReport {
+    let mut report = ComplianceReport::default();
+
+    let segments = match jpeg_segments(data) {
+        Ok(s) => s,
+        Err(issue) => {
+            report.issues.push(issue);
+            return report;
+        }
+    };
+
+    let mut saw_frame = false;
+    let mut adobe_transform: Option<u8> = None;
+    for seg in &segments {
+        match seg.marker {
+            0xE1 if seg.payload.starts_with(b"Exif\0") => {
+                report.issues.push(ComplianceIssue::HasExif);
+            }
+            0xE2 if seg.payload.starts_with(b"ICC_PROFILE\0") => {
+                if !report.issues.contains(&ComplianceIssue::HasIcc) {
+                    report.issues.push(ComplianceIssue::HasIcc);
+                }
+            }
+            // Adobe APP14: byte 11 is the colour transform (0 = none/RGB).
+            0xEE if seg.payload.starts_with(b"Adobe") && seg.payload.len() >= 12 => {
+                adobe_transform = Some(seg.payload[11]);
+            }
+            // SOFn markers (C4 = DHT, C8 = JPG, CC = DAC are not frames).
+            m @ 0xC0..=0xCF if !matches!(m, 0xC4 | 0xC8 | 0xCC) => {
+                saw_frame = true;
+                match m {
+                    0xC0 => {}
+                    0xC2 => report.issues.push(ComplianceIssue::Progressive),
+                    other => report.issues.push(ComplianceIssue::NotBaseline { marker: other }),
+                }
+                check_frame(&seg.payload, expected_size, &mut report);
+            }
+            _ => {}
+        }
+    }
+
+    if !saw_frame {
+        report
+            .issues
+            .push(ComplianceIssue::Malformed("no frame header".to_string()));
+    }
+    let flagged_colour = report
+        .issues
+        .iter()
+        .any(|i| matches!(i, ComplianceIssue::NotYCbCr { .. }));
+    if adobe_transform == Some(0) && !flagged_colour {
+        report.issues.push(ComplianceIssue::NotYCbCr { components: 3 });
+    }
+
+    report
+}
+
+fn check_frame(sof: &[u8], expected_size: u32, report: &mut ComplianceReport) {
+    if sof.len() < 6 {
+        report
+            .issues
+            .push(ComplianceIssue::Malformed("short frame header".to_string()));
+        return;
+    }
+    let precision = sof[0];
+    let height = u16::from_be_bytes([sof[1], sof[2]]);
+    let width = u16::from_be_bytes([sof[3], sof[4]]);
+    let components = sof[5];
+    report.size = Some((width, height));
+
+    if precision != 8 {
+        report.issues.push(ComplianceIssue::Precision(precision));
+    }
+    if width as u32 != expected_size || height as u32 != expected_size {
+        report.issues.push(ComplianceIssue::WrongSize {
+            width,
+            height,
+            expected: expected_size,
+        });
+    }
+    if components != 3 {
+        report.issues.push(ComplianceIssue::NotYCbCr { components });
+    }
+    for c in 0..components as usize {
+        let Some(comp) = sof.get(6 + c * 3..9 + c * 3) else {
+            report
+                .issues
+                .push(ComplianceIssue::Malformed("truncated component list".to_string()));
+            break;
+        };
+        let (h, v) = (comp[1] >> 4, comp[1] & 0x0F);
+        if (h, v) != (1, 1) {
+            report.issues.push(ComplianceIssue::Subsampled {
+                component: comp[0],
+                h,
+                v,
+            });
+        }
+    }
+}
+
+/// A marker segment before the scan data.
+pub(super) struct Segment {
+    pub(super) marker: u8,
+    pub(super) payload: Vec<u8>,
+}
+
+/// Walk the marker segments of a JPEG up to (and including) SOS.
+pub(super) fn jpeg_segments(data: &[u8]) -> Result<Vec<Segment>, ComplianceIssue> {
+    if data.len() < 4 || data[..2] != [0xFF, 0xD8] {
+        return Err(ComplianceIssue::NotJpeg);
+    }
+    let mut out = Vec::new();
+    let mut pos = 2;
+    loop {
+        // Markers may be preceded by fill bytes (0xFF).
+        while pos < data.len() && data[pos] == 0xFF && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if pos + 4 > data.len() {
+            return Err(ComplianceIssue::Malformed("ended before scan data".to_string()));
+        }
+        if data[pos] != 0xFF {
+            return Err(ComplianceIssue::Malformed(format!("expected marker at offset {pos}")));
+        }
+        let marker = data[pos + 1];
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        if len < 2 || pos + 2 + len > data.len() {
+            return Err(ComplianceIssue::Malformed(format!(
+                "segment {marker:02X} overruns file"
+            )));
+        }
+        out.push(Segment {
+            marker,
+            payload: data[pos + 4..pos + 2 + len].to_vec(),
+        });
+        if marker == 0xDA {
+            return Ok(out);
+        }
+        pos += 2 + len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    fn exported(size: u32) -> Vec<u8> {
+        let img = RgbImage::from_pixel(size, size, image::Rgb([200, 40, 40]));
+        super::super::encode_baseline_jpeg(&img, 90).unwrap()
+    }
+
+    /// Insert a segment right after SOI.
+    fn with_segment(data: &[u8], marker: u8, payload: &[u8]) -> Vec<u8> {
+        let len = (payload.len() + 2) as u16;
+        let mut out = data[..2].to_vec();
+        out.extend_from_slice(&[0xFF, marker]);
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(payload);
+        out.extend_from_slice(&data[2..]);
+        out
+    }
+
+    #[test]
+    fn own_export_is_compliant() {
+        let report = validate_jpeg_bytes(&exported(240), 240);
+        assert!(report.is_compliant(), "{}", report.summary());
+        assert_eq!(report.size, Some((240, 240)));
+    }
+
+    #[test]
+    fn reports_wrong_size() {
+        let report = validate_jpeg_bytes(&exported(100), 240);
+        assert_eq!(
+            report.issues,
+            vec![ComplianceIssue::WrongSize {
+                width: 100,
+                height: 100,
+                expected: 240
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_icc_and_exif() {
+        let data = with_segment(&exported(240), 0xE2, b"ICC_PROFILE\0\x01\x01");
+        let data = with_segment(&data, 0xE1, b"Exif\0\0MM");
+        let report = validate_jpeg_bytes(&data, 240);
+        assert!(report.issues.contains(&ComplianceIssue::HasIcc));
+        assert!(report.issues.contains(&ComplianceIssue::HasExif));
+    }
+
+    #[test]
+    fn reports_progressive_and_subsampled() {
+        let mut data = exported(240);
+        let sof = data.windows(2).position(|w| w == [0xFF, 0xC0]).unwrap();
+        data[sof + 1] = 0xC2;
+        // First component's sampling byte: marker(2) + len(2) + precision/h/w/n(6) + id(1)
+        data[sof + 11] = 0x22;
+        let report = validate_jpeg_bytes(&data, 240);
+        assert!(report.issues.contains(&ComplianceIssue::Progressive));
+        assert!(report
+            .issues
+            .contains(&ComplianceIssue::Subsampled { component: 1, h: 2, v: 2 }));
+    }
+
+    #[test]
+    fn rejects_non_jpeg() {
+        let report = validate_jpeg_bytes(b"\x89PNG\r\n\x1a\n", 240);
+        assert_eq!(report.issues, vec![ComplianceIssue::NotJpeg]);
+    }
+}
@@ -0,0 +1,211 @@
+//! Translations for the GUI.
+//!
+//! Window text lives in `assets/locales/<code>.ftl` ([Fluent] syntax) and
+//! is looked up by message id with [`tr!`]. English is the complete set;
+//! another language may leave messages out, and those fall back to English,
+//! then to the bare id so a missing message is visible rather than blank.
+//! Log messages stay in English so they read the same in bug reports.
+//!
+//! [Fluent]: https://projectfluent.org/
+
+use std::sync::RwLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+/// `language` setting that follows the operating system.
+pub const SYSTEM_LANGUAGE: &str = "system";
+
+/// Languages that can be picked, as code and native name. English first.
+pub const LANGUAGES: [(&str, &str); 5] = [
+    ("en", "English"),
+    ("de", "Deutsch"),
+    ("es", "Español"),
+    ("fr", "Français"),
+    ("ja", "日本語"),
+];
+
+const SOURCES: [(&str, &str); 5] = [
+    ("en", include_str!("../assets/locales/en.ftl")),
+    ("de", include_str!("../assets/locales/de.ftl")),
+    ("es", include_str!("../assets/locales/es.ftl")),
+    ("fr", include_str!("../assets/locales/fr.ftl")),
+    ("ja", include_str!("../assets/locales/ja.ftl")),
+];
+
+type Bundle = FluentBundle<FluentResource>;
+
+/// Bundles looked through in order: the chosen language, then English.
+/// Empty until the first lookup or [`set_language`].
+static ACTIVE: RwLock<Vec<Bundle>> = RwLock::new(Vec::new());
+
+/// Look `id` up in the active language. Use [`tr!`] rather than calling
+/// this directly.
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    {
+        let active = ACTIVE.read().unwrap_or_else(|e| e.into_inner());
+        if !active.is_empty() {
+            return format_message(&active, id, args);
+        }
+    }
+    set_language(&crate::config::get_config().language);
+    let active = ACTIVE.read().unwrap_or_else(|e| e.into_inner());
+    format_message(&active, id, args)
+}
+
+/// A message from the active language, with optional `name = value`
+/// arguments for its `{ $name }` placeholders.
+///
+/// ```ignore
+/// ui.button(tr!("open"));
+/// ui.label(tr!("queue-progress", done = finished, total = total));
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = ::fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+pub use crate::tr;
+
+/// Switch the window text to `setting`: a code from [`LANGUAGES`] or
+/// [`SYSTEM_LANGUAGE`]. Takes effect from the next frame.
+pub fn set_language(setting: &str) {
+    let code = resolve(setting);
+    let mut chain = Vec::new();
+    if code != "en" {
+        chain.push(bundle(code));
+    }
+    chain.push(bundle("en"));
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = chain;
+}
+
+/// The language code `setting` ends up as. `system` takes the OS locale's
+/// language; anything without a translation becomes English.
+pub fn resolve(setting: &str) -> &'static str {
+    let wanted = if setting == SYSTEM_LANGUAGE {
+        sys_locale::get_locale().unwrap_or_default()
+    } else {
+        setting.to_string()
+    };
+    let primary = wanted
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == primary)
+        .unwrap_or("en")
+}
+
+/// Native name of `setting`, for the language picker.
+pub fn language_name(setting: &str) -> String {
+    if setting == SYSTEM_LANGUAGE {
+        return tr!("language-system");
+    }
+    LANGUAGES
+        .iter()
+        .find(|(code, _)| *code == setting)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| setting.to_string())
+}
+
+fn bundle(code: &str) -> Bundle {
+    let source = SOURCES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map_or(SOURCES[0].1, |(_, source)| source);
+    build_bundle(code, source)
+}
+
+fn build_bundle(code: &str, source: &str) -> Bundle {
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            log::warn!("Errors in the {code} translation: {errors:?}");
+            resource
+        });
+    let language: LanguageIdentifier = code.parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // egui draws the Unicode isolation marks around placeholders as boxes
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        log::warn!("Duplicate messages in the {code} translation: {errors:?}");
+    }
+    bundle
+}
+
+fn format_message(chain: &[Bundle], id: &str, args: Option<&FluentArgs>) -> String {
+    for bundle in chain {
+        if let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) {
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
+        }
+    }
+    id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast::Entry;
+
+    fn message_ids(source: &str) -> Vec<String> {
+        fluent_syntax::parser::parse(source)
+            .expect("translation should parse")
+            .body
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Message(m) => Some(m.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn translations_parse_and_only_use_english_ids() {
+        let english = message_ids(SOURCES[0].1);
+        for (code, source) in &SOURCES[1..] {
+            for id in message_ids(source) {
+                assert!(english.contains(&id), "{code} has {id}, English doesn't");
+            }
+        }
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_english_then_the_id() {
+        let chain = [
+            build_bundle("ja", "language-system = システムに従う\n"),
+            bundle("en"),
+        ];
+        assert_eq!(
+            format_message(&chain, "language-system", None),
+            "システムに従う"
+        );
+        let mut args = FluentArgs::new();
+        args.set("count", 3);
+        assert_eq!(
+            format_message(&chain, "log-button-count", Some(&args)),
+            "Log (3)"
+        );
+        assert_eq!(
+            format_message(&chain, "no-such-message", None),
+            "no-such-message"
+        );
+    }
+
+    #[test]
+    fn unknown_languages_resolve_to_english() {
+        assert_eq!(resolve("ja"), "ja");
+        assert_eq!(resolve("pt"), "en");
+    }
+}
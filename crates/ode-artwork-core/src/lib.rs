@@ -0,0 +1,28 @@
+//! ODE Artwork Core
+//!
+//! Disc image identification and cover art downloading, without the window.
+//! The GUI (`ode-artwork-gui`) and the `ode-artwork` and `fuzzy_scan` CLIs are
+//! built on it, and other tools can depend on it directly.
+//!
+//! The stable surface is `disc` (reading images and identifying them), `db`
+//! (the redump lookup DB), `api` and `search` (metadata and artwork sources),
+//! `export`, `config`, `crypto` and `update`. The remaining modules are public
+//! because the app and the CLIs share them, but may change between releases.
+
+pub mod api;
+pub mod batch;
+pub mod config;
+pub mod crypto;
+pub mod db;
+pub mod disc;
+pub mod export;
+pub mod i18n;
+pub mod logging;
+pub mod net;
+pub mod packs;
+pub mod pool;
+pub mod profiles;
+pub mod search;
+pub mod update;
+pub mod usbode;
+pub mod watch;
@@ -0,0 +1,287 @@
+//! Crash reports: a panic writes `crash-reports/crash-<time>.txt` in the
+//! data directory with the panic message and location, a backtrace, the
+//! disc being worked on and the last log lines, so there's something to
+//! attach to a bug report besides "the window vanished".
+//!
+//! A panic on the main thread takes the window down with it, so the app
+//! installs the hook with [`install_with`] to ask there whether to open the
+//! report. Panics on worker threads (a search, a hash) only write the
+//! report. Work run through [`catch_recovered`] (pool items, background
+//! jobs) carries on after a panic, and its reports say so.
+
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use super::now_iso8601;
+
+/// Folder under the data directory holding the reports.
+pub const REPORTS_DIR: &str = "crash-reports";
+
+/// Log lines kept for the next report.
+const RECENT_LINES: usize = 200;
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static CURRENT_DISC: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+thread_local! {
+    /// How many [`catch_recovered`] calls this thread is inside
+    static RECOVERING: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Keep a log line for the crash report, dropping the oldest.
+pub fn remember(line: String) {
+    let mut recent = RECENT.lock().unwrap_or_else(|e| e.into_inner());
+    if recent.len() == RECENT_LINES {
+        recent.pop_front();
+    }
+    recent.push_back(line);
+}
+
+/// Note the disc being worked on, for the crash report.
+pub fn set_current_disc(path: Option<&Path>) {
+    *CURRENT_DISC.lock().unwrap_or_else(|e| e.into_inner()) = path.map(Path::to_path_buf);
+}
+
+/// Run `f`, catching a panic the caller recovers from (by failing just
+/// one item or job). The hook still writes a report, marked as recovered
+/// so it isn't taken for the crash that closed the app.
+pub fn catch_recovered<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    RECOVERING.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    RECOVERING.with(|depth| depth.set(depth.get() - 1));
+    result
+}
+
+/// What a crash report says.
+pub struct Crash {
+    pub time: String,
+    pub thread: String,
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    /// Caught by [`catch_recovered`]: the app kept running
+    pub recovered: bool,
+    pub disc: Option<PathBuf>,
+    pub backtrace: String,
+    pub recent: Vec<String>,
+}
+
+impl Crash {
+    /// The report for the panic `info` on the current thread.
+    fn capture(info: &PanicHookInfo) -> Self {
+        Crash {
+            time: now_iso8601(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("unnamed")
+                .to_string(),
+            message: panic_message(info),
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            recovered: RECOVERING.with(|depth| depth.get() > 0),
+            disc: try_lock(&CURRENT_DISC).and_then(|disc| disc.clone()),
+            backtrace: Backtrace::force_capture().to_string(),
+            recent: try_lock(&RECENT)
+                .map(|recent| recent.iter().cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The report's text.
+    pub fn report(&self) -> String {
+        let mut text = format!(
+            "ODE Artwork Downloader {} crash report\n\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        text += &format!("Time: {}\n", self.time);
+        text += &format!(
+            "System: {} {}\n",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        text += &format!("Thread: {}\n", self.thread);
+        text += &format!("Panic: {}\n", self.message);
+        if let Some(location) = &self.location {
+            text += &format!("At: {}\n", location);
+        }
+        if self.recovered {
+            text += "Recovered: the app caught this panic and kept running\n";
+        }
+        match &self.disc {
+            Some(disc) => text += &format!("Disc: {}\n", disc.display()),
+            None => text += "Disc: none\n",
+        }
+        text += &format!("\nBacktrace:\n{}\n", self.backtrace.trim_end());
+        text += &format!("\nLast {} log lines:\n", self.recent.len());
+        for line in &self.recent {
+            text += line;
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Write the report to a new file in `dir`. Returns its path.
+    pub fn write(&self, dir: &Path) -> Result<PathBuf, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let stamp: String = self
+            .time
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        // Threads panicking together get one report each
+        let mut path = dir.join(format!("crash-{}.txt", stamp));
+        for n in 2.. {
+            if !path.exists() {
+                break;
+            }
+            path = dir.join(format!("crash-{}-{}.txt", stamp, n));
+        }
+        fs::write(&path, self.report())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+/// Install the panic hook. The default hook still runs first, so the
+/// panic is printed to the terminal as before.
+pub fn install() {
+    install_with(|_| {});
+}
+
+/// [`install`], calling `on_main` with the saved report when the panic
+/// was on the main thread and nothing recovers from it.
+pub fn install_with(on_main: impl Fn(&Path) + Send + Sync + 'static) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let crash = Crash::capture(info);
+        let written = crate::config::data_dir().and_then(|dir| crash.write(&dir.join(REPORTS_DIR)));
+        match written {
+            Ok(path) => {
+                eprintln!("Crash report saved to {}", path.display());
+                if crash.thread == "main" && !crash.recovered {
+                    on_main(&path);
+                }
+            }
+            Err(e) => eprintln!("Failed to save a crash report: {}", e),
+        }
+    }));
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
+
+/// The lock, unless this thread panicked holding it.
+fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crash() -> Crash {
+        Crash {
+            time: "2026-10-16T09:30:05Z".to_string(),
+            thread: "main".to_string(),
+            message: "index out of bounds".to_string(),
+            location: Some("src/disc/reader.rs:120:9".to_string()),
+            recovered: false,
+            disc: Some(PathBuf::from("/discs/Myst.cue")),
+            backtrace: "   0: ode_artwork_core::disc::reader\n".to_string(),
+            recent: vec!["[INFO] Processing: /discs/Myst.cue".to_string()],
+        }
+    }
+
+    #[test]
+    fn report_has_the_details() {
+        let report = crash().report();
+        assert!(report.contains("Panic: index out of bounds\n"));
+        assert!(report.contains("At: src/disc/reader.rs:120:9\n"));
+        assert!(report.contains("Disc: /discs/Myst.cue\n"));
+        assert!(report.contains("Backtrace:\n   0: ode_artwork_core::disc::reader\n"));
+        assert!(report.ends_with("Last 1 log lines:\n[INFO] Processing: /discs/Myst.cue\n"));
+    }
+
+    #[test]
+    fn writes_a_file_named_after_the_time() {
+        let dir = std::env::temp_dir().join(format!("ode_crash_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = crash().write(&dir).unwrap();
+        assert_eq!(path, dir.join("crash-20261016T093005Z.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), crash().report());
+        let again = crash().write(&dir).unwrap();
+        assert_eq!(again, dir.join("crash-20261016T093005Z-2.txt"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn keeps_the_last_lines() {
+        for n in 0..RECENT_LINES + 5 {
+            remember(format!("line {}", n));
+        }
+        let recent = RECENT.lock().unwrap();
+        assert_eq!(recent.len(), RECENT_LINES);
+        assert_eq!(recent.front().unwrap(), "line 5");
+    }
+
+    type Hook = Box<dyn Fn(&PanicHookInfo) + Send + Sync>;
+
+    /// Puts the panic hook a test replaced back when it ends.
+    struct RestoreHook(Option<Hook>);
+
+    impl Drop for RestoreHook {
+        fn drop(&mut self) {
+            // A hook can't be set while unwinding; the test failed anyway
+            if let Some(hook) = self.0.take().filter(|_| !std::thread::panicking()) {
+                panic::set_hook(hook);
+            }
+        }
+    }
+
+    #[test]
+    fn recovered_panics_are_marked() {
+        const MESSAGE: &str = "crash test panic";
+        static SEEN: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+        let restore = RestoreHook(Some(panic::take_hook()));
+        // Other tests' panics can land here meanwhile; only ours count
+        panic::set_hook(Box::new(|info| {
+            if panic_message(info) == MESSAGE {
+                let recovered = Crash::capture(info).recovered;
+                SEEN.lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(recovered);
+            }
+        }));
+        assert!(catch_recovered(|| panic!("{}", MESSAGE)).is_err());
+        assert!(panic::catch_unwind(|| panic!("{}", MESSAGE)).is_err());
+        drop(restore);
+
+        assert_eq!(*SEEN.lock().unwrap(), vec![true, false]);
+        let report = Crash {
+            recovered: true,
+            ..crash()
+        }
+        .report();
+        assert!(report.contains("Recovered: the app caught this panic and kept running\n"));
+    }
+}
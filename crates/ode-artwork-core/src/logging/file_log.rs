@@ -0,0 +1,208 @@
+//! The log file: records at or above `log_file_level` are appended as one
+//! JSON object per line (time, level, target, message and source location)
+//! to `logs/ode-artwork.log` in the data directory, for attaching to bug
+//! reports.
+//!
+//! Once the file passes [`MAX_BYTES`] it's renamed to `ode-artwork.log.1`
+//! (older ones moving up to `.2` and so on) and a new one started; only the
+//! last [`KEEP`] old files are kept.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use log::Record;
+use serde::Serialize;
+
+/// Folder under the data directory holding the log files.
+pub const LOG_DIR: &str = "logs";
+
+pub const LOG_FILE_NAME: &str = "ode-artwork.log";
+
+/// Size a log file grows to before a new one is started.
+pub const MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Old log files kept.
+pub const KEEP: usize = 5;
+
+/// Where the current log file is.
+pub fn log_file_path() -> Result<PathBuf, String> {
+    Ok(crate::config::data_dir()?.join(LOG_DIR).join(LOG_FILE_NAME))
+}
+
+/// One line of the log file.
+#[derive(Debug, Serialize)]
+struct Line<'a> {
+    time: String,
+    level: &'a str,
+    target: &'a str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+}
+
+/// An open log file, rotated by size.
+pub struct FileLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl FileLog {
+    /// Open (or create) the log file at `path` for appending.
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        Self::with_limits(path, MAX_BYTES, KEEP)
+    }
+
+    /// Like [`FileLog::open`], rotating at `max_bytes` and keeping `keep`
+    /// old files.
+    pub fn with_limits(path: PathBuf, max_bytes: u64, keep: usize) -> Result<Self, String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let file =
+            append(&path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_bytes,
+            keep,
+        })
+    }
+
+    /// Append `record` as a JSON line, starting a new file first if this one
+    /// is full.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let line = Line {
+            time: super::now_iso8601(),
+            level: record.level().as_str(),
+            target: record.target(),
+            message: record.args().to_string(),
+            file: record.file(),
+            line: record.line(),
+        };
+        let mut json = serde_json::to_string(&line)?;
+        json.push('\n');
+        if self.size > 0 && self.size + json.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(json.as_bytes())?;
+        self.size += json.len() as u64;
+        Ok(())
+    }
+
+    /// Move `name.log` to `name.log.1`, `.1` to `.2` and so on, dropping the
+    /// oldest, and start an empty file.
+    fn rotate(&mut self) -> io::Result<()> {
+        let numbered = |n: usize| {
+            let mut name = self.path.clone().into_os_string();
+            name.push(format!(".{}", n));
+            PathBuf::from(name)
+        };
+        let _ = fs::remove_file(numbered(self.keep));
+        for n in (1..self.keep).rev() {
+            let from = numbered(n);
+            if from.exists() {
+                fs::rename(&from, numbered(n + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, numbered(1))?;
+        } else {
+            fs::remove_file(&self.path)?;
+        }
+        self.file = append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ode_file_log_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(log: &mut FileLog, level: log::Level, message: &str) {
+        log.write(
+            &Record::builder()
+                .level(level)
+                .target("ode::test")
+                .args(format_args!("{}", message))
+                .file(Some("src/disc/identifier.rs"))
+                .line(Some(42))
+                .build(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let dir = temp_dir("lines");
+        let path = dir.join(LOG_FILE_NAME);
+        let mut log = FileLog::open(path.clone()).unwrap();
+        write(&mut log, log::Level::Warn, "No match for \"Myst\"");
+        write(&mut log, log::Level::Debug, "second");
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "WARN");
+        assert_eq!(lines[0]["target"], "ode::test");
+        assert_eq!(lines[0]["message"], "No match for \"Myst\"");
+        assert_eq!(lines[0]["file"], "src/disc/identifier.rs");
+        assert_eq!(lines[0]["line"], 42);
+        assert!(lines[1]["time"].as_str().unwrap().ends_with('Z'));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_and_keeps_the_last_few() {
+        let dir = temp_dir("rotate");
+        let path = dir.join(LOG_FILE_NAME);
+        let mut log = FileLog::with_limits(path.clone(), 200, 2).unwrap();
+        for n in 0..20 {
+            write(&mut log, log::Level::Info, &format!("message {}", n));
+        }
+        log.flush().unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["ode-artwork.log", "ode-artwork.log.1", "ode-artwork.log.2"]
+        );
+        assert!(fs::metadata(&path).unwrap().len() <= 200);
+        let newest = fs::read_to_string(&path).unwrap();
+        assert!(newest.contains("message 19"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
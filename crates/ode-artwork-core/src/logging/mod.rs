@@ -0,0 +1,62 @@
+pub mod crash;
+pub mod file_log;
+pub mod ui_logger;
+
+use std::sync::mpsc::Receiver;
+
+use log::SetLoggerError;
+
+use crate::config::AppConfig;
+
+/// Install the logger: lines go to the Log window at `log_level` and to the
+/// log file (see [`file_log`]) at `log_file_level`. Returns the receiver the
+/// Log window reads from.
+pub fn init(config: &AppConfig) -> Result<Receiver<String>, SetLoggerError> {
+    let file = file_log::log_file_path().and_then(file_log::FileLog::open);
+    let (file, error) = match file {
+        Ok(file) => (Some(file), None),
+        Err(e) => (None, Some(e)),
+    };
+    let receiver = ui_logger::UiLogger::init(
+        ui_logger::parse_level(&config.log_level),
+        file,
+        ui_logger::parse_level(&config.log_file_level),
+    )?;
+    if let Some(e) = error {
+        log::warn!("No log file: {}", e);
+    }
+    Ok(receiver)
+}
+
+/// Format current UTC time as an ISO-8601 string for log lines, crash
+/// reports and the bulk sidecar log. Uses Howard Hinnant's days-from-civil
+/// inverse so we don't pull in chrono for a single line of output.
+pub fn now_iso8601() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (y, mo, d, h, mi, s) = unix_to_ymdhms(secs);
+    format!("{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}Z")
+}
+
+fn unix_to_ymdhms(t: i64) -> (i64, u32, u32, u32, u32, u32) {
+    let days = t.div_euclid(86_400);
+    let secs_of_day = t.rem_euclid(86_400) as u32;
+    let h = secs_of_day / 3600;
+    let mi = (secs_of_day % 3600) / 60;
+    let s = secs_of_day % 60;
+
+    let z = days + 719468;
+    let era = if z >= 0 { z / 146097 } else { (z - 146096) / 146097 };
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y_base = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = if m <= 2 { y_base + 1 } else { y_base };
+    (y, m, d, h, mi, s)
+}
@@ -0,0 +1,111 @@
+use log::{Log, Metadata, Record, SetLoggerError, LevelFilter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+use super::file_log::FileLog;
+
+/// Log window and log file filters, as `LevelFilter as usize`.
+static UI_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Info as usize);
+static FILE_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Off as usize);
+
+/// Parse a `LevelFilter` from a config string, falling back to `Info` for
+/// anything we don't recognize.
+pub fn parse_level(s: &str) -> LevelFilter {
+    LevelFilter::from_str(s).unwrap_or(LevelFilter::Info)
+}
+
+/// Change what reaches the Log window.
+pub fn set_level(level: LevelFilter) {
+    UI_LEVEL.store(level as usize, Ordering::Relaxed);
+    update_max_level();
+}
+
+/// Change what's written to the log file.
+pub fn set_file_level(level: LevelFilter) {
+    FILE_LEVEL.store(level as usize, Ordering::Relaxed);
+    update_max_level();
+}
+
+fn level(filter: &AtomicUsize) -> LevelFilter {
+    LevelFilter::iter()
+        .nth(filter.load(Ordering::Relaxed))
+        .unwrap_or(LevelFilter::Off)
+}
+
+/// `log`'s own filter has to let through whatever either side wants.
+fn update_max_level() {
+    log::set_max_level(level(&UI_LEVEL).max(level(&FILE_LEVEL)));
+}
+
+/// Simple logger that forwards formatted log lines into an mpsc channel,
+/// and writes them to the log file when there is one. The filters are
+/// controlled by `set_level` and `set_file_level`, so they can be updated
+/// at runtime without reinstalling the logger.
+pub struct UiLogger {
+    sender: Sender<String>,
+    file: Option<Mutex<FileLog>>,
+}
+
+impl UiLogger {
+    /// Install the UI logger with the given initial levels and return the
+    /// receiver to read log lines from.
+    pub fn init(
+        initial_level: LevelFilter,
+        file: Option<FileLog>,
+        file_level: LevelFilter,
+    ) -> Result<Receiver<String>, SetLoggerError> {
+        let (tx, rx) = mpsc::channel();
+        let logger = UiLogger {
+            sender: tx,
+            file: file.map(Mutex::new),
+        };
+        log::set_boxed_logger(Box::new(logger))?;
+        UI_LEVEL.store(initial_level as usize, Ordering::Relaxed);
+        FILE_LEVEL.store(file_level as usize, Ordering::Relaxed);
+        update_max_level();
+        Ok(rx)
+    }
+}
+
+impl Log for UiLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        super::crash::remember(format!(
+            "{} [{}] {}: {}",
+            super::now_iso8601(),
+            record.level(),
+            record.target(),
+            record.args()
+        ));
+        if record.level() <= level(&UI_LEVEL) {
+            let mut msg = format!("[{}] {}: {}", record.level(), record.target(), record.args());
+            if let (Some(file), Some(line)) = (record.file(), record.line()) {
+                msg.push_str(&format!(" ({}:{})", file, line));
+            }
+            let _ = self.sender.send(msg);
+        }
+        if record.level() <= level(&FILE_LEVEL) {
+            if let Some(file) = &self.file {
+                if let Ok(mut file) = file.lock() {
+                    let _ = file.write(record);
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
@@ -0,0 +1,515 @@
+//! Shared HTTP client setup
+//!
+//! Builds reqwest clients with the timeouts from the `network` config section
+//! and retries transient failures, so search, preview, and export downloads
+//! all behave the same way on a slow or flaky connection.
+
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use reqwest::blocking::{Client, ClientBuilder, RequestBuilder, Response};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, COOKIE, SERVER};
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::config::{get_config, NetworkConfig, SiteRule};
+
+/// User agent for plain image downloads (previews and exports).
+pub const DOWNLOAD_USER_AGENT: &str =
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36";
+
+/// Error string returned by cancellable operations when the token fires.
+pub const CANCELLED: &str = "Cancelled";
+
+/// Shared flag a UI thread flips to stop a worker. Workers poll it between
+/// steps (and between download chunks); a cancelled worker returns
+/// `Err(CANCELLED)` and its result is discarded.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(CANCELLED)` once the token has fired, for `?` at step boundaries.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Counting semaphore that queues downloads once `limit` are in flight.
+pub struct DownloadSlots {
+    in_use: Mutex<usize>,
+    freed: Condvar,
+}
+
+/// Held for the duration of one download; frees the slot on drop.
+pub struct SlotGuard<'a> {
+    slots: &'a DownloadSlots,
+}
+
+impl DownloadSlots {
+    pub const fn new() -> Self {
+        Self {
+            in_use: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Block until fewer than `limit` slots are taken, then take one. Waiters
+    /// wake periodically so a cancelled download leaves the queue promptly.
+    pub fn acquire(&self, limit: usize, cancel: &CancelToken) -> Result<SlotGuard<'_>, String> {
+        let limit = limit.max(1);
+        let mut in_use = self.in_use.lock().unwrap_or_else(|e| e.into_inner());
+        while *in_use >= limit {
+            cancel.check()?;
+            let (guard, _) = self
+                .freed
+                .wait_timeout(in_use, Duration::from_millis(200))
+                .unwrap_or_else(|e| e.into_inner());
+            in_use = guard;
+        }
+        cancel.check()?;
+        *in_use += 1;
+        Ok(SlotGuard { slots: self })
+    }
+
+    /// Number of downloads currently holding a slot.
+    pub fn in_use(&self) -> usize {
+        *self.in_use.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for DownloadSlots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SlotGuard<'_> {
+    fn drop(&mut self) {
+        let mut in_use = self.slots.in_use.lock().unwrap_or_else(|e| e.into_inner());
+        *in_use = in_use.saturating_sub(1);
+        self.slots.freed.notify_one();
+    }
+}
+
+/// App-wide download slots, sized by `network.max_concurrent_downloads`.
+static DOWNLOAD_SLOTS: DownloadSlots = DownloadSlots::new();
+
+/// Which overall timeout a client should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+    /// Search/API calls and preview loads.
+    Request,
+    /// Full-size image downloads during export.
+    Download,
+}
+
+/// A client builder with the configured connect and overall timeouts applied.
+pub fn client_builder(timeout: Timeout) -> ClientBuilder {
+    let net = &get_config().network;
+    Client::builder()
+        .connect_timeout(Duration::from_secs(net.connect_timeout_secs))
+        .timeout(overall_timeout(net, timeout))
+}
+
+fn overall_timeout(net: &NetworkConfig, timeout: Timeout) -> Duration {
+    let secs = match timeout {
+        Timeout::Request => net.request_timeout_secs,
+        Timeout::Download => net.download_timeout_secs,
+    };
+    Duration::from_secs(secs)
+}
+
+/// Run `send` up to `1 + retries` times, backing off between attempts.
+/// Connection errors, timeouts, 429 and 5xx responses are retried; anything
+/// else (including other HTTP errors) is returned to the caller as-is.
+pub fn send_with_retry<F>(mut send: F) -> reqwest::Result<Response>
+where
+    F: FnMut() -> reqwest::Result<Response>,
+{
+    let net = &get_config().network;
+    let mut attempt = 0u32;
+    loop {
+        let result = send();
+        let retryable = match &result {
+            Ok(resp) => is_retryable_status(resp.status()),
+            Err(e) => e.is_timeout() || e.is_connect(),
+        };
+        if !retryable || attempt >= net.retries {
+            return result;
+        }
+        let delay = backoff_delay(net.retry_backoff_ms, attempt);
+        match &result {
+            Ok(resp) => log::debug!("HTTP {} - retrying in {:?}", resp.status(), delay),
+            Err(e) => log::debug!("Request failed ({e}) - retrying in {:?}", delay),
+        }
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Exponential backoff: `base`, `2*base`, `4*base`, ... capped at 30s.
+fn backoff_delay(base_ms: u64, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.min(16);
+    Duration::from_millis(base_ms.saturating_mul(factor).min(30_000))
+}
+
+/// Add the headers and cookies from any `network.site_rules` entry matching
+/// the host of `url`.
+pub fn apply_site_rules(request: RequestBuilder, url: &str) -> RequestBuilder {
+    apply_rules(request, url, &get_config().network.site_rules)
+}
+
+fn apply_rules(mut request: RequestBuilder, url: &str, rules: &[SiteRule]) -> RequestBuilder {
+    for rule in matching_rules(url, rules) {
+        for (name, value) in &rule.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(cookie) = rule.cookie.as_deref().filter(|c| !c.is_empty()) {
+            request = request.header(COOKIE, cookie);
+        }
+    }
+    request
+}
+
+fn matching_rules<'a>(url: &str, rules: &'a [SiteRule]) -> Vec<&'a SiteRule> {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return Vec::new();
+    };
+    let Some(host) = parsed.host_str() else {
+        return Vec::new();
+    };
+    rules.iter().filter(|r| r.matches_host(host)).collect()
+}
+
+/// Bytes received so far, and the total from `Content-Length` when the
+/// server sent one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transfer {
+    pub done: u64,
+    pub total: Option<u64>,
+}
+
+impl Transfer {
+    /// Share received, when the total is known.
+    pub fn fraction(&self) -> Option<f32> {
+        self.total
+            .filter(|&total| total > 0)
+            .map(|total| (self.done as f32 / total as f32).min(1.0))
+    }
+}
+
+/// Why [`fetch_bytes`] and its variants failed.
+#[derive(Error, Debug)]
+pub enum FetchError {
+    /// No answer from the server: offline, DNS, timeout, or the body
+    /// stopped arriving
+    #[error("{context}: {source}")]
+    Network {
+        context: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// The server answered with an error status
+    #[error("HTTP error: {status}")]
+    Status { status: StatusCode },
+
+    /// Reading the response body failed part way
+    #[error("Failed to read image bytes: {0}")]
+    Read(#[source] std::io::Error),
+
+    /// A `file://` URL's file couldn't be read
+    #[error("{0}")]
+    File(String),
+
+    /// The `CancelToken` fired
+    #[error("{}", CANCELLED)]
+    Cancelled,
+}
+
+impl FetchError {
+    fn network(context: &'static str) -> impl FnOnce(reqwest::Error) -> Self {
+        move |source| FetchError::Network { context, source }
+    }
+}
+
+/// For callers that only report the error.
+impl From<FetchError> for String {
+    fn from(e: FetchError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Download the bytes behind `url`. `file://` URLs (local cover packs) are
+/// read straight from disk.
+pub fn fetch_bytes(url: &str, timeout: Timeout) -> Result<Vec<u8>, FetchError> {
+    fetch_bytes_cancellable(url, timeout, &CancelToken::new())
+}
+
+/// Like `fetch_bytes`, but stops between chunks once `cancel` fires.
+pub fn fetch_bytes_cancellable(
+    url: &str,
+    timeout: Timeout,
+    cancel: &CancelToken,
+) -> Result<Vec<u8>, FetchError> {
+    fetch_bytes_with_progress(url, timeout, cancel, &|_| {})
+}
+
+/// Like `fetch_bytes_cancellable`, calling `progress` after every chunk.
+/// Local files report once, when they've been read.
+pub fn fetch_bytes_with_progress(
+    url: &str,
+    timeout: Timeout,
+    cancel: &CancelToken,
+    progress: &dyn Fn(Transfer),
+) -> Result<Vec<u8>, FetchError> {
+    if let Some(local) = crate::packs::read_file_url(url) {
+        let bytes = local.map_err(FetchError::File)?;
+        let len = bytes.len() as u64;
+        progress(Transfer {
+            done: len,
+            total: Some(len),
+        });
+        return Ok(bytes);
+    }
+    let cancelled = || {
+        if cancel.is_cancelled() {
+            Err(FetchError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
+    // Queue behind other in-flight downloads (bulk sibling exports, preview
+    // clicks, ...) so we don't open dozens of sockets at once. It only
+    // fails once `cancel` fires.
+    let _slot = DOWNLOAD_SLOTS
+        .acquire(get_config().network.max_concurrent_downloads, cancel)
+        .map_err(|_| FetchError::Cancelled)?;
+
+    let client = client_builder(timeout)
+        .user_agent(DOWNLOAD_USER_AGENT)
+        .build()
+        .map_err(FetchError::network("Failed to create HTTP client"))?;
+
+    let mut response = send_with_retry(|| apply_site_rules(client.get(url), url).send())
+        .map_err(FetchError::network("Failed to fetch image"))?;
+    cancelled()?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(FetchError::Status { status });
+    }
+
+    let total = response.content_length();
+    let mut bytes = Vec::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        cancelled()?;
+        let n = response.read(&mut buf).map_err(FetchError::Read)?;
+        if n == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&buf[..n]);
+        progress(Transfer {
+            done: bytes.len() as u64,
+            total,
+        });
+    }
+    Ok(bytes)
+}
+
+/// What a server says about a URL before it's downloaded: the status and
+/// the `Content-Type`, `Content-Length` and `Server` headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteInfo {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub length: Option<u64>,
+    pub server: Option<String>,
+}
+
+impl RemoteInfo {
+    /// The media type, lowercased and without parameters such as `charset`.
+    pub fn mime(&self) -> Option<String> {
+        let mime = self.content_type.as_deref()?.split(';').next()?.trim();
+        (!mime.is_empty()).then(|| mime.to_ascii_lowercase())
+    }
+
+    /// The server answers with a web page (a 404 or landing page dressed up
+    /// as an image link) rather than an image.
+    pub fn is_html(&self) -> bool {
+        self.mime()
+            .is_some_and(|m| m == "text/html" || m == "application/xhtml+xml")
+    }
+}
+
+/// Ask the server about `url` with a HEAD request. Servers that refuse HEAD
+/// get a GET instead, whose body is dropped unread.
+pub fn head(url: &str) -> Result<RemoteInfo, String> {
+    let client = client_builder(Timeout::Request)
+        .user_agent(DOWNLOAD_USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut response = send_with_retry(|| apply_site_rules(client.head(url), url).send())
+        .map_err(|e| format!("Failed to check image: {}", e))?;
+    if matches!(
+        response.status(),
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    ) {
+        response = send_with_retry(|| apply_site_rules(client.get(url), url).send())
+            .map_err(|e| format!("Failed to check image: {}", e))?;
+    }
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    // Read the header itself: a HEAD response has no body to size
+    Ok(RemoteInfo {
+        status: response.status().as_u16(),
+        content_type: header(CONTENT_TYPE),
+        length: header(CONTENT_LENGTH).and_then(|l| l.trim().parse().ok()),
+        server: header(SERVER),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::FORBIDDEN));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(backoff_delay(500, 0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(500, 2), Duration::from_millis(2000));
+        assert_eq!(backoff_delay(500, 20), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn transfer_fraction_needs_a_total() {
+        let unknown = Transfer {
+            done: 500,
+            total: None,
+        };
+        assert_eq!(unknown.fraction(), None);
+        let half = Transfer {
+            done: 500,
+            total: Some(1000),
+        };
+        assert_eq!(half.fraction(), Some(0.5));
+        // Servers can under-report; never past full
+        let over = Transfer {
+            done: 1200,
+            total: Some(1000),
+        };
+        assert_eq!(over.fraction(), Some(1.0));
+        assert_eq!(Transfer::default().fraction(), None);
+    }
+
+    #[test]
+    fn cancel_token_is_shared_between_clones() {
+        let token = CancelToken::new();
+        let worker = token.clone();
+        assert!(worker.check().is_ok());
+        token.cancel();
+        assert!(worker.is_cancelled());
+        assert_eq!(worker.check().unwrap_err(), CANCELLED);
+    }
+
+    #[test]
+    fn missing_local_file_is_a_file_error() {
+        let result = fetch_bytes("file:///nonexistent/cover.jpg", Timeout::Request);
+        assert!(matches!(result, Err(FetchError::File(_))));
+    }
+
+    #[test]
+    fn download_slots_cap_concurrency() {
+        let slots = DownloadSlots::new();
+        let token = CancelToken::new();
+        let a = slots.acquire(2, &token).unwrap();
+        let _b = slots.acquire(2, &token).unwrap();
+        assert_eq!(slots.in_use(), 2);
+
+        // A third waiter gives up once cancelled instead of blocking forever.
+        let waiter = CancelToken::new();
+        waiter.cancel();
+        assert!(slots.acquire(2, &waiter).is_err());
+
+        drop(a);
+        assert_eq!(slots.in_use(), 1);
+        let _c = slots.acquire(2, &token).unwrap();
+        assert_eq!(slots.in_use(), 2);
+    }
+
+    #[test]
+    fn site_rules_match_host_and_subdomains() {
+        let rules = vec![SiteRule {
+            domain: "example.com".to_string(),
+            ..SiteRule::default()
+        }];
+        assert_eq!(matching_rules("https://example.com/a.jpg", &rules).len(), 1);
+        assert_eq!(matching_rules("https://img.example.com/a.jpg", &rules).len(), 1);
+        assert!(matching_rules("https://notexample.com/a.jpg", &rules).is_empty());
+        assert!(matching_rules("not a url", &rules).is_empty());
+    }
+
+    #[test]
+    fn remote_info_spots_web_pages() {
+        let info = |content_type: Option<&str>| RemoteInfo {
+            status: 200,
+            content_type: content_type.map(str::to_string),
+            ..RemoteInfo::default()
+        };
+        assert!(info(Some("text/html; charset=UTF-8")).is_html());
+        assert!(info(Some("Application/XHTML+XML")).is_html());
+        assert!(!info(Some("image/jpeg")).is_html());
+        assert!(!info(None).is_html());
+        let png = info(Some("IMAGE/PNG; q=1"));
+        assert_eq!(png.mime().as_deref(), Some("image/png"));
+        assert_eq!(info(Some(" ; charset=utf-8")).mime(), None);
+    }
+
+    #[test]
+    fn timeouts_follow_config() {
+        let net = NetworkConfig {
+            request_timeout_secs: 5,
+            download_timeout_secs: 90,
+            ..NetworkConfig::default()
+        };
+        assert_eq!(overall_timeout(&net, Timeout::Request), Duration::from_secs(5));
+        assert_eq!(overall_timeout(&net, Timeout::Download), Duration::from_secs(90));
+    }
+}
@@ -0,0 +1,434 @@
+//! Local cover-art packs
+//!
+//! Users can import a zip (or a plain folder) of cover images and have them
+//! offered as top-priority candidates in the artwork search. The index maps a
+//! normalized title (derived from each image's filename) to the image path and
+//! lives next to the lookup DB in the per-user data directory, so a fully
+//! offline batch run can still find artwork.
+//!
+//! Zip imports are extracted into `<data_dir>/packs/<pack name>/`; folder
+//! imports are indexed in place.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use strsim::normalized_levenshtein;
+
+use crate::search::ImageResult;
+
+/// Image extensions picked up from a pack. Same set the drop handler accepts.
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "webp", "bmp", "avif", "heic", "heif", "svg",
+];
+
+/// Minimum similarity for a pack entry to be offered as a candidate.
+const MATCH_THRESHOLD: f64 = 0.75;
+
+/// Prefix used for `ImageResult::source` on pack hits. The results list sorts
+/// anything with this prefix above the online sources.
+pub const SOURCE_PREFIX: &str = "Local pack";
+
+/// One indexed cover image.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackEntry {
+    /// Display title (filename stem as it appeared in the pack)
+    pub title: String,
+    /// Normalized title used for matching
+    pub key: String,
+    /// Absolute path to the image on disk
+    pub path: PathBuf,
+    /// Name of the pack this entry came from
+    pub pack: String,
+}
+
+/// The persisted title → path index across all imported packs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackIndex {
+    #[serde(default)]
+    pub entries: Vec<PackEntry>,
+}
+
+/// Outcome of a single import, for the log line.
+#[derive(Debug, Clone)]
+pub struct ImportSummary {
+    pub pack: String,
+    pub added: usize,
+    pub total: usize,
+}
+
+/// Directory holding extracted packs and `index.json`. Created on demand.
+pub fn packs_dir() -> Result<PathBuf, String> {
+    let dir = crate::config::data_dir()?.join("packs");
+    fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {e}", dir.display()))?;
+    Ok(dir)
+}
+
+fn index_path() -> Result<PathBuf, String> {
+    Ok(packs_dir()?.join("index.json"))
+}
+
+impl PackIndex {
+    /// Load the index from the data directory. A missing or unreadable index
+    /// is treated as empty so search never fails because of it.
+    pub fn load() -> Self {
+        let Ok(path) = index_path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(s) => serde_json::from_str(&s).unwrap_or_else(|e| {
+                log::warn!("Failed to parse {}: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write the index back to the data directory.
+    pub fn save(&self) -> Result<(), String> {
+        let path = index_path()?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize pack index: {e}"))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Replace every entry belonging to `pack` with `entries`.
+    pub fn replace_pack(&mut self, pack: &str, entries: Vec<PackEntry>) {
+        self.entries.retain(|e| e.pack != pack);
+        self.entries.extend(entries);
+    }
+
+    /// Rank entries against `title`, best first. Entries whose image has
+    /// since been deleted are skipped.
+    pub fn search(&self, title: &str, limit: usize) -> Vec<(f64, &PackEntry)> {
+        let query = normalize_title(title);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut hits: Vec<(f64, &PackEntry)> = self
+            .entries
+            .iter()
+            .map(|e| (score(&query, &e.key), e))
+            .filter(|(s, e)| *s >= MATCH_THRESHOLD && e.path.is_file())
+            .collect();
+        hits.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// Import a zip file or a folder of cover images into the local index.
+pub fn import_pack(src: &Path) -> Result<ImportSummary, String> {
+    let pack = src
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("pack")
+        .to_string();
+
+    let entries = if src.is_dir() {
+        scan_folder(src, &pack)
+    } else {
+        let dest = packs_dir()?.join(&pack);
+        extract_zip(src, &dest)?;
+        scan_folder(&dest, &pack)
+    };
+
+    if entries.is_empty() {
+        return Err(format!("No images found in {}", src.display()));
+    }
+
+    let added = entries.len();
+    let mut index = PackIndex::load();
+    index.replace_pack(&pack, entries);
+    index.save()?;
+
+    log::info!("Imported cover pack '{pack}' ({added} images)");
+    Ok(ImportSummary {
+        pack,
+        added,
+        total: index.entries.len(),
+    })
+}
+
+/// Look up `title` in the local index and convert hits to search results.
+pub fn search_local(title: &str, limit: usize) -> Vec<ImageResult> {
+    let index = PackIndex::load();
+    index
+        .search(title, limit)
+        .into_iter()
+        .map(|(score, e)| {
+            let url = file_url(&e.path);
+            let (width, height) = match image::image_dimensions(&e.path) {
+                Ok((w, h)) => (Some(w), Some(h)),
+                Err(_) => (None, None),
+            };
+            log::debug!("Pack hit {:.2}: {}", score, e.path.display());
+            ImageResult {
+                image_url: url.clone(),
+                thumbnail_url: url,
+                title: e.title.clone(),
+                source: format!("{SOURCE_PREFIX} ({})", e.pack),
+                width,
+                height,
+            }
+        })
+        .collect()
+}
+
+/// Read the bytes behind a `file://` URL. Returns `None` for any other
+/// scheme so callers can fall through to their HTTP path. Only images in
+/// the pack index are read: URLs reach here from search results and pasted
+/// links alike, and must not open arbitrary local files.
+pub fn read_file_url(url: &str) -> Option<Result<Vec<u8>, String>> {
+    let path = url.strip_prefix("file://")?;
+    let path = urlencoding::decode(path)
+        .map(|p| p.into_owned())
+        .unwrap_or_else(|_| path.to_string());
+    Some(read_pack_file(Path::new(&path), &PackIndex::load()))
+}
+
+/// Read `path` if it's one of `index`'s images.
+fn read_pack_file(path: &Path, index: &PackIndex) -> Result<Vec<u8>, String> {
+    if !index.entries.iter().any(|e| e.path == path) {
+        return Err(format!(
+            "{} isn't an image from an imported cover pack",
+            path.display()
+        ));
+    }
+    fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// `file://` URL for `path`, percent-encoded per segment so
+/// [`read_file_url`] decodes it back to the same path.
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let segments: Vec<_> = path.split('/').map(urlencoding::encode).collect();
+    format!("file://{}", segments.join("/"))
+}
+
+/// Recursively collect image files under `dir` as index entries.
+fn scan_folder(dir: &Path, pack: &str) -> Vec<PackEntry> {
+    let mut out = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(read) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in read.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if !is_image(&path) {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let key = normalize_title(stem);
+            if key.is_empty() {
+                continue;
+            }
+            let path = path.canonicalize().unwrap_or(path);
+            out.push(PackEntry {
+                title: stem.to_string(),
+                key,
+                path,
+                pack: pack.to_string(),
+            });
+        }
+    }
+    out.sort_by(|a, b| a.path.cmp(&b.path));
+    out
+}
+
+/// Extract the image members of a zip into `dest`. Non-image members are
+/// ignored; member paths that would escape `dest` are skipped.
+fn extract_zip(src: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(src).map_err(|e| format!("Failed to open {}: {e}", src.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip file: {e}"))?;
+
+    // Start from a clean folder so re-importing an updated pack doesn't keep
+    // images that were removed from it.
+    if dest.exists() {
+        fs::remove_dir_all(dest).map_err(|e| format!("Failed to clear {}: {e}", dest.display()))?;
+    }
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create {}: {e}", dest.display()))?;
+
+    for i in 0..archive.len() {
+        let mut member = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {e}"))?;
+        if member.is_dir() {
+            continue;
+        }
+        let Some(rel) = member.enclosed_name() else {
+            log::warn!("Skipping unsafe zip entry: {}", member.name());
+            continue;
+        };
+        if !is_image(&rel) {
+            continue;
+        }
+        let out_path = dest.join(&rel);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let mut bytes = Vec::new();
+        member
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to extract {}: {e}", rel.display()))?;
+        fs::write(&out_path, bytes)
+            .map_err(|e| format!("Failed to write {}: {e}", out_path.display()))?;
+    }
+    Ok(())
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Lowercase, drop `(...)` / `[...]` tags (region, revision, disc markers),
+/// and collapse everything that isn't alphanumeric into single spaces.
+pub fn normalize_title(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            c if c.is_alphanumeric() => out.extend(c.to_lowercase()),
+            _ => out.push(' '),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Similarity between two normalized titles: the better of whole-string
+/// edit distance and token overlap, so word order and extra subtitle words
+/// don't sink an otherwise obvious match.
+fn score(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let lev = normalized_levenshtein(a, b);
+    let ta: Vec<&str> = a.split_whitespace().collect();
+    let tb: Vec<&str> = b.split_whitespace().collect();
+    let shared = ta.iter().filter(|t| tb.contains(t)).count();
+    let overlap = shared as f64 / ta.len().max(tb.len()).max(1) as f64;
+    lev.max(overlap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, path: PathBuf) -> PackEntry {
+        PackEntry {
+            title: title.to_string(),
+            key: normalize_title(title),
+            path,
+            pack: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn normalize_strips_tags_and_punctuation() {
+        assert_eq!(normalize_title("Myst (USA) [!]"), "myst");
+        assert_eq!(
+            normalize_title("Monkey_Island-2: LeChuck's Revenge"),
+            "monkey island 2 lechuck s revenge"
+        );
+    }
+
+    #[test]
+    fn search_ranks_best_match_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let myst = dir.path().join("Myst (USA).jpg");
+        let riven = dir.path().join("Riven.jpg");
+        fs::write(&myst, b"x").unwrap();
+        fs::write(&riven, b"x").unwrap();
+
+        let index = PackIndex {
+            entries: vec![entry("Riven", riven), entry("Myst (USA)", myst.clone())],
+        };
+        let hits = index.search("Myst", 5);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].1.path, myst);
+    }
+
+    #[test]
+    fn search_skips_missing_files() {
+        let index = PackIndex {
+            entries: vec![entry("Myst", PathBuf::from("/nonexistent/Myst.jpg"))],
+        };
+        assert!(index.search("Myst", 5).is_empty());
+    }
+
+    #[test]
+    fn scan_folder_indexes_images_only() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("Doom.png"), b"x").unwrap();
+        fs::write(dir.path().join("sub").join("Quake.JPG"), b"x").unwrap();
+        fs::write(dir.path().join("readme.txt"), b"x").unwrap();
+
+        let entries = scan_folder(dir.path(), "p");
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(entries.len(), 2);
+        assert!(keys.contains(&"doom"));
+        assert!(keys.contains(&"quake"));
+    }
+
+    #[test]
+    fn replace_pack_drops_old_entries() {
+        let mut index = PackIndex {
+            entries: vec![entry("Old", PathBuf::from("/a.jpg"))],
+        };
+        index.replace_pack("test", vec![entry("New", PathBuf::from("/b.jpg"))]);
+        assert_eq!(index.entries.len(), 1);
+        assert_eq!(index.entries[0].title, "New");
+    }
+
+    #[test]
+    fn read_file_url_ignores_http() {
+        assert!(read_file_url("https://example.com/a.jpg").is_none());
+    }
+
+    #[test]
+    fn file_url_round_trips_escapes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("100%25 done.jpg");
+        fs::write(&path, b"cover").unwrap();
+        let url = file_url(&path);
+        assert!(url.ends_with("/100%2525%20done.jpg"));
+        let decoded = urlencoding::decode(url.strip_prefix("file://").unwrap()).unwrap();
+        assert_eq!(Path::new(decoded.as_ref()), path);
+        let index = PackIndex {
+            entries: vec![entry("100% done", path.clone())],
+        };
+        assert_eq!(read_pack_file(&path, &index).unwrap(), b"cover");
+    }
+
+    #[test]
+    fn only_pack_images_are_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let cover = dir.path().join("myst.jpg");
+        let other = dir.path().join("secrets.json");
+        fs::write(&cover, b"cover").unwrap();
+        fs::write(&other, b"{}").unwrap();
+        let index = PackIndex {
+            entries: vec![entry("Myst", cover.clone())],
+        };
+        assert!(read_pack_file(&other, &index).is_err());
+        assert!(read_pack_file(&cover.join("../secrets.json"), &index).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Bounded worker pool for per-disc work: folder-scan identification and
+//! the headless batch.
+//!
+//! [`map_with`] runs a fixed number of scoped threads that pull the next
+//! item until none are left, so a folder of 500 images never means 500
+//! threads, and hands the results back in item order. An item whose work
+//! panics comes back as a [`WorkerPanic`] instead of taking the rest of its
+//! worker's items with it.
+
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::logging::crash::catch_recovered;
+
+/// One worker per CPU core, for disc reading and hashing. Falls back to 2
+/// when the core count can't be read.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(2, NonZeroUsize::get)
+}
+
+/// The worker count for a `configured` value, where 0 means
+/// [`default_workers`].
+pub fn workers(configured: usize) -> usize {
+    match configured {
+        0 => default_workers(),
+        n => n,
+    }
+}
+
+/// An item that wasn't processed because `work` (or the `init` before it)
+/// panicked. Holds the panic message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("worker panicked: {0}")]
+pub struct WorkerPanic(pub String);
+
+impl WorkerPanic {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Self(message)
+    }
+}
+
+/// Run `work` over `items` on at most `workers` threads and return the
+/// results in the order of `items`. Each thread calls `init` once for
+/// state of its own (a database connection, say) that `work` gets with
+/// every item. Blocks until every item is done.
+///
+/// A panic fails only the item it happened on. The worker drops its state,
+/// since the panic may have left it half-updated, and calls `init` again
+/// before its next item.
+pub fn map_with<T, S, R>(
+    items: &[T],
+    workers: usize,
+    init: impl Fn() -> S + Sync,
+    work: impl Fn(&mut S, &T) -> R + Sync,
+) -> Vec<Result<R, WorkerPanic>>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let workers = workers.clamp(1, items.len().max(1));
+
+    let finished: Vec<(usize, Result<R, WorkerPanic>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut state = catch_recovered(&init).ok();
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        let result =
+                            catch_recovered(|| work(state.get_or_insert_with(&init), item));
+                        if result.is_err() {
+                            state = None;
+                        }
+                        done.push((index, result.map_err(WorkerPanic::from_payload)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+
+    let mut results: Vec<Option<Result<R, WorkerPanic>>> = items.iter().map(|_| None).collect();
+    for (index, result) in finished {
+        results[index] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .unwrap_or_else(|| Err(WorkerPanic("worker stopped before this item".to_string())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn results_come_back_in_item_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let doubled = map_with(
+            &items,
+            4,
+            || (),
+            |_, n| {
+                thread::sleep(Duration::from_millis(50 - n));
+                n * 2
+            },
+        );
+        let doubled: Vec<u64> = doubled.into_iter().map(Result::unwrap).collect();
+        assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn a_panicking_item_fails_alone() {
+        let items: Vec<u64> = (0..10).collect();
+        let results = map_with(
+            &items,
+            2,
+            || (),
+            |_, &n| {
+                if n == 3 {
+                    panic!("bad disc {n}");
+                }
+                n
+            },
+        );
+        assert_eq!(results.len(), items.len());
+        assert_eq!(results[3], Err(WorkerPanic("bad disc 3".to_string())));
+        for (n, result) in results.iter().enumerate().filter(|(n, _)| *n != 3) {
+            assert_eq!(result, &Ok(n as u64));
+        }
+    }
+
+    #[test]
+    fn never_runs_more_than_workers_at_once() {
+        let running = AtomicUsize::new(0);
+        let peak = Mutex::new(0);
+        let items = vec![(); 20];
+        map_with(
+            &items,
+            3,
+            || (),
+            |_, _| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                {
+                    let mut peak = peak.lock().unwrap();
+                    *peak = (*peak).max(now);
+                }
+                thread::sleep(Duration::from_millis(5));
+                running.fetch_sub(1, Ordering::SeqCst);
+            },
+        );
+        assert!(*peak.lock().unwrap() <= 3);
+    }
+
+    #[test]
+    fn init_runs_once_per_worker() {
+        let inits = AtomicUsize::new(0);
+        let items = vec![(); 10];
+        map_with(
+            &items,
+            2,
+            || inits.fetch_add(1, Ordering::SeqCst),
+            |_, _| (),
+        );
+        assert_eq!(inits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn zero_means_one_per_core() {
+        assert_eq!(workers(0), default_workers());
+        assert_eq!(workers(3), 3);
+        assert!(default_workers() >= 1);
+    }
+}
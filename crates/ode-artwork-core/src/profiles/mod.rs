@@ -0,0 +1,299 @@
+//! Settings profiles: named copies of the configuration, kept as
+//! `profiles/<name>.json` in the config directory, for switching between
+//! workflows ("PC games", "Audio CDs") or sharing tuned search sites and
+//! keywords.
+//!
+//! A profile holds everything but this computer's own settings: folders,
+//! the USBODE address, site cookies and headers, appearance, language and
+//! the like are reset when a
+//! profile is made and kept as they are when one is applied (see
+//! [`Profile::apply_to`]), so a profile file can be shared as it is.
+//! Importing also takes a plain `config.json`, or the settings of the
+//! original Python artwork script (see [`python`]), named after the file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{config_dir, AppConfig};
+
+pub mod python;
+
+/// Folder under the config directory holding the profiles.
+pub const PROFILES_DIR: &str = "profiles";
+
+/// A named configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub config: AppConfig,
+}
+
+impl Profile {
+    /// A profile of `config`, without this computer's own settings.
+    pub fn new(name: &str, config: &AppConfig) -> Self {
+        let mut config = config.clone();
+        keep_local(&AppConfig::default(), &mut config);
+        Self {
+            name: name.trim().to_string(),
+            config,
+        }
+    }
+
+    /// The profile's settings with the local ones taken from `current`.
+    pub fn apply_to(&self, current: &AppConfig) -> AppConfig {
+        let mut config = self.config.clone();
+        keep_local(current, &mut config);
+        config
+    }
+
+    /// Read a profile file, or a plain `config.json` or Python script
+    /// config named after the file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let profile = if let Ok(profile) = serde_json::from_str::<Profile>(&text) {
+            profile
+        } else if let Some(search) = python::search_settings(&text) {
+            let config = AppConfig {
+                search,
+                ..AppConfig::default()
+            };
+            Profile::new(&name, &config)
+        } else {
+            let config = serde_json::from_str::<AppConfig>(&text)
+                .map_err(|e| format!("{} isn't a settings profile: {}", path.display(), e))?;
+            Profile::new(&name, &config)
+        };
+        if profile.name.is_empty() {
+            return Err(format!("{} has no profile name", path.display()));
+        }
+        Ok(profile)
+    }
+
+    /// Write the profile to `path` as JSON.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize profile: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Copy the settings that belong to this computer rather than a workflow
+/// from `from` into `to`.
+fn keep_local(from: &AppConfig, to: &mut AppConfig) {
+    to.appearance = from.appearance.clone();
+    to.update_check = from.update_check.clone();
+    to.discogs = from.discogs.clone();
+    to.log_level = from.log_level.clone();
+    to.log_file_level = from.log_file_level.clone();
+    to.language = from.language.clone();
+    to.watch = from.watch.clone();
+    to.usbode = from.usbode.clone();
+    to.export.output_dir = from.export.output_dir.clone();
+    to.export.remote_target = from.export.remote_target.clone();
+    to.export.retroarch_thumbnails = from.export.retroarch_thumbnails.clone();
+    to.search.user_agent = from.search.user_agent.clone();
+    // Cookies and auth headers are the user's own logins
+    to.network.site_rules = from.network.site_rules.clone();
+}
+
+/// The profiles folder.
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    /// The store in the per-user config directory, created on demand.
+    pub fn open() -> Result<Self, String> {
+        let dir = config_dir()?.join(PROFILES_DIR);
+        fs::create_dir_all(&dir).map_err(|e| format!("could not create {}: {e}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// A store in `dir`, which must exist.
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Names of the saved profiles, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|e| e == "json"))
+                    .filter_map(|p| Profile::read(&p).ok())
+                    .map(|profile| profile.name)
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", file_name(name)))
+    }
+
+    /// Save `profile`, replacing one of the same name.
+    pub fn save(&self, profile: &Profile) -> Result<(), String> {
+        if profile.name.is_empty() {
+            return Err("A profile needs a name".to_string());
+        }
+        profile.write(&self.path(&profile.name))
+    }
+
+    pub fn load(&self, name: &str) -> Result<Profile, String> {
+        Profile::read(&self.path(name))
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let path = self.path(name);
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+    }
+
+    /// Add the profile in the file at `path`, checking its settings first.
+    /// Returns its name.
+    pub fn import(&self, path: &Path) -> Result<String, String> {
+        let profile = Profile::read(path)?;
+        profile
+            .apply_to(&AppConfig::default())
+            .validate()
+            .map_err(|e| format!("Profile {}: {}", profile.name, e))?;
+        self.save(&profile)?;
+        Ok(profile.name)
+    }
+
+    /// Write the profile called `name` to `path`, to share it.
+    pub fn export(&self, name: &str, path: &Path) -> Result<(), String> {
+        self.load(name)?.write(path)
+    }
+}
+
+/// `name` with the characters file systems don't allow replaced by `_`.
+fn file_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> (PathBuf, ProfileStore) {
+        let dir =
+            std::env::temp_dir().join(format!("ode_profiles_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        (dir.clone(), ProfileStore::at(dir))
+    }
+
+    #[test]
+    fn profiles_leave_local_settings_alone() {
+        let mut mine = AppConfig::default();
+        mine.search.content_type = "audio_cds".to_string();
+        mine.search.known_sites.audio_cds = vec!["discogs.com".to_string()];
+        mine.usbode.address = "usbode.local".to_string();
+        mine.watch.folders = vec!["/downloads".to_string()];
+
+        let profile = Profile::new("Audio CDs", &mine);
+        assert_eq!(profile.config.usbode.address, "");
+        assert!(profile.config.watch.folders.is_empty());
+
+        let mut current = AppConfig::default();
+        current.usbode.address = "10.0.0.5".to_string();
+        let applied = profile.apply_to(&current);
+        assert_eq!(applied.search.content_type, "audio_cds");
+        assert_eq!(applied.search.known_sites.audio_cds, vec!["discogs.com"]);
+        assert_eq!(applied.usbode.address, "10.0.0.5");
+    }
+
+    #[test]
+    fn profiles_carry_no_site_cookies() {
+        let mut mine = AppConfig::default();
+        mine.network.site_rules = vec![crate::config::SiteRule {
+            domain: "example.com".to_string(),
+            headers: [("Referer".to_string(), "https://example.com/".to_string())].into(),
+            cookie: Some("session=secret".to_string()),
+        }];
+
+        let (dir, store) = temp_store("cookies");
+        store.save(&Profile::new("Shared", &mine)).unwrap();
+        let saved = fs::read_to_string(dir.join("Shared.json")).unwrap();
+        assert!(!saved.contains("session=secret"));
+        assert!(!saved.contains("example.com"));
+
+        // Applying a profile keeps the user's own rules
+        let applied = store.load("Shared").unwrap().apply_to(&mine);
+        assert_eq!(applied.network.site_rules.len(), 1);
+        assert_eq!(
+            applied.network.site_rules[0].cookie.as_deref(),
+            Some("session=secret")
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn saves_lists_and_imports() {
+        let (dir, store) = temp_store("store");
+        let mut config = AppConfig::default();
+        config.search.content_type = "games".to_string();
+        store.save(&Profile::new("PC games: DOS", &config)).unwrap();
+        store
+            .save(&Profile::new("Audio CDs", &AppConfig::default()))
+            .unwrap();
+        assert_eq!(store.names(), vec!["Audio CDs", "PC games: DOS"]);
+        assert_eq!(
+            store
+                .load("PC games: DOS")
+                .unwrap()
+                .config
+                .search
+                .content_type,
+            "games"
+        );
+
+        store.delete("Audio CDs").unwrap();
+        assert_eq!(store.names(), vec!["PC games: DOS"]);
+
+        // A plain config.json is named after its file
+        let (shared_dir, _) = temp_store("shared");
+        let shared = shared_dir.join("Saturn.json");
+        fs::write(&shared, r#"{"search": {"content_type": "games"}}"#).unwrap();
+        assert_eq!(store.import(&shared).unwrap(), "Saturn");
+        assert_eq!(store.names(), vec!["PC games: DOS", "Saturn"]);
+
+        // So is the Python script's config.py
+        let script = shared_dir.join("config.py");
+        fs::write(&script, "KNOWN_PUBLISHERS = ['Sierra']\n").unwrap();
+        assert_eq!(store.import(&script).unwrap(), "config");
+        let imported = store.load("config").unwrap().config.search;
+        assert_eq!(imported.known_publishers, vec!["Sierra"]);
+        let _ = fs::remove_dir_all(&dir);
+        let _ = fs::remove_dir_all(&shared_dir);
+    }
+
+    #[test]
+    fn import_rejects_bad_settings() {
+        let (dir, store) = temp_store("bad");
+        let bad = dir.join("bad.json");
+        fs::write(&bad, r#"{"search": {"content_type": "movies"}}"#).unwrap();
+        assert!(store.import(&bad).is_err());
+        fs::write(&bad, "not json").unwrap();
+        assert!(store.import(&bad).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
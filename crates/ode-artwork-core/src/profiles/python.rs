@@ -0,0 +1,294 @@
+//! Settings from the original Python artwork script, so its users can bring
+//! their tuned sites, keywords and publishers across.
+//!
+//! The script kept them either in a JSON file or as constants at the top of
+//! `config.py`:
+//!
+//! ```text
+//! EXCLUSION_SITES = ["ebay.com", 'amazon.com']  # Python lists or tuples
+//! KNOWN_SITES = {"games": ["mobygames.com"], "audio_cds": ["discogs.com"]}
+//! KNOWN_PUBLISHERS = ("Sierra", "Maxis")
+//! ```
+//!
+//! Names are matched without regard to case, and the spellings the script
+//! used over time (`excluded_sites`, `publishers`, a plain `keywords` list,
+//! a flat `sites` list meaning game sites) are all accepted. Anything else
+//! in the file is ignored.
+
+use serde_json::{Map, Value};
+
+use crate::config::{KnownSites, SearchSettings};
+
+/// The search settings in a Python script config, on top of the defaults.
+/// `None` when `text` holds none of them.
+pub fn search_settings(text: &str) -> Option<SearchSettings> {
+    let values = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(map)) => map,
+        Ok(_) => return None,
+        Err(_) => constants(text),
+    };
+    let values: Map<String, Value> = values
+        .into_iter()
+        .map(|(key, value)| (key.to_lowercase().replace('-', "_"), value))
+        .collect();
+    let get = |names: &[&str]| names.iter().find_map(|name| values.get(*name));
+
+    let mut settings = SearchSettings::default();
+    let mut found = false;
+    let mut set = |target: &mut Vec<String>, value: Option<&Value>| {
+        if let Some(list) = value.and_then(strings) {
+            *target = list;
+            found = true;
+        }
+    };
+    set(
+        &mut settings.exclusion_sites,
+        get(&[
+            "exclusion_sites",
+            "excluded_sites",
+            "exclude_sites",
+            "blocked_sites",
+        ]),
+    );
+    set(
+        &mut settings.exclusion_platforms,
+        get(&[
+            "exclusion_platforms",
+            "excluded_platforms",
+            "exclude_platforms",
+        ]),
+    );
+    set(
+        &mut settings.cd_keywords,
+        get(&["cd_keywords", "keywords", "search_keywords"]),
+    );
+    set(&mut settings.dvd_keywords, get(&["dvd_keywords"]));
+    set(
+        &mut settings.known_publishers,
+        get(&["known_publishers", "publishers"]),
+    );
+    match get(&["known_sites", "sites", "preferred_sites"]) {
+        Some(Value::Object(sites)) => {
+            let sites: Map<String, Value> = sites
+                .iter()
+                .map(|(key, value)| (key.to_lowercase(), value.clone()))
+                .collect();
+            let list = |names: &[&str]| {
+                names
+                    .iter()
+                    .find_map(|name| sites.get(*name).and_then(strings))
+                    .unwrap_or_default()
+            };
+            settings.known_sites = KnownSites {
+                games: list(&["games"]),
+                apps_utilities: list(&["apps_utilities", "apps", "utilities"]),
+                audio_cds: list(&["audio_cds", "audio", "music"]),
+            };
+            found = true;
+        }
+        Some(value) => {
+            if let Some(games) = strings(value) {
+                settings.known_sites = KnownSites {
+                    games,
+                    ..KnownSites::default()
+                };
+                found = true;
+            }
+        }
+        None => {}
+    }
+    if let Some(Value::String(content_type)) = get(&["content_type"]) {
+        settings.content_type = content_type.to_lowercase();
+    }
+    found.then_some(settings)
+}
+
+/// A list of strings, or a single comma-separated string.
+fn strings(value: &Value) -> Option<Vec<String>> {
+    let list: Vec<String> = match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Value::String(text) => text.split(',').map(str::to_string).collect(),
+        _ => return None,
+    };
+    Some(
+        list.into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    )
+}
+
+/// The `NAME = <literal>` assignments at the top level of a Python file
+/// whose values are plain lists, tuples, dicts or strings.
+fn constants(source: &str) -> Map<String, Value> {
+    let mut values = Map::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let Some((name, rest)) = line.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let is_name = !line.starts_with(char::is_whitespace)
+            && !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_name || rest.starts_with('=') {
+            continue;
+        }
+        // Values spanning lines run until their brackets close
+        let mut literal = rest.to_string();
+        while depth(&literal) > 0 {
+            let Some(next) = lines.next() else { break };
+            literal.push('\n');
+            literal.push_str(next);
+        }
+        if let Some(value) =
+            python_to_json(&literal).and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        {
+            values.insert(name.to_string(), value);
+        }
+    }
+    values
+}
+
+/// Open brackets in `code`, outside strings and comments.
+fn depth(code: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '#') => {
+                chars.find(|&c| c == '\n');
+            }
+            (None, '[' | '(' | '{') => depth += 1,
+            (None, ']' | ')' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// A Python literal rewritten as JSON: strings in either quote, tuples as
+/// arrays, `True`/`False`/`None`, trailing commas and comments. `None` for
+/// anything else (a function call, an expression).
+fn python_to_json(literal: &str) -> Option<String> {
+    let mut json = String::new();
+    let mut chars = literal.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next()? {
+                        '\\' => match chars.next()? {
+                            'n' => text.push('\n'),
+                            't' => text.push('\t'),
+                            other => text.push(other),
+                        },
+                        q if q == c => break,
+                        other => text.push(other),
+                    }
+                }
+                json += &serde_json::to_string(&text).ok()?;
+            }
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '(' => json.push('['),
+            ']' | ')' | '}' => {
+                // Python allows a comma before the closing bracket
+                let kept = json.trim_end().trim_end_matches(',').len();
+                json.truncate(kept);
+                json.push(if c == '}' { '}' } else { ']' });
+            }
+            '[' | '{' | ',' | ':' => json.push(c),
+            c if c.is_whitespace() => json.push(c),
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '.' => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+                    word.push(c);
+                }
+                json += match word.as_str() {
+                    "True" => "true",
+                    "False" => "false",
+                    "None" => "null",
+                    number if number.parse::<f64>().is_ok() => number,
+                    _ => return None,
+                };
+            }
+            _ => return None,
+        }
+    }
+    Some(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_config_py_constants() {
+        let source = r#"
+import os
+
+# Sites to skip
+EXCLUSION_SITES = ["ebay.com", 'amazon.com']  # resellers
+KNOWN_PUBLISHERS = (
+    "Sierra",
+    'Maxis',   # SimCity
+)
+KNOWN_SITES = {
+    "games": ["mobygames.com"],
+    "audio_cds": ["discogs.com", "it's-a-site.com"],
+}
+CD_KEYWORDS = ["CD", "jewel case", "front"]
+DOWNLOAD_DIR = os.path.expanduser("~/covers")
+DEBUG = True
+"#;
+        let settings = search_settings(source).unwrap();
+        assert_eq!(settings.exclusion_sites, vec!["ebay.com", "amazon.com"]);
+        assert_eq!(settings.known_publishers, vec!["Sierra", "Maxis"]);
+        assert_eq!(settings.known_sites.games, vec!["mobygames.com"]);
+        assert_eq!(
+            settings.known_sites.audio_cds,
+            vec!["discogs.com", "it's-a-site.com"]
+        );
+        assert_eq!(settings.cd_keywords, vec!["CD", "jewel case", "front"]);
+        // Untouched settings keep their defaults
+        assert_eq!(
+            settings.dvd_keywords,
+            SearchSettings::default().dvd_keywords
+        );
+    }
+
+    #[test]
+    fn reads_json_with_old_names() {
+        let text = r#"{
+            "Excluded_Sites": "ebay.com, pinterest.com",
+            "publishers": ["Activision"],
+            "sites": ["gamesdatabase.org"],
+            "keywords": ["jewel case"]
+        }"#;
+        let settings = search_settings(text).unwrap();
+        assert_eq!(settings.exclusion_sites, vec!["ebay.com", "pinterest.com"]);
+        assert_eq!(settings.known_publishers, vec!["Activision"]);
+        assert_eq!(settings.known_sites.games, vec!["gamesdatabase.org"]);
+        assert!(settings.known_sites.audio_cds.is_empty());
+        assert_eq!(settings.cd_keywords, vec!["jewel case"]);
+    }
+
+    #[test]
+    fn ignores_files_without_search_settings() {
+        assert!(search_settings(r#"{"search": {"content_type": "games"}}"#).is_none());
+        assert!(search_settings("print('hello')").is_none());
+        assert!(search_settings("[1, 2]").is_none());
+    }
+}
@@ -2,17 +2,21 @@
 //!
 //! Fetches image search results from DuckDuckGo and parses them for display.
 
-use crate::config::config_file_path;
+pub mod pick;
+
+use crate::net::CancelToken;
 use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
-use serde::Deserialize;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use thiserror::Error;
 
 /// Default user agent used when none is configured
 const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
 
 /// A single image search result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageResult {
     /// URL of the full-size image
     pub image_url: String,
@@ -28,6 +32,95 @@ pub struct ImageResult {
     pub height: Option<u32>,
 }
 
+impl ImageResult {
+    pub fn result_source(&self) -> ResultSource {
+        ResultSource::of(&self.source)
+    }
+}
+
+/// Which provider a result came from, going by its `source` text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResultSource {
+    /// An imported cover pack
+    Local,
+    MusicBrainz,
+    Discogs,
+    MobyGames,
+    /// A DuckDuckGo image search hit
+    Web,
+}
+
+impl ResultSource {
+    /// Every source, in the order the results list shows their filters.
+    pub const ALL: [ResultSource; 5] = [
+        ResultSource::Local,
+        ResultSource::MusicBrainz,
+        ResultSource::Discogs,
+        ResultSource::MobyGames,
+        ResultSource::Web,
+    ];
+
+    /// Sorted from a result's `source`, such as "MusicBrainz (<release id>)"
+    /// or a DuckDuckGo hit's website.
+    pub fn of(source: &str) -> Self {
+        if source.starts_with(crate::packs::SOURCE_PREFIX) {
+            ResultSource::Local
+        } else if source.starts_with("MusicBrainz") {
+            ResultSource::MusicBrainz
+        } else if source.starts_with("Discogs") {
+            ResultSource::Discogs
+        } else if source.starts_with("MobyGames") {
+            ResultSource::MobyGames
+        } else {
+            ResultSource::Web
+        }
+    }
+}
+
+/// Why a search failed.
+#[derive(Error, Debug)]
+pub enum SearchError {
+    /// No answer from DuckDuckGo: offline, DNS, timeout
+    #[error("{context}: {source}")]
+    Network {
+        context: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// DuckDuckGo answered with an error status (often rate limiting)
+    #[error("{context}: HTTP {status}")]
+    Status {
+        context: &'static str,
+        status: StatusCode,
+    },
+
+    /// The page or JSON wasn't in the shape expected
+    #[error("{0}")]
+    Parse(String),
+
+    /// The user agent capture's local server or browser failed
+    #[error("{0}")]
+    Browser(String),
+
+    /// The search's `CancelToken` fired
+    #[error("{}", crate::net::CANCELLED)]
+    Cancelled,
+}
+
+impl SearchError {
+    fn network(context: &'static str) -> impl FnOnce(reqwest::Error) -> Self {
+        move |source| SearchError::Network { context, source }
+    }
+}
+
+/// For callers that only report the error.
+impl From<SearchError> for String {
+    fn from(e: SearchError) -> Self {
+        e.to_string()
+    }
+}
+
 /// DuckDuckGo image result from their JSON API
 #[derive(Debug, Deserialize)]
 struct DdgImageResult {
@@ -46,20 +139,56 @@ struct DdgImagesResponse {
 }
 
 /// Search for images using DuckDuckGo
-pub fn search_images(query: &str, max_results: usize) -> Result<Vec<ImageResult>, String> {
+pub fn search_images(query: &str, max_results: usize) -> Result<Vec<ImageResult>, SearchError> {
     search_images_with_ua(query, max_results, None)
 }
 
 /// Search for images using DuckDuckGo with a custom user agent
-pub fn search_images_with_ua(query: &str, max_results: usize, user_agent: Option<&str>) -> Result<Vec<ImageResult>, String> {
+pub fn search_images_with_ua(query: &str, max_results: usize, user_agent: Option<&str>) -> Result<Vec<ImageResult>, SearchError> {
+    search_images_cancellable(query, max_results, user_agent, &CancelToken::new())
+}
+
+/// Request [`search_images_with_progress`] is waiting on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStage {
+    /// Loading the search page for its vqd token
+    FetchingToken,
+    /// Fetching the image results with that token
+    FetchingResults,
+}
+
+/// Like `search_images_with_ua`, but gives up between requests once
+/// `cancel` fires.
+pub fn search_images_cancellable(
+    query: &str,
+    max_results: usize,
+    user_agent: Option<&str>,
+    cancel: &CancelToken,
+) -> Result<Vec<ImageResult>, SearchError> {
+    search_images_with_progress(query, max_results, user_agent, cancel, &|_| {})
+}
+
+/// Like `search_images_cancellable`, calling `progress` before each request.
+pub fn search_images_with_progress(
+    query: &str,
+    max_results: usize,
+    user_agent: Option<&str>,
+    cancel: &CancelToken,
+    progress: &dyn Fn(SearchStage),
+) -> Result<Vec<ImageResult>, SearchError> {
     log::info!("DDG Search Query: {}", query);
 
     let client = build_client(user_agent)?;
 
     // Step 1: Get the vqd token from the search page
+    progress(SearchStage::FetchingToken);
     let vqd = get_vqd_token(&client, query)?;
+    if cancel.is_cancelled() {
+        return Err(SearchError::Cancelled);
+    }
 
     // Step 2: Fetch image results using the token
+    progress(SearchStage::FetchingResults);
     let results = fetch_image_results(&client, query, &vqd, max_results)?;
 
     log::info!("DDG Search returned {} results", results.len());
@@ -70,7 +199,7 @@ pub fn search_images_with_ua(query: &str, max_results: usize, user_agent: Option
     Ok(results)
 }
 
-fn build_client(user_agent: Option<&str>) -> Result<Client, String> {
+fn build_client(user_agent: Option<&str>) -> Result<Client, SearchError> {
     let ua = user_agent.unwrap_or(DEFAULT_USER_AGENT);
     log::debug!("Using user agent: {}", ua);
 
@@ -78,30 +207,27 @@ fn build_client(user_agent: Option<&str>) -> Result<Client, String> {
     headers.insert(
         USER_AGENT,
         HeaderValue::from_str(ua)
-            .map_err(|e| format!("Invalid user agent string: {}", e))?,
+            .map_err(|e| SearchError::Parse(format!("Invalid user agent string: {}", e)))?,
     );
 
-    Client::builder()
+    crate::net::client_builder(crate::net::Timeout::Request)
         .default_headers(headers)
-        .timeout(Duration::from_secs(30))
         .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+        .map_err(SearchError::network("Failed to create HTTP client"))
 }
 
-fn get_vqd_token(client: &Client, query: &str) -> Result<String, String> {
+fn get_vqd_token(client: &Client, query: &str) -> Result<String, SearchError> {
     let url = format!(
         "https://duckduckgo.com/?q={}&ia=images&iax=images",
         urlencoding::encode(query)
     );
 
-    let response = client
-        .get(&url)
-        .send()
-        .map_err(|e| format!("Failed to fetch search page: {}", e))?;
+    let response = crate::net::send_with_retry(|| client.get(&url).send())
+        .map_err(SearchError::network("Failed to fetch search page"))?;
 
     let text = response
         .text()
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+        .map_err(SearchError::network("Failed to read response"))?;
 
     // Extract vqd token from the page
     // Look for: vqd="..." or vqd='...' or vqd=...&
@@ -137,7 +263,9 @@ fn get_vqd_token(client: &Client, query: &str) -> Result<String, String> {
         }
     }
 
-    Err("Could not find vqd token in search page".to_string())
+    Err(SearchError::Parse(
+        "Could not find vqd token in search page".to_string(),
+    ))
 }
 
 fn fetch_image_results(
@@ -145,7 +273,7 @@ fn fetch_image_results(
     query: &str,
     vqd: &str,
     max_results: usize,
-) -> Result<Vec<ImageResult>, String> {
+) -> Result<Vec<ImageResult>, SearchError> {
     let url = format!(
         "https://duckduckgo.com/i.js?l=us-en&o=json&q={}&vqd={}&f=,,,,,&p=1",
         urlencoding::encode(query),
@@ -154,27 +282,32 @@ fn fetch_image_results(
 
     log::debug!("Fetching images from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .header("Referer", "https://duckduckgo.com/")
-        .send()
-        .map_err(|e| format!("Failed to fetch images: {}", e))?;
+    let response = crate::net::send_with_retry(|| {
+        client
+            .get(&url)
+            .header("Accept", "application/json")
+            .header("Referer", "https://duckduckgo.com/")
+            .send()
+    })
+    .map_err(SearchError::network("Failed to fetch images"))?;
 
     let status = response.status();
     if !status.is_success() {
-        return Err(format!("Image search failed with status: {}", status));
+        return Err(SearchError::Status {
+            context: "Image search failed",
+            status,
+        });
     }
 
     let text = response
         .text()
-        .map_err(|e| format!("Failed to read image results: {}", e))?;
+        .map_err(SearchError::network("Failed to read image results"))?;
 
     log::debug!("Response length: {} bytes", text.len());
 
     // Parse JSON response
     let ddg_response: DdgImagesResponse = serde_json::from_str(&text)
-        .map_err(|e| format!("Failed to parse image results: {} (response: {}...)", e, &text[..200.min(text.len())]))?;
+        .map_err(|e| SearchError::Parse(format!("Failed to parse image results: {} (response: {}...)", e, &text[..200.min(text.len())])))?;
 
     let results: Vec<ImageResult> = ddg_response
         .results
@@ -197,16 +330,16 @@ fn fetch_image_results(
 /// and having the user navigate to it in their browser.
 ///
 /// Returns the captured user agent string on success.
-pub fn capture_browser_user_agent() -> Result<String, String> {
+pub fn capture_browser_user_agent() -> Result<String, SearchError> {
     use tiny_http::{Server, Response};
 
     // Start server on a random available port
     let server = Server::http("127.0.0.1:0")
-        .map_err(|e| format!("Failed to start local server: {}", e))?;
+        .map_err(|e| SearchError::Browser(format!("Failed to start local server: {}", e)))?;
 
     let addr = server.server_addr()
         .to_ip()
-        .ok_or_else(|| "Failed to get server address".to_string())?;
+        .ok_or_else(|| SearchError::Browser("Failed to get server address".to_string()))?;
     let url = format!("http://{}", addr);
 
     log::info!("User agent capture server started at {}", url);
@@ -217,8 +350,10 @@ pub fn capture_browser_user_agent() -> Result<String, String> {
     // Wait for a request (with 60 second timeout)
     let request = server
         .recv_timeout(Duration::from_secs(60))
-        .map_err(|e| format!("Error waiting for browser: {}", e))?
-        .ok_or_else(|| "Timeout waiting for browser connection (60s)".to_string())?;
+        .map_err(|e| SearchError::Browser(format!("Error waiting for browser: {}", e)))?
+        .ok_or_else(|| {
+            SearchError::Browser("Timeout waiting for browser connection (60s)".to_string())
+        })?;
 
     // Extract user agent from request headers
     let user_agent = request
@@ -240,7 +375,9 @@ pub fn capture_browser_user_agent() -> Result<String, String> {
         let response = Response::from_string(html)
             .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..]).unwrap());
         let _ = request.respond(response);
-        return Err("Browser did not send a user agent".to_string());
+        return Err(SearchError::Browser(
+            "Browser did not send a user agent".to_string(),
+        ));
     }
 
     log::info!("Captured user agent: {}", user_agent);
@@ -263,13 +400,13 @@ pub fn capture_browser_user_agent() -> Result<String, String> {
 }
 
 /// Open a URL in the system's default browser
-fn open_url_in_browser(url: &str) -> Result<(), String> {
+fn open_url_in_browser(url: &str) -> Result<(), SearchError> {
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("open")
             .arg(url)
             .spawn()
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
+            .map_err(|e| SearchError::Browser(format!("Failed to open browser: {}", e)))?;
     }
 
     #[cfg(target_os = "windows")]
@@ -277,7 +414,7 @@ fn open_url_in_browser(url: &str) -> Result<(), String> {
         std::process::Command::new("cmd")
             .args(["/c", "start", "", url])
             .spawn()
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
+            .map_err(|e| SearchError::Browser(format!("Failed to open browser: {}", e)))?;
     }
 
     #[cfg(target_os = "linux")]
@@ -285,47 +422,12 @@ fn open_url_in_browser(url: &str) -> Result<(), String> {
         std::process::Command::new("xdg-open")
             .arg(url)
             .spawn()
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
+            .map_err(|e| SearchError::Browser(format!("Failed to open browser: {}", e)))?;
     }
 
     Ok(())
 }
 
-/// Save user agent to the per-user `config.json`.
-pub fn save_user_agent_to_config(user_agent: &str) -> Result<(), String> {
-    let config_path = config_file_path()?;
-
-    // Read existing config, or start fresh if it doesn't exist yet.
-    let mut json: serde_json::Value = match std::fs::read_to_string(&config_path) {
-        Ok(s) => serde_json::from_str(&s)
-            .map_err(|e| format!("Failed to parse config.json: {}", e))?,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
-        Err(e) => return Err(format!("Failed to read config.json: {}", e)),
-    };
-
-    // Update or create the search.user_agent field
-    if let Some(search) = json.get_mut("search") {
-        if let Some(obj) = search.as_object_mut() {
-            obj.insert("user_agent".to_string(), serde_json::Value::String(user_agent.to_string()));
-        }
-    } else {
-        // Create search section if it doesn't exist
-        json["search"] = serde_json::json!({
-            "user_agent": user_agent
-        });
-    }
-
-    // Write back to file
-    let updated = serde_json::to_string_pretty(&json)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-
-    std::fs::write(&config_path, updated)
-        .map_err(|e| format!("Failed to write config.json: {}", e))?;
-
-    log::info!("Saved user agent to {}", config_path.display());
-    Ok(())
-}
-
 /// A web search result (URL + title)
 #[derive(Debug, Clone)]
 pub struct WebSearchResult {
@@ -337,7 +439,7 @@ pub struct WebSearchResult {
 
 /// Search DuckDuckGo for web results (not images)
 /// Returns a list of URLs matching the query
-pub fn ddg_web_search(query: &str, user_agent: Option<&str>, max_results: usize) -> Result<Vec<WebSearchResult>, String> {
+pub fn ddg_web_search(query: &str, user_agent: Option<&str>, max_results: usize) -> Result<Vec<WebSearchResult>, SearchError> {
     log::info!("DDG Web Search Query: {}", query);
 
     let client = build_client(user_agent)?;
@@ -353,11 +455,11 @@ pub fn ddg_web_search(query: &str, user_agent: Option<&str>, max_results: usize)
     let response = client
         .get(&url)
         .send()
-        .map_err(|e| format!("Failed to fetch search page: {}", e))?;
+        .map_err(SearchError::network("Failed to fetch search page"))?;
 
     let text = response
         .text()
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+        .map_err(SearchError::network("Failed to read response"))?;
 
     // Parse results from HTML
     // DDG HTML results have links in <a class="result__a" href="...">
@@ -372,19 +474,19 @@ pub fn ddg_web_search(query: &str, user_agent: Option<&str>, max_results: usize)
 }
 
 /// Parse DuckDuckGo HTML search results
-fn parse_ddg_html_results(html: &str, max_results: usize) -> Result<Vec<WebSearchResult>, String> {
+fn parse_ddg_html_results(html: &str, max_results: usize) -> Result<Vec<WebSearchResult>, SearchError> {
     let mut results = Vec::new();
 
     // Pattern to match result links
     // DDG HTML has: <a rel="nofollow" class="result__a" href="URL">TITLE</a>
     let link_pattern = regex::Regex::new(
         r#"<a[^>]*class="result__a"[^>]*href="([^"]+)"[^>]*>([^<]+)</a>"#
-    ).map_err(|e| format!("Failed to compile regex: {}", e))?;
+    ).map_err(|e| SearchError::Parse(format!("Failed to compile regex: {}", e)))?;
 
     // Also try alternate pattern with href before class
     let link_pattern_alt = regex::Regex::new(
         r#"<a[^>]*href="([^"]+)"[^>]*class="result__a"[^>]*>([^<]+)</a>"#
-    ).map_err(|e| format!("Failed to compile alt regex: {}", e))?;
+    ).map_err(|e| SearchError::Parse(format!("Failed to compile alt regex: {}", e)))?;
 
     // Collect matches from both patterns
     for caps in link_pattern.captures_iter(html) {
@@ -424,7 +526,7 @@ fn parse_ddg_html_results(html: &str, max_results: usize) -> Result<Vec<WebSearc
         // Look for any link with uddg= parameter (DDG's redirect format)
         let uddg_pattern = regex::Regex::new(
             r#"href="[^"]*uddg=([^&"]+)"#
-        ).map_err(|e| format!("Failed to compile uddg regex: {}", e))?;
+        ).map_err(|e| SearchError::Parse(format!("Failed to compile uddg regex: {}", e)))?;
 
         for caps in uddg_pattern.captures_iter(html) {
             if results.len() >= max_results {
@@ -479,6 +581,24 @@ fn html_decode(text: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn result_source_from_source_text() {
+        let local = format!("{} (Redump covers)", crate::packs::SOURCE_PREFIX);
+        assert_eq!(ResultSource::of(&local), ResultSource::Local);
+        assert_eq!(ResultSource::of("MusicBrainz (1a2b)"), ResultSource::MusicBrainz);
+        assert_eq!(ResultSource::of("Discogs (12345)"), ResultSource::Discogs);
+        assert_eq!(ResultSource::of("MobyGames"), ResultSource::MobyGames);
+        assert_eq!(ResultSource::of("Bing"), ResultSource::Web);
+        assert_eq!(ResultSource::of(""), ResultSource::Web);
+    }
+
+    #[test]
+    fn cancelled_reads_like_a_cancelled_check() {
+        // Same text as `CancelToken::check` gives elsewhere in a job
+        let e: String = SearchError::Cancelled.into();
+        assert_eq!(e, crate::net::CANCELLED);
+    }
+
     #[test]
     #[ignore] // Requires network access
     fn test_search_images() {
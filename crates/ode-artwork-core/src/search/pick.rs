@@ -0,0 +1,160 @@
+//! Scoring search results so the best one can be picked without asking.
+//!
+//! Each result scores 0–1 from four parts: how square it is, whether its
+//! short side reaches [`GOOD_EDGE`], how far its source is trusted, and how
+//! much of the disc title its own title contains. Used by Auto-pick in the
+//! search window and by the processing queue.
+
+use super::{ImageResult, ResultSource};
+
+/// Short side a cover should have; smaller ones score proportionally less.
+pub const GOOD_EDGE: u32 = 480;
+
+/// Best score under which [`auto_pick`] declines rather than save a poor
+/// match.
+pub const MIN_AUTO_SCORE: f32 = 0.55;
+
+const SQUARE_WEIGHT: f32 = 0.35;
+const RESOLUTION_WEIGHT: f32 = 0.25;
+const SOURCE_WEIGHT: f32 = 0.2;
+const TITLE_WEIGHT: f32 = 0.2;
+
+/// Longest/shortest edge ratio at which squareness scores zero.
+const MAX_ASPECT: f32 = 1.5;
+
+/// Part score when it can't be judged: the source gave no dimensions, or
+/// the disc has no title.
+const UNKNOWN: f32 = 0.5;
+
+/// Score of `result` as artwork for a disc titled `title`.
+pub fn score(result: &ImageResult, title: &str) -> f32 {
+    let (square, resolution) = match (result.width, result.height) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => {
+            let aspect = w.max(h) as f32 / w.min(h) as f32;
+            let square = (1.0 - (aspect - 1.0) / (MAX_ASPECT - 1.0)).clamp(0.0, 1.0);
+            let resolution = (w.min(h) as f32 / GOOD_EDGE as f32).min(1.0);
+            (square, resolution)
+        }
+        _ => (UNKNOWN, UNKNOWN),
+    };
+    SQUARE_WEIGHT * square
+        + RESOLUTION_WEIGHT * resolution
+        + SOURCE_WEIGHT * source_priority(&result.source)
+        + TITLE_WEIGHT * title_similarity(&result.title, title)
+}
+
+/// Index and score of the highest-scoring result. Ties go to the earlier
+/// result, keeping the search's own ranking.
+pub fn best(results: &[ImageResult], title: &str) -> Option<(usize, f32)> {
+    results
+        .iter()
+        .map(|r| score(r, title))
+        .enumerate()
+        .fold(None, |best, (i, s)| match best {
+            Some((_, top)) if top >= s => best,
+            _ => Some((i, s)),
+        })
+}
+
+/// [`best`], unless it scores under [`MIN_AUTO_SCORE`].
+pub fn auto_pick(results: &[ImageResult], title: &str) -> Option<(usize, f32)> {
+    best(results, title).filter(|&(_, s)| s >= MIN_AUTO_SCORE)
+}
+
+/// Local packs are curated covers; MusicBrainz, Discogs and MobyGames are
+/// catalog scans of the actual release; anything else is a web hit.
+fn source_priority(source: &str) -> f32 {
+    match ResultSource::of(source) {
+        ResultSource::Local => 1.0,
+        ResultSource::MusicBrainz => 0.9,
+        ResultSource::Discogs | ResultSource::MobyGames => 0.8,
+        ResultSource::Web => 0.5,
+    }
+}
+
+/// Share of the words of `title` that appear in `candidate`, ignoring case
+/// and punctuation.
+fn title_similarity(candidate: &str, title: &str) -> f32 {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let wanted = words(title);
+    if wanted.is_empty() {
+        return UNKNOWN;
+    }
+    let have = words(candidate);
+    let found = wanted.iter().filter(|w| have.contains(w)).count();
+    found as f32 / wanted.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(title: &str, source: &str, size: Option<(u32, u32)>) -> ImageResult {
+        ImageResult {
+            image_url: format!("https://example.com/{title}.jpg"),
+            thumbnail_url: String::new(),
+            title: title.to_string(),
+            source: source.to_string(),
+            width: size.map(|s| s.0),
+            height: size.map(|s| s.1),
+        }
+    }
+
+    #[test]
+    fn square_large_cover_beats_small_and_wide() {
+        let results = [
+            result("Myst CD cover", "example.com", Some((1000, 500))),
+            result("Myst CD cover", "example.com", Some((240, 240))),
+            result("Myst CD cover", "example.com", Some((600, 600))),
+        ];
+        assert_eq!(best(&results, "Myst").map(|b| b.0), Some(2));
+    }
+
+    #[test]
+    fn resolution_is_capped_at_good_edge() {
+        let at = score(&result("Myst", "web", Some((GOOD_EDGE, GOOD_EDGE))), "Myst");
+        let above = score(&result("Myst", "web", Some((2000, 2000))), "Myst");
+        assert_eq!(at, above);
+    }
+
+    #[test]
+    fn trusted_source_wins_otherwise_equal() {
+        let results = [
+            result("Myst", "example.com", Some((500, 500))),
+            result("Myst", "Local pack (redump)", Some((500, 500))),
+        ];
+        assert_eq!(best(&results, "Myst").map(|b| b.0), Some(1));
+    }
+
+    #[test]
+    fn title_words_are_matched_loosely() {
+        assert_eq!(title_similarity("MYST - Jewel Case (PC)", "Myst"), 1.0);
+        assert_eq!(title_similarity("Riven front", "Myst: Riven"), 0.5);
+        assert_eq!(title_similarity("anything", ""), UNKNOWN);
+    }
+
+    #[test]
+    fn ties_keep_search_order() {
+        let results = [
+            result("Myst", "example.com", Some((500, 500))),
+            result("Myst", "example.org", Some((500, 500))),
+        ];
+        assert_eq!(best(&results, "Myst").map(|b| b.0), Some(0));
+    }
+
+    #[test]
+    fn auto_pick_declines_poor_matches() {
+        let poor = [result("banner", "example.com", Some((1200, 300)))];
+        assert!(best(&poor, "Myst").is_some());
+        assert!(auto_pick(&poor, "Myst").is_none());
+
+        let good = [result("Myst cover", "example.com", Some((500, 500)))];
+        assert!(auto_pick(&good, "Myst").is_some());
+        assert!(auto_pick(&[], "Myst").is_none());
+    }
+}
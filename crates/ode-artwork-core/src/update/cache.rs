@@ -0,0 +1,192 @@
+//! The last GitHub releases response, kept in the cache folder as
+//! `update-check.json`.
+//!
+//! Within `check_interval_hours` of the last request, or while GitHub's
+//! rate limit says to wait, the check answers from the cached body without
+//! a request. Otherwise it asks with `If-None-Match`, and a `304 Not
+//! Modified` (which doesn't count against the limit) reuses the body.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, ETAG, IF_NONE_MATCH, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use super::UpdateCheckConfig;
+
+const CACHE_FILE: &str = "update-check.json";
+
+/// A releases response as last received.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+struct CachedResponse {
+    /// API URL it answers; a different repository or channel starts over
+    url: String,
+    etag: Option<String>,
+    body: String,
+    /// Unix seconds of the last request GitHub answered
+    checked_at: u64,
+    /// Unix seconds before which GitHub asked not to be called
+    #[serde(default)]
+    wait_until: u64,
+}
+
+impl CachedResponse {
+    /// Whether the body can be used as-is at `now`, without asking again.
+    fn fresh(&self, now: u64, interval_secs: u64) -> bool {
+        now < self.wait_until || now < self.checked_at.saturating_add(interval_secs)
+    }
+}
+
+/// The releases JSON for `config`, from GitHub or the cache.
+pub(super) fn releases_body(
+    client: &reqwest::blocking::Client,
+    config: &UpdateCheckConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let url = config.api_url();
+    let now = unix_now();
+    let cached = load().filter(|c| c.url == url);
+    if let Some(cached) = cached
+        .as_ref()
+        .filter(|c| c.fresh(now, config.check_interval_hours.saturating_mul(3600)))
+    {
+        log::debug!("Update check answered from cache");
+        return Ok(cached.body.clone());
+    }
+
+    let mut request = client.get(&url);
+    if let Some(etag) = cached.as_ref().and_then(|c| c.etag.as_deref()) {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    let response = request.send()?;
+    let status = response.status();
+    let wait_until = rate_limited_until(response.headers(), now);
+
+    if let (StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS, Some(wait_until)) =
+        (status, wait_until)
+    {
+        let wait = wait_until - now;
+        log::debug!("GitHub rate limit reached, next update check in {wait}s");
+        // Keep answering from the old body, if any, until the limit lifts
+        let Some(mut cached) = cached else {
+            return Err(format!(
+                "GitHub rate limit reached, try again in {} minutes",
+                wait.div_ceil(60)
+            )
+            .into());
+        };
+        cached.wait_until = wait_until;
+        save(&cached);
+        return Ok(cached.body);
+    }
+
+    let mut entry = match (status, cached) {
+        (StatusCode::NOT_MODIFIED, Some(cached)) => cached,
+        _ => {
+            let response = response.error_for_status()?;
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            CachedResponse {
+                url,
+                etag,
+                body: response.text()?,
+                ..CachedResponse::default()
+            }
+        }
+    };
+    entry.checked_at = now;
+    entry.wait_until = wait_until.unwrap_or_default();
+    save(&entry);
+    Ok(entry.body)
+}
+
+/// When a response says to stop calling: `Retry-After` seconds from `now`,
+/// or the `X-RateLimit-Reset` time once `X-RateLimit-Remaining` is 0.
+fn rate_limited_until(headers: &HeaderMap, now: u64) -> Option<u64> {
+    let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+    if let Some(secs) = number(RETRY_AFTER.as_str()) {
+        return Some(now.saturating_add(secs));
+    }
+    match number("x-ratelimit-remaining") {
+        Some(0) => number("x-ratelimit-reset").filter(|&reset| reset > now),
+        _ => None,
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    Some(crate::config::cache_dir().ok()?.join(CACHE_FILE))
+}
+
+fn load() -> Option<CachedResponse> {
+    let text = fs::read_to_string(cache_path()?).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn save(entry: &CachedResponse) {
+    let Some(path) = cache_path() else {
+        return;
+    };
+    let result = serde_json::to_string(entry)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::debug!("Could not save {}: {e}", path.display());
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn fresh_within_the_interval_or_the_wait() {
+        let entry = CachedResponse {
+            checked_at: 1_000,
+            ..CachedResponse::default()
+        };
+        assert!(entry.fresh(1_000 + 3599, 3600));
+        assert!(!entry.fresh(1_000 + 3600, 3600));
+        assert!(!entry.fresh(1_001, 0));
+
+        let limited = CachedResponse {
+            wait_until: 5_000,
+            ..entry
+        };
+        assert!(limited.fresh(4_999, 0));
+        assert!(!limited.fresh(5_000, 0));
+    }
+
+    #[test]
+    fn reads_rate_limit_headers() {
+        let now = 1_700_000_000;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000600"));
+        assert_eq!(rate_limited_until(&headers, now), None);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        assert_eq!(rate_limited_until(&headers, now), Some(1_700_000_600));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+        assert_eq!(rate_limited_until(&headers, now), Some(now + 60));
+
+        // A hostile Retry-After can't overflow
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("18446744073709551615"),
+        );
+        assert_eq!(rate_limited_until(&headers, now), Some(u64::MAX));
+    }
+}
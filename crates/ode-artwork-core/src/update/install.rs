@@ -0,0 +1,413 @@
+//! Downloading and applying a release.
+//!
+//! The asset built for this platform is downloaded and checked against the
+//! size and sha256 digest GitHub lists for it, and against the Ed25519
+//! signature published next to it (`<asset>.sig`). On Windows and in a Linux
+//! AppImage the program file is then swapped for the new one, and the next
+//! launch runs it. The macOS DMG is opened for the user to drag into
+//! Applications. Snap, Flatpak and distro packages update through their own
+//! stores, so those only get the releases page.
+
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::crypto::{self, SignatureError};
+use crate::net::{
+    fetch_bytes_cancellable, fetch_bytes_with_progress, CancelToken, FetchError, Timeout, Transfer,
+    CANCELLED,
+};
+
+/// The executable's name inside the Windows zip.
+const WINDOWS_EXE: &str = "ode-artwork-downloader.exe";
+
+#[derive(Debug, Error)]
+pub enum InstallError {
+    #[error("this release has no download for {0}")]
+    NoAsset(String),
+    #[error("this copy can't update itself ({0}); use the releases page")]
+    Unsupported(String),
+    #[error("download failed: {0}")]
+    Download(#[source] FetchError),
+    #[error("{0} isn't signed, refusing to install it")]
+    Unsigned(String),
+    #[error("{name} failed the signature check: {source}")]
+    Signature {
+        name: String,
+        source: SignatureError,
+    },
+    #[error("{name} has no sha256 digest listed, refusing to install it")]
+    NoDigest { name: String },
+    #[error("{name} is {got} bytes, the release says {expected}")]
+    SizeMismatch {
+        name: String,
+        expected: u64,
+        got: u64,
+    },
+    #[error("{name} sha256 mismatch (expected {expected}, got {got})")]
+    HashMismatch {
+        name: String,
+        expected: String,
+        got: String,
+    },
+    #[error("{0} has no ode-artwork-downloader.exe inside")]
+    NotInArchive(String),
+    #[error("zip: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+    #[error("{}", CANCELLED)]
+    Cancelled,
+}
+
+impl From<FetchError> for InstallError {
+    fn from(e: FetchError) -> Self {
+        match e {
+            FetchError::Cancelled => InstallError::Cancelled,
+            e => InstallError::Download(e),
+        }
+    }
+}
+
+/// A file attached to a GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    pub size: u64,
+    /// `sha256:<hex>`, computed by GitHub on upload
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+/// What [`download_and_install`] did with the download.
+#[derive(Debug, Clone)]
+pub enum Installed {
+    /// The program at this path was replaced; relaunching it runs the new
+    /// version
+    Replaced(PathBuf),
+    /// The installer was saved here and opened
+    Opened(PathBuf),
+}
+
+/// The release asset built for this OS and CPU, if there is one.
+pub fn platform_asset(assets: &[ReleaseAsset]) -> Option<&ReleaseAsset> {
+    asset_for(assets, std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn asset_for<'a>(assets: &'a [ReleaseAsset], os: &str, arch: &str) -> Option<&'a ReleaseAsset> {
+    // Names as the release workflow uploads them: OAD-<os>-<arch>-<version>.<ext>
+    let (os, ext) = match os {
+        "windows" => ("windows", ".zip"),
+        "macos" => ("macos", ".dmg"),
+        "linux" => ("linux", ".AppImage"),
+        _ => return None,
+    };
+    let arch = match (os, arch) {
+        ("windows", "x86") => "x86",
+        (_, "x86_64") => "x64",
+        ("macos" | "linux", "aarch64") => "arm64",
+        _ => return None,
+    };
+    let prefix = format!("OAD-{os}-{arch}-");
+    assets
+        .iter()
+        .find(|a| a.name.starts_with(&prefix) && a.name.ends_with(ext))
+}
+
+/// The signature published for `asset`, `<asset>.sig`.
+pub fn signature_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    asset: &ReleaseAsset,
+) -> Option<&'a ReleaseAsset> {
+    let name = format!("{}.sig", asset.name);
+    assets.iter().find(|a| a.name == name)
+}
+
+/// Why this copy can't apply an update, or `None` if it can.
+pub fn unsupported_reason() -> Option<String> {
+    if !crypto::has_update_key() {
+        return Some(SignatureError::NoKey.to_string());
+    }
+    target().err().map(|e| e.to_string())
+}
+
+/// Where an update goes.
+enum Target {
+    /// Swap this file for the new program
+    Replace(PathBuf),
+    /// Save the installer and open it
+    Open,
+}
+
+fn target() -> Result<Target, InstallError> {
+    if cfg!(target_os = "windows") {
+        Ok(Target::Replace(std::env::current_exe()?))
+    } else if cfg!(target_os = "macos") {
+        Ok(Target::Open)
+    } else if cfg!(target_os = "linux") {
+        // Set by the AppImage runtime to the .AppImage file itself
+        match std::env::var_os("APPIMAGE") {
+            Some(path) => Ok(Target::Replace(PathBuf::from(path))),
+            None => Err(InstallError::Unsupported(
+                "not running from an AppImage".into(),
+            )),
+        }
+    } else {
+        Err(InstallError::Unsupported(std::env::consts::OS.into()))
+    }
+}
+
+/// Download `asset`, check it against the release's size and digest and
+/// against its `signature`, and apply it. Nothing is written unless all
+/// three check out. Stops between chunks once `cancel` fires.
+pub fn download_and_install(
+    asset: &ReleaseAsset,
+    signature: Option<&ReleaseAsset>,
+    cancel: &CancelToken,
+    progress: &dyn Fn(Transfer),
+) -> Result<Installed, InstallError> {
+    let target = target()?;
+    let signature = signature.ok_or_else(|| InstallError::Unsigned(asset.name.clone()))?;
+    let bytes = fetch_bytes_with_progress(
+        &asset.browser_download_url,
+        Timeout::Download,
+        cancel,
+        progress,
+    )?;
+    verify(asset, &bytes)?;
+    let signature =
+        fetch_bytes_cancellable(&signature.browser_download_url, Timeout::Request, cancel)?;
+    crypto::verify_update_signature(&bytes, &signature).map_err(|source| {
+        InstallError::Signature {
+            name: asset.name.clone(),
+            source,
+        }
+    })?;
+
+    match target {
+        Target::Replace(exe) => {
+            let program = if asset.name.ends_with(".zip") {
+                exe_from_zip(&asset.name, &bytes)?
+            } else {
+                bytes
+            };
+            replace_program(&exe, &program)?;
+            log::info!("Installed {} over {}", asset.name, exe.display());
+            Ok(Installed::Replaced(exe))
+        }
+        Target::Open => {
+            let path = std::env::temp_dir().join(&asset.name);
+            fs::write(&path, &bytes)?;
+            crate::api::open_folder(&path).map_err(io::Error::other)?;
+            Ok(Installed::Opened(path))
+        }
+    }
+}
+
+/// Check `bytes` are the asset the release lists.
+fn verify(asset: &ReleaseAsset, bytes: &[u8]) -> Result<(), InstallError> {
+    let got = bytes.len() as u64;
+    if got != asset.size {
+        return Err(InstallError::SizeMismatch {
+            name: asset.name.clone(),
+            expected: asset.size,
+            got,
+        });
+    }
+    let Some(expected) = asset
+        .digest
+        .as_deref()
+        .and_then(|d| d.strip_prefix("sha256:"))
+    else {
+        return Err(InstallError::NoDigest {
+            name: asset.name.clone(),
+        });
+    };
+    let got = hex::encode(Sha256::digest(bytes));
+    if !expected.eq_ignore_ascii_case(&got) {
+        return Err(InstallError::HashMismatch {
+            name: asset.name.clone(),
+            expected: expected.to_ascii_lowercase(),
+            got,
+        });
+    }
+    Ok(())
+}
+
+/// The program out of the Windows release zip.
+fn exe_from_zip(name: &str, bytes: &[u8]) -> Result<Vec<u8>, InstallError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut entry = match archive.by_name(WINDOWS_EXE) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(InstallError::NotInArchive(name.to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut program = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut program)?;
+    Ok(program)
+}
+
+/// `<program>.old`, where the replaced program is left until the next
+/// start. Windows won't delete a running executable, but will rename it.
+fn old_path(program: &Path) -> PathBuf {
+    let mut name = program.file_name().unwrap_or_default().to_os_string();
+    name.push(".old");
+    program.with_file_name(name)
+}
+
+/// Swap `program` for `new`, keeping the old file until the next start.
+fn replace_program(program: &Path, new: &[u8]) -> Result<(), InstallError> {
+    let mut staged = program.as_os_str().to_os_string();
+    staged.push(".new");
+    let staged = PathBuf::from(staged);
+    fs::write(&staged, new)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    let old = old_path(program);
+    let _ = fs::remove_file(&old);
+    if let Err(e) = fs::rename(program, &old) {
+        let _ = fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    if let Err(e) = fs::rename(&staged, program) {
+        // Put the running version back so the next launch still works
+        let _ = fs::rename(&old, program);
+        let _ = fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Delete the program an earlier update replaced. Called at startup, once
+/// the old version is no longer running.
+pub fn remove_replaced() {
+    let Ok(Target::Replace(program)) = target() else {
+        return;
+    };
+    let old = old_path(&program);
+    if old.exists() {
+        match fs::remove_file(&old) {
+            Ok(()) => log::debug!("Removed {}", old.display()),
+            Err(e) => log::debug!("Could not remove {}: {e}", old.display()),
+        }
+    }
+}
+
+/// Start `program` (the one [`download_and_install`] replaced) as a new
+/// process. The caller quits afterwards.
+pub fn relaunch(program: &Path) -> io::Result<()> {
+    Command::new(program).spawn().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn asset(name: &str, bytes: &[u8]) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.into(),
+            browser_download_url: format!("https://example.invalid/{name}"),
+            size: bytes.len() as u64,
+            digest: Some(format!("sha256:{}", hex::encode(Sha256::digest(bytes)))),
+        }
+    }
+
+    #[test]
+    fn picks_the_asset_for_the_platform() {
+        let assets: Vec<_> = [
+            "OAD-windows-x86-1.4.0.zip",
+            "OAD-windows-x64-1.4.0.zip",
+            "OAD-macos-arm64-1.4.0.dmg",
+            "OAD-macos-x64-1.4.0.dmg",
+            "OAD-linux-x64-1.4.0.AppImage",
+            "OAD-linux-x64-1.4.0.flatpak",
+            "OAD-linux-arm64-1.4.0.snap",
+        ]
+        .iter()
+        .map(|n| asset(n, b""))
+        .collect();
+        let name = |os, arch| asset_for(&assets, os, arch).map(|a| a.name.as_str());
+        assert_eq!(name("windows", "x86_64"), Some("OAD-windows-x64-1.4.0.zip"));
+        assert_eq!(name("windows", "x86"), Some("OAD-windows-x86-1.4.0.zip"));
+        assert_eq!(name("macos", "aarch64"), Some("OAD-macos-arm64-1.4.0.dmg"));
+        assert_eq!(
+            name("linux", "x86_64"),
+            Some("OAD-linux-x64-1.4.0.AppImage")
+        );
+        assert_eq!(name("linux", "aarch64"), None);
+        assert_eq!(name("freebsd", "x86_64"), None);
+
+        let mut signed = assets.clone();
+        signed.push(asset("OAD-linux-x64-1.4.0.AppImage.sig", b""));
+        let appimage = asset_for(&signed, "linux", "x86_64").unwrap();
+        let sig = signature_asset(&signed, appimage).map(|a| a.name.as_str());
+        assert_eq!(sig, Some("OAD-linux-x64-1.4.0.AppImage.sig"));
+        assert!(signature_asset(&assets, appimage).is_none());
+    }
+
+    #[test]
+    fn verify_checks_size_and_digest() {
+        let good = asset("OAD-linux-x64-1.4.0.AppImage", b"new build");
+        assert!(verify(&good, b"new build").is_ok());
+        assert!(matches!(
+            verify(&good, b"new buil"),
+            Err(InstallError::SizeMismatch { .. })
+        ));
+        assert!(matches!(
+            verify(&good, b"old build"),
+            Err(InstallError::HashMismatch { .. })
+        ));
+        let unsigned = ReleaseAsset {
+            digest: None,
+            ..good
+        };
+        assert!(matches!(
+            verify(&unsigned, b"new build"),
+            Err(InstallError::NoDigest { .. })
+        ));
+    }
+
+    #[test]
+    fn takes_the_exe_out_of_the_zip() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("icon.ico", options).unwrap();
+        zip.write_all(b"icon").unwrap();
+        zip.start_file(WINDOWS_EXE, options).unwrap();
+        zip.write_all(b"MZ program").unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+        assert_eq!(exe_from_zip("x.zip", &bytes).unwrap(), b"MZ program");
+
+        let mut empty = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        empty.start_file("readme.txt", options).unwrap();
+        let bytes = empty.finish().unwrap().into_inner();
+        assert!(matches!(
+            exe_from_zip("x.zip", &bytes),
+            Err(InstallError::NotInArchive(_))
+        ));
+    }
+
+    #[test]
+    fn replaces_the_program_and_keeps_the_old_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("OAD.AppImage");
+        fs::write(&program, b"old").unwrap();
+        replace_program(&program, b"new").unwrap();
+        assert_eq!(fs::read(&program).unwrap(), b"new");
+        assert_eq!(fs::read(old_path(&program)).unwrap(), b"old");
+        assert!(!dir.path().join("OAD.AppImage.new").exists());
+    }
+}
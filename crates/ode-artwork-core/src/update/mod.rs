@@ -0,0 +1,443 @@
+//! Update checking functionality
+
+use crate::config::{config_file_path, UpdateChannel};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+mod cache;
+mod install;
+
+pub use install::{
+    download_and_install, platform_asset, relaunch, remove_replaced, signature_asset,
+    unsupported_reason, InstallError, Installed, ReleaseAsset,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateConfig {
+    pub update_check: UpdateCheckConfig,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpdateCheckConfig {
+    pub enabled: bool,
+    pub repository_url: String,
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    #[serde(default = "crate::config::default_update_interval_hours")]
+    pub check_interval_hours: u64,
+}
+
+impl UpdateCheckConfig {
+    /// Get the API URL for checking releases
+    pub fn api_url(&self) -> String {
+        // Convert https://github.com/owner/repo to https://api.github.com/repos/owner/repo/releases/latest,
+        // or to the release list (pre-releases included) for the beta channel
+        if let Some(path) = self.repository_url.strip_prefix("https://github.com/") {
+            let releases = format!("https://api.github.com/repos/{}/releases", path.trim_end_matches('/'));
+            match self.channel {
+                UpdateChannel::Stable => format!("{releases}/latest"),
+                UpdateChannel::Beta => format!("{releases}?per_page=20"),
+            }
+        } else {
+            // Fallback if URL doesn't match expected format
+            self.repository_url.clone()
+        }
+    }
+
+    /// Get the releases page URL
+    pub fn releases_url(&self) -> String {
+        format!("{}/releases", self.repository_url.trim_end_matches('/'))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    /// Release notes as written on GitHub (Markdown); `null` when empty
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            update_check: UpdateCheckConfig {
+                enabled: true,
+                repository_url: "https://github.com/danifunker/ODE-artwork-downloader".to_string(),
+                channel: UpdateChannel::default(),
+                check_interval_hours: crate::config::default_update_interval_hours(),
+            },
+        }
+    }
+}
+
+impl UpdateConfig {
+    /// Load configuration from the per-user `config.json`.
+    pub fn load() -> Self {
+        let path = match config_file_path() {
+            Ok(p) => p,
+            Err(_) => return Self::default(),
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| Self::default()),
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub releases_url: String,
+    pub is_outdated: bool,
+    /// The release is a pre-release, offered on the beta channel
+    pub prerelease: bool,
+    /// The release's notes, Markdown as written on GitHub. See
+    /// [`note_blocks`] for showing them.
+    pub notes: String,
+    /// The release's download for this platform, for
+    /// [`download_and_install`]
+    pub asset: Option<ReleaseAsset>,
+    /// The signature published for `asset`
+    pub signature: Option<ReleaseAsset>,
+}
+
+impl UpdateInfo {
+    /// Why `asset` can't be installed from here, or `None` if it can.
+    pub fn install_blocker(&self) -> Option<String> {
+        let asset = self.asset.as_ref()?;
+        if self.signature.is_none() {
+            return Some(InstallError::Unsigned(asset.name.clone()).to_string());
+        }
+        unsupported_reason()
+    }
+}
+
+/// Check for updates from GitHub releases. GitHub's answer is reused for
+/// `check_interval_hours` and revalidated with its ETag after that.
+pub fn check_for_updates(config: &UpdateCheckConfig, current_version: &str) -> Result<UpdateInfo, Box<dyn std::error::Error>> {
+    let client = crate::net::client_builder(crate::net::Timeout::Request)
+        .user_agent("ODE-Artwork-Downloader")
+        .build()?;
+    
+    let body = cache::releases_body(&client, config)?;
+    let release = match config.channel {
+        UpdateChannel::Stable => serde_json::from_str::<GithubRelease>(&body)?,
+        UpdateChannel::Beta => newest_release(serde_json::from_str(&body)?).ok_or("no releases published")?,
+    };
+    
+    let asset = platform_asset(&release.assets);
+
+    // Remove 'v' prefix if present
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let current = current_version.trim_start_matches('v');
+    
+    let is_outdated = is_newer(&latest_version, current);
+    
+    Ok(UpdateInfo {
+        current_version: current.to_string(),
+        latest_version,
+        releases_url: config.releases_url(),
+        is_outdated,
+        prerelease: release.prerelease,
+        notes: release.body.unwrap_or_default(),
+        asset: asset.cloned(),
+        signature: asset
+            .and_then(|a| signature_asset(&release.assets, a))
+            .cloned(),
+    })
+}
+
+/// Whether release `latest` comes after `current`, as semver orders them:
+/// `2.1.0-beta.3` is older than `2.1.0` but newer than `2.0.5`. Only a
+/// strictly newer release counts, so switching from a beta back to Stable
+/// doesn't offer an older build. Tags that aren't semver never do.
+fn is_newer(latest: &str, current: &str) -> bool {
+    match (semver::Version::parse(latest), semver::Version::parse(current)) {
+        (Ok(latest), Ok(current)) => latest > current,
+        _ => {
+            log::debug!("Can't compare versions {latest} and {current}");
+            false
+        }
+    }
+}
+
+/// The newest published release in a release list, which GitHub sends
+/// newest first. Drafts only show up with a token, but are skipped anyway.
+fn newest_release(releases: Vec<GithubRelease>) -> Option<GithubRelease> {
+    releases.into_iter().find(|r| !r.draft)
+}
+
+/// One block of release notes, as far as the update window renders
+/// Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteBlock {
+    Heading(String),
+    /// A list item, `depth` 0 for the outermost list
+    Bullet {
+        depth: usize,
+        text: String,
+    },
+    Paragraph(String),
+    /// A line from a fenced code block, verbatim
+    Code(String),
+}
+
+/// Split a release body into blocks. Only the Markdown GitHub's release
+/// notes actually use is understood: `#` headings, `-`/`*`/`+` and numbered
+/// lists, fenced code and paragraphs. Inline markup is reduced to its text
+/// (`**bold**`, `` `code` ``, links) and images and HTML comments are
+/// dropped.
+pub fn note_blocks(body: &str) -> Vec<NoteBlock> {
+    let body = strip_comments(body);
+    let mut blocks = Vec::new();
+    let mut paragraph = String::new();
+    let mut in_code = false;
+
+    fn flush(paragraph: &mut String, blocks: &mut Vec<NoteBlock>) {
+        if !paragraph.is_empty() {
+            blocks.push(NoteBlock::Paragraph(std::mem::take(paragraph)));
+        }
+    }
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut blocks);
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            blocks.push(NoteBlock::Code(line.trim_end().to_string()));
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        if let Some(heading) = heading(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(NoteBlock::Heading(inline_text(heading)));
+        } else if is_rule(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+        } else if let Some(item) = list_item(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            let indent = line.len() - line.trim_start().len();
+            blocks.push(NoteBlock::Bullet {
+                depth: indent / 2,
+                text: inline_text(item),
+            });
+        } else {
+            let text = inline_text(trimmed);
+            if text.is_empty() {
+                continue;
+            }
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(&text);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// `body` without `<!-- ... -->` comments (release templates leave them in).
+fn strip_comments(body: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The text of a `## Heading` line. `#123` on its own isn't one.
+fn heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    if text.len() == line.len() || !(text.is_empty() || text.starts_with(' ')) {
+        return None;
+    }
+    Some(text.trim())
+}
+
+/// A `---` or `***` horizontal rule, which only separates blocks here.
+fn is_rule(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| *c != ' ').collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|m| marks.iter().all(|c| c == m))
+}
+
+/// The text of a list item line, without its marker.
+fn list_item(line: &str) -> Option<&str> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some(item);
+        }
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        return line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "));
+    }
+    None
+}
+
+/// Inline Markdown reduced to plain text: emphasis and code markers go,
+/// links keep their text, images go altogether.
+fn inline_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("![") {
+            if let Some((_, tail)) = link_parts(after) {
+                rest = tail;
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            if let Some((label, tail)) = link_parts(after) {
+                out.push_str(&inline_text(label));
+                rest = tail;
+                continue;
+            }
+        }
+        if matches!(c, '*' | '`') || rest.starts_with("__") || rest.starts_with("~~") {
+            rest = rest.trim_start_matches(c);
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out.trim().to_string()
+}
+
+/// For `label](url)rest` (the opening `[` already gone), the label and rest.
+fn link_parts(after: &str) -> Option<(&str, &str)> {
+    let close = after.find("](")?;
+    let end = after[close..].find(')')? + close;
+    Some((&after[..close], &after[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beta_channel_takes_the_newest_release() {
+        let releases: Vec<GithubRelease> = serde_json::from_str(
+            r#"[
+                {"tag_name": "v2.1.0-beta.1", "html_url": "", "draft": true},
+                {"tag_name": "v2.0.5-beta.2", "html_url": "", "prerelease": true},
+                {"tag_name": "v2.0.4", "html_url": "", "body": null}
+            ]"#,
+        )
+        .unwrap();
+        let newest = newest_release(releases).unwrap();
+        assert_eq!(newest.tag_name, "v2.0.5-beta.2");
+        assert!(newest.prerelease);
+    }
+
+    #[test]
+    fn stable_release_is_older_than_a_newer_beta() {
+        // On 2.1.0-beta.3 and switched back to Stable
+        assert!(!is_newer("2.0.5", "2.1.0-beta.3"));
+        assert!(is_newer("2.1.0", "2.1.0-beta.3"));
+    }
+
+    #[test]
+    fn equal_versions_are_not_an_update() {
+        assert!(!is_newer("2.0.5", "2.0.5"));
+        assert!(!is_newer("2.1.0-beta.3", "2.1.0-beta.3"));
+    }
+
+    #[test]
+    fn newer_prerelease_is_an_update() {
+        assert!(is_newer("2.1.0-beta.4", "2.1.0-beta.3"));
+        assert!(is_newer("2.1.0-beta.1", "2.0.5"));
+        assert!(!is_newer("2.1.0-beta.2", "2.1.0-beta.10"));
+    }
+
+    #[test]
+    fn unparsable_tags_are_not_an_update() {
+        assert!(!is_newer("nightly", "2.0.5"));
+    }
+
+    #[test]
+    fn api_url_follows_the_channel() {
+        let mut config = UpdateConfig::default().update_check;
+        assert_eq!(
+            config.api_url(),
+            "https://api.github.com/repos/danifunker/ODE-artwork-downloader/releases/latest"
+        );
+        config.channel = UpdateChannel::Beta;
+        assert_eq!(
+            config.api_url(),
+            "https://api.github.com/repos/danifunker/ODE-artwork-downloader/releases?per_page=20"
+        );
+    }
+
+    #[test]
+    fn reads_a_typical_release_body() {
+        let body = "<!-- Release notes generated using configuration in .github/release.yml -->\r\n\
+                    ## What's Changed\r\n\
+                    * Fix **CHD** hashing by @someone in [#123](https://github.com/o/r/pull/123)\r\n  \
+                    - Nested `detail`\r\n\
+                    \r\n\
+                    Thanks to everyone\r\nwho tested.\r\n\
+                    \r\n\
+                    **Full Changelog**: https://github.com/o/r/compare/v1...v2";
+        assert_eq!(
+            note_blocks(body),
+            vec![
+                NoteBlock::Heading("What's Changed".into()),
+                NoteBlock::Bullet {
+                    depth: 0,
+                    text: "Fix CHD hashing by @someone in #123".into()
+                },
+                NoteBlock::Bullet {
+                    depth: 1,
+                    text: "Nested detail".into()
+                },
+                NoteBlock::Paragraph("Thanks to everyone who tested.".into()),
+                NoteBlock::Paragraph(
+                    "Full Changelog: https://github.com/o/r/compare/v1...v2".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_code_and_numbered_lists() {
+        let body = "1. First\n2) Second\n```\n  ode --batch *.iso\n```\n![shot](https://x/y.png)";
+        assert_eq!(
+            note_blocks(body),
+            vec![
+                NoteBlock::Bullet {
+                    depth: 0,
+                    text: "First".into()
+                },
+                NoteBlock::Bullet {
+                    depth: 0,
+                    text: "Second".into()
+                },
+                NoteBlock::Code("  ode --batch *.iso".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! A USBODE's SD card mounted on this computer. With
+//! `export.copy_to_sd_card` on, each saved cover is also written onto the
+//! card, next to the card's copy of the disc, so it's in place the next time
+//! the card goes back into the device.
+//!
+//! A USBODE card is a Raspberry Pi boot volume (`config.txt` or a kernel
+//! image at its root) with an [`IMAGES_DIR`] folder holding the disc
+//! images. The card's copy of a disc is found by file name like
+//! [`super::device_image_for`], or failing that by size and contents, for
+//! images renamed on the card.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::disc::scan::find_disc_images;
+
+use super::{artwork_path, device_image_for};
+
+/// Folder at the card's root the disc images live in.
+pub const IMAGES_DIR: &str = "images";
+
+/// Files at the root of a Raspberry Pi boot volume, one of which marks a
+/// card as the device's.
+const BOOT_FILES: [&str; 4] = ["config.txt", "kernel.img", "kernel7.img", "kernel8.img"];
+
+/// Bytes compared at each end of two same-sized images.
+const COMPARE_BYTES: usize = 64 * 1024;
+
+/// The USBODE cards mounted right now.
+pub fn find_cards() -> Vec<PathBuf> {
+    mount_points()
+        .into_iter()
+        .filter(|root| is_usbode_card(root))
+        .collect()
+}
+
+/// Whether the volume mounted at `root` looks like a USBODE card.
+pub fn is_usbode_card(root: &Path) -> bool {
+    root.join(IMAGES_DIR).is_dir() && BOOT_FILES.iter().any(|f| root.join(f).is_file())
+}
+
+/// Where removable volumes get mounted: drive letters on Windows,
+/// `/Volumes` on macOS, `/media`, `/run/media` (one folder per user) and
+/// `/mnt` elsewhere.
+fn mount_points() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        return (b'D'..=b'Z')
+            .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+            .filter(|root| root.is_dir())
+            .collect();
+    }
+    let children = |dir: &Path| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    if cfg!(target_os = "macos") {
+        return children(Path::new("/Volumes"));
+    }
+    let mut roots = children(Path::new("/mnt"));
+    for base in ["/media", "/run/media"] {
+        for dir in children(Path::new(base)) {
+            roots.extend(children(&dir));
+            roots.push(dir);
+        }
+    }
+    roots
+}
+
+/// The card's copy of the disc at `disc_path`, if it has one.
+pub fn card_image_for(card: &Path, disc_path: &Path) -> Option<PathBuf> {
+    let images_dir = card.join(IMAGES_DIR);
+    let images = find_disc_images(&images_dir);
+    let relative: Vec<String> = images
+        .iter()
+        .filter_map(|p| p.strip_prefix(&images_dir).ok())
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+    if let Some(name) = device_image_for(&relative, disc_path) {
+        return Some(images_dir.join(name));
+    }
+    let size = fs::metadata(disc_path).ok()?.len();
+    images
+        .into_iter()
+        .filter(|image| fs::metadata(image).is_ok_and(|m| m.len() == size))
+        .find(|image| same_contents(disc_path, image, size).unwrap_or(false))
+}
+
+/// Whether two files of `size` bytes start and end the same. Enough to tell
+/// disc images apart without reading gigabytes off an SD card.
+fn same_contents(a: &Path, b: &Path, size: u64) -> std::io::Result<bool> {
+    let ends = |path: &Path| -> std::io::Result<Vec<u8>> {
+        let mut file = File::open(path)?;
+        let mut data = Vec::new();
+        file.by_ref()
+            .take(COMPARE_BYTES as u64)
+            .read_to_end(&mut data)?;
+        if size > COMPARE_BYTES as u64 * 2 {
+            file.seek(SeekFrom::End(-(COMPARE_BYTES as i64)))?;
+        }
+        file.read_to_end(&mut data)?;
+        Ok(data)
+    };
+    Ok(ends(a)? == ends(b)?)
+}
+
+/// Where the cover of the disc at `disc_path` goes on the first mounted
+/// USBODE card that has the disc: beside its image. `None` when no card is
+/// mounted or none has the disc.
+pub fn card_target(disc_path: &Path) -> Option<PathBuf> {
+    for card in find_cards() {
+        let Some(image) = card_image_for(&card, disc_path) else {
+            log::debug!(
+                "{} isn't on the card at {}",
+                disc_path.display(),
+                card.display()
+            );
+            continue;
+        };
+        let name = image.file_name().unwrap_or_default().to_string_lossy();
+        return Some(image.with_file_name(artwork_path(&name)));
+    }
+    None
+}
+
+/// Copy the cover at `artwork` to `target` on a card (see `card_target`).
+pub fn copy_to_card(artwork: &Path, target: &Path) -> Result<(), String> {
+    fs::copy(artwork, target)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy the cover to {}: {}", target.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ode_card_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn card_needs_images_and_boot_files() {
+        let card = temp_dir("detect");
+        assert!(!is_usbode_card(&card));
+        fs::create_dir(card.join(IMAGES_DIR)).unwrap();
+        assert!(!is_usbode_card(&card));
+        fs::write(card.join("config.txt"), "").unwrap();
+        assert!(is_usbode_card(&card));
+        let _ = fs::remove_dir_all(&card);
+    }
+
+    #[test]
+    fn finds_the_disc_by_name_or_contents() {
+        let root = temp_dir("match");
+        let card = root.join("card");
+        let local = root.join("local");
+        fs::create_dir_all(card.join(IMAGES_DIR).join("PS1")).unwrap();
+        fs::create_dir_all(&local).unwrap();
+
+        fs::write(
+            card.join(IMAGES_DIR).join("PS1").join("Myst (USA).iso"),
+            b"myst",
+        )
+        .unwrap();
+        fs::write(local.join("myst (usa).iso"), b"myst").unwrap();
+        assert_eq!(
+            card_image_for(&card, &local.join("myst (usa).iso")),
+            Some(card.join(IMAGES_DIR).join("PS1/Myst (USA).iso"))
+        );
+
+        fs::write(card.join(IMAGES_DIR).join("renamed.iso"), b"ridge racer").unwrap();
+        fs::write(local.join("Ridge Racer.iso"), b"ridge racer").unwrap();
+        assert_eq!(
+            card_image_for(&card, &local.join("Ridge Racer.iso")),
+            Some(card.join(IMAGES_DIR).join("renamed.iso"))
+        );
+
+        fs::write(local.join("Doom.iso"), b"doom").unwrap();
+        assert_eq!(card_image_for(&card, &local.join("Doom.iso")), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
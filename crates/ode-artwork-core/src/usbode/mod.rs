@@ -0,0 +1,281 @@
+//! Pushing artwork straight to a USBODE over the network, instead of
+//! copying it to the SD card by hand.
+//!
+//! The device's web interface lists the disc images on its SD card
+//! ([`LIST_PATH`]) and takes file uploads ([`UPLOAD_PATH`]). A saved cover
+//! is sent as `<image stem>.jpg` next to the device's copy of the disc,
+//! found by matching file names, which is where the firmware looks for it.
+//!
+//! [`card`] does the same for the device's SD card mounted on this computer.
+
+use std::path::Path;
+
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+
+use crate::net::{client_builder, send_with_retry, Timeout};
+
+pub mod card;
+
+/// Lists the images on the SD card, as JSON.
+pub const LIST_PATH: &str = "/api/list";
+
+/// Takes a file as the request body, saved under the `path` query
+/// parameter (relative to the SD card's image folder).
+pub const UPLOAD_PATH: &str = "/api/upload";
+
+/// A USBODE on the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    /// `http://host[:port]`, no trailing slash
+    base_url: String,
+}
+
+impl Device {
+    /// The device at `address`: a host name or IP (`usbode.local`,
+    /// `192.168.1.50:8080`) or a full `http://` URL.
+    pub fn new(address: &str) -> Result<Self, String> {
+        let address = address.trim().trim_end_matches('/');
+        if address.is_empty() {
+            return Err("No USBODE address set".to_string());
+        }
+        if address.chars().any(char::is_whitespace) {
+            return Err(format!("USBODE address {address} has spaces in it"));
+        }
+        let base_url = match address.split_once("://") {
+            Some(("http" | "https", rest)) if !rest.is_empty() => address.to_string(),
+            Some((scheme, _)) => return Err(format!("USBODE address must use http, not {scheme}")),
+            None => format!("http://{address}"),
+        };
+        Ok(Self { base_url })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The disc images on the device's SD card, as paths relative to its
+    /// image folder.
+    pub fn list_images(&self) -> Result<Vec<String>, String> {
+        let client = client_builder(Timeout::Request)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        let url = format!("{}{}", self.base_url, LIST_PATH);
+        let response = send_with_retry(|| client.get(&url).send())
+            .map_err(|e| format!("Can't reach the USBODE at {}: {}", self.base_url, e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "USBODE image list failed: HTTP {}",
+                response.status().as_u16()
+            ));
+        }
+        let body = response
+            .text()
+            .map_err(|e| format!("Failed to read the USBODE image list: {}", e))?;
+        parse_image_list(&body)
+    }
+
+    /// Upload `data` as `remote_path` on the SD card, replacing any file
+    /// already there.
+    pub fn upload(&self, remote_path: &str, data: Vec<u8>) -> Result<(), String> {
+        let client = client_builder(Timeout::Download)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        let url = format!(
+            "{}{}?path={}",
+            self.base_url,
+            UPLOAD_PATH,
+            urlencoding::encode(remote_path)
+        );
+        let response = send_with_retry(|| {
+            client
+                .post(&url)
+                .header(CONTENT_TYPE, "image/jpeg")
+                .body(data.clone())
+                .send()
+        })
+        .map_err(|e| format!("Can't reach the USBODE at {}: {}", self.base_url, e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "USBODE refused {}: HTTP {}",
+                remote_path,
+                response.status().as_u16()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Send the cover at `artwork` to the device at `address`, next to its copy
+/// of the disc at `disc_path`. Returns where it went on the device.
+pub fn push_artwork(address: &str, disc_path: &Path, artwork: &Path) -> Result<String, String> {
+    let device = Device::new(address)?;
+    let images = device.list_images()?;
+    let image = device_image_for(&images, disc_path).ok_or_else(|| {
+        format!(
+            "{} isn't on the USBODE at {}",
+            disc_path.file_name().unwrap_or_default().to_string_lossy(),
+            device.base_url()
+        )
+    })?;
+    let remote_path = artwork_path(image);
+    let data = std::fs::read(artwork)
+        .map_err(|e| format!("Failed to read {}: {}", artwork.display(), e))?;
+    device.upload(&remote_path, data)?;
+    Ok(remote_path)
+}
+
+/// The listed image that's the same disc as `disc_path`: the same file
+/// name, else the same name without the extension (a CHD on the device for
+/// a BIN/CUE here). Case doesn't matter, as on the SD card's FAT/exFAT.
+pub fn device_image_for<'a>(images: &'a [String], disc_path: &Path) -> Option<&'a str> {
+    let name = disc_path.file_name()?.to_str()?.to_lowercase();
+    let stem = disc_path.file_stem()?.to_str()?.to_lowercase();
+    let file_name = |image: &str| image.rsplit('/').next().unwrap_or(image).to_lowercase();
+    images
+        .iter()
+        .find(|image| file_name(image) == name)
+        .or_else(|| {
+            images
+                .iter()
+                .find(|image| file_stem(&file_name(image)) == stem)
+        })
+        .map(String::as_str)
+}
+
+/// Where the cover for the device image at `image` goes: the same path with
+/// a `.jpg` extension.
+pub fn artwork_path(image: &str) -> String {
+    let (dir, name) = match image.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, image),
+    };
+    let jpg = format!("{}.jpg", file_stem(name));
+    match dir {
+        Some(dir) => format!("{dir}/{jpg}"),
+        None => jpg,
+    }
+}
+
+fn file_stem(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => name,
+    }
+}
+
+/// The `/api/list` reply: a list of names, or of objects with a `name` (or
+/// `path`), either bare or under `images`/`files`.
+pub fn parse_image_list(body: &str) -> Result<Vec<String>, String> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Name(String),
+        Object {
+            #[serde(alias = "path", alias = "file")]
+            name: String,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Listing {
+        Bare(Vec<Entry>),
+        Wrapped {
+            #[serde(alias = "files")]
+            images: Vec<Entry>,
+        },
+    }
+
+    let listing: Listing =
+        serde_json::from_str(body).map_err(|e| format!("Unexpected USBODE image list: {}", e))?;
+    let entries = match listing {
+        Listing::Bare(entries) | Listing::Wrapped { images: entries } => entries,
+    };
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            Entry::Name(name) | Entry::Object { name } => {
+                name.trim_start_matches('/').replace('\\', "/")
+            }
+        })
+        .filter(|name| !name.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_address_forms() {
+        assert_eq!(
+            Device::new("usbode.local").unwrap().base_url(),
+            "http://usbode.local"
+        );
+        assert_eq!(
+            Device::new(" http://192.168.1.50:8080/ ")
+                .unwrap()
+                .base_url(),
+            "http://192.168.1.50:8080"
+        );
+        assert!(Device::new("").is_err());
+        assert!(Device::new("ftp://usbode.local").is_err());
+        assert!(Device::new("usb ode").is_err());
+    }
+
+    #[test]
+    fn image_list_shapes() {
+        let expected = vec![
+            "Myst (USA).chd".to_string(),
+            "PS1/Ridge Racer.cue".to_string(),
+        ];
+        assert_eq!(
+            parse_image_list(r#"["Myst (USA).chd", "/PS1/Ridge Racer.cue"]"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_image_list(
+                r#"{"images": [{"name": "Myst (USA).chd", "size": 1}, {"name": "PS1\\Ridge Racer.cue"}]}"#
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_image_list(
+                r#"{"files": [{"path": "Myst (USA).chd"}, {"path": "PS1/Ridge Racer.cue"}]}"#
+            )
+            .unwrap(),
+            expected
+        );
+        assert!(parse_image_list("<html></html>").is_err());
+    }
+
+    #[test]
+    fn matches_by_name_then_stem() {
+        let images = vec![
+            "PS1/Ridge Racer (USA).chd".to_string(),
+            "Myst (USA).cue".to_string(),
+            "myst (usa).iso".to_string(),
+        ];
+        assert_eq!(
+            device_image_for(&images, Path::new("/discs/Ridge Racer (USA).cue")),
+            Some("PS1/Ridge Racer (USA).chd")
+        );
+        assert_eq!(
+            device_image_for(&images, Path::new("/discs/MYST (USA).iso")),
+            Some("myst (usa).iso")
+        );
+        assert_eq!(
+            device_image_for(&images, Path::new("/discs/Doom.iso")),
+            None
+        );
+    }
+
+    #[test]
+    fn artwork_goes_next_to_the_image() {
+        assert_eq!(artwork_path("Myst (USA).chd"), "Myst (USA).jpg");
+        assert_eq!(artwork_path("PS1/Ridge Racer.cue"), "PS1/Ridge Racer.jpg");
+        assert_eq!(artwork_path("Disc.v1.1.iso"), "Disc.v1.1.jpg");
+    }
+}
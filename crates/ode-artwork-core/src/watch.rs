@@ -0,0 +1,115 @@
+//! Watch mode: the folders in the `watch` config section are watched for
+//! new disc images, which then go through the processing queue on their own
+//! (in the app) or the batch pipeline (`ode-artwork watch`).
+//!
+//! A filesystem event only marks its folder as changed. Once the folder has
+//! been quiet for [`SETTLE_TIME`] (a copy still in progress keeps it busy),
+//! it's scanned again with [`find_disc_images`], and images that weren't
+//! there before are new. Discs already in a folder when watching starts are
+//! left alone.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::disc::scan::find_disc_images;
+
+/// How long a folder has to go without changes before it's scanned.
+pub const SETTLE_TIME: Duration = Duration::from_secs(3);
+
+struct WatchedFolder {
+    root: PathBuf,
+    /// Disc images found by the last scan
+    known: HashSet<PathBuf>,
+    /// Last change not scanned yet
+    changed_at: Option<Instant>,
+}
+
+/// Folders being watched.
+pub struct FolderWatch {
+    /// Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    /// Roots that saw a change
+    changes: Receiver<PathBuf>,
+    folders: Vec<WatchedFolder>,
+}
+
+impl FolderWatch {
+    /// Watch `roots`, taking note of the disc images already in them.
+    /// `on_change` is called on the watcher's thread for each filesystem
+    /// event (the app wakes its window [`SETTLE_TIME`] later), and
+    /// [`Self::poll`] picks up what settled.
+    pub fn start_with(
+        roots: &[PathBuf],
+        on_change: impl Fn() + Send + 'static,
+    ) -> Result<Self, String> {
+        let (tx, changes) = mpsc::channel();
+        let watched = roots.to_vec();
+        let on_event = move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            for path in &event.paths {
+                if let Some(root) = watched.iter().find(|root| path.starts_with(root)) {
+                    let _ = tx.send(root.clone());
+                }
+            }
+            on_change();
+        };
+        let mut watcher = notify::recommended_watcher(on_event)
+            .map_err(|e| format!("Failed to start watching folders: {}", e))?;
+
+        let mut folders = Vec::new();
+        for root in roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch {}: {}", root.display(), e))?;
+            folders.push(WatchedFolder {
+                root: root.clone(),
+                known: find_disc_images(root).into_iter().collect(),
+                changed_at: None,
+            });
+        }
+        Ok(Self {
+            _watcher: watcher,
+            changes,
+            folders,
+        })
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = &Path> {
+        self.folders.iter().map(|f| f.root.as_path())
+    }
+
+    /// Disc images that turned up since the last call, from folders that
+    /// have settled. Sorted by path.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        while let Ok(root) = self.changes.try_recv() {
+            for folder in self.folders.iter_mut().filter(|f| f.root == root) {
+                folder.changed_at = Some(now);
+            }
+        }
+        let mut new = Vec::new();
+        for folder in &mut self.folders {
+            if !folder
+                .changed_at
+                .is_some_and(|at| now.duration_since(at) >= SETTLE_TIME)
+            {
+                continue;
+            }
+            folder.changed_at = None;
+            let found: HashSet<PathBuf> = find_disc_images(&folder.root).into_iter().collect();
+            new.extend(found.difference(&folder.known).cloned());
+            folder.known = found;
+        }
+        new.sort();
+        new
+    }
+}
@@ -2,11 +2,11 @@
 //!
 //! This test hits the network and writes to a temp directory; it's `#[ignore]`
 //! so it doesn't run by default. Run with:
-//!   `cargo test --test db_update_e2e -- --ignored --nocapture`
+//!   `cargo test -p ode-artwork-core --test db_update_e2e -- --ignored --nocapture`
 
 use std::env;
 
-use ode_artwork_downloader::db::{lookup, DatabaseManager, UpdateOutcome};
+use ode_artwork_core::db::{lookup, DatabaseManager, UpdateOutcome};
 
 #[test]
 #[ignore]
@@ -1,20 +1,44 @@
 //! Main application state and UI implementation
 
 use eframe::egui;
+use std::collections::{HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
 use std::thread;
 
 use crate::api::{open_in_browser, ArtworkSearchQuery, SearchConfig, ContentType};
-use crate::disc::{supported_extensions, parse_filename, ConfidenceLevel, DiscInfo, DiscReader, DiscFormat, FilesystemType};
+use crate::config::ThemeMode;
+use crate::disc::rip::AudioFormat;
+use crate::disc::{chd_artwork, supported_extensions, parse_filename, ConfidenceLevel, DiscInfo, DiscReader, DiscFormat, FilesystemType};
 use crate::export::{
-    export_artwork, export_artwork_from_url_with_disc, export_artwork_from_url_with_label,
-    generate_output_path, ExportResult, ExportSettings,
+    claim_named_output_path, decode_preview, export_artwork, export_artwork_from_url_with_disc,
+    export_artwork_from_url_with_label, export_placeholder, fix_artwork_batch, format_disc_label,
+    format_hex_color, generate_named_output_path, parse_hex_color, read_provenance, render_output,
+    render_saved_output, scan_for_fixes, undo_export, validate_artwork_with_size,
+    validate_name_template, ArtTemplate, ArtworkRole, BadgePosition, BadgeStyle, DiscSummary,
+    ExportProgress, ExportResult, ExportSettings, FitMode, FixCandidate, FixSummary, ImageEdits, OutputFormat,
+    WrittenFile,
+    OutputNaming, PadFill, PreviewImage, Provenance, UpscaleMode, ADJUST_MAX, ADJUST_MIN, BACKUP_DIR_NAME,
+    DEFAULT_NAME_TEMPLATE, MAX_SHARPEN, MIN_JPEG_QUALITY,
 };
-use crate::search::ImageResult;
-use crate::update::{UpdateConfig, UpdateInfo};
+use crate::i18n::tr;
+use crate::net::{CancelToken, Timeout, Transfer};
+use crate::search::{ImageResult, ResultSource};
+use crate::update::{note_blocks, Installed, NoteBlock, ReleaseAsset, UpdateConfig, UpdateInfo};
 
 use super::browse_view::BrowseView;
+use super::events::{AppEvent, EventBus, Job};
+use super::folder_scan::{FolderScan, SortColumn};
+use super::progress::{export_bar, transfer_bar, ProgressFeed};
+use super::palette::{Command, CommandPalette, PALETTE_SHORTCUT};
+use super::queue::{Existing, ProcessingQueue, QueueJob, Stage};
+use super::recent::RecentList;
+use super::settings::SettingsDialog;
+use super::thumbs::Thumbnails;
+use super::toasts::Toasts;
+use super::tray::{Tray, TrayAction};
+use crate::watch::{FolderWatch, SETTLE_TIME};
 
 /// Main application state
 pub struct App {
@@ -22,8 +46,12 @@ pub struct App {
     selected_path: Option<PathBuf>,
     /// Information about the selected disc
     disc_info: Option<Result<DiscInfo, String>>,
-    /// Status/log messages
-    log_messages: Vec<LogMessage>,
+    /// Whether the selected disc is a CHD with a cover stored inside it
+    chd_artwork_found: bool,
+    /// Status/log messages, oldest first, at most `LOG_CAPACITY`
+    log_messages: VecDeque<LogMessage>,
+    /// Levels and text the Log window shows
+    log_filter: LogFilter,
     /// Dropped files (for drag-and-drop)
     dropped_files: Vec<egui::DroppedFile>,
     /// Search results from image search
@@ -32,18 +60,50 @@ pub struct App {
     search_receiver: Option<Receiver<Result<Vec<ImageResult>, String>>>,
     /// Currently selected image index
     selected_image_index: Option<usize>,
+    /// Scroll the results list to the selected result on the next frame
+    /// (set when the arrow keys move the selection)
+    scroll_to_selected_result: bool,
+    /// Sources unticked above the results list; their results are hidden
+    hidden_sources: HashSet<ResultSource>,
+    /// Preview URL the arrow keys landed on and when to load it, so holding a
+    /// key down doesn't start a download per result
+    pending_preview: Option<(String, std::time::Instant)>,
     /// Is a search in progress?
     search_in_progress: bool,
     /// Preview image texture
     preview_texture: Option<egui::TextureHandle>,
-    /// Receiver for preview image data
-    preview_receiver: Option<Receiver<Result<Vec<u8>, String>>>,
+    /// Decoded preview (downscaled to `PREVIEW_MAX_EDGE`), kept so edits can
+    /// re-render the texture without re-downloading
+    preview_image: Option<image::DynamicImage>,
+    /// Dimensions of the full-size preview source, before downscaling
+    preview_source_size: (u32, u32),
+    /// Rotation/flip and colour adjustments for the current preview; applied
+    /// on export
+    preview_edits: ImageEdits,
+    /// Show the rendered output (square, resized, sharpened) instead of the
+    /// source image in the preview pane
+    preview_show_output: bool,
+    /// With `preview_show_output`, render without sharpening ("Before")
+    preview_unsharpened: bool,
+    /// With `preview_show_output`, draw the output at 2x instead of 1:1
+    preview_magnified: bool,
+    /// Source for output renders (`PreviewImage::render_source`), kept when
+    /// `preview_image` had to be downscaled. Bounded by the raster size, so
+    /// renders never need the full-size pixels
+    preview_render_source: Option<Arc<image::DynamicImage>>,
+    /// Encoded size of the output shown by `preview_show_output`, or why it
+    /// couldn't be encoded
+    preview_output_size: Option<Result<usize, String>>,
+    /// Receiver for the downloaded and decoded preview
+    preview_receiver: Option<Receiver<Result<PreviewImage, String>>>,
+    /// Bytes downloaded so far for the preview
+    preview_progress: Option<ProgressFeed<Transfer>>,
     /// Is preview loading?
     preview_loading: bool,
     /// URL of the currently loaded preview (to avoid reloading)
     preview_url: Option<String>,
-    /// Receiver for export results
-    export_receiver: Option<Receiver<Result<ExportResult, String>>>,
+    /// Download and pipeline progress of the running export
+    export_progress: Option<ProgressFeed<ExportProgress>>,
     /// Is export in progress?
     export_in_progress: bool,
     /// Editable search query string
@@ -54,22 +114,60 @@ pub struct App {
     show_log_window: bool,
     /// Whether to show the log settings dialog
     show_log_settings: bool,
+    /// Settings window draft, while it's open
+    settings_dialog: Option<SettingsDialog>,
+    /// Ctrl+Shift+P command palette, while it's open
+    command_palette: Option<CommandPalette>,
+    /// Failed searches, previews and saves, popped up in the corner
+    toasts: Toasts,
     /// Whether to show the artwork search results / preview window
     show_search_window: bool,
     /// Current UI log level (one of error/warn/info/debug/trace/off)
     log_level: String,
+    /// Current log file level, same values
+    log_file_level: String,
+    /// Current theme and accent color
+    appearance: crate::config::AppearanceConfig,
     /// Last preview error message
     preview_error: Option<String>,
+    /// What the server said about a previewed URL: content type, size and
+    /// server, or why it couldn't be asked
+    candidate_info: Option<(String, Result<crate::net::RemoteInfo, String>)>,
+    /// Receiver for the HEAD request in flight, with its URL
+    candidate_info_receiver: Option<Receiver<(String, Result<crate::net::RemoteInfo, String>)>>,
+    /// Candidate pinned for side-by-side comparison with the selected one
+    compare_pin: Option<PinnedCandidate>,
+    /// Rendered output of the selected candidate, for the compare window
+    compare_output: Option<egui::TextureHandle>,
+    /// Artwork already saved for the disc under `export_role`, shown next to
+    /// the preview
+    existing_art: Option<ExistingArt>,
+    /// Reload `existing_art` before the search window is next drawn
+    existing_art_stale: bool,
+    /// Saving would overwrite `existing_art`; waiting for Replace / Keep
+    confirm_replace: bool,
+    /// Output path typed or browsed to for the next save, in place of the
+    /// one the filename template gives
+    output_path_edit: Option<String>,
+    /// Result chosen by Auto-pick, saved as soon as its preview loads
+    auto_save_url: Option<String>,
     /// Update configuration
     update_config: UpdateConfig,
-    /// Update information receiver
-    update_receiver: Option<Receiver<Result<UpdateInfo, String>>>,
+    /// Results of the app-wide background jobs: update check, exports,
+    /// rips, imports and the like
+    events: EventBus,
     /// Latest update info
     update_info: Option<UpdateInfo>,
     /// Whether to show update notification
     show_update_notification: bool,
     /// Whether update check has been performed
     update_check_done: bool,
+    /// Bytes of the update downloaded so far, while it downloads
+    update_progress: Option<ProgressFeed<Transfer>>,
+    /// Cancellation token for the update download, if any
+    update_cancel: Option<CancelToken>,
+    /// The program an update replaced, until the user restarts or says later
+    update_relaunch: Option<PathBuf>,
     /// Search configuration
     search_config: SearchConfig,
     /// Receiver for log messages from the global logger
@@ -78,22 +176,47 @@ pub struct App {
     browse_view: BrowseView,
     /// Whether to show the browse window
     show_browse_window: bool,
-    /// Receiver for user agent capture result
-    user_agent_receiver: Option<Receiver<Result<String, String>>>,
     /// Whether user agent capture is in progress
     user_agent_capture_in_progress: bool,
-    /// Receiver for the background redump DB update job
-    db_update_receiver: Option<Receiver<Result<crate::db::UpdateOutcome, String>>>,
     /// Whether the DB update has finished (success or failure)
     db_update_done: bool,
     /// Live progress of the current hashing job (shared with the worker thread)
     hash_progress: Option<std::sync::Arc<std::sync::Mutex<crate::disc::hasher::HashProgress>>>,
     /// Receiver for the hashing worker's final result
     hash_receiver: Option<Receiver<Result<crate::disc::hasher::TrackHashes, String>>>,
+    /// Data-track SHA-1 of the last disc hashed, keyed by its path, for
+    /// the provenance comment in exported covers
+    disc_sha1: Option<(PathBuf, String)>,
     /// Rolling rate/ETA estimator for the active hashing job
     hash_rate_tracker: super::progress::RateTracker,
     /// Active bulk-processing queue, or `None` when not in bulk mode.
     bulk_queue: Option<super::bulk::BulkQueue>,
+    /// Discs dropped several at a time, processed in the background
+    processing_queue: ProcessingQueue,
+    /// Whether to show the processing queue window
+    show_queue_window: bool,
+    /// Watch mode's folder watcher, while watching
+    folder_watch: Option<FolderWatch>,
+    /// Tray icon, once watch mode has been started
+    tray: Option<Tray>,
+    /// Discs the watcher queued that haven't finished yet
+    watch_queued: HashSet<PathBuf>,
+    /// How this batch's watched discs went: saved, failed, waiting on a
+    /// review or a replace-or-keep answer
+    watch_tally: (usize, usize, usize),
+    /// Whether to show the batch review window
+    show_review_window: bool,
+    /// Result thumbnails for the batch review window
+    review_thumbs: Thumbnails,
+    /// "Scan Folder..." results, shown in their own window while `Some`
+    folder_scan: Option<FolderScan>,
+    /// Discs and folders for the "Recent" menu
+    recent: RecentList,
+    /// Open disc tabs. The active one's state is in the fields above, so
+    /// its slot here is an empty placeholder.
+    tabs: Vec<DiscTab>,
+    /// Index of the tab shown in the main view
+    active_tab: usize,
     /// In-flight loader dialog state for "Open Bulk Job…".
     bulk_loader: Option<BulkLoaderDialog>,
     /// Cursor index of the last bulk item we loaded into the central panel.
@@ -109,6 +232,11 @@ pub struct App {
     /// URL of the artwork the user is currently saving. Read by poll_export
     /// on success so a bulk-mode save can record the chosen URL.
     pending_export_url: Option<String>,
+    /// Artwork role picked in the preview pane ("Save as").
+    export_role: ArtworkRole,
+    /// Role of the in-flight export. Only front-cover saves close the
+    /// search window, propagate to siblings and advance the bulk queue.
+    pending_export_role: ArtworkRole,
     /// Bottom Y coordinate of the most recently rendered bulk banner.
     /// Used to pick the default position of the Artwork Search window
     /// so it opens directly below the controls in bulk mode.
@@ -128,6 +256,137 @@ pub struct App {
     audio_modal_open: bool,
     /// Last playback failure, surfaced in the modal until the next play starts.
     audio_error: Option<String>,
+    /// Album matched to an audio CD, listed under its TOC in Disc Information
+    tracklist: Option<crate::api::Tracklist>,
+    /// Receiver for an in-flight tracklist lookup
+    tracklist_receiver: Option<Receiver<Result<Option<crate::api::Tracklist>, String>>>,
+    /// Album and track names from a BIN/CUE disc's cue sheet
+    cd_text: Option<crate::disc::CdText>,
+    /// The disc's own icon (autorun.inf or .VolumeIcon.icns), next to its label
+    volume_icon: Option<egui::TextureHandle>,
+    /// Receiver for the volume icon being read off the disc
+    volume_icon_receiver: Option<Receiver<Option<egui::ColorImage>>>,
+    /// Directories and files on the disc, for the volume descriptor details
+    entry_counts: Option<crate::disc::EntryCounts>,
+    /// Receiver for the directory and file count in progress
+    entry_counts_receiver: Option<Receiver<Option<crate::disc::EntryCounts>>>,
+    /// Container "Extract Audio..." rips to
+    audio_rip_format: AudioFormat,
+    /// Number of images in the local cover-pack index (shown in Search Settings)
+    pack_entry_count: usize,
+    /// Cancellation token for the in-flight search, if any
+    search_cancel: Option<CancelToken>,
+    /// Cancellation token for the in-flight preview load, if any
+    preview_cancel: Option<CancelToken>,
+    /// Cancellation token for the in-flight export, if any
+    export_cancel: Option<CancelToken>,
+    /// Output settings (size, extra sizes), edited in Search Settings
+    export_config: crate::config::ExportConfig,
+    /// Text buffer for the comma-separated "Also save" sizes field
+    export_extra_sizes_text: String,
+    /// Dry-run report awaiting confirmation
+    fix_dialog: Option<FixArtworkDialog>,
+    /// This session's saves, oldest first, each listing the files it wrote
+    /// (main cover first) with where the ones they replaced were moved.
+    /// "Undo Last Export" steps back through them.
+    export_history: Vec<Vec<WrittenFile>>,
+    /// System clipboard, opened on first use. Kept open because on X11 a
+    /// copied image is only served while the handle lives.
+    clipboard: Option<arboard::Clipboard>,
+}
+
+/// Dry-run result of "Fix Artwork...": covers under `root` that would be
+/// re-encoded if the user confirms.
+pub(super) struct FixArtworkDialog {
+    root: PathBuf,
+    candidates: Vec<FixCandidate>,
+}
+
+/// A preview kept aside by "Pin for Compare", with everything needed to
+/// switch back to it without downloading it again.
+struct PinnedCandidate {
+    url: String,
+    image: image::DynamicImage,
+    render_source: Option<Arc<image::DynamicImage>>,
+    source_size: (u32, u32),
+    edits: ImageEdits,
+    /// Rendered output, as it would be saved
+    output: egui::TextureHandle,
+}
+
+/// Cover already on disk (or in the CHD) for the loaded disc.
+struct ExistingArt {
+    texture: egui::TextureHandle,
+    size: (u32, u32),
+}
+
+/// Per-disc state of a tab in the background, moved in and out of the
+/// matching `App` fields by `App::swap_tab`. In-flight searches, previews
+/// and hashing travel with it and are picked up when it's active again.
+#[derive(Default)]
+struct DiscTab {
+    selected_path: Option<PathBuf>,
+    disc_info: Option<Result<DiscInfo, String>>,
+    chd_artwork_found: bool,
+    search_results: Vec<ImageResult>,
+    search_receiver: Option<Receiver<Result<Vec<ImageResult>, String>>>,
+    selected_image_index: Option<usize>,
+    search_in_progress: bool,
+    search_cancel: Option<CancelToken>,
+    search_query_text: String,
+    manual_url: String,
+    show_search_window: bool,
+    preview_texture: Option<egui::TextureHandle>,
+    preview_image: Option<image::DynamicImage>,
+    preview_source_size: (u32, u32),
+    preview_edits: ImageEdits,
+    preview_show_output: bool,
+    preview_unsharpened: bool,
+    preview_magnified: bool,
+    preview_render_source: Option<Arc<image::DynamicImage>>,
+    preview_output_size: Option<Result<usize, String>>,
+    preview_receiver: Option<Receiver<Result<PreviewImage, String>>>,
+    preview_progress: Option<ProgressFeed<Transfer>>,
+    preview_loading: bool,
+    preview_url: Option<String>,
+    preview_error: Option<String>,
+    candidate_info: Option<(String, Result<crate::net::RemoteInfo, String>)>,
+    candidate_info_receiver: Option<Receiver<(String, Result<crate::net::RemoteInfo, String>)>>,
+    preview_cancel: Option<CancelToken>,
+    compare_pin: Option<PinnedCandidate>,
+    compare_output: Option<egui::TextureHandle>,
+    existing_art: Option<ExistingArt>,
+    existing_art_stale: bool,
+    auto_save_url: Option<String>,
+    output_path_edit: Option<String>,
+    export_role: ArtworkRole,
+    browse_view: BrowseView,
+    show_browse_window: bool,
+    hash_progress: Option<std::sync::Arc<std::sync::Mutex<crate::disc::hasher::HashProgress>>>,
+    hash_receiver: Option<Receiver<Result<crate::disc::hasher::TrackHashes, String>>>,
+    hash_rate_tracker: super::progress::RateTracker,
+    audio_tracks: Option<Vec<crate::disc::cd_audio::CdTrack>>,
+    audio_modal_open: bool,
+    audio_error: Option<String>,
+    tracklist: Option<crate::api::Tracklist>,
+    tracklist_receiver: Option<Receiver<Result<Option<crate::api::Tracklist>, String>>>,
+    cd_text: Option<crate::disc::CdText>,
+    volume_icon: Option<egui::TextureHandle>,
+    volume_icon_receiver: Option<Receiver<Option<egui::ColorImage>>>,
+    entry_counts: Option<crate::disc::EntryCounts>,
+    entry_counts_receiver: Option<Receiver<Option<crate::disc::EntryCounts>>>,
+}
+
+impl DiscTab {
+    /// Stop the tab's background work before it's dropped.
+    fn cancel(&self) {
+        for token in [&self.search_cancel, &self.preview_cancel].into_iter().flatten() {
+            token.cancel();
+        }
+        if let Some(Ok(mut progress)) = self.hash_progress.as_ref().map(|p| p.lock()) {
+            progress.cancelled = true;
+        }
+    }
 }
 
 /// Pending decision for a cue file whose referenced BIN(s) don't exist.
@@ -145,6 +404,27 @@ struct BrokenCuePrompt {
 
 const BULK_AUTO_SKIP_SECS: u64 = 10;
 
+/// Ctrl+O (Cmd+O on macOS): pick a disc image.
+pub(super) const OPEN_FILE_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::O);
+/// Ctrl+S: download and save the previewed image.
+pub(super) const SAVE_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S);
+/// Ctrl+T: open an empty disc tab.
+pub(super) const NEW_TAB_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::T);
+/// Ctrl+L: show or hide the log.
+pub(super) const LOG_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::L);
+/// Ctrl+F: put the cursor in the search query.
+pub(super) const SEARCH_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::F);
+
+/// Search query fields [`SEARCH_SHORTCUT`] focuses: the Artwork Search
+/// window's when it's open, else the one under Disc Information.
+const SEARCH_WINDOW_QUERY_ID: &str = "search_window_query";
+const SEARCH_QUERY_ID: &str = "search_query";
+
 /// Modal state for the bulk-job loader: pending file the user picked, plus
 /// the load-time toggles (reprocess existing art, include fuzzy with a
 /// minimum confidence) and any parse error to surface.
@@ -161,11 +441,74 @@ struct BulkLoaderDialog {
     error: Option<String>,
 }
 
+/// Messages kept for the Log window; the oldest go first beyond this.
+const LOG_CAPACITY: usize = 5000;
+
+/// Saves "Undo Last Export" can step back through in one session.
+const EXPORT_HISTORY_CAPACITY: usize = 50;
+
 /// A log message with severity level
 #[derive(Clone)]
 struct LogMessage {
+    /// When it was logged, ISO-8601 UTC
+    time: String,
     text: String,
     level: LogLevel,
+    /// Cover saved by the export this line reports; the line offers Undo
+    /// while that's the latest save
+    undo: Option<PathBuf>,
+}
+
+impl LogMessage {
+    fn new(level: LogLevel, text: impl Into<String>) -> Self {
+        Self {
+            time: crate::logging::now_iso8601(),
+            text: text.into(),
+            level,
+            undo: None,
+        }
+    }
+
+    /// `HH:MM:SS` part of `time`
+    fn clock(&self) -> &str {
+        self.time.get(11..19).unwrap_or(&self.time)
+    }
+
+    /// One line of a saved or copied log.
+    fn to_line(&self) -> String {
+        format!("{} [{}] {}", self.time, self.level.tag(), self.text)
+    }
+}
+
+/// Log window filter: per-level toggles (Success counts as Info) and a
+/// case-insensitive text match.
+struct LogFilter {
+    info: bool,
+    warnings: bool,
+    errors: bool,
+    text: String,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            info: true,
+            warnings: true,
+            errors: true,
+            text: String::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    fn matches(&self, msg: &LogMessage) -> bool {
+        let level = match msg.level {
+            LogLevel::Info | LogLevel::Success => self.info,
+            LogLevel::Warning => self.warnings,
+            LogLevel::Error => self.errors,
+        };
+        level && (self.text.is_empty() || msg.text.to_lowercase().contains(&self.text.to_lowercase()))
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -176,70 +519,150 @@ enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    fn tag(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Success => "OK",
+            LogLevel::Warning => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
         Self {
             selected_path: None,
             disc_info: None,
-            log_messages: Vec::new(),
+            chd_artwork_found: false,
+            log_messages: VecDeque::new(),
+            log_filter: LogFilter::default(),
             dropped_files: Vec::new(),
             search_results: Vec::new(),
             search_receiver: None,
             selected_image_index: None,
+            scroll_to_selected_result: false,
+            hidden_sources: HashSet::new(),
+            pending_preview: None,
             search_in_progress: false,
             preview_texture: None,
+            preview_image: None,
+            preview_source_size: (0, 0),
+            preview_edits: ImageEdits::default(),
+            preview_show_output: false,
+            preview_unsharpened: false,
+            preview_magnified: false,
+            preview_render_source: None,
+            preview_output_size: None,
             preview_receiver: None,
+            preview_progress: None,
             preview_loading: false,
             preview_url: None,
-            export_receiver: None,
+            export_progress: None,
             export_in_progress: false,
             search_query_text: String::new(),
             manual_url: String::new(),
             show_log_window: false,
             show_log_settings: false,
+            settings_dialog: None,
+            command_palette: None,
+            toasts: Toasts::default(),
             show_search_window: false,
             log_level: crate::config::get_config().log_level.clone(),
+            log_file_level: crate::config::get_config().log_file_level.clone(),
+            appearance: crate::config::get_config().appearance.clone(),
             preview_error: None,
+            candidate_info: None,
+            candidate_info_receiver: None,
+            compare_pin: None,
+            compare_output: None,
+            existing_art: None,
+            existing_art_stale: false,
+            confirm_replace: false,
+            output_path_edit: None,
+            auto_save_url: None,
             update_config: UpdateConfig::load(),
-            update_receiver: None,
+            events: EventBus::default(),
             update_info: None,
             show_update_notification: false,
+            update_progress: None,
+            update_cancel: None,
+            update_relaunch: None,
             update_check_done: false,
             search_config: SearchConfig::default(),
             global_log_receiver: None,
             browse_view: BrowseView::new(),
             show_browse_window: false,
-            user_agent_receiver: None,
             user_agent_capture_in_progress: false,
-            db_update_receiver: None,
             db_update_done: false,
             hash_progress: None,
             hash_receiver: None,
+            disc_sha1: None,
             hash_rate_tracker: super::progress::RateTracker::default(),
             audio_tracks: None,
             audio_playback: None,
             audio_modal_open: false,
             audio_error: None,
+            tracklist: None,
+            tracklist_receiver: None,
+            cd_text: None,
+            volume_icon: None,
+            volume_icon_receiver: None,
+            entry_counts: None,
+            entry_counts_receiver: None,
+            audio_rip_format: AudioFormat::default(),
             bulk_queue: None,
+            processing_queue: ProcessingQueue::default(),
+            show_queue_window: false,
+            folder_watch: None,
+            tray: None,
+            watch_queued: HashSet::new(),
+            watch_tally: (0, 0, 0),
+            show_review_window: false,
+            review_thumbs: Thumbnails::default(),
+            folder_scan: None,
+            recent: RecentList::default(),
+            tabs: vec![DiscTab::default()],
+            active_tab: 0,
             bulk_loader: None,
             bulk_loaded_cursor: None,
             bulk_suppress_cascade: false,
             pending_hash_redump_id: None,
             pending_export_url: None,
+            export_role: ArtworkRole::Front,
+            pending_export_role: ArtworkRole::Front,
             bulk_banner_bottom_y: None,
             broken_cue_prompt: None,
+            pack_entry_count: crate::packs::PackIndex::load().entries.len(),
+            search_cancel: None,
+            preview_cancel: None,
+            export_cancel: None,
+            export_config: crate::config::get_config().export.clone(),
+            export_extra_sizes_text: format_sizes(&crate::config::get_config().export.extra_sizes),
+            fix_dialog: None,
+            export_history: Vec::new(),
+            clipboard: None,
         }
     }
 }
 
 impl App {
     /// Create a new App instance
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         let mut app = Self::default();
 
+        super::theme::apply(&cc.egui_ctx, &app.appearance);
+        super::fonts::add_cjk_fallback(&cc.egui_ctx);
+        crate::i18n::set_language(&crate::config::get_config().language);
+        app.recent = RecentList::load();
+
         // Take the log receiver from the global storage (set in main.rs)
         app.global_log_receiver = super::take_log_receiver();
 
+        // A program replaced by the last update can go now it isn't running
+        crate::update::remove_replaced();
+
         // Start update check in background if enabled
         if app.update_config.update_check.enabled {
             app.start_update_check();
@@ -253,17 +676,15 @@ impl App {
 
     /// Spawn a background thread to check for / download the latest redump DB.
     fn start_db_update(&mut self) {
-        if self.db_update_done || self.db_update_receiver.is_some() {
+        if self.db_update_done || self.events.is_running(Job::DbUpdate) {
             return;
         }
-        let (tx, rx) = mpsc::channel();
-        self.db_update_receiver = Some(rx);
-        thread::spawn(move || {
+        self.events.spawn(Job::DbUpdate, move || {
             let result = match crate::db::DatabaseManager::new() {
                 Ok(mgr) => mgr.update_if_needed().map_err(|e| e.to_string()),
                 Err(e) => Err(e.to_string()),
             };
-            let _ = tx.send(result);
+            AppEvent::DbUpdated(result)
         });
     }
 
@@ -420,25 +841,46 @@ impl App {
         let now_playing = self
             .audio_playback
             .as_ref()
-            .map(|pb| (pb.track(), pb.state(), pb.elapsed_secs()));
+            .map(|pb| (pb.track(), pb.state(), pb.elapsed_secs(), pb.is_paused()));
 
         ui.separator();
         let mut open_modal = false;
+        let mut rip_clicked = false;
         ui.horizontal(|ui| {
             if ui
-                .button(format!("🎵 Audio Tracks ({n_audio})"))
-                .on_hover_text("Open the CD-DA player")
+                .button(tr!("audio-tracks-button", count = n_audio))
+                .on_hover_text(tr!("open-the-cd-da-player-tip"))
                 .clicked()
             {
                 open_modal = true;
             }
-            if let Some((track, state, elapsed)) = now_playing.as_ref() {
+            let ripping = self.events.is_running(Job::AudioRip);
+            egui::ComboBox::from_id_salt("audio_rip_format")
+                .width(60.0)
+                .selected_text(self.audio_rip_format.label())
+                .show_ui(ui, |ui| {
+                    for format in AudioFormat::ALL {
+                        ui.selectable_value(&mut self.audio_rip_format, format, format.label());
+                    }
+                });
+            if ui
+                .add_enabled(!ripping, egui::Button::new(tr!("extract-audio")))
+                .on_hover_text(tr!("save-every-audio-track-to-tip"))
+                .clicked()
+            {
+                rip_clicked = true;
+            }
+            if ripping {
+                ui.spinner();
+            }
+            if let Some((track, state, elapsed, paused)) = now_playing.as_ref() {
+                let icon = if *paused { "⏸" } else { "▶" };
                 match state {
                     super::audio::PlaybackState::Preparing => {
-                        ui.label(format!("▶ Track {track} — preparing…"));
+                        ui.label(tr!("now-playing-preparing", icon = icon, track = *track));
                     }
                     super::audio::PlaybackState::Playing => {
-                        ui.label(format!("▶ Track {track} — {:.0}s", elapsed.unwrap_or(0.0)));
+                        ui.label(tr!("now-playing", icon = icon, track = *track, secs = elapsed.unwrap_or(0.0).round()));
                     }
                     _ => {}
                 }
@@ -447,1358 +889,2967 @@ impl App {
         if open_modal {
             self.audio_modal_open = true;
         }
+        if rip_clicked {
+            self.start_audio_rip();
+        }
 
         let ctx = ui.ctx().clone();
         self.render_audio_modal(&ctx);
 
         // Keep repainting while a job is active so both the panel readout and
         // the modal's seconds counter stay live even when idle otherwise.
-        if self.audio_playback.is_some() {
+        if self.audio_playback.is_some() || self.events.is_running(Job::AudioRip) {
             ctx.request_repaint_after(std::time::Duration::from_millis(200));
         }
     }
 
-    /// Drop a finished/failed playback job, remembering a failure so the modal
-    /// can surface it until the next play starts.
-    fn reap_audio_playback(&mut self) {
-        match self.audio_playback.as_ref().map(|pb| pb.state()) {
-            Some(super::audio::PlaybackState::Failed(e)) => {
-                self.audio_error = Some(e);
-                self.audio_playback = None;
-            }
-            Some(super::audio::PlaybackState::Finished) => {
-                self.audio_playback = None;
-            }
-            _ => {}
+    /// "Extract Audio...": ask for a folder and rip every audio track into it
+    /// in the background. Titles come from the cue's CD-TEXT, else from
+    /// MusicBrainz when the disc has a TOC to look up.
+    fn start_audio_rip(&mut self) {
+        let Some(disc_path) = self.selected_path.clone() else {
+            return;
+        };
+        let tracks: Vec<u32> = self
+            .audio_tracks
+            .as_ref()
+            .map(|ts| ts.iter().filter(|t| t.is_audio).map(|t| t.number).collect())
+            .unwrap_or_default();
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(parent) = disc_path.parent() {
+            dialog = dialog.set_directory(parent);
         }
+        let Some(out_dir) = dialog.pick_folder() else {
+            return;
+        };
+        let lookup = match &self.disc_info {
+            Some(Ok(info)) => info
+                .toc
+                .as_ref()
+                .map(|toc| (toc.musicbrainz_id(), toc.to_toc_string())),
+            _ => None,
+        };
+        let format = self.audio_rip_format;
+
+        self.log(
+            LogLevel::Info,
+            format!(
+                "Extracting {} audio tracks as {} to {}",
+                tracks.len(),
+                format.label(),
+                out_dir.display()
+            ),
+        );
+        self.events.spawn(Job::AudioRip, move || {
+            let mut titles = crate::disc::rip::cue_track_titles(&disc_path);
+            if titles.is_empty() {
+                if let Some((disc_id, toc)) = lookup {
+                    match crate::api::track_titles_by_discid(&disc_id, Some(&toc)) {
+                        Ok(found) => titles = found,
+                        Err(e) => log::warn!("Couldn't look up track titles: {}", e),
+                    }
+                }
+            }
+            let result = crate::disc::rip::rip_tracks(
+                &disc_path,
+                &tracks,
+                &out_dir,
+                format,
+                &titles,
+                |file| log::info!("Saved {}", file.display()),
+            );
+            AppEvent::AudioRipped(result)
+        });
     }
 
-    /// The pop-out CD-DA player modal: a multi-column track grid with per-track
-    /// play/stop and a live elapsed-seconds readout for the active track.
-    fn render_audio_modal(&mut self, ctx: &egui::Context) {
-        if !self.audio_modal_open {
-            return;
+    /// "Extract BIN/CUE...": ask for a folder and convert the CHD into it in
+    /// the background.
+    fn start_chd_extract(&mut self, chd: PathBuf) {
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(parent) = chd.parent() {
+            dialog = dialog.set_directory(parent);
         }
+        let Some(out_dir) = dialog.pick_folder() else {
+            return;
+        };
+        self.log(LogLevel::Info, format!("Extracting {} to {}", chd.display(), out_dir.display()));
+        self.events.spawn(Job::ChdExtract, move || {
+            AppEvent::ChdExtracted(crate::disc::chd_cue::extract_bincue(&chd, &out_dir))
+        });
+    }
 
-        // Snapshot the active job (number, state, elapsed) so the closures below
-        // never hold a borrow of `self.audio_playback` across the UI build.
-        let active = self
-            .audio_playback
-            .as_ref()
-            .map(|pb| (pb.track(), pb.state(), pb.elapsed_secs()));
-        let busy = active.is_some();
+    /// The "Extract BIN/CUE..." worker finished
+    fn on_chd_extracted(&mut self, result: Result<PathBuf, String>) {
+        match result {
+            Ok(cue) => self.log(LogLevel::Success, format!("Extracted BIN/CUE to {}", cue.display())),
+            Err(e) => self.log(LogLevel::Error, format!("BIN/CUE extraction failed: {}", e)),
+        }
+    }
 
-        let mut to_play: Option<u32> = None;
-        let mut do_stop = false;
-        let mut close = false;
+    /// Look up the audio CD's album in the background for Disc Information.
+    fn start_tracklist_lookup(&mut self, disc_id: String, toc: String) {
+        let (tx, rx) = mpsc::channel();
+        self.tracklist_receiver = Some(rx);
+        thread::spawn(move || {
+            let _ = tx.send(crate::api::lookup_tracklist(&disc_id, &toc).map_err(String::from));
+        });
+    }
 
-        let modal = egui::Modal::new(egui::Id::new("audio_player_modal")).show(ctx, |ui| {
-            ui.set_min_width(460.0);
+    /// Poll the tracklist lookup
+    fn poll_tracklist(&mut self) {
+        if let Some(ref receiver) = self.tracklist_receiver {
+            match receiver.try_recv() {
+                Ok(Ok(Some(list))) => {
+                    self.tracklist_receiver = None;
+                    self.log(
+                        LogLevel::Info,
+                        format!("Tracklist from {}: {} by {}", list.source, list.album, list.artist),
+                    );
+                    self.tracklist = Some(list);
+                }
+                Ok(Ok(None)) => {
+                    self.tracklist_receiver = None;
+                    self.log(LogLevel::Info, "No tracklist found for this disc");
+                }
+                Ok(Err(e)) => {
+                    self.tracklist_receiver = None;
+                    self.log(LogLevel::Warning, format!("Tracklist lookup failed: {}", e));
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.tracklist_receiver = None;
+                    self.log(LogLevel::Error, "Tracklist lookup thread terminated unexpectedly");
+                }
+            }
+        }
+    }
 
-            ui.horizontal(|ui| {
-                ui.heading("🎵 Audio Tracks");
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if close_x_button(ui).on_hover_text("Close").clicked() {
-                        close = true;
-                    }
-                });
+    /// Read the disc's own icon in the background; opening the filesystem
+    /// can take a moment on a large image.
+    fn start_volume_icon(&mut self, info: DiscInfo) {
+        let (tx, rx) = mpsc::channel();
+        self.volume_icon_receiver = Some(rx);
+        thread::spawn(move || {
+            let icon = crate::disc::read_volume_icon(&info).map(|icon| {
+                color_image_from(&icon.thumbnail(VOLUME_ICON_EDGE, VOLUME_ICON_EDGE))
             });
+            let _ = tx.send(icon);
+        });
+    }
 
-            // Now-playing banner: position in seconds + progress bar.
-            if let Some((track, state, elapsed)) = active.as_ref() {
-                let total = self
-                    .audio_tracks
-                    .as_ref()
-                    .and_then(|ts| ts.iter().find(|t| t.number == *track))
-                    .map(|t| f64::from(t.frames) / 75.0)
-                    .unwrap_or(0.0);
-                match state {
-                    super::audio::PlaybackState::Preparing => {
-                        ui.label(format!("Track {track}: preparing…"));
-                    }
-                    super::audio::PlaybackState::Playing => {
-                        let pos = elapsed.unwrap_or(0.0).min(total);
-                        ui.label(
-                            egui::RichText::new(format!("▶ Track {track}   {pos:.0}s / {total:.0}s"))
-                                .strong(),
-                        );
-                        let frac = if total > 0.0 { (pos / total) as f32 } else { 0.0 };
-                        ui.add(egui::ProgressBar::new(frac).desired_height(6.0));
-                    }
-                    _ => {}
+    /// Poll the volume icon reader
+    fn poll_volume_icon(&mut self, ctx: &egui::Context) {
+        if let Some(ref receiver) = self.volume_icon_receiver {
+            match receiver.try_recv() {
+                Ok(icon) => {
+                    self.volume_icon_receiver = None;
+                    self.volume_icon = icon.map(|image| {
+                        ctx.load_texture("volume_icon", image, egui::TextureOptions::LINEAR)
+                    });
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.volume_icon_receiver = None;
                 }
             }
+        }
+    }
 
-            if let Some(err) = self.audio_error.as_ref() {
-                ui.colored_label(egui::Color32::RED, format!("Playback failed: {err}"));
-            }
-
-            ui.separator();
-
-            // Multi-column grid of tracks.
-            const COLS: usize = 3;
-            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
-                egui::Grid::new("audio_tracks_grid")
-                    .num_columns(COLS)
-                    .spacing([18.0, 8.0])
-                    .show(ui, |ui| {
-                        let audio: Vec<&crate::disc::cd_audio::CdTrack> = self
-                            .audio_tracks
-                            .as_ref()
-                            .map(|ts| ts.iter().filter(|t| t.is_audio).collect())
-                            .unwrap_or_default();
-                        for (i, t) in audio.iter().enumerate() {
-                            let is_this = active
-                                .as_ref()
-                                .map(|(n, _, _)| *n == t.number)
-                                .unwrap_or(false);
-                            ui.horizontal(|ui| {
-                                if is_this {
-                                    if ui.button("■").on_hover_text("Stop").clicked() {
-                                        do_stop = true;
-                                    }
-                                } else if ui.add_enabled(!busy, egui::Button::new("▶")).clicked() {
-                                    to_play = Some(t.number);
-                                }
-
-                                match active.as_ref() {
-                                    Some((n, super::audio::PlaybackState::Playing, elapsed))
-                                        if *n == t.number =>
-                                    {
-                                        let total = f64::from(t.frames) / 75.0;
-                                        let pos = elapsed.unwrap_or(0.0).min(total);
-                                        ui.label(format!("Track {} — {pos:.0}s", t.number));
-                                    }
-                                    _ => {
-                                        ui.label(format!(
-                                            "Track {} ({})",
-                                            t.number,
-                                            t.duration_mmss()
-                                        ));
-                                    }
-                                }
-                            });
-                            if (i + 1) % COLS == 0 {
-                                ui.end_row();
-                            }
-                        }
-                    });
-            });
+    /// Count the disc's directories and files in the background; a big
+    /// disc takes a while to walk.
+    fn start_entry_counts(&mut self, info: DiscInfo) {
+        let (tx, rx) = mpsc::channel();
+        self.entry_counts_receiver = Some(rx);
+        thread::spawn(move || {
+            let _ = tx.send(crate::disc::count_entries(&info));
         });
+    }
 
-        if modal.should_close() {
-            close = true;
+    /// Poll the directory and file count
+    fn poll_entry_counts(&mut self) {
+        if let Some(ref receiver) = self.entry_counts_receiver {
+            match receiver.try_recv() {
+                Ok(counts) => {
+                    self.entry_counts_receiver = None;
+                    self.entry_counts = counts;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.entry_counts_receiver = None;
+                }
+            }
         }
+    }
 
-        if do_stop {
-            self.audio_playback = None;
-        }
-        if let Some(track) = to_play {
-            if let Some(disc_path) = self.selected_path.clone() {
-                self.audio_error = None;
-                self.audio_playback = Some(super::audio::AudioPlayback::start(disc_path, track));
+    /// The "Extract Audio..." worker finished
+    fn on_audio_ripped(&mut self, result: Result<Vec<PathBuf>, String>) {
+        match result {
+            Ok(files) => {
+                let dir = files
+                    .first()
+                    .and_then(|f| f.parent())
+                    .map(|d| d.display().to_string())
+                    .unwrap_or_default();
+                self.log(LogLevel::Success, format!("Extracted {} audio tracks to {}", files.len(), dir));
             }
-        }
-        if close {
-            self.audio_modal_open = false;
-            // Closing the player stops playback (dropping the job tears it down).
-            self.audio_playback = None;
+            Err(e) => self.log(LogLevel::Error, format!("Audio extraction failed: {}", e)),
         }
     }
 
-    fn poll_hash(&mut self) {
-        if let Some(progress) = self.hash_progress.as_ref() {
-            // Pump the rate tracker each frame while hashing is in flight.
-            if let Ok(p) = progress.lock() {
-                self.hash_rate_tracker.record(p.current_bytes, &p.stage);
-            }
-        }
-        let Some(rx) = self.hash_receiver.as_ref() else {
-            return;
-        };
-        match rx.try_recv() {
-            Ok(Ok(hashes)) => {
-                self.log(
-                    LogLevel::Info,
-                    format!(
-                        "Hashed {} ({}): sha1={}, md5={}, crc32={}",
-                        hashes.source,
-                        super::progress::format_size(hashes.size_bytes),
-                        &hashes.sha1[..16],
-                        &hashes.md5[..16],
-                        hashes.crc32,
-                    ),
-                );
-                self.apply_hash_match(&hashes);
-                self.hash_progress = None;
-                self.hash_receiver = None;
+    /// Start waiting discs in the processing queue and log the ones that
+    /// finished since the last frame
+    fn tick_processing_queue(&mut self) {
+        let finished = self.processing_queue.tick(|| QueueJob {
+            export_config: self.export_config.clone(),
+            search_config: self.search_config.clone(),
+        });
+        let batch_ended = !finished.is_empty() && !self.processing_queue.is_busy();
+        for entry in finished {
+            if self.watch_queued.remove(&entry.path) {
+                let (saved, failed, waiting) = &mut self.watch_tally;
+                match entry.stage {
+                    Stage::Done(_) | Stage::WouldSave { .. } => *saved += 1,
+                    Stage::Failed(_) => *failed += 1,
+                    Stage::Review | Stage::Exists(_) => *waiting += 1,
+                    _ => {}
+                }
             }
-            Ok(Err(e)) => {
-                // "Unsupported format" / "cancelled" are not real failures.
-                if !e.contains("cancelled") && !e.contains("not yet supported") {
-                    // Hashing's redump-cascade benefit is mostly for ISO9660
-                    // data discs that redump catalogs deeply. For HFS / HFS+
-                    // discs the hash tier rarely helps even when it succeeds,
-                    // and the underlying CHD/CUE path frequently rejects them
-                    // outright. Surface that context so the warning isn't
-                    // mysterious.
-                    let suffix = match self
-                        .disc_info
-                        .as_ref()
-                        .and_then(|r| r.as_ref().ok())
-                        .map(|i| i.filesystem)
-                    {
-                        Some(FilesystemType::Hfs) | Some(FilesystemType::HfsPlus) => {
-                            " (normal for HFS/HFS+ discs — redump doesn't catalog Mac \
-                             hashes deeply, so the hash tier wouldn't add much anyway)"
-                        }
-                        _ => "",
-                    };
-                    log::warn!("Hashing failed: {e}{suffix}");
+            let name = entry
+                .title
+                .unwrap_or_else(|| entry.path.display().to_string());
+            match entry.stage {
+                Stage::Done(output) => {
+                    self.log(LogLevel::Success, format!("Queue: saved artwork for {} to {}", name, output));
+                }
+                Stage::Failed(e) => {
+                    self.log(LogLevel::Error, format!("Queue: {} failed: {}", name, e));
+                }
+                Stage::Review => {
+                    self.log(LogLevel::Info, format!("Queue: {} is ready for review", name));
+                }
+                Stage::Exists(output) => {
                     self.log(
                         LogLevel::Warning,
-                        format!("Hashing failed: {e}{suffix}"),
+                        format!("Queue: {} already has artwork at {}, replace or keep it", name, output),
                     );
-                } else {
-                    log::info!("Hashing skipped: {e}");
                 }
-                self.hash_progress = None;
-                self.hash_receiver = None;
-            }
-            Err(TryRecvError::Empty) => {}
-            Err(TryRecvError::Disconnected) => {
-                self.hash_progress = None;
-                self.hash_receiver = None;
+                Stage::WouldSave { path, replaces } => {
+                    let replacing = if replaces { ", replacing the current cover" } else { "" };
+                    self.log(
+                        LogLevel::Info,
+                        format!("Queue (dry run): would save artwork for {} to {}{}", name, path, replacing),
+                    );
+                }
+                Stage::Skipped => {
+                    self.log(LogLevel::Info, format!("Queue: kept the existing artwork for {}", name));
+                }
+                _ => {}
             }
         }
+        // Review once the whole run has searched, so nothing shifts under
+        // the grid while choices are being made
+        if batch_ended && self.processing_queue.in_review() > 0 {
+            self.show_review_window = true;
+        }
+        if batch_ended && self.processing_queue.existing_count() > 0 {
+            self.show_queue_window = true;
+        }
+        if batch_ended && self.watch_tally != (0, 0, 0) {
+            let (saved, failed, waiting) = std::mem::take(&mut self.watch_tally);
+            super::tray::notify(
+                tr!("watch-notify-title"),
+                tr!("watch-notify-body", saved = saved, failed = failed, waiting = waiting),
+            );
+        }
     }
 
-    /// Re-run the redump cascade with hash inputs filled in. Replaces the
-    /// existing match list when the hash tier produces a hit, otherwise
-    /// leaves the serial/PVD result in place.
-    fn apply_hash_match(&mut self, hashes: &crate::disc::hasher::TrackHashes) {
-        // Run the lookup first; only touch self.disc_info to apply the
-        // result. Keeps the &mut borrow narrow so we can also call self.log.
-        let mgr = match crate::db::DatabaseManager::new() {
-            Ok(m) => m,
-            Err(e) => {
-                log::debug!("hash match skipped (manager init): {e}");
-                return;
-            }
-        };
-        let conn = match mgr.open() {
-            Ok(c) => c,
+    /// Start or stop watch mode. Starting minimizes the window, with the
+    /// tray icon to bring it back.
+    fn toggle_watch(&mut self, ctx: &egui::Context) {
+        if self.folder_watch.take().is_some() {
+            self.log(LogLevel::Info, "Stopped watching folders");
+            return;
+        }
+        let roots: Vec<PathBuf> = crate::config::get_config()
+            .watch
+            .folders
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        if roots.is_empty() {
+            self.log(LogLevel::Warning, "No watch folders set; add some under Watch Folders in Settings");
+            return;
+        }
+        let wake = ctx.clone();
+        match FolderWatch::start_with(&roots, move || wake.request_repaint_after(SETTLE_TIME)) {
+            Ok(watch) => self.folder_watch = Some(watch),
             Err(e) => {
-                log::debug!("hash match skipped: {e}");
-                return;
-            }
-        };
-        let inputs = crate::db::CascadeInputs {
-            track_sha1: Some(&hashes.sha1),
-            track_md5: Some(&hashes.md5),
-            track_crc32: Some(&hashes.crc32),
-            ..Default::default()
-        };
-        let matches = match crate::db::lookup::cascade(&conn, &inputs) {
-            Ok(Some(m)) if !m.is_empty() => m,
-            Ok(_) => {
-                self.log(LogLevel::Info, "Redump: no hash match (kept prior match)");
+                self.log(LogLevel::Error, e);
                 return;
             }
-            Err(e) => {
-                log::warn!("hash cascade failed: {e}");
-                return;
+        }
+        let list: Vec<String> = roots.iter().map(|r| r.display().to_string()).collect();
+        self.log(LogLevel::Info, format!("Watching {} for new disc images", list.join(", ")));
+        if self.tray.is_none() {
+            match Tray::new(ctx) {
+                Ok(tray) => self.tray = Some(tray),
+                Err(e) => self.log(LogLevel::Warning, e),
             }
-        };
-
-        let head = matches[0].clone();
-        let head_summary = format!(
-            "Redump hash match: {} [#{}] via {:?}",
-            head.title, head.redump_id, head.matched_via
-        );
+        }
+        if self.tray.is_some() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        }
+    }
 
-        // In bulk mode the queue's match is authoritative. Record the
-        // disagreement (if any) on the active queue item so it lands in the
-        // done log when the user saves/skips, but don't disturb the UI.
-        if let Some(queue) = self.bulk_queue.as_mut() {
-            if let Some(item) = queue.current() {
-                let queue_id = item.best.redump_id;
-                if queue_id != Some(head.redump_id) {
-                    self.log(
-                        LogLevel::Info,
-                        format!(
-                            "Bulk: hash points to #{} (queue locked to #{:?}); recorded as disagreement",
-                            head.redump_id, queue_id
-                        ),
-                    );
-                } else {
-                    self.log(LogLevel::Info, "Bulk: hash confirms queue match");
+    /// Handle the tray menu, and queue disc images the watcher found.
+    fn poll_watch(&mut self, ctx: &egui::Context) {
+        while let Some(action) = self.tray.as_ref().and_then(Tray::poll) {
+            match action {
+                TrayAction::Show => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
                 }
+                TrayAction::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
             }
-            self.pending_hash_redump_id = Some(head.redump_id);
-            return;
         }
-
-        if let Some(Ok(info)) = self.disc_info.as_mut() {
-            info.redump_matches = Some(matches);
-            // Hash hit beats any fuzzy candidates from the earlier pass —
-            // drop the stale list so the UI doesn't show both.
-            info.fuzzy_matches = None;
+        let Some(watch) = self.folder_watch.as_mut() else {
+            return;
+        };
+        for path in watch.poll() {
+            if self.processing_queue.add(path.clone()) {
+                self.log(LogLevel::Info, format!("Watch: queued {}", path.display()));
+                self.watch_queued.insert(path);
+            }
         }
-        self.log(LogLevel::Success, head_summary);
     }
 
-    /// Poll the background DB update channel and log the outcome once.
-    fn poll_db_update(&mut self) {
-        let Some(rx) = self.db_update_receiver.as_ref() else {
+    /// Processing queue window: overall progress, a row per disc, and
+    /// retry / clear / cancel controls
+    fn render_queue_window(&mut self, ctx: &egui::Context) {
+        if !self.show_queue_window {
             return;
-        };
-        match rx.try_recv() {
-            Ok(Ok(outcome)) => {
-                match &outcome {
-                    crate::db::UpdateOutcome::UpToDate { .. } => {
-                        self.log(LogLevel::Info, "Lookup DB is up to date");
+        }
+        let mut open = true;
+        let mut retry = None;
+        let mut retry_failed = false;
+        let mut clear_finished = false;
+        let mut cancel = false;
+        let mut open_review = false;
+        let mut review = self.processing_queue.review;
+        let mut dry_run = self.processing_queue.dry_run;
+        let mut resolve: Option<(Option<usize>, bool)> = None;
+        let queue = &self.processing_queue;
+        let (finished, total) = queue.progress();
+        let busy = queue.is_busy();
+        let in_review = queue.in_review();
+        let existing = queue.existing_count();
+        let any_failed = queue
+            .entries
+            .iter()
+            .any(|e| matches!(e.stage, Stage::Failed(_)));
+
+        egui::Window::new(tr!("processing-queue"))
+            .open(&mut open)
+            .default_size([520.0, 320.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                let fraction = if total == 0 { 0.0 } else { finished as f32 / total as f32 };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .text(tr!("queue-progress", done = finished, total = total))
+                        .animate(busy),
+                );
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    egui::Grid::new("processing_queue")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, entry) in queue.entries.iter().enumerate() {
+                                // Waiting discs start in this order
+                                ui.weak(format!("{}.", i + 1));
+                                let name = entry.title.clone().unwrap_or_else(|| {
+                                    entry
+                                        .path
+                                        .file_name()
+                                        .map(|n| n.to_string_lossy().into_owned())
+                                        .unwrap_or_default()
+                                });
+                                ui.label(name).on_hover_text(entry.path.display().to_string());
+                                match &entry.stage {
+                                    Stage::Done(output) => {
+                                        ui.colored_label(egui::Color32::GREEN, tr!("done")).on_hover_text(output);
+                                    }
+                                    stage @ Stage::WouldSave { path, replaces } => {
+                                        let tip = if *replaces {
+                                            tr!("would-replace-tip", path = path.as_str())
+                                        } else {
+                                            path.clone()
+                                        };
+                                        ui.colored_label(egui::Color32::LIGHT_BLUE, stage.label()).on_hover_text(tip);
+                                    }
+                                    stage @ Stage::Exists(output) => {
+                                        ui.colored_label(egui::Color32::YELLOW, stage.label()).on_hover_text(output);
+                                    }
+                                    Stage::Failed(e) => {
+                                        ui.colored_label(egui::Color32::LIGHT_RED, tr!("failed")).on_hover_text(e);
+                                    }
+                                    Stage::Skipped => {
+                                        ui.weak(Stage::Skipped.label());
+                                    }
+                                    stage => {
+                                        ui.horizontal(|ui| {
+                                            if !matches!(stage, Stage::Waiting | Stage::Review) {
+                                                ui.spinner();
+                                            }
+                                            ui.label(stage.label());
+                                        });
+                                    }
+                                }
+                                if matches!(entry.stage, Stage::Failed(_)) {
+                                    if ui.small_button(tr!("retry")).clicked() {
+                                        retry = Some(i);
+                                    }
+                                } else if matches!(entry.stage, Stage::Exists(_)) {
+                                    ui.horizontal(|ui| {
+                                        if ui.small_button(tr!("replace")).clicked() {
+                                            resolve = Some((Some(i), true));
+                                        }
+                                        if ui.small_button(tr!("keep-current")).clicked() {
+                                            resolve = Some((Some(i), false));
+                                        }
+                                    });
+                                } else {
+                                    ui.label("");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.add_space(6.0);
+                if existing > 0 {
+                    ui.colored_label(egui::Color32::YELLOW, tr!("queue-existing", count = existing));
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(tr!("replace-all"))
+                            .on_hover_text(tr!("replace-all-tip"))
+                            .clicked()
+                        {
+                            resolve = Some((None, true));
+                        }
+                        if ui.button(tr!("keep-all")).on_hover_text(tr!("keep-all-tip")).clicked() {
+                            resolve = Some((None, false));
+                        }
+                    });
+                    ui.add_space(6.0);
+                }
+                ui.checkbox(&mut review, tr!("review-before-saving"))
+                    .on_hover_text(tr!("review-before-saving-tip"));
+                ui.checkbox(&mut dry_run, tr!("dry-run"))
+                    .on_hover_text(tr!("dry-run-tip"));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(in_review > 0, egui::Button::new(tr!("review-count", count = in_review)))
+                        .clicked()
+                    {
+                        open_review = true;
                     }
-                    crate::db::UpdateOutcome::Updated { local_path, .. } => {
-                        self.log(
-                            LogLevel::Success,
-                            format!("Lookup DB updated: {}", local_path.display()),
-                        );
+                    if ui.add_enabled(any_failed, egui::Button::new(tr!("retry-failed"))).clicked() {
+                        retry_failed = true;
                     }
-                    crate::db::UpdateOutcome::OfflineUsingCached { error, .. } => {
-                        self.log(
-                            LogLevel::Warning,
-                            format!("Lookup DB update skipped (offline): {error}"),
-                        );
+                    if ui
+                        .add_enabled(!busy && finished > 0, egui::Button::new(tr!("clear-finished")))
+                        .clicked()
+                    {
+                        clear_finished = true;
                     }
-                    crate::db::UpdateOutcome::OfflineNoCache { error } => {
-                        self.log(
-                            LogLevel::Warning,
-                            format!("Lookup DB unavailable (offline, no cache): {error}"),
-                        );
+                    if ui.add_enabled(busy, egui::Button::new(tr!("cancel"))).clicked() {
+                        cancel = true;
                     }
-                }
-                self.db_update_done = true;
-                self.db_update_receiver = None;
-            }
-            Ok(Err(e)) => {
-                self.log(LogLevel::Error, format!("Lookup DB update failed: {e}"));
-                self.db_update_done = true;
-                self.db_update_receiver = None;
-            }
-            Err(TryRecvError::Empty) => {}
-            Err(TryRecvError::Disconnected) => {
-                self.db_update_done = true;
-                self.db_update_receiver = None;
+                });
+            });
+
+        self.show_queue_window = open;
+        self.processing_queue.review = review;
+        self.processing_queue.dry_run = dry_run;
+        if let Some((index, replace)) = resolve {
+            self.processing_queue.resolve_existing(index, replace);
+            // "Replace All" / "Keep All" also answer for the discs that
+            // haven't got that far yet
+            if index.is_none() {
+                self.processing_queue.existing = if replace { Existing::Replace } else { Existing::Keep };
             }
         }
+        if open_review {
+            self.show_review_window = true;
+        }
+        if let Some(i) = retry {
+            self.processing_queue.retry(i);
+        }
+        if retry_failed {
+            self.processing_queue.retry_failed();
+        }
+        if clear_finished {
+            self.processing_queue.clear_finished();
+        }
+        if cancel {
+            self.processing_queue.cancel();
+            self.log(LogLevel::Warning, "Processing queue cancelled");
+        }
     }
 
-    /// Add a log message
-    fn log(&mut self, level: LogLevel, message: impl Into<String>) {
-        self.log_messages.push(LogMessage {
-            text: message.into(),
-            level,
-        });
-        // Keep only last 100 messages
-        if self.log_messages.len() > 100 {
-            self.log_messages.remove(0);
+    /// Batch review window: every disc the queue stopped for review with its
+    /// pick's thumbnail, a menu of its other results and approve / skip,
+    /// then one button that saves all the approved discs
+    fn render_review_window(&mut self, ctx: &egui::Context) {
+        if !self.show_review_window {
+            return;
         }
-    }
+        let mut open = true;
+        let mut commit = false;
+        let mut approve_all = None;
+        let queue = &mut self.processing_queue;
+        let thumbs = &mut self.review_thumbs;
+        let in_review = queue.in_review();
+        let approved = queue
+            .entries
+            .iter()
+            .filter(|e| e.stage == Stage::Review && e.review.as_ref().is_some_and(|r| r.approved))
+            .count();
 
-    /// Process a selected file
-    fn process_file(&mut self, path: PathBuf) {
-        // Pre-flight: detect cue files whose BINs are missing and surface a
-        // "delete cue?" prompt before any of the rest of the load runs. The
-        // prompt is modal — the disc isn't loaded until the user resolves
-        // it (or, in bulk mode, the auto-skip timeout fires).
-        let scan = crate::disc::scan_cue_references(&path);
-        if !scan.missing.is_empty()
-            && path
-                .extension()
-                .and_then(|e| e.to_str())
-                .map(|e| e.eq_ignore_ascii_case("cue"))
-                .unwrap_or(false)
-        {
-            self.broken_cue_prompt = Some(BrokenCuePrompt {
-                cue_path: path.clone(),
-                missing: scan.missing,
-                total_refs: scan.total_refs,
-                in_bulk: self.bulk_queue.is_some(),
-                opened_at: std::time::Instant::now(),
+        egui::Window::new(tr!("batch-review"))
+            .open(&mut open)
+            .default_size([640.0, 480.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                if in_review == 0 {
+                    ui.label(tr!("review-empty"));
+                    return;
+                }
+                ui.label(tr!("review-summary", approved = approved, total = in_review));
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical()
+                    .max_height((ui.available_height() - 36.0).max(120.0))
+                    .show(ui, |ui| {
+                        egui::Grid::new("batch_review")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (i, entry) in queue.entries.iter_mut().enumerate() {
+                                    if entry.stage != Stage::Review {
+                                        continue;
+                                    }
+                                    let Some(item) = entry.review.as_mut() else {
+                                        continue;
+                                    };
+                                    let chosen = item.chosen();
+                                    let url = if chosen.thumbnail_url.is_empty() {
+                                        chosen.image_url.clone()
+                                    } else {
+                                        chosen.thumbnail_url.clone()
+                                    };
+                                    // Only rows scrolled into view fetch their thumbnail
+                                    let cell = egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::splat(REVIEW_THUMB_EDGE));
+                                    if !ui.is_rect_visible(cell) {
+                                        ui.allocate_exact_size(cell.size(), egui::Sense::hover());
+                                    } else {
+                                        match thumbs.get(ctx, &url).cloned() {
+                                            Some(texture) => {
+                                                let size = texture.size_vec2();
+                                                let scale = REVIEW_THUMB_EDGE / size.x.max(size.y);
+                                                ui.image((texture.id(), size * scale));
+                                            }
+                                            None if thumbs.failed(&url) => {
+                                                ui.add_sized([REVIEW_THUMB_EDGE; 2], egui::Label::new("—"))
+                                                    .on_hover_text(tr!("review-no-thumbnail"));
+                                            }
+                                            None => {
+                                                ui.add_sized([REVIEW_THUMB_EDGE; 2], egui::Spinner::new());
+                                            }
+                                        }
+                                    }
+
+                                    ui.vertical(|ui| {
+                                        let name = entry.title.clone().unwrap_or_else(|| {
+                                            entry
+                                                .path
+                                                .file_name()
+                                                .map(|n| n.to_string_lossy().into_owned())
+                                                .unwrap_or_default()
+                                        });
+                                        ui.strong(name).on_hover_text(entry.path.display().to_string());
+                                        let before = item.choice;
+                                        egui::ComboBox::from_id_salt(("review_choice", i))
+                                            .width(300.0)
+                                            .selected_text(candidate_label(item.chosen()))
+                                            .show_ui(ui, |ui| {
+                                                for (c, candidate) in item.candidates.iter().enumerate() {
+                                                    ui.selectable_value(&mut item.choice, c, candidate_label(candidate));
+                                                }
+                                            });
+                                        // Choosing another result is a vote for it
+                                        if item.choice != before {
+                                            item.approved = true;
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        ui.selectable_value(&mut item.approved, true, tr!("approve"));
+                                        ui.selectable_value(&mut item.approved, false, tr!("skip"));
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("approve-all")).clicked() {
+                        approve_all = Some(true);
+                    }
+                    if ui.button(tr!("skip-all")).clicked() {
+                        approve_all = Some(false);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .button(tr!("save-approved", count = approved))
+                            .on_hover_text(tr!("save-approved-tip"))
+                            .clicked()
+                        {
+                            commit = true;
+                        }
+                    });
+                });
             });
+
+        self.show_review_window = open;
+        if let Some(approve) = approve_all {
+            for entry in &mut self.processing_queue.entries {
+                if let (Stage::Review, Some(item)) = (&entry.stage, entry.review.as_mut()) {
+                    item.approved = approve;
+                }
+            }
+        }
+        if commit {
+            let (approved, skipped) = self.processing_queue.commit_review();
             self.log(
-                LogLevel::Warning,
-                format!(
-                    "Broken cue detected: {} (missing data file(s))",
-                    path.display()
-                ),
+                LogLevel::Info,
+                format!("Queue: saving {} reviewed disc(s), skipped {}", approved, skipped),
             );
-            return;
+            self.show_review_window = false;
+            self.show_queue_window = true;
+            self.review_thumbs.clear();
         }
+    }
 
-        self.log(LogLevel::Info, format!("Processing: {}", path.display()));
-        self.selected_path = Some(path.clone());
+    /// Drop a finished/failed playback job, remembering a failure so the modal
+    /// can surface it until the next play starts.
+    fn reap_audio_playback(&mut self) {
+        match self.audio_playback.as_ref().map(|pb| pb.state()) {
+            Some(super::audio::PlaybackState::Failed(e)) => {
+                self.audio_error = Some(e);
+                self.audio_playback = None;
+            }
+            Some(super::audio::PlaybackState::Finished) => {
+                self.audio_playback = None;
+            }
+            _ => {}
+        }
+    }
 
-        // Clear previous search state
-        self.search_query_text.clear();
-        self.manual_url.clear();
-        self.search_results.clear();
-        self.selected_image_index = None;
-        self.preview_texture = None;
-        self.preview_url = None;
-        self.audio_tracks = None;
-        self.audio_playback = None; // Drop stops any in-flight playback.
-        self.audio_modal_open = false;
-        self.audio_error = None;
+    /// The pop-out CD-DA player modal: a multi-column track grid with per-track
+    /// play/stop and a live elapsed-seconds readout for the active track.
+    fn render_audio_modal(&mut self, ctx: &egui::Context) {
+        if !self.audio_modal_open {
+            return;
+        }
 
-        // Clear browse view state
-        self.browse_view.clear();
-        self.show_browse_window = false;
+        // Snapshot the active job (number, state, elapsed) so the closures below
+        // never hold a borrow of `self.audio_playback` across the UI build.
+        let active = self
+            .audio_playback
+            .as_ref()
+            .map(|pb| (pb.track(), pb.state(), pb.elapsed_secs()));
+        let busy = active.is_some();
+        let paused = self.audio_playback.as_ref().is_some_and(|pb| pb.is_paused());
 
-        // Store log messages in a vector to add after disc reading
-        let log_messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
-        let log_messages_clone = log_messages.clone();
-        
-        // Set up logging callback to capture disc reading logs
-        crate::disc::set_log_callback(std::sync::Arc::new(std::sync::Mutex::new(
-            move |msg: String| {
-                if let Ok(mut messages) = log_messages_clone.lock() {
-                    messages.push(msg);
-                }
-            }
-        )));
+        let mut to_play: Option<u32> = None;
+        let mut toggle_pause = false;
+        let mut do_stop = false;
+        let mut close = false;
 
-        match DiscReader::read(&path) {
-            Ok(info) => {
-                // Add collected log messages
-                if let Ok(messages) = log_messages.lock() {
-                    for msg in messages.iter() {
-                        self.log(LogLevel::Info, msg.clone());
+        let modal = egui::Modal::new(egui::Id::new("audio_player_modal")).show(ctx, |ui| {
+            ui.set_min_width(460.0);
+
+            ui.horizontal(|ui| {
+                ui.heading(tr!("audio-tracks"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if named(close_x_button(ui), egui::WidgetType::Button, tr!("close")).clicked() {
+                        close = true;
                     }
-                }
-                
-                self.log(
-                    LogLevel::Success,
-                    format!("Successfully read disc: {}", info.title),
-                );
-                let mut info = info;
-                self.enrich_with_redump(&mut info);
-                // Cache the CD audio track list for CHD and BIN/CUE images so the
-                // disc panel can offer per-track playback without re-parsing the
-                // image each frame.
-                self.audio_tracks = if matches!(info.format, DiscFormat::Chd | DiscFormat::BinCue) {
-                    crate::disc::cd_audio::read_tracks(&info.path).ok()
-                } else {
-                    None
-                };
-                // Pop the audio player out automatically when the disc has audio.
-                self.audio_error = None;
-                self.audio_modal_open = self
+                });
+            });
+
+            // Now-playing banner: position in seconds + progress bar.
+            if let Some((track, state, elapsed)) = active.as_ref() {
+                let total = self
                     .audio_tracks
                     .as_ref()
-                    .is_some_and(|tracks| tracks.iter().any(|t| t.is_audio));
-                self.disc_info = Some(Ok(info));
-            }
-            Err(e) => {
-                // Add collected log messages even on error
-                if let Ok(messages) = log_messages.lock() {
-                    for msg in messages.iter() {
-                        self.log(LogLevel::Info, msg.clone());
+                    .and_then(|ts| ts.iter().find(|t| t.number == *track))
+                    .map(|t| f64::from(t.frames) / 75.0)
+                    .unwrap_or(0.0);
+                match state {
+                    super::audio::PlaybackState::Preparing => {
+                        ui.label(tr!("track-preparing", track = *track));
+                    }
+                    super::audio::PlaybackState::Playing => {
+                        let pos = elapsed.unwrap_or(0.0).min(total);
+                        let icon = if paused { "⏸" } else { "▶" };
+                        ui.label(
+                            egui::RichText::new(tr!("track-position", icon = icon, track = *track, pos = pos.round(), total = total.round()))
+                                .strong(),
+                        );
+                        let frac = if total > 0.0 { (pos / total) as f32 } else { 0.0 };
+                        ui.add(egui::ProgressBar::new(frac).desired_height(6.0));
                     }
+                    _ => {}
                 }
-                
-                let error_str = e.to_string();
-                // Check if this is likely an HFS/HFS+ disc or other non-ISO9660 format
-                let is_filesystem_error = error_str.contains("Primary Volume Descriptor")
-                    || error_str.contains("HFS")
-                    || error_str.contains("Parse error");
-
-                if is_filesystem_error {
-                    // Fall back to filename-only parsing for HFS and other unsupported filesystems
-                    self.log(
-                        LogLevel::Warning,
-                        format!("Could not read disc structure (may be HFS/Mac format), using filename only"),
-                    );
-
-                    // Create a minimal DiscInfo from filename
-                    let parsed = parse_filename(&path);
-                    let format = DiscFormat::from_path(&path).unwrap_or(DiscFormat::Iso);
-
-                    let fallback_info = DiscInfo {
-                        path: path.clone(),
-                        format,
-                        filesystem: FilesystemType::Unknown,
-                        volume_label: None,
-                        title: parsed.title.clone(),
-                        parsed_filename: parsed,
-                        confidence: ConfidenceLevel::Low,
-                        pvd: None,
-                        toc: None,
-                        hfs_mdb: None,
-                        hfsplus_header: None,
-                        redump_matches: None,
-                        fuzzy_matches: None,
-                    };
+            }
 
-                    let mut fallback_info = fallback_info;
-                    self.enrich_with_redump(&mut fallback_info);
-                    self.disc_info = Some(Ok(fallback_info));
-                } else {
-                    self.log(LogLevel::Error, format!("Error reading disc: {}", e));
-                    self.disc_info = Some(Err(error_str));
-                }
+            if let Some(err) = self.audio_error.as_ref() {
+                ui.colored_label(egui::Color32::RED, tr!("playback-failed", error = err));
             }
-        }
-        
-        // Clear the log callback after processing
-        crate::disc::clear_log_callback();
 
-        // Kick off track hashing in the background. The disc info is already
-        // displayed; when hashing finishes we'll re-run the cascade with the
-        // hash tier active and refresh `redump_matches`.
-        self.start_hashing();
-    }
+            ui.separator();
 
-    /// Prompt the user for a `fuzzy_scan --queue` JSON file and stage it in
-    /// the loader dialog. The actual queue activation happens when they
-    /// click "Start" in the dialog.
-    fn open_bulk_job_picker(&mut self) {
-        let Some(path) = rfd::FileDialog::new()
-            .add_filter("Queue (JSON or CSV)", &["json", "csv"])
-            .add_filter("Queue JSON", &["json"])
-            .add_filter("fuzzy_scan CSV", &["csv"])
-            .add_filter("All Files", &["*"])
-            .pick_file()
-        else {
-            return;
-        };
+            // Multi-column grid of tracks.
+            const COLS: usize = 3;
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                egui::Grid::new("audio_tracks_grid")
+                    .num_columns(COLS)
+                    .spacing([18.0, 8.0])
+                    .show(ui, |ui| {
+                        let audio: Vec<&crate::disc::cd_audio::CdTrack> = self
+                            .audio_tracks
+                            .as_ref()
+                            .map(|ts| ts.iter().filter(|t| t.is_audio).collect())
+                            .unwrap_or_default();
+                        for (i, t) in audio.iter().enumerate() {
+                            let is_this = active
+                                .as_ref()
+                                .map(|(n, _, _)| *n == t.number)
+                                .unwrap_or(false);
+                            ui.horizontal(|ui| {
+                                if is_this {
+                                    let (icon, hint) = if paused { ("▶", tr!("resume")) } else { ("⏸", tr!("pause")) };
+                                    if named(ui.button(icon), egui::WidgetType::Button, hint).clicked() {
+                                        toggle_pause = true;
+                                    }
+                                    if named(ui.button("■"), egui::WidgetType::Button, tr!("stop")).clicked() {
+                                        do_stop = true;
+                                    }
+                                } else {
+                                    let play = ui.add_enabled(!busy, egui::Button::new("▶"));
+                                    if named(play, egui::WidgetType::Button, tr!("play-track", track = t.number)).clicked() {
+                                        to_play = Some(t.number);
+                                    }
+                                }
 
-        let filter = super::bulk::QueueFilter::default();
-        let (items, error) = match super::bulk::parse_queue_file(&path, &filter) {
-            Ok(items) => (items, None),
-            Err(e) => (Vec::new(), Some(e)),
-        };
-        let resumed_count = count_resumed(&path, &items);
-
-        self.bulk_loader = Some(BulkLoaderDialog {
-            path,
-            item_count: items.len(),
-            resumed_count,
-            reprocess_existing: false,
-            filter: filter.clone(),
-            last_filter: filter,
-            error,
+                                match active.as_ref() {
+                                    Some((n, super::audio::PlaybackState::Playing, elapsed))
+                                        if *n == t.number =>
+                                    {
+                                        let total = f64::from(t.frames) / 75.0;
+                                        let pos = elapsed.unwrap_or(0.0).min(total);
+                                        ui.label(tr!("track-elapsed", track = t.number, pos = pos.round()));
+                                    }
+                                    _ => {
+                                        ui.label(tr!(
+                                            "track-duration",
+                                            track = t.number,
+                                            duration = t.duration_mmss()
+                                        ));
+                                    }
+                                }
+                            });
+                            if (i + 1) % COLS == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
         });
-    }
 
-    /// Re-parse the staged queue file under the dialog's current filter
-    /// settings so the displayed item count reflects what the user will get
-    /// when they click Start. Called when the include-fuzzy toggle or the
-    /// confidence slider changes value.
-    fn refresh_bulk_loader_counts(&mut self) {
-        let Some(dialog) = self.bulk_loader.as_mut() else {
-            return;
-        };
-        if filters_eq(&dialog.filter, &dialog.last_filter) {
-            return;
+        if modal.should_close() {
+            close = true;
         }
-        match super::bulk::parse_queue_file(&dialog.path, &dialog.filter) {
-            Ok(items) => {
-                dialog.item_count = items.len();
-                dialog.resumed_count = count_resumed(&dialog.path, &items);
-                dialog.error = None;
+
+        if toggle_pause {
+            if let Some(pb) = self.audio_playback.as_mut() {
+                pb.set_paused(!paused);
             }
-            Err(e) => {
-                dialog.item_count = 0;
-                dialog.resumed_count = 0;
-                dialog.error = Some(e);
+        }
+        if do_stop {
+            self.audio_playback = None;
+        }
+        if let Some(track) = to_play {
+            if let Some(disc_path) = self.selected_path.clone() {
+                self.audio_error = None;
+                self.audio_playback = Some(super::audio::AudioPlayback::start(disc_path, track));
             }
         }
-        dialog.last_filter = dialog.filter.clone();
+        if close {
+            self.audio_modal_open = false;
+            // Closing the player stops playback (dropping the job tears it down).
+            self.audio_playback = None;
+        }
     }
 
-    /// Activate the queue using the staged loader dialog. Closes the dialog.
-    fn start_bulk_job(&mut self) {
-        let Some(dialog) = self.bulk_loader.take() else {
+    fn poll_hash(&mut self) {
+        if let Some(progress) = self.hash_progress.as_ref() {
+            // Pump the rate tracker each frame while hashing is in flight.
+            if let Ok(p) = progress.lock() {
+                self.hash_rate_tracker.record(p.current_bytes, &p.stage);
+            }
+        }
+        let Some(rx) = self.hash_receiver.as_ref() else {
             return;
         };
-        match super::bulk::BulkQueue::load(
-            dialog.path.clone(),
-            dialog.reprocess_existing,
-            &dialog.filter,
-        ) {
-            Ok(queue) => {
+        match rx.try_recv() {
+            Ok(Ok(hashes)) => {
                 self.log(
-                    LogLevel::Success,
+                    LogLevel::Info,
                     format!(
-                        "Bulk job loaded: {} item(s), {} already done",
-                        queue.total(),
-                        queue.finished_count()
+                        "Hashed {} ({}): sha1={}, md5={}, crc32={}",
+                        hashes.source,
+                        super::progress::format_size(hashes.size_bytes),
+                        &hashes.sha1[..16],
+                        &hashes.md5[..16],
+                        hashes.crc32,
                     ),
                 );
-                self.bulk_queue = Some(queue);
+                self.apply_hash_match(&hashes);
+                self.disc_sha1 = self.selected_path.clone().map(|p| (p, hashes.sha1.clone()));
+                self.hash_progress = None;
+                self.hash_receiver = None;
             }
-            Err(e) => {
-                self.log(LogLevel::Error, format!("Bulk job load failed: {e}"));
+            Ok(Err(e)) => {
+                // "Unsupported format" / "cancelled" are not real failures.
+                if !e.contains("cancelled") && !e.contains("not yet supported") {
+                    // Hashing's redump-cascade benefit is mostly for ISO9660
+                    // data discs that redump catalogs deeply. For HFS / HFS+
+                    // discs the hash tier rarely helps even when it succeeds,
+                    // and the underlying CHD/CUE path frequently rejects them
+                    // outright. Surface that context so the warning isn't
+                    // mysterious.
+                    let suffix = match self
+                        .disc_info
+                        .as_ref()
+                        .and_then(|r| r.as_ref().ok())
+                        .map(|i| i.filesystem)
+                    {
+                        Some(FilesystemType::Hfs) | Some(FilesystemType::HfsPlus) => {
+                            " (normal for HFS/HFS+ discs — redump doesn't catalog Mac \
+                             hashes deeply, so the hash tier wouldn't add much anyway)"
+                        }
+                        _ => "",
+                    };
+                    log::warn!("Hashing failed: {e}{suffix}");
+                    self.log(
+                        LogLevel::Warning,
+                        format!("Hashing failed: {e}{suffix}"),
+                    );
+                } else {
+                    log::info!("Hashing skipped: {e}");
+                }
+                self.hash_progress = None;
+                self.hash_receiver = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.hash_progress = None;
+                self.hash_receiver = None;
             }
         }
     }
 
-    /// Exit bulk mode without affecting the sidecar log.
-    fn close_bulk_job(&mut self) {
-        if self.bulk_queue.is_some() {
-            self.log(LogLevel::Info, "Bulk job closed".to_string());
-        }
-        self.bulk_queue = None;
-        self.bulk_loaded_cursor = None;
-        self.bulk_suppress_cascade = false;
-        self.pending_hash_redump_id = None;
-    }
+    /// Re-run the redump cascade with hash inputs filled in. Replaces the
+    /// existing match list when the hash tier produces a hit, otherwise
+    /// leaves the serial/PVD result in place.
+    fn apply_hash_match(&mut self, hashes: &crate::disc::hasher::TrackHashes) {
+        // Run the lookup first; only touch self.disc_info to apply the
+        // result. Keeps the &mut borrow narrow so we can also call self.log.
+        let mgr = match crate::db::DatabaseManager::new() {
+            Ok(m) => m,
+            Err(e) => {
+                log::debug!("hash match skipped (manager init): {e}");
+                return;
+            }
+        };
+        let conn = match mgr.open() {
+            Ok(c) => c,
+            Err(e) => {
+                log::debug!("hash match skipped: {e}");
+                return;
+            }
+        };
+        let inputs = crate::db::CascadeInputs {
+            track_sha1: Some(&hashes.sha1),
+            track_md5: Some(&hashes.md5),
+            track_crc32: Some(&hashes.crc32),
+            ..Default::default()
+        };
+        let matches = match crate::db::lookup::cascade(&conn, &inputs) {
+            Ok(Some(m)) if !m.is_empty() => m,
+            Ok(_) => {
+                self.log(LogLevel::Info, "Redump: no hash match (kept prior match)");
+                return;
+            }
+            Err(e) => {
+                log::warn!("hash cascade failed: {e}");
+                return;
+            }
+        };
 
-    /// Clear all per-disc state so the central panel returns to the
-    /// "no file selected" view. Used when bulk mode finishes the last
-    /// item — the prior disc shouldn't stay loaded as if the user picked
-    /// it manually. Also cancels any in-flight hashing/search/preview.
-    fn unload_disc(&mut self) {
-        self.cancel_hashing();
-        self.selected_path = None;
-        self.disc_info = None;
-        self.search_query_text.clear();
-        self.manual_url.clear();
-        self.search_results.clear();
-        self.selected_image_index = None;
-        self.preview_texture = None;
-        self.preview_url = None;
-        self.preview_error = None;
-        self.show_search_window = false;
-        self.browse_view.clear();
-        self.show_browse_window = false;
-        self.audio_tracks = None;
-        self.audio_playback = None;
-        self.audio_modal_open = false;
-        self.audio_error = None;
-    }
+        let head = matches[0].clone();
+        let head_summary = format!(
+            "Redump hash match: {} [#{}] via {:?}",
+            head.title, head.redump_id, head.matched_via
+        );
 
-    /// Per-frame driver for bulk mode. If the cursor advanced to a new item,
-    /// either auto-skip it (existing art + reprocess off) or load the disc
-    /// and inject the queue's chosen match. After loading, the artwork
-    /// search is auto-triggered for exact matches.
-    fn tick_bulk(&mut self) {
-        let Some(queue) = self.bulk_queue.as_ref() else {
-            return;
-        };
-        if queue.is_complete() {
-            // First tick after the final item resolved: unload the disc so
-            // the central panel doesn't keep showing the last-processed
-            // file. The banner stays up (with the green "complete" notice)
-            // until the user clicks Exit bulk.
-            if self.bulk_loaded_cursor.is_some() {
-                self.unload_disc();
-                self.bulk_loaded_cursor = None;
+        // In bulk mode the queue's match is authoritative. Record the
+        // disagreement (if any) on the active queue item so it lands in the
+        // done log when the user saves/skips, but don't disturb the UI.
+        if let Some(queue) = self.bulk_queue.as_mut() {
+            if let Some(item) = queue.current() {
+                let queue_id = item.best.redump_id;
+                if queue_id != Some(head.redump_id) {
+                    self.log(
+                        LogLevel::Info,
+                        format!(
+                            "Bulk: hash points to #{} (queue locked to #{:?}); recorded as disagreement",
+                            head.redump_id, queue_id
+                        ),
+                    );
+                } else {
+                    self.log(LogLevel::Info, "Bulk: hash confirms queue match");
+                }
             }
-            return;
-        }
-        let cursor = queue.cursor;
-        if Some(cursor) == self.bulk_loaded_cursor {
+            self.pending_hash_redump_id = Some(head.redump_id);
             return;
         }
 
-        // Snapshot what we need; later mutating self requires releasing the
-        // queue borrow.
-        let Some(item) = queue.current() else {
-            return;
-        };
-        let path = std::path::PathBuf::from(&item.file);
-        let has_existing = item.has_existing_art;
-        let reprocess = queue.reprocess_existing;
-        let redump_id = item.best.redump_id;
-        let match_type = item.best.match_type.clone();
-        let display_title = item.best.title.clone();
-        let best_score = item.best.score;
-        let alt_count = item.alternates.len();
-        let already_resolved = queue
-            .statuses
-            .get(cursor)
-            .map(|s| *s != super::bulk::ItemStatus::Pending)
-            .unwrap_or(false);
-        log::debug!(
-            "bulk: tick advance cursor={} file={} best=#{:?} \"{}\" ({}, score={:?}) alts={} \
-             has_existing_art={} reprocess_existing={} already_resolved={}",
-            cursor, path.display(), redump_id, display_title, match_type, best_score,
-            alt_count, has_existing, reprocess, already_resolved,
-        );
+        if let Some(Ok(info)) = self.disc_info.as_mut() {
+            info.redump_matches = Some(matches);
+            // Hash hit beats any fuzzy candidates from the earlier pass —
+            // drop the stale list so the UI doesn't show both.
+            info.fuzzy_matches = None;
+        }
+        self.log(LogLevel::Success, head_summary);
+    }
 
-        // Existing-art auto-skip — but only for items that haven't been
-        // resolved yet. If the user explicitly went Back to a Saved /
-        // Skipped / existing-art item, load it so they can redo the
-        // choice; otherwise Back is useless on those items.
-        if has_existing && !reprocess && !already_resolved {
-            self.log(
-                LogLevel::Info,
-                format!("Bulk: skipping (existing art): {}", path.display()),
-            );
-            self.record_bulk_done("existing-art", None);
-            // Don't mark this cursor as loaded; let advance kick us to the
-            // next pending item on the next tick.
-            return;
+    /// Log the outcome of the background DB update.
+    fn on_db_updated(&mut self, result: Result<crate::db::UpdateOutcome, String>) {
+        self.db_update_done = true;
+        match result {
+            Ok(outcome) => {
+                match &outcome {
+                    crate::db::UpdateOutcome::UpToDate { .. } => {
+                        self.log(LogLevel::Info, "Lookup DB is up to date");
+                    }
+                    crate::db::UpdateOutcome::Updated { local_path, .. } => {
+                        self.log(
+                            LogLevel::Success,
+                            format!("Lookup DB updated: {}", local_path.display()),
+                        );
+                    }
+                    crate::db::UpdateOutcome::OfflineUsingCached { error, .. } => {
+                        self.log(
+                            LogLevel::Warning,
+                            format!("Lookup DB update skipped (offline): {error}"),
+                        );
+                    }
+                    crate::db::UpdateOutcome::OfflineNoCache { error } => {
+                        self.log(
+                            LogLevel::Warning,
+                            format!("Lookup DB unavailable (offline, no cache): {error}"),
+                        );
+                    }
+                }
+            }
+            Err(e) => self.log(LogLevel::Error, format!("Lookup DB update failed: {e}")),
         }
+    }
 
-        if already_resolved {
+    /// Add a log message
+    /// Log a failed search, preview or save, and pop it up as a toast so
+    /// it's seen with the Log window closed.
+    fn log_failure(&mut self, title: String, message: String) {
+        self.toasts.error(title, message.clone());
+        self.log(LogLevel::Error, message);
+    }
+
+    fn log(&mut self, level: LogLevel, message: impl Into<String>) {
+        self.log_messages.push_back(LogMessage::new(level, message));
+        while self.log_messages.len() > LOG_CAPACITY {
+            self.log_messages.pop_front();
+        }
+    }
+
+    /// Process a selected file
+    fn process_file(&mut self, path: PathBuf) {
+        // Pre-flight: detect cue files whose BINs are missing and surface a
+        // "delete cue?" prompt before any of the rest of the load runs. The
+        // prompt is modal — the disc isn't loaded until the user resolves
+        // it (or, in bulk mode, the auto-skip timeout fires).
+        let scan = crate::disc::scan_cue_references(&path);
+        if !scan.missing.is_empty()
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case("cue"))
+                .unwrap_or(false)
+        {
+            self.broken_cue_prompt = Some(BrokenCuePrompt {
+                cue_path: path.clone(),
+                missing: scan.missing,
+                total_refs: scan.total_refs,
+                in_bulk: self.bulk_queue.is_some(),
+                opened_at: std::time::Instant::now(),
+            });
             self.log(
-                LogLevel::Info,
+                LogLevel::Warning,
                 format!(
-                    "Bulk: reopening previously-resolved item {} for re-processing",
+                    "Broken cue detected: {} (missing data file(s))",
                     path.display()
                 ),
             );
+            return;
         }
 
-        // Reset per-item transient state before kicking off the load.
-        self.pending_hash_redump_id = None;
-        self.bulk_loaded_cursor = Some(cursor);
-        self.bulk_suppress_cascade = true;
-        self.process_file(path.clone());
-        self.bulk_suppress_cascade = false;
-
-        // Inject the queue's chosen match in place of whatever the disc-read
-        // path would have produced. We pass the queue's best Record as a
-        // fallback so a missing entry in the local DB (e.g. CSV built
-        // against a different snapshot) still seeds redump_matches with
-        // the title — otherwise the search query would silently revert to
-        // the filename.
-        let queue_best = self
-            .bulk_queue
-            .as_ref()
-            .and_then(|q| q.current())
-            .map(|it| it.best.clone());
-        if let Some(rid) = redump_id {
-            self.inject_bulk_match(rid, &display_title, queue_best.as_ref());
+        self.log(LogLevel::Info, format!("Processing: {}", path.display()));
+        self.selected_path = Some(path.clone());
+        crate::logging::crash::set_current_disc(Some(&path));
+        // Bulk jobs walk whole folders; only discs opened by hand are recent.
+        if self.bulk_queue.is_none() {
+            self.recent.add_file(&path);
+            self.save_recent();
         }
 
-        // For exact matches the queue is trusted enough to auto-trigger the
-        // artwork search. Fuzzy matches go through a confirm step in the
-        // next phase (Phase 4); for now they also auto-search so the flow
-        // is exercisable.
-        self.update_search_query_from_disc();
-        if !self.search_query_text.is_empty() {
-            let q = self.search_query_text.clone();
-            self.start_search(&q);
-        }
-        let _ = match_type; // reserved for the fuzzy-confirm step in Phase 4.
-    }
+        // Clear previous search state
+        self.search_query_text.clear();
+        self.manual_url.clear();
+        self.search_results.clear();
+        self.selected_image_index = None;
+        self.preview_texture = None;
+        self.preview_image = None;
+        self.preview_render_source = None;
+        self.preview_url = None;
+        self.existing_art = None;
+        self.existing_art_stale = true;
+        self.confirm_replace = false;
+        self.output_path_edit = None;
+        self.auto_save_url = None;
+        self.audio_tracks = None;
+        self.audio_playback = None; // Drop stops any in-flight playback.
+        self.audio_modal_open = false;
+        self.audio_error = None;
+        self.tracklist = None;
+        self.tracklist_receiver = None;
+        self.cd_text = None;
+        self.volume_icon = None;
+        self.volume_icon_receiver = None;
+        self.entry_counts = None;
+        self.entry_counts_receiver = None;
 
-    /// Replace `disc_info.redump_matches` with a single RedumpMatch fetched
-    /// by `redump_id`, so the UI reflects the queue's pre-decided answer
-    /// without re-running the cascade. If the local DB doesn't have the
-    /// id (e.g. it's older than the snapshot the CSV was built against),
-    /// synthesize a minimal match from `queue_best` so the search query
-    /// still uses the correct title.
-    fn inject_bulk_match(
-        &mut self,
-        redump_id: i64,
-        fallback_title: &str,
-        queue_best: Option<&super::bulk::Record>,
-    ) {
-        let result = (|| -> Result<Option<crate::db::RedumpMatch>, String> {
-            let mgr =
-                crate::db::DatabaseManager::new().map_err(|e| e.to_string())?;
-            let conn = mgr.open().map_err(|e| e.to_string())?;
-            crate::db::by_redump_id(&conn, redump_id).map_err(|e| e.to_string())
-        })();
+        // Clear browse view state
+        self.browse_view.clear();
+        self.show_browse_window = false;
 
-        let m = match result {
-            Ok(Some(m)) => {
-                log::debug!(
-                    "bulk: inject local DB hit #{} \"{}\" matched_via={:?}",
-                    m.redump_id, m.title, m.matched_via
-                );
-                m
+        // Store log messages in a vector to add after disc reading
+        let log_messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log_messages_clone = log_messages.clone();
+        
+        // Set up logging callback to capture disc reading logs
+        crate::disc::set_log_callback(std::sync::Arc::new(std::sync::Mutex::new(
+            move |msg: String| {
+                if let Ok(mut messages) = log_messages_clone.lock() {
+                    messages.push(msg);
+                }
             }
-            Ok(None) => {
+        )));
+
+        match DiscReader::read(&path) {
+            Ok(info) => {
+                // Add collected log messages
+                if let Ok(messages) = log_messages.lock() {
+                    for msg in messages.iter() {
+                        self.log(LogLevel::Info, msg.clone());
+                    }
+                }
+                
                 self.log(
-                    LogLevel::Warning,
-                    format!(
-                        "Bulk: redump #{redump_id} ({fallback_title}) not found in local DB; \
-                         using queue's title for search"
-                    ),
-                );
-                let Some(rec) = queue_best else { return };
-                log::debug!(
-                    "bulk: inject synthesized from queue Record #{:?} \"{}\"",
-                    rec.redump_id, rec.title
+                    LogLevel::Success,
+                    format!("Successfully read disc: {}", info.title),
                 );
-                synth_match_from_record(rec)
+                let mut info = info;
+                self.enrich_with_redump(&mut info);
+                // Cache the CD audio track list for CHD and BIN/CUE images so the
+                // disc panel can offer per-track playback without re-parsing the
+                // image each frame.
+                self.audio_tracks = if matches!(info.format, DiscFormat::Chd | DiscFormat::BinCue) {
+                    crate::disc::cd_audio::read_tracks(&info.path).ok()
+                } else {
+                    None
+                };
+                self.cd_text = if matches!(info.format, DiscFormat::BinCue) {
+                    crate::disc::read_cd_text(&info.path)
+                } else {
+                    None
+                };
+                // Pop the audio player out automatically when the disc has audio.
+                self.audio_error = None;
+                self.audio_modal_open = self
+                    .audio_tracks
+                    .as_ref()
+                    .is_some_and(|tracks| tracks.iter().any(|t| t.is_audio));
+                self.chd_artwork_found = chd_artwork::has_artwork(&info.path);
+                // Bulk jobs move on before a lookup would be looked at
+                if self.bulk_queue.is_none() {
+                    if let Some(ref toc) = info.toc {
+                        self.start_tracklist_lookup(toc.musicbrainz_id(), toc.to_toc_string());
+                    }
+                    if info.filesystem != FilesystemType::Unknown {
+                        self.start_volume_icon(info.clone());
+                    }
+                    if info.pvd.is_some() {
+                        self.start_entry_counts(info.clone());
+                    }
+                }
+                self.disc_info = Some(Ok(info));
             }
             Err(e) => {
-                self.log(
-                    LogLevel::Warning,
-                    format!("Bulk: failed to fetch redump #{redump_id}: {e}"),
-                );
-                let Some(rec) = queue_best else { return };
-                log::debug!(
-                    "bulk: inject synthesized after DB error from queue Record #{:?} \"{}\"",
-                    rec.redump_id, rec.title
-                );
-                synth_match_from_record(rec)
-            }
-        };
+                // Add collected log messages even on error
+                if let Ok(messages) = log_messages.lock() {
+                    for msg in messages.iter() {
+                        self.log(LogLevel::Info, msg.clone());
+                    }
+                }
+                
+                let error_str = e.to_string();
+                // Check if this is likely an HFS/HFS+ disc or other non-ISO9660 format
+                let is_filesystem_error = error_str.contains("Primary Volume Descriptor")
+                    || error_str.contains("HFS")
+                    || error_str.contains("Parse error");
+
+                if is_filesystem_error {
+                    // Fall back to filename-only parsing for HFS and other unsupported filesystems
+                    self.log(
+                        LogLevel::Warning,
+                        format!("Could not read disc structure (may be HFS/Mac format), using filename only"),
+                    );
+
+                    // Create a minimal DiscInfo from filename
+                    let parsed = parse_filename(&path);
+                    let format = DiscFormat::from_path(&path).unwrap_or(DiscFormat::Iso);
+
+                    let fallback_info = DiscInfo {
+                        path: path.clone(),
+                        format,
+                        filesystem: FilesystemType::Unknown,
+                        volume_label: None,
+                        title: parsed.title.clone(),
+                        parsed_filename: parsed,
+                        confidence: ConfidenceLevel::Low,
+                        pvd: None,
+                        toc: None,
+                        hfs_mdb: None,
+                        hfsplus_header: None,
+                        redump_matches: None,
+                        fuzzy_matches: None,
+                        image: None,
+                    };
+
+                    let mut fallback_info = fallback_info;
+                    self.enrich_with_redump(&mut fallback_info);
+                    self.chd_artwork_found = chd_artwork::has_artwork(&fallback_info.path);
+                    self.disc_info = Some(Ok(fallback_info));
+                } else {
+                    self.log(LogLevel::Error, format!("Error reading disc: {}", e));
+                    self.disc_info = Some(Err(error_str));
+                }
+            }
+        }
+        
+        // Clear the log callback after processing
+        crate::disc::clear_log_callback();
+
+        // Kick off track hashing in the background. The disc info is already
+        // displayed; when hashing finishes we'll re-run the cascade with the
+        // hash tier active and refresh `redump_matches`.
+        self.start_hashing();
+    }
+
+    /// Prompt the user for a `fuzzy_scan --queue` JSON file and stage it in
+    /// the loader dialog. The actual queue activation happens when they
+    /// click "Start" in the dialog.
+    fn open_bulk_job_picker(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("queue-json-or-csv"), &["json", "csv"])
+            .add_filter(tr!("queue-json"), &["json"])
+            .add_filter(tr!("fuzzy-scan-csv"), &["csv"])
+            .add_filter(tr!("all-files"), &["*"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        let filter = super::bulk::QueueFilter::default();
+        let (items, error) = match super::bulk::parse_queue_file(&path, &filter) {
+            Ok(items) => (items, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        let resumed_count = count_resumed(&path, &items);
+
+        self.bulk_loader = Some(BulkLoaderDialog {
+            path,
+            item_count: items.len(),
+            resumed_count,
+            reprocess_existing: false,
+            filter: filter.clone(),
+            last_filter: filter,
+            error,
+        });
+    }
+
+    /// Re-parse the staged queue file under the dialog's current filter
+    /// settings so the displayed item count reflects what the user will get
+    /// when they click Start. Called when the include-fuzzy toggle or the
+    /// confidence slider changes value.
+    fn refresh_bulk_loader_counts(&mut self) {
+        let Some(dialog) = self.bulk_loader.as_mut() else {
+            return;
+        };
+        if filters_eq(&dialog.filter, &dialog.last_filter) {
+            return;
+        }
+        match super::bulk::parse_queue_file(&dialog.path, &dialog.filter) {
+            Ok(items) => {
+                dialog.item_count = items.len();
+                dialog.resumed_count = count_resumed(&dialog.path, &items);
+                dialog.error = None;
+            }
+            Err(e) => {
+                dialog.item_count = 0;
+                dialog.resumed_count = 0;
+                dialog.error = Some(e);
+            }
+        }
+        dialog.last_filter = dialog.filter.clone();
+    }
+
+    /// Activate the queue using the staged loader dialog. Closes the dialog.
+    fn start_bulk_job(&mut self) {
+        let Some(dialog) = self.bulk_loader.take() else {
+            return;
+        };
+        match super::bulk::BulkQueue::load(
+            dialog.path.clone(),
+            dialog.reprocess_existing,
+            &dialog.filter,
+        ) {
+            Ok(queue) => {
+                self.log(
+                    LogLevel::Success,
+                    format!(
+                        "Bulk job loaded: {} item(s), {} already done",
+                        queue.total(),
+                        queue.finished_count()
+                    ),
+                );
+                self.bulk_queue = Some(queue);
+            }
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Bulk job load failed: {e}"));
+            }
+        }
+    }
+
+    /// Exit bulk mode without affecting the sidecar log.
+    fn close_bulk_job(&mut self) {
+        if self.bulk_queue.is_some() {
+            self.log(LogLevel::Info, "Bulk job closed".to_string());
+        }
+        self.bulk_queue = None;
+        self.bulk_loaded_cursor = None;
+        self.bulk_suppress_cascade = false;
+        self.pending_hash_redump_id = None;
+    }
+
+    /// Clear all per-disc state so the central panel returns to the
+    /// "no file selected" view. Used when bulk mode finishes the last
+    /// item — the prior disc shouldn't stay loaded as if the user picked
+    /// it manually. Also cancels any in-flight hashing/search/preview.
+    fn unload_disc(&mut self) {
+        self.cancel_hashing();
+        self.selected_path = None;
+        crate::logging::crash::set_current_disc(None);
+        self.disc_info = None;
+        self.chd_artwork_found = false;
+        self.search_query_text.clear();
+        self.manual_url.clear();
+        self.search_results.clear();
+        self.selected_image_index = None;
+        self.preview_texture = None;
+        self.preview_image = None;
+        self.preview_render_source = None;
+        self.preview_url = None;
+        self.preview_error = None;
+        self.candidate_info = None;
+        self.candidate_info_receiver = None;
+        self.existing_art = None;
+        self.confirm_replace = false;
+        self.output_path_edit = None;
+        self.auto_save_url = None;
+        self.show_search_window = false;
+        self.browse_view.clear();
+        self.show_browse_window = false;
+        self.audio_tracks = None;
+        self.audio_playback = None;
+        self.audio_modal_open = false;
+        self.audio_error = None;
+        self.tracklist = None;
+        self.tracklist_receiver = None;
+        self.cd_text = None;
+        self.volume_icon = None;
+        self.volume_icon_receiver = None;
+        self.entry_counts = None;
+        self.entry_counts_receiver = None;
+    }
+
+    /// Per-frame driver for bulk mode. If the cursor advanced to a new item,
+    /// either auto-skip it (existing art + reprocess off) or load the disc
+    /// and inject the queue's chosen match. After loading, the artwork
+    /// search is auto-triggered for exact matches.
+    fn tick_bulk(&mut self) {
+        let Some(queue) = self.bulk_queue.as_ref() else {
+            return;
+        };
+        if queue.is_complete() {
+            // First tick after the final item resolved: unload the disc so
+            // the central panel doesn't keep showing the last-processed
+            // file. The banner stays up (with the green "complete" notice)
+            // until the user clicks Exit bulk.
+            if self.bulk_loaded_cursor.is_some() {
+                self.unload_disc();
+                self.bulk_loaded_cursor = None;
+            }
+            return;
+        }
+        let cursor = queue.cursor;
+        if Some(cursor) == self.bulk_loaded_cursor {
+            return;
+        }
+
+        // Snapshot what we need; later mutating self requires releasing the
+        // queue borrow.
+        let Some(item) = queue.current() else {
+            return;
+        };
+        let path = std::path::PathBuf::from(&item.file);
+        let has_existing = item.has_existing_art;
+        let reprocess = queue.reprocess_existing;
+        let redump_id = item.best.redump_id;
+        let match_type = item.best.match_type.clone();
+        let display_title = item.best.title.clone();
+        let best_score = item.best.score;
+        let alt_count = item.alternates.len();
+        let already_resolved = queue
+            .statuses
+            .get(cursor)
+            .map(|s| *s != super::bulk::ItemStatus::Pending)
+            .unwrap_or(false);
+        log::debug!(
+            "bulk: tick advance cursor={} file={} best=#{:?} \"{}\" ({}, score={:?}) alts={} \
+             has_existing_art={} reprocess_existing={} already_resolved={}",
+            cursor, path.display(), redump_id, display_title, match_type, best_score,
+            alt_count, has_existing, reprocess, already_resolved,
+        );
+
+        // Existing-art auto-skip — but only for items that haven't been
+        // resolved yet. If the user explicitly went Back to a Saved /
+        // Skipped / existing-art item, load it so they can redo the
+        // choice; otherwise Back is useless on those items.
+        if has_existing && !reprocess && !already_resolved {
+            self.log(
+                LogLevel::Info,
+                format!("Bulk: skipping (existing art): {}", path.display()),
+            );
+            self.record_bulk_done("existing-art", None);
+            // Don't mark this cursor as loaded; let advance kick us to the
+            // next pending item on the next tick.
+            return;
+        }
+
+        if already_resolved {
+            self.log(
+                LogLevel::Info,
+                format!(
+                    "Bulk: reopening previously-resolved item {} for re-processing",
+                    path.display()
+                ),
+            );
+        }
+
+        // Reset per-item transient state before kicking off the load.
+        self.pending_hash_redump_id = None;
+        self.bulk_loaded_cursor = Some(cursor);
+        self.bulk_suppress_cascade = true;
+        self.process_file(path.clone());
+        self.bulk_suppress_cascade = false;
+
+        // Inject the queue's chosen match in place of whatever the disc-read
+        // path would have produced. We pass the queue's best Record as a
+        // fallback so a missing entry in the local DB (e.g. CSV built
+        // against a different snapshot) still seeds redump_matches with
+        // the title — otherwise the search query would silently revert to
+        // the filename.
+        let queue_best = self
+            .bulk_queue
+            .as_ref()
+            .and_then(|q| q.current())
+            .map(|it| it.best.clone());
+        if let Some(rid) = redump_id {
+            self.inject_bulk_match(rid, &display_title, queue_best.as_ref());
+        }
+
+        // For exact matches the queue is trusted enough to auto-trigger the
+        // artwork search. Fuzzy matches go through a confirm step in the
+        // next phase (Phase 4); for now they also auto-search so the flow
+        // is exercisable.
+        self.update_search_query_from_disc();
+        if !self.search_query_text.is_empty() {
+            let q = self.search_query_text.clone();
+            self.start_search(&q);
+        }
+        let _ = match_type; // reserved for the fuzzy-confirm step in Phase 4.
+    }
+
+    /// Replace `disc_info.redump_matches` with a single RedumpMatch fetched
+    /// by `redump_id`, so the UI reflects the queue's pre-decided answer
+    /// without re-running the cascade. If the local DB doesn't have the
+    /// id (e.g. it's older than the snapshot the CSV was built against),
+    /// synthesize a minimal match from `queue_best` so the search query
+    /// still uses the correct title.
+    fn inject_bulk_match(
+        &mut self,
+        redump_id: i64,
+        fallback_title: &str,
+        queue_best: Option<&super::bulk::Record>,
+    ) {
+        let result = (|| -> Result<Option<crate::db::RedumpMatch>, String> {
+            let mgr =
+                crate::db::DatabaseManager::new().map_err(|e| e.to_string())?;
+            let conn = mgr.open().map_err(|e| e.to_string())?;
+            crate::db::by_redump_id(&conn, redump_id).map_err(|e| e.to_string())
+        })();
+
+        let m = match result {
+            Ok(Some(m)) => {
+                log::debug!(
+                    "bulk: inject local DB hit #{} \"{}\" matched_via={:?}",
+                    m.redump_id, m.title, m.matched_via
+                );
+                m
+            }
+            Ok(None) => {
+                self.log(
+                    LogLevel::Warning,
+                    format!(
+                        "Bulk: redump #{redump_id} ({fallback_title}) not found in local DB; \
+                         using queue's title for search"
+                    ),
+                );
+                let Some(rec) = queue_best else { return };
+                log::debug!(
+                    "bulk: inject synthesized from queue Record #{:?} \"{}\"",
+                    rec.redump_id, rec.title
+                );
+                synth_match_from_record(rec)
+            }
+            Err(e) => {
+                self.log(
+                    LogLevel::Warning,
+                    format!("Bulk: failed to fetch redump #{redump_id}: {e}"),
+                );
+                let Some(rec) = queue_best else { return };
+                log::debug!(
+                    "bulk: inject synthesized after DB error from queue Record #{:?} \"{}\"",
+                    rec.redump_id, rec.title
+                );
+                synth_match_from_record(rec)
+            }
+        };
+
+        let log_line = format!(
+            "Bulk: locked match {} [#{}] via {:?}",
+            m.title, m.redump_id, m.matched_via
+        );
+        if let Some(Ok(info)) = self.disc_info.as_mut() {
+            info.redump_matches = Some(vec![m]);
+            info.fuzzy_matches = None;
+        }
+        self.log(LogLevel::Success, log_line);
+    }
+
+    /// Put the cursor in the search query field that's showing.
+    fn focus_search_query(&self, ctx: &egui::Context) {
+        let field = if self.show_search_window {
+            SEARCH_WINDOW_QUERY_ID
+        } else {
+            SEARCH_QUERY_ID
+        };
+        ctx.memory_mut(|m| m.request_focus(egui::Id::new(field)));
+    }
+
+    /// Commands the palette offers right now: those that would do
+    /// something, and none that would disturb a bulk job.
+    fn palette_commands(&self) -> Vec<Command> {
+        let has_disc = matches!(self.disc_info, Some(Ok(_)));
+        let bulk = self.bulk_queue.is_some();
+        let fix_busy = self.events.is_running(Job::FixScan) || self.events.is_running(Job::FixRun);
+        Command::ALL
+            .into_iter()
+            .filter(|command| match command {
+                Command::OpenFile | Command::ScanFolder | Command::BulkJob => !bulk,
+                Command::Watch => {
+                    self.folder_watch.is_some()
+                        || !crate::config::get_config().watch.folders.is_empty()
+                }
+                Command::FixArtwork => !bulk && !fix_busy,
+                Command::Search => has_disc && !self.search_in_progress,
+                Command::FocusSearch => has_disc,
+                Command::Save => self.preview_texture.is_some() && !self.export_in_progress,
+                Command::UndoExport => !self.export_history.is_empty() && !self.export_in_progress,
+                Command::NewTab => self.can_switch_tabs(),
+                Command::ToggleLog | Command::OpenLogFile => true,
+                Command::Queue => !self.processing_queue.entries.is_empty(),
+                Command::ImportPackZip | Command::ImportPackFolder => {
+                    !self.events.is_running(Job::PackImport)
+                }
+                Command::Settings => self.settings_dialog.is_none(),
+            })
+            .collect()
+    }
+
+    /// Command palette, while it's open; runs what's picked once it closes.
+    fn render_command_palette(&mut self, ctx: &egui::Context) {
+        let available = self.palette_commands();
+        let Some(palette) = self.command_palette.as_mut() else {
+            return;
+        };
+        let mut open = true;
+        let picked = palette.show(ctx, &available, &mut open);
+        if !open {
+            self.command_palette = None;
+        }
+        match picked {
+            Some(Command::OpenFile) => self.open_file_picker(),
+            Some(Command::ScanFolder) => self.open_folder_scan_picker(),
+            Some(Command::Watch) => self.toggle_watch(ctx),
+            Some(Command::BulkJob) => self.open_bulk_job_picker(),
+            Some(Command::FixArtwork) => self.open_fix_artwork_picker(),
+            Some(Command::Search) => self.search_disc(),
+            Some(Command::FocusSearch) => self.focus_search_query(ctx),
+            Some(Command::Save) => self.save_preview(),
+            Some(Command::UndoExport) => self.undo_last_export(),
+            Some(Command::NewTab) => self.new_tab(),
+            Some(Command::ToggleLog) => self.show_log_window = !self.show_log_window,
+            Some(Command::OpenLogFile) => self.open_log_file(),
+            Some(Command::Queue) => self.show_queue_window = true,
+            Some(Command::ImportPackZip) => self.open_pack_zip_picker(),
+            Some(Command::ImportPackFolder) => self.open_pack_folder_picker(),
+            Some(Command::Settings) => self.open_settings(),
+            None => {}
+        }
+    }
+
+    /// Keyboard shortcuts for the single-disc workflow:
+    ///   Ctrl+Shift+P — the command palette.
+    ///   Ctrl+O     — open a disc image.
+    ///   Ctrl+S     — Download & Save the previewed image.
+    ///   Ctrl+L     — show or hide the log.
+    ///   Ctrl+F     — focus the search query.
+    ///   Up/Down    — move through the search results, previewing each.
+    /// Cmd stands in for Ctrl on macOS. Enter in a search field runs the
+    /// search; that's handled where the field is drawn. Runs before the
+    /// bulk hotkeys so Ctrl+S isn't also taken as S (skip).
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let (palette, open, save, log, new_tab, search) = ctx.input_mut(|i| {
+            (
+                i.consume_shortcut(&PALETTE_SHORTCUT),
+                i.consume_shortcut(&OPEN_FILE_SHORTCUT),
+                i.consume_shortcut(&SAVE_SHORTCUT),
+                i.consume_shortcut(&LOG_SHORTCUT),
+                i.consume_shortcut(&NEW_TAB_SHORTCUT),
+                i.consume_shortcut(&SEARCH_SHORTCUT),
+            )
+        });
+        if palette {
+            self.command_palette = match self.command_palette {
+                Some(_) => None,
+                None => Some(CommandPalette::default()),
+            };
+            ctx.request_repaint();
+        }
+        if new_tab && self.can_switch_tabs() {
+            self.new_tab();
+        }
+        if log {
+            self.show_log_window = !self.show_log_window;
+        }
+        if save {
+            self.save_preview();
+        }
+        if open {
+            self.open_file_picker();
+        }
+        if search {
+            self.focus_search_query(ctx);
+        }
+
+        // Arrow keys belong to a focused text field or slider
+        if !self.show_search_window
+            || self.search_results.is_empty()
+            || ctx.memory(|m| m.focused().is_some())
+        {
+            return;
+        }
+        let (down, up) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            )
+        });
+        // Step over results from hidden sources
+        let shown: Vec<usize> = (0..self.search_results.len())
+            .filter(|&i| self.result_shown(i))
+            .collect();
+        let Some(&first) = shown.first() else {
+            return;
+        };
+        let at = self.selected_image_index.and_then(|i| shown.iter().position(|&s| s == i));
+        let next = match (at, down, up) {
+            (None, true, _) | (None, _, true) => first,
+            (Some(i), true, false) => shown[(i + 1).min(shown.len() - 1)],
+            (Some(i), false, true) => shown[i.saturating_sub(1)],
+            _ => return,
+        };
+        if self.selected_image_index != Some(next) {
+            self.selected_image_index = Some(next);
+            self.pending_preview = self
+                .search_results
+                .get(next)
+                .map(|r| (r.image_url.clone(), std::time::Instant::now() + PREVIEW_DEBOUNCE));
+            self.scroll_to_selected_result = true;
+        }
+    }
+
+    /// Preview the result the arrow keys stopped on once they've been still
+    /// for [`PREVIEW_DEBOUNCE`]. Dropped if the results changed meanwhile
+    /// (new search, other tab).
+    fn poll_pending_preview(&mut self, ctx: &egui::Context) {
+        let Some((_, due)) = self.pending_preview else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        if now < due {
+            ctx.request_repaint_after(due - now);
+            return;
+        }
+        let Some((url, _)) = self.pending_preview.take() else {
+            return;
+        };
+        let still_selected = self
+            .selected_image_index
+            .and_then(|i| self.search_results.get(i))
+            .is_some_and(|r| r.image_url == url);
+        if still_selected {
+            self.load_preview(&url);
+        }
+    }
+
+    /// Bulk-mode keyboard shortcuts:
+    ///   Enter — save the currently focused image (or re-trigger the save
+    ///           button) — handled by the existing Save button path.
+    ///   S     — skip the current item (record as 'skipped', advance).
+    ///   B     — go back to the previous item (no log entry; cursor only).
+    ///   Esc   — exit bulk mode entirely.
+    /// Hotkeys are no-ops outside bulk mode and when the loader dialog is
+    /// open. We also skip keys consumed by a focused text input so the
+    /// search-query field stays editable.
+    fn handle_bulk_hotkeys(&mut self, ctx: &egui::Context) {
+        if self.bulk_queue.is_none() {
+            return;
+        }
+        if self.bulk_loader.is_some() {
+            return;
+        }
+        let text_focused = ctx.memory(|m| m.focused().is_some());
+        if text_focused {
+            return;
+        }
+
+        let (skip, back, exit, enter) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::S),
+                i.key_pressed(egui::Key::B),
+                i.key_pressed(egui::Key::Escape),
+                i.key_pressed(egui::Key::Enter),
+            )
+        });
+
+        if exit {
+            self.close_bulk_job();
+            return;
+        }
+        if skip {
+            self.log(LogLevel::Info, "Bulk: skipped");
+            self.record_bulk_done("skipped", None);
+            return;
+        }
+        if back {
+            if let Some(queue) = self.bulk_queue.as_mut() {
+                queue.back();
+            }
+            self.bulk_loaded_cursor = None;
+            self.pending_hash_redump_id = None;
+            return;
+        }
+        if enter {
+            // Save currently-previewed image, if any. The bulk advance
+            // happens inside poll_export on success.
+            if self.export_in_progress {
+                return;
+            }
+            let url = self.preview_url.clone();
+            if let (Some(url), Some(disc_path)) = (url, self.selected_path.clone()) {
+                if let Some(path) = self.claim_output_path(&disc_path, ArtworkRole::Front) {
+                    self.start_export(&url, &path, ArtworkRole::Front);
+                }
+            } else {
+                self.log(
+                    LogLevel::Info,
+                    "Bulk: Enter pressed but no image is selected for preview",
+                );
+            }
+        }
+    }
+
+    /// Append a done-log entry for the current bulk item and advance the
+    /// cursor. Caller chooses the status string (saved/skipped/existing-art).
+    fn record_bulk_done(&mut self, status: &str, image_url: Option<String>) {
+        // Build the entry while holding the queue borrow; release it before
+        // we touch self.log.
+        let (write_err, item_status_for_advance) = {
+            let Some(queue) = self.bulk_queue.as_mut() else {
+                return;
+            };
+            let Some(item) = queue.current() else {
+                return;
+            };
+            let queue_redump_id = item.best.redump_id;
+            let file = item.file.clone();
+            let hash_redump_id = self
+                .pending_hash_redump_id
+                .filter(|h| Some(*h) != queue_redump_id);
+            let entry = super::bulk::DoneEntry {
+                file,
+                status: status.to_string(),
+                queue_redump_id,
+                hash_redump_id,
+                image_url,
+                ts: crate::logging::now_iso8601(),
+            };
+            let item_status = match status {
+                "saved" => super::bulk::ItemStatus::Saved,
+                "skipped" => super::bulk::ItemStatus::Skipped,
+                "existing-art" => super::bulk::ItemStatus::ExistingArt,
+                _ => super::bulk::ItemStatus::Skipped,
+            };
+            let err = queue.record(entry, item_status).err();
+            queue.advance();
+            (err, item_status)
+        };
+
+        if let Some(e) = write_err {
+            self.log(LogLevel::Error, format!("Bulk: done-log write failed: {e}"));
+        }
+        let _ = item_status_for_advance;
+        self.bulk_loaded_cursor = None;
+        self.pending_hash_redump_id = None;
+    }
+
+    /// Render the broken-cue confirmation modal when one is staged. In bulk
+    /// mode shows a countdown that auto-dismisses with "Keep" after
+    /// `BULK_AUTO_SKIP_SECS` so a long-running queue isn't stuck behind a
+    /// prompt the user isn't around to answer.
+    fn render_broken_cue_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = self.broken_cue_prompt.as_ref() else {
+            return;
+        };
+        let cue = prompt.cue_path.clone();
+        let missing = prompt.missing.clone();
+        let total = prompt.total_refs;
+        let in_bulk = prompt.in_bulk;
+        let elapsed = prompt.opened_at.elapsed().as_secs();
+        let remaining = BULK_AUTO_SKIP_SECS.saturating_sub(elapsed);
+
+        let mut delete_clicked = false;
+        let mut keep_clicked = false;
+
+        egui::Window::new(tr!("broken-cue-file"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(cue.display().to_string()).strong());
+                ui.add_space(6.0);
+                ui.label(tr!(
+                    "broken-cue-missing-files",
+                    missing = missing.len(),
+                    total = total,
+                ));
+                ui.add_space(4.0);
+                for name in &missing {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_RED,
+                        format!("  · {name}"),
+                    );
+                }
+                ui.add_space(8.0);
+                ui.label(tr!("delete-the-cue-file-the-cue"));
+
+                if in_bulk {
+                    ui.add_space(4.0);
+                    ui.weak(tr!("broken-cue-auto-skip", secs = remaining));
+                    // Pump the frame so the countdown ticks.
+                    ctx.request_repaint_after(std::time::Duration::from_millis(250));
+                }
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(egui::Button::new(
+                            egui::RichText::new(tr!("delete-cue")).color(egui::Color32::LIGHT_RED),
+                        ))
+                        .clicked()
+                    {
+                        delete_clicked = true;
+                    }
+                    if ui.button(tr!("keep-skip")).clicked() {
+                        keep_clicked = true;
+                    }
+                });
+            });
+
+        // Bulk-mode auto-skip after the timeout. Conservative default:
+        // never auto-delete — only auto-keep.
+        if in_bulk && remaining == 0 {
+            keep_clicked = true;
+        }
+
+        if delete_clicked {
+            self.resolve_broken_cue(true);
+        } else if keep_clicked {
+            self.resolve_broken_cue(false);
+        }
+    }
+
+    /// Apply the user's decision for the active broken-cue prompt. `delete`
+    /// removes the cue file from disk and logs the result; `false` keeps it.
+    /// In bulk mode also records the queue item as `skipped` (with a
+    /// distinct reason in the log) and advances the cursor.
+    fn resolve_broken_cue(&mut self, delete: bool) {
+        let Some(prompt) = self.broken_cue_prompt.take() else {
+            return;
+        };
+        let in_bulk = prompt.in_bulk;
+        let cue = prompt.cue_path;
+
+        if delete {
+            match std::fs::remove_file(&cue) {
+                Ok(()) => self.log(
+                    LogLevel::Success,
+                    format!("Deleted broken cue: {}", cue.display()),
+                ),
+                Err(e) => self.log(
+                    LogLevel::Error,
+                    format!("Could not delete {}: {e}", cue.display()),
+                ),
+            }
+        } else {
+            self.log(
+                LogLevel::Info,
+                format!("Kept broken cue (skipped): {}", cue.display()),
+            );
+        }
+
+        if in_bulk {
+            // Either path skips this queue item — record + advance so the
+            // bulk run keeps moving.
+            self.record_bulk_done("skipped", None);
+        }
+    }
+
+    /// Render the bulk loader modal when one is staged.
+    fn render_bulk_loader(&mut self, ctx: &egui::Context) {
+        let mut start_clicked = false;
+        let mut cancel_clicked = false;
+        let mut filter_changed = false;
+
+        if let Some(dialog) = self.bulk_loader.as_mut() {
+            egui::Window::new(tr!("open-bulk-job"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr!("bulk-file", path = dialog.path.display().to_string()));
+                    ui.add_space(6.0);
+                    if let Some(err) = &dialog.error {
+                        ui.colored_label(egui::Color32::LIGHT_RED, err);
+                    } else {
+                        ui.label(tr!("bulk-items", count = dialog.item_count));
+                        if dialog.resumed_count > 0 {
+                            ui.label(tr!("bulk-already-done", count = dialog.resumed_count));
+                        }
+                    }
+                    ui.add_space(8.0);
+                    ui.checkbox(
+                        &mut dialog.reprocess_existing,
+                        tr!("bulk-reprocess-existing"),
+                    );
+
+                    ui.add_space(4.0);
+                    let prev_include = dialog.filter.include_fuzzy;
+                    if ui
+                        .checkbox(
+                            &mut dialog.filter.include_fuzzy,
+                            tr!("bulk-include-fuzzy"),
+                        )
+                        .changed()
+                    {
+                        filter_changed = true;
+                    }
+                    if dialog.filter.include_fuzzy {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("minimum-confidence"));
+                            let resp = ui.add(
+                                egui::Slider::new(
+                                    &mut dialog.filter.fuzzy_min_score,
+                                    0.60..=0.95,
+                                )
+                                .fixed_decimals(2)
+                                .step_by(0.01),
+                            );
+                            if resp.drag_stopped() || resp.lost_focus() {
+                                filter_changed = true;
+                            }
+                        });
+                        ui.weak(tr!("bulk-fuzzy-score-hint"));
+                    }
+                    if !prev_include && dialog.filter.include_fuzzy {
+                        filter_changed = true;
+                    }
 
-        let log_line = format!(
-            "Bulk: locked match {} [#{}] via {:?}",
-            m.title, m.redump_id, m.matched_via
-        );
-        if let Some(Ok(info)) = self.disc_info.as_mut() {
-            info.redump_matches = Some(vec![m]);
-            info.fuzzy_matches = None;
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        let can_start = dialog.error.is_none() && dialog.item_count > 0;
+                        if ui
+                            .add_enabled(can_start, egui::Button::new(tr!("start")))
+                            .clicked()
+                        {
+                            start_clicked = true;
+                        }
+                        if ui.button(tr!("cancel")).clicked() {
+                            cancel_clicked = true;
+                        }
+                    });
+                });
         }
-        self.log(LogLevel::Success, log_line);
-    }
 
-    /// Bulk-mode keyboard shortcuts:
-    ///   Enter — save the currently focused image (or re-trigger the save
-    ///           button) — handled by the existing Save button path.
-    ///   S     — skip the current item (record as 'skipped', advance).
-    ///   B     — go back to the previous item (no log entry; cursor only).
-    ///   Esc   — exit bulk mode entirely.
-    /// Hotkeys are no-ops outside bulk mode and when the loader dialog is
-    /// open. We also skip keys consumed by a focused text input so the
-    /// search-query field stays editable.
-    fn handle_bulk_hotkeys(&mut self, ctx: &egui::Context) {
-        if self.bulk_queue.is_none() {
-            return;
+        if filter_changed {
+            self.refresh_bulk_loader_counts();
         }
-        if self.bulk_loader.is_some() {
-            return;
+        if start_clicked {
+            self.start_bulk_job();
+        } else if cancel_clicked {
+            self.bulk_loader = None;
         }
-        let text_focused = ctx.memory(|m| m.focused().is_some());
-        if text_focused {
+    }
+
+    /// Inline top banner shown above the central panel content when bulk
+    /// mode is active. Shows progress, the current item, a short preview
+    /// of upcoming items, and the action buttons (Skip / Back / Exit).
+    fn render_bulk_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(queue) = self.bulk_queue.as_ref() else {
+            self.bulk_banner_bottom_y = None;
             return;
-        }
+        };
+        let total = queue.total();
+        let done = queue.finished_count();
+        let current = queue.current().map(|it| it.file.clone());
+        let title = queue
+            .current()
+            .map(|it| it.best.title.clone())
+            .unwrap_or_default();
+        let cursor = queue.cursor;
+        let complete = queue.is_complete();
 
-        let (skip, back, exit, enter) = ctx.input(|i| {
-            (
-                i.key_pressed(egui::Key::S),
-                i.key_pressed(egui::Key::B),
-                i.key_pressed(egui::Key::Escape),
-                i.key_pressed(egui::Key::Enter),
-            )
-        });
+        // Snapshot the next ~3 pending items for the "Up next" strip.
+        let upcoming: Vec<(usize, String, String)> = queue
+            .items
+            .iter()
+            .enumerate()
+            .skip(cursor + 1)
+            .filter(|(idx, _)| queue.statuses[*idx] == super::bulk::ItemStatus::Pending)
+            .take(3)
+            .map(|(idx, it)| {
+                let stem = std::path::Path::new(&it.file)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&it.file)
+                    .to_string();
+                (idx, stem, it.best.title.clone())
+            })
+            .collect();
 
-        if exit {
+        let mut exit_clicked = false;
+        let mut skip_clicked = false;
+        let mut back_clicked = false;
+        let frame_resp = egui::Frame::group(ui.style())
+            .fill(ui.visuals().faint_bg_color)
+            .show(ui, |ui| {
+                // Row 1: status + current item. The current item gets a
+                // marquee so a long filename doesn't fight the layout —
+                // it scrolls inside a fixed-width region instead of
+                // wrapping or being clipped.
+                ui.horizontal(|ui| {
+                    ui.heading(if complete {
+                        tr!("bulk-complete")
+                    } else {
+                        tr!("bulk-mode")
+                    });
+                    ui.separator();
+                    ui.label(tr!("bulk-progress", done = done, total = total));
+                    ui.separator();
+                    if let Some(file) = current {
+                        let stem = std::path::Path::new(&file)
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or(&file);
+                        let line = if title.is_empty() {
+                            format!("[{}] {}", cursor + 1, stem)
+                        } else {
+                            format!("[{}] {}  →  {}", cursor + 1, stem, title)
+                        };
+                        let available = ui.available_width().max(120.0);
+                        marquee_label(ui, &line, available);
+                    }
+                });
+
+                ui.add_space(2.0);
+
+                // Row 2: controls + upcoming on the same line. Controls go
+                // first so they're always reachable; the upcoming list
+                // takes whatever space is left and marquees if too long.
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(cursor > 0, egui::Button::new(tr!("back")))
+                        .on_hover_text(tr!("bulk-back-tip"))
+                        .clicked()
+                    {
+                        back_clicked = true;
+                    }
+                    if ui
+                        .add_enabled(!complete, egui::Button::new(tr!("skip")))
+                        .on_hover_text(tr!("bulk-skip-tip"))
+                        .clicked()
+                    {
+                        skip_clicked = true;
+                    }
+                    if ui
+                        .button(tr!("exit-bulk"))
+                        .on_hover_text(tr!("bulk-exit-tip"))
+                        .clicked()
+                    {
+                        exit_clicked = true;
+                    }
+
+                    ui.add_space(8.0);
+                    ui.separator();
+
+                    if !upcoming.is_empty() {
+                        ui.weak(tr!("up-next"));
+                        let line = upcoming
+                            .iter()
+                            .map(|(_, stem, t)| {
+                                if t.is_empty() {
+                                    stem.clone()
+                                } else {
+                                    format!("{stem} → {t}")
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join("  ·  ");
+                        let avail = ui.available_width().max(120.0);
+                        marquee_label(ui, &line, avail);
+                    } else if !complete {
+                        ui.weak(tr!("last-item-in-queue"));
+                    } else {
+                        ui.colored_label(
+                            egui::Color32::LIGHT_GREEN,
+                            tr!("bulk-all-processed"),
+                        );
+                    }
+                });
+            });
+
+        self.bulk_banner_bottom_y = Some(frame_resp.response.rect.bottom());
+
+        ui.add_space(4.0);
+
+        if exit_clicked {
             self.close_bulk_job();
-            return;
         }
-        if skip {
+        if skip_clicked {
             self.log(LogLevel::Info, "Bulk: skipped");
             self.record_bulk_done("skipped", None);
-            return;
         }
-        if back {
+        if back_clicked {
             if let Some(queue) = self.bulk_queue.as_mut() {
                 queue.back();
             }
             self.bulk_loaded_cursor = None;
             self.pending_hash_redump_id = None;
-            return;
         }
-        if enter {
-            // Save currently-previewed image, if any. The bulk advance
-            // happens inside poll_export on success.
-            if self.export_in_progress {
-                return;
-            }
-            let url = self.preview_url.clone();
-            let path = self
-                .selected_path
-                .as_ref()
-                .map(|p| generate_output_path(p));
-            if let (Some(url), Some(path)) = (url, path) {
-                self.start_export(&url, &path);
-            } else {
-                self.log(
-                    LogLevel::Info,
-                    "Bulk: Enter pressed but no image is selected for preview",
-                );
-            }
+    }
+
+    /// Open file picker dialog
+    fn open_file_picker(&mut self) {
+        let extensions = supported_extensions();
+
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("disc-images"), &extensions)
+            .add_filter(tr!("iso-toast-files"), &["iso", "toast"])
+            .add_filter(tr!("chd-files"), &["chd"])
+            .add_filter(tr!("bin-cue-files"), &["bin", "cue"])
+            .add_filter(tr!("all-files"), &["*"])
+            .pick_file()
+        {
+            self.process_file(path);
         }
     }
 
-    /// Append a done-log entry for the current bulk item and advance the
-    /// cursor. Caller chooses the status string (saved/skipped/existing-art).
-    fn record_bulk_done(&mut self, status: &str, image_url: Option<String>) {
-        // Build the entry while holding the queue borrow; release it before
-        // we touch self.log.
-        let (write_err, item_status_for_advance) = {
-            let Some(queue) = self.bulk_queue.as_mut() else {
-                return;
-            };
-            let Some(item) = queue.current() else {
-                return;
-            };
-            let queue_redump_id = item.best.redump_id;
-            let file = item.file.clone();
-            let hash_redump_id = self
-                .pending_hash_redump_id
-                .filter(|h| Some(*h) != queue_redump_id);
-            let entry = super::bulk::DoneEntry {
-                file,
-                status: status.to_string(),
-                queue_redump_id,
-                hash_redump_id,
-                image_url,
-                ts: super::bulk::now_iso8601(),
-            };
-            let item_status = match status {
-                "saved" => super::bulk::ItemStatus::Saved,
-                "skipped" => super::bulk::ItemStatus::Skipped,
-                "existing-art" => super::bulk::ItemStatus::ExistingArt,
-                _ => super::bulk::ItemStatus::Skipped,
-            };
-            let err = queue.record(entry, item_status).err();
-            queue.advance();
-            (err, item_status)
+    /// Update search query from disc info using current config
+    fn update_search_query_from_disc(&mut self) {
+        if let Some(Ok(info)) = &self.disc_info {
+            let search_query = ArtworkSearchQuery::from_disc_info_with_config(info, &self.search_config);
+            self.search_query_text = search_query.build_query();
+        }
+    }
+
+    /// Open the Settings window on a copy of the current settings.
+    fn open_settings(&mut self) {
+        let mut config = crate::config::get_config().clone();
+        config.export = self.export_config.clone();
+        config.log_level = self.log_level.clone();
+        config.log_file_level = self.log_file_level.clone();
+        config.appearance = self.appearance.clone();
+        config.update_check.enabled = self.update_config.update_check.enabled;
+        config.update_check.repository_url = self.update_config.update_check.repository_url.clone();
+        config.update_check.channel = self.update_config.update_check.channel;
+        config.update_check.check_interval_hours = self.update_config.update_check.check_interval_hours;
+        self.settings_dialog = Some(SettingsDialog::new(config, &self.search_config));
+    }
+
+    /// Settings window; a successful Save closes it.
+    fn render_settings_window(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.settings_dialog.as_mut() else {
+            return;
+        };
+        let mut open = true;
+        if let Some((config, search)) = dialog.show(ctx, &mut open) {
+            match self.apply_settings(config, search) {
+                Ok(()) => {
+                    super::theme::apply(ctx, &self.appearance);
+                    open = false;
+                }
+                Err(e) => {
+                    if let Some(dialog) = self.settings_dialog.as_mut() {
+                        dialog.error = Some(e);
+                    }
+                }
+            }
+        }
+        if !open {
+            self.settings_dialog = None;
+        }
+    }
+
+    /// Switch theme from the top panel and save it.
+    fn set_theme(&mut self, ctx: &egui::Context, theme: ThemeMode) {
+        self.appearance.theme = theme;
+        super::theme::apply(ctx, &self.appearance);
+        let appearance = self.appearance.clone();
+        if let Err(e) = crate::config::update_config(|config| config.appearance = appearance) {
+            self.log(LogLevel::Error, format!("Failed to save theme: {}", e));
+        }
+    }
+
+    /// Write checked settings to `config.json` and switch the running app
+    /// over to them.
+    fn apply_settings(&mut self, mut config: crate::config::AppConfig, search: SearchConfig) -> Result<(), String> {
+        config.search = search.to_settings();
+        config.save()?;
+        crate::logging::ui_logger::set_level(crate::logging::ui_logger::parse_level(&config.log_level));
+        crate::logging::ui_logger::set_file_level(crate::logging::ui_logger::parse_level(&config.log_file_level));
+        self.log_level = config.log_level.clone();
+        self.log_file_level = config.log_file_level.clone();
+        self.appearance = config.appearance.clone();
+        self.export_config = config.export.clone();
+        self.export_extra_sizes_text = format_sizes(&self.export_config.extra_sizes);
+        self.update_config.update_check = crate::update::UpdateCheckConfig {
+            enabled: config.update_check.enabled,
+            repository_url: config.update_check.repository_url.clone(),
+            channel: config.update_check.channel,
+            check_interval_hours: config.update_check.check_interval_hours,
         };
+        self.search_config = search;
+        self.update_search_query_from_disc();
+        crate::i18n::set_language(&config.language);
+        // Network and fuzzy-match settings are read through `get_config`
+        crate::config::set_config(config);
+        self.log(LogLevel::Success, "Settings saved");
+        Ok(())
+    }
 
-        if let Some(e) = write_err {
-            self.log(LogLevel::Error, format!("Bulk: done-log write failed: {e}"));
+    /// Save search configuration to the per-user `config.json`.
+    fn save_search_config(&mut self) {
+        if let Err(e) = self.search_config.save() {
+            self.log(LogLevel::Error, format!("Failed to save search settings: {}", e));
         }
-        let _ = item_status_for_advance;
-        self.bulk_loaded_cursor = None;
-        self.pending_hash_redump_id = None;
     }
 
-    /// Render the broken-cue confirmation modal when one is staged. In bulk
-    /// mode shows a countdown that auto-dismisses with "Keep" after
-    /// `BULK_AUTO_SKIP_SECS` so a long-running queue isn't stuck behind a
-    /// prompt the user isn't around to answer.
-    fn render_broken_cue_prompt(&mut self, ctx: &egui::Context) {
-        let Some(prompt) = self.broken_cue_prompt.as_ref() else {
+    /// Search for the loaded disc's artwork with the query in the search
+    /// field: MusicBrainz for a pure audio CD, the web for anything else.
+    fn search_disc(&mut self) {
+        let Some(Ok(info)) = self.disc_info.as_ref() else {
             return;
         };
-        let cue = prompt.cue_path.clone();
-        let missing = prompt.missing.clone();
-        let total = prompt.total_refs;
-        let in_bulk = prompt.in_bulk;
-        let elapsed = prompt.opened_at.elapsed().as_secs();
-        let remaining = BULK_AUTO_SKIP_SECS.saturating_sub(elapsed);
+        // Same audio-CD test as the search controls
+        let use_musicbrainz = info.toc.is_some()
+            && info.pvd.is_none()
+            && info.hfs_mdb.is_none()
+            && info.hfsplus_header.is_none()
+            && info.redump_matches.as_ref().map(|ms| ms.is_empty()).unwrap_or(true);
+        let toc = info.toc.as_ref().map(|toc| (toc.musicbrainz_id(), toc.to_toc_string()));
+        let query = self.search_query_text.clone();
+        match toc {
+            Some((id, toc_string)) if use_musicbrainz => {
+                self.log(LogLevel::Info, format!("Searching MusicBrainz for disc ID: {}", id));
+                self.start_musicbrainz_search(&id, Some(toc_string), Some(query));
+            }
+            _ => {
+                self.log(LogLevel::Info, format!("Searching: {}", query));
+                self.start_search(&query);
+            }
+        }
+    }
 
-        let mut delete_clicked = false;
-        let mut keep_clicked = false;
+    /// Start an async image search
+    fn start_search(&mut self, query: &str) {
+        let query = query.to_string();
+        let user_agent = self.search_config.user_agent.clone();
+        let pack_title = self.pack_lookup_title(&query);
+        let cancel = self.replace_search_cancel();
+        let (tx, rx) = mpsc::channel();
 
-        egui::Window::new("Broken CUE file")
-            .collapsible(false)
-            .resizable(false)
-            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-            .show(ctx, |ui| {
-                ui.label(egui::RichText::new(cue.display().to_string()).strong());
-                ui.add_space(6.0);
-                ui.label(format!(
-                    "{} of {} referenced data file(s) cannot be found:",
-                    missing.len(),
-                    total,
-                ));
-                ui.add_space(4.0);
-                for name in &missing {
-                    ui.colored_label(
-                        egui::Color32::LIGHT_RED,
-                        format!("  · {name}"),
-                    );
-                }
-                ui.add_space(8.0);
-                ui.label("Delete the cue file? (The cue can't be used without its data.)");
+        self.search_in_progress = true;
+        self.search_results.clear();
+        self.selected_image_index = None;
+        self.search_receiver = Some(rx);
+        self.show_search_window = true;
 
-                if in_bulk {
-                    ui.add_space(4.0);
-                    ui.weak(format!(
-                        "Auto-skipping (keep, don't delete) in {remaining}s …"
-                    ));
-                    // Pump the frame so the countdown ticks.
-                    ctx.request_repaint_after(std::time::Duration::from_millis(250));
-                }
+        thread::spawn(move || {
+            let result = crate::search::search_images_cancellable(
+                &query,
+                20,
+                user_agent.as_deref(),
+                &cancel,
+            )
+            .map_err(String::from);
+            if cancel.is_cancelled() {
+                return;
+            }
+            let _ = tx.send(with_pack_results(&pack_title, result));
+        });
+    }
 
-                ui.add_space(10.0);
-                ui.horizontal(|ui| {
-                    if ui
-                        .add(egui::Button::new(
-                            egui::RichText::new("Delete cue").color(egui::Color32::LIGHT_RED),
-                        ))
-                        .clicked()
-                    {
-                        delete_clicked = true;
-                    }
-                    if ui.button("Keep (skip)").clicked() {
-                        keep_clicked = true;
-                    }
-                });
-            });
+    /// Cancel any running search and hand out a fresh token for the next one.
+    fn replace_search_cancel(&mut self) -> CancelToken {
+        if let Some(old) = self.search_cancel.take() {
+            old.cancel();
+        }
+        let token = CancelToken::new();
+        self.search_cancel = Some(token.clone());
+        token
+    }
 
-        // Bulk-mode auto-skip after the timeout. Conservative default:
-        // never auto-delete — only auto-keep.
-        if in_bulk && remaining == 0 {
-            keep_clicked = true;
+    /// Stop the in-flight search. The worker notices at its next step and
+    /// exits; anything it would have sent is dropped with the receiver.
+    fn cancel_search(&mut self) {
+        if let Some(token) = self.search_cancel.take() {
+            token.cancel();
         }
+        if self.search_in_progress {
+            self.search_in_progress = false;
+            self.search_receiver = None;
+            self.log(LogLevel::Warning, "Search cancelled");
+        }
+    }
 
-        if delete_clicked {
-            self.resolve_broken_cue(true);
-        } else if keep_clicked {
-            self.resolve_broken_cue(false);
+    /// Stop the in-flight preview download.
+    fn cancel_preview(&mut self) {
+        if let Some(token) = self.preview_cancel.take() {
+            token.cancel();
+        }
+        if self.preview_loading {
+            self.preview_loading = false;
+            self.preview_receiver = None;
+            self.preview_url = None;
+            self.log(LogLevel::Warning, "Preview cancelled");
         }
     }
 
-    /// Apply the user's decision for the active broken-cue prompt. `delete`
-    /// removes the cue file from disk and logs the result; `false` keeps it.
-    /// In bulk mode also records the queue item as `skipped` (with a
-    /// distinct reason in the log) and advances the cursor.
-    fn resolve_broken_cue(&mut self, delete: bool) {
-        let Some(prompt) = self.broken_cue_prompt.take() else {
+    /// Stop the in-flight export before it writes its next file. A file it
+    /// was already writing is finished, though its result is dropped.
+    fn cancel_export(&mut self) {
+        if let Some(token) = self.export_cancel.take() {
+            token.cancel();
+        }
+        if self.export_in_progress {
+            self.export_in_progress = false;
+            self.events.forget(Job::Export);
+            self.pending_export_url = None;
+            self.log(LogLevel::Warning, "Export cancelled; a cover it was already writing may still be saved");
+        }
+    }
+
+    /// Title used for the local cover-pack lookup: the disc's canonical
+    /// title when one is loaded (the search query carries extra keywords
+    /// like "jewel case" that would dilute the match), else `fallback`.
+    fn pack_lookup_title(&self, fallback: &str) -> String {
+        match &self.disc_info {
+            Some(Ok(info)) => {
+                ArtworkSearchQuery::from_disc_info_with_config(info, &self.search_config).title
+            }
+            _ => fallback.to_string(),
+        }
+    }
+
+    /// Ask for a cover-pack zip and import it in the background.
+    fn open_pack_zip_picker(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("cover-pack-zip"), &["zip"])
+            .pick_file()
+        else {
             return;
         };
-        let in_bulk = prompt.in_bulk;
-        let cue = prompt.cue_path;
+        self.start_pack_import(path);
+    }
 
-        if delete {
-            match std::fs::remove_file(&cue) {
-                Ok(()) => self.log(
+    /// Ask for a folder of cover images and index it in the background.
+    fn open_pack_folder_picker(&mut self) {
+        let Some(path) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        self.start_pack_import(path);
+    }
+
+    fn start_pack_import(&mut self, path: PathBuf) {
+        self.log(LogLevel::Info, format!("Importing cover pack: {}", path.display()));
+        self.events.spawn(Job::PackImport, move || {
+            AppEvent::PackImported(crate::packs::import_pack(&path))
+        });
+    }
+
+    /// A cover-pack import finished
+    fn on_pack_imported(&mut self, result: Result<crate::packs::ImportSummary, String>) {
+        match result {
+            Ok(summary) => {
+                self.pack_entry_count = summary.total;
+                self.log(
                     LogLevel::Success,
-                    format!("Deleted broken cue: {}", cue.display()),
-                ),
-                Err(e) => self.log(
-                    LogLevel::Error,
-                    format!("Could not delete {}: {e}", cue.display()),
-                ),
+                    format!(
+                        "Imported cover pack '{}' ({} images, {} indexed total)",
+                        summary.pack, summary.added, summary.total
+                    ),
+                );
             }
-        } else {
-            self.log(
-                LogLevel::Info,
-                format!("Kept broken cue (skipped): {}", cue.display()),
-            );
+            Err(e) => self.log(LogLevel::Error, format!("Cover pack import failed: {}", e)),
         }
+    }
 
-        if in_bulk {
-            // Either path skips this queue item — record + advance so the
-            // bulk run keeps moving.
-            self.record_bulk_done("skipped", None);
+    /// Ask for a folder and dry-run the artwork fixer over it.
+    fn open_fix_artwork_picker(&mut self) {
+        let Some(root) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        let settings = self.export_settings();
+        self.log(LogLevel::Info, format!("Checking artwork in {}", root.display()));
+        self.events.spawn(Job::FixScan, move || {
+            let result = scan_for_fixes(&root, &settings)
+                .map(|candidates| FixArtworkDialog { root, candidates });
+            AppEvent::FixScanned(result)
+        });
+    }
+
+    /// Ask for a folder and identify every disc image under it.
+    fn open_folder_scan_picker(&mut self) {
+        let Some(root) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        self.start_folder_scan(root);
+    }
+
+    /// Identify every disc image under `root`, replacing any running scan.
+    fn start_folder_scan(&mut self, root: PathBuf) {
+        if let Some(scan) = self.folder_scan.as_mut() {
+            scan.cancel();
         }
+        self.recent.add_folder(&root);
+        self.save_recent();
+        self.log(LogLevel::Info, format!("Scanning {} for disc images", root.display()));
+        self.folder_scan = Some(FolderScan::start(root, self.export_config.clone()));
     }
 
-    /// Render the bulk loader modal when one is staged.
-    fn render_bulk_loader(&mut self, ctx: &egui::Context) {
-        let mut start_clicked = false;
-        let mut cancel_clicked = false;
-        let mut filter_changed = false;
+    /// Re-open a disc from the "Recent" menu, bringing back the results of
+    /// its last search if there were any.
+    fn open_recent_file(&mut self, path: PathBuf) {
+        let cached = self.recent.file(&path).cloned();
+        self.process_file(path.clone());
+        // A broken cue stops at its prompt without loading the disc.
+        if self.selected_path.as_ref() != Some(&path) {
+            return;
+        }
+        let Some(entry) = cached.filter(|e| !e.results.is_empty()) else {
+            return;
+        };
+        self.log(
+            LogLevel::Info,
+            format!("Restored {} cached results for \"{}\"", entry.results.len(), entry.query),
+        );
+        self.search_query_text = entry.query;
+        self.search_results = entry.results;
+        self.show_search_window = true;
+    }
 
-        if let Some(dialog) = self.bulk_loader.as_mut() {
-            egui::Window::new("Open Bulk Job")
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    ui.label(format!("File: {}", dialog.path.display()));
-                    ui.add_space(6.0);
-                    if let Some(err) = &dialog.error {
-                        ui.colored_label(egui::Color32::LIGHT_RED, err);
+    /// "Recent" menu next to Browse: discs re-open in the main view (with
+    /// their cached results), folders start a new scan.
+    fn render_recent_menu(&mut self, ui: &mut egui::Ui) {
+        let mut open_file = None;
+        let mut scan_folder = None;
+        let mut clear = false;
+        ui.menu_button(tr!("recent"), |ui| {
+            if self.recent.is_empty() {
+                ui.weak(tr!("recent-empty"));
+                return;
+            }
+            if !self.recent.files.is_empty() {
+                ui.label(egui::RichText::new(tr!("recent-files")).strong());
+                for file in &self.recent.files {
+                    let name = file
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| file.path.display().to_string());
+                    let text = if file.results.is_empty() {
+                        name
                     } else {
-                        ui.label(format!("Items: {}", dialog.item_count));
-                        if dialog.resumed_count > 0 {
-                            ui.label(format!(
-                                "Already done (will skip): {}",
-                                dialog.resumed_count
-                            ));
-                        }
-                    }
-                    ui.add_space(8.0);
-                    ui.checkbox(
-                        &mut dialog.reprocess_existing,
-                        "Reprocess discs that already have artwork",
-                    );
-
-                    ui.add_space(4.0);
-                    let prev_include = dialog.filter.include_fuzzy;
+                        tr!("recent-file-results", name = name, count = file.results.len())
+                    };
                     if ui
-                        .checkbox(
-                            &mut dialog.filter.include_fuzzy,
-                            "Include partially-matched discs (fuzzy)",
-                        )
-                        .changed()
+                        .add_enabled(file.path.exists(), egui::Button::new(text))
+                        .on_hover_text(file.path.display().to_string())
+                        .on_disabled_hover_text(tr!("recent-missing", path = file.path.display().to_string()))
+                        .clicked()
                     {
-                        filter_changed = true;
-                    }
-                    if dialog.filter.include_fuzzy {
-                        ui.horizontal(|ui| {
-                            ui.label("Minimum confidence:");
-                            let resp = ui.add(
-                                egui::Slider::new(
-                                    &mut dialog.filter.fuzzy_min_score,
-                                    0.60..=0.95,
-                                )
-                                .fixed_decimals(2)
-                                .step_by(0.01),
-                            );
-                            if resp.drag_stopped() || resp.lost_focus() {
-                                filter_changed = true;
-                            }
-                        });
-                        ui.weak(
-                            "0.60 = fuzzy floor (noisy). 0.85 is selective without rejecting \
-                             genuine matches. 0.90+ is exact-like confidence.",
-                        );
+                        open_file = Some(file.path.clone());
+                        ui.close();
                     }
-                    if !prev_include && dialog.filter.include_fuzzy {
-                        filter_changed = true;
+                }
+            }
+            if !self.recent.folders.is_empty() {
+                ui.separator();
+                ui.label(egui::RichText::new(tr!("recent-folders")).strong());
+                for folder in &self.recent.folders {
+                    if ui
+                        .add_enabled(folder.is_dir(), egui::Button::new(folder.display().to_string()))
+                        .on_disabled_hover_text(tr!("recent-missing", path = folder.display().to_string()))
+                        .clicked()
+                    {
+                        scan_folder = Some(folder.clone());
+                        ui.close();
                     }
+                }
+            }
+            ui.separator();
+            if ui.button(tr!("recent-clear")).clicked() {
+                clear = true;
+                ui.close();
+            }
+        });
 
-                    ui.add_space(12.0);
-                    ui.horizontal(|ui| {
-                        let can_start = dialog.error.is_none() && dialog.item_count > 0;
-                        if ui
-                            .add_enabled(can_start, egui::Button::new("Start"))
-                            .clicked()
-                        {
-                            start_clicked = true;
-                        }
-                        if ui.button("Cancel").clicked() {
-                            cancel_clicked = true;
-                        }
-                    });
-                });
+        if let Some(path) = open_file {
+            self.open_recent_file(path);
+        }
+        if let Some(root) = scan_folder {
+            self.start_folder_scan(root);
+        }
+        if clear {
+            self.recent = RecentList::default();
+            self.save_recent();
+        }
+    }
+
+    /// Exchange the main view's per-disc state with `tab`'s.
+    fn swap_tab(&mut self, tab: &mut DiscTab) {
+        use std::mem::swap;
+        swap(&mut self.selected_path, &mut tab.selected_path);
+        crate::logging::crash::set_current_disc(self.selected_path.as_deref());
+        swap(&mut self.disc_info, &mut tab.disc_info);
+        swap(&mut self.chd_artwork_found, &mut tab.chd_artwork_found);
+        swap(&mut self.search_results, &mut tab.search_results);
+        swap(&mut self.search_receiver, &mut tab.search_receiver);
+        swap(&mut self.selected_image_index, &mut tab.selected_image_index);
+        swap(&mut self.search_in_progress, &mut tab.search_in_progress);
+        swap(&mut self.search_cancel, &mut tab.search_cancel);
+        swap(&mut self.search_query_text, &mut tab.search_query_text);
+        swap(&mut self.manual_url, &mut tab.manual_url);
+        swap(&mut self.show_search_window, &mut tab.show_search_window);
+        swap(&mut self.preview_texture, &mut tab.preview_texture);
+        swap(&mut self.preview_image, &mut tab.preview_image);
+        swap(&mut self.preview_source_size, &mut tab.preview_source_size);
+        swap(&mut self.preview_edits, &mut tab.preview_edits);
+        swap(&mut self.preview_show_output, &mut tab.preview_show_output);
+        swap(&mut self.preview_unsharpened, &mut tab.preview_unsharpened);
+        swap(&mut self.preview_magnified, &mut tab.preview_magnified);
+        swap(&mut self.preview_render_source, &mut tab.preview_render_source);
+        swap(&mut self.preview_output_size, &mut tab.preview_output_size);
+        swap(&mut self.preview_receiver, &mut tab.preview_receiver);
+        swap(&mut self.preview_progress, &mut tab.preview_progress);
+        swap(&mut self.preview_loading, &mut tab.preview_loading);
+        swap(&mut self.preview_url, &mut tab.preview_url);
+        swap(&mut self.preview_error, &mut tab.preview_error);
+        swap(&mut self.candidate_info, &mut tab.candidate_info);
+        swap(&mut self.candidate_info_receiver, &mut tab.candidate_info_receiver);
+        swap(&mut self.preview_cancel, &mut tab.preview_cancel);
+        swap(&mut self.compare_pin, &mut tab.compare_pin);
+        swap(&mut self.compare_output, &mut tab.compare_output);
+        swap(&mut self.existing_art, &mut tab.existing_art);
+        swap(&mut self.existing_art_stale, &mut tab.existing_art_stale);
+        swap(&mut self.output_path_edit, &mut tab.output_path_edit);
+        swap(&mut self.auto_save_url, &mut tab.auto_save_url);
+        swap(&mut self.export_role, &mut tab.export_role);
+        swap(&mut self.browse_view, &mut tab.browse_view);
+        swap(&mut self.show_browse_window, &mut tab.show_browse_window);
+        swap(&mut self.hash_progress, &mut tab.hash_progress);
+        swap(&mut self.hash_receiver, &mut tab.hash_receiver);
+        swap(&mut self.hash_rate_tracker, &mut tab.hash_rate_tracker);
+        swap(&mut self.audio_tracks, &mut tab.audio_tracks);
+        swap(&mut self.audio_modal_open, &mut tab.audio_modal_open);
+        swap(&mut self.audio_error, &mut tab.audio_error);
+        swap(&mut self.tracklist, &mut tab.tracklist);
+        swap(&mut self.tracklist_receiver, &mut tab.tracklist_receiver);
+        swap(&mut self.cd_text, &mut tab.cd_text);
+        swap(&mut self.volume_icon, &mut tab.volume_icon);
+        swap(&mut self.volume_icon_receiver, &mut tab.volume_icon_receiver);
+        swap(&mut self.entry_counts, &mut tab.entry_counts);
+        swap(&mut self.entry_counts_receiver, &mut tab.entry_counts_receiver);
+    }
+
+    fn switch_tab(&mut self, idx: usize) {
+        if idx == self.active_tab || idx >= self.tabs.len() {
+            return;
+        }
+        // Playback and a pending Replace belong to the disc being left
+        self.audio_playback = None;
+        self.confirm_replace = false;
+        let mut tab = std::mem::take(&mut self.tabs[idx]);
+        self.swap_tab(&mut tab);
+        self.tabs[self.active_tab] = tab;
+        self.active_tab = idx;
+    }
+
+    /// Open an empty tab and show it.
+    fn new_tab(&mut self) {
+        self.tabs.push(DiscTab::default());
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Close a tab, cancelling its searches, preview and hashing. The last
+    /// tab can't be closed.
+    fn close_tab(&mut self, idx: usize) {
+        if self.tabs.len() < 2 || idx >= self.tabs.len() {
+            return;
         }
+        if idx == self.active_tab {
+            self.switch_tab(if idx + 1 < self.tabs.len() { idx + 1 } else { idx - 1 });
+        }
+        let tab = self.tabs.remove(idx);
+        tab.cancel();
+        if idx < self.active_tab {
+            self.active_tab -= 1;
+        }
+    }
 
-        if filter_changed {
-            self.refresh_bulk_loader_counts();
+    /// Tabs stay put while an export, the bulk queue or a broken-cue prompt
+    /// is working on the current one.
+    fn can_switch_tabs(&self) -> bool {
+        self.bulk_queue.is_none() && !self.export_in_progress && self.broken_cue_prompt.is_none()
+    }
+
+    /// Tab strip above the main view.
+    fn render_disc_tabs(&mut self, ui: &mut egui::Ui) {
+        let can_switch = self.can_switch_tabs();
+        let mut switch_to = None;
+        let mut close = None;
+        let mut open_new = false;
+        ui.add_enabled_ui(can_switch, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for idx in 0..self.tabs.len() {
+                    let path = if idx == self.active_tab {
+                        self.selected_path.as_ref()
+                    } else {
+                        self.tabs[idx].selected_path.as_ref()
+                    };
+                    let name = path
+                        .and_then(|p| p.file_name())
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| tr!("tab-empty"));
+                    let response = ui.selectable_label(idx == self.active_tab, name);
+                    let response = match path {
+                        Some(path) => response.on_hover_text(path.display().to_string()),
+                        None => response,
+                    };
+                    if response.clicked() {
+                        switch_to = Some(idx);
+                    }
+                    if self.tabs.len() > 1
+                        && icon_button(ui, "×", tr!("tab-close")).clicked()
+                    {
+                        close = Some(idx);
+                    }
+                    ui.separator();
+                }
+                let new_tab_label = format!(
+                    "{} ({})",
+                    tr!("tab-new"),
+                    ui.ctx().format_shortcut(&NEW_TAB_SHORTCUT)
+                );
+                if icon_button(ui, "+", new_tab_label).clicked() {
+                    open_new = true;
+                }
+            });
+        });
+        if let Some(idx) = switch_to {
+            self.switch_tab(idx);
         }
-        if start_clicked {
-            self.start_bulk_job();
-        } else if cancel_clicked {
-            self.bulk_loader = None;
+        if let Some(idx) = close {
+            self.close_tab(idx);
+        }
+        if open_new {
+            self.new_tab();
         }
     }
 
-    /// Inline top banner shown above the central panel content when bulk
-    /// mode is active. Shows progress, the current item, a short preview
-    /// of upcoming items, and the action buttons (Skip / Back / Exit).
-    fn render_bulk_banner(&mut self, ui: &mut egui::Ui) {
-        let Some(queue) = self.bulk_queue.as_ref() else {
-            self.bulk_banner_bottom_y = None;
+    fn save_recent(&mut self) {
+        if let Err(e) = self.recent.save() {
+            self.log(LogLevel::Warning, e);
+        }
+    }
+
+    /// Poll the "Scan Folder..." identification workers
+    fn poll_folder_scan(&mut self) {
+        let Some(scan) = self.folder_scan.as_mut() else {
             return;
         };
-        let total = queue.total();
-        let done = queue.finished_count();
-        let current = queue.current().map(|it| it.file.clone());
-        let title = queue
-            .current()
-            .map(|it| it.best.title.clone())
-            .unwrap_or_default();
-        let cursor = queue.cursor;
-        let complete = queue.is_complete();
-
-        // Snapshot the next ~3 pending items for the "Up next" strip.
-        let upcoming: Vec<(usize, String, String)> = queue
-            .items
-            .iter()
-            .enumerate()
-            .skip(cursor + 1)
-            .filter(|(idx, _)| queue.statuses[*idx] == super::bulk::ItemStatus::Pending)
-            .take(3)
-            .map(|(idx, it)| {
-                let stem = std::path::Path::new(&it.file)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or(&it.file)
-                    .to_string();
-                (idx, stem, it.best.title.clone())
-            })
-            .collect();
+        let was_running = scan.is_running();
+        scan.poll();
+        if was_running && !scan.is_running() {
+            let found = scan.rows.len();
+            let missing = scan.rows.iter().filter(|r| r.error.is_none() && !r.has_artwork).count();
+            let root = scan.root.display().to_string();
+            self.log(
+                LogLevel::Success,
+                format!("Scanned {}: {} disc images, {} without artwork", root, found, missing),
+            );
+        }
+    }
 
-        let mut exit_clicked = false;
-        let mut skip_clicked = false;
-        let mut back_clicked = false;
-        let frame_resp = egui::Frame::group(ui.style())
-            .fill(ui.visuals().faint_bg_color)
-            .show(ui, |ui| {
-                // Row 1: status + current item. The current item gets a
-                // marquee so a long filename doesn't fight the layout —
-                // it scrolls inside a fixed-width region instead of
-                // wrapping or being clipped.
+    /// "Scan Folder..." results table. Headers sort; rows open in the main
+    /// view or go to the processing queue.
+    fn render_folder_scan_window(&mut self, ctx: &egui::Context) {
+        let Some(scan) = self.folder_scan.as_mut() else {
+            return;
+        };
+        let mut open = true;
+        let mut open_path = None;
+        let mut queue_selected = false;
+        let mut sort_by = None;
+
+        egui::Window::new(tr!("folder-scan"))
+            .open(&mut open)
+            .default_size([720.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    ui.heading(if complete {
-                        "Bulk job — complete"
-                    } else {
-                        "Bulk mode"
-                    });
-                    ui.separator();
-                    ui.label(format!("{done} / {total} done"));
-                    ui.separator();
-                    if let Some(file) = current {
-                        let stem = std::path::Path::new(&file)
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or(&file);
-                        let line = if title.is_empty() {
-                            format!("[{}] {}", cursor + 1, stem)
-                        } else {
-                            format!("[{}] {}  →  {}", cursor + 1, stem, title)
+                    ui.label(scan.root.display().to_string());
+                    if scan.is_running() {
+                        ui.spinner();
+                        let progress = match scan.total {
+                            Some(total) => tr!("scan-identified", done = scan.rows.len(), total = total),
+                            None => tr!("scan-looking"),
                         };
-                        let available = ui.available_width().max(120.0);
-                        marquee_label(ui, &line, available);
+                        ui.label(progress);
+                        if ui.button(tr!("stop")).clicked() {
+                            scan.cancel();
+                        }
+                    } else {
+                        ui.label(tr!("scan-disc-images", count = scan.rows.len()));
                     }
                 });
-
-                ui.add_space(2.0);
-
-                // Row 2: controls + upcoming on the same line. Controls go
-                // first so they're always reachable; the upcoming list
-                // takes whatever space is left and marquees if too long.
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("folder_scan")
+                        .num_columns(7)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            for column in SortColumn::ALL {
+                                let arrow = match (scan.sort == column, scan.ascending) {
+                                    (true, true) => " ⬆",
+                                    (true, false) => " ⬇",
+                                    (false, _) => "",
+                                };
+                                let header = egui::RichText::new(format!("{}{}", column.label(), arrow)).strong();
+                                if ui.add(egui::Button::new(header).frame(false)).clicked() {
+                                    sort_by = Some(column);
+                                }
+                            }
+                            ui.label("");
+                            ui.end_row();
+
+                            for row in &mut scan.rows {
+                                ui.checkbox(&mut row.selected, "");
+                                ui.label(&row.title).on_hover_text(row.path.display().to_string());
+                                match &row.error {
+                                    Some(e) => {
+                                        ui.colored_label(egui::Color32::LIGHT_RED, tr!("unreadable")).on_hover_text(e);
+                                    }
+                                    None => {
+                                        ui.label(&row.format);
+                                    }
+                                }
+                                match row.confidence {
+                                    Some(level) => {
+                                        ui.colored_label(confidence_color(level), super::names::confidence(level));
+                                    }
+                                    None => {
+                                        ui.label("");
+                                    }
+                                }
+                                match (&row.redump_title, &row.sha1) {
+                                    (Some(title), _) => {
+                                        ui.colored_label(egui::Color32::GREEN, tr!("match-hash")).on_hover_text(title);
+                                    }
+                                    (None, Some(sha1)) => {
+                                        ui.label(tr!("scan-hash-unmatched")).on_hover_text(sha1);
+                                    }
+                                    (None, None) => {
+                                        ui.label("");
+                                    }
+                                }
+                                if row.has_artwork {
+                                    ui.colored_label(egui::Color32::GREEN, tr!("found"));
+                                } else {
+                                    ui.colored_label(egui::Color32::YELLOW, tr!("missing"));
+                                }
+                                if ui.small_button(tr!("open")).clicked() {
+                                    open_path = Some(row.path.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.add_space(6.0);
                 ui.horizontal(|ui| {
-                    if ui
-                        .add_enabled(cursor > 0, egui::Button::new("Back"))
-                        .on_hover_text("B — return to previous item")
-                        .clicked()
-                    {
-                        back_clicked = true;
+                    if ui.button(tr!("select-missing-artwork")).clicked() {
+                        scan.select_missing_artwork();
                     }
+                    let selected = scan.rows.iter().filter(|r| r.selected).count();
                     if ui
-                        .add_enabled(!complete, egui::Button::new("Skip"))
-                        .on_hover_text("S — record as skipped and advance")
+                        .add_enabled(selected > 0, egui::Button::new(tr!("scan-process-selected", count = selected)))
+                        .on_hover_text(tr!("process-selected-tip"))
                         .clicked()
                     {
-                        skip_clicked = true;
-                    }
-                    if ui
-                        .button("Exit bulk")
-                        .on_hover_text("Esc")
-                        .clicked()
-                    {
-                        exit_clicked = true;
-                    }
-
-                    ui.add_space(8.0);
-                    ui.separator();
-
-                    if !upcoming.is_empty() {
-                        ui.weak("Up next:");
-                        let line = upcoming
-                            .iter()
-                            .map(|(_, stem, t)| {
-                                if t.is_empty() {
-                                    stem.clone()
-                                } else {
-                                    format!("{stem} → {t}")
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                            .join("  ·  ");
-                        let avail = ui.available_width().max(120.0);
-                        marquee_label(ui, &line, avail);
-                    } else if !complete {
-                        ui.weak("Last item in queue.");
-                    } else {
-                        ui.colored_label(
-                            egui::Color32::LIGHT_GREEN,
-                            "All items processed — click Exit bulk to return to single-disc mode.",
-                        );
+                        queue_selected = true;
                     }
                 });
             });
 
-        self.bulk_banner_bottom_y = Some(frame_resp.response.rect.bottom());
-
-        ui.add_space(4.0);
-
-        if exit_clicked {
-            self.close_bulk_job();
+        if let Some(column) = sort_by {
+            scan.sort_by(column);
         }
-        if skip_clicked {
-            self.log(LogLevel::Info, "Bulk: skipped");
-            self.record_bulk_done("skipped", None);
+        let selected = if queue_selected { scan.selected_paths() } else { Vec::new() };
+        if !open {
+            scan.cancel();
+            self.folder_scan = None;
         }
-        if back_clicked {
-            if let Some(queue) = self.bulk_queue.as_mut() {
-                queue.back();
-            }
-            self.bulk_loaded_cursor = None;
-            self.pending_hash_redump_id = None;
+        if !selected.is_empty() {
+            let queued = selected
+                .into_iter()
+                .filter(|path| self.processing_queue.add(path.clone()))
+                .count();
+            self.log(LogLevel::Info, format!("Queued {} discs for processing", queued));
+            self.show_queue_window = true;
         }
-    }
-
-    /// Open file picker dialog
-    fn open_file_picker(&mut self) {
-        let extensions = supported_extensions();
-
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("Disc Images", &extensions)
-            .add_filter("ISO/Toast Files", &["iso", "toast"])
-            .add_filter("CHD Files", &["chd"])
-            .add_filter("BIN/CUE Files", &["bin", "cue"])
-            .add_filter("All Files", &["*"])
-            .pick_file()
-        {
+        if let Some(path) = open_path {
             self.process_file(path);
         }
     }
 
-    /// Update search query from disc info using current config
-    fn update_search_query_from_disc(&mut self) {
-        if let Some(Ok(info)) = &self.disc_info {
-            let search_query = ArtworkSearchQuery::from_disc_info_with_config(info, &self.search_config);
-            self.search_query_text = search_query.build_query();
+    /// The "Fix Artwork" dry-run scan finished
+    fn on_fix_scanned(&mut self, result: Result<FixArtworkDialog, String>) {
+        match result {
+            Ok(dialog) => {
+                if dialog.candidates.is_empty() {
+                    self.log(
+                        LogLevel::Success,
+                        format!("All artwork in {} is already compatible", dialog.root.display()),
+                    );
+                } else {
+                    // Dry-run report goes to the log too, so it survives
+                    // the dialog being dismissed.
+                    self.log(
+                        LogLevel::Warning,
+                        format!("{} cover(s) need fixing:", dialog.candidates.len()),
+                    );
+                    for c in &dialog.candidates {
+                        self.log(
+                            LogLevel::Warning,
+                            format!("  {}: {}", c.path.display(), c.problems.join("; ")),
+                        );
+                    }
+                    self.fix_dialog = Some(dialog);
+                }
+            }
+            Err(e) => self.log(LogLevel::Error, format!("Artwork check failed: {}", e)),
         }
     }
 
-    /// Save search configuration to the per-user `config.json`.
-    fn save_search_config(&self) {
-        let Ok(path) = crate::config::config_file_path() else { return };
-        let mut json: serde_json::Value = match std::fs::read_to_string(&path) {
-            Ok(s) => serde_json::from_str(&s).unwrap_or_else(|_| serde_json::json!({})),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
-            Err(_) => return,
-        };
-
-        let search = json
-            .as_object_mut()
-            .expect("config root is an object")
-            .entry("search".to_string())
-            .or_insert_with(|| serde_json::json!({}));
-        if let Some(obj) = search.as_object_mut() {
-            obj.insert(
-                "content_type".to_string(),
-                serde_json::Value::String(self.search_config.content_type.as_str().to_string()),
+    /// The "Fix Artwork" batch re-encode finished
+    fn on_fix_ran(&mut self, summary: FixSummary) {
+        for (path, e) in &summary.failed {
+            self.log(
+                LogLevel::Error,
+                format!("Failed to fix {}: {}", path.display(), e),
             );
         }
-
-        if let Ok(updated) = serde_json::to_string_pretty(&json) {
-            let _ = std::fs::write(&path, updated);
-        }
+        let level = if summary.failed.is_empty() {
+            LogLevel::Success
+        } else {
+            LogLevel::Warning
+        };
+        self.log(
+            level,
+            format!(
+                "Fixed {} cover(s), {} failed. Originals backed up to {}",
+                summary.fixed,
+                summary.failed.len(),
+                summary.backup_dir.display()
+            ),
+        );
     }
 
-    /// Start an async image search
-    fn start_search(&mut self, query: &str) {
-        let query = query.to_string();
-        let user_agent = self.search_config.user_agent.clone();
-        let (tx, rx) = mpsc::channel();
+    /// Render the "Fix Artwork" dry-run report with its confirm button.
+    fn render_fix_artwork_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.fix_dialog.as_ref() else {
+            return;
+        };
+        let mut fix_clicked = false;
+        let mut cancel_clicked = false;
 
-        self.search_in_progress = true;
-        self.search_results.clear();
-        self.selected_image_index = None;
-        self.search_receiver = Some(rx);
-        self.show_search_window = true;
+        egui::Window::new(tr!("fix-existing-artwork"))
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "fix-covers-failing",
+                    count = dialog.candidates.len(),
+                    root = dialog.root.display().to_string()
+                ));
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for c in &dialog.candidates {
+                        let name = c
+                            .path
+                            .strip_prefix(&dialog.root)
+                            .unwrap_or(&c.path)
+                            .display()
+                            .to_string();
+                        ui.label(egui::RichText::new(name).strong());
+                        for problem in &c.problems {
+                            ui.colored_label(egui::Color32::LIGHT_RED, format!("  · {problem}"));
+                        }
+                    }
+                });
+                ui.add_space(8.0);
+                ui.weak(tr!("fix-backup-note", dir = BACKUP_DIR_NAME));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("fix-files", count = dialog.candidates.len())).clicked() {
+                        fix_clicked = true;
+                    }
+                    if ui.button(tr!("cancel")).clicked() {
+                        cancel_clicked = true;
+                    }
+                });
+            });
 
-        thread::spawn(move || {
-            let result = crate::search::search_images_with_ua(&query, 20, user_agent.as_deref());
-            let _ = tx.send(result);
-        });
+        if fix_clicked {
+            if let Some(dialog) = self.fix_dialog.take() {
+                let settings = self.export_settings();
+                self.log(
+                    LogLevel::Info,
+                    format!("Re-encoding {} cover(s)...", dialog.candidates.len()),
+                );
+                self.events.spawn(Job::FixRun, move || {
+                    AppEvent::FixRan(fix_artwork_batch(&dialog.root, &dialog.candidates, &settings))
+                });
+            }
+        } else if cancel_clicked {
+            self.fix_dialog = None;
+        }
     }
 
     /// Start async user agent capture from browser
-    fn start_user_agent_capture(&mut self) {
-        let (tx, rx) = mpsc::channel();
-
-        self.user_agent_capture_in_progress = true;
-        self.user_agent_receiver = Some(rx);
-
-        thread::spawn(move || {
-            let result = crate::search::capture_browser_user_agent();
-            let _ = tx.send(result);
+    fn start_user_agent_capture(&mut self) {
+        self.user_agent_capture_in_progress = true;
+        self.events.spawn(Job::UserAgentCapture, move || {
+            let result = crate::search::capture_browser_user_agent().map_err(String::from);
+            AppEvent::UserAgentCaptured(result)
         });
     }
 
@@ -1807,6 +3858,8 @@ impl App {
     fn start_musicbrainz_search(&mut self, disc_id: &str, toc_string: Option<String>, fallback_query: Option<String>) {
         let disc_id = disc_id.to_string();
         let user_agent = self.search_config.user_agent.clone();
+        let pack_title = self.pack_lookup_title(fallback_query.as_deref().unwrap_or_default());
+        let cancel = self.replace_search_cancel();
         let (tx, rx) = mpsc::channel();
 
         self.search_in_progress = true;
@@ -1817,7 +3870,7 @@ impl App {
 
         thread::spawn(move || {
             // Query MusicBrainz for releases
-            let mb_results = crate::api::search_by_discid(&disc_id, toc_string.as_deref());
+            let mb_results = crate::api::search_by_discid(&disc_id, toc_string.as_deref()).map_err(String::from);
 
             let result = mb_results.and_then(|releases| {
                 let mut all_results = Vec::new();
@@ -1849,6 +3902,7 @@ impl App {
                 all_results.extend(mb_images);
 
                 // If we got at least one MusicBrainz result, search Discogs API for the album
+                cancel.check()?;
                 if let Some(first_release) = releases.first() {
                     log::info!("Searching Discogs API for album: {} - {}", first_release.artist, first_release.title);
 
@@ -1898,7 +3952,8 @@ impl App {
                 Ok(results) if results.is_empty() => {
                     if let Some(query) = fallback_query {
                         log::info!("MusicBrainz returned no results, falling back to DDG search");
-                        crate::search::search_images_with_ua(&query, 20, user_agent.as_deref())
+                        crate::search::search_images_cancellable(&query, 20, user_agent.as_deref(), &cancel)
+                            .map_err(String::from)
                     } else {
                         Ok(results)
                     }
@@ -1907,7 +3962,8 @@ impl App {
                     // MusicBrainz failed, try fallback
                     if let Some(query) = fallback_query {
                         log::warn!("MusicBrainz search failed: {}, falling back to DDG search", e);
-                        crate::search::search_images_with_ua(&query, 20, user_agent.as_deref())
+                        crate::search::search_images_cancellable(&query, 20, user_agent.as_deref(), &cancel)
+                            .map_err(String::from)
                     } else {
                         Err(e)
                     }
@@ -1915,7 +3971,10 @@ impl App {
                 other => other,
             };
 
-            let _ = tx.send(final_result);
+            if cancel.is_cancelled() {
+                return;
+            }
+            let _ = tx.send(with_pack_results(&pack_title, final_result));
         });
     }
 
@@ -1942,14 +4001,26 @@ impl App {
                     let mb_count = results.iter().filter(|r| r.source.starts_with("MusicBrainz")).count();
                     let discogs_count = results.iter().filter(|r| r.source == "Discogs").count();
 
-                    // Sort: MusicBrainz results first, then web results by aspect ratio
-                    results.sort_by(|a, b| {
-                        // MusicBrainz results always come first
-                        let a_is_mb = a.source.starts_with("MusicBrainz");
-                        let b_is_mb = b.source.starts_with("MusicBrainz");
+                    let pack_count = results
+                        .iter()
+                        .filter(|r| r.source.starts_with(crate::packs::SOURCE_PREFIX))
+                        .count();
 
-                        if a_is_mb != b_is_mb {
-                            return if a_is_mb { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater };
+                    // Sort: local cover packs first, then MusicBrainz, then web
+                    // results by aspect ratio
+                    results.sort_by(|a, b| {
+                        let rank = |r: &ImageResult| {
+                            if r.source.starts_with(crate::packs::SOURCE_PREFIX) {
+                                0
+                            } else if r.source.starts_with("MusicBrainz") {
+                                1
+                            } else {
+                                2
+                            }
+                        };
+                        let (rank_a, rank_b) = (rank(a), rank(b));
+                        if rank_a != rank_b {
+                            return rank_a.cmp(&rank_b);
                         }
 
                         // Within same category, sort by aspect ratio (closest to 1.0)
@@ -1973,6 +4044,13 @@ impl App {
                     self.search_results = results;
                     self.search_in_progress = false;
                     self.search_receiver = None;
+                    if count > 0 {
+                        if let Some(path) = self.selected_path.clone() {
+                            if self.recent.set_results(&path, &self.search_query_text, &self.search_results) {
+                                self.save_recent();
+                            }
+                        }
+                    }
 
                     let msg = if mb_count > 0 && discogs_count > 0 {
                         format!("Found {} MusicBrainz releases + {} Discogs images", mb_count, discogs_count)
@@ -1982,13 +4060,22 @@ impl App {
                         format!("Found {} images", count)
                     };
                     self.log(LogLevel::Success, msg);
+                    if pack_count > 0 {
+                        self.log(
+                            LogLevel::Success,
+                            format!("{} match(es) from local cover packs", pack_count),
+                        );
+                    }
+                    if self.search_config.auto_pick {
+                        self.auto_pick_result();
+                    }
                 }
                 Ok(Err(e)) => {
                     self.search_results.clear();
                     self.selected_image_index = None;
                     self.search_in_progress = false;
                     self.search_receiver = None;
-                    self.log(LogLevel::Error, format!("Search failed: {}", e));
+                    self.log_failure(tr!("toast-search-failed"), format!("Search failed: {}", e));
                 }
                 Err(TryRecvError::Empty) => {
                     // Still searching, keep waiting
@@ -1998,12 +4085,40 @@ impl App {
                     self.selected_image_index = None;
                     self.search_in_progress = false;
                     self.search_receiver = None;
-                    self.log(LogLevel::Error, "Search thread terminated unexpectedly");
+                    self.log_failure(tr!("toast-search-failed"), "Search thread terminated unexpectedly".to_string());
                 }
             }
         }
     }
 
+    /// Auto-pick: preview the best-scoring result and save it once it has
+    /// loaded, as Download & Save would.
+    fn auto_pick_result(&mut self) {
+        let title = match self.disc_info.as_ref() {
+            Some(Ok(info)) => info.title.clone(),
+            _ => self.search_query_text.clone(),
+        };
+        let Some((idx, score)) = crate::search::pick::auto_pick(&self.search_results, &title) else {
+            self.log(
+                LogLevel::Warning,
+                format!(
+                    "Auto-pick: no result scores {:.2} or more, pick one by hand",
+                    crate::search::pick::MIN_AUTO_SCORE
+                ),
+            );
+            return;
+        };
+        self.log(LogLevel::Info, format!("Auto-pick: result {} (score {:.2})", idx + 1, score));
+        self.auto_save_url = self.search_results.get(idx).map(|r| r.image_url.clone());
+        self.scroll_to_selected_result = true;
+        self.select_result(idx);
+        // Already previewed, so poll_preview won't see it load
+        if !self.preview_loading && self.preview_texture.is_some() {
+            self.auto_save_url = None;
+            self.save_preview();
+        }
+    }
+
     /// Get the currently selected image URL
     #[allow(dead_code)]
     fn selected_image_url(&self) -> Option<&str> {
@@ -2012,6 +4127,113 @@ impl App {
             .map(|r| r.image_url.as_str())
     }
 
+    /// Select search result `idx` and preview it.
+    fn select_result(&mut self, idx: usize) {
+        self.selected_image_index = Some(idx);
+        self.pending_preview = None;
+        let url = self.search_results.get(idx).map(|r| r.image_url.clone());
+        if let Some(url) = url {
+            self.load_preview(&url);
+        }
+    }
+
+    /// Load the artwork already saved for the disc under the chosen role;
+    /// a front cover may be stored inside a CHD instead.
+    fn load_existing_art(&mut self, ctx: &egui::Context) {
+        self.existing_art_stale = false;
+        self.existing_art = None;
+        let Some(disc_path) = self.selected_path.clone() else {
+            return;
+        };
+        let path = PathBuf::from(self.output_path_for(&disc_path, self.export_role));
+        let bytes = if path.exists() {
+            std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        } else if self.export_role == ArtworkRole::Front && self.chd_artwork_found {
+            match chd_artwork::read_artwork(&disc_path) {
+                Ok(Some(bytes)) => Ok(bytes),
+                Ok(None) => return,
+                Err(e) => Err(e),
+            }
+        } else {
+            return;
+        };
+        let image = bytes.and_then(|bytes| {
+            image::load_from_memory(&bytes).map_err(|e| format!("Failed to decode current artwork: {}", e))
+        });
+        match image {
+            Ok(image) => {
+                self.existing_art = Some(ExistingArt {
+                    size: (image.width(), image.height()),
+                    // Nearest, like the output preview it's compared with
+                    texture: ctx.load_texture("existing_art", color_image_from(&image), egui::TextureOptions::NEAREST),
+                });
+            }
+            Err(e) => self.log(LogLevel::Warning, e),
+        }
+    }
+
+    /// Download & Save the previewed image under the chosen role, once
+    /// the preview has loaded. Asks first if that would replace artwork.
+    fn save_preview(&mut self) {
+        if self.export_in_progress || self.preview_loading || self.preview_texture.is_none() {
+            return;
+        }
+        let (Some(url), Some(disc_path)) = (self.preview_url.clone(), self.selected_path.clone()) else {
+            return;
+        };
+        if self.preview_is_html() {
+            self.log_failure(
+                tr!("toast-save-failed"),
+                format!("Not saving {}: the server sends a web page, not an image", url),
+            );
+            return;
+        }
+        let custom_path = self.output_path_edit.clone().filter(|p| !p.trim().is_empty());
+        let replacing = match &custom_path {
+            Some(path) => std::path::Path::new(path).exists(),
+            None => self.existing_art.is_some(),
+        };
+        // Ask before overwriting artwork, except in bulk mode where
+        // reprocessing existing art was chosen up front
+        if replacing && self.bulk_queue.is_none() && !self.confirm_replace {
+            self.confirm_replace = true;
+            return;
+        }
+        self.confirm_replace = false;
+        let role = self.export_role;
+        let path = match custom_path {
+            Some(path) => Some(path),
+            None => self.claim_output_path(&disc_path, role),
+        };
+        if let Some(path) = path {
+            self.start_export(&url, &path, role);
+        }
+    }
+
+    /// "Browse..." next to the output path: pick where the next save goes.
+    fn browse_output_path(&mut self) {
+        let Some(disc_path) = self.selected_path.clone() else {
+            return;
+        };
+        let current = PathBuf::from(
+            self.output_path_edit
+                .clone()
+                .unwrap_or_else(|| self.output_path_for(&disc_path, self.export_role)),
+        );
+        let format = self.export_config.format;
+        let mut dialog = rfd::FileDialog::new().add_filter(format.display_name(), &[format.extension()]);
+        if let Some(dir) = current.parent().filter(|d| d.is_dir()) {
+            dialog = dialog.set_directory(dir);
+        }
+        if let Some(name) = current.file_name() {
+            dialog = dialog.set_file_name(name.to_string_lossy());
+        }
+        if let Some(path) = dialog.save_file() {
+            self.output_path_edit = Some(path.display().to_string());
+            self.confirm_replace = false;
+        }
+    }
+
     /// Start loading a preview image
     fn load_preview(&mut self, url: &str) {
         // Don't reload if already loading this URL
@@ -2022,51 +4244,160 @@ impl App {
         let url = url.to_string();
         let (tx, rx) = mpsc::channel();
 
+        // Switching selection abandons the previous download.
+        if let Some(old) = self.preview_cancel.take() {
+            old.cancel();
+        }
+        let cancel = CancelToken::new();
+        self.preview_cancel = Some(cancel.clone());
+
         self.preview_loading = true;
         self.preview_url = Some(url.clone());
+        self.confirm_replace = false;
         self.preview_texture = None;
+        self.preview_image = None;
+        self.preview_render_source = None;
+        self.preview_edits = ImageEdits::default();
         self.preview_error = None;
         self.preview_receiver = Some(rx);
+        let (progress, feed) = ProgressFeed::channel();
+        self.preview_progress = Some(feed);
 
+        self.start_candidate_check(&url);
+        let raster_size = self.export_settings().raster_size();
         thread::spawn(move || {
-            let result = fetch_image_bytes(&url);
+            // Decoded here so a huge scan never reaches the window full-size.
+            // Output renders start from a copy at the raster size, which is
+            // already twice the largest output.
+            let result = fetch_image_bytes(&url, &cancel, &progress)
+                .map_err(|e| format!("Failed to load: {}", e))
+                .and_then(|bytes| {
+                    decode_preview(&bytes, raster_size, PREVIEW_MAX_EDGE, raster_size).map_err(String::from)
+                });
             let _ = tx.send(result);
         });
     }
 
+    /// Ask the server what's behind a web URL (HEAD), to show its type and
+    /// size next to the preview. Local pack files are skipped.
+    fn start_candidate_check(&mut self, url: &str) {
+        self.candidate_info = None;
+        self.candidate_info_receiver = None;
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return;
+        }
+        let url = url.to_string();
+        let (tx, rx) = mpsc::channel();
+        self.candidate_info_receiver = Some(rx);
+        thread::spawn(move || {
+            let info = crate::net::head(&url);
+            let _ = tx.send((url, info));
+        });
+    }
+
+    /// Poll the HEAD request for the previewed URL
+    fn poll_candidate_info(&mut self) {
+        if let Some(ref receiver) = self.candidate_info_receiver {
+            match receiver.try_recv() {
+                Ok((url, info)) => {
+                    self.candidate_info_receiver = None;
+                    if let Ok(ref info) = info {
+                        if info.is_html() {
+                            self.log(
+                                LogLevel::Warning,
+                                format!("{} returns a web page, not an image", url),
+                            );
+                        }
+                    }
+                    self.candidate_info = Some((url, info));
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.candidate_info_receiver = None;
+                }
+            }
+        }
+    }
+
+    /// Content type, size and server of the previewed URL, under the
+    /// preview heading. A web page or an error status is flagged in red.
+    fn candidate_info_line(&self, ui: &mut egui::Ui) {
+        let Some((url, info)) = &self.candidate_info else {
+            return;
+        };
+        if self.preview_url.as_ref() != Some(url) {
+            return;
+        }
+        match info {
+            Ok(info) => {
+                let parts: Vec<String> = [
+                    info.mime(),
+                    info.length.map(super::progress::format_size),
+                    info.server.clone(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !parts.is_empty() {
+                    ui.label(egui::RichText::new(parts.join(" · ")).small().color(egui::Color32::GRAY));
+                }
+                if info.is_html() {
+                    ui.colored_label(egui::Color32::RED, tr!("url-is-web-page"));
+                } else if !(200..300).contains(&info.status) {
+                    ui.colored_label(egui::Color32::RED, tr!("url-http-status", status = info.status));
+                }
+            }
+            Err(e) => {
+                ui.label(egui::RichText::new(tr!("url-check-failed")).small().color(egui::Color32::GRAY))
+                    .on_hover_text(e);
+            }
+        }
+    }
+
+    /// Whether result `idx` is listed, i.e. its source isn't hidden.
+    fn result_shown(&self, idx: usize) -> bool {
+        self.search_results
+            .get(idx)
+            .is_some_and(|r| !self.hidden_sources.contains(&r.result_source()))
+    }
+
+    /// Whether the server said the previewed URL is a web page.
+    fn preview_is_html(&self) -> bool {
+        matches!(
+            (&self.candidate_info, &self.preview_url),
+            (Some((url, Ok(info))), Some(preview)) if url == preview && info.is_html()
+        )
+    }
+
     /// Poll for preview image data
     fn poll_preview(&mut self, ctx: &egui::Context) {
+        if let Some(feed) = self.preview_progress.as_mut() {
+            feed.poll();
+        }
         if let Some(ref receiver) = self.preview_receiver {
             match receiver.try_recv() {
-                Ok(Ok(bytes)) => {
+                Ok(Ok(preview)) => {
                     self.preview_loading = false;
                     self.preview_receiver = None;
 
-                    // Convert bytes to texture
-                    match load_image_from_bytes(&bytes) {
-                        Ok(color_image) => {
-                            let texture = ctx.load_texture(
-                                "preview",
-                                color_image,
-                                egui::TextureOptions::LINEAR,
-                            );
-                            self.preview_texture = Some(texture);
-                            self.preview_error = None;
-                            self.log(LogLevel::Success, "Preview loaded");
-                        }
-                        Err(e) => {
-                            let msg = format!("Failed to decode image: {}", e);
-                            self.preview_error = Some(msg.clone());
-                            self.log(LogLevel::Error, msg);
-                        }
+                    // Render the texture from the (edited) image
+                    self.preview_source_size = preview.source_size;
+                    self.preview_render_source = preview.render_source.map(Arc::new);
+                    self.preview_image = Some(preview.image);
+                    self.refresh_preview_texture(ctx);
+                    self.preview_error = None;
+                    self.log(LogLevel::Success, "Preview loaded");
+                    if self.auto_save_url.is_some() && self.auto_save_url == self.preview_url {
+                        self.auto_save_url = None;
+                        self.save_preview();
                     }
                 }
-                Ok(Err(e)) => {
+                Ok(Err(msg)) => {
                     self.preview_loading = false;
                     self.preview_receiver = None;
-                    let msg = format!("Failed to load: {}", e);
+                    self.auto_save_url = None;
                     self.preview_error = Some(msg.clone());
-                    self.log(LogLevel::Error, msg);
+                    self.log_failure(tr!("toast-preview-failed"), msg);
                 }
                 Err(TryRecvError::Empty) => {
                     // Still loading
@@ -2079,30 +4410,291 @@ impl App {
         }
     }
 
+    /// Re-upload the preview texture with the current edits applied, or the
+    /// final output when "Show output" is on: rendered from the bounded
+    /// source the worker kept, badged and encoded as Download & Save would,
+    /// then drawn pixel-for-pixel.
+    fn refresh_preview_texture(&mut self, ctx: &egui::Context) {
+        self.refresh_compare_texture(ctx);
+        let Some(ref image) = self.preview_image else {
+            return;
+        };
+        self.preview_output_size = None;
+        if !self.preview_show_output {
+            let edited = self.preview_edits.apply(image.clone());
+            self.preview_texture = Some(ctx.load_texture(
+                "preview",
+                color_image_from(&edited),
+                egui::TextureOptions::LINEAR,
+            ));
+            return;
+        }
+
+        let source = render_source(image, self.preview_render_source.as_deref());
+        let mut settings = ExportSettings {
+            edits: self.preview_edits,
+            // Counts towards the size on disk
+            provenance: self
+                .selected_path
+                .as_deref()
+                .map(|p| self.provenance_for(p, self.preview_url.as_deref())),
+            ..self.export_settings()
+        };
+        if self.preview_unsharpened {
+            settings.sharpen = 0;
+        }
+        let badge_label = match (self.export_role, self.current_disc_marker()) {
+            (ArtworkRole::Front, (Some(n), total)) if n > 1 => Some(format_disc_label(n, total)),
+            _ => None,
+        };
+        let output = match render_saved_output(source.clone(), &settings, badge_label.as_deref()) {
+            Ok((output, bytes)) => {
+                self.preview_output_size = Some(Ok(bytes));
+                output
+            }
+            Err(e) => {
+                // Still show the unencoded render so the crop can be judged
+                self.preview_output_size = Some(Err(e.to_string()));
+                render_output(source, &settings)
+            }
+        };
+        // Nearest so 1:1 maps texels to screen pixels and 2x shows them crisp
+        self.preview_texture = Some(ctx.load_texture(
+            "preview",
+            color_image_from(&output),
+            egui::TextureOptions::NEAREST,
+        ));
+    }
+
+    /// `source` squared and resized with `edits` applied, as a texture drawn
+    /// texel-for-pixel. No badge or encoding, so it's cheap enough to redo
+    /// for both sides of the compare window.
+    fn output_texture(
+        &self,
+        ctx: &egui::Context,
+        name: &str,
+        source: &image::DynamicImage,
+        edits: ImageEdits,
+    ) -> egui::TextureHandle {
+        let settings = ExportSettings {
+            edits,
+            ..self.export_settings()
+        };
+        let output = render_output(source.clone(), &settings);
+        ctx.load_texture(name, color_image_from(&output), egui::TextureOptions::NEAREST)
+    }
+
+    /// Re-render the selected candidate's output while another is pinned
+    fn refresh_compare_texture(&mut self, ctx: &egui::Context) {
+        self.compare_output = None;
+        if self.compare_pin.is_none() {
+            return;
+        }
+        let Some(image) = self.preview_image.as_ref() else {
+            return;
+        };
+        let source = render_source(image, self.preview_render_source.as_deref());
+        self.compare_output = Some(self.output_texture(ctx, "compare_selected", &source, self.preview_edits));
+    }
+
+    /// Pin the current preview so the next selection shows next to it
+    fn pin_preview(&mut self, ctx: &egui::Context) {
+        let (Some(url), Some(image)) = (self.preview_url.clone(), self.preview_image.clone()) else {
+            return;
+        };
+        let source = render_source(&image, self.preview_render_source.as_deref());
+        let output = self.output_texture(ctx, "compare_pinned", &source, self.preview_edits);
+        self.compare_pin = Some(PinnedCandidate {
+            url,
+            image,
+            render_source: self.preview_render_source.clone(),
+            source_size: self.preview_source_size,
+            edits: self.preview_edits,
+            output,
+        });
+        // Same image on both sides until another result is selected
+        self.compare_output = None;
+        self.log(LogLevel::Info, "Pinned for comparison; select another result to compare");
+    }
+
+    /// Make the pinned candidate the preview again and close the comparison
+    fn use_pinned(&mut self, ctx: &egui::Context) {
+        let Some(pin) = self.compare_pin.take() else {
+            return;
+        };
+        if let Some(cancel) = self.preview_cancel.take() {
+            cancel.cancel();
+        }
+        self.preview_receiver = None;
+        self.preview_loading = false;
+        self.preview_error = None;
+        self.selected_image_index = self.search_results.iter().position(|r| r.image_url == pin.url);
+        self.preview_url = Some(pin.url);
+        self.preview_image = Some(pin.image);
+        self.preview_render_source = pin.render_source;
+        self.preview_source_size = pin.source_size;
+        self.preview_edits = pin.edits;
+        self.refresh_preview_texture(ctx);
+    }
+
+    /// Pinned and selected candidates side by side, each as it would be
+    /// saved, with a button to keep either
+    fn render_compare_window(&mut self, ctx: &egui::Context) {
+        let Some(pin) = self.compare_pin.as_ref() else {
+            return;
+        };
+        let mut open = true;
+        let mut use_pinned = false;
+        let mut use_selected = false;
+        let title_of = |url: &str| {
+            self.search_results
+                .iter()
+                .find(|r| r.image_url == url)
+                .map(|r| r.title.clone())
+                .unwrap_or_else(|| url.to_string())
+        };
+        let pinned_title = title_of(&pin.url);
+        let selected = self.preview_url.as_deref().filter(|url| *url != pin.url);
+        let selected_title = selected.map(title_of);
+        let texel = 1.0 / ctx.pixels_per_point();
+
+        egui::Window::new(tr!("compare-candidates"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.columns(2, |columns| {
+                    let ui = &mut columns[0];
+                    ui.heading(tr!("pinned"));
+                    ui.image((pin.output.id(), pin.output.size_vec2() * texel));
+                    ui.label(truncate_chars(&pinned_title, 40)).on_hover_text(&pin.url);
+                    ui.label(tr!("source-size", width = pin.source_size.0, height = pin.source_size.1));
+                    if ui.button(tr!("use-pinned")).clicked() {
+                        use_pinned = true;
+                    }
+
+                    let ui = &mut columns[1];
+                    ui.heading(tr!("selected"));
+                    match (&selected_title, &self.compare_output) {
+                        (None, _) => {
+                            ui.label(tr!("select-another-result-to-compare"));
+                        }
+                        (Some(_), None) if self.preview_loading => {
+                            transfer_bar(ui, self.preview_progress.as_ref().and_then(ProgressFeed::latest));
+                        }
+                        (Some(_), None) => {
+                            ui.colored_label(egui::Color32::RED, tr!("image-load-failed"));
+                        }
+                        (Some(title), Some(texture)) => {
+                            ui.image((texture.id(), texture.size_vec2() * texel));
+                            ui.label(truncate_chars(title, 40))
+                                .on_hover_text(self.preview_url.as_deref().unwrap_or_default());
+                            let (w, h) = self.preview_source_size;
+                            ui.label(tr!("source-size", width = w, height = h));
+                            if ui.button(tr!("use-selected")).clicked() {
+                                use_selected = true;
+                            }
+                        }
+                    }
+                });
+            });
+
+        if use_pinned {
+            self.use_pinned(ctx);
+        } else if use_selected || !open {
+            self.compare_pin = None;
+            self.compare_output = None;
+        }
+    }
+
+    /// Edits to export `url` with: the preview's edits when `url` is what's
+    /// being previewed, none otherwise.
+    fn edits_for_url(&self, url: &str) -> ImageEdits {
+        if self.preview_url.as_deref() == Some(url) {
+            self.preview_edits
+        } else {
+            ImageEdits::default()
+        }
+    }
+
     /// Start exporting artwork. Reads the current disc's `disc_number` from
     /// the parsed filename so discs 2+ pick up the "Disc N" overlay badge.
-    fn start_export(&mut self, image_url: &str, output_path: &str) {
+    /// Back covers and disc scans (`role` other than `Front`) are saved
+    /// without a badge.
+    fn start_export(&mut self, image_url: &str, output_path: &str, role: ArtworkRole) {
         let url = image_url.to_string();
         let path = output_path.to_string();
-        let (tx, rx) = mpsc::channel();
-
         self.export_in_progress = true;
-        self.export_receiver = Some(rx);
         self.pending_export_url = Some(url.clone());
+        self.pending_export_role = role;
+        let cancel = CancelToken::new();
+        self.export_cancel = Some(cancel.clone());
 
-        let (disc_number, disc_total) = self.current_disc_marker();
+        let (disc_number, disc_total) = match role {
+            ArtworkRole::Front => self.current_disc_marker(),
+            _ => (None, None),
+        };
 
         self.log(LogLevel::Info, format!("Downloading and converting to {}", path));
 
-        thread::spawn(move || {
-            let settings = ExportSettings::default();
-            let result = export_artwork_from_url_with_disc(
-                &url, &path, &settings, disc_number, disc_total,
-            );
-            let _ = tx.send(result);
+        let (progress, feed) = ProgressFeed::channel();
+        self.export_progress = Some(feed);
+        let settings = ExportSettings {
+            edits: self.edits_for_url(&url),
+            provenance: self.selected_path.as_deref().map(|p| self.provenance_for(p, Some(&url))),
+            disc: self.selected_path.as_deref().map(|p| self.disc_summary_for(p)),
+            // Only the front cover goes into the CHD
+            embed_in_chd: role == ArtworkRole::Front && self.export_config.embed_in_chd,
+            progress: Some(progress.into()),
+            // The download stops and nothing gets written once the user
+            // backs out
+            cancel: Some(cancel),
+            ..self.export_settings()
+        };
+        self.events.spawn(Job::Export, move || {
+            let result =
+                export_artwork_from_url_with_disc(&url, &path, &settings, disc_number, disc_total)
+                    .map_err(String::from);
+            AppEvent::Exported(result)
         });
     }
 
+    /// Write a generated title placeholder for the selected disc when no
+    /// usable artwork turned up. Rendering is cheap, so this runs on the UI
+    /// thread. In bulk mode the disc is recorded as saved.
+    fn save_placeholder(&mut self) {
+        let Some(selected) = self.selected_path.clone() else {
+            return;
+        };
+        let title = match &self.disc_info {
+            Some(Ok(info)) if !info.title.trim().is_empty() => info.title.clone(),
+            _ => parse_filename(&selected).title,
+        };
+        let Some(path) = self.claim_output_path(&selected, ArtworkRole::Front) else {
+            return;
+        };
+        let settings = ExportSettings {
+            provenance: Some(self.provenance_for(&selected, None)),
+            disc: Some(self.disc_summary_for(&selected)),
+            ..self.export_settings()
+        };
+        match export_placeholder(&title, &path, &settings) {
+            Ok(result) => {
+                self.log_export(
+                    format!("Saved placeholder for \"{}\" to {}", title, result.output_path),
+                    result.written,
+                );
+                for extra in &result.extra_outputs {
+                    self.log(LogLevel::Success, format!("Also saved {}", extra));
+                }
+                self.show_search_window = false;
+                if self.bulk_queue.is_some() {
+                    self.record_bulk_done("saved", None);
+                }
+            }
+            Err(e) => self.log_failure(tr!("toast-save-failed"), format!("Placeholder failed: {}", e)),
+        }
+    }
+
     /// After a successful save, re-export the same image URL to every
     /// multi-disc sibling of the current disc — same source, but each one
     /// stamped with its own disc-number badge. In bulk mode, also marks
@@ -2156,7 +4748,10 @@ impl App {
             ),
         );
 
-        let settings = ExportSettings::default();
+        let settings = ExportSettings {
+            edits: self.edits_for_url(image_url),
+            ..self.export_settings()
+        };
         for (sib_path, sib_marker) in &siblings {
             // For numbered markers, swap in the total hint we got from the
             // redump title (if any) so "Disc 2" becomes "Disc 2/3".
@@ -2170,11 +4765,18 @@ impl App {
                 role @ crate::disc::set_membership::DiscMarker::Role(_) => role.clone(),
             };
             let label = badge_marker.badge_label();
-            let out_path = generate_output_path(sib_path);
+            let Some(out_path) = self.claim_output_path(sib_path, ArtworkRole::Front) else {
+                continue;
+            };
+            let sib_settings = ExportSettings {
+                provenance: Some(self.provenance_for(sib_path, Some(image_url))),
+                disc: Some(self.disc_summary_for(sib_path)),
+                ..settings.clone()
+            };
             let result = export_artwork_from_url_with_label(
                 image_url,
                 &out_path,
-                &settings,
+                &sib_settings,
                 label.as_deref(),
             );
             let pretty = label.clone().unwrap_or_else(|| "(unbadged)".into());
@@ -2193,7 +4795,7 @@ impl App {
         // For bulk mode, mark these sibling queue items as done so the
         // user doesn't have to re-pick artwork for each.
         if let Some(queue) = self.bulk_queue.as_mut() {
-            let now = super::bulk::now_iso8601();
+            let now = crate::logging::now_iso8601();
             for (sib_path, _) in &siblings {
                 let sib_file = sib_path.to_string_lossy().to_string();
                 let Some(idx) = queue.items.iter().position(|it| it.file == sib_file) else {
@@ -2235,24 +4837,191 @@ impl App {
             if queue.statuses[idx] != super::bulk::ItemStatus::Pending {
                 continue;
             }
-            let path = std::path::PathBuf::from(&item.file);
-            if path == selected {
-                continue;
+            let path = std::path::PathBuf::from(&item.file);
+            if path == selected {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if crate::disc::set_membership::set_key(stem) != my_key {
+                continue;
+            }
+            let Some(marker) = crate::disc::set_membership::disc_marker_from_stem(stem)
+                .or_else(|| crate::disc::set_membership::disc_marker_from_stem(&item.best.title))
+            else {
+                continue;
+            };
+            out.push((path, marker));
+        }
+        out
+    }
+
+    /// Export settings for the current output configuration.
+    fn export_settings(&self) -> ExportSettings {
+        ExportSettings::from_config(&self.export_config)
+    }
+
+    /// Where artwork for `disc_path` is saved, per the filename template,
+    /// output format and `role`. For display; saves go through
+    /// `claim_output_path`.
+    fn output_path_for(&self, disc_path: &std::path::Path, role: ArtworkRole) -> String {
+        generate_named_output_path(disc_path, &OutputNaming::from_config(&self.export_config), role)
+    }
+
+    /// Output path to save to. In central-folder mode this reserves the
+    /// name for `disc_path`, numbering it if another disc already uses it.
+    /// Logs and returns `None` if the folder's index can't be updated.
+    fn claim_output_path(&mut self, disc_path: &std::path::Path, role: ArtworkRole) -> Option<String> {
+        match claim_named_output_path(disc_path, &OutputNaming::from_config(&self.export_config), role) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Can't choose an artwork name: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Log a save with an Undo link, and remember it for "Undo Last Export".
+    fn log_export(&mut self, message: String, written: Vec<WrittenFile>) {
+        let mut entry = LogMessage::new(LogLevel::Success, message);
+        entry.undo = written.first().map(|file| file.path.clone());
+        self.log_messages.push_back(entry);
+        while self.log_messages.len() > LOG_CAPACITY {
+            self.log_messages.pop_front();
+        }
+        self.export_history.push(written);
+        if self.export_history.len() > EXPORT_HISTORY_CAPACITY {
+            self.export_history.remove(0);
+        }
+    }
+
+    /// Restore every file the last save overwrote (extra sizes, sidecar, SD
+    /// card copy and RetroArch thumbnail included) and remove the ones it
+    /// created. Multi-disc siblings are separate saves, undone in turn.
+    fn undo_last_export(&mut self) {
+        let Some(written) = self.export_history.pop() else {
+            return;
+        };
+        let Some(main) = written.first() else {
+            return;
+        };
+        self.existing_art_stale = true;
+        match undo_export(&written) {
+            Ok(()) if main.backup.is_some() => {
+                self.log(LogLevel::Success, format!("Restored previous artwork at {}", main.path.display()))
+            }
+            Ok(()) => self.log(LogLevel::Success, format!("Removed {}", main.path.display())),
+            Err(e) => self.log(LogLevel::Error, format!("Undo failed: {}", e)),
+        }
+    }
+
+    /// The main cover of the last save, if there was one this session.
+    fn last_export_path(&self) -> Option<PathBuf> {
+        let written = self.export_history.last()?;
+        written.first().map(|file| file.path.clone())
+    }
+
+    /// Ask for the central artwork folder. Returns whether one was chosen.
+    fn pick_artwork_folder(&mut self) -> bool {
+        let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+            return false;
+        };
+        self.export_config.output_dir = Some(dir.display().to_string());
+        true
+    }
+
+    /// Parse the "Also save" field and persist the `export` config section.
+    fn save_export_config(&mut self) {
+        self.export_config.extra_sizes = parse_sizes(&self.export_extra_sizes_text);
+        self.export_extra_sizes_text = format_sizes(&self.export_config.extra_sizes);
+        let export = self.export_config.clone();
+        if let Err(e) = crate::config::update_config(|config| config.export = export) {
+            self.log(LogLevel::Error, format!("Failed to save export settings: {}", e));
+        }
+    }
+
+    /// Validate the artwork saved next to the current disc image and log
+    /// every USBODE format rule it breaks, then where the cover came from
+    /// if it carries our provenance comment.
+    fn check_artwork(&mut self, path: &std::path::Path) {
+        match validate_artwork_with_size(path, self.export_config.target_size) {
+            Ok(report) if report.is_compliant() => {
+                self.log(
+                    LogLevel::Success,
+                    format!("{} is USBODE-compatible", path.display()),
+                );
+            }
+            Ok(report) => {
+                self.log(
+                    LogLevel::Warning,
+                    format!(
+                        "{} fails {} check(s):",
+                        path.display(),
+                        report.issues.len()
+                    ),
+                );
+                for issue in &report.issues {
+                    self.log(LogLevel::Warning, format!("  - {}", issue));
+                }
+            }
+            Err(e) => self.log(LogLevel::Error, e),
+        }
+        match read_provenance(path) {
+            Ok(Some(p)) => {
+                self.log(LogLevel::Info, format!("Saved by ODE-artwork-downloader {}", p.tool_version));
+                for (label, value) in [("Title", &p.title), ("Source", &p.source_url), ("Disc SHA-1", &p.disc_sha1)] {
+                    if let Some(v) = value {
+                        self.log(LogLevel::Info, format!("  {}: {}", label, v));
+                    }
+                }
             }
-            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
-                continue;
-            };
-            if crate::disc::set_membership::set_key(stem) != my_key {
-                continue;
+            Ok(None) => self.log(LogLevel::Info, "No provenance recorded in this cover"),
+            Err(e) => log::debug!("provenance read failed: {e}"),
+        }
+    }
+
+    /// Provenance comment for artwork saved for `disc_path`. The title comes
+    /// from the loaded disc when it's the same one, else from the filename;
+    /// the SHA-1 is only included once that disc has been hashed.
+    fn provenance_for(&self, disc_path: &std::path::Path, source_url: Option<&str>) -> Provenance {
+        let title = match &self.disc_info {
+            Some(Ok(info)) if info.path == disc_path => info.title.clone(),
+            _ => parse_filename(disc_path).title,
+        };
+        Provenance::new(Some(title), source_url.map(str::to_string), self.disc_sha1_for(disc_path))
+    }
+
+    /// SHA-1 of `disc_path`, if it's the disc that was last hashed.
+    fn disc_sha1_for(&self, disc_path: &std::path::Path) -> Option<String> {
+        self.disc_sha1
+            .as_ref()
+            .filter(|(path, _)| path == disc_path)
+            .map(|(_, sha1)| sha1.clone())
+    }
+
+    /// Disc details for the metadata sidecar. Format, filesystem and volume
+    /// label are only known for the loaded disc; siblings get what the
+    /// filename tells us.
+    fn disc_summary_for(&self, disc_path: &std::path::Path) -> DiscSummary {
+        let parsed = parse_filename(disc_path);
+        let mut summary = DiscSummary {
+            path: disc_path.display().to_string(),
+            title: parsed.title,
+            serial: parsed.serial,
+            region: parsed.region,
+            sha1: self.disc_sha1_for(disc_path),
+            ..DiscSummary::default()
+        };
+        if let Some(Ok(info)) = &self.disc_info {
+            if info.path == disc_path {
+                summary.title = info.title.clone();
+                summary.format = Some(info.format.display_name().to_string());
+                summary.filesystem = Some(info.filesystem.display_name().to_string());
+                summary.volume_label = info.volume_label.clone();
             }
-            let Some(marker) = crate::disc::set_membership::disc_marker_from_stem(stem)
-                .or_else(|| crate::disc::set_membership::disc_marker_from_stem(&item.best.title))
-            else {
-                continue;
-            };
-            out.push((path, marker));
         }
-        out
+        summary
     }
 
     /// Snapshot the current disc's `(Disc N)` marker for export-time badging.
@@ -2274,83 +5043,362 @@ impl App {
     /// Convert a local image file (for drag-and-drop artwork)
     fn convert_local_image(&mut self, image_path: &std::path::Path, output_path: &str) {
         let image_path = image_path.to_path_buf();
-        let output = output_path.to_string();
-        let (tx, rx) = mpsc::channel();
+        self.convert_image_data(output_path, move || {
+            std::fs::read(&image_path).map_err(|e| format!("Failed to read file: {}", e))
+        });
+    }
+
+    /// Convert an image pasted from the clipboard, as if it had been dropped
+    fn convert_pasted_image(&mut self, img: image::RgbaImage, output_path: &str) {
+        self.convert_image_data(output_path, move || {
+            let mut png = Vec::new();
+            img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|e| format!("Failed to encode pasted image: {}", e))?;
+            Ok(png)
+        });
+    }
 
+    /// Export the image bytes produced by `load` (run on the worker thread)
+    /// to `output_path`.
+    fn convert_image_data<F>(&mut self, output_path: &str, load: F)
+    where
+        F: FnOnce() -> Result<Vec<u8>, String> + Send + 'static,
+    {
+        let output = output_path.to_string();
         self.export_in_progress = true;
-        self.export_receiver = Some(rx);
 
         self.log(LogLevel::Info, format!("Converting to {}", output));
 
-        thread::spawn(move || {
-            // Read the local file
-            let result = std::fs::read(&image_path)
-                .map_err(|e| format!("Failed to read file: {}", e))
-                .and_then(|bytes| {
-                    let settings = ExportSettings::default();
-                    export_artwork(&bytes, &output, &settings)
-                });
-            let _ = tx.send(result);
+        let (progress, feed) = ProgressFeed::channel();
+        self.export_progress = Some(feed);
+        let settings = ExportSettings {
+            provenance: self.selected_path.as_deref().map(|p| self.provenance_for(p, None)),
+            disc: self.selected_path.as_deref().map(|p| self.disc_summary_for(p)),
+            progress: Some(progress.into()),
+            ..self.export_settings()
+        };
+        self.events.spawn(Job::Export, move || {
+            let result = load()
+                .and_then(|bytes| export_artwork(&bytes, &output, &settings).map_err(String::from));
+            AppEvent::Exported(result)
         });
     }
 
-    /// Poll for export results
-    fn poll_export(&mut self) {
-        if let Some(ref receiver) = self.export_receiver {
-            match receiver.try_recv() {
-                Ok(Ok(result)) => {
-                    self.export_in_progress = false;
-                    self.export_receiver = None;
-                    self.show_search_window = false;
-                    let saved_url = self.pending_export_url.take();
-                    let msg = if result.was_cropped {
-                        format!(
-                            "Saved to {} (cropped from {}x{} to {}x{})",
-                            result.output_path,
-                            result.original_size.0,
-                            result.original_size.1,
-                            result.final_size.0,
-                            result.final_size.1
-                        )
-                    } else {
-                        format!(
-                            "Saved to {} ({}x{})",
-                            result.output_path,
-                            result.final_size.0,
-                            result.final_size.1
-                        )
-                    };
-                    self.log(LogLevel::Success, msg);
+    /// Fill the Manual URL field with a pasted or dropped URL and preview it.
+    fn preview_pasted_url(&mut self, url: String) {
+        self.log(LogLevel::Info, format!("Previewing pasted URL: {}", url));
+        self.load_preview(&url);
+        self.manual_url = url;
+    }
 
-                    // Multi-disc siblings get the same image with their own
-                    // disc-number badge. Done before bulk-advance so the
-                    // queue cursor doesn't move past siblings we still need
-                    // to process.
-                    if let Some(url) = saved_url.clone() {
-                        self.apply_to_siblings(&url);
-                    }
+    /// A web page dropped on the window: add the title its address names
+    /// to the search query, for the next search to pick up.
+    fn add_search_hint(&mut self, hint: &str) {
+        let query = self.search_query_text.trim();
+        if query.to_lowercase().contains(&hint.to_lowercase()) {
+            return;
+        }
+        self.search_query_text = if query.is_empty() {
+            hint.to_string()
+        } else {
+            format!("{} {}", query, hint)
+        };
+        self.log(LogLevel::Info, format!("Added \"{}\" from the dropped page to the search query", hint));
+    }
 
-                    // If we're in bulk mode, record the save and advance.
-                    if self.bulk_queue.is_some() {
-                        self.record_bulk_done("saved", saved_url);
-                    }
-                }
-                Ok(Err(e)) => {
-                    self.export_in_progress = false;
-                    self.export_receiver = None;
-                    self.pending_export_url = None;
-                    self.log(LogLevel::Error, format!("Export failed: {}", e));
-                }
-                Err(TryRecvError::Empty) => {
-                    // Still exporting
-                }
-                Err(TryRecvError::Disconnected) => {
-                    self.export_in_progress = false;
-                    self.export_receiver = None;
-                    self.pending_export_url = None;
-                    self.log(LogLevel::Error, "Export thread terminated unexpectedly");
-                }
+    /// The system clipboard, opening it if needed.
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new()?);
+        }
+        Ok(self.clipboard.as_mut().expect("clipboard was just opened"))
+    }
+
+    /// "Open Folder" / "Reveal": show the last saved cover's folder, or the
+    /// cover itself selected, in the system file manager.
+    fn show_last_export(&mut self, reveal: bool) {
+        let Some(output) = self.last_export_path() else {
+            return;
+        };
+        let result = if reveal {
+            crate::api::reveal_in_file_manager(&output)
+        } else {
+            let dir = output.parent().unwrap_or(&output);
+            crate::api::open_folder(dir)
+        };
+        if let Err(e) = result {
+            self.log(LogLevel::Error, e);
+        }
+    }
+
+    /// "Open Log File": show the log file in its default app, to attach to
+    /// a bug report.
+    fn open_log_file(&mut self) {
+        log::logger().flush();
+        // open_folder hands files to their default app too
+        let result = crate::logging::file_log::log_file_path().and_then(|path| crate::api::open_folder(&path));
+        if let Err(e) = result {
+            self.log(LogLevel::Error, e);
+        }
+    }
+
+    /// "Send to USBODE": upload the current disc's saved cover to the
+    /// device set in Settings, next to its copy of the disc.
+    fn send_to_usbode(&mut self) {
+        let Some(disc_path) = self.selected_path.clone() else {
+            return;
+        };
+        let artwork = PathBuf::from(self.output_path_for(&disc_path, ArtworkRole::Front));
+        if !artwork.exists() {
+            self.log(LogLevel::Warning, format!("No saved artwork at {} to send", artwork.display()));
+            return;
+        }
+        let address = crate::config::get_config().usbode.address.clone();
+        self.log(LogLevel::Info, format!("Sending {} to the USBODE at {}", artwork.display(), address.trim()));
+        self.events.spawn(Job::UsbodePush, move || {
+            AppEvent::UsbodePushed(crate::usbode::push_artwork(&address, &disc_path, &artwork))
+        });
+    }
+
+    fn on_usbode_pushed(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(remote_path) => self.log(LogLevel::Success, format!("Sent artwork to the USBODE as {}", remote_path)),
+            Err(e) => self.log_failure(tr!("toast-usbode-failed"), e),
+        }
+    }
+
+    /// "Copy Result": put the last saved cover on the clipboard as an image,
+    /// for pasting into other cataloguing tools.
+    fn copy_last_export(&mut self) {
+        let Some(output) = self.last_export_path() else {
+            return;
+        };
+        let image = match std::fs::read(&output)
+            .map_err(|e| format!("Failed to read {}: {}", output.display(), e))
+            .and_then(|data| {
+                image::load_from_memory(&data)
+                    .map_err(|e| format!("Failed to decode {}: {}", output.display(), e))
+            }) {
+            Ok(img) => img.to_rgba8(),
+            Err(e) => {
+                self.log(LogLevel::Error, e);
+                return;
+            }
+        };
+        let (width, height) = image.dimensions();
+        let data = arboard::ImageData {
+            width: width as usize,
+            height: height as usize,
+            bytes: image.into_raw().into(),
+        };
+        match self.clipboard().and_then(|c| c.set_image(data)) {
+            Ok(()) => self.log(
+                LogLevel::Success,
+                format!("Copied {} ({}x{}) to the clipboard", output.display(), width, height),
+            ),
+            Err(e) => self.log(LogLevel::Error, format!("Failed to copy to the clipboard: {}", e)),
+        }
+    }
+
+    /// Ctrl+V: convert an image on the clipboard for the selected disc.
+    /// Clipboards holding text (or nothing) are left alone.
+    fn paste_clipboard_image(&mut self) {
+        let pasted = match self.clipboard().and_then(|c| c.get_image()) {
+            Ok(pasted) => pasted,
+            Err(arboard::Error::ContentNotAvailable) => return,
+            Err(e) => {
+                self.log(LogLevel::Warning, format!("Couldn't read the clipboard: {}", e));
+                return;
+            }
+        };
+        let Some(disc_path) = self.selected_path.clone() else {
+            self.log(LogLevel::Warning, "Drop a disc image first, then paste artwork to convert");
+            return;
+        };
+        let Some(image) = image::RgbaImage::from_raw(
+            pasted.width as u32,
+            pasted.height as u32,
+            pasted.bytes.into_owned(),
+        ) else {
+            self.log(LogLevel::Error, "Clipboard image has an unexpected pixel layout");
+            return;
+        };
+        if let Some(output_path) = self.claim_output_path(&disc_path, ArtworkRole::Front) {
+            self.log(
+                LogLevel::Info,
+                format!("Converting pasted image ({}x{})", image.width(), image.height()),
+            );
+            self.convert_pasted_image(image, &output_path);
+        }
+    }
+
+    /// Handle everything dropped in one go. Several discs are queued for
+    /// processing in file-name order (the OS hands them over in no
+    /// particular order); a single disc loads into the main view, and a
+    /// dropped image is then converted as its cover.
+    fn handle_dropped_files(&mut self) {
+        let mut discs = Vec::new();
+        let mut images = Vec::new();
+        let mut link = None;
+        for file in std::mem::take(&mut self.dropped_files) {
+            // Link drags arrive without a path, named after the URL; some
+            // platforms put the URL in the path instead
+            let url = match &file.path {
+                Some(path) => dropped_link(path),
+                None => pasted_url(&file.name).map(str::to_string),
+            };
+            if let Some(url) = url {
+                link.get_or_insert(url);
+                continue;
+            }
+            match file.path {
+                Some(path) if is_artwork_file(&path) => images.push(path),
+                Some(path) => discs.push(path),
+                None => {}
+            }
+        }
+
+        let mut loaded_disc = None;
+        if discs.len() > 1 {
+            discs.sort_by_cached_key(|p| {
+                p.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default()
+            });
+            let total = discs.len();
+            let queued = discs
+                .into_iter()
+                .filter(|path| self.processing_queue.add(path.clone()))
+                .count();
+            let msg = if queued < total {
+                format!("Queued {} dropped discs for processing ({} already queued)", queued, total - queued)
+            } else {
+                format!("Queued {} dropped discs for processing", queued)
+            };
+            self.log(LogLevel::Info, msg);
+            self.show_queue_window = true;
+        } else if let Some(path) = discs.pop() {
+            self.process_file(path.clone());
+            loaded_disc = Some(path);
+        }
+
+        if let Some(url) = link {
+            match page_search_hint(&url) {
+                Some(hint) => self.add_search_hint(&hint),
+                None => self.preview_pasted_url(url),
+            }
+        }
+
+        let Some(image) = images.first() else {
+            return;
+        };
+        if images.len() > 1 {
+            self.log(
+                LogLevel::Warning,
+                format!("Dropped {} images; only {} is converted", images.len(), image.display()),
+            );
+        }
+        // A disc dropped alongside that didn't load (broken cue) mustn't
+        // have its artwork go to the previous disc
+        if loaded_disc.is_some() && self.selected_path != loaded_disc {
+            return;
+        }
+        if let Some(disc_path) = self.selected_path.clone() {
+            if let Some(output_path) = self.claim_output_path(&disc_path, ArtworkRole::Front) {
+                self.log(LogLevel::Info, format!("Converting dropped image: {}", image.display()));
+                self.convert_local_image(image, &output_path);
+            }
+        } else {
+            self.log(LogLevel::Warning, "Drop a disc image first, then drop artwork to convert");
+        }
+    }
+
+    /// Poll the running export's progress
+    fn poll_export(&mut self) {
+        if let Some(feed) = self.export_progress.as_mut() {
+            feed.poll();
+        }
+        if let Some(feed) = self.update_progress.as_mut() {
+            feed.poll();
+        }
+    }
+
+    /// An export finished
+    fn on_exported(&mut self, result: Result<ExportResult, String>) {
+        self.export_in_progress = false;
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                self.pending_export_url = None;
+                self.log_failure(tr!("toast-save-failed"), format!("Export failed: {}", e));
+                return;
             }
+        };
+        let saved_url = self.pending_export_url.take();
+        let role = std::mem::take(&mut self.pending_export_role);
+        self.existing_art_stale = true;
+        // A typed path is for one save; the next goes back to the template
+        self.output_path_edit = None;
+        let msg = if result.was_cropped {
+            format!(
+                "Saved to {} (cropped from {}x{} to {}x{}, {})",
+                result.output_path,
+                result.original_size.0,
+                result.original_size.1,
+                result.final_size.0,
+                result.final_size.1,
+                super::progress::format_size(result.file_size as u64)
+            )
+        } else {
+            format!(
+                "Saved to {} ({}x{}, {})",
+                result.output_path,
+                result.final_size.0,
+                result.final_size.1,
+                super::progress::format_size(result.file_size as u64)
+            )
+        };
+        self.log_export(msg, result.written.clone());
+        for extra in &result.extra_outputs {
+            self.log(LogLevel::Success, format!("Also saved {}", extra));
+        }
+        if result.embedded_in_chd {
+            self.chd_artwork_found = true;
+            self.log(LogLevel::Success, "Also stored the cover inside the CHD");
+        }
+        match &result.uploaded {
+            Some(Ok(remote)) => self.log(LogLevel::Success, format!("Also copied to {}", remote)),
+            Some(Err(e)) => self.log_failure(tr!("toast-upload-failed"), e.clone()),
+            None => {}
+        }
+        match &result.on_sd_card {
+            Some(Ok(path)) => self.log(LogLevel::Success, format!("Also copied to the SD card at {}", path.display())),
+            Some(Err(e)) => self.log_failure(tr!("toast-sd-card-failed"), e.clone()),
+            None => {}
+        }
+        match &result.retroarch_thumbnail {
+            Some(Ok(path)) => self.log(LogLevel::Success, format!("Also saved RetroArch thumbnail {}", path.display())),
+            Some(Err(e)) => self.log_failure(tr!("toast-retroarch-failed"), e.clone()),
+            None => {}
+        }
+
+        // Back covers / disc scans are extras: keep the window
+        // open so the front (or another role) can still be
+        // picked, and leave siblings and the bulk queue alone.
+        if role != ArtworkRole::Front {
+            return;
+        }
+        self.show_search_window = false;
+
+        // Multi-disc siblings get the same image with their own
+        // disc-number badge. Done before bulk-advance so the
+        // queue cursor doesn't move past siblings we still need
+        // to process.
+        if let Some(url) = saved_url.clone() {
+            self.apply_to_siblings(&url);
+        }
+
+        // If we're in bulk mode, record the save and advance.
+        if self.bulk_queue.is_some() {
+            self.record_bulk_done("saved", saved_url);
         }
     }
 
@@ -2362,135 +5410,363 @@ impl App {
 
         let config = self.update_config.update_check.clone();
         let current_version = env!("APP_VERSION").to_string();
-        let (tx, rx) = mpsc::channel();
 
-        self.update_receiver = Some(rx);
-
-        thread::spawn(move || {
+        self.events.spawn(Job::UpdateCheck, move || {
             let result = crate::update::check_for_updates(&config, &current_version)
                 .map_err(|e: Box<dyn std::error::Error>| e.to_string());
-            let _ = tx.send(result);
+            AppEvent::UpdateChecked(result)
+        });
+    }
+
+    /// Download the release's `asset`, check it against its `signature`
+    /// and install it over this copy
+    fn start_update_install(&mut self, asset: ReleaseAsset, signature: Option<ReleaseAsset>) {
+        if self.events.is_running(Job::UpdateInstall) {
+            return;
+        }
+        self.log(LogLevel::Info, format!("Downloading {}", asset.name));
+        let cancel = CancelToken::new();
+        self.update_cancel = Some(cancel.clone());
+        let (progress, feed) = ProgressFeed::channel();
+        self.update_progress = Some(feed);
+        self.events.spawn(Job::UpdateInstall, move || {
+            let result = crate::update::download_and_install(&asset, signature.as_ref(), &cancel, &|t| {
+                let _ = progress.send(t);
+            })
+            .map_err(|e| e.to_string());
+            AppEvent::UpdateInstalled(result)
         });
     }
 
+    /// Stop the update download. Nothing is installed once the token fires.
+    fn cancel_update_install(&mut self) {
+        if let Some(token) = self.update_cancel.take() {
+            token.cancel();
+        }
+        self.update_progress = None;
+        self.events.forget(Job::UpdateInstall);
+    }
+
+    /// The update download and install finished
+    fn on_update_installed(&mut self, result: Result<Installed, String>) {
+        self.update_cancel = None;
+        self.update_progress = None;
+        match result {
+            Ok(Installed::Replaced(program)) => {
+                self.log(LogLevel::Success, format!("Update installed to {}", program.display()));
+                self.show_update_notification = false;
+                self.update_relaunch = Some(program);
+            }
+            Ok(Installed::Opened(installer)) => {
+                self.log(
+                    LogLevel::Success,
+                    format!("Opened {}; quit ODE Artwork Downloader to finish installing", installer.display()),
+                );
+                self.show_update_notification = false;
+            }
+            Err(e) => self.log_failure(tr!("toast-update-failed"), e),
+        }
+    }
+
+    /// Handle what the app-wide background jobs published since the last
+    /// frame
+    fn poll_events(&mut self) {
+        for event in self.events.drain() {
+            match event {
+                AppEvent::UpdateChecked(result) => self.on_update_checked(result),
+                AppEvent::UpdateInstalled(result) => self.on_update_installed(result),
+                AppEvent::UserAgentCaptured(result) => self.on_user_agent_captured(result),
+                AppEvent::DbUpdated(result) => self.on_db_updated(result),
+                AppEvent::Exported(result) => self.on_exported(result),
+                AppEvent::AudioRipped(result) => self.on_audio_ripped(result),
+                AppEvent::ChdExtracted(result) => self.on_chd_extracted(result),
+                AppEvent::PackImported(result) => self.on_pack_imported(result),
+                AppEvent::FixScanned(result) => self.on_fix_scanned(result),
+                AppEvent::FixRan(summary) => self.on_fix_ran(summary),
+                AppEvent::UsbodePushed(result) => self.on_usbode_pushed(result),
+                AppEvent::Lost(job) => self.on_job_lost(job),
+            }
+        }
+    }
+
+    /// A job's thread died without publishing: clear its state and say so
+    fn on_job_lost(&mut self, job: Job) {
+        match job {
+            Job::UpdateCheck => self.update_check_done = true,
+            Job::UpdateInstall => {
+                self.update_cancel = None;
+                self.update_progress = None;
+                self.log(LogLevel::Error, "Update download thread terminated unexpectedly");
+            }
+            Job::UserAgentCapture => self.user_agent_capture_in_progress = false,
+            Job::DbUpdate => self.db_update_done = true,
+            Job::Export => {
+                self.export_in_progress = false;
+                self.pending_export_url = None;
+                self.log_failure(tr!("toast-save-failed"), "Export thread terminated unexpectedly".to_string());
+            }
+            Job::AudioRip => self.log(LogLevel::Error, "Audio extraction thread terminated unexpectedly"),
+            Job::ChdExtract => self.log(LogLevel::Error, "BIN/CUE extraction thread terminated unexpectedly"),
+            Job::PackImport => self.log(LogLevel::Error, "Cover pack import thread terminated unexpectedly"),
+            Job::FixScan => self.log(LogLevel::Error, "Artwork check thread terminated unexpectedly"),
+            Job::FixRun => self.log(LogLevel::Error, "Artwork fix thread terminated unexpectedly"),
+            Job::UsbodePush => {}
+        }
+    }
+
     /// Poll for global log messages (from the UiLogger)
     fn poll_global_logs(&mut self) {
         if let Some(ref receiver) = self.global_log_receiver {
             // Drain all available log messages
             while let Ok(msg) = receiver.try_recv() {
-                // Parse log level from the message format: "[LEVEL] target: message"
-                let level = if msg.starts_with("[ERROR]") {
-                    LogLevel::Error
-                } else if msg.starts_with("[WARN]") {
-                    LogLevel::Warning
-                } else if msg.starts_with("[INFO]") {
-                    LogLevel::Info
+                // Parse log level from the message format: "[LEVEL] target: message".
+                // The tag is dropped where the level carries it.
+                let (level, text) = if let Some(rest) = msg.strip_prefix("[ERROR] ") {
+                    (LogLevel::Error, rest)
+                } else if let Some(rest) = msg.strip_prefix("[WARN] ") {
+                    (LogLevel::Warning, rest)
+                } else if let Some(rest) = msg.strip_prefix("[INFO] ") {
+                    (LogLevel::Info, rest)
                 } else {
                     // DEBUG and TRACE are shown as Info in the UI
-                    LogLevel::Info
+                    (LogLevel::Info, msg.as_str())
                 };
 
-                self.log_messages.push(LogMessage {
-                    text: msg,
-                    level,
-                });
-
-                // Keep only last 100 messages
-                if self.log_messages.len() > 100 {
-                    self.log_messages.remove(0);
+                self.log_messages.push_back(LogMessage::new(level, text));
+                while self.log_messages.len() > LOG_CAPACITY {
+                    self.log_messages.pop_front();
                 }
             }
         }
     }
 
-    /// Poll for update check results
-    fn poll_update_check(&mut self) {
-        if let Some(ref receiver) = self.update_receiver {
-            match receiver.try_recv() {
-                Ok(Ok(info)) => {
-                    self.update_check_done = true;
-                    self.update_receiver = None;
-                    
-                    if info.is_outdated {
-                        self.update_info = Some(info.clone());
-                        self.show_update_notification = true;
-                        self.log(
-                            LogLevel::Info,
-                            format!("Update available: v{} → v{}", info.current_version, info.latest_version)
-                        );
-                    }
-                }
-                Ok(Err(_e)) => {
-                    self.update_check_done = true;
-                    self.update_receiver = None;
-                    // Silently fail update checks - don't spam users with errors
-                }
-                Err(TryRecvError::Empty) => {
-                    // Still checking
+    /// Write the messages the Log window shows to a file the user picks.
+    fn save_log(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name("ode-artwork-downloader.log")
+            .add_filter("Log", &["log", "txt"])
+            .save_file()
+        else {
+            return;
+        };
+        let mut text = String::new();
+        for msg in self.log_messages.iter().filter(|m| self.log_filter.matches(m)) {
+            text.push_str(&msg.to_line());
+            text.push('\n');
+        }
+        match std::fs::write(&path, text) {
+            Ok(()) => self.log(LogLevel::Success, format!("Saved log to {}", path.display())),
+            Err(e) => self.log(LogLevel::Error, format!("Failed to save log to {}: {}", path.display(), e)),
+        }
+    }
+
+    /// The update check finished
+    fn on_update_checked(&mut self, result: Result<UpdateInfo, String>) {
+        self.update_check_done = true;
+        match result {
+            Ok(info) => {
+                if info.is_outdated {
+                    self.update_info = Some(info.clone());
+                    self.show_update_notification = true;
+                    self.log(
+                        LogLevel::Info,
+                        format!(
+                            "{} available: v{} → v{}",
+                            if info.prerelease { "Beta" } else { "Update" },
+                            info.current_version,
+                            info.latest_version
+                        )
+                    );
                 }
-                Err(TryRecvError::Disconnected) => {
-                    self.update_check_done = true;
-                    self.update_receiver = None;
+            }
+            Err(_e) => {
+                // Silently fail update checks - don't spam users with errors
+            }
+        }
+    }
+
+    /// The user agent capture finished
+    fn on_user_agent_captured(&mut self, result: Result<String, String>) {
+        self.user_agent_capture_in_progress = false;
+        match result {
+            Ok(user_agent) => {
+                // Save to config file
+                let saved = crate::config::update_config(|config| {
+                    config.search.user_agent = Some(user_agent)
+                });
+                if let Err(e) = saved {
+                    self.log(LogLevel::Error, format!("Failed to save user agent: {}", e));
+                } else {
+                    self.log(LogLevel::Info, "Browser identity captured and saved".to_string());
+                    // Reload the search config to pick up the new user agent
+                    self.search_config = SearchConfig::default();
                 }
             }
+            Err(e) => {
+                self.log(LogLevel::Error, format!("Failed to capture browser identity: {}", e));
+            }
         }
     }
+}
 
-    /// Poll for user agent capture results
-    fn poll_user_agent_capture(&mut self) {
-        if let Some(ref receiver) = self.user_agent_receiver {
-            match receiver.try_recv() {
-                Ok(Ok(user_agent)) => {
-                    self.user_agent_capture_in_progress = false;
-                    self.user_agent_receiver = None;
+/// Parse a comma/space separated list of pixel sizes, ignoring junk.
+/// `text` as a URL if it's a single http(s) link, as copied from a
+/// browser's address bar or "Copy Image Address".
+fn pasted_url(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let is_url = (text.starts_with("http://") || text.starts_with("https://"))
+        && !text.contains(char::is_whitespace);
+    is_url.then_some(text)
+}
 
-                    // Save to config file
-                    if let Err(e) = crate::search::save_user_agent_to_config(&user_agent) {
-                        self.log(LogLevel::Error, format!("Failed to save user agent: {}", e));
-                    } else {
-                        self.log(LogLevel::Info, "Browser identity captured and saved".to_string());
-                        // Reload the search config to pick up the new user agent
-                        self.search_config = SearchConfig::default();
-                    }
-                }
-                Ok(Err(e)) => {
-                    self.user_agent_capture_in_progress = false;
-                    self.user_agent_receiver = None;
-                    self.log(LogLevel::Error, format!("Failed to capture browser identity: {}", e));
-                }
-                Err(TryRecvError::Empty) => {
-                    // Still waiting for browser
-                }
-                Err(TryRecvError::Disconnected) => {
-                    self.user_agent_capture_in_progress = false;
-                    self.user_agent_receiver = None;
-                }
+/// The URL in a dropped "file" that is really a link: a path that is a URL
+/// itself, or an Internet shortcut (`.url`) or `.webloc` saved from a browser.
+fn dropped_link(path: &std::path::Path) -> Option<String> {
+    let text = path.to_string_lossy();
+    // Joined as a path, "https://" can lose a slash
+    for (scheme, fixed) in [("https:/", "https://"), ("http:/", "http://")] {
+        if let Some(rest) = text.strip_prefix(scheme) {
+            return pasted_url(&format!("{}{}", fixed, rest.trim_start_matches('/'))).map(str::to_string);
+        }
+    }
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    if ext != "url" && ext != "webloc" {
+        return None;
+    }
+    let contents = std::fs::read_to_string(path).ok()?;
+    let url = if ext == "url" {
+        contents.lines().find_map(|l| l.trim().strip_prefix("URL="))
+    } else {
+        contents.split("<string>").nth(1).and_then(|s| s.split("</string>").next())
+    };
+    pasted_url(url?).map(str::to_string)
+}
+
+/// Search words for a dragged link that looks like a web page rather than
+/// an image, taken from the title slug at the end of its address
+/// (".../game/1234/myst-masterpiece-edition/" gives "myst masterpiece
+/// edition"). `None` for image links and for addresses without one.
+fn page_search_hint(url: &str) -> Option<String> {
+    let address = url.split(['?', '#']).next()?;
+    let path = address.splitn(4, '/').nth(3).unwrap_or_default();
+    let last = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .rfind(|s| !s.chars().all(|c| c.is_ascii_digit()))?;
+    let last = urlencoding::decode(last).ok()?;
+    let stem = match last.rsplit_once('.') {
+        Some((stem, ext)) => {
+            let ext = ext.to_ascii_lowercase();
+            if !matches!(ext.as_str(), "html" | "htm" | "php" | "asp" | "aspx" | "jsp") {
+                return None;
             }
+            stem
         }
+        None => last.as_ref(),
+    };
+    let words: Vec<&str> = stem.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let is_title = words.iter().any(|w| w.chars().count() > 1 && is_title_word(w))
+        && words.iter().all(|w| is_title_word(w) || w.chars().all(|c| c.is_ascii_digit()));
+    is_title.then(|| words.join(" "))
+}
+
+/// "myst", "Exile" or "III", but not ids and hashes like "aB3x" or "xKqPz".
+fn is_title_word(word: &str) -> bool {
+    let mut rest = word.chars().skip(1);
+    word.chars().all(char::is_alphabetic)
+        && (rest.clone().all(|c| !c.is_uppercase()) || rest.all(|c| !c.is_lowercase()))
+}
+
+fn parse_sizes(text: &str) -> Vec<u32> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|s| s.trim().trim_end_matches("px").parse::<u32>().ok())
+        .filter(|s| *s > 0)
+        .collect()
+}
+
+fn format_sizes(sizes: &[u32]) -> String {
+    sizes
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `text` cut to `max` characters, with "..." when anything was cut
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        format!("{}...", text.chars().take(max).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Short name and colour of a result source, for its badge in the results
+/// list and its filter tick box.
+fn source_badge(source: ResultSource) -> (String, egui::Color32) {
+    match source {
+        ResultSource::Local => (tr!("source-local"), egui::Color32::from_rgb(120, 200, 120)),
+        ResultSource::MusicBrainz => ("MB".to_string(), egui::Color32::from_rgb(235, 116, 59)),
+        ResultSource::Discogs => ("Discogs".to_string(), egui::Color32::from_rgb(200, 200, 200)),
+        ResultSource::MobyGames => ("Moby".to_string(), egui::Color32::from_rgb(90, 170, 230)),
+        ResultSource::Web => ("DDG".to_string(), egui::Color32::from_rgb(222, 88, 51)),
     }
 }
 
-/// Fetch image bytes from a URL
-fn fetch_image_bytes(url: &str) -> Result<Vec<u8>, String> {
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+/// Label colour for an identification confidence level
+fn confidence_color(level: ConfidenceLevel) -> egui::Color32 {
+    match level {
+        ConfidenceLevel::High => egui::Color32::GREEN,
+        ConfidenceLevel::Medium => egui::Color32::YELLOW,
+        ConfidenceLevel::Low => egui::Color32::LIGHT_RED,
+    }
+}
 
-    let response = client
-        .get(url)
-        .send()
-        .map_err(|e| format!("Failed to fetch image: {}", e))?;
+/// Whether a dropped file is artwork to convert rather than a disc image
+fn is_artwork_file(path: &std::path::Path) -> bool {
+    let ext = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    matches!(
+        ext.as_str(),
+        "jpg" | "jpeg" | "png" | "gif" | "webp" | "bmp"
+            | "avif" | "heic" | "heif" | "svg" | "pdf"
+    )
+}
 
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+/// Prepend local cover-pack hits for `title` to an online search result. A
+/// failed online search still succeeds when the packs have something, so
+/// offline runs keep working.
+fn with_pack_results(
+    title: &str,
+    online: Result<Vec<ImageResult>, String>,
+) -> Result<Vec<ImageResult>, String> {
+    let mut local = crate::packs::search_local(title, 5);
+    match online {
+        Ok(results) => {
+            local.extend(results);
+            Ok(local)
+        }
+        Err(e) if !local.is_empty() => {
+            log::warn!("Online search failed ({}); using local cover packs only", e);
+            Ok(local)
+        }
+        Err(e) => Err(e),
     }
+}
 
-    response
-        .bytes()
-        .map(|b| b.to_vec())
-        .map_err(|e| format!("Failed to read image bytes: {}", e))
+/// Fetch image bytes from a URL for the preview pane, sending the bytes
+/// received to `progress`
+fn fetch_image_bytes(
+    url: &str,
+    cancel: &CancelToken,
+    progress: &mpsc::Sender<Transfer>,
+) -> Result<Vec<u8>, String> {
+    crate::net::fetch_bytes_with_progress(url, Timeout::Request, cancel, &|transfer| {
+        let _ = progress.send(transfer);
+    })
+    .map_err(String::from)
 }
 
 /// Single-line scrolling text. If `text` fits in `max_width`, renders as a
@@ -2607,16 +5883,43 @@ fn count_resumed(job_path: &std::path::Path, items: &[super::bulk::QueueItem]) -
     items.iter().filter(|it| done_files.contains(&it.file)).count()
 }
 
-/// Load image from bytes into egui ColorImage
-fn load_image_from_bytes(bytes: &[u8]) -> Result<egui::ColorImage, String> {
-    let image = image::load_from_memory(bytes)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+/// Longest edge of a thumbnail in the batch review window
+const REVIEW_THUMB_EDGE: f32 = 96.0;
+
+/// One search result in the batch review window's menu: title, source and
+/// size when known
+fn candidate_label(result: &ImageResult) -> String {
+    match (result.width, result.height) {
+        (Some(w), Some(h)) => format!("{} — {} ({}×{})", result.title, result.source, w, h),
+        _ => format!("{} — {}", result.title, result.source),
+    }
+}
+
+/// How long the arrow keys have to rest on a result before it's previewed.
+const PREVIEW_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Longest edge of the decoded image kept for the preview pane. Edits are
+/// re-applied to this copy, so it needs to stay small enough to redo per click.
+const PREVIEW_MAX_EDGE: u32 = 1024;
+
+/// Longest edge the volume icon is decoded to, and the size it's shown at.
+const VOLUME_ICON_EDGE: u32 = 64;
+const VOLUME_ICON_SIZE: f32 = 32.0;
 
+/// What renders that must match the saved file start from: the worker's
+/// bounded copy of the source when `preview` was shrunk, else `preview`
+/// itself. Nothing is decoded here, so it's cheap on the UI thread.
+fn render_source(preview: &image::DynamicImage, bounded: Option<&image::DynamicImage>) -> image::DynamicImage {
+    bounded.unwrap_or(preview).clone()
+}
+
+/// Convert a decoded image into an egui ColorImage
+fn color_image_from(image: &image::DynamicImage) -> egui::ColorImage {
     let size = [image.width() as usize, image.height() as usize];
     let image_buffer = image.to_rgba8();
     let pixels = image_buffer.as_flat_samples();
 
-    Ok(egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice()))
+    egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice())
 }
 
 impl eframe::App for App {
@@ -2631,21 +5934,31 @@ impl eframe::App for App {
 
         // Poll for preview image
         self.poll_preview(&ctx);
+        self.poll_pending_preview(&ctx);
 
-        // Poll for export results
+        // Export progress, then what the app-wide jobs (update check, DB
+        // update, exports, rips, imports, fixes) finished with
         self.poll_export();
+        self.poll_events();
+
+        // Poll the track-hashing worker
+        self.poll_hash();
 
-        // Poll for update check
-        self.poll_update_check();
+        // Poll the audio CD tracklist lookup
+        self.poll_tracklist();
 
-        // Poll for user agent capture
-        self.poll_user_agent_capture();
+        // Poll the volume icon reader
+        self.poll_volume_icon(&ctx);
+        self.poll_entry_counts();
+        self.poll_candidate_info();
 
-        // Poll for redump DB update result
-        self.poll_db_update();
+        // Queue what the watched folders gained, then start and collect
+        // processing-queue workers
+        self.poll_watch(&ctx);
+        self.tick_processing_queue();
 
-        // Poll the track-hashing worker
-        self.poll_hash();
+        // Pick up "Scan Folder..." rows
+        self.poll_folder_scan();
 
         // Bulk-job loader modal (rendered as a centered Window). Independent
         // of the central-panel ui, so render through the context.
@@ -2655,7 +5968,32 @@ impl eframe::App for App {
         // user (or the bulk-mode timeout) resolves it.
         self.render_broken_cue_prompt(&ctx);
 
-        // Bulk-mode keyboard shortcuts.
+        // "Fix Artwork" dry-run report, awaiting confirmation.
+        self.render_fix_artwork_dialog(&ctx);
+
+        // Processing queue for multi-disc drops.
+        self.render_queue_window(&ctx);
+        self.render_review_window(&ctx);
+
+        // "Scan Folder..." results table.
+        self.render_folder_scan_window(&ctx);
+
+        // Pinned vs selected search candidate.
+        self.render_compare_window(&ctx);
+
+        // Settings window.
+        self.render_settings_window(&ctx);
+
+        // Ctrl+Shift+P command palette.
+        self.render_command_palette(&ctx);
+
+        // Error toasts in the bottom right corner.
+        if self.toasts.show(&ctx) {
+            self.show_log_window = true;
+        }
+
+        // Keyboard shortcuts, then the bulk-mode ones.
+        self.handle_shortcuts(&ctx);
         self.handle_bulk_hotkeys(&ctx);
 
         // Drive the bulk queue: load next item if cursor advanced. The
@@ -2669,7 +6007,7 @@ impl eframe::App for App {
         self.render_bulk_banner(ui);
 
         // Request repaint while loading
-        if self.search_in_progress || self.preview_loading || self.export_in_progress || self.user_agent_capture_in_progress || self.hash_progress.is_some() {
+        if self.search_in_progress || self.preview_loading || self.export_in_progress || self.user_agent_capture_in_progress || self.hash_progress.is_some() || self.events.is_running(Job::PackImport) || self.events.is_running(Job::FixScan) || self.events.is_running(Job::FixRun) || self.tracklist_receiver.is_some() || self.volume_icon_receiver.is_some() || self.entry_counts_receiver.is_some() || self.candidate_info_receiver.is_some() || self.events.is_running(Job::UsbodePush) || self.processing_queue.is_busy() || self.folder_scan.as_ref().is_some_and(|s| s.is_running()) {
             ctx.request_repaint_after(std::time::Duration::from_millis(100));
         }
 
@@ -2680,33 +6018,26 @@ impl eframe::App for App {
             }
         });
 
-        // Process dropped files
-        if let Some(file) = self.dropped_files.pop() {
-            if let Some(path) = file.path {
-                // Check if it's an image file (for manual artwork drop)
-                let ext = path.extension()
-                    .and_then(|e| e.to_str())
-                    .map(|e| e.to_lowercase())
-                    .unwrap_or_default();
-
-                let is_image = matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "gif" | "webp" | "bmp");
-
-                if is_image {
-                    // It's an image - convert and save if we have a disc selected
-                    if let Some(ref disc_path) = self.selected_path {
-                        let output_path = generate_output_path(disc_path);
-                        self.log(LogLevel::Info, format!("Converting dropped image: {}", path.display()));
-                        self.convert_local_image(&path, &output_path);
-                    } else {
-                        self.log(LogLevel::Warning, "Drop a disc image first, then drop artwork to convert");
-                    }
-                } else {
-                    // It's a disc image
-                    self.process_file(path);
-                }
+        // Ctrl+V (Cmd+V) outside a text field: a pasted URL is previewed,
+        // otherwise the clipboard is tried for image data
+        let (paste, pasted_text) = ctx.input(|i| {
+            let text = i.events.iter().find_map(|e| match e {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            });
+            (i.modifiers.command && i.key_pressed(egui::Key::V), text)
+        });
+        if (paste || pasted_text.is_some()) && ctx.memory(|m| m.focused().is_none()) {
+            if let Some(url) = pasted_text.as_deref().and_then(pasted_url) {
+                self.preview_pasted_url(url.to_string());
+            } else if paste && !self.export_in_progress {
+                self.paste_clipboard_image();
             }
         }
 
+        // Process dropped files
+        self.handle_dropped_files();
+
         // Top panel with title
         egui::Panel::top("top_panel").show_inside(ui, |ui| {
             ui.add_space(4.0);
@@ -2717,13 +6048,38 @@ impl eframe::App for App {
                     ui.separator();
                     let log_count = self.log_messages.len();
                     let log_btn_text = if log_count > 0 {
-                        format!("Log ({})", log_count)
+                        tr!("log-button-count", count = log_count)
                     } else {
-                        "Log".to_string()
+                        tr!("log")
                     };
-                    if ui.button(log_btn_text).clicked() {
+                    if ui
+                        .button(log_btn_text)
+                        .on_hover_text(ui.ctx().format_shortcut(&LOG_SHORTCUT))
+                        .clicked()
+                    {
                         self.show_log_window = !self.show_log_window;
                     }
+                    if ui.button(tr!("settings")).clicked() && self.settings_dialog.is_none() {
+                        self.open_settings();
+                    }
+                    let mut theme = self.appearance.theme;
+                    egui::ComboBox::from_id_salt("theme_combo")
+                        .width(70.0)
+                        .selected_text(super::names::theme(theme))
+                        .show_ui(ui, |ui| {
+                            for mode in ThemeMode::ALL {
+                                ui.selectable_value(&mut theme, mode, super::names::theme(mode));
+                            }
+                        });
+                    if theme != self.appearance.theme {
+                        self.set_theme(&ctx, theme);
+                    }
+                    if !self.processing_queue.entries.is_empty() {
+                        let (finished, total) = self.processing_queue.progress();
+                        if ui.button(tr!("queue-button", done = finished, total = total)).clicked() {
+                            self.show_queue_window = !self.show_queue_window;
+                        }
+                    }
                 });
             });
             ui.add_space(4.0);
@@ -2731,57 +6087,99 @@ impl eframe::App for App {
 
         // Log window (separate window, hidden by default)
         if self.show_log_window {
-            egui::Window::new("Log")
+            let mut save_log_clicked = false;
+            let mut clear_log_clicked = false;
+            let mut undo_clicked = false;
+            // Only the latest save can be undone
+            let undo_target = self.last_export_path().filter(|_| !self.export_in_progress);
+            egui::Window::new(tr!("log"))
                 .open(&mut self.show_log_window)
-                .default_size([500.0, 300.0])
+                .default_size([600.0, 320.0])
                 .resizable(true)
                 .show(&ctx, |ui| {
+                    let shown: Vec<&LogMessage> =
+                        self.log_messages.iter().filter(|m| self.log_filter.matches(m)).collect();
                     ui.horizontal(|ui| {
-                        if ui.button("Clear").clicked() {
-                            self.log_messages.clear();
-                        }
-                        if ui.button("Copy").clicked() {
-                            let joined = self
-                                .log_messages
-                                .iter()
-                                .map(|m| m.text.as_str())
-                                .collect::<Vec<_>>()
-                                .join("\n");
+                        if ui.button(tr!("clear")).clicked() {
+                            clear_log_clicked = true;
+                        }
+                        if ui.button(tr!("copy")).clicked() {
+                            let joined = shown.iter().map(|m| m.to_line()).collect::<Vec<_>>().join("\n");
                             ui.ctx().copy_text(joined);
                         }
-                        if ui.button("Settings").clicked() {
+                        if ui.button(tr!("save-log")).on_hover_text(tr!("save-log-tip")).clicked() {
+                            save_log_clicked = true;
+                        }
+                        if ui.button(tr!("settings")).clicked() {
                             self.show_log_settings = true;
                         }
                     });
+                    ui.horizontal(|ui| {
+                        ui.toggle_value(&mut self.log_filter.info, tr!("log-info"));
+                        ui.toggle_value(&mut self.log_filter.warnings, tr!("log-warnings"));
+                        ui.toggle_value(&mut self.log_filter.errors, tr!("log-errors"));
+                        ui.separator();
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.log_filter.text)
+                                .hint_text(tr!("log-filter-hint"))
+                                .desired_width(180.0),
+                        );
+                        if shown.len() < self.log_messages.len() {
+                            ui.weak(tr!("log-showing", shown = shown.len(), total = self.log_messages.len()));
+                        }
+                    });
                     ui.separator();
 
-                    egui::ScrollArea::vertical()
+                    // Only the visible rows are laid out, so a full log
+                    // stays cheap to draw; lines don't wrap
+                    let row_height = ui.text_style_height(&egui::TextStyle::Body);
+                    egui::ScrollArea::both()
                         .auto_shrink([false, false])
                         .stick_to_bottom(true)
-                        .show(ui, |ui| {
-                            for msg in &self.log_messages {
+                        .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                            for msg in &shown[rows] {
                                 let color = match msg.level {
                                     LogLevel::Info => egui::Color32::GRAY,
                                     LogLevel::Success => egui::Color32::GREEN,
                                     LogLevel::Warning => egui::Color32::YELLOW,
                                     LogLevel::Error => egui::Color32::RED,
                                 };
-                                ui.colored_label(color, &msg.text);
+                                ui.horizontal(|ui| {
+                                    ui.weak(msg.clock()).on_hover_text(&msg.time);
+                                    ui.add(egui::Label::new(egui::RichText::new(&msg.text).color(color)).extend());
+                                    if msg.undo.is_some()
+                                        && msg.undo == undo_target
+                                        && ui.link(tr!("undo")).on_hover_text(tr!("undo-last-export")).clicked()
+                                    {
+                                        undo_clicked = true;
+                                    }
+                                });
                             }
                         });
                 });
+            if clear_log_clicked {
+                self.log_messages.clear();
+            }
+            if undo_clicked {
+                self.undo_last_export();
+            }
+            if save_log_clicked {
+                self.save_log();
+            }
         }
 
         // Log settings dialog
         if self.show_log_settings {
             let mut open = self.show_log_settings;
             let mut new_level: Option<String> = None;
-            egui::Window::new("Log Settings")
+            let mut new_file_level: Option<String> = None;
+            let mut open_log_file = false;
+            egui::Window::new(tr!("log-settings"))
                 .open(&mut open)
                 .collapsible(false)
                 .resizable(false)
                 .show(&ctx, |ui| {
-                    ui.label("Log level:");
+                    ui.label(tr!("log-level"));
                     egui::ComboBox::from_id_salt("log_level_combo")
                         .selected_text(&self.log_level)
                         .show_ui(ui, |ui| {
@@ -2794,20 +6192,42 @@ impl eframe::App for App {
                                 }
                             }
                         });
+                    ui.label(tr!("log-file-level"));
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_salt("log_file_level_combo")
+                            .selected_text(&self.log_file_level)
+                            .show_ui(ui, |ui| {
+                                for level in crate::config::LOG_LEVELS {
+                                    if ui.selectable_label(self.log_file_level == level, level).clicked() {
+                                        new_file_level = Some(level.to_string());
+                                    }
+                                }
+                            });
+                        if ui.button(tr!("open-log-file")).on_hover_text(tr!("open-log-file-tip")).clicked() {
+                            open_log_file = true;
+                        }
+                    });
                     ui.add_space(4.0);
-                    ui.label("Applies immediately and is saved to config.json.");
+                    ui.label(tr!("log-settings-note"));
                 });
             self.show_log_settings = open;
             if let Some(level) = new_level {
                 self.log_level = level.clone();
-                log::set_max_level(crate::logging::ui_logger::parse_level(&level));
-                if let Err(e) = crate::config::save_config_field(
-                    "log_level",
-                    serde_json::Value::String(level),
-                ) {
+                crate::logging::ui_logger::set_level(crate::logging::ui_logger::parse_level(&level));
+                if let Err(e) = crate::config::update_config(|config| config.log_level = level) {
                     self.log(LogLevel::Error, format!("Failed to save log level: {e}"));
                 }
             }
+            if let Some(level) = new_file_level {
+                self.log_file_level = level.clone();
+                crate::logging::ui_logger::set_file_level(crate::logging::ui_logger::parse_level(&level));
+                if let Err(e) = crate::config::update_config(|config| config.log_file_level = level) {
+                    self.log(LogLevel::Error, format!("Failed to save log file level: {e}"));
+                }
+            }
+            if open_log_file {
+                self.open_log_file();
+            }
         }
 
         // Browse window (for filesystem browsing)
@@ -2821,7 +6241,7 @@ impl eframe::App for App {
             let default_w = (app_rect.x * 0.75).max(600.0);
             let default_h = (app_rect.y * 0.75).max(400.0);
 
-            egui::Window::new("Browse Disc Contents")
+            egui::Window::new(tr!("browse-disc-contents"))
                 .open(&mut self.show_browse_window)
                 .default_size([default_w, default_h])
                 .min_width(600.0)
@@ -2836,7 +6256,7 @@ impl eframe::App for App {
                     if let Some(ref info) = disc_info_clone {
                         self.browse_view.show(ui, info, content_h);
                     } else {
-                        ui.label("No disc loaded");
+                        ui.label(tr!("no-disc-loaded"));
                     }
                 });
 
@@ -2848,6 +6268,9 @@ impl eframe::App for App {
 
         // Artwork Search window — opens automatically when a search is started.
         if self.show_search_window {
+            if self.existing_art_stale {
+                self.load_existing_art(&ctx);
+            }
             // Pre-compute values needed by both the window UI and the post-render
             // handlers, so we don't have to re-borrow `self.disc_info` inside the
             // window closure (which already mutably borrows other fields of self).
@@ -2896,7 +6319,19 @@ impl eframe::App for App {
             let mut search_clicked = false;
             let mut browser_clicked = false;
             let mut manual_preview_clicked = false;
-            let mut start_export_data: Option<(String, String)> = None;
+            let mut cancel_search_clicked = false;
+            let mut cancel_preview_clicked = false;
+            let mut cancel_export_clicked = false;
+            let mut preview_edits_change: Option<ImageEdits> = None;
+            let mut pin_clicked = false;
+            let mut preview_view_change: Option<(bool, bool, bool)> = None;
+            let mut template_changed = false;
+            let mut role_changed = false;
+            let mut placeholder_clicked = false;
+            let mut save_clicked = false;
+            let mut replace_answer: Option<bool> = None;
+            let mut output_path_change: Option<String> = None;
+            let mut browse_output_clicked = false;
             let mut selected_idx_change: Option<usize> = None;
 
             // Draw the window at 75% width / 85% height of the app's content
@@ -2912,7 +6347,7 @@ impl eframe::App for App {
                 let content = ctx.content_rect();
                 egui::pos2(content.left() + 12.0, y + 8.0)
             });
-            let mut window = egui::Window::new("Artwork Search")
+            let mut window = egui::Window::new(tr!("artwork-search"))
                 .open(&mut self.show_search_window)
                 .default_size([default_w, default_h])
                 .min_width(600.0)
@@ -2924,14 +6359,14 @@ impl eframe::App for App {
             window.show(&ctx, |ui| {
                     // ---- Content type ----
                     ui.horizontal(|ui| {
-                        ui.label("Content Type:");
+                        ui.label(tr!("content-type"));
                         egui::ComboBox::new("search_window_content_type", "")
-                            .selected_text(self.search_config.content_type.display_name())
+                            .selected_text(super::names::content_type(self.search_config.content_type))
                             .show_ui(ui, |ui| {
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Any, "Any").clicked() { content_type_changed = true; }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Games, "Games").clicked() { content_type_changed = true; }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AppsUtilities, "Apps & Utilities").clicked() { content_type_changed = true; }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AudioCDs, "Audio CDs").clicked() { content_type_changed = true; }
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Any, tr!("content-type-any")).clicked() { content_type_changed = true; }
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Games, tr!("content-type-games")).clicked() { content_type_changed = true; }
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AppsUtilities, tr!("content-type-apps")).clicked() { content_type_changed = true; }
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AudioCDs, tr!("content-type-audio")).clicked() { content_type_changed = true; }
                             });
                     });
 
@@ -2940,14 +6375,24 @@ impl eframe::App for App {
                     // ---- Search query (textbox + Reset on its own row so
                     //      the trigger buttons below don't fight for width) ----
                     ui.horizontal(|ui| {
-                        ui.label("Search:");
+                        let label = ui.label(tr!("search-label"));
                         let avail = ui.available_width() - 80.0; // Reset button + spacing
-                        ui.add(
-                            egui::TextEdit::singleline(&mut self.search_query_text)
-                                .desired_width(avail.max(200.0))
-                                .hint_text("Refine search query..."),
-                        );
-                        if ui.button("Reset").clicked() {
+                        let query = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.search_query_text)
+                                    .id(egui::Id::new(SEARCH_WINDOW_QUERY_ID))
+                                    .desired_width(avail.max(200.0))
+                                    .hint_text(tr!("refine-search-query")),
+                            )
+                            .labelled_by(label.id);
+                        if query.lost_focus()
+                            && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                            && !search_in_progress
+                            && has_disc
+                        {
+                            search_clicked = true;
+                        }
+                        if ui.button(tr!("reset")).clicked() {
                             reset_query_clicked = true;
                         }
                     });
@@ -2957,13 +6402,16 @@ impl eframe::App for App {
                     // ---- Trigger buttons on their own row ----
                     ui.horizontal(|ui| {
                         ui.add_enabled_ui(!search_in_progress && has_disc, |ui| {
-                            let label = if use_musicbrainz { "Search MusicBrainz" } else { "Search" };
+                            let label = if use_musicbrainz { tr!("search-musicbrainz") } else { tr!("search-button") };
                             if ui.button(label).clicked() { search_clicked = true; }
-                            if ui.button("Open in Browser").clicked() { browser_clicked = true; }
+                            if ui.button(tr!("open-in-browser")).clicked() { browser_clicked = true; }
                         });
                         if search_in_progress {
                             ui.spinner();
-                            ui.label("Searching...");
+                            ui.label(tr!("searching"));
+                            if ui.button(tr!("cancel")).clicked() {
+                                cancel_search_clicked = true;
+                            }
                         }
                     });
 
@@ -2971,15 +6419,24 @@ impl eframe::App for App {
 
                     // ---- Manual URL ----
                     ui.horizontal(|ui| {
-                        ui.label("Manual URL:");
+                        let label = ui.label(tr!("manual-url"));
                         let avail = ui.available_width() - 95.0; // Preview button + spacing
-                        ui.add(
-                            egui::TextEdit::singleline(&mut self.manual_url)
-                                .desired_width(avail.max(160.0))
-                                .hint_text("Paste image URL here..."),
-                        );
+                        let field = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.manual_url)
+                                    .desired_width(avail.max(160.0))
+                                    .hint_text(tr!("paste-image-url-here")),
+                            )
+                            .labelled_by(label.id);
+                        // Pasting a URL previews it straight away
+                        if field.changed()
+                            && pasted_into(ui)
+                            && pasted_url(&self.manual_url).is_some()
+                        {
+                            manual_preview_clicked = true;
+                        }
                         let can_preview_manual = !self.manual_url.is_empty() && !preview_loading;
-                        if ui.add_enabled(can_preview_manual, egui::Button::new("Preview")).clicked() {
+                        if ui.add_enabled(can_preview_manual, egui::Button::new(tr!("preview"))).clicked() {
                             manual_preview_clicked = true;
                         }
                     });
@@ -2990,15 +6447,55 @@ impl eframe::App for App {
                     ui.horizontal_top(|ui| {
                         // Left: results list
                         ui.vertical(|ui| {
-                            ui.heading("Search Results");
+                            ui.heading(tr!("search-results"));
                             if self.search_results.is_empty() {
-                                let msg = if search_in_progress { "Searching..." } else { "No results yet." };
+                                let msg = if search_in_progress { tr!("searching") } else { tr!("no-results-yet") };
                                 ui.colored_label(egui::Color32::GRAY, msg);
+                                let can_placeholder = !search_in_progress
+                                    && self.selected_path.is_some()
+                                    && !self.export_in_progress;
+                                if ui
+                                    .add_enabled(can_placeholder, egui::Button::new(tr!("save-placeholder")))
+                                    .on_hover_text(tr!("save-placeholder-tip"))
+                                    .clicked()
+                                {
+                                    placeholder_clicked = true;
+                                }
                             } else {
-                                ui.label(format!(
-                                    "{} images - click to preview",
-                                    self.search_results.len()
-                                ));
+                                let count = self.search_results.len();
+                                let shown = (0..count).filter(|&i| self.result_shown(i)).count();
+                                if shown < count {
+                                    ui.label(tr!("results-count-hidden", shown = shown, count = count));
+                                } else {
+                                    ui.label(tr!("results-count", count = count));
+                                }
+                                // One tick box per source present, to hide its results
+                                ui.horizontal_wrapped(|ui| {
+                                    for source in ResultSource::ALL {
+                                        let n = self
+                                            .search_results
+                                            .iter()
+                                            .filter(|r| r.result_source() == source)
+                                            .count();
+                                        if n == 0 {
+                                            continue;
+                                        }
+                                        let (name, color) = source_badge(source);
+                                        let mut visible = !self.hidden_sources.contains(&source);
+                                        let text = egui::RichText::new(format!("{} ({})", name, n)).color(color);
+                                        if ui
+                                            .checkbox(&mut visible, text)
+                                            .on_hover_text(tr!("source-filter-tip"))
+                                            .changed()
+                                        {
+                                            if visible {
+                                                self.hidden_sources.remove(&source);
+                                            } else {
+                                                self.hidden_sources.insert(source);
+                                            }
+                                        }
+                                    }
+                                });
                             }
                             ui.add_space(4.0);
 
@@ -3010,12 +6507,12 @@ impl eframe::App for App {
                                 .max_width(360.0)
                                 .show(ui, |ui| {
                                     for (idx, result) in self.search_results.iter().enumerate() {
+                                        let source = result.result_source();
+                                        if self.hidden_sources.contains(&source) {
+                                            continue;
+                                        }
                                         let is_selected = self.selected_image_index == Some(idx);
-                                        let truncated_title = if result.title.chars().count() > 40 {
-                                            format!("{}...", result.title.chars().take(40).collect::<String>())
-                                        } else {
-                                            result.title.clone()
-                                        };
+                                        let truncated_title = truncate_chars(&result.title, 40);
                                         let text = format!(
                                             "{}. {} ({}x{})",
                                             idx + 1,
@@ -3024,17 +6521,33 @@ impl eframe::App for App {
                                             result.height.unwrap_or(0)
                                         );
 
-                                        let response = ui.selectable_label(is_selected, &text);
+                                        let response = ui
+                                            .horizontal(|ui| {
+                                                let (name, color) = source_badge(source);
+                                                ui.label(
+                                                    egui::RichText::new(name)
+                                                        .small()
+                                                        .strong()
+                                                        .color(egui::Color32::BLACK)
+                                                        .background_color(color),
+                                                )
+                                                .on_hover_text(&result.source);
+                                                ui.selectable_label(is_selected, &text)
+                                            })
+                                            .inner;
+                                        if is_selected && self.scroll_to_selected_result {
+                                            response.scroll_to_me(None);
+                                        }
                                         if response.clicked() {
                                             selected_idx_change = Some(idx);
                                         }
                                         let response = response.on_hover_text(&result.image_url);
                                         response.context_menu(|ui| {
-                                            if ui.button("Copy URL to clipboard").clicked() {
+                                            if ui.button(tr!("copy-url-to-clipboard")).clicked() {
                                                 ui.ctx().copy_text(result.image_url.clone());
                                                 ui.close();
                                             }
-                                            if ui.button("Open in browser").clicked() {
+                                            if ui.button(tr!("open-in-browser-tip")).clicked() {
                                                 let _ = open_in_browser(&result.image_url);
                                                 ui.close();
                                             }
@@ -3047,96 +6560,314 @@ impl eframe::App for App {
 
                         // Right: preview
                         ui.vertical(|ui| {
-                            ui.heading("Preview");
+                            ui.heading(tr!("preview"));
+                            self.candidate_info_line(ui);
 
                             if self.preview_loading {
                                 ui.add_space(20.0);
                                 ui.horizontal(|ui| {
-                                    ui.spinner();
-                                    ui.label("Loading...");
+                                    transfer_bar(ui, self.preview_progress.as_ref().and_then(ProgressFeed::latest));
+                                    if ui.button(tr!("cancel")).clicked() {
+                                        cancel_preview_clicked = true;
+                                    }
                                 });
                             } else if let Some(ref texture) = self.preview_texture {
                                 let size = texture.size_vec2();
-                                let max_size = 280.0;
-                                let scale = (max_size / size.x).min(max_size / size.y).min(1.0);
+                                let scale = if self.preview_show_output {
+                                    // One texel per physical pixel, as the ODE shows it
+                                    let zoom = if self.preview_magnified { 2.0 } else { 1.0 };
+                                    zoom / ui.ctx().pixels_per_point()
+                                } else {
+                                    let max_size = 280.0;
+                                    (max_size / size.x).min(max_size / size.y).min(1.0)
+                                };
                                 let display_size = egui::vec2(size.x * scale, size.y * scale);
 
                                 let texture_id = texture.id();
-                                let img_width = size.x as u32;
-                                let img_height = size.y as u32;
+                                // Report the full-size source dimensions, as
+                                // oriented by the current edits.
+                                let (mut img_width, mut img_height) = self.preview_source_size;
+                                if self.preview_edits.quarter_turns % 2 == 1 {
+                                    std::mem::swap(&mut img_width, &mut img_height);
+                                }
+                                let edits = self.preview_edits;
                                 let can_download = self.selected_path.is_some()
                                     && self.preview_url.is_some()
                                     && !self.export_in_progress;
                                 let export_in_progress = self.export_in_progress;
-                                let output_path = self
-                                    .selected_path
-                                    .as_ref()
-                                    .map(|p| generate_output_path(p));
-                                let preview_url = self.preview_url.clone();
-
-                                ui.image((texture_id, display_size));
-                                ui.label(format!("{}x{}", img_width, img_height));
+                                let output_path =
+                                    self.selected_path.as_ref().map(|p| self.output_path_for(p, self.export_role));
+
+                                ui.horizontal_top(|ui| {
+                                    ui.image((texture_id, display_size));
+                                    // Current artwork at the same scale, to
+                                    // judge whether the candidate is better
+                                    if let Some(ref art) = self.existing_art {
+                                        let art_size = art.texture.size_vec2();
+                                        let art_scale = if self.preview_show_output {
+                                            scale
+                                        } else {
+                                            (280.0 / art_size.x).min(280.0 / art_size.y).min(1.0)
+                                        };
+                                        ui.separator();
+                                        ui.vertical(|ui| {
+                                            ui.label(egui::RichText::new(tr!("current-artwork")).strong());
+                                            ui.image((art.texture.id(), art_size * art_scale));
+                                            ui.label(
+                                                egui::RichText::new(format!("{}x{}", art.size.0, art.size.1))
+                                                    .small()
+                                                    .color(egui::Color32::GRAY),
+                                            );
+                                        });
+                                    }
+                                });
+                                let mut next = edits;
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{}x{}", img_width, img_height));
+                                    ui.separator();
+                                    if icon_button(ui, "⟲", tr!("rotate-90-left")).clicked() {
+                                        next.rotate_ccw();
+                                    }
+                                    if icon_button(ui, "⟳", tr!("rotate-90-right")).clicked() {
+                                        next.rotate_cw();
+                                    }
+                                    if icon_button(ui, "⇆", tr!("flip-horizontally")).clicked() {
+                                        next.flip();
+                                    }
+                                    if ui
+                                        .add_enabled(!edits.is_identity(), egui::Button::new(tr!("reset")).small())
+                                        .clicked()
+                                    {
+                                        next = ImageEdits::default();
+                                    }
+                                });
+                                egui::CollapsingHeader::new(tr!("adjust-colours"))
+                                    .id_salt("preview_adjust")
+                                    .show(ui, |ui| {
+                                        let range = ADJUST_MIN..=ADJUST_MAX;
+                                        ui.add(egui::Slider::new(&mut next.brightness, range.clone()).text(tr!("brightness")));
+                                        ui.add(egui::Slider::new(&mut next.contrast, range.clone()).text(tr!("contrast")));
+                                        ui.add(egui::Slider::new(&mut next.saturation, range).text(tr!("saturation")));
+                                    });
+                                if next != edits {
+                                    preview_edits_change = Some(next);
+                                }
+                                let mut show_output = self.preview_show_output;
+                                let mut unsharpened = self.preview_unsharpened;
+                                let mut magnified = self.preview_magnified;
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut show_output, tr!("show-output"))
+                                        .on_hover_text(tr!("show-output-tip"));
+                                    if show_output {
+                                        ui.selectable_value(&mut magnified, false, "1:1");
+                                        ui.selectable_value(&mut magnified, true, tr!("zoom-2x"));
+                                    }
+                                    if show_output && self.export_config.sharpen > 0 {
+                                        ui.separator();
+                                        ui.selectable_value(&mut unsharpened, true, tr!("before"));
+                                        ui.selectable_value(&mut unsharpened, false, tr!("after"));
+                                    }
+                                });
+                                if show_output {
+                                    match &self.preview_output_size {
+                                        Some(Ok(bytes)) => {
+                                            ui.label(
+                                                egui::RichText::new(tr!("size-on-disk", size = super::progress::format_size(*bytes as u64)))
+                                                    .small()
+                                                    .color(egui::Color32::GRAY),
+                                            );
+                                        }
+                                        Some(Err(e)) => {
+                                            ui.colored_label(egui::Color32::RED, e);
+                                        }
+                                        None => {}
+                                    }
+                                }
+                                if (show_output, unsharpened, magnified)
+                                    != (self.preview_show_output, self.preview_unsharpened, self.preview_magnified)
+                                {
+                                    preview_view_change = Some((show_output, unsharpened, magnified));
+                                }
                                 ui.add_space(8.0);
 
+                                ui.horizontal(|ui| {
+                                    ui.label(tr!("style"));
+                                    egui::ComboBox::new("art_template_combo", "")
+                                        .selected_text(super::names::template(self.export_config.template))
+                                        .show_ui(ui, |ui| {
+                                            for template in [ArtTemplate::None, ArtTemplate::CdLabel, ArtTemplate::JewelCase] {
+                                                if ui
+                                                    .selectable_value(&mut self.export_config.template, template, super::names::template(template))
+                                                    .clicked()
+                                                {
+                                                    template_changed = true;
+                                                }
+                                            }
+                                        });
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label(tr!("save-as"));
+                                    egui::ComboBox::new("artwork_role_combo", "")
+                                        .selected_text(super::names::role(self.export_role))
+                                        .show_ui(ui, |ui| {
+                                            for role in ArtworkRole::ALL {
+                                                if ui
+                                                    .selectable_value(&mut self.export_role, role, super::names::role(role))
+                                                    .clicked()
+                                                {
+                                                    role_changed = true;
+                                                }
+                                            }
+                                        })
+                                        .response
+                                        .on_hover_text(tr!("save-as-tip"));
+                                });
                                 ui.add_enabled_ui(can_download, |ui| {
                                     let btn_text = if export_in_progress {
-                                        "Downloading..."
+                                        tr!("downloading")
                                     } else {
-                                        "Download & Save"
+                                        tr!("download-and-save")
                                     };
-                                    if ui.button(btn_text).clicked() {
-                                        if let (Some(url), Some(path)) =
-                                            (preview_url.clone(), output_path.clone())
-                                        {
-                                            start_export_data = Some((url, path));
-                                        }
+                                    if ui
+                                        .button(btn_text)
+                                        .on_hover_text(ui.ctx().format_shortcut(&SAVE_SHORTCUT))
+                                        .clicked()
+                                    {
+                                        save_clicked = true;
                                     }
                                 });
+                                if self.confirm_replace {
+                                    ui.colored_label(egui::Color32::YELLOW, tr!("replace-current-artwork"));
+                                    ui.horizontal(|ui| {
+                                        if ui.add_enabled(can_download, egui::Button::new(tr!("replace"))).clicked() {
+                                            replace_answer = Some(true);
+                                        }
+                                        if ui.button(tr!("keep-current")).clicked() {
+                                            replace_answer = Some(false);
+                                        }
+                                    });
+                                }
+                                if ui
+                                    .button(tr!("pin-for-compare"))
+                                    .on_hover_text(tr!("pin-for-compare-tip"))
+                                    .clicked()
+                                {
+                                    pin_clicked = true;
+                                }
                                 if export_in_progress {
                                     ui.horizontal(|ui| {
-                                        ui.spinner();
-                                        ui.label("Converting...");
+                                        export_bar(ui, self.export_progress.as_ref().and_then(ProgressFeed::latest));
+                                        if ui.button(tr!("cancel")).clicked() {
+                                            cancel_export_clicked = true;
+                                        }
                                     });
                                 }
-                                if let Some(ref path) = output_path {
+                                if let Some(ref default_path) = output_path {
                                     ui.add_space(4.0);
-                                    ui.label(
-                                        egui::RichText::new("Save to:")
-                                            .small()
-                                            .color(egui::Color32::GRAY),
-                                    );
-                                    ui.label(
-                                        egui::RichText::new(path)
+                                    let save_to = ui.label(
+                                        egui::RichText::new(tr!("save-to"))
                                             .small()
                                             .color(egui::Color32::GRAY),
                                     );
+                                    ui.horizontal(|ui| {
+                                        let mut path = self
+                                            .output_path_edit
+                                            .clone()
+                                            .unwrap_or_else(|| default_path.clone());
+                                        let edit = egui::TextEdit::singleline(&mut path)
+                                            .font(egui::TextStyle::Small)
+                                            .desired_width((ui.available_width() - 110.0).max(120.0));
+                                        if ui
+                                            .add(edit)
+                                            .labelled_by(save_to.id)
+                                            .on_hover_text(tr!("output-path-tip"))
+                                            .changed()
+                                        {
+                                            output_path_change = Some(path);
+                                        }
+                                        if ui.small_button(tr!("browse")).clicked() {
+                                            browse_output_clicked = true;
+                                        }
+                                        if self.output_path_edit.is_some()
+                                            && icon_button(ui, "↺", tr!("output-path-reset-tip")).clicked()
+                                        {
+                                            output_path_change = Some(default_path.clone());
+                                        }
+                                    });
                                 }
                             } else if let Some(ref error) = self.preview_error {
                                 ui.add_space(20.0);
                                 ui.colored_label(egui::Color32::RED, error);
                                 ui.add_space(10.0);
-                                ui.label("Tip: Download the image manually and drop it here");
+                                ui.label(tr!("download-manually-tip"));
                                 let output_path = self
                                     .selected_path
                                     .as_ref()
-                                    .map(|p| generate_output_path(p));
+                                    .map(|p| self.output_path_for(p, ArtworkRole::Front));
                                 if let Some(ref path) = output_path {
                                     ui.add_space(10.0);
                                     ui.label(
-                                        egui::RichText::new(format!("Will save to: {}", path))
+                                        egui::RichText::new(tr!("will-save-to", path = path.as_str()))
                                             .small()
                                             .color(egui::Color32::GRAY),
                                     );
                                 }
                             } else {
                                 ui.add_space(20.0);
-                                ui.label("Select an image to preview");
+                                ui.label(tr!("select-an-image-to-preview"));
                             }
                         });
                     });
                 });
 
             // ---- Handlers for the search window ----
+            if pin_clicked {
+                self.pin_preview(&ctx);
+            }
+            if let Some(edits) = preview_edits_change {
+                self.preview_edits = edits;
+                self.refresh_preview_texture(&ctx);
+            }
+            if let Some((show_output, unsharpened, magnified)) = preview_view_change {
+                let rerender = (show_output, unsharpened) != (self.preview_show_output, self.preview_unsharpened);
+                self.preview_show_output = show_output;
+                self.preview_unsharpened = unsharpened;
+                self.preview_magnified = magnified;
+                if rerender {
+                    self.refresh_preview_texture(&ctx);
+                }
+            }
+            if template_changed {
+                self.save_export_config();
+                if self.preview_show_output {
+                    self.refresh_preview_texture(&ctx);
+                }
+            }
+            if role_changed {
+                self.existing_art_stale = true;
+                self.confirm_replace = false;
+                self.output_path_edit = None;
+                if self.preview_show_output {
+                    // Only front covers get the disc badge
+                    self.refresh_preview_texture(&ctx);
+                }
+            }
+            if let Some(path) = output_path_change {
+                let default_path = self
+                    .selected_path
+                    .as_ref()
+                    .map(|p| self.output_path_for(p, self.export_role));
+                self.output_path_edit = (Some(&path) != default_path.as_ref()).then_some(path);
+                self.confirm_replace = false;
+            }
+            if browse_output_clicked {
+                self.browse_output_path();
+            }
+            if replace_answer == Some(true) {
+                self.save_preview();
+            } else if replace_answer == Some(false) {
+                self.confirm_replace = false;
+            }
             if content_type_changed {
                 self.save_search_config();
                 self.update_search_query_from_disc();
@@ -3145,30 +6876,11 @@ impl eframe::App for App {
                 self.search_query_text = default_query;
             }
             if let Some(idx) = selected_idx_change {
-                self.selected_image_index = Some(idx);
-                let url = self.search_results.get(idx).map(|r| r.image_url.clone());
-                if let Some(url) = url {
-                    self.load_preview(&url);
-                }
+                self.select_result(idx);
             }
+            self.scroll_to_selected_result = false;
             if search_clicked {
-                let query_for_search = self.search_query_text.clone();
-                if use_musicbrainz {
-                    if let Some(ref id) = disc_id {
-                        self.log(
-                            LogLevel::Info,
-                            format!("Searching MusicBrainz for disc ID: {}", id),
-                        );
-                        let toc = info_for_search
-                            .as_ref()
-                            .and_then(|i| i.toc.as_ref())
-                            .map(|toc| toc.to_toc_string());
-                        self.start_musicbrainz_search(id, toc, Some(query_for_search.clone()));
-                    }
-                } else {
-                    self.log(LogLevel::Info, format!("Searching: {}", query_for_search));
-                    self.start_search(&query_for_search);
-                }
+                self.search_disc();
             }
             if browser_clicked {
                 let query_for_search = self.search_query_text.clone();
@@ -3207,29 +6919,135 @@ impl eframe::App for App {
                     self.load_preview(&url);
                 }
             }
-            if let Some((url, path)) = start_export_data {
-                self.start_export(&url, &path);
+            if save_clicked {
+                self.save_preview();
+            }
+            if placeholder_clicked {
+                self.save_placeholder();
+            }
+            if cancel_search_clicked {
+                self.cancel_search();
+            }
+            if cancel_preview_clicked {
+                self.cancel_preview();
+            }
+            if cancel_export_clicked {
+                self.cancel_export();
             }
         }
 
         // Main central panel
         egui::CentralPanel::default().show_inside(ui, |ui| {
+            self.render_disc_tabs(ui);
+            ui.add_space(4.0);
+
             // Top section with File Selection and Search Settings in columns
             ui.columns(2, |columns| {
 
                 // --- Left Column: File Selection ---
                 columns[0].group(|ui| {
                     ui.set_min_height(120.0);
-                    ui.heading("File Selection");
+                    ui.heading(tr!("file-selection"));
                     ui.add_space(8.0);
 
                     ui.horizontal(|ui| {
-                        if ui.button("Browse...").clicked() {
+                        if ui
+                            .button(tr!("browse"))
+                            .on_hover_text(ui.ctx().format_shortcut(&OPEN_FILE_SHORTCUT))
+                            .clicked()
+                        {
                             self.open_file_picker();
                         }
-                        if ui.button("Bulk Job...").clicked() {
+                        self.render_recent_menu(ui);
+                        if ui.button(tr!("bulk-job")).clicked() {
                             self.open_bulk_job_picker();
                         }
+                        if ui
+                            .button(tr!("scan-folder"))
+                            .on_hover_text(tr!("scan-folder-tip"))
+                            .clicked()
+                        {
+                            self.open_folder_scan_picker();
+                        }
+                        let watch_label = if self.folder_watch.is_some() {
+                            tr!("stop-watching")
+                        } else {
+                            tr!("watch-folders")
+                        };
+                        if ui
+                            .button(watch_label)
+                            .on_hover_text(tr!("watch-folders-tip"))
+                            .clicked()
+                        {
+                            self.toggle_watch(&ctx);
+                        }
+                        let fix_busy = self.events.is_running(Job::FixScan)
+                            || self.events.is_running(Job::FixRun);
+                        if ui
+                            .add_enabled(!fix_busy, egui::Button::new(tr!("fix-artwork")))
+                            .on_hover_text(tr!("fix-artwork-tip"))
+                            .clicked()
+                        {
+                            self.open_fix_artwork_picker();
+                        }
+                        if fix_busy {
+                            ui.spinner();
+                        }
+                        let can_undo = !self.export_history.is_empty() && !self.export_in_progress;
+                        let undo_hint = match self.export_history.last().and_then(|written| written.first()) {
+                            Some(WrittenFile { path, backup: Some(_) }) => {
+                                tr!("undo-restore-tip", path = path.display().to_string())
+                            }
+                            Some(WrittenFile { path, backup: None }) => {
+                                tr!("undo-delete-tip", path = path.display().to_string())
+                            }
+                            None => tr!("undo-nothing-tip"),
+                        };
+                        if ui
+                            .add_enabled(can_undo, egui::Button::new(tr!("undo-last-export")))
+                            .on_hover_text(undo_hint)
+                            .clicked()
+                        {
+                            self.undo_last_export();
+                        }
+                        if ui
+                            .add_enabled(!self.export_history.is_empty(), egui::Button::new(tr!("copy-result")))
+                            .on_hover_text(tr!("copy-result-tip"))
+                            .clicked()
+                        {
+                            self.copy_last_export();
+                        }
+                        if ui
+                            .add_enabled(!self.export_history.is_empty(), egui::Button::new(tr!("open-output-folder")))
+                            .on_hover_text(tr!("open-output-folder-tip"))
+                            .clicked()
+                        {
+                            self.show_last_export(false);
+                        }
+                        if ui
+                            .add_enabled(!self.export_history.is_empty(), egui::Button::new(tr!("reveal-in-file-manager")))
+                            .on_hover_text(tr!("reveal-in-file-manager-tip"))
+                            .clicked()
+                        {
+                            self.show_last_export(true);
+                        }
+                        if !crate::config::get_config().usbode.address.trim().is_empty() {
+                            let has_cover = self
+                                .selected_path
+                                .as_ref()
+                                .is_some_and(|p| std::path::Path::new(&self.output_path_for(p, ArtworkRole::Front)).exists());
+                            let sending = self.events.is_running(Job::UsbodePush);
+                            if ui
+                                .add_enabled(has_cover && !sending, egui::Button::new(tr!("send-to-usbode")))
+                                .on_hover_text(tr!("send-to-usbode-tip"))
+                                .clicked()
+                            {
+                                self.send_to_usbode();
+                            }
+                            if sending {
+                                ui.spinner();
+                            }
+                        }
                     });
 
                     ui.add_space(8.0);
@@ -3241,32 +7059,32 @@ impl eframe::App for App {
                             ).wrap()
                         );
                     } else {
-                        ui.colored_label(egui::Color32::GRAY, "No file selected (drag & drop supported)");
+                        ui.colored_label(egui::Color32::GRAY, tr!("no-file-selected"));
                     }
                 });
 
                 // --- Right Column: Search Settings ---
                 columns[1].group(|ui| {
                     ui.set_min_height(120.0);
-                    ui.heading("Search Settings");
+                    ui.heading(tr!("search-settings"));
                     ui.add_space(8.0);
 
                     ui.horizontal(|ui| {
-                        ui.label("Content Type:");
+                        ui.label(tr!("content-type"));
                         let mut changed = false;
                         egui::ComboBox::new("content_type_combo", "")
-                            .selected_text(self.search_config.content_type.display_name())
+                            .selected_text(super::names::content_type(self.search_config.content_type))
                             .show_ui(ui, |ui| {
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Any, "Any").clicked() { 
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Any, tr!("content-type-any")).clicked() { 
                                     changed = true; 
                                 }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Games, "Games").clicked() { 
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::Games, tr!("content-type-games")).clicked() { 
                                     changed = true; 
                                 }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AppsUtilities, "Apps & Utilities").clicked() { 
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AppsUtilities, tr!("content-type-apps")).clicked() { 
                                     changed = true; 
                                 }
-                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AudioCDs, "Audio CDs").clicked() { 
+                                if ui.selectable_value(&mut self.search_config.content_type, ContentType::AudioCDs, tr!("content-type-audio")).clicked() { 
                                     changed = true; 
                                 }
                             });
@@ -3277,30 +7095,308 @@ impl eframe::App for App {
                         }
                     });
 
+                    if ui
+                        .checkbox(&mut self.search_config.auto_pick, tr!("auto-pick"))
+                        .on_hover_text(tr!("auto-pick-tip"))
+                        .changed()
+                    {
+                        self.save_search_config();
+                    }
+
                     ui.add_space(8.0);
 
                     // Browser Identity section
                     ui.horizontal(|ui| {
-                        ui.label("Browser Identity:");
+                        ui.label(tr!("browser-identity"));
                         if self.search_config.user_agent.is_some() {
-                            ui.label(egui::RichText::new("Configured").color(egui::Color32::GREEN));
+                            ui.label(egui::RichText::new(tr!("configured")).color(egui::Color32::GREEN));
                         } else {
-                            ui.label(egui::RichText::new("Not set").color(egui::Color32::YELLOW));
+                            ui.label(egui::RichText::new(tr!("not-set")).color(egui::Color32::YELLOW));
                         }
                     });
 
                     ui.horizontal(|ui| {
                         let button_enabled = !self.user_agent_capture_in_progress;
                         let button_text = if self.user_agent_capture_in_progress {
-                            "Waiting for browser..."
+                            tr!("waiting-for-browser")
                         } else {
-                            "Configure Browser Identity"
+                            tr!("configure-browser-identity")
                         };
 
                         if ui.add_enabled(button_enabled, egui::Button::new(button_text)).clicked() {
                             self.start_user_agent_capture();
                         }
                     });
+
+                    ui.add_space(8.0);
+
+                    // Local cover packs
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("cover-packs"));
+                        if self.pack_entry_count > 0 {
+                            ui.label(tr!("pack-images", count = self.pack_entry_count));
+                        } else {
+                            ui.colored_label(egui::Color32::GRAY, tr!("none"));
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let importing = self.events.is_running(Job::PackImport);
+                        if ui.add_enabled(!importing, egui::Button::new(tr!("import-zip"))).clicked() {
+                            self.open_pack_zip_picker();
+                        }
+                        if ui.add_enabled(!importing, egui::Button::new(tr!("import-folder"))).clicked() {
+                            self.open_pack_folder_picker();
+                        }
+                        if importing {
+                            ui.spinner();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+
+                    // Output size(s) for saved artwork
+                    let mut export_changed = false;
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("output-size"));
+                        let size = ui.add(
+                            egui::DragValue::new(&mut self.export_config.target_size)
+                                .range(crate::export::MIN_TARGET_SIZE..=crate::export::MAX_TARGET_SIZE)
+                                .suffix(" px"),
+                        );
+                        if size.drag_stopped() || (size.changed() && !size.dragged()) {
+                            export_changed = true;
+                        }
+                        let label = ui.label(tr!("also-save"));
+                        let extra = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.export_extra_sizes_text)
+                                    .desired_width(80.0)
+                                    .hint_text(tr!("also-save-hint")),
+                            )
+                            .labelled_by(label.id);
+                        if extra.lost_focus() {
+                            export_changed = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("format"));
+                        egui::ComboBox::new("output_format_combo", "")
+                            .selected_text(self.export_config.format.display_name())
+                            .show_ui(ui, |ui| {
+                                for format in [OutputFormat::Jpeg, OutputFormat::Png, OutputFormat::Bmp] {
+                                    if ui
+                                        .selectable_value(&mut self.export_config.format, format, format.display_name())
+                                        .clicked()
+                                    {
+                                        export_changed = true;
+                                    }
+                                }
+                            });
+                        ui.label(tr!("max-size"));
+                        let mut max_kb = self.export_config.max_file_size_kb.unwrap_or(0);
+                        let max = ui
+                            .add_enabled(
+                                self.export_config.format == OutputFormat::Jpeg,
+                                egui::DragValue::new(&mut max_kb)
+                                    .range(0..=4096)
+                                    .suffix(" KB"),
+                            )
+                            .on_hover_text(tr!("max-size-tip"));
+                        self.export_config.max_file_size_kb = (max_kb > 0).then_some(max_kb);
+                        if max.drag_stopped() || (max.changed() && !max.dragged()) {
+                            export_changed = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("jpeg-quality"));
+                        let quality = ui
+                            .add_enabled(
+                                self.export_config.format == OutputFormat::Jpeg,
+                                egui::Slider::new(&mut self.export_config.quality, MIN_JPEG_QUALITY..=100),
+                            )
+                            .on_hover_text(tr!("jpeg-quality-tip"));
+                        if quality.drag_stopped() || (quality.changed() && !quality.dragged()) {
+                            export_changed = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let label = ui.label(tr!("file-name"));
+                        let name = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut self.export_config.name_template)
+                                    .desired_width(180.0)
+                                    .hint_text(DEFAULT_NAME_TEMPLATE),
+                            )
+                            .labelled_by(label.id)
+                            .on_hover_text(tr!("file-name-tip"));
+                        match validate_name_template(&self.export_config.name_template) {
+                            Ok(()) => {
+                                if name.lost_focus() {
+                                    export_changed = true;
+                                }
+                            }
+                            Err(e) => {
+                                ui.colored_label(egui::Color32::RED, e);
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let mut central = self.export_config.output_dir.is_some();
+                        if ui
+                            .checkbox(&mut central, tr!("artwork-folder"))
+                            .on_hover_text(tr!("artwork-folder-tip"))
+                            .changed()
+                        {
+                            if central {
+                                export_changed |= self.pick_artwork_folder();
+                            } else {
+                                self.export_config.output_dir = None;
+                                export_changed = true;
+                            }
+                        }
+                        if let Some(dir) = self.export_config.output_dir.clone() {
+                            ui.label(dir);
+                            if ui.small_button(tr!("change")).clicked() {
+                                export_changed |= self.pick_artwork_folder();
+                            }
+                        } else {
+                            ui.colored_label(egui::Color32::GRAY, tr!("next-to-each-disc-image"));
+                        }
+                    });
+                    if ui
+                        .checkbox(&mut self.export_config.write_sidecar, tr!("write-metadata-sidecar"))
+                        .on_hover_text(tr!("write-sidecar-tip"))
+                        .changed()
+                    {
+                        export_changed = true;
+                    }
+                    if ui
+                        .checkbox(&mut self.export_config.embed_in_chd, tr!("store-cover-inside-chd-images"))
+                        .on_hover_text(tr!("embed-in-chd-tip"))
+                        .changed()
+                    {
+                        export_changed = true;
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("fit"));
+                        egui::ComboBox::new("fit_mode_combo", "")
+                            .selected_text(super::names::fit(self.export_config.fit))
+                            .show_ui(ui, |ui| {
+                                for fit in [FitMode::Crop, FitMode::Pad] {
+                                    if ui
+                                        .selectable_value(&mut self.export_config.fit, fit, super::names::fit(fit))
+                                        .clicked()
+                                    {
+                                        export_changed = true;
+                                    }
+                                }
+                            });
+                        if self.export_config.fit == FitMode::Pad {
+                            egui::ComboBox::new("pad_fill_combo", "")
+                                .selected_text(super::names::pad_fill(self.export_config.pad_fill))
+                                .show_ui(ui, |ui| {
+                                    for fill in [PadFill::Color, PadFill::Blur] {
+                                        if ui
+                                            .selectable_value(&mut self.export_config.pad_fill, fill, super::names::pad_fill(fill))
+                                            .clicked()
+                                        {
+                                            export_changed = true;
+                                        }
+                                    }
+                                });
+                            if self.export_config.pad_fill == PadFill::Color {
+                                let mut rgb = parse_hex_color(&self.export_config.pad_color)
+                                    .map(|c| c.0)
+                                    .unwrap_or([0, 0, 0]);
+                                if ui.color_edit_button_srgb(&mut rgb).changed() {
+                                    self.export_config.pad_color = format_hex_color(image::Rgb(rgb));
+                                    export_changed = true;
+                                }
+                            }
+                        }
+                        ui.label(tr!("sharpen"));
+                        let sharpen = ui
+                            .add(
+                                egui::DragValue::new(&mut self.export_config.sharpen)
+                                    .range(0..=MAX_SHARPEN)
+                                    .suffix("%"),
+                            )
+                            .on_hover_text(tr!("sharpen-tip"));
+                        if sharpen.drag_stopped() || (sharpen.changed() && !sharpen.dragged()) {
+                            export_changed = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.export_config.smart_crop, tr!("smart-crop"))
+                            .on_hover_text(tr!("smart-crop-tip"))
+                            .changed()
+                        {
+                            export_changed = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("upscale-small-images"));
+                        egui::ComboBox::new("upscale_mode_combo", "")
+                            .selected_text(super::names::upscale(self.export_config.upscale))
+                            .show_ui(ui, |ui| {
+                                for mode in [UpscaleMode::Lanczos, UpscaleMode::Scale2x] {
+                                    if ui
+                                        .selectable_value(&mut self.export_config.upscale, mode, super::names::upscale(mode))
+                                        .clicked()
+                                    {
+                                        export_changed = true;
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr!("upscale-tip"));
+                    });
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut self.export_config.disc_badge, tr!("disc-badge"))
+                            .on_hover_text(tr!("disc-badge-tip"))
+                            .changed()
+                        {
+                            export_changed = true;
+                        }
+                        ui.add_enabled_ui(self.export_config.disc_badge, |ui| {
+                            egui::ComboBox::new("badge_position_combo", "")
+                                .selected_text(super::names::badge_position(self.export_config.badge_position))
+                                .show_ui(ui, |ui| {
+                                    for position in [
+                                        BadgePosition::TopLeft,
+                                        BadgePosition::TopRight,
+                                        BadgePosition::BottomLeft,
+                                        BadgePosition::BottomRight,
+                                    ] {
+                                        if ui
+                                            .selectable_value(&mut self.export_config.badge_position, position, super::names::badge_position(position))
+                                            .clicked()
+                                        {
+                                            export_changed = true;
+                                        }
+                                    }
+                                });
+                            egui::ComboBox::new("badge_style_combo", "")
+                                .selected_text(super::names::badge_style(self.export_config.badge_style))
+                                .show_ui(ui, |ui| {
+                                    for style in [BadgeStyle::Pill, BadgeStyle::Circle] {
+                                        if ui
+                                            .selectable_value(&mut self.export_config.badge_style, style, super::names::badge_style(style))
+                                            .clicked()
+                                        {
+                                            export_changed = true;
+                                        }
+                                    }
+                                });
+                        });
+                    });
+                    if export_changed {
+                        self.save_export_config();
+                        if self.preview_show_output {
+                            self.refresh_preview_texture(&ctx);
+                        }
+                    }
                 });
             });
 
@@ -3308,7 +7404,7 @@ impl eframe::App for App {
 
             // Disc information section
             ui.group(|ui| {
-                ui.heading("Disc Information");
+                ui.heading(tr!("disc-information"));
                 ui.add_space(8.0);
 
                 // Snapshot the hashing state outside the match so we can reach
@@ -3356,14 +7452,22 @@ impl eframe::App for App {
                                 .unwrap_or(true);
                         let disc_id = info.toc.as_ref().map(|toc| toc.musicbrainz_id());
                         let toc_string_for_browser = info.toc.as_ref().map(|toc| toc.to_toc_string());
+                        let disc_path = info.path.clone();
                         let preview_loading = self.preview_loading;
                         let search_in_progress = self.search_in_progress;
 
                         let mut browse_clicked = false;
+                        let mut write_cue_clicked = false;
+                        let mut extract_bincue_clicked = false;
                         let mut search_clicked = false;
                         let mut browser_clicked = false;
                         let mut manual_preview_clicked = false;
                         let mut reset_query_clicked = false;
+                        let mut cancel_search_clicked = false;
+                        let mut check_art_clicked = false;
+                        let mut cd_text_query: Option<String> = None;
+                        let cover_art_path =
+                            std::path::PathBuf::from(self.output_path_for(&info.path, ArtworkRole::Front));
 
                         // Stretch the value column so the table fills most of the
                         // panel width instead of shrinking to its content.
@@ -3373,72 +7477,103 @@ impl eframe::App for App {
                             .spacing([40.0, 4.0])
                             .striped(true)
                             .show(ui, |ui| {
-                                ui.label("Volume Label:");
+                                ui.label(tr!("volume-label"));
                                 ui.horizontal(|ui| {
                                     ui.set_min_width(value_col_w);
+                                    if let Some(ref icon) = self.volume_icon {
+                                        let size = egui::vec2(VOLUME_ICON_SIZE, VOLUME_ICON_SIZE);
+                                        ui.image((icon.id(), size)).on_hover_text(tr!("volume-icon-tip"));
+                                    }
                                     if let Some(ref label) = info.volume_label {
                                         ui.strong(label);
                                     } else {
-                                        ui.colored_label(egui::Color32::GRAY, "Not found");
+                                        ui.colored_label(egui::Color32::GRAY, tr!("not-found"));
                                     }
                                 });
                                 ui.end_row();
 
-                                ui.label("Format:");
-                                ui.label(info.format.display_name());
+                                ui.label(tr!("format"));
+                                ui.horizontal(|ui| {
+                                    ui.label(info.format.display_name());
+                                    if matches!(info.format, DiscFormat::Chd) {
+                                        if ui
+                                            .button(tr!("write-cue"))
+                                            .on_hover_text(tr!("write-cue-tip"))
+                                            .clicked()
+                                        {
+                                            write_cue_clicked = true;
+                                        }
+                                        let extracting = self.events.is_running(Job::ChdExtract);
+                                        if ui
+                                            .add_enabled(!extracting, egui::Button::new(tr!("extract-bin-cue")))
+                                            .on_hover_text(tr!("extract-bin-cue-tip"))
+                                            .clicked()
+                                        {
+                                            extract_bincue_clicked = true;
+                                        }
+                                        if extracting {
+                                            ui.spinner();
+                                        }
+                                    }
+                                });
                                 ui.end_row();
 
-                                ui.label("Filesystem:");
+                                ui.label(tr!("filesystem"));
                                 ui.horizontal(|ui| {
                                     ui.label(info.filesystem.display_name());
                                     if can_browse {
-                                        if ui.button("Browse Contents...").clicked() {
+                                        if ui.button(tr!("browse-contents")).clicked() {
                                             browse_clicked = true;
                                         }
                                     } else {
                                         ui.colored_label(
                                             egui::Color32::GRAY,
-                                            "(no browser)",
+                                            tr!("no-browser"),
                                         );
                                     }
                                 });
                                 ui.end_row();
 
-                                ui.label("Confidence:");
-                                let (color, text) = match info.confidence {
-                                    ConfidenceLevel::High => {
-                                        (egui::Color32::GREEN, info.confidence.display_name())
-                                    }
-                                    ConfidenceLevel::Medium => {
-                                        (egui::Color32::YELLOW, info.confidence.display_name())
-                                    }
-                                    ConfidenceLevel::Low => {
-                                        (egui::Color32::LIGHT_RED, info.confidence.display_name())
-                                    }
-                                };
-                                ui.colored_label(color, text);
+                                ui.label(tr!("confidence"));
+                                ui.colored_label(
+                                    confidence_color(info.confidence),
+                                    super::names::confidence(info.confidence),
+                                );
                                 ui.end_row();
 
                                 // Parsed filename info
-                                ui.label("Parsed Title:");
+                                ui.label(tr!("parsed-title"));
                                 ui.label(&info.parsed_filename.title);
                                 ui.end_row();
 
                                 if let Some(disc) = info.parsed_filename.disc_number {
-                                    ui.label("Disc Number:");
+                                    ui.label(tr!("disc-number"));
                                     ui.label(format!("{}", disc));
                                     ui.end_row();
                                 }
 
                                 if let Some(ref serial) = info.parsed_filename.serial {
-                                    ui.label("Serial:");
+                                    ui.label(tr!("serial"));
                                     ui.label(serial);
                                     ui.end_row();
                                 }
 
+                                if let Some(ref pvd) = info.pvd {
+                                    ui.label(tr!("volume-descriptor"));
+                                    ui.collapsing(tr!("volume-descriptor-details"), |ui| {
+                                        volume_descriptor_details(
+                                            ui,
+                                            pvd,
+                                            self.entry_counts,
+                                            self.entry_counts_receiver.is_some(),
+                                        );
+                                    });
+                                    ui.end_row();
+                                }
+
                                 // Hashing progress row (only while a worker is active)
                                 if let Some(ref h) = hash_snapshot {
-                                    ui.label("Hashing:");
+                                    ui.label(tr!("hashing"));
                                     let text = format!(
                                         "{} / {} ({:.0}%){}",
                                         super::progress::format_size(h.current_bytes),
@@ -3456,7 +7591,7 @@ impl eframe::App for App {
 
                                 // Redump database match
                                 if let Some(matches) = &info.redump_matches {
-                                    ui.label("Redump:");
+                                    ui.label(tr!("redump"));
                                     if let Some(first) = matches.first() {
                                         let (color, label) = match_styling(first.matched_via);
                                         ui.vertical(|ui| {
@@ -3471,7 +7606,7 @@ impl eframe::App for App {
                                                 );
                                                 ui.separator();
                                                 ui.hyperlink_to(
-                                                    "View on redump.org",
+                                                    tr!("view-on-redump"),
                                                     &first.redump_url,
                                                 );
                                             });
@@ -3487,13 +7622,13 @@ impl eframe::App for App {
                                             // Structured fields, laid out as two
                                             // key/value pairs per row so they spread
                                             // horizontally instead of a tall column.
-                                            let present: Vec<(&str, &str)> = [
-                                                ("System", Some(first.system.as_str())),
-                                                ("Media", first.media.as_deref()),
-                                                ("Category", first.category.as_deref()),
-                                                ("Edition", first.edition.as_deref()),
-                                                ("Version", first.version.as_deref()),
-                                                ("Catalog", first.catalog.as_deref()),
+                                            let present: Vec<(String, &str)> = [
+                                                (tr!("redump-system"), Some(first.system.as_str())),
+                                                (tr!("redump-media"), first.media.as_deref()),
+                                                (tr!("redump-category"), first.category.as_deref()),
+                                                (tr!("redump-edition"), first.edition.as_deref()),
+                                                (tr!("redump-version"), first.version.as_deref()),
+                                                (tr!("redump-catalog"), first.catalog.as_deref()),
                                             ]
                                             .into_iter()
                                             .filter_map(|(k, v)| {
@@ -3515,9 +7650,9 @@ impl eframe::App for App {
                                                     .spacing([24.0, 6.0])
                                                     .show(ui, |ui| {
                                                         for pair in present.chunks(2) {
-                                                            cell(ui, pair[0].0, pair[0].1);
+                                                            cell(ui, &pair[0].0, pair[0].1);
                                                             if let Some(second) = pair.get(1) {
-                                                                cell(ui, second.0, second.1);
+                                                                cell(ui, &second.0, second.1);
                                                             } else {
                                                                 ui.label("");
                                                                 ui.label("");
@@ -3529,10 +7664,7 @@ impl eframe::App for App {
 
                                             if matches.len() > 1 {
                                                 ui.collapsing(
-                                                    format!(
-                                                        "{} other candidate(s)",
-                                                        matches.len() - 1
-                                                    ),
+                                                    tr!("other-candidates", count = matches.len() - 1),
                                                     |ui| {
                                                         for m in matches.iter().skip(1) {
                                                             ui.horizontal(|ui| {
@@ -3540,9 +7672,10 @@ impl eframe::App for App {
                                                                     "#{}: {}",
                                                                     m.redump_id, m.title
                                                                 ));
-                                                                ui.hyperlink_to(
-                                                                    "↗",
-                                                                    &m.redump_url,
+                                                                named(
+                                                                    ui.hyperlink_to("↗", &m.redump_url),
+                                                                    egui::WidgetType::Link,
+                                                                    tr!("view-on-redump"),
                                                                 );
                                                             });
                                                         }
@@ -3553,17 +7686,17 @@ impl eframe::App for App {
                                     } else if hashing_in_progress {
                                         ui.colored_label(
                                             egui::Color32::LIGHT_GRAY,
-                                            "Searching… (waiting on hash)",
+                                            tr!("redump-waiting-on-hash"),
                                         );
                                     } else {
-                                        ui.colored_label(egui::Color32::GRAY, "No match");
+                                        ui.colored_label(egui::Color32::GRAY, tr!("no-match"));
                                     }
                                     ui.end_row();
                                 } else if hashing_in_progress {
-                                    ui.label("Redump:");
+                                    ui.label(tr!("redump"));
                                     ui.colored_label(
                                         egui::Color32::LIGHT_GRAY,
-                                        "Searching… (waiting on hash)",
+                                        tr!("redump-waiting-on-hash"),
                                     );
                                     ui.end_row();
                                 }
@@ -3571,7 +7704,7 @@ impl eframe::App for App {
                                 // Fuzzy candidates (only when exact match missed)
                                 if let Some(fuzzy) = &info.fuzzy_matches {
                                     if !fuzzy.is_empty() {
-                                        ui.label("Possible matches:");
+                                        ui.label(tr!("possible-matches"));
                                         ui.vertical(|ui| {
                                             for c in fuzzy {
                                                 ui.horizontal(|ui| {
@@ -3587,7 +7720,11 @@ impl eframe::App for App {
                                                         title.push_str(&format!(" v{v}"));
                                                     }
                                                     ui.label(title);
-                                                    ui.hyperlink_to("↗", &c.redump_url);
+                                                    named(
+                                                        ui.hyperlink_to("↗", &c.redump_url),
+                                                        egui::WidgetType::Link,
+                                                        tr!("view-on-redump"),
+                                                    );
                                                 });
                                                 ui.label(
                                                     egui::RichText::new(&c.match_reason)
@@ -3601,33 +7738,44 @@ impl eframe::App for App {
                                 }
 
                                 // Cover art status
-                                ui.label("Cover Art:");
-                                if info.has_cover_art() {
-                                    ui.colored_label(egui::Color32::GREEN, "Found");
+                                ui.label(tr!("cover-art"));
+                                if cover_art_path.exists() {
+                                    ui.horizontal(|ui| {
+                                        ui.colored_label(egui::Color32::GREEN, tr!("found"));
+                                        if ui
+                                            .small_button(tr!("check"))
+                                            .on_hover_text(tr!("check-artwork-tip"))
+                                            .clicked()
+                                        {
+                                            check_art_clicked = true;
+                                        }
+                                    });
+                                } else if self.chd_artwork_found {
+                                    ui.colored_label(egui::Color32::GREEN, tr!("stored-in-chd"));
                                 } else {
-                                    ui.colored_label(egui::Color32::LIGHT_RED, "Not found");
+                                    ui.colored_label(egui::Color32::LIGHT_RED, tr!("not-found"));
                                 }
                                 ui.end_row();
 
                                 // TOC information (for audio CDs)
                                 if let Some(ref toc) = info.toc {
-                                    ui.label("Audio Tracks:");
+                                    ui.label(tr!("audio-tracks-label"));
                                     ui.label(format!("{}", toc.track_count()));
                                     ui.end_row();
 
-                                    ui.label("Total Length:");
+                                    ui.label(tr!("total-length"));
                                     ui.label(toc.total_time_string());
                                     ui.end_row();
 
-                                    ui.label("MusicBrainz ID:");
+                                    ui.label(tr!("musicbrainz-id"));
                                     let disc_id = toc.musicbrainz_id();
                                     let toc_string = toc.to_toc_string();
                                     ui.horizontal(|ui| {
                                         ui.label(&disc_id);
-                                        if ui.small_button("📋").on_hover_text("Copy to clipboard").clicked() {
+                                        if icon_button(ui, "📋", tr!("copy-to-clipboard")).clicked() {
                                             ui.ctx().copy_text(disc_id.clone());
                                         }
-                                        if ui.small_button("🔍").on_hover_text("Search on MusicBrainz").clicked() {
+                                        if icon_button(ui, "🔍", tr!("search-on-musicbrainz")).clicked() {
                                             // Use the exact same URL as the API lookup
                                             let url = format!(
                                                 "https://musicbrainz.org/ws/2/discid/{}?fmt=json&inc=artist-credits+release-groups&toc={}",
@@ -3637,31 +7785,111 @@ impl eframe::App for App {
                                         }
                                     });
                                     ui.end_row();
+
+                                    if self.tracklist_receiver.is_some() {
+                                        ui.label(tr!("tracklist"));
+                                        ui.horizontal(|ui| {
+                                            ui.spinner();
+                                            ui.label(tr!("tracklist-looking-up"));
+                                        });
+                                        ui.end_row();
+                                    } else if let Some(ref list) = self.tracklist {
+                                        ui.label(tr!("tracklist"));
+                                        egui::CollapsingHeader::new(format!("{} — {}", list.artist, list.album))
+                                            .id_salt("tracklist")
+                                            .default_open(true)
+                                            .show(ui, |ui| {
+                                                egui::Grid::new("tracklist_grid")
+                                                    .num_columns(3)
+                                                    .spacing([8.0, 2.0])
+                                                    .show(ui, |ui| {
+                                                        for track in &list.tracks {
+                                                            ui.label(format!("{}.", track.number));
+                                                            ui.label(&track.title);
+                                                            ui.weak(track.length().unwrap_or_default());
+                                                            ui.end_row();
+                                                        }
+                                                    });
+                                            })
+                                            .header_response
+                                            .on_hover_text(tr!("tracklist-source", source = list.source));
+                                        ui.end_row();
+                                    }
+                                }
+
+                                // CD-TEXT names from the cue sheet
+                                if let Some(ref cd_text) = self.cd_text {
+                                    let names: Vec<&str> = [&cd_text.performer, &cd_text.album]
+                                        .into_iter()
+                                        .flatten()
+                                        .map(String::as_str)
+                                        .collect();
+                                    if !names.is_empty() {
+                                        ui.label(tr!("cd-text"));
+                                        ui.horizontal(|ui| {
+                                            ui.label(names.join(" — "));
+                                            if let Some(query) = cd_text.search_query() {
+                                                if ui
+                                                    .small_button(tr!("use-as-search-query"))
+                                                    .on_hover_text(tr!("use-as-search-query-tip", query = query.as_str()))
+                                                    .clicked()
+                                                {
+                                                    cd_text_query = Some(query);
+                                                }
+                                            }
+                                        });
+                                        ui.end_row();
+                                    }
+
+                                    if !cd_text.tracks.is_empty() {
+                                        ui.label(tr!("cd-text-tracks"));
+                                        egui::CollapsingHeader::new(tr!("cd-text-track-count", count = cd_text.tracks.len()))
+                                            .id_salt("cd_text_tracks")
+                                            .show(ui, |ui| {
+                                                egui::Grid::new("cd_text_tracks_grid")
+                                                    .num_columns(3)
+                                                    .spacing([8.0, 2.0])
+                                                    .show(ui, |ui| {
+                                                        for (number, track) in &cd_text.tracks {
+                                                            ui.label(format!("{}.", number));
+                                                            ui.label(track.title.as_deref().unwrap_or_default());
+                                                            // Only a performer other than the album's
+                                                            let performer = track
+                                                                .performer
+                                                                .as_ref()
+                                                                .filter(|p| Some(*p) != cd_text.performer.as_ref());
+                                                            ui.weak(performer.map(String::as_str).unwrap_or_default());
+                                                            ui.end_row();
+                                                        }
+                                                    });
+                                            });
+                                        ui.end_row();
+                                    }
                                 }
 
                                 // HFS information
                                 if let Some(ref mdb) = info.hfs_mdb {
-                                    ui.label("Files:");
+                                    ui.label(tr!("files"));
                                     ui.label(format!("{}", mdb.file_count));
                                     ui.end_row();
                                 }
 
                                 if let Some(ref header) = info.hfsplus_header {
-                                    ui.label("HFS+ Version:");
+                                    ui.label(tr!("hfs-version"));
                                     ui.label(format!("{}", header.version));
                                     ui.end_row();
 
-                                    ui.label("Files/Folders:");
+                                    ui.label(tr!("files-folders"));
                                     ui.label(format!("{} / {}", header.file_count, header.folder_count));
                                     ui.end_row();
 
                                     let total_size = header.total_blocks as u64 * header.block_size as u64;
                                     let free_size = header.free_blocks as u64 * header.block_size as u64;
-                                    ui.label("Total Size:");
+                                    ui.label(tr!("total-size"));
                                     ui.label(format!("{:.2} GB", total_size as f64 / 1_073_741_824.0));
                                     ui.end_row();
 
-                                    ui.label("Free Space:");
+                                    ui.label(tr!("free-space"));
                                     ui.label(format!("{:.2} GB", free_size as f64 / 1_073_741_824.0));
                                     ui.end_row();
                                 }
@@ -3671,14 +7899,24 @@ impl eframe::App for App {
 
                         // ---- Search controls (full width) — triggers the Artwork Search window
                         ui.horizontal(|ui| {
-                            ui.label("Search:");
+                            let label = ui.label(tr!("search-label"));
                             let avail = ui.available_width() - 70.0;
-                            ui.add(
-                                egui::TextEdit::singleline(&mut self.search_query_text)
-                                    .desired_width(avail.max(200.0))
-                                    .hint_text("Enter search query..."),
-                            );
-                            if ui.button("Reset").clicked() {
+                            let query = ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut self.search_query_text)
+                                        .id(egui::Id::new(SEARCH_QUERY_ID))
+                                        .desired_width(avail.max(200.0))
+                                        .hint_text(tr!("enter-search-query")),
+                                )
+                                .labelled_by(label.id);
+                            // Enter runs the search, like the button
+                            if query.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                                && !search_in_progress
+                            {
+                                search_clicked = true;
+                            }
+                            if ui.button(tr!("reset")).clicked() {
                                 reset_query_clicked = true;
                             }
                         });
@@ -3688,37 +7926,49 @@ impl eframe::App for App {
                         ui.horizontal(|ui| {
                             ui.add_enabled_ui(!search_in_progress, |ui| {
                                 let search_label = if use_musicbrainz {
-                                    "Search MusicBrainz"
+                                    tr!("search-musicbrainz")
                                 } else {
-                                    "Search"
+                                    tr!("search-button")
                                 };
                                 if ui.button(search_label).clicked() {
                                     search_clicked = true;
                                 }
-                                if ui.button("Open in Browser").clicked() {
+                                if ui.button(tr!("open-in-browser")).clicked() {
                                     browser_clicked = true;
                                 }
                             });
                             if search_in_progress {
                                 ui.spinner();
-                                ui.label("Searching...");
+                                ui.label(tr!("searching"));
+                                if ui.button(tr!("cancel")).clicked() {
+                                    cancel_search_clicked = true;
+                                }
                             }
                         });
 
                         ui.add_space(8.0);
 
                         ui.horizontal(|ui| {
-                            ui.label("Manual URL:");
+                            let label = ui.label(tr!("manual-url"));
                             let avail = ui.available_width() - 90.0;
-                            ui.add(
-                                egui::TextEdit::singleline(&mut self.manual_url)
-                                    .desired_width(avail.max(200.0))
-                                    .hint_text("Paste image URL here..."),
-                            );
+                            let field = ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut self.manual_url)
+                                        .desired_width(avail.max(200.0))
+                                        .hint_text(tr!("paste-image-url-here")),
+                                )
+                                .labelled_by(label.id);
+                            // Pasting a URL previews it straight away
+                            if field.changed()
+                                && pasted_into(ui)
+                                && pasted_url(&self.manual_url).is_some()
+                            {
+                                manual_preview_clicked = true;
+                            }
                             let can_preview_manual =
                                 !self.manual_url.is_empty() && !preview_loading;
                             if ui
-                                .add_enabled(can_preview_manual, egui::Button::new("Preview"))
+                                .add_enabled(can_preview_manual, egui::Button::new(tr!("preview")))
                                 .clicked()
                             {
                                 manual_preview_clicked = true;
@@ -3737,52 +7987,51 @@ impl eframe::App for App {
                             if !self.browse_view.is_active() {
                                 match self.browse_view.initialize(&info_clone) {
                                     Ok(()) => {
-                                        self.log_messages.push(LogMessage {
-                                            text: "Opened filesystem for browsing".to_string(),
-                                            level: LogLevel::Success,
-                                        });
+                                        self.log_messages.push_back(LogMessage::new(
+                                            LogLevel::Success,
+                                            "Opened filesystem for browsing",
+                                        ));
                                     }
                                     Err(e) => {
-                                        self.log_messages.push(LogMessage {
-                                            text: format!("Failed to open filesystem: {}", e),
-                                            level: LogLevel::Error,
-                                        });
+                                        self.log_messages.push_back(LogMessage::new(
+                                            LogLevel::Error,
+                                            format!("Failed to open filesystem: {}", e),
+                                        ));
                                         self.show_browse_window = false;
                                     }
                                 }
                             }
                         }
 
-                        if search_clicked {
-                            let query_for_search = self.search_query_text.clone();
-                            if use_musicbrainz {
-                                if let Some(ref id) = disc_id {
-                                    self.log(
-                                        LogLevel::Info,
-                                        format!("Searching MusicBrainz for disc ID: {}", id),
-                                    );
-                                    let toc = self
-                                        .disc_info
-                                        .as_ref()
-                                        .and_then(|result| result.as_ref().ok())
-                                        .and_then(|info| info.toc.as_ref())
-                                        .map(|toc| toc.to_toc_string());
-                                    self.start_musicbrainz_search(
-                                        id,
-                                        toc,
-                                        Some(query_for_search.clone()),
-                                    );
-                                } else {
-                                    self.log(LogLevel::Error, "No disc ID available");
-                                }
-                            } else {
-                                self.log(
-                                    LogLevel::Info,
-                                    format!("Searching: {}", query_for_search),
-                                );
-                                self.start_search(&query_for_search);
+                        // `info` is no longer needed past this point, so the
+                        // handlers below are free to take `&mut self`.
+                        if write_cue_clicked {
+                            match crate::disc::chd_cue::write_cue(&disc_path) {
+                                Ok(cue) => self.log(LogLevel::Success, format!("Wrote {}", cue.display())),
+                                Err(e) => self.log(LogLevel::Error, format!("Couldn't write CUE sheet: {}", e)),
                             }
                         }
+                        if extract_bincue_clicked {
+                            self.start_chd_extract(disc_path);
+                        }
+
+                        if cancel_search_clicked {
+                            self.cancel_search();
+                        }
+
+                        if check_art_clicked {
+                            self.check_artwork(&cover_art_path);
+                        }
+
+                        if let Some(query) = cd_text_query {
+                            self.log(LogLevel::Info, format!("Searching: {}", query));
+                            self.search_query_text = query.clone();
+                            self.start_search(&query);
+                        }
+
+                        if search_clicked {
+                            self.search_disc();
+                        }
 
                         if browser_clicked {
                             let query_for_search = self.search_query_text.clone();
@@ -3831,7 +8080,7 @@ impl eframe::App for App {
                         }
                     }
                     Some(Err(ref error)) => {
-                        ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
+                        ui.colored_label(egui::Color32::RED, tr!("error-message", error = error.as_str()));
                     }
                     None => {
                         // Center the drag-and-drop hint in available space
@@ -3839,16 +8088,16 @@ impl eframe::App for App {
                         ui.allocate_space(egui::vec2(0.0, available.y * 0.3));
 
                         ui.vertical_centered(|ui| {
-                            ui.label("Select a disc image file to view information.");
+                            ui.label(tr!("select-a-disc-image-file-to"));
                             ui.add_space(40.0);
                             ui.label(
-                                egui::RichText::new("Drag and drop disc image files here")
+                                egui::RichText::new(tr!("drag-and-drop-disc-image-files"))
                                     .size(18.0)
                                     .color(egui::Color32::GRAY)
                             );
                             ui.add_space(10.0);
                             ui.label(
-                                egui::RichText::new("Supported: ISO, Toast, CHD, BIN/CUE, MDS/MDF")
+                                egui::RichText::new(tr!("supported-iso-toast-chd-bin-cue"))
                                     .small()
                                     .color(egui::Color32::DARK_GRAY)
                             );
@@ -3871,35 +8120,147 @@ impl eframe::App for App {
         // Show update notification dialog
         if self.show_update_notification {
             if let Some(update_info) = self.update_info.clone() {
-                egui::Window::new("Update Available")
+                egui::Window::new(tr!("update-available"))
                     .collapsible(false)
                     .resizable(false)
                     .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                     .show(&ctx, |ui| {
-                        ui.label(format!(
-                            "A new version is available: v{}",
-                            update_info.latest_version
+                        if update_info.prerelease {
+                            ui.label(tr!(
+                                "update-new-beta",
+                                version = update_info.latest_version.as_str()
+                            ));
+                            ui.label(egui::RichText::new(tr!("update-beta-warning")).weak());
+                        } else {
+                            ui.label(tr!(
+                                "update-new-version",
+                                version = update_info.latest_version.as_str()
+                            ));
+                        }
+                        ui.label(tr!(
+                            "update-current-version",
+                            version = update_info.current_version.as_str()
                         ));
-                        ui.label(format!("Current version: v{}", update_info.current_version));
+                        if !update_info.notes.trim().is_empty() {
+                            ui.add_space(6.0);
+                            ui.label(egui::RichText::new(tr!("update-whats-new")).strong());
+                            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                                ui.set_max_width(480.0);
+                                show_release_notes(ui, &update_info.notes);
+                            });
+                        }
                         ui.add_space(10.0);
 
+                        if self.events.is_running(Job::UpdateInstall) {
+                            ui.horizontal(|ui| {
+                                transfer_bar(ui, self.update_progress.as_ref().and_then(ProgressFeed::latest));
+                                if ui.button(tr!("cancel")).clicked() {
+                                    self.cancel_update_install();
+                                }
+                            });
+                            return;
+                        }
                         ui.horizontal(|ui| {
-                            if ui.button("Take me to the download").clicked() {
+                            if let Some(asset) = &update_info.asset {
+                                match update_info.install_blocker() {
+                                    None => {
+                                        if ui.button(tr!("update-install")).clicked() {
+                                            self.start_update_install(asset.clone(), update_info.signature.clone());
+                                        }
+                                    }
+                                    Some(reason) => {
+                                        ui.add_enabled(false, egui::Button::new(tr!("update-install")))
+                                            .on_disabled_hover_text(reason);
+                                    }
+                                }
+                            }
+                            if ui.button(tr!("take-me-to-the-download")).clicked() {
                                 if let Err(e) = open_in_browser(&update_info.releases_url) {
                                     self.log(LogLevel::Error, format!("Failed to open browser: {}", e));
                                 }
                                 self.show_update_notification = false;
                             }
-                            if ui.button("Skip").clicked() {
+                            if ui.button(tr!("skip")).clicked() {
                                 self.show_update_notification = false;
                             }
                         });
                     });
             }
         }
+
+        // Offer to restart into the version just installed
+        if let Some(program) = self.update_relaunch.clone() {
+            egui::Window::new(tr!("update-installed"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(&ctx, |ui| {
+                    ui.label(tr!("update-restart-prompt"));
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("update-restart-now")).clicked() {
+                            match crate::update::relaunch(&program) {
+                                Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                                Err(e) => self.log(LogLevel::Error, format!("Failed to restart: {}", e)),
+                            }
+                            self.update_relaunch = None;
+                        }
+                        if ui.button(tr!("update-later")).clicked() {
+                            self.update_relaunch = None;
+                        }
+                    });
+                });
+        }
+    }
+}
+
+/// A release body as headings, bullets and paragraphs, for the update
+/// window.
+fn show_release_notes(ui: &mut egui::Ui, notes: &str) {
+    for block in note_blocks(notes) {
+        match block {
+            NoteBlock::Heading(text) => {
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new(text).strong().size(15.0));
+            }
+            NoteBlock::Bullet { depth, text } => {
+                ui.horizontal_top(|ui| {
+                    ui.add_space(depth as f32 * 14.0);
+                    ui.label("•");
+                    ui.add(egui::Label::new(text).wrap());
+                });
+            }
+            NoteBlock::Paragraph(text) => {
+                ui.add(egui::Label::new(text).wrap());
+                ui.add_space(4.0);
+            }
+            NoteBlock::Code(text) => {
+                ui.label(egui::RichText::new(text).monospace());
+            }
+        }
     }
 }
 
+/// Whether this frame's input includes a paste.
+fn pasted_into(ui: &egui::Ui) -> bool {
+    ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Paste(_))))
+}
+
+/// A small button showing only `icon`, named `label` for screen readers and
+/// on hover.
+pub(super) fn icon_button(ui: &mut egui::Ui, icon: &str, label: impl Into<String>) -> egui::Response {
+    named(ui.small_button(icon), egui::WidgetType::Button, label)
+}
+
+/// Give a widget whose visible text is a symbol (or nothing) a name: eframe
+/// passes it to the platform's accessibility API, and it's the hover text.
+fn named(response: egui::Response, kind: egui::WidgetType, label: impl Into<String>) -> egui::Response {
+    let label = label.into();
+    let enabled = response.enabled();
+    response.widget_info(|| egui::WidgetInfo::labeled(kind, enabled, &label));
+    response.on_hover_text(label)
+}
+
 /// A frameless "✕" close button drawn with strokes, matching egui's own window
 /// close button. Drawn rather than typed because egui's default font has no glyph
 /// for U+2715 (it renders as a tofu box).
@@ -3920,7 +8281,13 @@ fn close_x_button(ui: &mut egui::Ui) -> egui::Response {
 fn preview_files_being_dropped(ctx: &egui::Context) {
     use egui::{Align2, Color32, Id, LayerId, Order, TextStyle};
 
-    if !ctx.input(|i| i.raw.hovered_files.is_empty()) {
+    let hovered = ctx.input(|i| i.raw.hovered_files.clone());
+    if let Some(first) = hovered.first() {
+        let is_link = first
+            .path
+            .as_ref()
+            .is_none_or(|path| path.to_string_lossy().starts_with("http"));
+        let text = if is_link { tr!("drop-link") } else { tr!("drop-disc-image") };
         let painter =
             ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("file_drop_target")));
 
@@ -3929,13 +8296,78 @@ fn preview_files_being_dropped(ctx: &egui::Context) {
         painter.text(
             screen_rect.center(),
             Align2::CENTER_CENTER,
-            "Drop disc image to scan",
+            text,
             TextStyle::Heading.resolve(&ctx.global_style()),
             Color32::WHITE,
         );
     }
 }
 
+/// The ISO 9660 volume descriptor's identifiers, creation date and size,
+/// and the disc's directory and file counts, each with a copy button.
+/// Blank identifiers are left out.
+fn volume_descriptor_details(
+    ui: &mut egui::Ui,
+    pvd: &crate::disc::PrimaryVolumeDescriptor,
+    counts: Option<crate::disc::EntryCounts>,
+    counting: bool,
+) {
+    use crate::disc::volume_details::{id_field, volume_bytes};
+
+    let mut rows: Vec<(String, String)> = [
+        (tr!("pvd-system-id"), id_field(&pvd.system_id)),
+        (tr!("pvd-application-id"), id_field(&pvd.application_id)),
+        (tr!("pvd-publisher"), id_field(&pvd.publisher_id)),
+        (tr!("pvd-data-preparer"), id_field(&pvd.data_preparer_id)),
+    ]
+    .into_iter()
+    .filter_map(|(k, v)| v.map(|v| (k, v.to_string())))
+    .collect();
+    if let Some(ref date) = pvd.creation_date {
+        rows.push((tr!("pvd-created"), date.to_iso8601()));
+    }
+    let bytes = volume_bytes(pvd.volume_space_size as u64, pvd.logical_block_size as u64);
+    rows.push((
+        tr!("pvd-size"),
+        tr!(
+            "pvd-size-value",
+            sectors = pvd.volume_space_size,
+            size = super::progress::format_size(bytes)
+        ),
+    ));
+    if let Some(counts) = counts {
+        let value = if counts.truncated {
+            tr!("pvd-contents-partial", directories = counts.directories, files = counts.files)
+        } else {
+            tr!("pvd-contents-value", directories = counts.directories, files = counts.files)
+        };
+        rows.push((tr!("pvd-contents"), value));
+    }
+
+    egui::Grid::new("volume_descriptor_grid")
+        .num_columns(3)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for (key, value) in &rows {
+                ui.label(egui::RichText::new(format!("{key}:")).color(egui::Color32::GRAY));
+                ui.label(value);
+                if icon_button(ui, "📋", tr!("copy-to-clipboard")).clicked() {
+                    ui.ctx().copy_text(value.clone());
+                }
+                ui.end_row();
+            }
+            if counting {
+                ui.label(egui::RichText::new(format!("{}:", tr!("pvd-contents"))).color(egui::Color32::GRAY));
+                ui.spinner();
+                ui.end_row();
+            }
+        });
+    if ui.small_button(tr!("copy-all")).clicked() {
+        let text: Vec<String> = rows.iter().map(|(k, v)| format!("{k}: {v}")).collect();
+        ui.ctx().copy_text(text.join("\n"));
+    }
+}
+
 /// Captured for one frame so the disc-info grid can render the hashing row
 /// without re-locking `hash_progress` mid-closure.
 struct HashRowSnapshot {
@@ -3948,15 +8380,15 @@ struct HashRowSnapshot {
 }
 
 /// Map a redump match source to (badge color, human label).
-fn match_styling(source: crate::db::MatchSource) -> (egui::Color32, &'static str) {
+fn match_styling(source: crate::db::MatchSource) -> (egui::Color32, String) {
     use crate::db::MatchSource as M;
     match source {
         M::TrackSha1 | M::TrackMd5 | M::TrackCrc32 => {
-            (egui::Color32::GREEN, "Confirmed (hash)")
+            (egui::Color32::GREEN, tr!("match-hash"))
         }
-        M::Serial => (egui::Color32::GREEN, "Confirmed (serial)"),
-        M::Barcode => (egui::Color32::GREEN, "Confirmed (barcode)"),
-        M::PvdVolumeId => (egui::Color32::YELLOW, "Likely (volume label)"),
-        M::FuzzyTitle => (egui::Color32::from_rgb(220, 200, 80), "Possible (title)"),
+        M::Serial => (egui::Color32::GREEN, tr!("match-serial")),
+        M::Barcode => (egui::Color32::GREEN, tr!("match-barcode")),
+        M::PvdVolumeId => (egui::Color32::YELLOW, tr!("match-volume-label")),
+        M::FuzzyTitle => (egui::Color32::from_rgb(220, 200, 80), tr!("match-title")),
     }
 }
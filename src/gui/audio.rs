@@ -3,10 +3,10 @@
 //! Playing an audio track (see [`crate::disc::cd_audio`]) can be slow to start —
 //! a CHD extracts the whole disc to a temporary BIN first — so all the work runs
 //! on a dedicated thread. The UI polls [`AudioPlayback::state`] each frame and
-//! offers a Stop button.
+//! offers Pause/Resume and Stop buttons.
 //!
 //! rodio's `OutputStream` is `!Send`, so it is created and owned entirely on
-//! the playback thread; the UI only ever touches the shared state flag.
+//! the playback thread; the UI only ever touches the shared flags.
 
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -40,31 +40,51 @@ pub enum PlaybackState {
 pub struct AudioPlayback {
     track: u32,
     stop: Arc<AtomicBool>,
+    /// Requested pause state; the worker mirrors it onto the sink.
+    paused: Arc<AtomicBool>,
     state: Arc<Mutex<PlaybackState>>,
     /// Set to `Instant::now()` the moment the first audio reaches the sink, i.e.
     /// when playback actually starts. `None` while still `Preparing`.
     play_start: Arc<Mutex<Option<Instant>>>,
+    /// Time spent paused in earlier pauses, and when the current one began,
+    /// so the elapsed readout stands still while paused.
+    paused_total: Duration,
+    paused_at: Option<Instant>,
 }
 
 impl AudioPlayback {
     /// Spawn a worker that extracts and plays `track` from `image_path`.
     pub fn start(image_path: PathBuf, track: u32) -> Self {
         let stop = Arc::new(AtomicBool::new(false));
+        let paused = Arc::new(AtomicBool::new(false));
         let state = Arc::new(Mutex::new(PlaybackState::Preparing));
         let play_start = Arc::new(Mutex::new(None));
         let stop_bg = Arc::clone(&stop);
+        let paused_bg = Arc::clone(&paused);
         let state_bg = Arc::clone(&state);
         let play_start_bg = Arc::clone(&play_start);
 
         // Detached: the worker owns its rodio stream and the temp extraction,
         // and tears itself down when it sees the stop flag or finishes.
-        thread::spawn(move || run(image_path, track, &stop_bg, &state_bg, &play_start_bg));
+        thread::spawn(move || {
+            run(
+                image_path,
+                track,
+                &stop_bg,
+                &paused_bg,
+                &state_bg,
+                &play_start_bg,
+            )
+        });
 
         Self {
             track,
             stop,
+            paused,
             state,
             play_start,
+            paused_total: Duration::ZERO,
+            paused_at: None,
         }
     }
 
@@ -85,12 +105,38 @@ impl AudioPlayback {
     ///
     /// CD-DA streams to the sink in real time, so wall-clock since the first
     /// sample was queued tracks the speaker position (bar a few ms of startup
-    /// latency). Callers should clamp to the track length for display.
+    /// latency), minus any time spent paused. Callers should clamp to the
+    /// track length for display.
     pub fn elapsed_secs(&self) -> Option<f64> {
-        self.play_start
-            .lock()
-            .ok()
-            .and_then(|p| p.map(|t| t.elapsed().as_secs_f64()))
+        let start = self.play_start.lock().ok().and_then(|p| *p)?;
+        let end = self.paused_at.unwrap_or_else(Instant::now);
+        let played = end
+            .saturating_duration_since(start)
+            .saturating_sub(self.paused_total);
+        Some(played.as_secs_f64())
+    }
+
+    /// Whether playback is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Pause or resume. Audio already queued holds where it is; while
+    /// preparing, the first audio waits until resumed.
+    pub fn set_paused(&mut self, paused: bool) {
+        if paused == self.is_paused() {
+            return;
+        }
+        if paused {
+            self.paused_at = Some(Instant::now());
+        } else if let Some(at) = self.paused_at.take() {
+            // Only time after the first audio counts; a pause while
+            // preparing just delays the start.
+            if let Some(start) = self.play_start.lock().ok().and_then(|p| *p) {
+                self.paused_total += start.max(at).elapsed();
+            }
+        }
+        self.paused.store(paused, Ordering::Relaxed);
     }
 
     /// Signal the worker to stop. Extraction in progress stops at the next
@@ -116,6 +162,7 @@ fn run(
     image_path: PathBuf,
     track: u32,
     stop: &Arc<AtomicBool>,
+    paused: &Arc<AtomicBool>,
     state: &Arc<Mutex<PlaybackState>>,
     play_start: &Arc<Mutex<Option<Instant>>>,
 ) {
@@ -137,14 +184,17 @@ fn run(
             set(state, PlaybackState::Playing);
             started = true;
         }
-        // Bounded queue: wait while the sink is a few chunks ahead, bailing if
-        // the user hit Stop.
+        // Bounded queue: wait while the sink is a few chunks ahead (a paused
+        // sink doesn't drain, so this also holds extraction), bailing if the
+        // user hit Stop.
         while sink.len() >= MAX_QUEUED_CHUNKS {
             if stop.load(Ordering::Relaxed) {
                 return;
             }
+            sync_pause(&sink, paused);
             thread::sleep(Duration::from_millis(20));
         }
+        sync_pause(&sink, paused);
         sink.append(SamplesBuffer::new(
             CDDA_CHANNELS,
             CDDA_SAMPLE_RATE,
@@ -163,7 +213,7 @@ fn run(
         return;
     }
 
-    // Drain the queued audio, honoring Stop.
+    // Drain the queued audio, honoring Pause and Stop.
     loop {
         if stop.load(Ordering::Relaxed) {
             sink.stop();
@@ -172,7 +222,20 @@ fn run(
         if sink.empty() {
             break;
         }
+        sync_pause(&sink, paused);
         thread::sleep(Duration::from_millis(50));
     }
     set(state, PlaybackState::Finished);
 }
+
+/// Mirror the UI's pause request onto the sink.
+fn sync_pause(sink: &rodio::Sink, paused: &AtomicBool) {
+    let want = paused.load(Ordering::Relaxed);
+    if want != sink.is_paused() {
+        if want {
+            sink.pause();
+        } else {
+            sink.play();
+        }
+    }
+}
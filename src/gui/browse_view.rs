@@ -4,8 +4,9 @@ use eframe::egui;
 use std::collections::HashSet;
 use std::sync::mpsc::Receiver;
 
-use crate::disc::browse::{EntryType, FileEntry, open_filesystem};
+use crate::disc::browse::{EntryType, FileEntry, Filesystem, open_filesystem};
 use crate::disc::DiscInfo;
+use crate::i18n::tr;
 
 use super::hex_view::HexView;
 use super::text_view::{TextView, TextEncoding, detect_text_encoding};
@@ -39,6 +40,10 @@ pub enum FileContent {
 
 /// Browse view state
 pub struct BrowseView {
+    /// Filesystem opened by `initialize`. Kept so expanding folders and
+    /// opening files reuse its reader, and the CHD hunks it already
+    /// decompressed, instead of reopening the image every click
+    fs: Option<Box<dyn Filesystem>>,
     /// Root file entry
     root: Option<FileEntry>,
     /// Set of expanded directory paths
@@ -72,6 +77,7 @@ pub struct BrowseView {
 impl Default for BrowseView {
     fn default() -> Self {
         Self {
+            fs: None,
             root: None,
             expanded_paths: HashSet::new(),
             directory_cache: std::collections::HashMap::new(),
@@ -114,10 +120,19 @@ impl BrowseView {
         self.directory_cache.insert("/".to_string(), root_entries);
         self.expanded_paths.insert("/".to_string());
         self.error = None;
+        self.fs = Some(fs);
 
         Ok(())
     }
 
+    /// The open filesystem, opening it again if `initialize` didn't
+    fn filesystem(&mut self, disc_info: &DiscInfo) -> Option<&mut dyn Filesystem> {
+        if self.fs.is_none() {
+            self.fs = open_filesystem(disc_info).ok();
+        }
+        self.fs.as_deref_mut()
+    }
+
     /// Poll for async results
     pub fn poll(&mut self) {
         // Check for directory listing results
@@ -185,7 +200,7 @@ impl BrowseView {
             .size_range(200.0..=700.0)
             .show_inside(ui, |ui| {
                 ui.set_min_height(panel_height);
-                ui.heading("Files");
+                ui.heading(tr!("browse-files"));
                 ui.separator();
 
                 egui::ScrollArea::vertical()
@@ -203,20 +218,20 @@ impl BrowseView {
         egui::CentralPanel::default().show_inside(ui, |ui| {
             ui.vertical(|ui| {
                 ui.set_min_height(panel_height);
-                ui.heading("Content");
+                ui.heading(tr!("content"));
 
                 // View mode selector
                 ui.horizontal(|ui| {
-                    ui.label("View:");
-                    ui.selectable_value(&mut self.view_mode, ViewMode::Auto, "Auto");
-                    ui.selectable_value(&mut self.view_mode, ViewMode::Hex, "Hex");
-                    ui.selectable_value(&mut self.view_mode, ViewMode::Text, "Text");
+                    ui.label(tr!("view"));
+                    ui.selectable_value(&mut self.view_mode, ViewMode::Auto, tr!("auto"));
+                    ui.selectable_value(&mut self.view_mode, ViewMode::Hex, tr!("hex"));
+                    ui.selectable_value(&mut self.view_mode, ViewMode::Text, tr!("text"));
 
                     ui.separator();
 
                     // Export button
                     let export_entry = if let Some(ref entry) = self.selected_entry {
-                        if entry.is_file() && ui.button("Export...").clicked() {
+                        if entry.is_file() && ui.button(tr!("export-file")).clicked() {
                             Some(entry.clone())
                         } else {
                             None
@@ -236,10 +251,10 @@ impl BrowseView {
                 if self.loading {
                     ui.horizontal(|ui| {
                         ui.spinner();
-                        ui.label("Loading...");
+                        ui.label(tr!("loading"));
                     });
                 } else if let Some(ref error) = self.error {
-                    ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
+                    ui.colored_label(egui::Color32::RED, tr!("error-message", error = error.as_str()));
                 } else if let Some(ref content) = self.content {
                     match content {
                         FileContent::Binary(data) => {
@@ -261,7 +276,7 @@ impl BrowseView {
                                     self.hex_view.show(ui, text.as_bytes(), 0);
                                 }
                                 _ => {
-                                    ui.label(format!("Encoding: {}", encoding.display_name()));
+                                    ui.label(tr!("text-encoding", encoding = encoding.display_name()));
                                     self.text_view.show(ui, text);
                                 }
                             }
@@ -269,19 +284,19 @@ impl BrowseView {
                     }
                 } else if let Some(ref entry) = self.selected_entry {
                     if entry.is_directory() {
-                        ui.label("Select a file to view its contents");
+                        ui.label(tr!("select-a-file-to-view-its"));
                     } else if entry.size > MAX_VIEW_SIZE {
-                        ui.label(format!(
-                            "File is too large to view ({} > {} MB)",
-                            entry.size_string(),
-                            MAX_VIEW_SIZE / 1024 / 1024
+                        ui.label(tr!(
+                            "file-too-large",
+                            size = entry.size_string(),
+                            limit = MAX_VIEW_SIZE / 1024 / 1024
                         ));
-                        ui.label("Use Export to save the file");
+                        ui.label(tr!("use-export-to-save-the-file"));
                     } else {
-                        ui.label("Click on a file to load its contents");
+                        ui.label(tr!("click-on-a-file-to-load"));
                     }
                 } else {
-                    ui.label("Select a file from the tree to view its contents");
+                    ui.label(tr!("select-a-file-from-the-tree"));
                 }
             });
         });
@@ -306,7 +321,7 @@ impl BrowseView {
                                 self.render_tree_entry(ui, &child, disc_info);
                             }
                         } else if !self.loading {
-                            ui.label("Loading...");
+                            ui.label(tr!("loading"));
                         }
                     });
 
@@ -330,11 +345,10 @@ impl BrowseView {
     fn load_directory(&mut self, entry: FileEntry, disc_info: &DiscInfo) {
         // For simplicity, load synchronously in this version
         // A full async implementation would use channels
-        if let Ok(mut fs) = open_filesystem(disc_info) {
-            if let Ok(children) = fs.list_directory(&entry) {
-                self.directory_cache.insert(entry.path.clone(), children);
-                self.expanded_paths.insert(entry.path);
-            }
+        let children = self.filesystem(disc_info).and_then(|fs| fs.list_directory(&entry).ok());
+        if let Some(children) = children {
+            self.directory_cache.insert(entry.path.clone(), children);
+            self.expanded_paths.insert(entry.path);
         }
     }
 
@@ -351,8 +365,8 @@ impl BrowseView {
 
         // Load file content synchronously
         self.loading = true;
-        if let Ok(mut fs) = open_filesystem(disc_info) {
-            match fs.read_file(&entry) {
+        if let Some(result) = self.filesystem(disc_info).map(|fs| fs.read_file(&entry)) {
+            match result {
                 Ok(data) => {
                     let content = if let Some(encoding) = detect_text_encoding(&data) {
                         let text = encoding.decode(&data);
@@ -378,8 +392,8 @@ impl BrowseView {
             .save_file()
         {
             // Read and save the file
-            if let Ok(mut fs) = open_filesystem(disc_info) {
-                match fs.read_file(entry) {
+            if let Some(result) = self.filesystem(disc_info).map(|fs| fs.read_file(entry)) {
+                match result {
                     Ok(data) => {
                         if let Err(e) = std::fs::write(&path, &data) {
                             self.error = Some(format!("Failed to write file: {}", e));
@@ -12,6 +12,8 @@ use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
 
+use crate::disc::chd_artwork;
+
 /// Mirrors `fuzzy_scan::Record`. Kept independent so the GUI doesn't depend on
 /// the binary crate; structurally identical so the JSON round-trips.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -402,13 +404,13 @@ fn group_records(records: Vec<Record>) -> Vec<QueueItem> {
 fn has_sidecar_art(disc_path: &Path) -> bool {
     let Some(stem) = disc_path.file_stem() else { return false; };
     let Some(dir) = disc_path.parent() else { return false; };
-    for ext in ["jpg", "jpeg", "png"] {
+    for ext in ["jpg", "jpeg", "png", "bmp"] {
         let candidate = dir.join(format!("{}.{}", stem.to_string_lossy(), ext));
         if candidate.is_file() {
             return true;
         }
     }
-    false
+    chd_artwork::has_artwork(disc_path)
 }
 
 /// Minimal CSV reader for fuzzy_scan's exact 12-column output. Handles
@@ -583,36 +585,3 @@ fn sidecar_done_path(job_path: &Path) -> PathBuf {
     p.set_file_name(new_name);
     p
 }
-
-/// Format current UTC time as an ISO-8601 string for the sidecar log.
-/// Uses Howard Hinnant's days-from-civil inverse so we don't pull in chrono
-/// for a single line of output.
-pub fn now_iso8601() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let secs = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs() as i64)
-        .unwrap_or(0);
-    let (y, mo, d, h, mi, s) = unix_to_ymdhms(secs);
-    format!("{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}Z")
-}
-
-fn unix_to_ymdhms(t: i64) -> (i64, u32, u32, u32, u32, u32) {
-    let days = t.div_euclid(86_400);
-    let secs_of_day = t.rem_euclid(86_400) as u32;
-    let h = secs_of_day / 3600;
-    let mi = (secs_of_day % 3600) / 60;
-    let s = secs_of_day % 60;
-
-    let z = days + 719468;
-    let era = if z >= 0 { z / 146097 } else { (z - 146096) / 146097 };
-    let doe = (z - era * 146097) as u64;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y_base = yoe as i64 + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
-    let y = if m <= 2 { y_base + 1 } else { y_base };
-    (y, m, d, h, mi, s)
-}
@@ -0,0 +1,129 @@
+//! App-wide background jobs: the update and lookup DB checks, update
+//! installs, exports, rips, pack imports, artwork fixes and USBODE pushes
+//! each run on their own thread and publish an [`AppEvent`] on one [`EventBus`] when done, which
+//! `App` drains once a frame.
+//!
+//! Per-disc work (searches, previews, hashing) keeps its own receivers:
+//! those move in and out of background tabs with the rest of the disc's
+//! state.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::db::UpdateOutcome;
+use crate::export::{ExportResult, FixSummary};
+use crate::logging::crash::catch_recovered;
+use crate::packs::ImportSummary;
+use crate::update::{Installed, UpdateInfo};
+
+use super::app::FixArtworkDialog;
+
+/// A kind of background job. At most one of each runs at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Job {
+    UpdateCheck,
+    UpdateInstall,
+    UserAgentCapture,
+    DbUpdate,
+    Export,
+    AudioRip,
+    ChdExtract,
+    PackImport,
+    FixScan,
+    FixRun,
+    UsbodePush,
+}
+
+/// What a finished job published.
+pub enum AppEvent {
+    UpdateChecked(Result<UpdateInfo, String>),
+    UpdateInstalled(Result<Installed, String>),
+    UserAgentCaptured(Result<String, String>),
+    DbUpdated(Result<UpdateOutcome, String>),
+    Exported(Result<ExportResult, String>),
+    /// The files written
+    AudioRipped(Result<Vec<PathBuf>, String>),
+    /// The cue sheet written
+    ChdExtracted(Result<PathBuf, String>),
+    PackImported(Result<ImportSummary, String>),
+    FixScanned(Result<FixArtworkDialog, String>),
+    FixRan(FixSummary),
+    /// Where the cover went on the device
+    UsbodePushed(Result<String, String>),
+    /// The job's thread panicked before publishing anything
+    Lost(Job),
+}
+
+struct Envelope {
+    job: Job,
+    /// Which start of `job` this is from
+    run: u64,
+    /// `None` if the thread panicked
+    event: Option<AppEvent>,
+}
+
+pub struct EventBus {
+    tx: Sender<Envelope>,
+    rx: Receiver<Envelope>,
+    /// Jobs running, with the run whose result is still wanted
+    running: HashMap<Job, u64>,
+    last_run: u64,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            tx,
+            rx,
+            running: HashMap::new(),
+            last_run: 0,
+        }
+    }
+}
+
+impl EventBus {
+    /// Run `work` on a new thread and publish its event. Starting a job
+    /// that's already running leaves the old thread be, but only the new
+    /// one's result comes out of [`Self::drain`].
+    pub fn spawn<F>(&mut self, job: Job, work: F)
+    where
+        F: FnOnce() -> AppEvent + Send + 'static,
+    {
+        self.last_run += 1;
+        let run = self.last_run;
+        self.running.insert(job, run);
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            // The panic hook has already written the crash report, marked as
+            // recovered; this only tells the window the job is gone.
+            let event = catch_recovered(work).ok();
+            let _ = tx.send(Envelope { job, run, event });
+        });
+    }
+
+    /// Whether `job` has been started and hasn't published yet.
+    pub fn is_running(&self, job: Job) -> bool {
+        self.running.contains_key(&job)
+    }
+
+    /// Stop waiting for `job`: its result is dropped when it arrives.
+    pub fn forget(&mut self, job: Job) {
+        self.running.remove(&job);
+    }
+
+    /// Events published since the last call, oldest first.
+    pub fn drain(&mut self) -> Vec<AppEvent> {
+        let mut events = Vec::new();
+        while let Ok(envelope) = self.rx.try_recv() {
+            if self.running.get(&envelope.job) != Some(&envelope.run) {
+                continue;
+            }
+            self.running.remove(&envelope.job);
+            events.push(envelope.event.unwrap_or(AppEvent::Lost(envelope.job)));
+        }
+        events
+    }
+}
@@ -0,0 +1,335 @@
+//! "Scan Folder..." results: every disc image under a folder, identified on
+//! background threads, for the sortable table in the scan window.
+//!
+//! Identification is the plain `DiscReader::read` the main view does on
+//! load. When the redump database is installed, each worker also hashes
+//! the data track and looks the hash up, so the table shows which discs are
+//! verified dumps. Rows can be opened in the main view or sent to the
+//! processing queue ([`super::queue`]).
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use rusqlite::Connection;
+
+use crate::config::ExportConfig;
+use crate::db::{CascadeInputs, DatabaseManager};
+use crate::disc::hasher::hash_data_track_with_progress;
+use crate::disc::scan::find_disc_images;
+use crate::disc::{ConfidenceLevel, DiscInfo, DiscReader};
+use crate::export::{generate_named_output_path, ArtworkRole, OutputNaming};
+use crate::i18n::tr;
+use crate::net::CancelToken;
+
+/// One scanned disc.
+#[derive(Debug, Clone)]
+pub struct ScanRow {
+    pub path: PathBuf,
+    pub title: String,
+    /// Format name, empty when the disc couldn't be read
+    pub format: String,
+    /// `None` when the disc couldn't be read
+    pub confidence: Option<ConfidenceLevel>,
+    pub has_artwork: bool,
+    /// SHA-1 of the data track; `None` when it wasn't hashed (no redump
+    /// database, or a format the hasher doesn't read)
+    pub sha1: Option<String>,
+    /// Redump title the hash matched
+    pub redump_title: Option<String>,
+    pub error: Option<String>,
+    /// Ticked for batch processing
+    pub selected: bool,
+}
+
+/// Table column the rows are sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortColumn {
+    #[default]
+    Title,
+    Format,
+    Confidence,
+    Redump,
+    Artwork,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 5] = [
+        SortColumn::Title,
+        SortColumn::Format,
+        SortColumn::Confidence,
+        SortColumn::Redump,
+        SortColumn::Artwork,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            SortColumn::Title => tr!("scan-column-title"),
+            SortColumn::Format => tr!("scan-column-format"),
+            SortColumn::Confidence => tr!("scan-column-confidence"),
+            SortColumn::Redump => tr!("scan-column-redump"),
+            SortColumn::Artwork => tr!("scan-column-artwork"),
+        }
+    }
+}
+
+enum ScanUpdate {
+    /// Number of disc images found, once the walk is done
+    Found(usize),
+    Row(ScanRow),
+}
+
+/// A folder scan, running or finished.
+pub struct FolderScan {
+    pub root: PathBuf,
+    pub rows: Vec<ScanRow>,
+    /// Images found; `None` while the folder is still being walked
+    pub total: Option<usize>,
+    pub sort: SortColumn,
+    pub ascending: bool,
+    receiver: Option<Receiver<ScanUpdate>>,
+    cancel: CancelToken,
+}
+
+impl FolderScan {
+    /// Walk `root` and identify what's found in the background, on the
+    /// configured number of workers. Artwork is looked for under the names
+    /// `export_config` would save to.
+    pub fn start(root: PathBuf, export_config: ExportConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = CancelToken::new();
+        let walk_root = root.clone();
+        let walk_cancel = cancel.clone();
+        thread::spawn(move || {
+            let paths = find_disc_images(&walk_root);
+            if tx.send(ScanUpdate::Found(paths.len())).is_err() {
+                return;
+            }
+            let workers = crate::pool::workers(crate::config::get_config().scan_workers);
+            let results = crate::pool::map_with(
+                &paths,
+                workers,
+                // One connection per worker; rusqlite's isn't Sync
+                open_database,
+                |conn, path| {
+                    // Left-over paths are skipped once the window stops listening
+                    if walk_cancel.is_cancelled() {
+                        return;
+                    }
+                    let row = identify(path.clone(), &export_config, conn.as_ref(), &walk_cancel);
+                    if tx.send(ScanUpdate::Row(row)).is_err() {
+                        walk_cancel.cancel();
+                    }
+                },
+            );
+            for (path, result) in paths.into_iter().zip(results) {
+                if let Err(e) = result {
+                    let row = unreadable(path, e.to_string(), &export_config);
+                    if tx.send(ScanUpdate::Row(row)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            root,
+            rows: Vec::new(),
+            total: None,
+            sort: SortColumn::default(),
+            ascending: true,
+            receiver: Some(rx),
+            cancel,
+        }
+    }
+
+    /// Pick up rows identified since the last call.
+    pub fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        let mut changed = false;
+        let mut done = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(ScanUpdate::Found(n)) => self.total = Some(n),
+                Ok(ScanUpdate::Row(row)) => {
+                    self.rows.push(row);
+                    changed = true;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+        if done {
+            self.receiver = None;
+        }
+        if changed {
+            self.sort_rows();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Stop identifying; rows found so far stay.
+    pub fn cancel(&mut self) {
+        self.cancel.cancel();
+        self.receiver = None;
+    }
+
+    /// Sort by `column`, flipping the direction if it's already the sort.
+    pub fn sort_by(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.ascending = !self.ascending;
+        } else {
+            self.sort = column;
+            self.ascending = true;
+        }
+        self.sort_rows();
+    }
+
+    fn sort_rows(&mut self) {
+        let column = self.sort;
+        self.rows.sort_by(|a, b| {
+            let order = match column {
+                SortColumn::Title => compare_titles(a, b),
+                SortColumn::Format => a.format.cmp(&b.format),
+                SortColumn::Confidence => a.confidence.cmp(&b.confidence),
+                SortColumn::Redump => (a.redump_title.is_some(), a.sha1.is_some())
+                    .cmp(&(b.redump_title.is_some(), b.sha1.is_some())),
+                SortColumn::Artwork => a.has_artwork.cmp(&b.has_artwork),
+            };
+            order.then_with(|| compare_titles(a, b))
+        });
+        if !self.ascending {
+            self.rows.reverse();
+        }
+    }
+
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        self.rows
+            .iter()
+            .filter(|r| r.selected)
+            .map(|r| r.path.clone())
+            .collect()
+    }
+
+    /// Tick the readable rows without artwork, and untick the rest.
+    pub fn select_missing_artwork(&mut self) {
+        for row in &mut self.rows {
+            row.selected = row.error.is_none() && !row.has_artwork;
+        }
+    }
+}
+
+fn compare_titles(a: &ScanRow, b: &ScanRow) -> Ordering {
+    a.title
+        .to_lowercase()
+        .cmp(&b.title.to_lowercase())
+        .then_with(|| a.path.cmp(&b.path))
+}
+
+/// The redump database, or `None` (no hashing) when it isn't downloaded or
+/// won't open.
+fn open_database() -> Option<Connection> {
+    match DatabaseManager::new().and_then(|m| m.open()) {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            log::debug!("Scan: hashing skipped, no redump database: {e}");
+            None
+        }
+    }
+}
+
+fn named_cover_exists(path: &Path, export_config: &ExportConfig) -> bool {
+    let naming = OutputNaming::from_config(export_config);
+    Path::new(&generate_named_output_path(
+        path,
+        &naming,
+        ArtworkRole::Front,
+    ))
+    .exists()
+}
+
+fn identify(
+    path: PathBuf,
+    export_config: &ExportConfig,
+    conn: Option<&Connection>,
+    cancel: &CancelToken,
+) -> ScanRow {
+    let info = match DiscReader::read(&path) {
+        Ok(info) => info,
+        Err(e) => return unreadable(path, e.to_string(), export_config),
+    };
+    let (sha1, redump_title) = match conn.and_then(|conn| hash_lookup(conn, &info, cancel)) {
+        Some((sha1, title)) => (Some(sha1), title),
+        None => (None, None),
+    };
+    ScanRow {
+        title: info.title.clone(),
+        format: info.format.display_name().to_string(),
+        confidence: Some(info.confidence),
+        has_artwork: info.has_cover_art() || named_cover_exists(&path, export_config),
+        sha1,
+        redump_title,
+        error: None,
+        selected: false,
+        path,
+    }
+}
+
+/// Hash the disc's data track and look the hash up in the redump database.
+/// Returns the SHA-1 and the title it matched, or `None` when the disc
+/// couldn't be hashed or looked up.
+fn hash_lookup(
+    conn: &Connection,
+    info: &DiscInfo,
+    cancel: &CancelToken,
+) -> Option<(String, Option<String>)> {
+    let hashes = match hash_data_track_with_progress(info, cancel, &|_| {}) {
+        Ok(hashes) => hashes,
+        Err(e) => {
+            log::debug!("Scan: {} not hashed: {e}", info.path.display());
+            return None;
+        }
+    };
+    let inputs = CascadeInputs {
+        track_sha1: Some(&hashes.sha1),
+        track_md5: Some(&hashes.md5),
+        track_crc32: Some(&hashes.crc32),
+        ..Default::default()
+    };
+    match crate::db::lookup::cascade(conn, &inputs) {
+        Ok(matches) => {
+            let title = matches.and_then(|m| m.into_iter().next()).map(|m| m.title);
+            Some((hashes.sha1, title))
+        }
+        Err(e) => {
+            log::warn!("Scan: hash lookup failed for {}: {e}", info.path.display());
+            None
+        }
+    }
+}
+
+/// Row for a disc that couldn't be read, or whose worker panicked.
+fn unreadable(path: PathBuf, error: String, export_config: &ExportConfig) -> ScanRow {
+    ScanRow {
+        title: path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        format: String::new(),
+        confidence: None,
+        has_artwork: named_cover_exists(&path, export_config),
+        sha1: None,
+        redump_title: None,
+        error: Some(error),
+        selected: false,
+        path,
+    }
+}
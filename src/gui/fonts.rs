@@ -0,0 +1,52 @@
+//! Fallback font for Japanese, Chinese and Korean text.
+//!
+//! egui's built-in fonts only cover Latin, Greek and Cyrillic, so the
+//! Japanese translation and disc titles in CJK scripts would draw as boxes.
+//! Rather than bundle a 20 MB font, the first CJK font found among the
+//! usual system locations is added after the built-in ones.
+
+use std::path::Path;
+
+use eframe::egui;
+use egui::epaint::text::{FontInsert, FontPriority, InsertFontFamily};
+
+/// System CJK fonts, most preferred first.
+const CJK_FONT_PATHS: &[&str] = &[
+    // Windows
+    "C:\\Windows\\Fonts\\meiryo.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+    // macOS
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+    // Linux (Debian/Ubuntu, Arch, Fedora)
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+];
+
+/// Add the first CJK font found as the last fallback of both families.
+/// Does nothing when none is installed.
+pub fn add_cjk_fallback(ctx: &egui::Context) {
+    let Some((path, bytes)) = CJK_FONT_PATHS
+        .iter()
+        .find_map(|path| Some((*path, std::fs::read(Path::new(path)).ok()?)))
+    else {
+        log::debug!("No CJK system font found");
+        return;
+    };
+    log::debug!("Using {path} for CJK text");
+    let families = [egui::FontFamily::Proportional, egui::FontFamily::Monospace]
+        .into_iter()
+        .map(|family| InsertFontFamily {
+            family,
+            priority: FontPriority::Lowest,
+        })
+        .collect();
+    ctx.add_font(FontInsert::new(
+        "cjk-fallback",
+        egui::FontData::from_owned(bytes),
+        families,
+    ));
+}
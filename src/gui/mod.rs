@@ -4,18 +4,33 @@
 
 mod app;
 mod audio;
+mod events;
 pub mod browse_view;
 pub mod bulk;
+pub mod folder_scan;
+pub mod fonts;
 pub mod hex_view;
+pub mod names;
+pub mod palette;
 pub mod progress;
+pub mod queue;
+pub mod recent;
+pub mod settings;
 pub mod text_view;
+pub mod theme;
+pub mod thumbs;
+pub mod toasts;
+pub mod tray;
 
 pub use app::App;
 pub use browse_view::BrowseView;
 
+use std::path::Path;
 use std::sync::mpsc::Receiver;
 use std::sync::Mutex;
 
+use crate::i18n::tr;
+
 /// Global storage for the log receiver, used to transfer from main() to App::new()
 static LOG_RECEIVER: Mutex<Option<Receiver<String>>> = Mutex::new(None);
 
@@ -30,3 +45,20 @@ pub fn set_log_receiver(receiver: Receiver<String>) {
 pub(crate) fn take_log_receiver() -> Option<Receiver<String>> {
     LOG_RECEIVER.lock().ok().and_then(|mut guard| guard.take())
 }
+
+/// Ask whether to open a crash report written for a panic on the main
+/// thread, and open it. Installed with `logging::crash::install_with`.
+pub fn offer_crash_report(path: &Path) {
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title(tr!("crash-title"))
+        .set_description(tr!("crash-description", path = path.display().to_string()))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    if answer == rfd::MessageDialogResult::Yes {
+        // open_folder hands files to their default app too
+        if let Err(e) = crate::api::open_folder(path) {
+            eprintln!("{}", e);
+        }
+    }
+}
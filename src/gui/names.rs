@@ -0,0 +1,96 @@
+//! Names of the setting enums as the window shows them, in the window
+//! language. The enums' own `display_name()` stays English for logs and the
+//! command-line tools.
+
+use crate::api::ContentType;
+use crate::config::{
+    ArtTemplate, BadgePosition, BadgeStyle, FitMode, PadFill, ThemeMode, UpdateChannel, UpscaleMode,
+};
+use crate::disc::ConfidenceLevel;
+use crate::export::ArtworkRole;
+use crate::i18n::tr;
+
+pub fn content_type(value: ContentType) -> String {
+    match value {
+        ContentType::Any => tr!("content-type-any"),
+        ContentType::Games => tr!("content-type-games"),
+        ContentType::AppsUtilities => tr!("content-type-apps"),
+        ContentType::AudioCDs => tr!("content-type-audio"),
+    }
+}
+
+pub fn template(value: ArtTemplate) -> String {
+    match value {
+        ArtTemplate::None => tr!("template-cover"),
+        ArtTemplate::CdLabel => tr!("template-cd-label"),
+        ArtTemplate::JewelCase => tr!("template-jewel-case"),
+    }
+}
+
+pub fn role(value: ArtworkRole) -> String {
+    match value {
+        ArtworkRole::Front => tr!("role-front"),
+        ArtworkRole::Back => tr!("role-back"),
+        ArtworkRole::Disc => tr!("role-disc"),
+    }
+}
+
+pub fn fit(value: FitMode) -> String {
+    match value {
+        FitMode::Crop => tr!("fit-crop"),
+        FitMode::Pad => tr!("fit-pad"),
+    }
+}
+
+pub fn pad_fill(value: PadFill) -> String {
+    match value {
+        PadFill::Color => tr!("pad-fill-color"),
+        PadFill::Blur => tr!("pad-fill-blur"),
+    }
+}
+
+pub fn upscale(value: UpscaleMode) -> String {
+    match value {
+        UpscaleMode::Lanczos => tr!("upscale-lanczos"),
+        UpscaleMode::Scale2x => tr!("upscale-scale2x"),
+    }
+}
+
+pub fn badge_position(value: BadgePosition) -> String {
+    match value {
+        BadgePosition::TopLeft => tr!("badge-top-left"),
+        BadgePosition::TopRight => tr!("badge-top-right"),
+        BadgePosition::BottomLeft => tr!("badge-bottom-left"),
+        BadgePosition::BottomRight => tr!("badge-bottom-right"),
+    }
+}
+
+pub fn badge_style(value: BadgeStyle) -> String {
+    match value {
+        BadgeStyle::Pill => tr!("badge-style-pill"),
+        BadgeStyle::Circle => tr!("badge-style-circle"),
+    }
+}
+
+pub fn confidence(value: ConfidenceLevel) -> String {
+    match value {
+        ConfidenceLevel::Low => tr!("confidence-low"),
+        ConfidenceLevel::Medium => tr!("confidence-medium"),
+        ConfidenceLevel::High => tr!("confidence-high"),
+    }
+}
+
+pub fn update_channel(value: UpdateChannel) -> String {
+    match value {
+        UpdateChannel::Stable => tr!("update-channel-stable"),
+        UpdateChannel::Beta => tr!("update-channel-beta"),
+    }
+}
+
+pub fn theme(value: ThemeMode) -> String {
+    match value {
+        ThemeMode::System => tr!("theme-system"),
+        ThemeMode::Dark => tr!("theme-dark"),
+        ThemeMode::Light => tr!("theme-light"),
+    }
+}
@@ -0,0 +1,200 @@
+//! Ctrl+Shift+P command palette: every action in one list, narrowed down by
+//! typing any part of its name.
+//!
+//! The query's characters have to appear in the name in order, not next to
+//! each other, so "scf" finds "Scan folder". Runs of adjacent characters and
+//! characters at the start of a word rank higher.
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+use super::app::{
+    LOG_SHORTCUT, NEW_TAB_SHORTCUT, OPEN_FILE_SHORTCUT, SAVE_SHORTCUT, SEARCH_SHORTCUT,
+};
+
+/// Ctrl+Shift+P (Cmd+Shift+P on macOS): open or close the palette.
+pub const PALETTE_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+    egui::Key::P,
+);
+
+/// An action the palette can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    OpenFile,
+    ScanFolder,
+    Watch,
+    BulkJob,
+    FixArtwork,
+    Search,
+    FocusSearch,
+    Save,
+    UndoExport,
+    NewTab,
+    ToggleLog,
+    OpenLogFile,
+    Queue,
+    ImportPackZip,
+    ImportPackFolder,
+    Settings,
+}
+
+impl Command {
+    /// Every command, in the order an empty query lists them.
+    pub const ALL: [Command; 16] = [
+        Command::OpenFile,
+        Command::ScanFolder,
+        Command::Watch,
+        Command::BulkJob,
+        Command::FixArtwork,
+        Command::Search,
+        Command::FocusSearch,
+        Command::Save,
+        Command::UndoExport,
+        Command::NewTab,
+        Command::ToggleLog,
+        Command::OpenLogFile,
+        Command::Queue,
+        Command::ImportPackZip,
+        Command::ImportPackFolder,
+        Command::Settings,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            Command::OpenFile => tr!("command-open-file"),
+            Command::ScanFolder => tr!("command-scan-folder"),
+            Command::Watch => tr!("command-watch"),
+            Command::BulkJob => tr!("command-bulk-job"),
+            Command::FixArtwork => tr!("command-fix-artwork"),
+            Command::Search => tr!("command-search"),
+            Command::FocusSearch => tr!("command-focus-search"),
+            Command::Save => tr!("command-save"),
+            Command::UndoExport => tr!("undo-last-export"),
+            Command::NewTab => tr!("tab-new"),
+            Command::ToggleLog => tr!("command-toggle-log"),
+            Command::OpenLogFile => tr!("command-open-log-file"),
+            Command::Queue => tr!("command-queue"),
+            Command::ImportPackZip => tr!("command-import-pack-zip"),
+            Command::ImportPackFolder => tr!("command-import-pack-folder"),
+            Command::Settings => tr!("settings"),
+        }
+    }
+
+    fn shortcut(self) -> Option<egui::KeyboardShortcut> {
+        match self {
+            Command::OpenFile => Some(OPEN_FILE_SHORTCUT),
+            Command::FocusSearch => Some(SEARCH_SHORTCUT),
+            Command::Save => Some(SAVE_SHORTCUT),
+            Command::NewTab => Some(NEW_TAB_SHORTCUT),
+            Command::ToggleLog => Some(LOG_SHORTCUT),
+            _ => None,
+        }
+    }
+}
+
+/// The open palette.
+#[derive(Default)]
+pub struct CommandPalette {
+    query: String,
+    /// Highlighted row of the current matches
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Draw the palette over the window, offering the `available` commands.
+    /// Returns the command picked with Enter or a click; `open` goes false
+    /// when one is picked or the palette is dismissed.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        available: &[Command],
+        open: &mut bool,
+    ) -> Option<Command> {
+        let matches = self.matches(available);
+        let (down, up, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+        if down {
+            self.selected = (self.selected + 1).min(matches.len().saturating_sub(1));
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        let mut picked = matches.get(self.selected).copied().filter(|_| enter);
+
+        let modal = egui::Modal::new(egui::Id::new("command_palette")).show(ctx, |ui| {
+            ui.set_width(420.0);
+            let query = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .desired_width(f32::INFINITY)
+                    .hint_text(tr!("command-palette-hint")),
+            );
+            query.request_focus();
+            if query.changed() {
+                self.selected = 0;
+            }
+            ui.separator();
+            if matches.is_empty() {
+                ui.weak(tr!("command-palette-empty"));
+            }
+            for (i, command) in matches.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(i == self.selected, command.label())
+                        .clicked()
+                    {
+                        picked = Some(*command);
+                    }
+                    if let Some(shortcut) = command.shortcut() {
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.weak(ui.ctx().format_shortcut(&shortcut));
+                        });
+                    }
+                });
+            }
+        });
+        if picked.is_some() || modal.should_close() {
+            *open = false;
+        }
+        picked
+    }
+
+    /// `available` commands matching the query, best first.
+    fn matches(&self, available: &[Command]) -> Vec<Command> {
+        let mut scored: Vec<(i32, Command)> = available
+            .iter()
+            .filter_map(|c| fuzzy_score(&self.query, &c.label()).map(|score| (score, *c)))
+            .collect();
+        // Stable, so equal scores keep the `available` order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, c)| c).collect()
+    }
+}
+
+/// How well `query` matches `text`, ignoring case and spaces in the query.
+/// `None` unless every query character appears in `text` in order.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let at = from + text[from..].iter().position(|c| *c == q)?;
+        score += 1;
+        if at > 0 && last == Some(at - 1) {
+            score += 2;
+        }
+        if at == 0 || !text[at - 1].is_alphanumeric() {
+            score += 3;
+        }
+        last = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
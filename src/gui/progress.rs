@@ -2,10 +2,23 @@
 //!
 //! Adapted from the `rusty-backup` pattern (`src/gui/progress.rs` there).
 //! Pair with an `Arc<Mutex<HashProgress>>` written from a worker thread.
+//!
+//! Downloads and exports report over a channel instead: [`ProgressFeed`]
+//! keeps the newest report for [`transfer_bar`] / [`export_bar`] to draw.
 
 use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
 
+use eframe::egui;
+
+use crate::export::ExportProgress;
+use crate::i18n::tr;
+use crate::net::Transfer;
+
+/// Width of the download and export bars.
+const BAR_WIDTH: f32 = 240.0;
+
 /// Rolling-window sampler. Caller `record`s the current byte counter each
 /// frame; the tracker exposes a smoothed rate and ETA.
 #[derive(Default)]
@@ -82,6 +95,82 @@ impl RateTracker {
     }
 }
 
+/// The newest report from a worker's progress channel. Workers send as
+/// often as they like; the UI `poll`s once a frame and draws `latest`.
+pub struct ProgressFeed<T> {
+    receiver: Receiver<T>,
+    latest: Option<T>,
+}
+
+impl<T> ProgressFeed<T> {
+    /// A feed and the sender to hand to the worker.
+    pub fn channel() -> (Sender<T>, Self) {
+        let (sender, receiver) = mpsc::channel();
+        let feed = Self {
+            receiver,
+            latest: None,
+        };
+        (sender, feed)
+    }
+
+    /// Take everything sent since the last poll, keeping the newest.
+    pub fn poll(&mut self) {
+        while let Ok(report) = self.receiver.try_recv() {
+            self.latest = Some(report);
+        }
+    }
+
+    pub fn latest(&self) -> Option<&T> {
+        self.latest.as_ref()
+    }
+}
+
+/// Bar for a download: bytes received out of the total, or a moving bar
+/// with just the bytes received when the server didn't send a size.
+pub fn transfer_bar(ui: &mut egui::Ui, transfer: Option<&Transfer>) {
+    let transfer = transfer.copied().unwrap_or_default();
+    ui.add(bar(transfer.fraction(), transfer_text(&transfer)));
+}
+
+/// Bar for an export: the download, then processing, then each file
+/// written.
+pub fn export_bar(ui: &mut egui::Ui, progress: Option<&ExportProgress>) {
+    let text = match progress {
+        None => tr!("converting"),
+        Some(ExportProgress::Downloading(transfer)) => transfer_text(transfer),
+        Some(ExportProgress::Processing) => tr!("export-processing"),
+        Some(ExportProgress::Writing { n, total }) => {
+            tr!("export-writing", done = *n, total = *total)
+        }
+    };
+    ui.add(bar(progress.and_then(ExportProgress::fraction), text));
+}
+
+fn transfer_text(transfer: &Transfer) -> String {
+    if transfer.done == 0 {
+        return tr!("download-connecting");
+    }
+    match transfer.total {
+        Some(total) => tr!(
+            "download-progress",
+            done = format_size(transfer.done),
+            total = format_size(total)
+        ),
+        None => tr!(
+            "download-progress-unknown",
+            done = format_size(transfer.done)
+        ),
+    }
+}
+
+/// A bar filled to `fraction`, or animated while there's none to show.
+fn bar(fraction: Option<f32>, text: String) -> egui::ProgressBar {
+    egui::ProgressBar::new(fraction.unwrap_or(0.0))
+        .desired_width(BAR_WIDTH)
+        .text(text)
+        .animate(fraction.is_none())
+}
+
 pub fn format_size(bytes: u64) -> String {
     const KIB: f64 = 1024.0;
     const MIB: f64 = KIB * 1024.0;
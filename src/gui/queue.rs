@@ -0,0 +1,514 @@
+//! Processing queue for discs dropped several at a time.
+//!
+//! Dropping more than one disc image lines them all up here instead of
+//! loading just one. Each disc then runs identify → search → auto-pick →
+//! export on a small pool of worker threads without any input, and the
+//! queue window shows where each one is. Failed discs can be retried.
+//!
+//! Unlike the bulk job in [`super::bulk`] there's no `fuzzy_scan` queue file:
+//! the pick is the best-scoring result ([`crate::search::pick`]), from the
+//! local cover packs if they have the title, else from the web search. A
+//! disc whose best result scores too low fails rather than get a poor cover.
+//!
+//! With [`ProcessingQueue::review`] on, discs stop after the search instead
+//! ([`Stage::Review`]) so each pick can be approved, swapped for another
+//! result or skipped; [`ProcessingQueue::commit_review`] then saves all the
+//! approved ones.
+//!
+//! A disc that already has a cover stops at [`Stage::Exists`] until it's
+//! replaced or kept, one at a time or for the rest of the batch
+//! ([`ProcessingQueue::existing`]). With [`ProcessingQueue::dry_run`] on,
+//! nothing is written: each disc ends in [`Stage::WouldSave`] instead.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::api::{ArtworkSearchQuery, SearchConfig};
+use crate::config::ExportConfig;
+use crate::disc::{DiscInfo, DiscReader};
+use crate::i18n::tr;
+use crate::export::{
+    claim_named_output_path, export_artwork_from_url, generate_named_output_path, ArtworkRole,
+    DiscSummary, ExportSettings, OutputNaming, Provenance,
+};
+use crate::net::CancelToken;
+use crate::search::{pick, ImageResult};
+
+/// Discs processed at once. Kept low so the image search isn't rate-limited.
+const MAX_WORKERS: usize = 2;
+
+/// Where a disc is in the pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stage {
+    Waiting,
+    Identifying,
+    Searching,
+    Exporting,
+    /// Searched, waiting for the pick to be approved or skipped
+    Review,
+    /// Picked, but the disc already has a cover at the carried path;
+    /// waiting to be told whether to replace it
+    Exists(String),
+    /// Saved; carries the output path
+    Done(String),
+    /// Dry run: where the pick would have been saved, and whether that
+    /// replaces a cover
+    WouldSave {
+        path: String,
+        replaces: bool,
+    },
+    /// Carries the error
+    Failed(String),
+    /// Skipped in review, or its existing cover kept
+    Skipped,
+}
+
+impl Stage {
+    pub fn label(&self) -> String {
+        match self {
+            Stage::Waiting => tr!("stage-waiting"),
+            Stage::Identifying => tr!("stage-identifying"),
+            Stage::Searching => tr!("stage-searching"),
+            Stage::Exporting => tr!("stage-exporting"),
+            Stage::Review => tr!("stage-review"),
+            Stage::Exists(_) => tr!("stage-exists"),
+            Stage::Done(_) => tr!("done"),
+            Stage::WouldSave { .. } => tr!("stage-would-save"),
+            Stage::Failed(_) => tr!("failed"),
+            Stage::Skipped => tr!("stage-skipped"),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            Stage::Done(_) | Stage::WouldSave { .. } | Stage::Failed(_) | Stage::Skipped
+        )
+    }
+
+    fn is_running(&self) -> bool {
+        matches!(
+            self,
+            Stage::Identifying | Stage::Searching | Stage::Exporting
+        )
+    }
+}
+
+/// One queued disc.
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub path: PathBuf,
+    /// Identified title, once known
+    pub title: Option<String>,
+    pub stage: Stage,
+    /// Search results kept for review. While set, a waiting entry only
+    /// exports the chosen result instead of running the whole pipeline.
+    pub review: Option<ReviewItem>,
+    /// Told to replace the disc's existing cover
+    replace: bool,
+}
+
+/// A searched disc's results and the call made on them in review.
+#[derive(Debug, Clone)]
+pub struct ReviewItem {
+    info: DiscInfo,
+    pub candidates: Vec<ImageResult>,
+    /// Index into `candidates` of the result to save
+    pub choice: usize,
+    /// Whether to save `choice`; otherwise the disc is skipped. Starts on
+    /// when the best result scores high enough for auto-pick.
+    pub approved: bool,
+}
+
+impl ReviewItem {
+    pub fn chosen(&self) -> &ImageResult {
+        &self.candidates[self.choice]
+    }
+}
+
+/// What to do with a disc that already has a cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Existing {
+    /// Stop at [`Stage::Exists`] and ask
+    #[default]
+    Ask,
+    Replace,
+    /// Skip the disc
+    Keep,
+}
+
+/// Settings snapshot a worker runs with.
+#[derive(Clone)]
+pub struct QueueJob {
+    pub export_config: ExportConfig,
+    pub search_config: SearchConfig,
+}
+
+/// Progress report from a worker, keyed by entry index.
+enum Update {
+    Title(usize, String),
+    Review(usize, Box<ReviewItem>),
+    Stage(usize, Stage),
+}
+
+/// Discs waiting for or going through automatic processing.
+pub struct ProcessingQueue {
+    pub entries: Vec<QueueEntry>,
+    /// Stop discs at [`Stage::Review`] instead of saving the pick.
+    pub review: bool,
+    /// Answer for discs that already have a cover, once one was given for
+    /// the whole batch. Back to asking when a new batch starts.
+    pub existing: Existing,
+    /// Report where each pick would be saved without writing anything.
+    pub dry_run: bool,
+    sender: Sender<Update>,
+    receiver: Receiver<Update>,
+    /// Shared by the running workers; replaced on `cancel`.
+    cancel: CancelToken,
+}
+
+impl Default for ProcessingQueue {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            entries: Vec::new(),
+            review: false,
+            existing: Existing::Ask,
+            dry_run: false,
+            sender,
+            receiver,
+            cancel: CancelToken::new(),
+        }
+    }
+}
+
+impl ProcessingQueue {
+    /// Queue `path` unless it's already queued and not finished. Returns
+    /// whether it was added.
+    pub fn add(&mut self, path: PathBuf) -> bool {
+        if self
+            .entries
+            .iter()
+            .any(|e| e.path == path && !e.stage.is_finished())
+        {
+            return false;
+        }
+        if !self.is_busy() && self.existing_count() == 0 {
+            self.existing = Existing::Ask;
+        }
+        self.entries.push(QueueEntry {
+            path,
+            title: None,
+            stage: Stage::Waiting,
+            review: None,
+            replace: false,
+        });
+        true
+    }
+
+    /// Put a failed entry back in line.
+    pub fn retry(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            if matches!(entry.stage, Stage::Failed(_)) {
+                entry.stage = Stage::Waiting;
+            }
+        }
+    }
+
+    pub fn retry_failed(&mut self) {
+        for index in 0..self.entries.len() {
+            self.retry(index);
+        }
+    }
+
+    /// Drop finished entries. Only while nothing is running, since workers
+    /// report by index.
+    pub fn clear_finished(&mut self) {
+        if !self.is_busy() {
+            self.entries.retain(|e| !e.stage.is_finished());
+        }
+    }
+
+    /// Stop the running workers and fail everything waiting or running.
+    /// Discs awaiting review keep their results.
+    pub fn cancel(&mut self) {
+        self.cancel.cancel();
+        self.cancel = CancelToken::new();
+        for entry in &mut self.entries {
+            if entry.stage == Stage::Waiting || entry.stage.is_running() {
+                entry.stage = Stage::Failed("Cancelled".to_string());
+            }
+        }
+    }
+
+    /// Discs waiting to be told whether to replace their cover.
+    pub fn existing_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.stage, Stage::Exists(_)))
+            .count()
+    }
+
+    /// Replace or keep the existing cover of entry `index`, or with `None`
+    /// of every disc waiting on it. Replaced discs go back in line to save
+    /// their pick; kept ones are skipped.
+    pub fn resolve_existing(&mut self, index: Option<usize>, replace: bool) {
+        for (i, entry) in self.entries.iter_mut().enumerate() {
+            if index.is_some_and(|index| index != i) || !matches!(entry.stage, Stage::Exists(_)) {
+                continue;
+            }
+            if replace {
+                entry.replace = true;
+                entry.stage = Stage::Waiting;
+            } else {
+                entry.stage = Stage::Skipped;
+            }
+        }
+    }
+
+    /// Discs awaiting review.
+    pub fn in_review(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.stage == Stage::Review)
+            .count()
+    }
+
+    /// End the review: approved discs go back in line to save their choice,
+    /// the rest are skipped. Returns `(approved, skipped)`.
+    pub fn commit_review(&mut self) -> (usize, usize) {
+        let (mut approved, mut skipped) = (0, 0);
+        for entry in &mut self.entries {
+            if entry.stage != Stage::Review {
+                continue;
+            }
+            if entry.review.as_ref().is_some_and(|r| r.approved) {
+                entry.stage = Stage::Waiting;
+                approved += 1;
+            } else {
+                entry.stage = Stage::Skipped;
+                skipped += 1;
+            }
+        }
+        (approved, skipped)
+    }
+
+    /// `(finished, total)`
+    pub fn progress(&self) -> (usize, usize) {
+        let finished = self
+            .entries
+            .iter()
+            .filter(|e| e.stage.is_finished())
+            .count();
+        (finished, self.entries.len())
+    }
+
+    /// Whether any entry is waiting or running.
+    pub fn is_busy(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.stage == Stage::Waiting || e.stage.is_running())
+    }
+
+    /// Apply worker updates and start waiting entries while there are free
+    /// workers. `job` is only called when one starts. Returns entries that
+    /// finished or stopped for review since the last call.
+    pub fn tick(&mut self, job: impl Fn() -> QueueJob) -> Vec<QueueEntry> {
+        let mut finished = Vec::new();
+        while let Ok(update) = self.receiver.try_recv() {
+            match update {
+                Update::Title(index, title) => {
+                    if let Some(entry) = self.entries.get_mut(index) {
+                        entry.title = Some(title);
+                    }
+                }
+                Update::Review(index, item) => {
+                    if let Some(entry) = self.entries.get_mut(index) {
+                        if entry.stage.is_running() {
+                            entry.review = Some(*item);
+                        }
+                    }
+                }
+                Update::Stage(index, stage) => {
+                    // Late reports from cancelled workers are dropped
+                    let Some(entry) = self.entries.get_mut(index) else {
+                        continue;
+                    };
+                    if !entry.stage.is_running() {
+                        continue;
+                    }
+                    entry.stage = stage;
+                    if entry.stage.is_finished()
+                        || matches!(entry.stage, Stage::Review | Stage::Exists(_))
+                    {
+                        finished.push(entry.clone());
+                    }
+                }
+            }
+        }
+
+        let running = self.entries.iter().filter(|e| e.stage.is_running()).count();
+        let waiting: Vec<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.stage == Stage::Waiting)
+            .map(|(i, _)| i)
+            .take(MAX_WORKERS.saturating_sub(running))
+            .collect();
+        for index in waiting {
+            let entry = &mut self.entries[index];
+            // Reviewed discs only need their choice saved
+            let reviewed = entry.review.clone();
+            entry.stage = if reviewed.is_some() {
+                Stage::Exporting
+            } else {
+                Stage::Identifying
+            };
+            let path = entry.path.clone();
+            let review = self.review;
+            let target = Target {
+                existing: if entry.replace {
+                    Existing::Replace
+                } else {
+                    self.existing
+                },
+                dry_run: self.dry_run,
+            };
+            let job = job();
+            let sender = self.sender.clone();
+            let cancel = self.cancel.clone();
+            thread::spawn(move || {
+                let report = |update: Update| {
+                    let _ = sender.send(update);
+                };
+                let result = match reviewed {
+                    Some(item) => export_pick(&path, &item.info, item.chosen(), &job, target),
+                    None => process_disc(index, &path, &job, review, target, &cancel, &report),
+                };
+                let stage = result.unwrap_or_else(Stage::Failed);
+                report(Update::Stage(index, stage));
+            });
+        }
+        finished
+    }
+}
+
+/// How a worker treats the output file.
+#[derive(Debug, Clone, Copy)]
+struct Target {
+    existing: Existing,
+    dry_run: bool,
+}
+
+/// Identify, search and pick one disc, then export the pick, or with
+/// `review` hand the results over and stop. Returns the stage to end in.
+fn process_disc(
+    index: usize,
+    path: &Path,
+    job: &QueueJob,
+    review: bool,
+    target: Target,
+    cancel: &CancelToken,
+    report: &dyn Fn(Update),
+) -> Result<Stage, String> {
+    let info = DiscReader::read(path).map_err(|e| format!("Failed to read disc: {}", e))?;
+    report(Update::Title(index, info.title.clone()));
+    cancel.check()?;
+
+    report(Update::Stage(index, Stage::Searching));
+    let query = ArtworkSearchQuery::from_disc_info_with_config(&info, &job.search_config);
+    let mut results = crate::packs::search_local(&query.title, 5);
+    if results.is_empty() {
+        results = crate::search::search_images_cancellable(
+            &query.build_query(),
+            20,
+            job.search_config.user_agent.as_deref(),
+            cancel,
+        )?;
+    }
+    if results.is_empty() {
+        return Err("No artwork found".to_string());
+    }
+    if review {
+        let (choice, score) = pick::best(&results, &info.title).unwrap_or_default();
+        report(Update::Review(
+            index,
+            Box::new(ReviewItem {
+                info,
+                candidates: results,
+                choice,
+                approved: score >= pick::MIN_AUTO_SCORE,
+            }),
+        ));
+        return Ok(Stage::Review);
+    }
+    let (best, _) = pick::auto_pick(&results, &info.title)
+        .ok_or_else(|| "No result scored high enough to pick".to_string())?;
+    cancel.check()?;
+
+    report(Update::Stage(index, Stage::Exporting));
+    let stage = export_pick(path, &info, &results[best], job, target)?;
+    if matches!(stage, Stage::Exists(_)) {
+        // Keep the pick so a Replace only has to save it
+        report(Update::Review(
+            index,
+            Box::new(ReviewItem {
+                info,
+                candidates: results,
+                choice: best,
+                approved: true,
+            }),
+        ));
+    }
+    Ok(stage)
+}
+
+/// Save `pick` as the front artwork of the disc at `path`, unless the
+/// disc's cover is there already and `target` doesn't say to replace it.
+/// Returns the stage to end in.
+fn export_pick(
+    path: &Path,
+    info: &DiscInfo,
+    pick: &ImageResult,
+    job: &QueueJob,
+    target: Target,
+) -> Result<Stage, String> {
+    let naming = OutputNaming::from_config(&job.export_config);
+    if target.dry_run {
+        // Not claimed, so nothing is written to a central folder's index
+        let path = generate_named_output_path(path, &naming, ArtworkRole::Front);
+        let replaces = Path::new(&path).exists();
+        return Ok(Stage::WouldSave { path, replaces });
+    }
+    let output = claim_named_output_path(path, &naming, ArtworkRole::Front)?;
+    if Path::new(&output).exists() {
+        match target.existing {
+            Existing::Ask => return Ok(Stage::Exists(output)),
+            Existing::Keep => return Ok(Stage::Skipped),
+            Existing::Replace => {}
+        }
+    }
+    let settings = ExportSettings {
+        provenance: Some(Provenance::new(
+            Some(info.title.clone()),
+            Some(pick.image_url.clone()),
+            None,
+        )),
+        disc: Some(DiscSummary {
+            path: path.display().to_string(),
+            title: info.title.clone(),
+            format: Some(info.format.display_name().to_string()),
+            filesystem: Some(info.filesystem.display_name().to_string()),
+            volume_label: info.volume_label.clone(),
+            ..DiscSummary::default()
+        }),
+        ..ExportSettings::from_config(&job.export_config)
+    };
+    let result = export_artwork_from_url(&pick.image_url, &output, &settings)?;
+    match result.upload_error() {
+        Some(e) => Err(format!("Saved {} but {}", result.output_path, e)),
+        None => Ok(Stage::Done(result.output_path)),
+    }
+}
@@ -0,0 +1,102 @@
+//! "Recent" menu: disc images and scanned folders opened lately, kept in
+//! `recent.json` in the config directory.
+//!
+//! Each disc keeps the query and results of its last search, so opening it
+//! from the menu shows those results again instead of searching afresh.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::search::ImageResult;
+
+/// Entries kept per list.
+pub const MAX_RECENT: usize = 10;
+
+/// A recently opened disc image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    /// Query of the last search that found something, if any.
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub results: Vec<ImageResult>,
+}
+
+/// Recently opened disc images and scanned folders, newest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentList {
+    #[serde(default)]
+    pub files: Vec<RecentFile>,
+    #[serde(default)]
+    pub folders: Vec<PathBuf>,
+}
+
+impl RecentList {
+    /// The saved list, or an empty one if there's none or it can't be read.
+    pub fn load() -> Self {
+        let Ok(path) = recent_file_path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable {}: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = recent_file_path()?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize recent list: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Move `path` to the top of the files, keeping its cached results.
+    pub fn add_file(&mut self, path: &Path) {
+        let entry = match self.files.iter().position(|f| f.path == path) {
+            Some(i) => self.files.remove(i),
+            None => RecentFile {
+                path: path.to_path_buf(),
+                query: String::new(),
+                results: Vec::new(),
+            },
+        };
+        self.files.insert(0, entry);
+        self.files.truncate(MAX_RECENT);
+    }
+
+    /// Move `path` to the top of the folders.
+    pub fn add_folder(&mut self, path: &Path) {
+        self.folders.retain(|f| f != path);
+        self.folders.insert(0, path.to_path_buf());
+        self.folders.truncate(MAX_RECENT);
+    }
+
+    /// Cache a search for `path`. Returns false if `path` isn't in the list.
+    pub fn set_results(&mut self, path: &Path, query: &str, results: &[ImageResult]) -> bool {
+        let Some(entry) = self.files.iter_mut().find(|f| f.path == path) else {
+            return false;
+        };
+        entry.query = query.to_string();
+        entry.results = results.to_vec();
+        true
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&RecentFile> {
+        self.files.iter().find(|f| f.path == path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.folders.is_empty()
+    }
+}
+
+fn recent_file_path() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("recent.json"))
+}
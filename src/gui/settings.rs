@@ -0,0 +1,918 @@
+//! Settings window: every `config.json` field in one place.
+//!
+//! Edits go into a draft; nothing changes until Save, which validates the
+//! whole draft (`AppConfig::validate`) and hands it back to the app to write
+//! and apply. Lists are edited one entry per line, and the per-site download
+//! rules as the same JSON they're stored as.
+
+use eframe::egui;
+
+use crate::api::{ContentType, SearchConfig};
+use crate::config::{
+    AppConfig, ArtTemplate, BadgePosition, BadgeStyle, FitMode, OutputFormat, PadFill, SiteRule,
+    ThemeMode, UpdateChannel, UpscaleMode, LOG_LEVELS, MAX_UI_SCALE, MIN_UI_SCALE,
+};
+use crate::export::{
+    format_hex_color, parse_hex_color, DEFAULT_NAME_TEMPLATE, MAX_SHARPEN, MAX_TARGET_SIZE,
+    MIN_JPEG_QUALITY, MIN_TARGET_SIZE,
+};
+use crate::i18n::{self, tr, LANGUAGES, SYSTEM_LANGUAGE};
+use crate::profiles::{Profile, ProfileStore};
+
+use super::names;
+
+/// Draft settings being edited.
+pub struct SettingsDialog {
+    config: AppConfig,
+    content_type: ContentType,
+    exclusion_sites: String,
+    exclusion_platforms: String,
+    cd_keywords: String,
+    dvd_keywords: String,
+    known_publishers: String,
+    games_sites: String,
+    apps_sites: String,
+    audio_sites: String,
+    user_agent: String,
+    auto_pick: bool,
+    extra_sizes: String,
+    output_dir: String,
+    remote_target: String,
+    retroarch_thumbnails: String,
+    max_file_size_kb: u32,
+    site_rules: String,
+    accent_color: String,
+    watch_folders: String,
+    /// Saved profile names
+    profiles: Vec<String>,
+    /// Profile to load, save, delete or export
+    profile_name: String,
+    /// What the last profile action did
+    notice: Option<String>,
+    /// Why the last Save was refused
+    pub error: Option<String>,
+}
+
+/// A button clicked under Profiles.
+#[derive(Clone, Copy)]
+enum ProfileAction {
+    Load,
+    Save,
+    Delete,
+    Import,
+    Export,
+}
+
+impl SettingsDialog {
+    pub fn new(config: AppConfig, search: &SearchConfig) -> Self {
+        Self {
+            content_type: search.content_type,
+            exclusion_sites: to_lines(&search.exclusion_sites),
+            exclusion_platforms: to_lines(&search.exclusion_platforms),
+            cd_keywords: to_lines(&search.cd_keywords),
+            dvd_keywords: to_lines(&search.dvd_keywords),
+            known_publishers: to_lines(&search.known_publishers),
+            games_sites: to_lines(&search.games_sites),
+            apps_sites: to_lines(&search.apps_sites),
+            audio_sites: to_lines(&search.audio_sites),
+            user_agent: search.user_agent.clone().unwrap_or_default(),
+            auto_pick: search.auto_pick,
+            extra_sizes: config
+                .export
+                .extra_sizes
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            output_dir: config.export.output_dir.clone().unwrap_or_default(),
+            remote_target: config.export.remote_target.clone().unwrap_or_default(),
+            retroarch_thumbnails: config
+                .export
+                .retroarch_thumbnails
+                .clone()
+                .unwrap_or_default(),
+            max_file_size_kb: config.export.max_file_size_kb.unwrap_or(0),
+            site_rules: serde_json::to_string_pretty(&config.network.site_rules)
+                .unwrap_or_else(|_| "[]".to_string()),
+            accent_color: config.appearance.accent_color.clone().unwrap_or_default(),
+            watch_folders: to_lines(&config.watch.folders),
+            profiles: ProfileStore::open()
+                .map(|store| store.names())
+                .unwrap_or_default(),
+            profile_name: String::new(),
+            notice: None,
+            config,
+            error: None,
+        }
+    }
+
+    /// Draw the window. Returns the checked settings when Save is clicked;
+    /// `open` goes false on Cancel or close.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+    ) -> Option<(AppConfig, SearchConfig)> {
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(tr!("settings"))
+            .open(open)
+            .default_size([560.0, 520.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(ctx.content_rect().height() * 0.7)
+                    .show(ui, |ui| {
+                        self.profiles_section(ui);
+                        self.search_section(ui);
+                        self.export_section(ui);
+                        self.network_section(ui);
+                        self.fuzzy_section(ui);
+                        self.appearance_section(ui);
+                        self.watch_section(ui);
+                        self.usbode_section(ui);
+                        self.general_section(ui);
+                    });
+                ui.separator();
+                if let Some(e) = &self.error {
+                    ui.colored_label(egui::Color32::RED, e);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("save")).clicked() {
+                        save = true;
+                    }
+                    if ui.button(tr!("cancel")).clicked() {
+                        cancel = true;
+                    }
+                    ui.weak(tr!("saved-to-config-json-and-applied"));
+                });
+            });
+        if cancel {
+            *open = false;
+        }
+        if !save {
+            return None;
+        }
+        match self.collect() {
+            Ok(settings) => Some(settings),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// The draft as configs, checked.
+    fn collect(&self) -> Result<(AppConfig, SearchConfig), String> {
+        let config = self.draft()?;
+        config.validate()?;
+        let search = SearchConfig::from_settings(&config.search);
+        Ok((config, search))
+    }
+
+    /// The draft as a config, with the search settings in it. Only the
+    /// fields edited as text are checked.
+    fn draft(&self) -> Result<AppConfig, String> {
+        let mut config = self.config.clone();
+        config.export.extra_sizes = self
+            .extra_sizes
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.trim_end_matches("px")
+                    .parse::<u32>()
+                    .map_err(|_| format!("\"{s}\" isn't a size in pixels"))
+            })
+            .collect::<Result<_, _>>()?;
+        config.export.output_dir =
+            Some(self.output_dir.trim().to_string()).filter(|d| !d.is_empty());
+        config.export.remote_target =
+            Some(self.remote_target.trim().to_string()).filter(|u| !u.is_empty());
+        config.export.retroarch_thumbnails =
+            Some(self.retroarch_thumbnails.trim().to_string()).filter(|d| !d.is_empty());
+        config.export.max_file_size_kb =
+            (self.max_file_size_kb > 0).then_some(self.max_file_size_kb);
+        config.network.site_rules = serde_json::from_str::<Vec<SiteRule>>(&self.site_rules)
+            .map_err(|e| format!("Site rules: {e}"))?;
+        config.appearance.accent_color =
+            Some(self.accent_color.trim().to_string()).filter(|c| !c.is_empty());
+        config.watch.folders = from_lines(&self.watch_folders);
+
+        let search = SearchConfig {
+            exclusion_sites: from_lines(&self.exclusion_sites),
+            exclusion_platforms: from_lines(&self.exclusion_platforms),
+            cd_keywords: from_lines(&self.cd_keywords),
+            dvd_keywords: from_lines(&self.dvd_keywords),
+            known_publishers: from_lines(&self.known_publishers),
+            content_type: self.content_type,
+            games_sites: from_lines(&self.games_sites),
+            apps_sites: from_lines(&self.apps_sites),
+            audio_sites: from_lines(&self.audio_sites),
+            user_agent: Some(self.user_agent.trim().to_string()).filter(|ua| !ua.is_empty()),
+            auto_pick: self.auto_pick,
+        };
+        config.search = search.to_settings();
+        Ok(config)
+    }
+
+    fn profiles_section(&mut self, ui: &mut egui::Ui) {
+        let mut action = None;
+        egui::CollapsingHeader::new(tr!("profiles")).show(ui, |ui| {
+            ui.weak(tr!("profiles-tip"));
+            ui.horizontal(|ui| {
+                let label = ui.label(tr!("profile-name"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.profile_name)
+                        .desired_width(200.0)
+                        .hint_text(tr!("profile-name-hint")),
+                )
+                .labelled_by(label.id);
+                ui.add_enabled_ui(!self.profiles.is_empty(), |ui| {
+                    egui::ComboBox::new("settings_profiles", "")
+                        .selected_text(tr!("saved-profiles"))
+                        .show_ui(ui, |ui| {
+                            for name in &self.profiles {
+                                if ui
+                                    .selectable_label(*name == self.profile_name, name)
+                                    .clicked()
+                                {
+                                    self.profile_name = name.clone();
+                                }
+                            }
+                        });
+                });
+            });
+            let saved = self
+                .profiles
+                .contains(&self.profile_name.trim().to_string());
+            let named = !self.profile_name.trim().is_empty();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(saved, egui::Button::new(tr!("profile-load")))
+                    .clicked()
+                {
+                    action = Some(ProfileAction::Load);
+                }
+                if ui
+                    .add_enabled(named, egui::Button::new(tr!("profile-save")))
+                    .clicked()
+                {
+                    action = Some(ProfileAction::Save);
+                }
+                if ui
+                    .add_enabled(saved, egui::Button::new(tr!("profile-delete")))
+                    .clicked()
+                {
+                    action = Some(ProfileAction::Delete);
+                }
+                if ui.button(tr!("profile-import")).clicked() {
+                    action = Some(ProfileAction::Import);
+                }
+                if ui
+                    .add_enabled(saved, egui::Button::new(tr!("profile-export")))
+                    .clicked()
+                {
+                    action = Some(ProfileAction::Export);
+                }
+            });
+            if let Some(notice) = &self.notice {
+                ui.weak(notice);
+            }
+        });
+        if let Some(action) = action {
+            match self.run_profile_action(action) {
+                Ok(Some(notice)) => {
+                    self.notice = Some(notice);
+                    self.error = None;
+                }
+                Ok(None) => {}
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    /// Do what a Profiles button asks. Returns what to tell the user, or
+    /// `None` when a file dialog was cancelled. A loaded profile replaces
+    /// the draft but, like any edit, is only kept on Save.
+    fn run_profile_action(&mut self, action: ProfileAction) -> Result<Option<String>, String> {
+        let store = ProfileStore::open()?;
+        let name = self.profile_name.trim().to_string();
+        let notice = match action {
+            ProfileAction::Load => {
+                let config = store.load(&name)?.apply_to(&self.draft()?);
+                let search = SearchConfig::from_settings(&config.search);
+                *self = Self::new(config, &search);
+                self.profile_name = name.clone();
+                tr!("profile-loaded", name = name)
+            }
+            ProfileAction::Save => {
+                store.save(&Profile::new(&name, &self.draft()?))?;
+                tr!("profile-saved", name = name)
+            }
+            ProfileAction::Delete => {
+                store.delete(&name)?;
+                self.profile_name.clear();
+                tr!("profile-deleted", name = name)
+            }
+            ProfileAction::Import => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter(tr!("profile-files"), &["json", "py"])
+                    .pick_file()
+                else {
+                    return Ok(None);
+                };
+                let imported = store.import(&path)?;
+                self.profile_name = imported.clone();
+                tr!("profile-imported", name = imported)
+            }
+            ProfileAction::Export => {
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(format!("{name}.json"))
+                    .save_file()
+                else {
+                    return Ok(None);
+                };
+                store.export(&name, &path)?;
+                tr!("profile-exported", path = path.display().to_string())
+            }
+        };
+        self.profiles = store.names();
+        Ok(Some(notice))
+    }
+
+    fn search_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr!("search-button"))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("settings_search")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr!("settings-content-type"));
+                        egui::ComboBox::new("settings_content_type", "")
+                            .selected_text(names::content_type(self.content_type))
+                            .show_ui(ui, |ui| {
+                                for content_type in [
+                                    ContentType::Any,
+                                    ContentType::Games,
+                                    ContentType::AppsUtilities,
+                                    ContentType::AudioCDs,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut self.content_type,
+                                        content_type,
+                                        names::content_type(content_type),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                        let label = ui.label(tr!("user-agent"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.user_agent)
+                                .desired_width(320.0)
+                                .hint_text(tr!("built-in-browser-identity")),
+                        )
+                        .labelled_by(label.id);
+                        ui.end_row();
+                        let label = ui.label(tr!("settings-auto-pick"));
+                        ui.checkbox(&mut self.auto_pick, "")
+                            .labelled_by(label.id)
+                            .on_hover_text(tr!("auto-pick-tip"));
+                        ui.end_row();
+                        list_row(ui, tr!("excluded-sites"), &mut self.exclusion_sites);
+                        list_row(ui, tr!("excluded-platforms"), &mut self.exclusion_platforms);
+                        list_row(ui, tr!("cd-keywords"), &mut self.cd_keywords);
+                        list_row(ui, tr!("dvd-keywords"), &mut self.dvd_keywords);
+                        list_row(ui, tr!("known-publishers"), &mut self.known_publishers);
+                        list_row(ui, tr!("game-sites"), &mut self.games_sites);
+                        list_row(ui, tr!("app-sites"), &mut self.apps_sites);
+                        list_row(ui, tr!("audio-cd-sites"), &mut self.audio_sites);
+                    });
+            });
+    }
+
+    fn export_section(&mut self, ui: &mut egui::Ui) {
+        let export = &mut self.config.export;
+        egui::CollapsingHeader::new(tr!("settings-export")).show(ui, |ui| {
+            egui::Grid::new("settings_export")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let label = ui.label(tr!("settings-output-size"));
+                    ui.add(
+                        egui::DragValue::new(&mut export.target_size)
+                            .range(MIN_TARGET_SIZE..=MAX_TARGET_SIZE)
+                            .suffix(" px"),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    let label = ui.label(tr!("also-save-sizes"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.extra_sizes)
+                            .hint_text(tr!("also-save-sizes-hint")),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("format"));
+                    combo(
+                        ui,
+                        "settings_format",
+                        &mut export.format,
+                        &[OutputFormat::Jpeg, OutputFormat::Png, OutputFormat::Bmp],
+                        |f| f.display_name().to_string(),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("jpeg-quality"));
+                    ui.add(egui::Slider::new(
+                        &mut export.quality,
+                        MIN_JPEG_QUALITY..=100,
+                    ));
+                    ui.end_row();
+                    let label = ui.label(tr!("max-file-size"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.max_file_size_kb)
+                            .range(0..=4096)
+                            .suffix(" KB"),
+                    )
+                    .labelled_by(label.id)
+                    .on_hover_text(tr!("max-file-size-tip"));
+                    ui.end_row();
+                    let label = ui.label(tr!("file-name"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut export.name_template)
+                            .hint_text(DEFAULT_NAME_TEMPLATE),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("artwork-folder"));
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.output_dir)
+                                .hint_text(tr!("settings-next-to-each-disc-image")),
+                        );
+                        if ui.small_button(tr!("browse")).clicked() {
+                            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                self.output_dir = dir.display().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+                    let label = ui.label(tr!("remote-target"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.remote_target)
+                            .desired_width(320.0)
+                            .hint_text("smb://nas/images"),
+                    )
+                    .labelled_by(label.id)
+                    .on_hover_text(tr!("remote-target-tip"));
+                    ui.end_row();
+                    ui.label(tr!("retroarch-thumbnails"))
+                        .on_hover_text(tr!("retroarch-thumbnails-tip"));
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.retroarch_thumbnails)
+                                .hint_text(tr!("none")),
+                        );
+                        if ui.small_button(tr!("browse")).clicked() {
+                            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                self.retroarch_thumbnails = dir.display().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+                    let label = ui.label(tr!("retroarch-playlist"));
+                    ui.add_enabled(
+                        !self.retroarch_thumbnails.trim().is_empty(),
+                        egui::TextEdit::singleline(&mut export.retroarch_system)
+                            .hint_text("Sony - PlayStation"),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("fit"));
+                    combo(
+                        ui,
+                        "settings_fit",
+                        &mut export.fit,
+                        &[FitMode::Crop, FitMode::Pad],
+                        |f| names::fit(*f),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("pad-with"));
+                    ui.horizontal(|ui| {
+                        combo(
+                            ui,
+                            "settings_pad_fill",
+                            &mut export.pad_fill,
+                            &[PadFill::Color, PadFill::Blur],
+                            |f| names::pad_fill(*f),
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(&mut export.pad_color).desired_width(80.0),
+                        );
+                    });
+                    ui.end_row();
+                    let label = ui.label(tr!("sharpen"));
+                    ui.add(
+                        egui::DragValue::new(&mut export.sharpen)
+                            .range(0..=MAX_SHARPEN)
+                            .suffix("%"),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("upscale"));
+                    combo(
+                        ui,
+                        "settings_upscale",
+                        &mut export.upscale,
+                        &[UpscaleMode::Lanczos, UpscaleMode::Scale2x],
+                        |m| names::upscale(*m),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("style"));
+                    combo(
+                        ui,
+                        "settings_template",
+                        &mut export.template,
+                        &[
+                            ArtTemplate::None,
+                            ArtTemplate::CdLabel,
+                            ArtTemplate::JewelCase,
+                        ],
+                        |t| names::template(*t),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("settings-disc-badge"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut export.disc_badge, "");
+                        combo(
+                            ui,
+                            "settings_badge_position",
+                            &mut export.badge_position,
+                            &[
+                                BadgePosition::TopLeft,
+                                BadgePosition::TopRight,
+                                BadgePosition::BottomLeft,
+                                BadgePosition::BottomRight,
+                            ],
+                            |p| names::badge_position(*p),
+                        );
+                        combo(
+                            ui,
+                            "settings_badge_style",
+                            &mut export.badge_style,
+                            &[BadgeStyle::Pill, BadgeStyle::Circle],
+                            |s| names::badge_style(*s),
+                        );
+                    });
+                    ui.end_row();
+                });
+            ui.checkbox(&mut export.smart_crop, tr!("smart-crop"));
+            ui.checkbox(
+                &mut export.backup_existing,
+                tr!("back-up-overwritten-covers"),
+            )
+            .on_hover_text(tr!("back-up-overwritten-covers-tip"));
+            ui.checkbox(&mut export.write_sidecar, tr!("write-metadata-sidecar"));
+            ui.checkbox(&mut export.embed_in_chd, tr!("store-cover-inside-chd-images"));
+            ui.checkbox(&mut export.copy_to_sd_card, tr!("copy-to-sd-card"))
+                .on_hover_text(tr!("copy-to-sd-card-tip"));
+        });
+    }
+
+    fn network_section(&mut self, ui: &mut egui::Ui) {
+        let network = &mut self.config.network;
+        egui::CollapsingHeader::new(tr!("network")).show(ui, |ui| {
+            egui::Grid::new("settings_network").num_columns(2).show(ui, |ui| {
+                ui.label(tr!("connect-timeout"));
+                ui.add(egui::DragValue::new(&mut network.connect_timeout_secs).range(1..=600).suffix(" s"));
+                ui.end_row();
+                ui.label(tr!("request-timeout"));
+                ui.add(egui::DragValue::new(&mut network.request_timeout_secs).range(1..=600).suffix(" s"));
+                ui.end_row();
+                ui.label(tr!("download-timeout"));
+                ui.add(egui::DragValue::new(&mut network.download_timeout_secs).range(1..=3600).suffix(" s"));
+                ui.end_row();
+                ui.label(tr!("retries"));
+                ui.add(egui::DragValue::new(&mut network.retries).range(0..=10));
+                ui.end_row();
+                ui.label(tr!("retry-backoff"));
+                ui.add(egui::DragValue::new(&mut network.retry_backoff_ms).range(0..=60_000).suffix(" ms"));
+                ui.end_row();
+                ui.label(tr!("parallel-downloads"));
+                ui.add(egui::DragValue::new(&mut network.max_concurrent_downloads).range(1..=32));
+                ui.end_row();
+            });
+            ui.label(tr!("site-rules-json"))
+                .on_hover_text(r#"[{"domain": "example.com", "headers": {"Referer": "https://example.com/"}, "cookie": null}]"#);
+            ui.add(
+                egui::TextEdit::multiline(&mut self.site_rules)
+                    .code_editor()
+                    .desired_rows(4)
+                    .desired_width(f32::INFINITY),
+            );
+        });
+    }
+
+    fn fuzzy_section(&mut self, ui: &mut egui::Ui) {
+        let fuzzy = &mut self.config.fuzzy_match;
+        egui::CollapsingHeader::new(tr!("fuzzy-redump-matching")).show(ui, |ui| {
+            egui::Grid::new("settings_fuzzy")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (label, value) in [
+                        (tr!("fuzzy-source-threshold"), &mut fuzzy.source_threshold),
+                        (tr!("fuzzy-merged-floor"), &mut fuzzy.merged_floor),
+                        (tr!("fuzzy-strong-score"), &mut fuzzy.strong_score),
+                        (tr!("fuzzy-agreement-bonus"), &mut fuzzy.agreement_bonus),
+                        (tr!("fuzzy-size-ok-ratio"), &mut fuzzy.size_ok_ratio),
+                        (tr!("fuzzy-size-drop-ratio"), &mut fuzzy.size_drop_ratio),
+                        (tr!("fuzzy-size-penalty"), &mut fuzzy.size_penalty),
+                    ] {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(value).range(0.0..=1.0).speed(0.01));
+                        ui.end_row();
+                    }
+                    ui.label(tr!("candidate-cap"));
+                    ui.add(egui::DragValue::new(&mut fuzzy.candidate_cap).range(1..=200));
+                    ui.end_row();
+                    let label = ui.label(tr!("date-window"));
+                    ui.add(
+                        egui::DragValue::new(&mut fuzzy.date_window_days)
+                            .range(0..=3650)
+                            .suffix(format!(" {}", tr!("unit-days"))),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    let label = ui.label(tr!("track-tolerance"));
+                    ui.add(
+                        egui::DragValue::new(&mut fuzzy.track_frame_tolerance)
+                            .range(0..=7500)
+                            .suffix(format!(" {}", tr!("unit-frames"))),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("min-tracks-for-signature"));
+                    ui.add(egui::DragValue::new(&mut fuzzy.min_tracks_for_signature).range(1..=99));
+                    ui.end_row();
+                    ui.label(tr!("min-strong-for-verify"));
+                    ui.add(egui::DragValue::new(&mut fuzzy.min_strong_for_verify).range(0..=20));
+                    ui.end_row();
+                });
+        });
+    }
+
+    fn appearance_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr!("appearance")).show(ui, |ui| {
+            egui::Grid::new("settings_appearance")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("theme"));
+                    combo(
+                        ui,
+                        "settings_theme",
+                        &mut self.config.appearance.theme,
+                        &ThemeMode::ALL,
+                        |t| names::theme(*t),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("language"));
+                    egui::ComboBox::new("settings_language", "")
+                        .selected_text(i18n::language_name(&self.config.language))
+                        .show_ui(ui, |ui| {
+                            let codes = LANGUAGES.iter().map(|(code, _)| *code);
+                            for code in std::iter::once(SYSTEM_LANGUAGE).chain(codes) {
+                                ui.selectable_value(
+                                    &mut self.config.language,
+                                    code.to_string(),
+                                    i18n::language_name(code),
+                                );
+                            }
+                        });
+                    ui.end_row();
+                    ui.label(tr!("accent-color"));
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.accent_color)
+                                .desired_width(80.0)
+                                .hint_text(tr!("default")),
+                        );
+                        let mut rgb = match parse_hex_color(&self.accent_color) {
+                            Some(c) => c.0,
+                            None => {
+                                let c = ui.visuals().selection.bg_fill;
+                                [c.r(), c.g(), c.b()]
+                            }
+                        };
+                        if ui.color_edit_button_srgb(&mut rgb).changed() {
+                            self.accent_color = format_hex_color(image::Rgb(rgb));
+                        }
+                        if ui.button(tr!("default")).clicked() {
+                            self.accent_color.clear();
+                        }
+                    });
+                    ui.end_row();
+                    let label = ui.label(tr!("ui-scale"));
+                    ui.horizontal(|ui| {
+                        let appearance = &mut self.config.appearance;
+                        let mut scale = appearance.ui_scale.unwrap_or(1.0);
+                        let slider = ui
+                            .add(
+                                egui::Slider::new(&mut scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+                                    .step_by(0.05)
+                                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                            )
+                            .labelled_by(label.id)
+                            .on_hover_text(tr!("ui-scale-tip"));
+                        if slider.changed() {
+                            appearance.ui_scale = Some(scale);
+                        }
+                        if ui.button(tr!("default")).clicked() {
+                            appearance.ui_scale = None;
+                        }
+                    });
+                    ui.end_row();
+                });
+        });
+    }
+
+    fn watch_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr!("watch-folders")).show(ui, |ui| {
+            ui.weak(tr!("watch-folders-tip"));
+            egui::Grid::new("settings_watch")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    list_row(ui, tr!("folders"), &mut self.watch_folders);
+                    ui.label("");
+                    if ui.small_button(tr!("add-folder")).clicked() {
+                        if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                            if !self.watch_folders.trim().is_empty() {
+                                self.watch_folders.push('\n');
+                            }
+                            self.watch_folders.push_str(&dir.display().to_string());
+                        }
+                    }
+                    ui.end_row();
+                });
+        });
+    }
+
+    fn usbode_section(&mut self, ui: &mut egui::Ui) {
+        let usbode = &mut self.config.usbode;
+        egui::CollapsingHeader::new(tr!("usbode-device")).show(ui, |ui| {
+            ui.weak(tr!("usbode-device-tip"));
+            egui::Grid::new("settings_usbode")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let label = ui.label(tr!("usbode-address"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut usbode.address)
+                            .desired_width(320.0)
+                            .hint_text("usbode.local"),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                });
+        });
+    }
+
+    fn general_section(&mut self, ui: &mut egui::Ui) {
+        let config = &mut self.config;
+        egui::CollapsingHeader::new(tr!("updates-logging-and-discogs")).show(ui, |ui| {
+            egui::Grid::new("settings_general")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let label = ui.label(tr!("check-for-updates"));
+                    ui.checkbox(&mut config.update_check.enabled, "")
+                        .labelled_by(label.id);
+                    ui.end_row();
+                    let label = ui.label(tr!("repository"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut config.update_check.repository_url)
+                            .desired_width(320.0),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    ui.label(tr!("update-channel"))
+                        .on_hover_text(tr!("update-channel-tip"));
+                    combo(
+                        ui,
+                        "settings_update_channel",
+                        &mut config.update_check.channel,
+                        &UpdateChannel::ALL,
+                        |c| names::update_channel(*c),
+                    );
+                    ui.end_row();
+                    let label = ui.label(tr!("update-check-interval"));
+                    ui.add(
+                        egui::DragValue::new(&mut config.update_check.check_interval_hours)
+                            .range(0..=720)
+                            .suffix(" h"),
+                    )
+                    .labelled_by(label.id)
+                    .on_hover_text(tr!("update-check-interval-tip"));
+                    ui.end_row();
+                    ui.label(tr!("log-level"));
+                    egui::ComboBox::new("settings_log_level", "")
+                        .selected_text(&config.log_level)
+                        .show_ui(ui, |ui| {
+                            for level in LOG_LEVELS {
+                                ui.selectable_value(
+                                    &mut config.log_level,
+                                    level.to_string(),
+                                    level,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                    ui.label(tr!("log-file-level"));
+                    egui::ComboBox::new("settings_log_file_level", "")
+                        .selected_text(&config.log_file_level)
+                        .show_ui(ui, |ui| {
+                            for level in LOG_LEVELS {
+                                ui.selectable_value(
+                                    &mut config.log_file_level,
+                                    level.to_string(),
+                                    level,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                    let label = ui.label(tr!("scan-workers"));
+                    ui.add(egui::DragValue::new(&mut config.scan_workers).range(0..=64))
+                        .labelled_by(label.id)
+                        .on_hover_text(tr!("scan-workers-tip"));
+                    ui.end_row();
+                    let label = ui.label(tr!("chd-cache-mb"));
+                    ui.add(
+                        egui::DragValue::new(&mut config.chd_cache_mb)
+                            .range(0..=4096)
+                            .suffix(" MB"),
+                    )
+                    .labelled_by(label.id)
+                    .on_hover_text(tr!("chd-cache-mb-tip"));
+                    ui.end_row();
+                    let label = ui.label(tr!("discogs-request-token-url"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut config.discogs.request_token_url)
+                            .desired_width(320.0),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    let label = ui.label(tr!("discogs-authorize-url"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut config.discogs.authorize_url)
+                            .desired_width(320.0),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                    let label = ui.label(tr!("discogs-access-token-url"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut config.discogs.access_token_url)
+                            .desired_width(320.0),
+                    )
+                    .labelled_by(label.id);
+                    ui.end_row();
+                });
+        });
+    }
+}
+
+/// A label and a one-entry-per-line list editor, as a grid row.
+fn list_row(ui: &mut egui::Ui, label: String, text: &mut String) {
+    let label = ui.label(label);
+    ui.add(
+        egui::TextEdit::multiline(text)
+            .desired_rows(2)
+            .desired_width(320.0)
+            .hint_text(tr!("one-per-line")),
+    )
+    .labelled_by(label.id);
+    ui.end_row();
+}
+
+fn combo<T: Copy + PartialEq>(
+    ui: &mut egui::Ui,
+    id: &str,
+    value: &mut T,
+    options: &[T],
+    name: impl Fn(&T) -> String,
+) {
+    egui::ComboBox::new(id, "")
+        .selected_text(name(value))
+        .show_ui(ui, |ui| {
+            for option in options {
+                ui.selectable_value(value, *option, name(option));
+            }
+        });
+}
+
+fn to_lines(items: &[String]) -> String {
+    items.join("\n")
+}
+
+fn from_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -2,6 +2,8 @@
 
 use eframe::egui;
 
+use crate::i18n::tr;
+
 /// Text viewer widget
 pub struct TextView {
     /// Whether to wrap lines
@@ -23,7 +25,7 @@ impl TextView {
     /// Render the text view
     pub fn show(&mut self, ui: &mut egui::Ui, content: &str) {
         ui.horizontal(|ui| {
-            ui.checkbox(&mut self.wrap_lines, "Wrap lines");
+            ui.checkbox(&mut self.wrap_lines, tr!("wrap-lines"));
             ui.separator();
             ui.label(format!("{} characters", content.len()));
         });
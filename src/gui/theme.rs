@@ -0,0 +1,37 @@
+//! Theme, accent color and UI scale from the `appearance` config section.
+//!
+//! The theme maps straight onto egui's theme preference. The accent goes
+//! into both the dark and the light style, so switching theme (or the OS
+//! switching it under `System`) keeps it. The scale is egui's zoom factor,
+//! which multiplies the display's pixels per point.
+
+use eframe::egui;
+
+use crate::config::{AppearanceConfig, ThemeMode};
+use crate::export::parse_hex_color;
+
+/// Apply `appearance` to every style of `ctx`.
+pub fn apply(ctx: &egui::Context, appearance: &AppearanceConfig) {
+    ctx.set_theme(match appearance.theme {
+        ThemeMode::System => egui::ThemePreference::System,
+        ThemeMode::Dark => egui::ThemePreference::Dark,
+        ThemeMode::Light => egui::ThemePreference::Light,
+    });
+    let accent = accent_color(appearance);
+    ctx.all_styles_mut(|style| {
+        let defaults = if style.visuals.dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        style.visuals.selection.bg_fill = accent.unwrap_or(defaults.selection.bg_fill);
+        style.visuals.hyperlink_color = accent.unwrap_or(defaults.hyperlink_color);
+    });
+    ctx.set_zoom_factor(appearance.ui_scale.unwrap_or(1.0));
+}
+
+/// The configured accent, if it parses.
+pub fn accent_color(appearance: &AppearanceConfig) -> Option<egui::Color32> {
+    let [r, g, b] = parse_hex_color(appearance.accent_color.as_deref()?)?.0;
+    Some(egui::Color32::from_rgb(r, g, b))
+}
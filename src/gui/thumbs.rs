@@ -0,0 +1,204 @@
+//! Thumbnails of search results for the batch review window, fetched on
+//! background threads and cached by URL, in memory and as PNGs under the
+//! per-user cache directory so reviewing the same discs again doesn't
+//! download them again.
+//!
+//! Only rows on screen ask for their thumbnail, and at most
+//! [`MAX_FETCHES`] download at once, so a long review list fills in from
+//! what's visible. A fetch whose row scrolls away before it finishes is
+//! cancelled and starts over if the row comes back.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use eframe::egui;
+use sha2::{Digest, Sha256};
+
+use crate::net::{CancelToken, Timeout};
+
+/// Longest edge thumbnails are decoded to.
+const THUMB_EDGE: u32 = 192;
+
+/// Folder under `config::cache_dir` holding the thumbnails.
+const CACHE_DIR: &str = "thumbnails";
+
+/// Thumbnails downloading at once; the rest wait their turn.
+const MAX_FETCHES: usize = 4;
+
+enum Thumb {
+    /// Asked for, waiting for a free fetch
+    Queued,
+    Loading(CancelToken),
+    Ready(egui::TextureHandle),
+    Failed,
+}
+
+struct Slot {
+    thumb: Thumb,
+    /// Pass the thumbnail was last asked for in
+    wanted: u64,
+}
+
+type Fetched = (String, Result<egui::ColorImage, String>);
+
+/// Thumbnail textures keyed by URL.
+pub struct Thumbnails {
+    cache: HashMap<String, Slot>,
+    sender: Sender<Fetched>,
+    receiver: Receiver<Fetched>,
+    /// Pass the last [`Self::poll`] ran in
+    polled: Option<u64>,
+}
+
+impl Default for Thumbnails {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            cache: HashMap::new(),
+            sender,
+            receiver,
+            polled: None,
+        }
+    }
+}
+
+impl Thumbnails {
+    /// The thumbnail for `url` once it has loaded. Call it only for rows on
+    /// screen, every frame they are: the first call queues the fetch, and
+    /// one that isn't asked for again is cancelled. Failures stay `None`
+    /// until [`Self::clear`].
+    pub fn get(&mut self, ctx: &egui::Context, url: &str) -> Option<&egui::TextureHandle> {
+        let pass = ctx.cumulative_pass_nr();
+        if self.polled != Some(pass) {
+            self.polled = Some(pass);
+            self.poll(ctx, pass);
+        }
+        let loading = self
+            .cache
+            .values()
+            .filter(|slot| matches!(slot.thumb, Thumb::Loading(_)))
+            .count();
+        let slot = self.cache.entry(url.to_string()).or_insert(Slot {
+            thumb: Thumb::Queued,
+            wanted: pass,
+        });
+        slot.wanted = pass;
+        if matches!(slot.thumb, Thumb::Queued) && loading < MAX_FETCHES {
+            slot.thumb = Thumb::Loading(fetch(ctx, url, self.sender.clone()));
+        }
+        match &slot.thumb {
+            Thumb::Ready(texture) => Some(texture),
+            _ => None,
+        }
+    }
+
+    /// Whether `url` was fetched and couldn't be shown.
+    pub fn failed(&self, url: &str) -> bool {
+        matches!(
+            self.cache.get(url).map(|slot| &slot.thumb),
+            Some(Thumb::Failed)
+        )
+    }
+
+    /// Drop every thumbnail, e.g. once the review is over, cancelling the
+    /// fetches still running.
+    pub fn clear(&mut self) {
+        for slot in self.cache.values() {
+            if let Thumb::Loading(cancel) = &slot.thumb {
+                cancel.cancel();
+            }
+        }
+        self.cache.clear();
+    }
+
+    /// Take in finished fetches, and give up on the ones no row asked for
+    /// in the previous pass (scrolled away or closed).
+    fn poll(&mut self, ctx: &egui::Context, pass: u64) {
+        self.cache.retain(|_, slot| {
+            let stale = slot.wanted + 1 < pass;
+            match &slot.thumb {
+                Thumb::Queued => !stale,
+                Thumb::Loading(cancel) if stale => {
+                    cancel.cancel();
+                    false
+                }
+                _ => true,
+            }
+        });
+        while let Ok((url, result)) = self.receiver.try_recv() {
+            // A cancelled fetch may end after its row came back and fetched
+            // again; that newer fetch answers for the row
+            if matches!(&result, Err(e) if e == crate::net::CANCELLED) {
+                continue;
+            }
+            // Fetches that finish after a clear aren't wanted any more
+            let Some(Slot {
+                thumb: thumb @ Thumb::Loading(_),
+                ..
+            }) = self.cache.get_mut(&url)
+            else {
+                continue;
+            };
+            *thumb = match result {
+                Ok(image) => Thumb::Ready(ctx.load_texture(
+                    format!("thumb:{url}"),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                )),
+                Err(e) => {
+                    log::debug!("Thumbnail {url} failed: {e}");
+                    Thumb::Failed
+                }
+            };
+        }
+    }
+}
+
+/// Load `url` on a new thread, sending it to `sender` when done. The token
+/// cancels the download.
+fn fetch(ctx: &egui::Context, url: &str, sender: Sender<Fetched>) -> CancelToken {
+    let cancel = CancelToken::new();
+    let token = cancel.clone();
+    let url = url.to_string();
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let result = load_thumbnail(&url, &token).map(|image| {
+            let size = [image.width() as usize, image.height() as usize];
+            egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice())
+        });
+        let _ = sender.send((url, result));
+        ctx.request_repaint();
+    });
+    cancel
+}
+
+/// Where the thumbnail of `url` is cached on disk, if there's a cache
+/// directory.
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let dir = crate::config::cache_dir().ok()?.join(CACHE_DIR);
+    std::fs::create_dir_all(&dir).ok()?;
+    let name = hex::encode(Sha256::digest(url.as_bytes()));
+    Some(dir.join(format!("{name}.png")))
+}
+
+/// The thumbnail of `url` from the disk cache, or downloaded and added to
+/// it unless `cancel` fires first.
+fn load_thumbnail(url: &str, cancel: &CancelToken) -> Result<image::RgbaImage, String> {
+    let cached = cache_path(url);
+    if let Some(image) = cached.as_ref().and_then(|path| image::open(path).ok()) {
+        return Ok(image.to_rgba8());
+    }
+    let bytes = crate::net::fetch_bytes_cancellable(url, Timeout::Request, cancel)?;
+    let image = image::load_from_memory(&bytes)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .thumbnail(THUMB_EDGE, THUMB_EDGE)
+        .to_rgba8();
+    if let Some(path) = cached {
+        if let Err(e) = image.save(&path) {
+            log::debug!("Couldn't cache thumbnail {}: {e}", path.display());
+        }
+    }
+    Ok(image)
+}
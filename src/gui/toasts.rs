@@ -0,0 +1,115 @@
+//! Error toasts: failed searches, previews and saves pop up in the bottom
+//! right corner for a few seconds, so they're noticed with the Log window
+//! closed. They're logged as usual too.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+use super::app::icon_button;
+
+/// How long a toast stays up. Hovering one keeps it.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// Toasts on screen at once; older ones go first.
+const MAX_TOASTS: usize = 4;
+
+const TOAST_WIDTH: f32 = 320.0;
+
+struct Toast {
+    title: String,
+    detail: String,
+    shown_at: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Pop up `title` with `detail` (the error) under it. The same toast
+    /// again only restarts its timer.
+    pub fn error(&mut self, title: String, detail: String) {
+        let now = Instant::now();
+        if let Some(toast) = self
+            .toasts
+            .iter_mut()
+            .find(|t| t.title == title && t.detail == detail)
+        {
+            toast.shown_at = now;
+            return;
+        }
+        self.toasts.push_back(Toast {
+            title,
+            detail,
+            shown_at: now,
+        });
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Draw the toasts, dropping expired and dismissed ones. Returns true
+    /// when "Show log" was clicked on one.
+    pub fn show(&mut self, ctx: &egui::Context) -> bool {
+        let now = Instant::now();
+        self.toasts
+            .retain(|t| now.duration_since(t.shown_at) < TOAST_DURATION);
+        if self.toasts.is_empty() {
+            return false;
+        }
+
+        let mut dismissed = None;
+        let mut show_log = false;
+        let mut hovered = None;
+        egui::Area::new(egui::Id::new("error_toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                for (i, toast) in self.toasts.iter().enumerate() {
+                    let frame = egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::RED))
+                        .show(ui, |ui| {
+                            ui.set_width(TOAST_WIDTH);
+                            ui.horizontal(|ui| {
+                                ui.colored_label(egui::Color32::RED, &toast.title);
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if icon_button(ui, "×", tr!("close")).clicked() {
+                                            dismissed = Some(i);
+                                        }
+                                    },
+                                );
+                            });
+                            ui.add(egui::Label::new(&toast.detail).wrap());
+                            if ui.link(tr!("show-log")).clicked() {
+                                show_log = true;
+                                dismissed = Some(i);
+                            }
+                        });
+                    if frame.response.contains_pointer() {
+                        hovered = Some(i);
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+
+        if let Some(toast) = hovered.and_then(|i| self.toasts.get_mut(i)) {
+            toast.shown_at = now;
+        }
+        if let Some(i) = dismissed {
+            self.toasts.remove(i);
+        }
+        // Wake up again when the oldest one is due to go
+        if let Some(oldest) = self.toasts.iter().map(|t| t.shown_at).min() {
+            ctx.request_repaint_after(TOAST_DURATION.saturating_sub(now.duration_since(oldest)));
+        }
+        show_log
+    }
+}
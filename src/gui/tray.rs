@@ -0,0 +1,114 @@
+//! System tray icon for watch mode, and the desktop notification that sums
+//! up each batch it processed.
+//!
+//! The window minimizes while watching and the tray icon's menu brings it
+//! back or quits. Linux trays run on GTK, so there the icon lives on a thread
+//! of its own running GTK's main loop; GTK can only ever be started on one
+//! thread, so the icon stays until the app quits. Elsewhere it's made on the
+//! UI thread, whose event loop drives it.
+
+use std::sync::mpsc::{self, Receiver};
+
+use eframe::egui;
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{TrayIcon, TrayIconBuilder};
+
+use crate::i18n::tr;
+
+const APP_NAME: &str = "ODE Artwork Downloader";
+const SHOW_ID: &str = "show";
+const QUIT_ID: &str = "quit";
+
+/// Picked from the tray icon's menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    Show,
+    Quit,
+}
+
+pub struct Tray {
+    actions: Receiver<TrayAction>,
+    #[cfg(not(target_os = "linux"))]
+    _icon: TrayIcon,
+}
+
+impl Tray {
+    /// Put the icon in the tray. `ctx` is woken when its menu is used.
+    pub fn new(ctx: &egui::Context) -> Result<Self, String> {
+        let (tx, actions) = mpsc::channel();
+        let ctx = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let action = match event.id.as_ref() {
+                SHOW_ID => TrayAction::Show,
+                QUIT_ID => TrayAction::Quit,
+                _ => return,
+            };
+            let _ = tx.send(action);
+            ctx.request_repaint();
+        }));
+
+        #[cfg(target_os = "linux")]
+        {
+            std::thread::spawn(|| {
+                if let Err(e) = gtk::init() {
+                    log::warn!("No tray icon, GTK didn't start: {}", e);
+                    return;
+                }
+                match build_icon() {
+                    Ok(_icon) => gtk::main(),
+                    Err(e) => log::warn!("{}", e),
+                }
+            });
+            Ok(Self { actions })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(Self {
+                actions,
+                _icon: build_icon()?,
+            })
+        }
+    }
+
+    /// The next menu pick, if any.
+    pub fn poll(&self) -> Option<TrayAction> {
+        self.actions.try_recv().ok()
+    }
+}
+
+fn build_icon() -> Result<TrayIcon, String> {
+    let menu = Menu::new();
+    menu.append_items(&[
+        &MenuItem::with_id(SHOW_ID, tr!("tray-show"), true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(QUIT_ID, tr!("tray-quit"), true, None),
+    ])
+    .map_err(|e| format!("Failed to build the tray menu: {}", e))?;
+    let rgba = image::load_from_memory(include_bytes!("../../assets/icons/icon-32.png"))
+        .map_err(|e| format!("Failed to load the tray icon: {}", e))?
+        .to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let icon = tray_icon::Icon::from_rgba(rgba.into_raw(), width, height)
+        .map_err(|e| format!("Failed to load the tray icon: {}", e))?;
+    TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip(APP_NAME)
+        .with_icon(icon)
+        .build()
+        .map_err(|e| format!("Failed to create the tray icon: {}", e))
+}
+
+/// Show a desktop notification. Sent from a thread since some platforms
+/// wait on the notification service.
+pub fn notify(summary: String, body: String) {
+    std::thread::spawn(move || {
+        let shown = notify_rust::Notification::new()
+            .appname(APP_NAME)
+            .summary(&summary)
+            .body(&body)
+            .show();
+        if let Err(e) = shown {
+            log::warn!("Failed to show notification: {}", e);
+        }
+    });
+}
@@ -1,14 +1,12 @@
-//! ODE Artwork Downloader Library
+//! ODE Artwork Downloader
 //!
-//! Core functionality for disc image identification and cover art downloading.
+//! The window, on top of `ode-artwork-core`. The core modules are re-exported
+//! so the GUI reaches them the same way it always has (`crate::config`,
+//! `crate::disc`, ...).
+
+pub use ode_artwork_core::{
+    api, batch, config, crypto, db, disc, export, i18n, logging, net, packs, pool, profiles,
+    search, update, usbode, watch,
+};
 
-pub mod api;
-pub mod config;
-pub mod crypto;
-pub mod db;
-pub mod disc;
-pub mod export;
 pub mod gui;
-pub mod logging;
-pub mod search;
-pub mod update;
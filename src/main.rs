@@ -8,14 +8,15 @@
 
 use eframe::egui;
 
-use ode_artwork_downloader::{gui, logging};
+use ode_artwork_gui::{gui, logging};
 
 fn main() -> eframe::Result<()> {
-    // Initialize the UI logger using the configured level (defaults to "info").
-    let initial_level =
-        logging::ui_logger::parse_level(&ode_artwork_downloader::config::get_config().log_level);
-    let log_receiver = logging::ui_logger::UiLogger::init(initial_level)
-        .expect("Failed to initialize logger");
+    // Initialize the UI logger and log file using the configured levels
+    // (both default to "info").
+    let log_receiver =
+        logging::init(ode_artwork_gui::config::get_config()).expect("Failed to initialize logger");
+    // Write a crash report instead of just vanishing on a panic
+    logging::crash::install_with(gui::offer_crash_report);
 
     // Store the receiver so the App can take it during initialization
     gui::set_log_receiver(log_receiver);